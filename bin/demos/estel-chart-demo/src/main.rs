@@ -13,9 +13,13 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 
+mod render_pipeline;
+
 use eframe::egui;
-use std::path::PathBuf;
+use render_pipeline::{spec_key, RenderPipeline, RenderStatus, Renderer};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::runtime::Runtime;
 
 use estel::{
@@ -48,7 +52,9 @@ enum ActiveTab {
 }
 
 struct ChartSuggestionApp {
-    chart_html_files: std::collections::HashMap<usize, String>,
+    chart_html_files: std::collections::HashMap<String, String>,
+    generated_html_paths: std::collections::HashMap<String, String>,
+    render_pipeline: RenderPipeline,
     selected_file: Option<PathBuf>,
     file_content: String,
     profiles: Vec<DimensionProfile>,
@@ -86,23 +92,10 @@ struct ChartSuggestionApp {
 impl ChartSuggestionApp {
     fn new() -> Self {
         let runtime = Arc::new(Runtime::new().expect("Failed to create Tokio runtime"));
-        let config_paths = [
-            "config/plotly_api.yml",
-            "bin/demos/estel-chart-demo/config/plotly_api.yml",
-            "../../../crates/estel/config/plotly_api.yml",
-            "crates/estel/config/plotly_api.yml",
-        ];
-
-        let api_graph = config_paths
-            .iter()
-            .find_map(|path| ApiGraph::from_yaml_file(path).ok());
-
-        if api_graph.is_none() {
-            eprintln!("Failed to load API graph from any of the following paths:");
-            for path in &config_paths {
-                eprintln!("- {path}");
-            }
-        }
+        let api_graph = ApiGraph::from_yaml_file("config/plotly_api.yml")
+            .or_else(|_| ApiGraph::default_embedded())
+            .map_err(|e| eprintln!("Failed to load API graph: {e}"))
+            .ok();
 
         Self {
             selected_file: None,
@@ -119,10 +112,12 @@ impl ChartSuggestionApp {
             selected_chart: None,
             error_message: None,
             error_reporter: ErrorReporter::new(),
+            render_pipeline: RenderPipeline::new(Arc::clone(&runtime), chart_renderer(None)),
             runtime,
             is_processing: false,
             progress_message: String::new(),
             chart_html_files: std::collections::HashMap::new(),
+            generated_html_paths: std::collections::HashMap::new(),
             symbolic_scores: Vec::new(),
             symbolic_notes: Vec::new(),
             learned_scores: Vec::new(),
@@ -153,6 +148,9 @@ impl ChartSuggestionApp {
         self.learned_symbolic_scores.clear();
         self.learned_symbolic_notes.clear();
         self.selected_chart = None;
+        self.chart_html_files.clear();
+        self.generated_html_paths.clear();
+        self.render_pipeline.reset(chart_renderer(Some(file_path.clone())));
 
         match self.analyse_file(file_path) {
             Ok(()) => {
@@ -224,6 +222,7 @@ impl ChartSuggestionApp {
                             estel::DataType::Numeric => SymDataType::Numeric,
                             estel::DataType::Categorical => SymDataType::Categorical,
                             estel::DataType::Temporal => SymDataType::Temporal,
+                            estel::DataType::Geospatial => SymDataType::Numeric,
                         };
                         col_profiles.insert(
                             p.name.clone(),
@@ -294,16 +293,6 @@ impl ChartSuggestionApp {
                 use estel::LearnedScorer;
                 use std::collections::HashMap;
 
-                fn spec_key(spec: &RenderSpec) -> String {
-                    let mut parts: Vec<String> = spec
-                        .mappings
-                        .iter()
-                        .map(|(k, v)| format!("{k}={v}"))
-                        .collect();
-                    parts.sort();
-                    format!("{}|{}", spec.chart_name, parts.join(","))
-                }
-
                 let mut sym_score_by_key: HashMap<String, f64> = HashMap::new();
                 let mut sym_notes_by_key: HashMap<String, Vec<String>> = HashMap::new();
                 for (i, s) in self.base_chart_suggestions.iter().enumerate() {
@@ -339,98 +328,15 @@ impl ChartSuggestionApp {
         }
         Ok(())
     }
-
-    fn render_chart(&self, render_spec: &RenderSpec) -> Result<String> {
-        use std::process::Command;
-        let data_json = if let Some(ref path) = self.selected_file {
-            let mut rdr = csv::Reader::from_path(path).map_err(std::io::Error::other)?;
-            let mut data = std::collections::HashMap::new();
-            let headers = rdr.headers().map_err(std::io::Error::other)?.clone();
-
-            for header in headers.iter() {
-                data.insert(header.to_string(), Vec::<String>::new());
-            }
-
-            for (i, result) in rdr.records().enumerate() {
-                if i >= 100 {
-                    break;
-                }
-                let record = result.map_err(std::io::Error::other)?;
-                for (j, field) in record.iter().enumerate() {
-                    if let Some(header) = headers.get(j) {
-                        if let Some(column) = data.get_mut(header) {
-                            column.push(field.to_string());
-                        }
-                    }
-                }
-            }
-            serde_json::to_string(&data).unwrap_or_default()
-        } else {
-            r#"{"col1": [1, 2, 3, 4, 5], "col2": [10, 20, 30, 40, 50]}"#.to_string()
-        };
-
-        let mappings_json = serde_json::to_string(&render_spec.mappings).unwrap_or_default();
-
-        let script = format!(
-            r#"
-import sys
-import os
-import json
-
-candidate_paths = [
-    'python_helpers',
-    'bin/demos/estel-chart-demo/python_helpers',
-    '../../../crates/estel/python_helpers',
-    'crates/estel/python_helpers',
-]
-for p in candidate_paths:
-    if os.path.isdir(p) and p not in sys.path:
-        sys.path.insert(0, p)
-
-from renderer import render_chart
-
-data_json = r'''{data_json}'''
-
-data = json.loads(data_json)
-
-mappings_json = r'''{mappings_json}'''
-
-mappings = json.loads(mappings_json)
-
-try:
-    result = render_chart('{chart}', json.dumps(data), mappings)
-    print(result)
-except Exception as e:
-    import traceback
-    traceback.print_exc(file=sys.stderr);
-    sys.exit(1)
-"#,
-            chart = render_spec.chart_name
-        );
-
-        let output = Command::new("python3")
-            .arg("-c")
-            .arg(&script)
-            .current_dir(".")
-            .output()
-            .map_err(ChartSuggestionError::Io)?;
-
-        if output.status.success() {
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            Ok(stdout)
-        } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            eprintln!("Python script error: {stderr}");
-            Err(ChartSuggestionError::Chart(ChartError::InvalidRenderSpec {
-                name: render_spec.chart_name.clone(),
-                reason: stderr.to_string(),
-            }))
-        }
-    }
 }
 
 impl eframe::App for ChartSuggestionApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.render_pipeline.poll(&mut self.chart_html_files);
+        if self.render_pipeline.is_busy() {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             ui.horizontal(|ui| {
                 ui.heading("Chart Suggestion Tool");
@@ -646,14 +552,10 @@ impl ChartSuggestionApp {
 
         let suggestions = self.chart_suggestions.clone();
         let mut actions = Vec::new();
-        let mut rendered_charts: std::collections::HashMap<usize, Result<String>> =
-            std::collections::HashMap::new();
-
-        if let Some(selected_idx) = self.selected_chart {
-            if let Some(suggestion) = suggestions.get(selected_idx) {
-                rendered_charts.insert(selected_idx, self.render_chart(suggestion));
-            }
-        }
+        let render_status = self
+            .selected_chart
+            .and_then(|index| suggestions.get(index))
+            .map(|suggestion| self.render_pipeline.dispatch(&self.chart_html_files, suggestion));
 
         egui::ScrollArea::vertical().show(ui, |ui| {
             for (i, suggestion) in suggestions.iter().enumerate() {
@@ -736,25 +638,31 @@ impl ChartSuggestionApp {
 
                         if is_selected {
                             ui.separator();
-                            if let Some(render_result) = rendered_charts.get(&i) {
-                                match render_result {
-                                    Ok(rendered) => {
-                                        ui.label(" Raw Chart JSON:");
-                                        egui::ScrollArea::vertical()
-                                            .max_height(300.0)
-                                            .show(ui, |ui| {
-                                                ui.monospace(rendered);
-                                            });
-                                    }
-                                    Err(e) => {
-                                        ui.colored_label(egui::Color32::RED, format!("Render error: {e}"));
-                                    }
+                            match &render_status {
+                                Some(RenderStatus::Ready(rendered)) => {
+                                    ui.label(" Raw Chart JSON:");
+                                    egui::ScrollArea::vertical()
+                                        .max_height(300.0)
+                                        .show(ui, |ui| {
+                                            ui.monospace(rendered);
+                                        });
+                                }
+                                Some(RenderStatus::Pending) => {
+                                    ui.horizontal(|ui| {
+                                        ui.spinner();
+                                        ui.label("Rendering chart...");
+                                    });
+                                }
+                                Some(RenderStatus::Failed(e)) => {
+                                    ui.colored_label(egui::Color32::RED, format!("Render error: {e}"));
                                 }
+                                None => {}
                             }
 
-                            if self.chart_html_files.contains_key(&i) {
+                            let key = spec_key(suggestion);
+                            if self.generated_html_paths.contains_key(&key) {
                                 ui.separator();
-                                if let Some(html_path) = self.chart_html_files.get(&i) {
+                                if let Some(html_path) = self.generated_html_paths.get(&key) {
                                     ui.horizontal(|ui| {
                                         ui.label(" HTML file:");
                                         ui.monospace(html_path);
@@ -789,7 +697,7 @@ impl ChartSuggestionApp {
                 }
                 "generate_html" => {
                     if let Some(suggestion) = suggestions.get(index) {
-                        match self.generate_chart_html(suggestion, index) {
+                        match self.generate_chart_html(suggestion) {
                             Ok(_) => {
                                 self.selected_chart = Some(index);
                             }
@@ -895,11 +803,7 @@ impl ChartSuggestionApp {
         });
     }
 
-    fn generate_chart_html(
-        &mut self,
-        render_spec: &RenderSpec,
-        chart_index: usize,
-    ) -> Result<String> {
+    fn generate_chart_html(&mut self, render_spec: &RenderSpec) -> Result<String> {
         let data_json = self.get_chart_data_json()?;
         let mappings_json = serde_json::to_string(&render_spec.mappings)?;
 
@@ -947,7 +851,7 @@ except Exception as e:
 
         if output.status.success() {
             let html_path = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            self.chart_html_files.insert(chart_index, html_path.clone());
+            self.generated_html_paths.insert(spec_key(render_spec), html_path.clone());
             Ok(html_path)
         } else {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -1016,32 +920,104 @@ except Exception as e:
     }
 
     fn get_chart_data_json(&self) -> Result<String> {
-        if let Some(ref path) = self.selected_file {
-            let mut rdr = csv::Reader::from_path(path).map_err(std::io::Error::other)?;
-            let mut data = std::collections::HashMap::new();
-            let headers = rdr.headers().map_err(std::io::Error::other)?.clone();
+        chart_data_json(self.selected_file.as_deref())
+    }
+}
 
-            for header in headers.iter() {
-                data.insert(header.to_string(), Vec::<String>::new());
-            }
+fn chart_renderer(selected_file: Option<PathBuf>) -> Renderer {
+    Arc::new(move |render_spec: &RenderSpec| {
+        render_chart(selected_file.as_deref(), render_spec)
+    })
+}
 
-            for (i, result) in rdr.records().enumerate() {
-                if i >= 100 {
-                    break;
-                }
-                let record = result.map_err(std::io::Error::other)?;
-                for (j, field) in record.iter().enumerate() {
-                    if let Some(header) = headers.get(j) {
-                        if let Some(column) = data.get_mut(header) {
-                            column.push(field.to_string());
-                        }
+fn chart_data_json(selected_file: Option<&Path>) -> Result<String> {
+    if let Some(path) = selected_file {
+        let mut rdr = csv::Reader::from_path(path).map_err(std::io::Error::other)?;
+        let mut data = std::collections::HashMap::new();
+        let headers = rdr.headers().map_err(std::io::Error::other)?.clone();
+
+        for header in headers.iter() {
+            data.insert(header.to_string(), Vec::<String>::new());
+        }
+
+        for (i, result) in rdr.records().enumerate() {
+            if i >= 100 {
+                break;
+            }
+            let record = result.map_err(std::io::Error::other)?;
+            for (j, field) in record.iter().enumerate() {
+                if let Some(header) = headers.get(j) {
+                    if let Some(column) = data.get_mut(header) {
+                        column.push(field.to_string());
                     }
                 }
             }
-            let json = serde_json::to_string(&data).unwrap_or_default();
-            Ok(json)
-        } else {
-            Ok(r#"{"col1": [1, 2, 3, 4, 5], "col2": [10, 20, 30, 40, 50]}"#.to_string())
         }
+        let json = serde_json::to_string(&data).unwrap_or_default();
+        Ok(json)
+    } else {
+        Ok(r#"{"col1": [1, 2, 3, 4, 5], "col2": [10, 20, 30, 40, 50]}"#.to_string())
+    }
+}
+
+fn render_chart(selected_file: Option<&Path>, render_spec: &RenderSpec) -> Result<String> {
+    let data_json = chart_data_json(selected_file)?;
+
+    let mappings_json = serde_json::to_string(&render_spec.mappings).unwrap_or_default();
+
+    let script = format!(
+        r#"
+import sys
+import os
+import json
+
+candidate_paths = [
+    'python_helpers',
+    'bin/demos/estel-chart-demo/python_helpers',
+    '../../../crates/estel/python_helpers',
+    'crates/estel/python_helpers',
+]
+for p in candidate_paths:
+    if os.path.isdir(p) and p not in sys.path:
+        sys.path.insert(0, p)
+
+from renderer import render_chart
+
+data_json = r'''{data_json}'''
+
+data = json.loads(data_json)
+
+mappings_json = r'''{mappings_json}'''
+
+mappings = json.loads(mappings_json)
+
+try:
+    result = render_chart('{chart}', json.dumps(data), mappings)
+    print(result)
+except Exception as e:
+    import traceback
+    traceback.print_exc(file=sys.stderr);
+    sys.exit(1)
+"#,
+        chart = render_spec.chart_name
+    );
+
+    let output = Command::new("python3")
+        .arg("-c")
+        .arg(&script)
+        .current_dir(".")
+        .output()
+        .map_err(ChartSuggestionError::Io)?;
+
+    if output.status.success() {
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        Ok(stdout)
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        eprintln!("Python script error: {stderr}");
+        Err(ChartSuggestionError::Chart(ChartError::InvalidRenderSpec {
+            name: render_spec.chart_name.clone(),
+            reason: stderr.to_string(),
+        }))
     }
 }
@@ -0,0 +1,191 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use estel::{RenderSpec, Result};
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+pub type Renderer = Arc<dyn Fn(&RenderSpec) -> Result<String> + Send + Sync>;
+
+struct RenderOutcome {
+    generation: u64,
+    key: String,
+    rendered: std::result::Result<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderStatus {
+    Ready(String),
+    Pending,
+    Failed(String),
+}
+
+pub fn spec_key(spec: &RenderSpec) -> String {
+    format!("{}|{}", spec.chart_name, spec.mapping_key())
+}
+
+pub struct RenderPipeline {
+    runtime: Arc<Runtime>,
+    renderer: Renderer,
+    generation: u64,
+    sender: Sender<RenderOutcome>,
+    receiver: Receiver<RenderOutcome>,
+    pending: HashSet<String>,
+    failures: HashMap<String, String>,
+}
+
+impl RenderPipeline {
+    pub fn new(runtime: Arc<Runtime>, renderer: Renderer) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            runtime,
+            renderer,
+            generation: 0,
+            sender,
+            receiver,
+            pending: HashSet::new(),
+            failures: HashMap::new(),
+        }
+    }
+
+    pub fn reset(&mut self, renderer: Renderer) {
+        self.renderer = renderer;
+        self.generation += 1;
+        self.pending.clear();
+        self.failures.clear();
+    }
+
+    pub fn dispatch(&mut self, cache: &HashMap<String, String>, spec: &RenderSpec) -> RenderStatus {
+        let key = spec_key(spec);
+        if let Some(rendered) = cache.get(&key) {
+            return RenderStatus::Ready(rendered.clone());
+        }
+        if let Some(error) = self.failures.get(&key) {
+            return RenderStatus::Failed(error.clone());
+        }
+        if self.pending.insert(key.clone()) {
+            let renderer = Arc::clone(&self.renderer);
+            let sender = self.sender.clone();
+            let generation = self.generation;
+            let spec = spec.clone();
+            self.runtime.spawn_blocking(move || {
+                let rendered = renderer(&spec).map_err(|e| e.to_string());
+                let _ = sender.send(RenderOutcome {
+                    generation,
+                    key,
+                    rendered,
+                });
+            });
+        }
+        RenderStatus::Pending
+    }
+
+    pub fn poll(&mut self, cache: &mut HashMap<String, String>) -> usize {
+        let mut completed = 0;
+        while let Ok(outcome) = self.receiver.try_recv() {
+            if outcome.generation != self.generation {
+                continue;
+            }
+            self.pending.remove(&outcome.key);
+            match outcome.rendered {
+                Ok(rendered) => {
+                    cache.insert(outcome.key, rendered);
+                }
+                Err(error) => {
+                    self.failures.insert(outcome.key, error);
+                }
+            }
+            completed += 1;
+        }
+        completed
+    }
+
+    pub fn is_busy(&self) -> bool {
+        !self.pending.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
+
+    fn bar_spec() -> RenderSpec {
+        RenderSpec {
+            chart_name: "bar".to_string(),
+            library: "plotly".to_string(),
+            description: String::new(),
+            mappings: BTreeMap::from([
+                ("x".to_string(), "region".to_string()),
+                ("y".to_string(), "revenue".to_string()),
+            ]),
+            quality_score: 0.8,
+            dimensions_used: 2,
+            complete: true,
+            detailed_score: None,
+        }
+    }
+
+    fn counting_pipeline(calls: Arc<AtomicUsize>) -> RenderPipeline {
+        let runtime = Arc::new(Runtime::new().unwrap());
+        RenderPipeline::new(
+            runtime,
+            Arc::new(move |spec: &RenderSpec| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok(format!("<div>{}</div>", spec.chart_name))
+            }),
+        )
+    }
+
+    fn wait_for(pipeline: &mut RenderPipeline, cache: &mut HashMap<String, String>) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while pipeline.is_busy() && Instant::now() < deadline {
+            pipeline.poll(cache);
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert!(!pipeline.is_busy(), "render did not finish");
+    }
+
+    #[test]
+    fn identical_spec_hits_the_cache_on_the_second_call() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut pipeline = counting_pipeline(Arc::clone(&calls));
+        let mut cache = HashMap::new();
+
+        assert_eq!(pipeline.dispatch(&cache, &bar_spec()), RenderStatus::Pending);
+        wait_for(&mut pipeline, &mut cache);
+
+        assert_eq!(
+            pipeline.dispatch(&cache, &bar_spec()),
+            RenderStatus::Ready("<div>bar</div>".to_string())
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(cache.contains_key(&spec_key(&bar_spec())));
+    }
+
+    #[test]
+    fn repeated_dispatch_while_pending_renders_once() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut pipeline = counting_pipeline(Arc::clone(&calls));
+        let mut cache = HashMap::new();
+
+        for _ in 0..3 {
+            pipeline.dispatch(&cache, &bar_spec());
+        }
+        wait_for(&mut pipeline, &mut cache);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
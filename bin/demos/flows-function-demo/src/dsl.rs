@@ -58,16 +58,53 @@ enum Cond {
         op: RelOp,
         value: u64,
     },
+    Compare {
+        lhs: Expr,
+        op: RelOp,
+        rhs: Expr,
+    },
     All(Vec<Cond>), 
     Any(Vec<Cond>), 
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum RelOp {
     Lt,
     Le,
     Gt,
     Ge,
+    Eq,
+    Ne,
+}
+
+const MAX_OPERANDS: usize = 8;
+const OPERATOR_CHARS: &str = "=!<>%+-*/&|^~";
+
+#[derive(Debug, Clone)]
+enum Expr {
+    N,
+    Const(u64),
+    Bin(Box<Expr>, BinOp, Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    And,
+    Or,
+    Xor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AtomToken {
+    N,
+    Int(u64),
+    Arith(BinOp),
+    Cmp(RelOp),
 }
 
 #[derive(Debug, Clone)]
@@ -244,8 +281,9 @@ fn parse_cond(s: &str) -> Result<Cond, String> {
         }
         
         if parts.len() == 3 && parts[0] == "n" && parts[1] == "==" {
-            let value: u64 = parts[2].parse().map_err(|_| "invalid const".to_string())?;
-            return Ok(Cond::EqConst { value });
+            if let Ok(value) = parts[2].parse() {
+                return Ok(Cond::EqConst { value });
+            }
         }
         
         if parts.len() == 3 && parts[0] == "n" {
@@ -258,41 +296,32 @@ fn parse_cond(s: &str) -> Result<Cond, String> {
                 "<=" => Some(RelOp::Le),
                 _ => None,
             };
-            if let Some(r) = rel {
-                let value: u64 = val_tok
-                    .parse()
-                    .map_err(|_| "invalid rel const".to_string())?;
+            if let (Some(r), Ok(value)) = (rel, val_tok.parse()) {
                 return Ok(Cond::Rel { op: r, value });
             }
         }
         
         if parts.len() == 5 && parts[0] == "n" && parts[1] == "%" && parts[3] == "==" {
-            let modulus: u64 = parts[2]
-                .parse()
-                .map_err(|_| "invalid modulus".to_string())?;
-            let equals: u64 = parts[4].parse().map_err(|_| "invalid equals".to_string())?;
-            if modulus == 0 {
-                return Err("modulus zero".into());
+            if let (Ok(modulus), Ok(equals)) = (parts[2].parse::<u64>(), parts[4].parse()) {
+                if modulus == 0 {
+                    return Err("modulus zero".into());
+                }
+                return Ok(Cond::ModEq { modulus, equals });
             }
-            return Ok(Cond::ModEq { modulus, equals });
         }
         
         if parts.len() == 5 && parts[0] == "n" && parts[1] == "%" && parts[3] == "!=" {
-            let modulus: u64 = parts[2]
-                .parse()
-                .map_err(|_| "invalid modulus".to_string())?;
-            let not_equals: u64 = parts[4]
-                .parse()
-                .map_err(|_| "invalid not-equals".to_string())?;
-            if modulus == 0 {
-                return Err("modulus zero".into());
+            if let (Ok(modulus), Ok(not_equals)) = (parts[2].parse::<u64>(), parts[4].parse()) {
+                if modulus == 0 {
+                    return Err("modulus zero".into());
+                }
+                return Ok(Cond::NotModEq {
+                    modulus,
+                    not_equals,
+                });
             }
-            return Ok(Cond::NotModEq {
-                modulus,
-                not_equals,
-            });
         }
-        Err("unsupported condition".into())
+        parse_comparison(seg)
     }
     
     let mut tokens: Vec<String> = Vec::new();
@@ -366,6 +395,170 @@ fn parse_cond(s: &str) -> Result<Cond, String> {
     Ok(optimize(cond))
 }
 
+fn parse_comparison(seg: &str) -> Result<Cond, String> {
+    let tokens = lex_atom(seg)?;
+    let comparisons: Vec<(usize, RelOp)> = tokens
+        .iter()
+        .enumerate()
+        .filter_map(|(i, t)| match t {
+            AtomToken::Cmp(op) => Some((i, *op)),
+            _ => None,
+        })
+        .collect();
+    let (pos, op) = match comparisons.as_slice() {
+        [] => return Err(format!("missing comparison operator in '{seg}'")),
+        [single] => *single,
+        _ => return Err(format!("chained comparison in '{seg}'; combine with 'and'")),
+    };
+    let lhs = parse_operand_expr(&tokens[..pos], "left")?;
+    let rhs = parse_operand_expr(&tokens[pos + 1..], "right")?;
+    Ok(Cond::Compare { lhs, op, rhs })
+}
+
+fn lex_atom(seg: &str) -> Result<Vec<AtomToken>, String> {
+    let chars: Vec<char> = seg.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let value = text
+                .parse()
+                .map_err(|_| format!("integer '{text}' out of range"))?;
+            tokens.push(AtomToken::Int(value));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if word != "n" {
+                return Err(format!("unknown operand '{word}'; only 'n' and integers are allowed"));
+            }
+            tokens.push(AtomToken::N);
+        } else if OPERATOR_CHARS.contains(c) {
+            while i < chars.len() && OPERATOR_CHARS.contains(chars[i]) {
+                i += 1;
+            }
+            let op: String = chars[start..i].iter().collect();
+            tokens.push(match op.as_str() {
+                "==" => AtomToken::Cmp(RelOp::Eq),
+                "!=" => AtomToken::Cmp(RelOp::Ne),
+                "<" => AtomToken::Cmp(RelOp::Lt),
+                "<=" => AtomToken::Cmp(RelOp::Le),
+                ">" => AtomToken::Cmp(RelOp::Gt),
+                ">=" => AtomToken::Cmp(RelOp::Ge),
+                "+" => AtomToken::Arith(BinOp::Add),
+                "-" => AtomToken::Arith(BinOp::Sub),
+                "*" => AtomToken::Arith(BinOp::Mul),
+                "/" => AtomToken::Arith(BinOp::Div),
+                "%" => AtomToken::Arith(BinOp::Rem),
+                "&" => AtomToken::Arith(BinOp::And),
+                "|" => AtomToken::Arith(BinOp::Or),
+                "^" => AtomToken::Arith(BinOp::Xor),
+                _ => return Err(format!("unsupported operator '{op}'")),
+            });
+        } else {
+            return Err(format!("unexpected character '{c}'"));
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_operand_expr(tokens: &[AtomToken], side: &str) -> Result<Expr, String> {
+    if tokens.is_empty() {
+        return Err(format!("missing {side} operand"));
+    }
+    let mut operands: Vec<Expr> = Vec::new();
+    let mut ops: Vec<BinOp> = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        match (token, i % 2 == 0) {
+            (AtomToken::N, true) => operands.push(Expr::N),
+            (AtomToken::Int(v), true) => operands.push(Expr::Const(*v)),
+            (AtomToken::Arith(op), false) => ops.push(*op),
+            (AtomToken::N | AtomToken::Int(_), false) => {
+                return Err(format!("missing operator between {side} operands"))
+            }
+            (_, true) => return Err(format!("expected {side} operand, found an operator")),
+            (AtomToken::Cmp(_), false) => return Err("chained comparison".into()),
+        }
+    }
+    if ops.len() == operands.len() {
+        return Err(format!("dangling operator at end of {side} operand"));
+    }
+    if operands.len() > MAX_OPERANDS {
+        return Err(format!("{side} operand exceeds {MAX_OPERANDS} terms"));
+    }
+    for (op, divisor) in ops.iter().zip(operands.iter().skip(1)) {
+        if matches!(op, BinOp::Div | BinOp::Rem) && matches!(divisor, Expr::Const(0)) {
+            return Err(format!("division by literal zero in {side} operand"));
+        }
+    }
+    let mut operands = operands.into_iter();
+    let mut output = vec![operands.next().unwrap()];
+    let mut pending: Vec<BinOp> = Vec::new();
+    for (op, rhs) in ops.into_iter().zip(operands) {
+        while pending
+            .last()
+            .is_some_and(|top| top.precedence() >= op.precedence())
+        {
+            reduce(&mut output, pending.pop().unwrap());
+        }
+        pending.push(op);
+        output.push(rhs);
+    }
+    while let Some(op) = pending.pop() {
+        reduce(&mut output, op);
+    }
+    Ok(output.pop().unwrap())
+}
+
+fn reduce(output: &mut Vec<Expr>, op: BinOp) {
+    let rhs = output.pop().unwrap();
+    let lhs = output.pop().unwrap();
+    output.push(Expr::Bin(Box::new(lhs), op, Box::new(rhs)));
+}
+
+impl BinOp {
+    fn precedence(self) -> u8 {
+        match self {
+            BinOp::Mul | BinOp::Div | BinOp::Rem => 4,
+            BinOp::Add | BinOp::Sub => 3,
+            BinOp::And => 2,
+            BinOp::Xor => 1,
+            BinOp::Or => 0,
+        }
+    }
+}
+
+impl Expr {
+    fn eval(&self, n: u64) -> Option<u64> {
+        match self {
+            Expr::N => Some(n),
+            Expr::Const(v) => Some(*v),
+            Expr::Bin(lhs, op, rhs) => {
+                let (a, b) = (lhs.eval(n)?, rhs.eval(n)?);
+                match op {
+                    BinOp::Add => a.checked_add(b),
+                    BinOp::Sub => a.checked_sub(b),
+                    BinOp::Mul => a.checked_mul(b),
+                    BinOp::Div => a.checked_div(b),
+                    BinOp::Rem => a.checked_rem(b),
+                    BinOp::And => Some(a & b),
+                    BinOp::Or => Some(a | b),
+                    BinOp::Xor => Some(a ^ b),
+                }
+            }
+        }
+    }
+}
+
 fn parse_ops(line: &str) -> Result<Vec<Op>, String> {
     
     
@@ -564,6 +757,10 @@ impl Cond {
                 op,
                 value,
             } => (n % *modulus == *equals) && rel_cmp(n, *op, *value),
+            Cond::Compare { lhs, op, rhs } => match (lhs.eval(n), rhs.eval(n)) {
+                (Some(a), Some(b)) => rel_cmp(a, *op, b),
+                _ => false,
+            },
             Cond::All(list) => list.iter().all(|c| c.matches(n)),
             Cond::Any(list) => list.iter().any(|c| c.matches(n)),
         }
@@ -576,5 +773,105 @@ fn rel_cmp(n: u64, op: RelOp, v: u64) -> bool {
         RelOp::Le => n <= v,
         RelOp::Gt => n > v,
         RelOp::Ge => n >= v,
+        RelOp::Eq => n == v,
+        RelOp::Ne => n != v,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stele::flows::dynamic_executor::strategy::DenseMemo;
+
+    fn cond(src: &str) -> Cond {
+        parse_cond(src).unwrap()
+    }
+
+    #[test]
+    fn modular_terms_compare_against_each_other() {
+        let c = cond("n % 3 == n % 5");
+        assert!(matches!(c, Cond::Compare { .. }));
+        assert!(c.matches(15));
+        assert!(c.matches(16));
+        assert!(!c.matches(6));
+        assert!(cond("n%3!=n%5").matches(6));
+    }
+
+    #[test]
+    fn relational_operators_accept_expressions() {
+        let ge = cond("n >= n % 7 + 10");
+        assert!(!ge.matches(10));
+        assert!(ge.matches(20));
+        let le = cond("n * 2 <= 3 * 5 + 1");
+        assert!(le.matches(8));
+        assert!(!le.matches(9));
+        assert!(cond("n % 4 < n % 6 and n > 3").matches(8));
+    }
+
+    #[test]
+    fn bitwise_operators_bind_looser_than_arithmetic() {
+        let even = cond("n & 1 == 0");
+        assert!(even.matches(10));
+        assert!(!even.matches(11));
+        let masked = cond("n & 3 + 1 == 0");
+        assert!(masked.matches(3));
+        assert!(!masked.matches(4));
+        assert!(cond("n ^ 6 | 1 == 1").matches(6));
+    }
+
+    #[test]
+    fn runtime_division_by_zero_fails_the_condition() {
+        let c = cond("100 % n == 0");
+        assert!(c.matches(25));
+        assert!(!c.matches(0));
+    }
+
+    #[test]
+    fn runtime_subtraction_underflow_fails_the_condition() {
+        let c = cond("n - 5 == 0");
+        assert!(c.matches(5));
+        assert!(!c.matches(3));
+    }
+
+    #[test]
+    fn literal_forms_keep_their_fast_paths() {
+        assert!(matches!(cond("n % 2 == 0"), Cond::ModEq { .. }));
+        assert!(matches!(cond("n >= 4"), Cond::Rel { .. }));
+        assert!(matches!(cond("n == 7"), Cond::EqConst { .. }));
+        assert!(matches!(cond("n % 2 == 1 and n > 5"), Cond::ModAndRel { .. }));
+    }
+
+    #[test]
+    fn unsupported_operator_is_reported_with_its_line() {
+        let src = "rule n % 2 == 0 -> n = n / 2\nrule n ** 2 == 4 -> terminate";
+        let err = DslEvaluator::parse(src, 100).unwrap_err().to_string();
+        assert!(err.starts_with("DSL_PARSE_ERRORS: "), "{err}");
+        assert!(err.contains("line 2: unsupported operator '**'"), "{err}");
+    }
+
+    #[test]
+    fn out_of_grammar_operands_are_rejected() {
+        for (src, expected) in [
+            ("n % 3 == m", "unknown operand 'm'"),
+            ("n < 5 < 7", "chained comparison"),
+            ("n % 3 + == 1", "dangling operator at end of left operand"),
+            ("n == * 2", "expected right operand, found an operator"),
+            ("n % 3 +", "missing comparison operator"),
+            ("n % 0 == n", "division by literal zero"),
+            ("n + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 1 == 9", "exceeds 8 terms"),
+        ] {
+            let err = parse_cond(src).unwrap_err();
+            assert!(err.contains(expected), "{src}: {err}");
+        }
+    }
+
+    #[test]
+    fn parsed_program_evaluates_with_expression_rules() {
+        let src = "rule n % 2 == n % 4 -> n = n / 2\nrule n >= 2 -> terminate";
+        let evaluator = DslEvaluator::parse(src, 64).unwrap();
+        let memo = DenseMemo::new(64);
+        let outcome = evaluator.eval(8, &memo);
+        assert_eq!(outcome.path.first().map(|p| p.0), Some(8));
+        assert!(outcome.score >= 2);
     }
 }
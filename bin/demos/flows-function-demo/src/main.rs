@@ -45,9 +45,7 @@ pub mod dsl;
 mod llm_helpers;
 mod strategy_eval;
 
-use stele::database::{
-    connection::DatabaseConnection, dynamic_storage::DynamicStorage, types::DatabaseCommand,
-};
+use stele::database::{dynamic_storage::DynamicStorage, ConnectionPool, PoolConfig, PoolGuard};
 use stele::flows::core::BlockDefinition;
 use stele::flows::dynamic_executor::DynamicFunction as SteleDynamicFunction;
 use stele::flows::flowgorithm::Flowgorithm;
@@ -56,11 +54,11 @@ use stele::nlu::llm_processor::{CustomLLMAdapter, LLMAdapter};
 use stele::nlu::orchestrator::NLUOrchestrator;
 use stele::nlu::query_processor::QueryProcessor;
 use stele::{BlockRegistry, BlockType, FlowDefinition, SecurityConfig, UnifiedFlowEngine};
-use tokio::sync::{mpsc, oneshot, RwLock};
+use tokio::sync::RwLock;
 #[cfg(feature = "ui")]
 mod ui;
 
-pub async fn build_query_processor() -> anyhow::Result<QueryProcessor> {
+pub async fn build_query_processor() -> anyhow::Result<(QueryProcessor, PoolGuard)> {
     let manifest_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
 
     let config_dir = manifest_dir
@@ -68,25 +66,13 @@ pub async fn build_query_processor() -> anyhow::Result<QueryProcessor> {
         .canonicalize()
         .unwrap_or_else(|_| manifest_dir.join("../../../crates/stele/src/nlu/config"));
     let config_dir_str = config_dir.to_string_lossy().to_string();
-    let (command_tx, command_rx) = mpsc::channel(32);
-    let (client_tx, mut client_rx) = mpsc::channel(1);
-    let mut db_conn = DatabaseConnection::new(command_rx);
-    tokio::spawn(async move {
-        let _ = db_conn.run().await;
-    });
-    let (connect_response_tx, connect_response_rx) = oneshot::channel();
-    command_tx
-        .send(DatabaseCommand::Connect {
-            client_sender: client_tx,
-            response_sender: connect_response_tx,
-        })
-        .await?;
-    connect_response_rx.await??;
-    let db_client = client_rx
-        .recv()
-        .await
-        .ok_or_else(|| anyhow::anyhow!("db client missing"))?;
-    let storage = Arc::new(DynamicStorage::new(db_client.clone()));
+    let pool = ConnectionPool::connect(PoolConfig {
+        size: 1,
+        ..PoolConfig::default()
+    })
+    .await?;
+    let db_guard = pool.acquire().await?;
+    let storage = Arc::new(DynamicStorage::new(db_guard.client.clone()));
     let orchestrator = Arc::new(RwLock::new(
         NLUOrchestrator::new(&config_dir_str)
             .await
@@ -99,7 +85,7 @@ pub async fn build_query_processor() -> anyhow::Result<QueryProcessor> {
     )
     .await
     .map_err(|e| anyhow::anyhow!("Failed to init QueryProcessor: {e}"))?;
-    Ok(query_processor)
+    Ok((query_processor, db_guard))
 }
 
 #[tokio::main]
@@ -606,7 +592,8 @@ async fn main() -> Result<()> {
     }
 
     let registry = Arc::new(BlockRegistry::with_standard_blocks()?);
-    let query_processor = build_query_processor().await?; // required by engine
+    // The guard holds the pooled connection for as long as the engine uses its client.
+    let (query_processor, _db_guard) = build_query_processor().await?; // required by engine
     let llm_adapter = plan_adapter.clone();
     let navigator = Flowgorithm::new();
     let mut engine = UnifiedFlowEngine::new(
@@ -312,6 +312,7 @@ fn create_data_collection_workflow() -> OrchestrationFlowDefinition {
                 next_block: "consolidate_data".to_string(),
             },
             metadata: None,
+            compensation_block: None,
         },
         OrchestrationBlockDefinition {
             id: "web_search".to_string(),
@@ -334,6 +335,7 @@ fn create_data_collection_workflow() -> OrchestrationFlowDefinition {
                 "output_key".to_string(),
                 json!("web_results"),
             )])),
+            compensation_block: None,
         },
         OrchestrationBlockDefinition {
             id: "api_fetch".to_string(),
@@ -342,6 +344,7 @@ fn create_data_collection_workflow() -> OrchestrationFlowDefinition {
                 catch_block_id: "handle_api_failure".to_string(),
             },
             metadata: None,
+            compensation_block: None,
         },
         OrchestrationBlockDefinition {
             id: "fetch_from_api".to_string(),
@@ -370,6 +373,7 @@ fn create_data_collection_workflow() -> OrchestrationFlowDefinition {
                 "output_key".to_string(),
                 json!("api_results"),
             )])),
+            compensation_block: None,
         },
         OrchestrationBlockDefinition {
             id: "handle_api_failure".to_string(),
@@ -380,6 +384,7 @@ fn create_data_collection_workflow() -> OrchestrationFlowDefinition {
                 next_block: "".to_string(),
             },
             metadata: None,
+            compensation_block: None,
         },
         OrchestrationBlockDefinition {
             id: "consolidate_data".to_string(),
@@ -402,11 +407,13 @@ fn create_data_collection_workflow() -> OrchestrationFlowDefinition {
                 next_block: "terminate_collection".to_string(),
             },
             metadata: None,
+            compensation_block: None,
         },
         OrchestrationBlockDefinition {
             id: "terminate_collection".to_string(),
             block_type: OrchestrationBlockType::Terminate,
             metadata: None,
+            compensation_block: None,
         },
     ];
 
@@ -616,6 +623,7 @@ fn create_dynamic_research_flow() -> OrchestrationFlowDefinition {
                 "description".to_string(),
                 json!("Collect research topic from user"),
             )])),
+            compensation_block: None,
         },
 
         OrchestrationBlockDefinition {
@@ -629,6 +637,7 @@ fn create_dynamic_research_flow() -> OrchestrationFlowDefinition {
                 "description".to_string(),
                 json!("Set agents_available variable to true"),
             )])),
+            compensation_block: None,
         },
 
         OrchestrationBlockDefinition {
@@ -639,6 +648,7 @@ fn create_dynamic_research_flow() -> OrchestrationFlowDefinition {
                 false_block: "handle_no_agent".to_string(),
             },
             metadata: None,
+            compensation_block: None,
         },
 
 
@@ -650,6 +660,7 @@ fn create_dynamic_research_flow() -> OrchestrationFlowDefinition {
                 next_block: "parallel_analysis".to_string(),
             },
             metadata: None,
+            compensation_block: None,
         },
 
 
@@ -665,6 +676,7 @@ fn create_dynamic_research_flow() -> OrchestrationFlowDefinition {
                 next_block: "synthesis_llm".to_string(),
             },
             metadata: None,
+            compensation_block: None,
         },
 
 
@@ -699,6 +711,7 @@ fn create_dynamic_research_flow() -> OrchestrationFlowDefinition {
                 "output_key".to_string(),
                 json!("creative_insights"),
             )])),
+            compensation_block: None,
         },
 
 
@@ -726,6 +739,7 @@ fn create_dynamic_research_flow() -> OrchestrationFlowDefinition {
                 "output_key".to_string(),
                 json!("technical_assessment"),
             )])),
+            compensation_block: None,
         },
 
 
@@ -740,6 +754,7 @@ fn create_dynamic_research_flow() -> OrchestrationFlowDefinition {
                 "description".to_string(),
                 json!("Generate synthesised strategy (mock)"),
             )])),
+            compensation_block: None,
         },
 
 
@@ -753,6 +768,7 @@ fn create_dynamic_research_flow() -> OrchestrationFlowDefinition {
                 next_block: "decision_branch".to_string(),
             },
             metadata: None,
+            compensation_block: None,
         },
 
 
@@ -764,6 +780,7 @@ fn create_dynamic_research_flow() -> OrchestrationFlowDefinition {
                 false_block: "handle_refinement".to_string(),
             },
             metadata: None,
+            compensation_block: None,
         },
 
 
@@ -778,6 +795,7 @@ fn create_dynamic_research_flow() -> OrchestrationFlowDefinition {
                 "description".to_string(),
                 json!("Extract key takeaways (mock)"),
             )])),
+            compensation_block: None,
         },
 
 
@@ -789,6 +807,7 @@ fn create_dynamic_research_flow() -> OrchestrationFlowDefinition {
                 false_block: "initialise_research".to_string(),
             },
             metadata: None,
+            compensation_block: None,
         },
 
 
@@ -803,6 +822,7 @@ fn create_dynamic_research_flow() -> OrchestrationFlowDefinition {
                 "description".to_string(),
                 json!("Generate final report (mock)"),
             )])),
+            compensation_block: None,
         },
 
 
@@ -810,6 +830,7 @@ fn create_dynamic_research_flow() -> OrchestrationFlowDefinition {
             id: "terminate_success".to_string(),
             block_type: OrchestrationBlockType::Terminate,
             metadata: Some(HashMap::from([("status".to_string(), json!("Success"))])),
+            compensation_block: None,
         },
         OrchestrationBlockDefinition {
             id: "terminate_manual".to_string(),
@@ -818,6 +839,7 @@ fn create_dynamic_research_flow() -> OrchestrationFlowDefinition {
                 "status".to_string(),
                 json!("Terminated by user"),
             )])),
+            compensation_block: None,
         },
         OrchestrationBlockDefinition {
             id: "handle_no_agent".to_string(),
@@ -826,6 +848,7 @@ fn create_dynamic_research_flow() -> OrchestrationFlowDefinition {
                 "status".to_string(),
                 json!("Failed: No suitable agent found"),
             )])),
+            compensation_block: None,
         },
     ];
 
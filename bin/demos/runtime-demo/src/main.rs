@@ -240,7 +240,8 @@ impl FlowHotSwapper {
                         for existing_block in &mut flow.blocks {
                             if existing_block.id == insert_after {
                                 match &mut existing_block.block_type {
-                                    BlockType::Compute { next_block, .. } => {
+                                    BlockType::Compute { next_block, .. }
+                                    | BlockType::Generate { next_block, .. } => {
                                         let original_next = next_block.clone();
                                         *next_block = block.id.clone();
 
@@ -395,7 +396,11 @@ impl FlowHotSwapper {
                                         flow.blocks.push(modified_block);
                                         injection_successful = true;
                                     }
-                                    BlockType::SubFlow { next_block, .. } => {
+                                    BlockType::SubFlow { next_block, .. }
+                                    | BlockType::Parallel {
+                                        join_block: next_block,
+                                        ..
+                                    } => {
                                         let original_next = next_block.clone();
                                         *next_block = block.id.clone();
 
@@ -2389,9 +2394,9 @@ impl RuntimeDemoOrchestrator {
                 "Large number arithmetic",
             ),
             (
-                "negative_array_index",
-                "state.data[-1]",
-                "Negative array index",
+                "negative_array_bounds",
+                "state.data[-6]",
+                "Negative array index before the start",
             ),
         ];
 
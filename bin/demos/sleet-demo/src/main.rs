@@ -419,7 +419,7 @@ impl WorkflowOrchestrator {
                         );
                     }
 
-                    runtime.resume_with_input(&interaction_id, response);
+                    runtime.resume_with_input(&interaction_id, response)?;
                 }
                 ExecutionStatus::Completed(result) => {
                     final_result = result;
@@ -852,7 +852,7 @@ async fn execute_simple_workflow(
                     );
                 }
 
-                runtime.resume_with_input(&interaction_id, response);
+                runtime.resume_with_input(&interaction_id, response)?;
             }
             ExecutionStatus::Completed(result) => {
                 final_result = result;
@@ -23,17 +23,24 @@ if System.get_env("ENABLE_NIFS") == "1" do
 
     # --- Original GTR NIFs ---
     def calculate_potential_value(_node_metrics, _sla), do: :erlang.nif_error(:nif_not_loaded)
-    def calculate_forwarding_decision(_candidate_hops, _multipath_threshold), do: :erlang.nif_error(:nif_not_loaded)
-    def analyse_dag(_packet_trails, _sla, _total_packets_sent), do: :erlang.nif_error(:nif_not_loaded)
+    def calculate_forwarding_decision(_candidate_hops, _multipath_threshold, _seed), do: :erlang.nif_error(:nif_not_loaded)
+    def calculate_forwarding_decision_detailed(_candidate_hops, _multipath_threshold, _weights, _seed), do: :erlang.nif_error(:nif_not_loaded)
+    def analyse_dag(_packet_trails, _sla, _total_packets_sent, _seed), do: :erlang.nif_error(:nif_not_loaded)
+    def analyse_dag_multi(_packet_trails, _slas, _total_packets_sent), do: :erlang.nif_error(:nif_not_loaded)
+    def analyse_dag_detailed(_packet_trails, _sla, _total_packets_sent), do: :erlang.nif_error(:nif_not_loaded)
 
     # --- Dynamic PoPS NIFs ---
     def adjust_parameters_for_epoch_nif(_current_params, _state), do: :erlang.nif_error(:nif_not_loaded)
+    def simulate_epochs_nif(_initial_params, _states), do: :erlang.nif_error(:nif_not_loaded)
     def calculate_slash_percentage_nif(_required, _actual, _params), do: :erlang.nif_error(:nif_not_loaded)
     def update_trust_score_on_success_nif(_score, _weight), do: :erlang.nif_error(:nif_not_loaded)
     def update_trust_score_on_failure_nif(_score, _slash, _params), do: :erlang.nif_error(:nif_not_loaded)
+    def update_trust_scores_on_success_batch_nif(_updates), do: :erlang.nif_error(:nif_not_loaded)
+    def update_trust_scores_on_failure_batch_nif(_updates, _params), do: :erlang.nif_error(:nif_not_loaded)
     def decay_trust_score_continuously_nif(_score, _seconds_elapsed, _params), do: :erlang.nif_error(:nif_not_loaded)
     def calculate_supplier_offering_nif(_trust, _params), do: :erlang.nif_error(:nif_not_loaded)
     def calculate_consumer_utility_nif(_offering, _trust, _consumer), do: :erlang.nif_error(:nif_not_loaded)
+    def consumer_utility_gradient_nif(_offering, _trust, _consumer), do: :erlang.nif_error(:nif_not_loaded)
     def create_trust_score_credential_nif(_subject_did, _trust_score, _performance_summary, _issuer_token), do: :erlang.nif_error(:nif_not_loaded)
     def test_add(_a, _b), do: :erlang.nif_error(:nif_not_loaded)
   end
@@ -48,17 +55,24 @@ else
 
     # --- Original GTR NIFs (stubs) ---
     def calculate_potential_value(_node_metrics, _sla), do: @error
-    def calculate_forwarding_decision(_candidate_hops, _multipath_threshold), do: @error
-    def analyse_dag(_packet_trails, _sla, _total_packets_sent), do: @error
+    def calculate_forwarding_decision(_candidate_hops, _multipath_threshold, _seed), do: @error
+    def calculate_forwarding_decision_detailed(_candidate_hops, _multipath_threshold, _weights, _seed), do: @error
+    def analyse_dag(_packet_trails, _sla, _total_packets_sent, _seed), do: @error
+    def analyse_dag_multi(_packet_trails, _slas, _total_packets_sent), do: @error
+    def analyse_dag_detailed(_packet_trails, _sla, _total_packets_sent), do: @error
 
     # --- Dynamic PoPS NIFs (stubs) ---
     def adjust_parameters_for_epoch_nif(_current_params, _state), do: @error
+    def simulate_epochs_nif(_initial_params, _states), do: @error
     def calculate_slash_percentage_nif(_required, _actual, _params), do: @error
     def update_trust_score_on_success_nif(_score, _weight), do: @error
     def update_trust_score_on_failure_nif(_score, _slash, _params), do: @error
+    def update_trust_scores_on_success_batch_nif(_updates), do: @error
+    def update_trust_scores_on_failure_batch_nif(_updates, _params), do: @error
     def decay_trust_score_continuously_nif(_score, _seconds_elapsed, _params), do: @error
     def calculate_supplier_offering_nif(_trust, _params), do: @error
     def calculate_consumer_utility_nif(_offering, _trust, _consumer), do: @error
+    def consumer_utility_gradient_nif(_offering, _trust, _consumer), do: @error
     def create_trust_score_credential_nif(_subject_did, _trust_score, _performance_summary, _issuer_token), do: @error
     def test_add(_a, _b), do: @error
   end
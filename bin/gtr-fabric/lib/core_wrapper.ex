@@ -14,15 +14,22 @@ defmodule GtrFabric.CoreWrapper do
 
   # Public API
   def consumer_utility(offering, trust, consumer), do: wrap(:calculate_consumer_utility_nif, [offering, trust, consumer])
+  def consumer_utility_gradient(offering, trust, consumer), do: wrap(:consumer_utility_gradient_nif, [offering, trust, consumer])
   def supplier_offering(trust, params), do: wrap(:calculate_supplier_offering_nif, [trust, params])
   def adjust_parameters(params, state), do: wrap(:adjust_parameters_for_epoch_nif, [params, state])
+  def simulate_epochs(params, states), do: wrap(:simulate_epochs_nif, [params, states])
   def slash_percentage(req, actual, params), do: wrap(:calculate_slash_percentage_nif, [req, actual, params])
   def trust_success(score, weight), do: wrap(:update_trust_score_on_success_nif, [score, weight])
   def trust_failure(score, slash, params), do: wrap(:update_trust_score_on_failure_nif, [score, slash, params])
+  def trust_success_batch(updates), do: wrap(:update_trust_scores_on_success_batch_nif, [updates])
+  def trust_failure_batch(updates, params), do: wrap(:update_trust_scores_on_failure_batch_nif, [updates, params])
   def trust_decay(score, secs, params), do: wrap(:decay_trust_score_continuously_nif, [score, secs, params])
   def potential_value(metrics, sla), do: wrap(:calculate_potential_value, [metrics, sla])
-  def forwarding_decision(hops, threshold), do: wrap(:calculate_forwarding_decision, [hops, threshold])
-  def analyse_dag(trails, sla, total), do: wrap(:analyse_dag, [trails, sla, total])
+  def forwarding_decision(hops, threshold, seed \\ nil), do: wrap(:calculate_forwarding_decision, [hops, threshold, seed])
+  def forwarding_decision_detailed(hops, threshold, weights, seed \\ nil), do: wrap(:calculate_forwarding_decision_detailed, [hops, threshold, weights, seed])
+  def analyse_dag(trails, sla, total, seed \\ nil), do: wrap(:analyse_dag, [trails, sla, total, seed])
+  def analyse_dag_multi(trails, slas, total), do: wrap(:analyse_dag_multi, [trails, slas, total])
+  def analyse_dag_detailed(trails, sla, total), do: wrap(:analyse_dag_detailed, [trails, sla, total])
   def create_trust_vc(subject_did, trust_score, perf_summary, issuer_token), do: wrap(:create_trust_score_credential_nif, [subject_did, trust_score, perf_summary, issuer_token])
 
   # Generic wrapper
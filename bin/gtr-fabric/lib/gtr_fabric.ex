@@ -37,19 +37,65 @@ defmodule GtrFabric do
     end
   end
 
-  def calculate_forwarding_decision(candidate_hops, multipath_threshold \\ 1.05) do
+  @doc """
+  Picks the next hop for a packet. When several hops fall within `multipath_threshold`
+  of the best cost, one is drawn at random weighted by inverse cost; pass an integer
+  `seed` to make that draw reproducible, or `nil` to seed from entropy.
+  """
+  def calculate_forwarding_decision(candidate_hops, multipath_threshold \\ 1.05, seed \\ nil) do
     validate_candidate_hops_for_forwarding!(candidate_hops)
 
     # This is a performance-critical function.
     # We delegate directly to the NIF for speed.
-    case GtrFabric.CoreWrapper.forwarding_decision(candidate_hops, multipath_threshold) do
+    case GtrFabric.CoreWrapper.forwarding_decision(candidate_hops, multipath_threshold, seed) do
+      {:ok, v} -> v
+      {:error, r} -> raise ArgumentError, inspect(r)
+    end
+  end
+
+  @doc """
+  Like `calculate_forwarding_decision/3`, but weighs latency, potential and trust
+  according to a `GtrFabric.ForwardingWeights` and returns a
+  `GtrFabric.ForwardingDecision` with the per-hop score breakdown.
+  """
+  def calculate_forwarding_decision_detailed(
+        candidate_hops,
+        multipath_threshold \\ 1.05,
+        weights \\ %GtrFabric.ForwardingWeights{},
+        seed \\ nil
+      ) do
+    validate_candidate_hops_for_forwarding!(candidate_hops)
+
+    case GtrFabric.CoreWrapper.forwarding_decision_detailed(candidate_hops, multipath_threshold, weights, seed) do
       {:ok, v} -> v
       {:error, r} -> raise ArgumentError, inspect(r)
     end
   end
 
-  def analyse_dag(packet_trails, sla, total_packets_sent) do
-    GtrFabric.CoreWrapper.analyse_dag(packet_trails, sla, total_packets_sent)
+  @doc """
+  Analyses the packet trails of a completed task against `sla` and returns a
+  `GtrFabric.ResolutionReport`. The optional `seed` pins any randomised step so
+  that a run can be reproduced; identical trails and seed give identical reports.
+  """
+  def analyse_dag(packet_trails, sla, total_packets_sent, seed \\ nil) do
+    GtrFabric.CoreWrapper.analyse_dag(packet_trails, sla, total_packets_sent, seed)
+  end
+
+  @doc """
+  Evaluates the same packet trails against several SLAs, walking the trails once.
+  Returns one `GtrFabric.ResolutionReport` per SLA, in the order given.
+  """
+  def analyse_dag_multi(packet_trails, slas, total_packets_sent) when is_list(slas) do
+    GtrFabric.CoreWrapper.analyse_dag_multi(packet_trails, slas, total_packets_sent)
+  end
+
+  @doc """
+  Analyses the trails like `analyse_dag/4` and also attributes traffic to each node.
+  Returns a `GtrFabric.DetailedResolutionReport` whose `hops` map holds packets
+  forwarded, packets dropped and hop latency percentiles per node.
+  """
+  def analyse_dag_detailed(packet_trails, sla, total_packets_sent) do
+    GtrFabric.CoreWrapper.analyse_dag_detailed(packet_trails, sla, total_packets_sent)
   end
 
   # --- Dynamic PoPS API ---
@@ -62,6 +108,14 @@ defmodule GtrFabric do
     Protocol.adjust_parameters_for_epoch(current_params, state)
   end
 
+  @doc """
+  Previews how the dynamic parameters evolve over a projected trajectory of
+  network states, returning one parameter set per epoch.
+  """
+  def simulate_epochs(initial_params, states) do
+    Protocol.simulate_epochs(initial_params, states)
+  end
+
   @doc "Calculates the percentage of collateral to be slashed based on performance."
   def calculate_slash_percentage(required_performance, actual_performance, params) do
     Protocol.calculate_slash_percentage(
@@ -81,6 +135,22 @@ defmodule GtrFabric do
     Reputation.update_trust_score_on_failure(score, slash_percentage, params)
   end
 
+  @doc """
+  Updates a batch of `{score, success_weight}` pairs in a single NIF call.
+  Returns `{:ok, scores}` or `{:error, reason}`.
+  """
+  def update_trust_scores_on_success(updates) do
+    Reputation.update_trust_scores_on_success(updates)
+  end
+
+  @doc """
+  Updates a batch of `{score, slash_percentage}` pairs in a single NIF call.
+  Returns `{:ok, scores}` or `{:error, reason}`.
+  """
+  def update_trust_scores_on_failure(updates, params) do
+    Reputation.update_trust_scores_on_failure(updates, params)
+  end
+
   @doc "Decays a trust score over a period of time based on the dynamic lambda."
   def decay_trust_score_continuously(score, seconds_elapsed, params) do
     Reputation.decay_trust_score_continuously(score, seconds_elapsed, params)
@@ -96,6 +166,11 @@ defmodule GtrFabric do
     GtrFabric.Strategy.calculate_consumer_utility(offering, trust_score, consumer)
   end
 
+  @doc "Returns the sensitivity of consumer utility to each consumer factor and to price."
+  def consumer_utility_gradient(offering, trust_score, consumer) do
+    GtrFabric.Strategy.consumer_utility_gradient(offering, trust_score, consumer)
+  end
+
   @doc """
   Start a new routing task in the GTR network.
 
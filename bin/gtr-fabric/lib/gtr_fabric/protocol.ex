@@ -25,6 +25,14 @@ defmodule GtrFabric.Protocol do
     GtrFabric.CoreNifs.adjust_parameters_for_epoch_nif(current_params, state)
   end
 
+  @doc """
+  Applies `adjust_parameters_for_epoch/2` once per projected network state and
+  returns the parameters in force after each epoch, in order.
+  """
+  def simulate_epochs(initial_params, states) do
+    GtrFabric.CoreNifs.simulate_epochs_nif(initial_params, states)
+  end
+
   @doc "Calculates the percentage of collateral to be slashed based on performance."
   def calculate_slash_percentage(required_performance, actual_performance, params) do
     GtrFabric.CoreNifs.calculate_slash_percentage_nif(
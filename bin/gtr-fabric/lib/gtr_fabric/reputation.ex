@@ -33,6 +33,22 @@ defmodule GtrFabric.Reputation do
     end
   end
 
+  @doc """
+  Applies `update_trust_score_on_success/2` to each `{score, success_weight}` pair in one NIF call.
+  Returns `{:ok, scores}` or `{:error, reason}` when the NIF call fails.
+  """
+  def update_trust_scores_on_success(updates) do
+    GtrFabric.CoreWrapper.trust_success_batch(updates)
+  end
+
+  @doc """
+  Applies `update_trust_score_on_failure/3` to each `{score, slash_percentage}` pair in one NIF call.
+  Returns `{:ok, scores}` or `{:error, reason}` when the NIF call fails.
+  """
+  def update_trust_scores_on_failure(updates, params) do
+    GtrFabric.CoreWrapper.trust_failure_batch(updates, params)
+  end
+
   @doc "Decays a trust score over a period of time based on the dynamic lambda."
   def decay_trust_score_continuously(score, seconds_elapsed, params) do
     case GtrFabric.CoreWrapper.trust_decay(score, seconds_elapsed, params) do
@@ -32,4 +32,15 @@ defmodule GtrFabric.Strategy do
       {:error, _} -> 0.0
     end
   end
+
+  @doc """
+  Returns how consumer utility responds to each `ConsumerFactors` field and to the
+  offering price, as a `GtrFabric.ConsumerFactorsGradient` of finite-difference slopes.
+  """
+  def consumer_utility_gradient(offering, trust_score, consumer) do
+    case GtrFabric.CoreWrapper.consumer_utility_gradient(offering, trust_score, consumer) do
+      {:ok, v} -> v
+      {:error, _} -> {:error, :nif_disabled}
+    end
+  end
 end
@@ -41,17 +41,73 @@ defmodule GtrFabric.CandidateHop do
   @moduledoc """
   Represents a potential next hop for a packet.
   Passed from Elixir to the `calculate_forwarding_decision` NIF.
+  `trust` is the neighbour's trust score in [0, 1] and defaults to fully trusted.
   """
   defstruct [
     :id,
     :potential,
-    :latency
+    :latency,
+    trust: 1.0
   ]
 
   @type t :: %__MODULE__{
     id: String.t(),
     potential: float(),
-    latency: float()
+    latency: float(),
+    trust: float()
+  }
+end
+
+defmodule GtrFabric.ForwardingWeights do
+  @moduledoc """
+  Corresponds to the Rust `ForwardingWeights` struct.
+  Scales each component of a hop's cost; the lowest total cost wins.
+
+    * `latency` - multiplies the hop's link latency. Raising it favours fast links.
+    * `potential` - multiplies the hop's potential towards the target. Raising it
+      favours hops that are closer to satisfying the SLA.
+    * `trust` - multiplies the hop's distrust (`1 - trust`). Raising it steers
+      traffic away from poorly trusted neighbours even when they are faster.
+
+  The defaults reproduce the unweighted `latency + potential` cost.
+  """
+  defstruct latency: 1.0, potential: 1.0, trust: 0.0
+
+  @type t :: %__MODULE__{
+    latency: float(),
+    potential: float(),
+    trust: float()
+  }
+end
+
+defmodule GtrFabric.HopScore do
+  @moduledoc """
+  Corresponds to the Rust `HopScore` struct.
+  The weighted cost breakdown for a single candidate hop.
+  """
+  defstruct [:id, :latency_cost, :potential_cost, :trust_cost, :total]
+
+  @type t :: %__MODULE__{
+    id: String.t(),
+    latency_cost: float(),
+    potential_cost: float(),
+    trust_cost: float(),
+    total: float()
+  }
+end
+
+defmodule GtrFabric.ForwardingDecision do
+  @moduledoc """
+  Corresponds to the Rust `ForwardingDecision` struct.
+  The chosen hop, the hops within the multipath threshold, and every hop's
+  score ordered from cheapest to most expensive.
+  """
+  defstruct [:next_hop, :viable_hops, :scores]
+
+  @type t :: %__MODULE__{
+    next_hop: String.t(),
+    viable_hops: [String.t()],
+    scores: [GtrFabric.HopScore.t()]
   }
 end
 
@@ -138,6 +194,51 @@ defmodule GtrFabric.ResolutionReport do
   }
 end
 
+defmodule GtrFabric.HopAttribution do
+  @moduledoc """
+  Corresponds to the Rust `HopAttribution` struct.
+  Traffic observed at one node: packets it forwarded, packets whose trail ended
+  there undelivered, and percentiles of the latency to the next hop.
+  Percentiles are -1.0 when the node never forwarded a packet.
+  """
+  defstruct [
+    :packets_forwarded,
+    :packets_dropped,
+    :latency_p50_ms,
+    :latency_p90_ms,
+    :latency_p99_ms
+  ]
+
+  @type t :: %__MODULE__{
+    packets_forwarded: non_neg_integer(),
+    packets_dropped: non_neg_integer(),
+    latency_p50_ms: float(),
+    latency_p90_ms: float(),
+    latency_p99_ms: float()
+  }
+end
+
+defmodule GtrFabric.DetailedResolutionReport do
+  @moduledoc """
+  Returned by `analyse_dag_detailed`. Wraps the usual `ResolutionReport` with a
+  per-node attribution map. Packets sent but absent from every trail are counted
+  in `unattributed_drops`.
+  """
+  defstruct [
+    :report,
+    :packets_delivered,
+    :unattributed_drops,
+    :hops
+  ]
+
+  @type t :: %__MODULE__{
+    report: GtrFabric.ResolutionReport.t(),
+    packets_delivered: non_neg_integer(),
+    unattributed_drops: non_neg_integer(),
+    hops: %{String.t() => GtrFabric.HopAttribution.t()}
+  }
+end
+
 # --- PoPS (Proof-of-Performance Staking) Model Types ---
 
 defmodule GtrFabric.NetworkState do
@@ -163,6 +264,11 @@ defmodule GtrFabric.DynamicParameters do
   @moduledoc """
   Corresponds to the Rust `DynamicParameters` struct.
   Holds the dynamic parameters for the PoPS model.
+
+  `slash_curve` selects how a performance shortfall maps to a slash:
+  `:logistic` (the default sigmoid shaped by `steepness` and `centre`),
+  `:linear`, `:quadratic`, or `{:stepwise, %{threshold: t, penalty: p}}`,
+  which slashes `p` once the shortfall reaches `t`. Every curve is clamped to [0, 1].
   """
   @enforce_keys [
     :steepness,
@@ -178,16 +284,24 @@ defmodule GtrFabric.DynamicParameters do
     :failure_weight,
     :decay_lambda_per_day,
     :collateral_multiplier,
-    :bonus_multiplier
+    :bonus_multiplier,
+    slash_curve: :logistic
   ]
 
+  @type slash_curve ::
+          :logistic
+          | :linear
+          | :quadratic
+          | {:stepwise, %{threshold: float(), penalty: float()}}
+
   @type t :: %__MODULE__{
     steepness: float(),
     centre: float(),
     failure_weight: float(),
     decay_lambda_per_day: float(),
     collateral_multiplier: float(),
-    bonus_multiplier: float()
+    bonus_multiplier: float(),
+    slash_curve: slash_curve()
   }
 
   def new() do
@@ -198,7 +312,8 @@ defmodule GtrFabric.DynamicParameters do
       failure_weight: 0.2,
       decay_lambda_per_day: 0.01,
       collateral_multiplier: 2.0,
-      bonus_multiplier: 0.25
+      bonus_multiplier: 0.25,
+      slash_curve: :logistic
     }
   end
 end
@@ -243,6 +358,22 @@ defmodule GtrFabric.ConsumerFactors do
   }
 end
 
+defmodule GtrFabric.ConsumerFactorsGradient do
+  @moduledoc """
+  Corresponds to the Rust `ConsumerFactorsGradient` struct.
+  The slope of consumer utility with respect to each `ConsumerFactors` field and
+  to the offering's `price_per_call`.
+  """
+  defstruct [:risk_aversion, :budget, :cost_of_failure, :price_per_call]
+
+  @type t :: %__MODULE__{
+    risk_aversion: float(),
+    budget: float(),
+    cost_of_failure: float(),
+    price_per_call: float()
+  }
+end
+
 defmodule GtrFabric.PenaltyCurve do
   @moduledoc """
   Corresponds to the Rust `PenaltyCurve` struct.
@@ -12,10 +12,13 @@
 
 use crate::dynamic_parameters::DynamicParameters;
 use crate::types::{
-    Breadcrumb, CandidateHop, ConsumerFactors, NodeMetrics, PublishedOffering, ResolutionReport,
-    Sla, TrustScore,
+    Breadcrumb, CandidateHop, ConsumerFactors, ConsumerFactorsGradient, DetailedResolutionReport,
+    ForwardingDecision, ForwardingWeights, HopAttribution, HopScore, NodeMetrics,
+    PublishedOffering, ResolutionReport, Sla, TrustScore,
 };
 use rand::prelude::*;
+use std::cmp::Ordering;
+use std::collections::HashMap;
 
 pub fn calculate_potential_value_impl(metrics: NodeMetrics, sla: Sla) -> Result<f64, String> {
     if metrics.trust_score < 0.0 || metrics.trust_score > 1.0 {
@@ -56,16 +59,46 @@ pub fn calculate_potential_value_impl(metrics: NodeMetrics, sla: Sla) -> Result<
 pub fn calculate_forwarding_decision_impl(
     candidate_hops: Vec<CandidateHop>,
     multipath_threshold: f64,
+    seed: Option<u64>,
 ) -> Result<String, String> {
-    calculate_forwarding_decision_with_config(&candidate_hops, multipath_threshold)
+    let weights = ForwardingWeights::default();
+    calculate_forwarding_decision_seeded(&candidate_hops, multipath_threshold, &weights, seed)
+        .map(|decision| decision.next_hop)
 }
 
 pub fn calculate_forwarding_decision_with_config(
     candidate_hops: &[CandidateHop],
     multipath_threshold: f64,
 ) -> Result<String, String> {
-    if candidate_hops.is_empty() {
-        return Ok("loop".to_string());
+    let weights = ForwardingWeights::default();
+    calculate_forwarding_decision_detailed(candidate_hops, multipath_threshold, &weights)
+        .map(|decision| decision.next_hop)
+}
+
+pub fn calculate_forwarding_decision_detailed(
+    candidate_hops: &[CandidateHop],
+    multipath_threshold: f64,
+    weights: &ForwardingWeights,
+) -> Result<ForwardingDecision, String> {
+    calculate_forwarding_decision_seeded(candidate_hops, multipath_threshold, weights, None)
+}
+
+pub fn calculate_forwarding_decision_seeded(
+    candidate_hops: &[CandidateHop],
+    multipath_threshold: f64,
+    weights: &ForwardingWeights,
+    seed: Option<u64>,
+) -> Result<ForwardingDecision, String> {
+    for (name, weight) in [
+        ("latency", weights.latency),
+        ("potential", weights.potential),
+        ("trust", weights.trust),
+    ] {
+        if !weight.is_finite() || weight < 0.0 {
+            return Err(format!(
+                "Invalid {name} weight: {weight}. Must be finite and non-negative"
+            ));
+        }
     }
 
     for hop in candidate_hops {
@@ -87,38 +120,74 @@ pub fn calculate_forwarding_decision_with_config(
                 hop.id, hop.latency
             ));
         }
+        if !(0.0..=1.0).contains(&hop.trust) {
+            return Err(format!(
+                "Invalid trust for hop '{}': {}. Must be between 0.0 and 1.0",
+                hop.id, hop.trust
+            ));
+        }
     }
 
-    let mut candidates_with_cost: Vec<(String, f64)> = candidate_hops
+    let mut scores: Vec<HopScore> = candidate_hops
         .iter()
-        .map(|hop| {
-            let cost = hop.latency + hop.potential;
-            (hop.id.clone(), cost)
-        })
+        .map(|hop| score_hop(hop, weights))
         .collect();
 
-    candidates_with_cost.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+    scores.sort_by(|a, b| a.total.partial_cmp(&b.total).unwrap_or(Ordering::Equal));
 
-    let best_cost = match candidates_with_cost.first() {
-        Some((_, cost)) => *cost,
-        None => return Ok("loop".to_string()),
+    let best_cost = match scores.first() {
+        Some(score) => score.total,
+        None => {
+            return Ok(ForwardingDecision {
+                next_hop: "loop".to_string(),
+                viable_hops: Vec::new(),
+                scores,
+            })
+        }
     };
 
-    let viable_paths: Vec<(String, f64)> = candidates_with_cost
-        .into_iter()
-        .filter(|(_, cost)| *cost <= best_cost * multipath_threshold)
+    let viable_paths: Vec<(String, f64)> = scores
+        .iter()
+        .filter(|score| score.total <= best_cost * multipath_threshold)
+        .map(|score| (score.id.clone(), score.total))
         .collect();
 
-    if viable_paths.len() <= 1 {
-        return Ok(viable_paths
+    let next_hop = if viable_paths.len() <= 1 {
+        viable_paths
             .first()
-            .map_or("loop".to_string(), |(id, _)| id.clone()));
+            .map_or("loop".to_string(), |(id, _)| id.clone())
+    } else {
+        select_weighted_random_path(&viable_paths, &mut seeded_rng(seed))
+    };
+
+    Ok(ForwardingDecision {
+        next_hop,
+        viable_hops: viable_paths.into_iter().map(|(id, _)| id).collect(),
+        scores,
+    })
+}
+
+fn score_hop(hop: &CandidateHop, weights: &ForwardingWeights) -> HopScore {
+    let latency_cost = weights.latency * hop.latency;
+    let potential_cost = weights.potential * hop.potential;
+    let trust_cost = weights.trust * (1.0 - hop.trust);
+    HopScore {
+        id: hop.id.clone(),
+        latency_cost,
+        potential_cost,
+        trust_cost,
+        total: latency_cost + potential_cost + trust_cost,
     }
+}
 
-    Ok(select_weighted_random_path(&viable_paths))
+fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
 }
 
-fn select_weighted_random_path(viable_paths: &[(String, f64)]) -> String {
+fn select_weighted_random_path(viable_paths: &[(String, f64)], rng: &mut StdRng) -> String {
     if viable_paths.is_empty() {
         return "loop".to_string();
     }
@@ -127,7 +196,6 @@ fn select_weighted_random_path(viable_paths: &[(String, f64)]) -> String {
         return id.clone();
     }
 
-    let mut rng = thread_rng();
     let weights: Vec<f64> = viable_paths.iter().map(|(_, cost)| 1.0 / cost).collect();
 
     let total_weight: f64 = weights.iter().sum();
@@ -153,7 +221,11 @@ pub fn analyse_dag_impl(
     packet_trails: Vec<Vec<Breadcrumb>>,
     sla: Sla,
     total_packets_sent: u32,
+    seed: Option<u64>,
 ) -> Result<ResolutionReport, String> {
+    // The measurement itself draws no random numbers; the seed is taken so that
+    // callers can pin analysis and forwarding runs with the same value.
+    let _ = seed;
     analyse_dag_with_trails(&packet_trails, &sla, total_packets_sent)
 }
 
@@ -162,84 +234,202 @@ pub fn analyse_dag_with_trails(
     sla: &Sla,
     total_packets_sent: u32,
 ) -> Result<ResolutionReport, String> {
-    if total_packets_sent == 0 {
-        return Err("Cannot analyse DAG: total_packets_sent cannot be zero.".to_string());
-    }
+    DagMeasurement::from_trails(packet_trails, total_packets_sent)
+        .map(|measurement| measurement.report(sla))
+}
 
-    if packet_trails.is_empty() || packet_trails.iter().all(|trail| trail.is_empty()) {
-        return Ok(ResolutionReport {
-            sla_met: false,
-            avg_latency_ms: -1.0,
-            jitter_ms: -1.0,
-            loss_percentage: 100.0,
-            analysis_summary: "Analysis failed: No successful packets received.".to_string(),
-        });
-    }
+pub fn analyse_dag_multi(
+    packet_trails: Vec<Vec<Breadcrumb>>,
+    slas: Vec<Sla>,
+    total_packets_sent: u32,
+) -> Result<Vec<ResolutionReport>, String> {
+    let measurement = DagMeasurement::from_trails(&packet_trails, total_packets_sent)?;
+    Ok(slas.iter().map(|sla| measurement.report(sla)).collect())
+}
+
+pub fn analyse_dag_detailed(
+    packet_trails: &[Vec<Breadcrumb>],
+    sla: &Sla,
+    total_packets_sent: u32,
+) -> Result<DetailedResolutionReport, String> {
+    let report = analyse_dag_with_trails(packet_trails, sla, total_packets_sent)?;
 
-    for (trail_index, trail) in packet_trails.iter().enumerate() {
-        for (breadcrumb_index, breadcrumb) in trail.iter().enumerate() {
-            if breadcrumb.node_id.is_empty() {
-                return Err(format!(
-                    "Invalid breadcrumb at trail {trail_index} position {breadcrumb_index}: node_id cannot be empty"
-                ));
+    let mut tallies: HashMap<&str, HopTally> = HashMap::new();
+    let mut packets_delivered = 0_u32;
+    let mut attributed_drops = 0_u32;
+    for trail in packet_trails {
+        for breadcrumb in trail {
+            tallies.entry(&breadcrumb.node_id).or_default();
+        }
+        for pair in trail.windows(2) {
+            let tally = tallies.entry(&pair[0].node_id).or_default();
+            tally.forwarded += 1;
+            if let Some(latency) = pair[1].timestamp_ms.checked_sub(pair[0].timestamp_ms) {
+                tally.latencies.push(latency as f64);
             }
         }
+        if calculate_trail_latency(trail).is_some() {
+            packets_delivered += 1;
+        } else if let Some(last) = trail.last() {
+            tallies.entry(&last.node_id).or_default().dropped += 1;
+            attributed_drops += 1;
+        }
     }
 
-    let mut latencies: Vec<f64> = Vec::new();
-    for trail in packet_trails {
-        if let Some(latency) = calculate_trail_latency(trail) {
-            latencies.push(latency);
+    let hops = tallies
+        .into_iter()
+        .map(|(node_id, tally)| (node_id.to_string(), tally.into_attribution()))
+        .collect();
+
+    Ok(DetailedResolutionReport {
+        report,
+        packets_delivered,
+        unattributed_drops: total_packets_sent.saturating_sub(packets_delivered + attributed_drops),
+        hops,
+    })
+}
+
+#[derive(Default)]
+struct HopTally {
+    forwarded: u32,
+    dropped: u32,
+    latencies: Vec<f64>,
+}
+
+impl HopTally {
+    fn into_attribution(mut self) -> HopAttribution {
+        self.latencies.sort_by(f64::total_cmp);
+        HopAttribution {
+            packets_forwarded: self.forwarded,
+            packets_dropped: self.dropped,
+            latency_p50_ms: percentile(&self.latencies, 50.0),
+            latency_p90_ms: percentile(&self.latencies, 90.0),
+            latency_p99_ms: percentile(&self.latencies, 99.0),
         }
     }
+}
 
-    if latencies.is_empty() {
-        return Ok(ResolutionReport {
-            sla_met: false,
-            avg_latency_ms: -1.0,
-            jitter_ms: -1.0,
-            loss_percentage: 100.0,
-            analysis_summary: "Analysis failed: No valid packet trails with measurable latency."
-                .to_string(),
-        });
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return -1.0;
     }
+    let rank = (pct / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DagMeasurement {
+    Unmeasurable {
+        reason: &'static str,
+    },
+    Measured {
+        avg_latency_ms: f64,
+        jitter_ms: f64,
+        loss_percentage: f64,
+    },
+}
 
-    let successful_packets = latencies.len() as u32;
-    let loss_percentage = 100.0 * (1.0 - (successful_packets as f64 / total_packets_sent as f64));
+impl DagMeasurement {
+    pub fn from_trails(
+        packet_trails: &[Vec<Breadcrumb>],
+        total_packets_sent: u32,
+    ) -> Result<DagMeasurement, String> {
+        if total_packets_sent == 0 {
+            return Err("Cannot analyse DAG: total_packets_sent cannot be zero.".to_string());
+        }
 
-    let total_latency: f64 = latencies.iter().sum();
-    let avg_latency_ms = total_latency / successful_packets as f64;
+        if packet_trails.is_empty() || packet_trails.iter().all(|trail| trail.is_empty()) {
+            return Ok(DagMeasurement::Unmeasurable {
+                reason: "Analysis failed: No successful packets received.",
+            });
+        }
 
-    let variance: f64 = latencies
-        .iter()
-        .map(|l| {
-            let diff = l - avg_latency_ms;
-            diff * diff
+        for (trail_index, trail) in packet_trails.iter().enumerate() {
+            for (breadcrumb_index, breadcrumb) in trail.iter().enumerate() {
+                if breadcrumb.node_id.is_empty() {
+                    return Err(format!(
+                        "Invalid breadcrumb at trail {trail_index} position {breadcrumb_index}: node_id cannot be empty"
+                    ));
+                }
+            }
+        }
+
+        let mut latencies: Vec<f64> = Vec::new();
+        for trail in packet_trails {
+            if let Some(latency) = calculate_trail_latency(trail) {
+                latencies.push(latency);
+            }
+        }
+
+        if latencies.is_empty() {
+            return Ok(DagMeasurement::Unmeasurable {
+                reason: "Analysis failed: No valid packet trails with measurable latency.",
+            });
+        }
+
+        let successful_packets = latencies.len() as u32;
+        let loss_percentage =
+            100.0 * (1.0 - (successful_packets as f64 / total_packets_sent as f64));
+
+        let total_latency: f64 = latencies.iter().sum();
+        let avg_latency_ms = total_latency / successful_packets as f64;
+
+        let variance: f64 = latencies
+            .iter()
+            .map(|l| {
+                let diff = l - avg_latency_ms;
+                diff * diff
+            })
+            .sum::<f64>()
+            / successful_packets as f64;
+        let jitter_ms = variance.sqrt();
+
+        Ok(DagMeasurement::Measured {
+            avg_latency_ms,
+            jitter_ms,
+            loss_percentage,
         })
-        .sum::<f64>()
-        / successful_packets as f64;
-    let jitter_ms = variance.sqrt();
-
-    let latency_met = avg_latency_ms <= sla.e2e_latency_ms as f64;
-    let jitter_met = jitter_ms <= sla.jitter_ms as f64;
-    let loss_met = loss_percentage <= sla.loss_percentage as f64;
-    let sla_met = latency_met && jitter_met && loss_met;
-
-    let summary = format!(
-        "SLA Check: Latency {:.2}ms (Req: {}ms) -> {}, Jitter {:.2}ms (Req: {}ms) -> {}, Loss {:.2}% (Req: {}%) -> {}. Overall: {}",
-        avg_latency_ms, sla.e2e_latency_ms, latency_met,
-        jitter_ms, sla.jitter_ms, jitter_met,
-        loss_percentage, sla.loss_percentage, loss_met,
-        if sla_met { "MET" } else { "FAILED" }
-    );
-
-    Ok(ResolutionReport {
-        sla_met,
-        avg_latency_ms,
-        jitter_ms,
-        loss_percentage,
-        analysis_summary: summary,
-    })
+    }
+
+    pub fn report(&self, sla: &Sla) -> ResolutionReport {
+        let (avg_latency_ms, jitter_ms, loss_percentage) = match *self {
+            DagMeasurement::Unmeasurable { reason } => {
+                return ResolutionReport {
+                    sla_met: false,
+                    avg_latency_ms: -1.0,
+                    jitter_ms: -1.0,
+                    loss_percentage: 100.0,
+                    analysis_summary: reason.to_string(),
+                }
+            }
+            DagMeasurement::Measured {
+                avg_latency_ms,
+                jitter_ms,
+                loss_percentage,
+            } => (avg_latency_ms, jitter_ms, loss_percentage),
+        };
+
+        let latency_met = avg_latency_ms <= sla.e2e_latency_ms as f64;
+        let jitter_met = jitter_ms <= sla.jitter_ms as f64;
+        let loss_met = loss_percentage <= sla.loss_percentage as f64;
+        let sla_met = latency_met && jitter_met && loss_met;
+
+        let summary = format!(
+            "SLA Check: Latency {:.2}ms (Req: {}ms) -> {}, Jitter {:.2}ms (Req: {}ms) -> {}, Loss {:.2}% (Req: {}%) -> {}. Overall: {}",
+            avg_latency_ms, sla.e2e_latency_ms, latency_met,
+            jitter_ms, sla.jitter_ms, jitter_met,
+            loss_percentage, sla.loss_percentage, loss_met,
+            if sla_met { "MET" } else { "FAILED" }
+        );
+
+        ResolutionReport {
+            sla_met,
+            avg_latency_ms,
+            jitter_ms,
+            loss_percentage,
+            analysis_summary: summary,
+        }
+    }
 }
 
 fn calculate_trail_latency(trail: &[Breadcrumb]) -> Option<f64> {
@@ -267,13 +457,16 @@ pub fn calculate_slash_percentage(
     actual_performance: f64,
     params: &DynamicParameters,
 ) -> f64 {
-    if actual_performance >= required_performance {
+    if actual_performance.partial_cmp(&required_performance) != Some(Ordering::Less) {
         return 0.0;
     }
 
-    let shortfall = (required_performance - actual_performance) / required_performance;
+    let shortfall =
+        ((required_performance - actual_performance) / required_performance).clamp(0.0, 1.0);
 
-    let penalty_factor = 1.0 / (1.0 + (-params.steepness * (shortfall - params.centre)).exp());
+    let penalty_factor = params
+        .slash_curve
+        .penalty_factor(shortfall, params.steepness, params.centre);
 
     penalty_factor * 100.0
 }
@@ -301,6 +494,25 @@ pub fn update_trust_score_on_failure(
     score
 }
 
+pub fn update_trust_scores_on_success(updates: Vec<(TrustScore, f64)>) -> Vec<TrustScore> {
+    updates
+        .into_iter()
+        .map(|(score, success_weight)| update_trust_score_on_success(score, success_weight))
+        .collect()
+}
+
+pub fn update_trust_scores_on_failure(
+    updates: Vec<(TrustScore, f64)>,
+    params: &DynamicParameters,
+) -> Vec<TrustScore> {
+    updates
+        .into_iter()
+        .map(|(score, slash_percentage)| {
+            update_trust_score_on_failure(score, slash_percentage, params)
+        })
+        .collect()
+}
+
 pub fn decay_trust_score_continuously(
     mut score: TrustScore,
     seconds_elapsed: u64,
@@ -351,3 +563,57 @@ pub fn calculate_consumer_utility(
 
     promised_performance / adjusted_cost
 }
+
+const FINITE_DIFFERENCE_STEP: f64 = 1e-6;
+
+pub fn consumer_utility_gradient(
+    offering: &PublishedOffering,
+    trust_score: &TrustScore,
+    consumer: &ConsumerFactors,
+) -> ConsumerFactorsGradient {
+    let utility = |offering: &PublishedOffering, consumer: &ConsumerFactors| {
+        calculate_consumer_utility(offering, trust_score, consumer)
+    };
+
+    ConsumerFactorsGradient {
+        risk_aversion: central_difference(consumer.risk_aversion, |risk_aversion| {
+            let consumer = ConsumerFactors {
+                risk_aversion,
+                ..consumer.clone()
+            };
+            utility(offering, &consumer)
+        }),
+        budget: integer_difference(consumer.budget, |budget| {
+            let consumer = ConsumerFactors {
+                budget,
+                ..consumer.clone()
+            };
+            utility(offering, &consumer)
+        }),
+        cost_of_failure: central_difference(consumer.cost_of_failure, |cost_of_failure| {
+            let consumer = ConsumerFactors {
+                cost_of_failure,
+                ..consumer.clone()
+            };
+            utility(offering, &consumer)
+        }),
+        price_per_call: integer_difference(offering.price_per_call, |price_per_call| {
+            let offering = PublishedOffering {
+                price_per_call,
+                ..offering.clone()
+            };
+            utility(&offering, consumer)
+        }),
+    }
+}
+
+fn central_difference(value: f64, evaluate: impl Fn(f64) -> f64) -> f64 {
+    let step = FINITE_DIFFERENCE_STEP * value.abs().max(1.0);
+    (evaluate(value + step) - evaluate(value - step)) / (2.0 * step)
+}
+
+fn integer_difference(value: u64, evaluate: impl Fn(u64) -> f64) -> f64 {
+    let lower = value.saturating_sub(1);
+    let upper = value.saturating_add(1);
+    (evaluate(upper) - evaluate(lower)) / (upper - lower) as f64
+}
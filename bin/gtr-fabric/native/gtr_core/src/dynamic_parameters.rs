@@ -12,7 +12,7 @@
 
 
 
-use rustler::NifStruct;
+use rustler::{NifStruct, NifTaggedEnum};
 
 #[derive(Debug, Clone, NifStruct)]
 #[module = "GtrFabric.NetworkState"]
@@ -27,6 +27,30 @@ pub struct NetworkState {
 }
 
 
+#[derive(Debug, Clone, Copy, PartialEq, NifTaggedEnum)]
+pub enum SlashCurve {
+    Logistic,
+    Linear,
+    Quadratic,
+    Stepwise { threshold: f64, penalty: f64 },
+}
+
+impl SlashCurve {
+    pub fn penalty_factor(&self, shortfall: f64, steepness: f64, centre: f64) -> f64 {
+        let factor = match *self {
+            SlashCurve::Logistic => 1.0 / (1.0 + (-steepness * (shortfall - centre)).exp()),
+            SlashCurve::Linear => shortfall,
+            SlashCurve::Quadratic => shortfall * shortfall,
+            SlashCurve::Stepwise { threshold, penalty } if shortfall >= threshold => penalty,
+            SlashCurve::Stepwise { .. } => 0.0,
+        };
+        if factor.is_nan() {
+            return 0.0;
+        }
+        factor.clamp(0.0, 1.0)
+    }
+}
+
 #[derive(Debug, Clone, NifStruct)]
 #[module = "GtrFabric.DynamicParameters"]
 pub struct DynamicParameters {
@@ -36,6 +60,86 @@ pub struct DynamicParameters {
     pub decay_lambda_per_day: f64,
     pub collateral_multiplier: f64,
     pub bonus_multiplier: f64,
+    pub slash_curve: SlashCurve,
+}
+
+pub const STEEPNESS_BOUNDS: (f64, f64) = (3.0, 15.0);
+pub const CENTRE_BOUNDS: (f64, f64) = (0.0, 1.0);
+pub const FAILURE_WEIGHT_BOUNDS: (f64, f64) = (0.1, 0.75);
+pub const DECAY_LAMBDA_BOUNDS: (f64, f64) = (0.005, 0.05);
+pub const COLLATERAL_MULTIPLIER_BOUNDS: (f64, f64) = (0.5, 5.0);
+pub const BONUS_MULTIPLIER_BOUNDS: (f64, f64) = (0.1, 1.0);
+pub const STEPWISE_BOUNDS: (f64, f64) = (0.0, 1.0);
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ParamError {
+    #[error("{field} is {value}, outside [{min}, {max}]")]
+    OutOfBounds {
+        field: &'static str,
+        value: f64,
+        min: f64,
+        max: f64,
+    },
+}
+
+impl DynamicParameters {
+    pub fn validate(&self) -> Result<(), ParamError> {
+        for (field, value, (min, max)) in self.bounded_fields() {
+            if !(min..=max).contains(&value) {
+                return Err(ParamError::OutOfBounds {
+                    field,
+                    value,
+                    min,
+                    max,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn clamp_to_bounds(&mut self) {
+        self.steepness = clamp_field("steepness", self.steepness, STEEPNESS_BOUNDS);
+        self.centre = clamp_field("centre", self.centre, CENTRE_BOUNDS);
+        self.failure_weight =
+            clamp_field("failure_weight", self.failure_weight, FAILURE_WEIGHT_BOUNDS);
+        self.decay_lambda_per_day =
+            clamp_field("decay_lambda_per_day", self.decay_lambda_per_day, DECAY_LAMBDA_BOUNDS);
+        self.collateral_multiplier = clamp_field(
+            "collateral_multiplier",
+            self.collateral_multiplier,
+            COLLATERAL_MULTIPLIER_BOUNDS,
+        );
+        self.bonus_multiplier =
+            clamp_field("bonus_multiplier", self.bonus_multiplier, BONUS_MULTIPLIER_BOUNDS);
+        if let SlashCurve::Stepwise { threshold, penalty } = &mut self.slash_curve {
+            *threshold = clamp_field("slash_curve.threshold", *threshold, STEPWISE_BOUNDS);
+            *penalty = clamp_field("slash_curve.penalty", *penalty, STEPWISE_BOUNDS);
+        }
+    }
+
+    fn bounded_fields(&self) -> Vec<(&'static str, f64, (f64, f64))> {
+        let mut fields = vec![
+            ("steepness", self.steepness, STEEPNESS_BOUNDS),
+            ("centre", self.centre, CENTRE_BOUNDS),
+            ("failure_weight", self.failure_weight, FAILURE_WEIGHT_BOUNDS),
+            ("decay_lambda_per_day", self.decay_lambda_per_day, DECAY_LAMBDA_BOUNDS),
+            ("collateral_multiplier", self.collateral_multiplier, COLLATERAL_MULTIPLIER_BOUNDS),
+            ("bonus_multiplier", self.bonus_multiplier, BONUS_MULTIPLIER_BOUNDS),
+        ];
+        if let SlashCurve::Stepwise { threshold, penalty } = self.slash_curve {
+            fields.push(("slash_curve.threshold", threshold, STEPWISE_BOUNDS));
+            fields.push(("slash_curve.penalty", penalty, STEPWISE_BOUNDS));
+        }
+        fields
+    }
+}
+
+fn clamp_field(field: &'static str, value: f64, (min, max): (f64, f64)) -> f64 {
+    let clamped = if value.is_nan() { min } else { value.clamp(min, max) };
+    if clamped != value {
+        tracing::warn!(field, value, clamped, "dynamic parameter clamped to its bounds");
+    }
+    clamped
 }
 
 impl Default for DynamicParameters {
@@ -48,6 +152,7 @@ impl Default for DynamicParameters {
             decay_lambda_per_day: 0.01,
             collateral_multiplier: 2.0,
             bonus_multiplier: 0.25,
+            slash_curve: SlashCurve::Logistic,
         }
     }
 }
@@ -93,11 +198,19 @@ pub fn adjust_parameters_for_epoch(
     }
 
 
-    next_params.steepness = next_params.steepness.clamp(3.0, 15.0);
-    next_params.failure_weight = next_params.failure_weight.clamp(0.1, 0.75);
-    next_params.collateral_multiplier = next_params.collateral_multiplier.clamp(0.5, 5.0);
-    next_params.bonus_multiplier = next_params.bonus_multiplier.clamp(0.1, 1.0);
-    next_params.decay_lambda_per_day = next_params.decay_lambda_per_day.clamp(0.005, 0.05);
-
+    next_params.clamp_to_bounds();
     next_params
 }
+
+pub fn simulate_epochs(
+    initial: DynamicParameters,
+    states: &[NetworkState],
+) -> Vec<DynamicParameters> {
+    states
+        .iter()
+        .scan(initial, |params, state| {
+            *params = adjust_parameters_for_epoch(params, state);
+            Some(params.clone())
+        })
+        .collect()
+}
@@ -15,10 +15,17 @@ pub mod dynamic_parameters;
 pub mod types;
 pub mod vc_bridge;
 pub mod vc_types;
+pub mod wire;
 
 
-pub use dynamic_parameters::{adjust_parameters_for_epoch, DynamicParameters, NetworkState};
-pub use types::{ConsumerFactors, PublishedOffering, TrustScore};
+pub use dynamic_parameters::{
+    adjust_parameters_for_epoch, simulate_epochs, DynamicParameters, NetworkState, ParamError,
+    SlashCurve,
+};
+pub use types::{
+    CandidateHop, ConsumerFactors, ConsumerFactorsGradient, ForwardingDecision, ForwardingWeights,
+    HopScore, PublishedOffering, TrustScore,
+};
 
 mod atoms {
     rustler::atoms! { ok, error, invalid_input }
@@ -36,8 +43,19 @@ fn calculate_potential_value(
 fn calculate_forwarding_decision(
     candidate_hops: Vec<types::CandidateHop>,
     multipath_threshold: f64,
+    seed: Option<u64>,
 ) -> Result<String, String> {
-    core::calculate_forwarding_decision_impl(candidate_hops, multipath_threshold)
+    core::calculate_forwarding_decision_impl(candidate_hops, multipath_threshold, seed)
+}
+
+#[rustler::nif]
+fn calculate_forwarding_decision_detailed(
+    candidate_hops: Vec<types::CandidateHop>,
+    multipath_threshold: f64,
+    weights: types::ForwardingWeights,
+    seed: Option<u64>,
+) -> Result<types::ForwardingDecision, String> {
+    core::calculate_forwarding_decision_seeded(&candidate_hops, multipath_threshold, &weights, seed)
 }
 
 #[rustler::nif]
@@ -45,8 +63,27 @@ fn analyse_dag(
     packet_trails: Vec<Vec<types::Breadcrumb>>,
     sla: types::Sla,
     total_packets_sent: u32,
+    seed: Option<u64>,
 ) -> Result<types::ResolutionReport, String> {
-    core::analyse_dag_impl(packet_trails, sla, total_packets_sent)
+    core::analyse_dag_impl(packet_trails, sla, total_packets_sent, seed)
+}
+
+#[rustler::nif]
+fn analyse_dag_multi(
+    packet_trails: Vec<Vec<types::Breadcrumb>>,
+    slas: Vec<types::Sla>,
+    total_packets_sent: u32,
+) -> Result<Vec<types::ResolutionReport>, String> {
+    core::analyse_dag_multi(packet_trails, slas, total_packets_sent)
+}
+
+#[rustler::nif]
+fn analyse_dag_detailed(
+    packet_trails: Vec<Vec<types::Breadcrumb>>,
+    sla: types::Sla,
+    total_packets_sent: u32,
+) -> Result<types::DetailedResolutionReport, String> {
+    core::analyse_dag_detailed(&packet_trails, &sla, total_packets_sent)
 }
 
 #[rustler::nif]
@@ -57,6 +94,14 @@ pub fn adjust_parameters_for_epoch_nif(
     adjust_parameters_for_epoch(&current_params, &state)
 }
 
+#[rustler::nif]
+pub fn simulate_epochs_nif(
+    initial_params: DynamicParameters,
+    states: Vec<NetworkState>,
+) -> Vec<DynamicParameters> {
+    simulate_epochs(initial_params, &states)
+}
+
 #[rustler::nif]
 pub fn calculate_slash_percentage_nif(
     required_performance: f64,
@@ -80,6 +125,21 @@ pub fn update_trust_score_on_failure_nif(
     core::update_trust_score_on_failure(score, slash_percentage, &params)
 }
 
+#[rustler::nif]
+pub fn update_trust_scores_on_success_batch_nif(
+    updates: Vec<(TrustScore, f64)>,
+) -> Vec<TrustScore> {
+    core::update_trust_scores_on_success(updates)
+}
+
+#[rustler::nif]
+pub fn update_trust_scores_on_failure_batch_nif(
+    updates: Vec<(TrustScore, f64)>,
+    params: DynamicParameters,
+) -> Vec<TrustScore> {
+    core::update_trust_scores_on_failure(updates, &params)
+}
+
 #[rustler::nif]
 pub fn decay_trust_score_continuously_nif(
     score: TrustScore,
@@ -106,6 +166,15 @@ pub fn calculate_consumer_utility_nif(
     core::calculate_consumer_utility(&offering, &trust_score, &consumer)
 }
 
+#[rustler::nif]
+pub fn consumer_utility_gradient_nif(
+    offering: PublishedOffering,
+    trust_score: TrustScore,
+    consumer: ConsumerFactors,
+) -> ConsumerFactorsGradient {
+    core::consumer_utility_gradient(&offering, &trust_score, &consumer)
+}
+
 #[rustler::nif]
 pub fn test_add(a: i64, b: i64) -> i64 {
     a + b
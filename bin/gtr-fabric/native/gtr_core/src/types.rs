@@ -11,6 +11,7 @@
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
 use rustler::NifStruct;
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, NifStruct)]
 #[module = "GtrFabric.CandidateHop"]
@@ -18,6 +19,43 @@ pub struct CandidateHop {
     pub id: String,
     pub potential: f64,
     pub latency: f64,
+    pub trust: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, NifStruct)]
+#[module = "GtrFabric.ForwardingWeights"]
+pub struct ForwardingWeights {
+    pub latency: f64,
+    pub potential: f64,
+    pub trust: f64,
+}
+
+impl Default for ForwardingWeights {
+    fn default() -> Self {
+        Self {
+            latency: 1.0,
+            potential: 1.0,
+            trust: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, NifStruct)]
+#[module = "GtrFabric.HopScore"]
+pub struct HopScore {
+    pub id: String,
+    pub latency_cost: f64,
+    pub potential_cost: f64,
+    pub trust_cost: f64,
+    pub total: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, NifStruct)]
+#[module = "GtrFabric.ForwardingDecision"]
+pub struct ForwardingDecision {
+    pub next_hop: String,
+    pub viable_hops: Vec<String>,
+    pub scores: Vec<HopScore>,
 }
 
 #[derive(Debug, Clone, NifStruct)]
@@ -49,7 +87,7 @@ pub struct Sla {
     pub multipath_threshold: f64,
 }
 
-#[derive(Debug, NifStruct)]
+#[derive(Debug, Clone, PartialEq, NifStruct)]
 #[module = "GtrFabric.ResolutionReport"]
 pub struct ResolutionReport {
     pub sla_met: bool,
@@ -60,6 +98,25 @@ pub struct ResolutionReport {
     pub analysis_summary: String,
 }
 
+#[derive(Debug, Clone, PartialEq, NifStruct)]
+#[module = "GtrFabric.HopAttribution"]
+pub struct HopAttribution {
+    pub packets_forwarded: u32,
+    pub packets_dropped: u32,
+    pub latency_p50_ms: f64,
+    pub latency_p90_ms: f64,
+    pub latency_p99_ms: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, NifStruct)]
+#[module = "GtrFabric.DetailedResolutionReport"]
+pub struct DetailedResolutionReport {
+    pub report: ResolutionReport,
+    pub packets_delivered: u32,
+    pub unattributed_drops: u32,
+    pub hops: HashMap<String, HopAttribution>,
+}
+
 #[derive(Debug, Clone, NifStruct)]
 #[module = "GtrFabric.TrustScore"]
 pub struct TrustScore {
@@ -81,3 +138,12 @@ pub struct ConsumerFactors {
     pub budget: u64,
     pub cost_of_failure: f64,
 }
+
+#[derive(Debug, Clone, PartialEq, NifStruct)]
+#[module = "GtrFabric.ConsumerFactorsGradient"]
+pub struct ConsumerFactorsGradient {
+    pub risk_aversion: f64,
+    pub budget: f64,
+    pub cost_of_failure: f64,
+    pub price_per_call: f64,
+}
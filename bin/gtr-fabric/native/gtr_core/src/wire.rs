@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use crate::types::TrustScore;
+use serde::Deserialize;
+
+pub const TRUST_SCORE_WIRE_VERSION: u8 = 1;
+const TRUST_SCORE_WIRE_LEN: usize = 17;
+
+#[derive(Deserialize)]
+struct LegacyTrustScore {
+    value: f64,
+    last_updated_ts: u64,
+}
+
+impl TrustScore {
+    pub fn to_wire(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(TRUST_SCORE_WIRE_LEN);
+        bytes.push(TRUST_SCORE_WIRE_VERSION);
+        bytes.extend_from_slice(&self.value.to_bits().to_be_bytes());
+        bytes.extend_from_slice(&self.last_updated_ts.to_be_bytes());
+        bytes
+    }
+
+    pub fn from_wire(bytes: &[u8]) -> Result<TrustScore, String> {
+        match bytes.first() {
+            None => Err("Empty trust score payload".to_string()),
+            Some(b'{') => migrate_legacy_json(bytes),
+            Some(&TRUST_SCORE_WIRE_VERSION) => decode_v1(bytes),
+            Some(version) => Err(format!("Unsupported trust score wire version: {version}")),
+        }
+    }
+}
+
+fn decode_v1(bytes: &[u8]) -> Result<TrustScore, String> {
+    if bytes.len() != TRUST_SCORE_WIRE_LEN {
+        return Err(format!(
+            "Invalid trust score payload length: {}. Expected {TRUST_SCORE_WIRE_LEN}",
+            bytes.len()
+        ));
+    }
+    let mut value = [0u8; 8];
+    let mut last_updated_ts = [0u8; 8];
+    value.copy_from_slice(&bytes[1..9]);
+    last_updated_ts.copy_from_slice(&bytes[9..17]);
+    Ok(TrustScore {
+        value: f64::from_bits(u64::from_be_bytes(value)),
+        last_updated_ts: u64::from_be_bytes(last_updated_ts),
+    })
+}
+
+fn migrate_legacy_json(bytes: &[u8]) -> Result<TrustScore, String> {
+    let legacy: LegacyTrustScore = serde_json::from_slice(bytes)
+        .map_err(|e| format!("Invalid legacy trust score payload: {e}"))?;
+    Ok(TrustScore {
+        value: legacy.value,
+        last_updated_ts: legacy.last_updated_ts,
+    })
+}
@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::{sla, trail};
+use gtr_core::core::{analyse_dag_detailed, analyse_dag_with_trails};
+use gtr_core::types::Breadcrumb;
+
+fn trails() -> Vec<Vec<Breadcrumb>> {
+    vec![
+        trail(&[("a", 0), ("b", 10), ("d", 40)]),
+        trail(&[("a", 100), ("c", 130), ("d", 150)]),
+        trail(&[("a", 200), ("b", 220), ("d", 250)]),
+        trail(&[("a", 300), ("b", 305)]),
+        trail(&[("a", 400)]),
+        trail(&[("a", 500), ("c", 520), ("d", 490)]),
+    ]
+}
+
+#[test]
+fn per_hop_drops_add_up_to_undelivered_packets() {
+    let trails = trails();
+    let total_packets_sent = trails.len() as u32;
+    let detailed = analyse_dag_detailed(&trails, &sla(200, 50, 50.0), total_packets_sent).unwrap();
+
+    assert_eq!(detailed.packets_delivered, 4);
+    assert_eq!(detailed.unattributed_drops, 0);
+    let dropped: u32 = detailed.hops.values().map(|hop| hop.packets_dropped).sum();
+    assert_eq!(dropped, total_packets_sent - detailed.packets_delivered);
+
+    assert_eq!(detailed.hops["a"].packets_dropped, 1);
+    assert_eq!(detailed.hops["d"].packets_dropped, 1);
+    assert_eq!(detailed.hops["b"].packets_dropped, 0);
+}
+
+#[test]
+fn packets_without_trails_are_unattributed() {
+    let trails = trails();
+    let detailed = analyse_dag_detailed(&trails, &sla(200, 50, 50.0), 10).unwrap();
+
+    let dropped: u32 = detailed.hops.values().map(|hop| hop.packets_dropped).sum();
+    assert_eq!(detailed.unattributed_drops, 4);
+    assert_eq!(dropped + detailed.unattributed_drops, 10 - detailed.packets_delivered);
+}
+
+#[test]
+fn forwarding_counts_and_latency_percentiles() {
+    let detailed = analyse_dag_detailed(&trails(), &sla(200, 50, 50.0), 6).unwrap();
+
+    let a = &detailed.hops["a"];
+    assert_eq!(a.packets_forwarded, 5);
+    assert_eq!(a.latency_p50_ms, 20.0);
+    assert_eq!(a.latency_p90_ms, 30.0);
+    assert_eq!(a.latency_p99_ms, 30.0);
+
+    let b = &detailed.hops["b"];
+    assert_eq!(b.packets_forwarded, 2);
+    assert_eq!(b.latency_p50_ms, 30.0);
+
+    let d = &detailed.hops["d"];
+    assert_eq!(d.packets_forwarded, 0);
+    assert_eq!(d.latency_p50_ms, -1.0);
+}
+
+#[test]
+fn detailed_report_wraps_the_plain_report() {
+    let trails = trails();
+    let detailed = analyse_dag_detailed(&trails, &sla(200, 50, 50.0), 6).unwrap();
+    assert_eq!(
+        detailed.report,
+        analyse_dag_with_trails(&trails, &sla(200, 50, 50.0), 6).unwrap()
+    );
+    assert!(analyse_dag_detailed(&trails, &sla(200, 50, 50.0), 0).is_err());
+}
@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::{numbered_trail, sla};
+use gtr_core::core::{analyse_dag_multi, analyse_dag_with_trails, DagMeasurement};
+use gtr_core::types::{Breadcrumb, Sla};
+
+fn candidate_slas() -> Vec<Sla> {
+    vec![
+        sla(200, 50, 50.0),
+        sla(100, 50, 50.0),
+        sla(200, 5, 50.0),
+        sla(200, 50, 30.0),
+    ]
+}
+
+fn trails() -> Vec<Vec<Breadcrumb>> {
+    vec![
+        numbered_trail(&[0, 40, 120]),
+        numbered_trail(&[1_000, 1_150]),
+        numbered_trail(&[5_000, 5_060, 5_090, 5_130]),
+        numbered_trail(&[9_000]),
+    ]
+}
+
+#[test]
+fn multi_matches_individual_analyses() {
+    let trails = trails();
+    let slas = candidate_slas();
+
+    let individual: Vec<_> = slas
+        .iter()
+        .map(|sla| analyse_dag_with_trails(&trails, sla, 5).unwrap())
+        .collect();
+    let multi = analyse_dag_multi(trails, slas, 5).unwrap();
+
+    assert_eq!(multi, individual);
+    let met: Vec<bool> = multi.iter().map(|report| report.sla_met).collect();
+    assert_eq!(met, vec![true, false, false, false]);
+}
+
+#[test]
+fn a_single_measurement_serves_every_sla() {
+    let trails = trails();
+    let measurement = DagMeasurement::from_trails(&trails, 5).unwrap();
+    let DagMeasurement::Measured {
+        avg_latency_ms,
+        loss_percentage,
+        ..
+    } = measurement
+    else {
+        panic!("expected a measurable DAG, got {measurement:?}");
+    };
+    assert!((avg_latency_ms - 133.333_333).abs() < 1e-3);
+    assert!((loss_percentage - 40.0).abs() < 1e-9);
+
+    let from_measurement: Vec<_> = candidate_slas()
+        .iter()
+        .map(|sla| measurement.report(sla))
+        .collect();
+    assert_eq!(
+        analyse_dag_multi(trails, candidate_slas(), 5).unwrap(),
+        from_measurement
+    );
+}
+
+#[test]
+fn unmeasurable_trails_fail_every_sla() {
+    let reports = analyse_dag_multi(vec![numbered_trail(&[10])], candidate_slas(), 3).unwrap();
+    assert_eq!(reports.len(), 4);
+    assert!(reports.iter().all(|report| !report.sla_met));
+    assert!(reports.iter().all(|report| report.loss_percentage == 100.0));
+}
+
+#[test]
+fn invalid_input_is_rejected_once() {
+    assert!(analyse_dag_multi(trails(), candidate_slas(), 0).is_err());
+    assert_eq!(analyse_dag_multi(trails(), Vec::new(), 5).unwrap(), Vec::new());
+}
@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+#![allow(dead_code)]
+
+use gtr_core::types::{Breadcrumb, Sla};
+use gtr_core::CandidateHop;
+
+pub fn hop(id: &str, latency: f64, potential: f64, trust: f64) -> CandidateHop {
+    CandidateHop {
+        id: id.to_string(),
+        potential,
+        latency,
+        trust,
+    }
+}
+
+pub fn trail(hops: &[(&str, u64)]) -> Vec<Breadcrumb> {
+    hops.iter()
+        .map(|&(node_id, timestamp_ms)| Breadcrumb {
+            node_id: node_id.to_string(),
+            timestamp_ms,
+        })
+        .collect()
+}
+
+pub fn numbered_trail(timestamps: &[u64]) -> Vec<Breadcrumb> {
+    timestamps
+        .iter()
+        .enumerate()
+        .map(|(i, &timestamp_ms)| Breadcrumb {
+            node_id: format!("node-{i}"),
+            timestamp_ms,
+        })
+        .collect()
+}
+
+pub fn sla(e2e_latency_ms: u32, jitter_ms: u32, loss_percentage: f32) -> Sla {
+    Sla {
+        e2e_latency_ms,
+        jitter_ms,
+        loss_percentage,
+        weight_latency: 0.5,
+        weight_throughput: 0.2,
+        weight_trust: 0.3,
+        multipath_threshold: 1.05,
+    }
+}
@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use gtr_core::core::{calculate_consumer_utility, consumer_utility_gradient};
+use gtr_core::{ConsumerFactors, PublishedOffering, TrustScore};
+
+fn offering(price_per_call: u64) -> PublishedOffering {
+    PublishedOffering {
+        staked_collateral: 500,
+        price_per_call,
+    }
+}
+
+fn trust(value: f64) -> TrustScore {
+    TrustScore {
+        value,
+        last_updated_ts: 1_700_000_000,
+    }
+}
+
+fn consumer() -> ConsumerFactors {
+    ConsumerFactors {
+        risk_aversion: 0.4,
+        budget: 1_000,
+        cost_of_failure: 250.0,
+    }
+}
+
+#[test]
+fn utility_falls_as_price_rises() {
+    for price in [0, 1, 10, 100, 10_000] {
+        for value in [0.0, 0.5, 0.95] {
+            let gradient = consumer_utility_gradient(&offering(price), &trust(value), &consumer());
+            assert!(gradient.price_per_call < 0.0, "price {price}, trust {value}");
+        }
+    }
+}
+
+#[test]
+fn price_partial_agrees_with_neighbouring_evaluations() {
+    let gradient = consumer_utility_gradient(&offering(100), &trust(0.6), &consumer());
+    let below = calculate_consumer_utility(&offering(99), &trust(0.6), &consumer());
+    let above = calculate_consumer_utility(&offering(101), &trust(0.6), &consumer());
+    assert_eq!(gradient.price_per_call, (above - below) / 2.0);
+}
+
+#[test]
+fn cost_of_failure_matters_only_while_trust_is_imperfect() {
+    let uncertain = consumer_utility_gradient(&offering(100), &trust(0.6), &consumer());
+    assert!(uncertain.cost_of_failure < 0.0);
+
+    let certain = consumer_utility_gradient(&offering(100), &trust(1.0), &consumer());
+    assert!(certain.cost_of_failure.abs() < 1e-9);
+}
+
+#[test]
+fn unused_factors_have_zero_slope() {
+    let gradient = consumer_utility_gradient(&offering(100), &trust(0.6), &consumer());
+    assert_eq!(gradient.risk_aversion, 0.0);
+    assert_eq!(gradient.budget, 0.0);
+}
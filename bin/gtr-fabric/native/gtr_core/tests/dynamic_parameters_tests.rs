@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use gtr_core::{
+    adjust_parameters_for_epoch, simulate_epochs, DynamicParameters, NetworkState, ParamError,
+    SlashCurve,
+};
+
+fn rising_load(epochs: usize) -> Vec<NetworkState> {
+    (0..epochs)
+        .map(|i| {
+            let progress = i as f64 / epochs as f64;
+            NetworkState {
+                network_failure_rate: 0.06 + 0.2 * progress,
+                supply_demand_ratio: 1.1 - 0.6 * progress,
+                avg_network_trust: 0.8 - 0.3 * progress,
+            }
+        })
+        .collect()
+}
+
+fn non_decreasing(values: impl Iterator<Item = f64>) -> bool {
+    let values: Vec<f64> = values.collect();
+    values.windows(2).all(|w| w[1] >= w[0])
+}
+
+fn non_increasing(values: impl Iterator<Item = f64>) -> bool {
+    let values: Vec<f64> = values.collect();
+    values.windows(2).all(|w| w[1] <= w[0])
+}
+
+#[test]
+fn rising_load_tightens_parameters_monotonically() {
+    let initial = DynamicParameters::default();
+    let trajectory = simulate_epochs(initial.clone(), &rising_load(40));
+    assert_eq!(trajectory.len(), 40);
+
+    let with_initial = || std::iter::once(&initial).chain(&trajectory);
+    assert!(non_decreasing(with_initial().map(|p| p.steepness)));
+    assert!(non_decreasing(with_initial().map(|p| p.failure_weight)));
+    assert!(non_decreasing(with_initial().map(|p| p.bonus_multiplier)));
+    assert!(non_increasing(with_initial().map(|p| p.collateral_multiplier)));
+    assert!(non_increasing(with_initial().map(|p| p.decay_lambda_per_day)));
+
+    let last = trajectory.last().unwrap();
+    assert!(last.steepness > initial.steepness);
+    assert!(last.failure_weight > initial.failure_weight);
+}
+
+#[test]
+fn simulated_parameters_stay_within_bounds() {
+    for params in simulate_epochs(DynamicParameters::default(), &rising_load(500)) {
+        assert!((3.0..=15.0).contains(&params.steepness));
+        assert!((0.1..=0.75).contains(&params.failure_weight));
+        assert!((0.5..=5.0).contains(&params.collateral_multiplier));
+        assert!((0.1..=1.0).contains(&params.bonus_multiplier));
+        assert!((0.005..=0.05).contains(&params.decay_lambda_per_day));
+    }
+}
+
+#[test]
+fn each_epoch_matches_a_single_adjustment() {
+    let states = rising_load(5);
+    let trajectory = simulate_epochs(DynamicParameters::default(), &states);
+    let mut expected = DynamicParameters::default();
+    for (state, actual) in states.iter().zip(&trajectory) {
+        expected = adjust_parameters_for_epoch(&expected, state);
+        assert_eq!(actual.steepness, expected.steepness);
+        assert_eq!(actual.collateral_multiplier, expected.collateral_multiplier);
+    }
+    assert!(simulate_epochs(DynamicParameters::default(), &[]).is_empty());
+}
+
+fn extreme_states() -> Vec<NetworkState> {
+    [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, f64::MAX, -1e300, 0.0]
+        .iter()
+        .map(|&extreme| NetworkState {
+            network_failure_rate: extreme,
+            supply_demand_ratio: -extreme,
+            avg_network_trust: extreme,
+        })
+        .collect()
+}
+
+fn pathological_params() -> DynamicParameters {
+    DynamicParameters {
+        steepness: f64::MAX,
+        centre: f64::NAN,
+        failure_weight: -3.0,
+        decay_lambda_per_day: f64::INFINITY,
+        collateral_multiplier: 0.0,
+        bonus_multiplier: f64::NEG_INFINITY,
+        slash_curve: SlashCurve::Stepwise {
+            threshold: -1.0,
+            penalty: 7.5,
+        },
+    }
+}
+
+#[test]
+fn extreme_network_state_keeps_parameters_in_bounds() {
+    for state in extreme_states() {
+        for current in [DynamicParameters::default(), pathological_params()] {
+            let next = adjust_parameters_for_epoch(&current, &state);
+            assert_eq!(next.validate(), Ok(()));
+            assert!((3.0..=15.0).contains(&next.steepness));
+            assert!((0.0..=1.0).contains(&next.centre));
+            assert!((0.1..=0.75).contains(&next.failure_weight));
+            assert!((0.005..=0.05).contains(&next.decay_lambda_per_day));
+            assert!((0.5..=5.0).contains(&next.collateral_multiplier));
+            assert!((0.1..=1.0).contains(&next.bonus_multiplier));
+            if let SlashCurve::Stepwise { threshold, penalty } = next.slash_curve {
+                assert!((0.0..=1.0).contains(&threshold));
+                assert!((0.0..=1.0).contains(&penalty));
+            }
+        }
+    }
+}
+
+#[test]
+fn validate_reports_the_first_out_of_bounds_field() {
+    assert_eq!(DynamicParameters::default().validate(), Ok(()));
+
+    let params = DynamicParameters {
+        failure_weight: 2.0,
+        ..DynamicParameters::default()
+    };
+    assert_eq!(
+        params.validate(),
+        Err(ParamError::OutOfBounds {
+            field: "failure_weight",
+            value: 2.0,
+            min: 0.1,
+            max: 0.75,
+        })
+    );
+
+    let err = pathological_params().validate().unwrap_err();
+    assert!(matches!(err, ParamError::OutOfBounds { field: "steepness", .. }));
+}
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::hop;
+use gtr_core::core::{
+    calculate_forwarding_decision_detailed, calculate_forwarding_decision_with_config,
+};
+use gtr_core::{CandidateHop, ForwardingWeights};
+
+fn candidates() -> Vec<CandidateHop> {
+    vec![
+        hop("fast_untrusted", 10.0, 5.0, 0.1),
+        hop("slow_trusted", 30.0, 5.0, 0.95),
+    ]
+}
+
+#[test]
+fn shifting_weight_to_trust_changes_the_chosen_hop() {
+    let latency_weighted = ForwardingWeights {
+        latency: 1.0,
+        potential: 1.0,
+        trust: 0.0,
+    };
+    let trust_weighted = ForwardingWeights {
+        latency: 0.1,
+        potential: 1.0,
+        trust: 100.0,
+    };
+
+    let by_latency =
+        calculate_forwarding_decision_detailed(&candidates(), 1.0, &latency_weighted).unwrap();
+    let by_trust =
+        calculate_forwarding_decision_detailed(&candidates(), 1.0, &trust_weighted).unwrap();
+
+    assert_eq!(by_latency.next_hop, "fast_untrusted");
+    assert_eq!(by_trust.next_hop, "slow_trusted");
+    assert_eq!(by_trust.viable_hops, vec!["slow_trusted".to_string()]);
+}
+
+#[test]
+fn detailed_scores_break_down_each_weighted_component() {
+    let weights = ForwardingWeights {
+        latency: 2.0,
+        potential: 0.5,
+        trust: 10.0,
+    };
+    let hops = candidates();
+    let decision = calculate_forwarding_decision_detailed(&hops, 1.0, &weights).unwrap();
+
+    assert_eq!(decision.scores.len(), 2);
+    assert!(decision.scores.windows(2).all(|w| w[0].total <= w[1].total));
+
+    let fast = decision
+        .scores
+        .iter()
+        .find(|score| score.id == "fast_untrusted")
+        .unwrap();
+    assert!((fast.latency_cost - 20.0).abs() < 1e-9);
+    assert!((fast.potential_cost - 2.5).abs() < 1e-9);
+    assert!((fast.trust_cost - 9.0).abs() < 1e-9);
+    assert!((fast.total - 31.5).abs() < 1e-9);
+}
+
+#[test]
+fn default_weights_match_the_unweighted_decision() {
+    let hops = candidates();
+    let weights = ForwardingWeights::default();
+    let detailed = calculate_forwarding_decision_detailed(&hops, 1.0, &weights).unwrap();
+
+    assert_eq!(
+        detailed.next_hop,
+        calculate_forwarding_decision_with_config(&hops, 1.0).unwrap()
+    );
+    assert!(detailed.scores.iter().all(|score| score.trust_cost == 0.0));
+}
+
+#[test]
+fn invalid_weights_and_trust_are_rejected() {
+    let negative = ForwardingWeights {
+        trust: -1.0,
+        ..ForwardingWeights::default()
+    };
+    let hops = candidates();
+    let err = calculate_forwarding_decision_detailed(&hops, 1.05, &negative).unwrap_err();
+    assert!(err.contains("trust weight"));
+
+    let untrusted = vec![hop("bad", 1.0, 1.0, 1.5)];
+    let weights = ForwardingWeights::default();
+    assert!(calculate_forwarding_decision_detailed(&untrusted, 1.05, &weights).is_err());
+}
+
+#[test]
+fn no_candidates_loops_back() {
+    let weights = ForwardingWeights::default();
+    let decision = calculate_forwarding_decision_detailed(&[], 1.05, &weights).unwrap();
+    assert_eq!(decision.next_hop, "loop");
+    assert!(decision.viable_hops.is_empty());
+    assert!(decision.scores.is_empty());
+}
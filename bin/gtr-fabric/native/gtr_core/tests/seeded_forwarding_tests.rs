@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::{hop, numbered_trail, sla};
+use gtr_core::core::{
+    analyse_dag_impl, calculate_forwarding_decision_impl, calculate_forwarding_decision_seeded,
+};
+use gtr_core::types::Breadcrumb;
+use gtr_core::{CandidateHop, ForwardingWeights};
+use std::collections::HashSet;
+
+fn close_hops() -> Vec<CandidateHop> {
+    vec![
+        hop("a", 10.0, 1.0, 0.9),
+        hop("b", 10.5, 1.0, 0.9),
+        hop("c", 11.0, 1.0, 0.9),
+        hop("d", 11.5, 1.0, 0.9),
+    ]
+}
+
+fn trails() -> Vec<Vec<Breadcrumb>> {
+    vec![
+        numbered_trail(&[0, 40, 120]),
+        numbered_trail(&[1_000, 1_090, 1_150]),
+        numbered_trail(&[5_000, 5_060, 5_130]),
+    ]
+}
+
+#[test]
+fn same_seed_picks_the_same_hop() {
+    for seed in 0..32 {
+        let first = calculate_forwarding_decision_impl(close_hops(), 1.5, Some(seed)).unwrap();
+        let second = calculate_forwarding_decision_impl(close_hops(), 1.5, Some(seed)).unwrap();
+        assert_eq!(first, second);
+    }
+}
+
+#[test]
+fn different_seeds_still_spread_across_viable_hops() {
+    let chosen: HashSet<String> = (0..64)
+        .map(|seed| calculate_forwarding_decision_impl(close_hops(), 1.5, Some(seed)).unwrap())
+        .collect();
+    assert!(chosen.len() > 1);
+}
+
+#[test]
+fn seeded_detailed_decisions_are_identical() {
+    let weights = ForwardingWeights::default();
+    let first = calculate_forwarding_decision_seeded(&close_hops(), 1.5, &weights, Some(7));
+    let second = calculate_forwarding_decision_seeded(&close_hops(), 1.5, &weights, Some(7));
+    assert_eq!(first.unwrap(), second.unwrap());
+}
+
+#[test]
+fn analyse_dag_reports_are_identical_for_the_same_seed() {
+    let first = analyse_dag_impl(trails(), sla(200, 50, 50.0), 4, Some(7)).unwrap();
+    let second = analyse_dag_impl(trails(), sla(200, 50, 50.0), 4, Some(7)).unwrap();
+    assert_eq!(first, second);
+    assert_eq!(first.avg_latency_ms.to_bits(), second.avg_latency_ms.to_bits());
+}
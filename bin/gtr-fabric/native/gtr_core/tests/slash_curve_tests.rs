@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use gtr_core::core::calculate_slash_percentage;
+use gtr_core::{DynamicParameters, SlashCurve};
+
+fn with_curve(slash_curve: SlashCurve) -> DynamicParameters {
+    DynamicParameters {
+        slash_curve,
+        ..DynamicParameters::default()
+    }
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-9,
+        "expected {expected}, got {actual}"
+    );
+}
+
+#[test]
+fn default_curve_is_logistic() {
+    let params = DynamicParameters::default();
+    assert_eq!(params.slash_curve, SlashCurve::Logistic);
+
+    let expected = 100.0 / (1.0 + (-5.0f64 * (0.4 - 0.3)).exp());
+    assert_close(calculate_slash_percentage(1.0, 0.6, &params), expected);
+}
+
+#[test]
+fn each_curve_produces_documented_slash() {
+    let stepwise = SlashCurve::Stepwise {
+        threshold: 0.3,
+        penalty: 0.5,
+    };
+
+    let slash = |curve, actual| calculate_slash_percentage(1.0, actual, &with_curve(curve));
+
+    assert_close(slash(SlashCurve::Linear, 0.6), 40.0);
+    assert_close(slash(SlashCurve::Quadratic, 0.6), 16.0);
+    assert_close(slash(stepwise, 0.6), 50.0);
+    assert_close(slash(stepwise, 0.8), 0.0);
+}
+
+#[test]
+fn meeting_the_requirement_is_never_slashed() {
+    for curve in [SlashCurve::Logistic, SlashCurve::Linear, SlashCurve::Quadratic] {
+        assert_eq!(calculate_slash_percentage(0.9, 0.95, &with_curve(curve)), 0.0);
+        assert_eq!(calculate_slash_percentage(0.9, 0.9, &with_curve(curve)), 0.0);
+    }
+}
+
+#[test]
+fn extreme_inputs_stay_in_range() {
+    let curves = [
+        SlashCurve::Logistic,
+        SlashCurve::Linear,
+        SlashCurve::Quadratic,
+        SlashCurve::Stepwise {
+            threshold: 0.0,
+            penalty: 7.5,
+        },
+        SlashCurve::Stepwise {
+            threshold: 0.5,
+            penalty: -2.0,
+        },
+    ];
+    let inputs = [
+        (1.0, -1e12),
+        (0.0, -1.0),
+        (-1.0, -5.0),
+        (f64::MAX, f64::MIN),
+        (f64::INFINITY, 0.0),
+        (1.0, f64::NEG_INFINITY),
+        (f64::NAN, 0.5),
+        (1.0, f64::NAN),
+    ];
+
+    for curve in curves {
+        let params = with_curve(curve);
+        for (required, actual) in inputs {
+            let slash = calculate_slash_percentage(required, actual, &params);
+            assert!(
+                (0.0..=100.0).contains(&slash),
+                "{curve:?} gave {slash} for required={required}, actual={actual}"
+            );
+        }
+    }
+}
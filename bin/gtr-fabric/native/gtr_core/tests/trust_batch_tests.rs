@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use gtr_core::core::{
+    update_trust_score_on_failure, update_trust_score_on_success,
+    update_trust_scores_on_failure, update_trust_scores_on_success,
+};
+use gtr_core::{DynamicParameters, TrustScore};
+
+fn updates() -> Vec<(TrustScore, f64)> {
+    (0..50_u32)
+        .map(|i| {
+            let score = TrustScore {
+                value: f64::from(i) / 50.0,
+                last_updated_ts: 1_700_000_000 + u64::from(i),
+            };
+            (score, 0.05 + f64::from(i % 7) * 0.1)
+        })
+        .collect()
+}
+
+fn assert_same(batch: &[TrustScore], single: &[TrustScore]) {
+    assert_eq!(batch.len(), single.len());
+    for (b, s) in batch.iter().zip(single) {
+        assert_eq!(b.value.to_bits(), s.value.to_bits());
+        assert!(b.last_updated_ts.abs_diff(s.last_updated_ts) <= 1);
+    }
+}
+
+#[test]
+fn success_batch_matches_repeated_single_calls() {
+    let single: Vec<TrustScore> = updates()
+        .into_iter()
+        .map(|(score, weight)| update_trust_score_on_success(score, weight))
+        .collect();
+    let batch = update_trust_scores_on_success(updates());
+    assert_same(&batch, &single);
+}
+
+#[test]
+fn failure_batch_matches_repeated_single_calls() {
+    let params = DynamicParameters::default();
+    let slashed: Vec<(TrustScore, f64)> = updates()
+        .into_iter()
+        .map(|(score, weight)| (score, weight * 100.0))
+        .collect();
+    let single: Vec<TrustScore> = slashed
+        .iter()
+        .cloned()
+        .map(|(score, slash)| update_trust_score_on_failure(score, slash, &params))
+        .collect();
+    let batch = update_trust_scores_on_failure(slashed, &params);
+    assert_same(&batch, &single);
+}
+
+#[test]
+fn empty_batch_is_empty() {
+    assert!(update_trust_scores_on_success(Vec::new()).is_empty());
+    assert!(update_trust_scores_on_failure(Vec::new(), &DynamicParameters::default()).is_empty());
+}
@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use gtr_core::wire::TRUST_SCORE_WIRE_VERSION;
+use gtr_core::TrustScore;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn wire_round_trip_is_lossless(bits in any::<u64>(), ts in any::<u64>()) {
+        let score = TrustScore { value: f64::from_bits(bits), last_updated_ts: ts };
+        let wire = score.to_wire();
+        prop_assert_eq!(wire[0], TRUST_SCORE_WIRE_VERSION);
+        prop_assert_eq!(&wire, &score.to_wire());
+
+        let decoded = TrustScore::from_wire(&wire).unwrap();
+        prop_assert_eq!(decoded.value.to_bits(), bits);
+        prop_assert_eq!(decoded.last_updated_ts, ts);
+    }
+
+    #[test]
+    fn legacy_json_payload_migrates(value in 0.0f64..=1.0, ts in any::<u64>()) {
+        let legacy = format!(r#"{{"value":{value:?},"last_updated_ts":{ts}}}"#);
+        let migrated = TrustScore::from_wire(legacy.as_bytes()).unwrap();
+        prop_assert!((migrated.value - value).abs() <= f64::EPSILON);
+        prop_assert_eq!(migrated.last_updated_ts, ts);
+
+        let upgraded = TrustScore::from_wire(&migrated.to_wire()).unwrap();
+        prop_assert_eq!(upgraded.value.to_bits(), migrated.value.to_bits());
+    }
+}
+
+#[test]
+fn malformed_payloads_are_rejected() {
+    assert!(TrustScore::from_wire(&[]).is_err());
+    assert!(TrustScore::from_wire(&[TRUST_SCORE_WIRE_VERSION, 0, 1]).is_err());
+
+    let mut future = TrustScore { value: 0.5, last_updated_ts: 1 }.to_wire();
+    future[0] = TRUST_SCORE_WIRE_VERSION + 1;
+    let err = TrustScore::from_wire(&future).unwrap_err();
+    assert!(err.contains("Unsupported trust score wire version"));
+
+    assert!(TrustScore::from_wire(br#"{"value":"high"}"#).is_err());
+}
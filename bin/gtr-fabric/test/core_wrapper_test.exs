@@ -15,4 +15,16 @@ defmodule CoreWrapperTest do
     assert tag in [:ok, :error]
     if @enable, do: assert(tag == :ok), else: assert(val == :nif_disabled)
   end
+
+  test "batch trust updates return the NIF error instead of the inputs" do
+    trust = %{__struct__: GtrFabric.TrustScore, value: 0.85, last_updated_ts: 0}
+    result = GtrFabric.Reputation.update_trust_scores_on_success([{trust, 0.1}])
+
+    if @enable do
+      assert {:ok, [%{value: value}]} = result
+      assert value > 0.85
+    else
+      assert result == {:error, :nif_disabled}
+    end
+  end
 end
@@ -10,11 +10,11 @@ if System.get_env("ENABLE_NIFS") == "1" do
     defp trust(v), do: %{__struct__: GtrFabric.TrustScore, value: v, last_updated_ts: 0}
     defp offering(coll, price), do: %{__struct__: GtrFabric.PublishedOffering, staked_collateral: coll, price_per_call: price}
     defp consumer(risk, budget, cof), do: %{__struct__: GtrFabric.ConsumerFactors, risk_aversion: risk, budget: budget, cost_of_failure: cof}
-    defp params(), do: %{__struct__: GtrFabric.DynamicParameters, steepness: 5.0, centre: 0.3, failure_weight: 0.2, decay_lambda_per_day: 0.01, collateral_multiplier: 2.0, bonus_multiplier: 0.25}
+    defp params(), do: %{__struct__: GtrFabric.DynamicParameters, steepness: 5.0, centre: 0.3, failure_weight: 0.2, decay_lambda_per_day: 0.01, collateral_multiplier: 2.0, bonus_multiplier: 0.25, slash_curve: :logistic}
     defp net_state(), do: %{__struct__: GtrFabric.NetworkState, network_failure_rate: 0.02, supply_demand_ratio: 1.1, avg_network_trust: 0.7}
     defp sla(), do: %{__struct__: GtrFabric.SLA, e2e_latency_ms: 120, jitter_ms: 10, loss_percentage: 0.5, weight_latency: 0.5, weight_throughput: 0.2, weight_trust: 0.3, multipath_threshold: 0.4}
     defp node_metrics(), do: %{__struct__: GtrFabric.NodeMetrics, trust_score: 0.8, available_throughput: 500.0, predicted_latency_to_target: 80.0}
-    defp hop(id, pot, lat), do: %{__struct__: GtrFabric.CandidateHop, id: id, potential: pot, latency: lat}
+    defp hop(id, pot, lat), do: %{__struct__: GtrFabric.CandidateHop, id: id, potential: pot, latency: lat, trust: 1.0}
     defp breadcrumb(id, ts), do: %{__struct__: GtrFabric.Breadcrumb, node_id: id, timestamp_ms: ts}
 
     test "calculate_consumer_utility_nif" do
@@ -55,7 +55,7 @@ if System.get_env("ENABLE_NIFS") == "1" do
       {:ok, decision} = GtrFabric.CoreNifs.calculate_forwarding_decision([
         hop("a", 0.9, 50.0),
         hop("b", 0.85, 40.0)
-      ], 0.01)
+      ], 0.01, nil)
       assert is_binary(decision)
     end
 
@@ -64,7 +64,7 @@ if System.get_env("ENABLE_NIFS") == "1" do
         [breadcrumb("a", 1), breadcrumb("b", 2)],
         [breadcrumb("a", 3), breadcrumb("c", 4)]
       ]
-      {:ok, report} = GtrFabric.CoreNifs.analyse_dag(trails, sla(), 10)
+      {:ok, report} = GtrFabric.CoreNifs.analyse_dag(trails, sla(), 10, nil)
       assert is_boolean(report.sla_met) and is_number(report.avg_latency_ms)
     end
 
@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+use crate::chart_matcher::RenderSpec;
+use serde::{Deserialize, Serialize};
+
+pub const PLOTLY_DEFAULT_PALETTE: [&str; 10] = [
+    "#636EFA", "#EF553B", "#00CC96", "#AB63FA", "#FFA15A", "#19D3F3", "#FF6692", "#B6E880",
+    "#FF97FF", "#FECB52",
+];
+
+pub const COLOUR_BLIND_SAFE_PALETTE: [&str; 8] = [
+    "#000000", "#E69F00", "#56B4E9", "#009E73", "#F0E442", "#0072B2", "#D55E00", "#CC79A7",
+];
+
+const MIN_GRAPHICAL_CONTRAST: f64 = 3.0;
+const COLOUR_ARGS: [&str; 2] = ["color", "colour"];
+const REDUNDANT_ENCODING_ARGS: [&str; 6] = [
+    "symbol",
+    "line_dash",
+    "pattern_shape",
+    "text",
+    "facet_row",
+    "facet_col",
+];
+const PALETTE_ARGS: [&str; 2] = ["color_discrete_sequence", "color_discrete_map"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AccessibilityIssueKind {
+    MissingAxisLabel,
+    ColourOnlyEncoding,
+    LowContrastPalette,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessibilityIssue {
+    pub kind: AccessibilityIssueKind,
+    pub message: String,
+    pub remediation: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccessibilityReport {
+    pub chart_name: String,
+    pub issues: Vec<AccessibilityIssue>,
+}
+
+impl AccessibilityReport {
+    pub fn is_accessible(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn has_issue(&self, kind: AccessibilityIssueKind) -> bool {
+        self.issues.iter().any(|i| i.kind == kind)
+    }
+
+    pub fn remediations(&self) -> Vec<&str> {
+        self.issues.iter().map(|i| i.remediation.as_str()).collect()
+    }
+}
+
+impl RenderSpec {
+    pub fn accessibility_report(&self) -> AccessibilityReport {
+        let mut issues = Vec::new();
+        self.check_axis_labels(&mut issues);
+        if let Some(colour_col) = self.colour_mapping() {
+            self.check_colour_only_encoding(colour_col, &mut issues);
+            self.check_default_palette_contrast(&mut issues);
+        }
+        AccessibilityReport {
+            chart_name: self.chart_name.clone(),
+            issues,
+        }
+    }
+
+    fn colour_mapping(&self) -> Option<&str> {
+        COLOUR_ARGS
+            .iter()
+            .find_map(|arg| self.mappings.get(*arg))
+            .map(String::as_str)
+    }
+
+    fn check_axis_labels(&self, issues: &mut Vec<AccessibilityIssue>) {
+        for axis in ["x", "y"] {
+            let Some(column) = self.mappings.get(axis) else {
+                continue;
+            };
+            if !is_descriptive_label(column) {
+                issues.push(AccessibilityIssue {
+                    kind: AccessibilityIssueKind::MissingAxisLabel,
+                    message: format!(
+                        "{} axis of '{}' is labelled by column '{}', which does not describe the data",
+                        axis, self.chart_name, column
+                    ),
+                    remediation: format!(
+                        "Provide a descriptive {axis}-axis title (e.g. via `labels`) including units where relevant"
+                    ),
+                });
+            }
+        }
+    }
+
+    fn check_colour_only_encoding(&self, colour_col: &str, issues: &mut Vec<AccessibilityIssue>) {
+        let has_redundant = REDUNDANT_ENCODING_ARGS
+            .iter()
+            .any(|arg| self.mappings.contains_key(*arg));
+        if has_redundant {
+            return;
+        }
+        let suggestion = match self.chart_name.as_str() {
+            "scatter" | "scatter_polar" | "scatter_matrix" | "scatter_geo" => "symbol",
+            "line" | "line_polar" => "line_dash",
+            "bar" | "histogram" => "pattern_shape",
+            _ => "facet_col",
+        };
+        issues.push(AccessibilityIssue {
+            kind: AccessibilityIssueKind::ColourOnlyEncoding,
+            message: format!(
+                "Series in '{}' are distinguished by colour alone (column '{}')",
+                self.chart_name, colour_col
+            ),
+            remediation: format!(
+                "Map '{colour_col}' to `{suggestion}` as well so series remain distinguishable without colour"
+            ),
+        });
+    }
+
+    fn check_default_palette_contrast(&self, issues: &mut Vec<AccessibilityIssue>) {
+        if PALETTE_ARGS
+            .iter()
+            .any(|arg| self.mappings.contains_key(*arg))
+        {
+            return;
+        }
+        let low: Vec<&str> = PLOTLY_DEFAULT_PALETTE
+            .iter()
+            .copied()
+            .filter(|hex| {
+                contrast_ratio(hex, "#FFFFFF").is_some_and(|r| r < MIN_GRAPHICAL_CONTRAST)
+            })
+            .collect();
+        if low.is_empty() {
+            return;
+        }
+        issues.push(AccessibilityIssue {
+            kind: AccessibilityIssueKind::LowContrastPalette,
+            message: format!(
+                "Default palette includes {} colour(s) below {:.1}:1 contrast on white: {}",
+                low.len(),
+                MIN_GRAPHICAL_CONTRAST,
+                low.join(", ")
+            ),
+            remediation: format!(
+                "Set `color_discrete_sequence` to a colour-blind safe palette such as [{}]",
+                COLOUR_BLIND_SAFE_PALETTE.join(", ")
+            ),
+        });
+    }
+}
+
+fn is_descriptive_label(label: &str) -> bool {
+    let trimmed = label.trim();
+    if trimmed.chars().count() < 2 {
+        return false;
+    }
+    let lower = trimmed.to_lowercase();
+    if lower.starts_with("unnamed") {
+        return false;
+    }
+    let stem = lower.trim_end_matches(|c: char| c.is_ascii_digit());
+    let generic = ["", "col", "column", "field", "var", "_c", "c", "x", "y"];
+    !(stem.len() < lower.len() && generic.contains(&stem.trim_end_matches(['_', ' '])))
+}
+
+fn relative_luminance(hex: &str) -> Option<f64> {
+    let hex = hex.trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| -> Option<f64> {
+        let v = u8::from_str_radix(&hex[i..i + 2], 16).ok()? as f64 / 255.0;
+        Some(if v <= 0.03928 {
+            v / 12.92
+        } else {
+            ((v + 0.055) / 1.055).powf(2.4)
+        })
+    };
+    Some(0.2126 * channel(0)? + 0.7152 * channel(2)? + 0.0722 * channel(4)?)
+}
+
+pub fn contrast_ratio(foreground: &str, background: &str) -> Option<f64> {
+    let a = relative_luminance(foreground)?;
+    let b = relative_luminance(background)?;
+    let (hi, lo) = if a > b { (a, b) } else { (b, a) };
+    Some((hi + 0.05) / (lo + 0.05))
+}
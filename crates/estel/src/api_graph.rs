@@ -10,6 +10,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
+use crate::data_profiler::DimensionProfile;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -21,6 +22,7 @@ pub enum DataType {
     Numeric,
     Categorical,
     Temporal,
+    Geospatial,
 }
 impl DataType {
     pub fn is_numeric(&self) -> bool {
@@ -32,6 +34,9 @@ impl DataType {
     pub fn is_temporal(&self) -> bool {
         matches!(self, DataType::Temporal)
     }
+    pub fn is_geospatial(&self) -> bool {
+        matches!(self, DataType::Geospatial)
+    }
 }
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArgSpec {
@@ -69,6 +74,58 @@ impl DataTypeSpec {
         matches!(self, DataTypeSpec::Multiple(_))
     }
 }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Coercion {
+    pub from: DataType,
+    pub to: DataType,
+    pub penalty: f64,
+}
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoercionMatrix {
+    coercions: Vec<Coercion>,
+}
+impl Default for CoercionMatrix {
+    fn default() -> Self {
+        Self::none().with(DataType::Categorical, DataType::Temporal, 0.3)
+    }
+}
+impl CoercionMatrix {
+    pub fn none() -> Self {
+        Self {
+            coercions: Vec::new(),
+        }
+    }
+    pub fn with(mut self, from: DataType, to: DataType, penalty: f64) -> Self {
+        let penalty = penalty.clamp(0.0, 1.0);
+        match self
+            .coercions
+            .iter_mut()
+            .find(|c| c.from == from && c.to == to)
+        {
+            Some(existing) => existing.penalty = penalty,
+            None => self.coercions.push(Coercion { from, to, penalty }),
+        }
+        self
+    }
+    pub fn coercions(&self) -> &[Coercion] {
+        &self.coercions
+    }
+    pub fn penalty(&self, from: &DataType, to: &DataType) -> Option<f64> {
+        if from == to {
+            return Some(0.0);
+        }
+        self.coercions
+            .iter()
+            .find(|c| &c.from == from && &c.to == to)
+            .map(|c| c.penalty)
+    }
+    pub fn penalty_for(&self, spec: &DataTypeSpec, data_type: &DataType) -> Option<f64> {
+        spec.accepted_types()
+            .into_iter()
+            .filter_map(|target| self.penalty(data_type, target))
+            .min_by(f64::total_cmp)
+    }
+}
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChartNode {
     pub name: String,
@@ -76,27 +133,57 @@ pub struct ChartNode {
     pub description: String,
     pub tags: Vec<String>,
     pub args: HashMap<String, ArgSpec>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_any: Vec<Vec<String>>,
 }
 impl ChartNode {
     pub fn required_args(&self) -> Vec<(&String, &ArgSpec)> {
-        self.args.iter().filter(|(_, spec)| spec.required).collect()
+        let mut args: Vec<_> = self.args.iter().filter(|(_, spec)| spec.required).collect();
+        args.sort_by(|a, b| a.0.cmp(b.0));
+        args
+    }
+    pub fn required_arg_sets(&self) -> Vec<Vec<(&String, &ArgSpec)>> {
+        let required = self.required_args();
+        if self.required_any.is_empty() {
+            return vec![required];
+        }
+        self.required_any
+            .iter()
+            .map(|group| {
+                let mut args = required.clone();
+                args.extend(
+                    group
+                        .iter()
+                        .filter_map(|name| self.args.get_key_value(name)),
+                );
+                args
+            })
+            .collect()
+    }
+    pub fn required_arg_count(&self) -> usize {
+        self.required_arg_sets()
+            .iter()
+            .map(Vec::len)
+            .min()
+            .unwrap_or(0)
     }
     pub fn optional_args(&self) -> Vec<(&String, &ArgSpec)> {
-        self.args
+        let mut args: Vec<_> = self
+            .args
             .iter()
             .filter(|(_, spec)| !spec.required)
-            .collect()
+            .collect();
+        args.sort_by(|a, b| a.0.cmp(b.0));
+        args
     }
     pub fn can_render_with(&self, available_data_types: &HashMap<String, DataType>) -> bool {
-        for (_, arg_spec) in self.required_args() {
-            let compatible = available_data_types
-                .values()
-                .any(|dt| arg_spec.data_type.accepts(dt));
-            if !compatible {
-                return false;
-            }
-        }
-        true
+        self.required_arg_sets().iter().any(|args| {
+            args.iter().all(|(_, arg_spec)| {
+                available_data_types
+                    .values()
+                    .any(|dt| arg_spec.data_type.accepts(dt))
+            })
+        })
     }
     pub fn has_any_tag(&self, tags: &[&str]) -> bool {
         tags.iter().any(|tag| self.tags.contains(&tag.to_string()))
@@ -239,7 +326,7 @@ impl ChartNode {
         total_dimensions: usize,
     ) -> bool {
         let required_args = self.required_args();
-        let min_required = required_args.len();
+        let min_required = self.required_arg_count();
         if total_dimensions < min_required {
             return false;
         }
@@ -281,8 +368,13 @@ pub struct ApiGraph {
     chart_by_name: HashMap<String, ChartNode>,
     charts_by_library: HashMap<String, Vec<usize>>,
     charts_by_tag: HashMap<String, Vec<usize>>,
+    coercions: CoercionMatrix,
 }
+pub const EMBEDDED_API_YAML: &str = include_str!("../config/plotly_api.yml");
 impl ApiGraph {
+    pub fn default_embedded() -> Result<Self> {
+        Self::from_yaml_string(EMBEDDED_API_YAML)
+    }
     pub fn from_yaml_file<P: AsRef<Path>>(path: P) -> Result<Self> {
         let content = fs::read_to_string(path.as_ref()).with_context(|| {
             format!(
@@ -318,8 +410,16 @@ impl ApiGraph {
             chart_by_name,
             charts_by_library,
             charts_by_tag,
+            coercions: CoercionMatrix::default(),
         })
     }
+    pub fn with_coercions(mut self, coercions: CoercionMatrix) -> Self {
+        self.coercions = coercions;
+        self
+    }
+    pub fn coercions(&self) -> &CoercionMatrix {
+        &self.coercions
+    }
     pub fn get_all_charts(&self) -> &[ChartNode] {
         &self.charts
     }
@@ -430,6 +530,24 @@ impl ApiGraph {
             })
             .collect()
     }
+    pub fn can_render(&self, chart_name: &str, profiles: &[DimensionProfile]) -> RenderEligibility {
+        let Some(chart) = self.get_chart(chart_name) else {
+            return RenderEligibility::UnknownChart {
+                chart_name: chart_name.to_string(),
+            };
+        };
+        let mut first_unmet = None;
+        for required in chart.required_arg_sets() {
+            let (mappings, unmet) = assign_required(&required, profiles);
+            if unmet.is_empty() {
+                return RenderEligibility::Eligible { mappings };
+            }
+            first_unmet.get_or_insert(unmet);
+        }
+        RenderEligibility::Ineligible {
+            unmet: first_unmet.unwrap_or_default(),
+        }
+    }
     pub fn get_charts_supporting_data_type(
         &self,
         arg_name: &str,
@@ -618,6 +736,106 @@ pub struct ChartCompatibility {
     pub supports_faceting: bool,
     pub supports_marginals: bool,
 }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RenderEligibility {
+    Eligible { mappings: HashMap<String, String> },
+    Ineligible { unmet: Vec<UnmetArgSpec> },
+    UnknownChart { chart_name: String },
+}
+impl RenderEligibility {
+    pub fn is_eligible(&self) -> bool {
+        matches!(self, RenderEligibility::Eligible { .. })
+    }
+    pub fn unmet(&self) -> &[UnmetArgSpec] {
+        match self {
+            RenderEligibility::Ineligible { unmet } => unmet,
+            _ => &[],
+        }
+    }
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnmetArgSpec {
+    pub arg: String,
+    pub spec: ArgSpec,
+    pub reason: String,
+}
+fn assign_required(
+    required: &[(&String, &ArgSpec)],
+    profiles: &[DimensionProfile],
+) -> (HashMap<String, String>, Vec<UnmetArgSpec>) {
+    let candidates: Vec<Vec<usize>> = required
+        .iter()
+        .map(|(_, spec)| {
+            profiles
+                .iter()
+                .enumerate()
+                .filter(|(_, profile)| spec.data_type.accepts(&profile.data_type))
+                .map(|(idx, _)| idx)
+                .collect()
+        })
+        .collect();
+    let mut column_owner = vec![None; profiles.len()];
+    for arg in 0..required.len() {
+        let mut visited = vec![false; profiles.len()];
+        assign_column(arg, &candidates, &mut column_owner, &mut visited);
+    }
+    let mut mappings = HashMap::new();
+    for (column, owner) in column_owner.iter().enumerate() {
+        if let Some(arg) = owner {
+            mappings.insert(required[*arg].0.clone(), profiles[column].name.clone());
+        }
+    }
+    let unmet: Vec<UnmetArgSpec> = required
+        .iter()
+        .zip(&candidates)
+        .filter(|((arg, _), _)| !mappings.contains_key(*arg))
+        .map(|((arg, spec), columns)| UnmetArgSpec {
+            arg: (*arg).clone(),
+            spec: (*spec).clone(),
+            reason: unmet_reason(arg, spec, columns.len()),
+        })
+        .collect();
+    (mappings, unmet)
+}
+fn assign_column(
+    arg: usize,
+    candidates: &[Vec<usize>],
+    column_owner: &mut [Option<usize>],
+    visited: &mut [bool],
+) -> bool {
+    for &column in &candidates[arg] {
+        if visited[column] {
+            continue;
+        }
+        visited[column] = true;
+        let free = match column_owner[column] {
+            None => true,
+            Some(other) => assign_column(other, candidates, column_owner, visited),
+        };
+        if free {
+            column_owner[column] = Some(arg);
+            return true;
+        }
+    }
+    false
+}
+pub(crate) fn unmet_reason(arg: &str, spec: &ArgSpec, available: usize) -> String {
+    let types = spec
+        .data_type
+        .accepted_types()
+        .iter()
+        .map(|dt| format!("{dt:?}").to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" or ");
+    let ordinal = match available {
+        0 => "a",
+        1 => "a second",
+        2 => "a third",
+        3 => "a fourth",
+        _ => "another",
+    };
+    format!("needs {ordinal} {types} column for {arg}")
+}
 #[derive(Debug, Clone)]
 pub struct MissingArgument {
     pub name: String,
@@ -10,10 +10,12 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
-use crate::api_graph::{ApiGraph, ArgSpec, ChartNode, DataType};
-use crate::data_profiler::DimensionProfile;
+use crate::api_graph::{unmet_reason, ApiGraph, ArgSpec, ChartNode, DataType, UnmetArgSpec};
+use crate::data_profiler::{DimensionProfile, GeoRole, SemanticType};
 use rayon::prelude::*;
-use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
 use std::time::Instant;
 use thiserror::Error;
 #[derive(Error, Debug)]
@@ -27,6 +29,8 @@ pub struct MatchingConfig {
     pub max_suggestions_per_chart: usize,
     pub include_partial_matches: bool,
     pub prefer_high_dimensionality: bool,
+    pub allowed_libraries: Option<Vec<String>>,
+    pub excluded_chart_names: Vec<String>,
 }
 impl Default for MatchingConfig {
     fn default() -> Self {
@@ -35,6 +39,8 @@ impl Default for MatchingConfig {
             max_suggestions_per_chart: 10,
             include_partial_matches: true,
             prefer_high_dimensionality: false,
+            allowed_libraries: None,
+            excluded_chart_names: Vec::new(),
         }
     }
 }
@@ -52,8 +58,22 @@ impl MatchingConfig {
                     .to_string(),
             );
         }
+        if self
+            .allowed_libraries
+            .as_ref()
+            .is_some_and(|libraries| libraries.is_empty())
+        {
+            return Err("allowed_libraries must name at least one library".to_string());
+        }
         Ok(())
     }
+    pub fn allows_chart(&self, chart: &ChartNode) -> bool {
+        let library_allowed = self
+            .allowed_libraries
+            .as_ref()
+            .is_none_or(|libraries| libraries.contains(&chart.library));
+        library_allowed && !self.excluded_chart_names.contains(&chart.name)
+    }
     pub fn for_performance() -> Self {
         Self {
             min_quality_score: 0.5,
@@ -68,6 +88,7 @@ impl MatchingConfig {
             max_suggestions_per_chart: 15,
             include_partial_matches: true,
             prefer_high_dimensionality: true,
+            ..Default::default()
         }
     }
     pub fn for_presentation() -> Self {
@@ -76,6 +97,7 @@ impl MatchingConfig {
             max_suggestions_per_chart: 8,
             include_partial_matches: false,
             prefer_high_dimensionality: false,
+            ..Default::default()
         }
     }
 }
@@ -96,18 +118,32 @@ impl Default for DomainHints {
         }
     }
 }
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RenderSpec {
     pub chart_name: String,
     pub library: String,
     pub description: String,
-    pub mappings: HashMap<String, String>,
+    pub mappings: BTreeMap<String, String>,
     pub quality_score: f64,
     pub dimensions_used: usize,
     pub complete: bool,
     pub detailed_score: Option<ChartScore>,
 }
-#[derive(Debug, Clone)]
+impl RenderSpec {
+    pub fn mapping_key(&self) -> String {
+        self.mappings
+            .iter()
+            .map(|(arg, col)| format!("{arg}={col}"))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+    pub fn tie_break_cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.chart_name
+            .cmp(&other.chart_name)
+            .then_with(|| self.mapping_key().cmp(&other.mapping_key()))
+    }
+}
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ChartScore {
     pub technical_feasibility: f64,
     pub semantic_appropriateness: f64,
@@ -193,6 +229,137 @@ pub struct SingleChartExplanation {
     pub available_mappings: Vec<String>,
     pub quality_issues: Vec<String>,
 }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DimensionAssignment {
+    pub arg: String,
+    pub spec: ArgSpec,
+    pub column: String,
+    pub data_type: DataType,
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchPenalty {
+    pub arg: String,
+    pub column: String,
+    pub reason: String,
+    pub amount: f64,
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchExplanation {
+    pub satisfied: Vec<DimensionAssignment>,
+    pub unsatisfied: Vec<UnmetArgSpec>,
+    pub penalties: Vec<MatchPenalty>,
+}
+impl MatchExplanation {
+    pub fn column_for(&self, arg: &str) -> Option<&str> {
+        self.satisfied
+            .iter()
+            .find(|assignment| assignment.arg == arg)
+            .map(|assignment| assignment.column.as_str())
+    }
+    pub fn total_penalty(&self) -> f64 {
+        self.penalties.iter().map(|penalty| penalty.amount).sum()
+    }
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RejectionReason {
+    NoDimensions,
+    UnsupportedLibrary(String),
+    ExcludedByConfig,
+    LowScore { score: f64, threshold: f64 },
+    Outranked { score: f64, limit: usize },
+    UnmetRequirements(Vec<UnmetArgSpec>),
+    InvalidMapping(String),
+    BelowQualityThreshold { quality: f64, threshold: f64 },
+}
+impl std::fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RejectionReason::NoDimensions => write!(f, "no columns to map"),
+            RejectionReason::UnsupportedLibrary(library) => {
+                write!(f, "library '{library}' is not supported by the matcher")
+            }
+            RejectionReason::ExcludedByConfig => {
+                write!(f, "excluded by the matching configuration")
+            }
+            RejectionReason::LowScore { score, threshold } => {
+                write!(f, "score {score:.2} is below the {threshold:.2} cut-off")
+            }
+            RejectionReason::Outranked { score, limit } => {
+                write!(f, "score {score:.2} is outside the top {limit}")
+            }
+            RejectionReason::UnmetRequirements(unmet) => {
+                let reasons: Vec<_> = unmet.iter().map(|u| u.reason.as_str()).collect();
+                write!(f, "{}", reasons.join("; "))
+            }
+            RejectionReason::InvalidMapping(reason) => write!(f, "{reason}"),
+            RejectionReason::BelowQualityThreshold { quality, threshold } => {
+                write!(f, "mapping quality {quality:.2} is below {threshold:.2}")
+            }
+        }
+    }
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectedChart {
+    pub chart: ChartNode,
+    pub reason: RejectionReason,
+}
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExplainedCharts {
+    pub matches: Vec<(RenderSpec, MatchExplanation)>,
+    pub rejected: Vec<RejectedChart>,
+}
+impl ExplainedCharts {
+    pub fn rejection(&self, chart_name: &str) -> Option<&RejectionReason> {
+        self.rejected
+            .iter()
+            .find(|rejected| rejected.chart.name == chart_name)
+            .map(|rejected| &rejected.reason)
+    }
+}
+struct RankedSpec {
+    score: f64,
+    spec: RenderSpec,
+}
+impl PartialEq for RankedSpec {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for RankedSpec {}
+impl PartialOrd for RankedSpec {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for RankedSpec {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .total_cmp(&other.score)
+            .then_with(|| other.spec.tie_break_cmp(&self.spec))
+    }
+}
+pub struct QualifiedCharts {
+    heap: BinaryHeap<RankedSpec>,
+    capacity: usize,
+}
+impl QualifiedCharts {
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    pub fn buffered(&self) -> usize {
+        self.heap.len()
+    }
+}
+impl Iterator for QualifiedCharts {
+    type Item = RenderSpec;
+    fn next(&mut self) -> Option<RenderSpec> {
+        self.heap.pop().map(|ranked| ranked.spec)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+impl ExactSizeIterator for QualifiedCharts {}
 mod internal {
     use super::*;
     pub mod scoring_weights {
@@ -224,8 +391,8 @@ mod internal {
             let mut sorted_profiles: Vec<&'a DimensionProfile> = profiles.iter().collect();
             sorted_profiles.sort_unstable_by(|a, b| {
                 b.quality_score
-                    .partial_cmp(&a.quality_score)
-                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .total_cmp(&a.quality_score)
+                    .then_with(|| a.name.cmp(&b.name))
             });
             for p in profiles {
                 by_name.insert(p.name.clone(), p);
@@ -249,8 +416,9 @@ mod internal {
         dimension_index: &'a DimensionIndex<'a>,
         matcher: &'a ChartMatcher<'a>,
         detailed_score: Option<&'b ChartScore>,
-        mappings: HashMap<String, String>,
+        mappings: BTreeMap<String, String>,
         used_profiles: HashSet<String>,
+        unmet: Vec<UnmetArgSpec>,
     }
     impl<'a, 'b> SpecBuilder<'a, 'b> {
         fn new(
@@ -264,38 +432,65 @@ mod internal {
                 dimension_index: index,
                 matcher,
                 detailed_score: score,
-                mappings: HashMap::new(),
+                mappings: BTreeMap::new(),
                 used_profiles: HashSet::new(),
+                unmet: Vec::new(),
             }
         }
         fn try_map_required(&mut self) -> bool {
-            let required_args = self.chart.required_args();
-            for (arg_name, arg_spec) in &required_args {
-                if let Some(profile) = self.find_best_match(arg_spec) {
+            let chart = self.chart;
+            let mut first_unmet = None;
+            for required_args in chart.required_arg_sets() {
+                if self.try_map_args(&required_args) {
+                    return true;
+                }
+                first_unmet.get_or_insert(std::mem::take(&mut self.unmet));
+                self.mappings.clear();
+                self.used_profiles.clear();
+            }
+            self.unmet = first_unmet.unwrap_or_default();
+            false
+        }
+        fn try_map_args(&mut self, required_args: &[(&String, &ArgSpec)]) -> bool {
+            for (arg_name, arg_spec) in required_args {
+                if let Some(profile) = self.find_best_match(arg_name, arg_spec) {
                     self.mappings
                         .insert(arg_name.to_string(), profile.name.clone());
                     self.used_profiles.insert(profile.name.clone());
                 } else {
-                    return false;
+                    let available = self
+                        .dimension_index
+                        .sorted_profiles
+                        .iter()
+                        .filter(|p| self.is_candidate(arg_name, arg_spec, p))
+                        .count();
+                    self.unmet.push(UnmetArgSpec {
+                        arg: arg_name.to_string(),
+                        spec: (*arg_spec).clone(),
+                        reason: unmet_reason(arg_name, arg_spec, available),
+                    });
                 }
             }
-            true
+            self.unmet.is_empty()
         }
-        fn validate_mappings(&self) -> bool {
+        fn mapping_violation(&self) -> Option<String> {
             for (arg_name, col_name) in &self.mappings {
                 if let Some(profile) = self.dimension_index.get_by_name(col_name) {
                     if arg_name == "y"
                         && self.chart.name == "bar"
                         && !matches!(profile.data_type, DataType::Categorical)
                     {
-                        return false;
+                        return Some(format!(
+                            "bar needs a categorical column for y, but '{col_name}' is {}",
+                            format_data_types(vec![&profile.data_type])
+                        ));
                     }
                 }
             }
-            true
+            None
         }
         fn build(&self) -> Option<RenderSpec> {
-            if !self.validate_mappings() {
+            if self.mapping_violation().is_some() {
                 return None;
             }
             let spec = RenderSpec {
@@ -304,29 +499,53 @@ mod internal {
                 description: self.chart.description.clone(),
                 quality_score: self.calculate_mapping_quality(),
                 dimensions_used: self.mappings.len(),
-                complete: self.mappings.len() >= self.chart.required_args().len(),
+                complete: self.mappings.len() >= self.chart.required_arg_count(),
                 mappings: self.mappings.clone(),
                 detailed_score: self.detailed_score.cloned(),
             };
             Some(spec)
         }
-        fn find_best_match(&self, arg_spec: &ArgSpec) -> Option<&'a DimensionProfile> {
+        fn geo_role_matches(arg_name: &str, profile: &DimensionProfile) -> bool {
+            if !profile.data_type.is_geospatial() {
+                return true;
+            }
+            match arg_name {
+                "lat" => profile.geo_role == Some(GeoRole::Latitude),
+                "lon" => profile.geo_role == Some(GeoRole::Longitude),
+                "locations" => profile.geo_role == Some(GeoRole::Region),
+                _ => true,
+            }
+        }
+        fn semantic_type_allows(&self, arg_name: &str, profile: &DimensionProfile) -> bool {
+            if profile.semantic_type != Some(SemanticType::Identifier) {
+                return true;
+            }
+            let measure_arg = matches!(arg_name, "y" | "z" | "r" | "size" | "values" | "color");
+            !(measure_arg || (self.chart.name == "histogram" && arg_name == "x"))
+        }
+        fn find_best_match(
+            &self,
+            arg_name: &str,
+            arg_spec: &ArgSpec,
+        ) -> Option<&'a DimensionProfile> {
             let mut compatible: Vec<_> = self
                 .dimension_index
                 .sorted_profiles
                 .iter()
                 .filter(|p| {
                     !self.used_profiles.contains(&p.name)
-                        && arg_spec
-                            .data_type
-                            .accepted_types()
-                            .contains(&(&p.data_type))
+                        && self.is_candidate(arg_name, arg_spec, p)
                 })
                 .cloned()
                 .collect();
             if compatible.is_empty() {
                 return None;
             }
+            compatible.sort_by(|a, b| {
+                let a_penalty = self.matcher.coercion_penalty(arg_spec, a).unwrap_or(1.0);
+                let b_penalty = self.matcher.coercion_penalty(arg_spec, b).unwrap_or(1.0);
+                a_penalty.total_cmp(&b_penalty)
+            });
             if (self.chart.name == "treemap" || self.chart.name == "sunburst")
                 && arg_spec.data_type.accepts(&DataType::Numeric)
             {
@@ -334,12 +553,22 @@ mod internal {
                     let a_score = self.calculate_hierarchical_suitability(a);
                     let b_score = self.calculate_hierarchical_suitability(b);
                     b_score
-                        .partial_cmp(&a_score)
-                        .unwrap_or(std::cmp::Ordering::Equal)
+                        .total_cmp(&a_score)
+                        .then_with(|| a.name.cmp(&b.name))
                 });
             }
             compatible.first().cloned()
         }
+        fn is_candidate(
+            &self,
+            arg_name: &str,
+            arg_spec: &ArgSpec,
+            profile: &DimensionProfile,
+        ) -> bool {
+            self.matcher.coercion_penalty(arg_spec, profile).is_some()
+                && Self::geo_role_matches(arg_name, profile)
+                && self.semantic_type_allows(arg_name, profile)
+        }
         fn calculate_hierarchical_suitability(&self, profile: &DimensionProfile) -> f64 {
             let mut score = profile.quality_score;
             if let Some(stats) = &profile.numeric_stats {
@@ -391,19 +620,11 @@ mod internal {
                             scoring_weights::MAPPING_QUALITY_SEMANTIC_BONUS_SIZE_NUMERIC,
                         _ => 0.0,
                     };
-                            if let (DataType::Categorical, Some(card)) =
-                                (&profile.data_type, profile.cardinality)
-                            {
-                                quality -= match arg_name.as_str() {
-                                    "colour" if card > scoring_weights::COLOR_MAX_CATEGORIES => {
-                                        scoring_weights::CARDINALITY_PENALTY_COLOR
-                                    }
-                                    "x" if card > scoring_weights::HIGH_CARDINALITY_THRESHOLD => {
-                                        scoring_weights::CARDINALITY_PENALTY_X_AXIS
-                                    }
-                                    _ => 0.0,
-                                };
-                            }
+                            quality -= self
+                                .mapping_penalties(arg_name, profile)
+                                .iter()
+                                .map(|penalty| penalty.amount)
+                                .sum::<f64>();
                             quality.max(0.0)
                         } else {
                             0.0
@@ -412,6 +633,88 @@ mod internal {
                     .sum();
             (total_quality / self.mappings.len() as f64).clamp(0.0, 1.0)
         }
+        fn mapping_penalties(
+            &self,
+            arg_name: &str,
+            profile: &DimensionProfile,
+        ) -> Vec<MatchPenalty> {
+            let mut penalties = Vec::new();
+            let penalty = |reason: String, amount: f64| MatchPenalty {
+                arg: arg_name.to_string(),
+                column: profile.name.clone(),
+                reason,
+                amount,
+            };
+            if let (DataType::Categorical, Some(card)) = (&profile.data_type, profile.cardinality) {
+                match arg_name {
+                    "colour" if card > scoring_weights::COLOR_MAX_CATEGORIES => {
+                        penalties.push(penalty(
+                            format!(
+                                "{card} categories exceed the {} a colour scale can separate",
+                                scoring_weights::COLOR_MAX_CATEGORIES
+                            ),
+                            scoring_weights::CARDINALITY_PENALTY_COLOR,
+                        ));
+                    }
+                    "x" if card > scoring_weights::HIGH_CARDINALITY_THRESHOLD => {
+                        penalties.push(penalty(
+                            format!(
+                                "{card} categories exceed the {} that fit on an x axis",
+                                scoring_weights::HIGH_CARDINALITY_THRESHOLD
+                            ),
+                            scoring_weights::CARDINALITY_PENALTY_X_AXIS,
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+            if let Some(spec) = self.chart.args.get(arg_name) {
+                if let Some(amount) = self
+                    .matcher
+                    .coercion_penalty(spec, profile)
+                    .filter(|amount| *amount > 0.0)
+                {
+                    penalties.push(penalty(
+                        format!(
+                            "{} column coerced to {}",
+                            format_data_types(vec![&profile.data_type]),
+                            format_data_types(spec.data_type.accepted_types())
+                        ),
+                        amount,
+                    ));
+                }
+            }
+            penalties
+        }
+        fn explain(&self) -> MatchExplanation {
+            let mut satisfied: Vec<DimensionAssignment> = self
+                .mappings
+                .iter()
+                .filter_map(|(arg_name, col_name)| {
+                    let profile = self.dimension_index.get_by_name(col_name)?;
+                    Some(DimensionAssignment {
+                        arg: arg_name.clone(),
+                        spec: self.chart.args.get(arg_name)?.clone(),
+                        column: col_name.clone(),
+                        data_type: profile.data_type.clone(),
+                    })
+                })
+                .collect();
+            satisfied.sort_by(|a, b| a.arg.cmp(&b.arg));
+            let penalties = satisfied
+                .iter()
+                .filter_map(|assignment| {
+                    let profile = self.dimension_index.get_by_name(&assignment.column)?;
+                    Some(self.mapping_penalties(&assignment.arg, profile))
+                })
+                .flatten()
+                .collect();
+            MatchExplanation {
+                satisfied,
+                unsatisfied: self.unmet.clone(),
+                penalties,
+            }
+        }
     }
     #[derive(Debug, Clone)]
     pub(super) struct ChartCandidate<'a> {
@@ -455,6 +758,7 @@ mod internal {
         pub has_temporal: bool,
         pub numeric_count: usize,
         pub categorical_count: usize,
+        pub geospatial_count: usize,
     }
     impl DatasetCharacteristics {
         pub fn from_profiles(profiles: &[DimensionProfile]) -> Self {
@@ -467,6 +771,7 @@ mod internal {
                     has_temporal: false,
                     numeric_count: 0,
                     categorical_count: 0,
+                    geospatial_count: 0,
                 };
             }
             let mut total_cardinality = 0.0;
@@ -487,10 +792,11 @@ mod internal {
             let numeric_count = *type_counts.get(&DataType::Numeric).unwrap_or(&0);
             let categorical_count = *type_counts.get(&DataType::Categorical).unwrap_or(&0);
             let temporal_count = *type_counts.get(&DataType::Temporal).unwrap_or(&0);
+            let geospatial_count = *type_counts.get(&DataType::Geospatial).unwrap_or(&0);
             let complexity_score = {
                 let dim_factor = (dimensionality as f64 / 10.0).min(1.0);
                 let card_factor = (avg_cardinality / 100.0).min(1.0);
-                let type_diversity = type_counts.keys().len() as f64 / 3.0;
+                let type_diversity = (type_counts.keys().len() as f64 / 3.0).min(1.0);
                 (dim_factor * 0.4 + card_factor * 0.4 + type_diversity * 0.2).min(1.0)
             };
             Self {
@@ -500,12 +806,14 @@ mod internal {
                 has_temporal: temporal_count > 0,
                 numeric_count,
                 categorical_count,
+                geospatial_count,
             }
         }
     }
     pub(super) struct ChartMatcher<'a> {
         api_graph: &'a ApiGraph,
         config: InternalConfig,
+        chart_filter: &'a MatchingConfig,
         characteristics: DatasetCharacteristics,
         scoring_weights: ScoringWeights,
         dimension_index: DimensionIndex<'a>,
@@ -527,6 +835,7 @@ mod internal {
             Self {
                 api_graph,
                 config: internal_config,
+                chart_filter: config,
                 characteristics,
                 scoring_weights,
                 dimension_index,
@@ -563,20 +872,152 @@ mod internal {
                 return Vec::new();
             }
             let mut final_results = self.stage3_full_analysis(&stage2_candidates);
-            final_results.sort_unstable_by(|a, b| {
-                self.calculate_final_ranking_score(b)
-                    .partial_cmp(&self.calculate_final_ranking_score(a))
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            });
+            final_results.sort_by(|a, b| self.ranking_cmp(a, b));
             final_results.truncate(self.config.final_max_results);
             final_results
         }
+        pub fn find_charts_explained(&self) -> ExplainedCharts {
+            let reject = |chart: &ChartNode, reason| RejectedChart {
+                chart: chart.clone(),
+                reason,
+            };
+            let charts = self.api_graph.get_all_charts();
+            if self.profile_names.is_empty() {
+                return ExplainedCharts {
+                    matches: Vec::new(),
+                    rejected: charts
+                        .iter()
+                        .map(|chart| reject(chart, RejectionReason::NoDimensions))
+                        .collect(),
+                };
+            }
+            let mut rejected = Vec::new();
+            let mut candidates = Vec::new();
+            for chart in charts {
+                if chart.library != "plotly" {
+                    let library = chart.library.clone();
+                    rejected.push(reject(chart, RejectionReason::UnsupportedLibrary(library)));
+                    continue;
+                }
+                if !self.chart_filter.allows_chart(chart) {
+                    rejected.push(reject(chart, RejectionReason::ExcludedByConfig));
+                    continue;
+                }
+                let detailed_score = self.calculate_detailed_scores(chart);
+                let score = detailed_score.overall_score;
+                if score < self.config.stage1_threshold {
+                    let threshold = self.config.stage1_threshold;
+                    rejected.push(reject(chart, RejectionReason::LowScore { score, threshold }));
+                    continue;
+                }
+                candidates.push(ChartCandidate {
+                    chart,
+                    score,
+                    detailed_score: Some(detailed_score),
+                });
+            }
+            for (limit, threshold) in [
+                (self.config.stage1_max_candidates, None),
+                (self.config.stage2_max_candidates, Some(self.config.stage2_threshold)),
+            ] {
+                if let Some(threshold) = threshold {
+                    let (kept, dropped): (Vec<_>, Vec<_>) =
+                        candidates.into_iter().partition(|c| c.score >= threshold);
+                    for candidate in dropped {
+                        let score = candidate.score;
+                        let reason = RejectionReason::LowScore { score, threshold };
+                        rejected.push(reject(candidate.chart, reason));
+                    }
+                    candidates = kept;
+                }
+                Self::sort_candidates(&mut candidates);
+                for candidate in candidates.split_off(limit.min(candidates.len())) {
+                    let reason = RejectionReason::Outranked {
+                        score: candidate.score,
+                        limit,
+                    };
+                    rejected.push(reject(candidate.chart, reason));
+                }
+            }
+            let mut matches = Vec::new();
+            for candidate in &candidates {
+                let mut builder = SpecBuilder::new(
+                    candidate.chart,
+                    &self.dimension_index,
+                    self,
+                    candidate.detailed_score.as_ref(),
+                );
+                if !builder.try_map_required() {
+                    let reason = RejectionReason::UnmetRequirements(builder.unmet.clone());
+                    rejected.push(reject(candidate.chart, reason));
+                    continue;
+                }
+                if let Some(violation) = builder.mapping_violation() {
+                    let reason = RejectionReason::InvalidMapping(violation);
+                    rejected.push(reject(candidate.chart, reason));
+                    continue;
+                }
+                let Some(spec) = builder.build() else {
+                    continue;
+                };
+                if spec.quality_score < self.config.min_quality_score {
+                    let reason = RejectionReason::BelowQualityThreshold {
+                        quality: spec.quality_score,
+                        threshold: self.config.min_quality_score,
+                    };
+                    rejected.push(reject(candidate.chart, reason));
+                    continue;
+                }
+                matches.push((spec, builder.explain()));
+            }
+            matches.sort_by(|(a, _), (b, _)| self.ranking_cmp(a, b));
+            let limit = self.config.final_max_results;
+            for (spec, _) in matches.split_off(limit.min(matches.len())) {
+                if let Some(chart) = self.api_graph.get_chart(&spec.chart_name) {
+                    let score = self.calculate_final_ranking_score(&spec);
+                    rejected.push(reject(chart, RejectionReason::Outranked { score, limit }));
+                }
+            }
+            ExplainedCharts { matches, rejected }
+        }
+        fn ranking_cmp(&self, a: &RenderSpec, b: &RenderSpec) -> Ordering {
+            self.calculate_final_ranking_score(b)
+                .total_cmp(&self.calculate_final_ranking_score(a))
+                .then_with(|| a.tie_break_cmp(b))
+        }
+        fn sort_candidates(candidates: &mut [ChartCandidate<'a>]) {
+            candidates.sort_unstable_by(|a, b| {
+                b.score
+                    .total_cmp(&a.score)
+                    .then_with(|| a.chart.name.cmp(&b.chart.name))
+            });
+        }
+        pub fn find_charts_top_k(&self) -> QualifiedCharts {
+            let capacity = self.config.final_max_results;
+            let mut top: BinaryHeap<Reverse<RankedSpec>> = BinaryHeap::with_capacity(capacity + 1);
+            if !self.profile_names.is_empty() {
+                let candidates = self.stage2_semantic_analysis(self.stage1_fast_filter());
+                for spec in candidates.iter().filter_map(|c| self.build_qualified_spec(c)) {
+                    top.push(Reverse(RankedSpec {
+                        score: self.calculate_final_ranking_score(&spec),
+                        spec,
+                    }));
+                    if top.len() > capacity {
+                        top.pop();
+                    }
+                }
+            }
+            QualifiedCharts {
+                heap: top.into_iter().map(|Reverse(ranked)| ranked).collect(),
+                capacity,
+            }
+        }
         fn stage1_fast_filter(&self) -> Vec<ChartCandidate<'a>> {
             let mut candidates: Vec<_> = self
                 .api_graph
                 .get_all_charts()
                 .iter()
-                .filter(|chart| chart.library == "plotly")
+                .filter(|chart| chart.library == "plotly" && self.chart_filter.allows_chart(chart))
                 .map(|chart| {
                     let detailed_score = self.calculate_detailed_scores(chart);
                     let score = detailed_score.overall_score;
@@ -589,11 +1030,7 @@ mod internal {
                     detailed_score: Some(detailed_score),
                 })
                 .collect();
-            candidates.sort_unstable_by(|a, b| {
-                b.score
-                    .partial_cmp(&a.score)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            });
+            Self::sort_candidates(&mut candidates);
             candidates.truncate(self.config.stage1_max_candidates);
             candidates
         }
@@ -605,35 +1042,30 @@ mod internal {
                 .into_iter()
                 .filter(|c| c.score >= self.config.stage2_threshold)
                 .collect();
-            qualified_candidates.sort_unstable_by(|a, b| {
-                b.score
-                    .partial_cmp(&a.score)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            });
+            Self::sort_candidates(&mut qualified_candidates);
             qualified_candidates.truncate(self.config.stage2_max_candidates);
             qualified_candidates
         }
         fn stage3_full_analysis(&self, candidates: &[ChartCandidate<'a>]) -> Vec<RenderSpec> {
             candidates
                 .par_iter()
-                .flat_map(|candidate| {
-                    let mut builder = SpecBuilder::new(
-                        candidate.chart,
-                        &self.dimension_index,
-                        self,
-                        candidate.detailed_score.as_ref(),
-                    );
-                    let mut specs = Vec::new();
-                    if builder.try_map_required() {
-                        if let Some(spec) = builder.build() {
-                            specs.push(spec);
-                        }
-                    }
-                    specs
-                })
-                .filter(|spec| spec.quality_score >= self.config.min_quality_score)
+                .filter_map(|candidate| self.build_qualified_spec(candidate))
                 .collect()
         }
+        fn build_qualified_spec(&self, candidate: &ChartCandidate<'a>) -> Option<RenderSpec> {
+            let mut builder = SpecBuilder::new(
+                candidate.chart,
+                &self.dimension_index,
+                self,
+                candidate.detailed_score.as_ref(),
+            );
+            if !builder.try_map_required() {
+                return None;
+            }
+            builder
+                .build()
+                .filter(|spec| spec.quality_score >= self.config.min_quality_score)
+        }
         pub fn calculate_detailed_scores(&self, chart: &ChartNode) -> ChartScore {
             let mut score = ChartScore {
                 technical_feasibility: self.calculate_technical_feasibility(chart),
@@ -647,15 +1079,20 @@ mod internal {
             score
         }
         fn calculate_technical_feasibility(&self, chart: &ChartNode) -> f64 {
-            let required_args = chart.required_args();
-            if required_args.is_empty() {
-                return 1.0;
-            }
-            let total_score: f64 = required_args
+            chart
+                .required_arg_sets()
                 .iter()
-                .map(|(_, arg)| self.calculate_arg_compatibility(arg))
-                .sum();
-            (total_score / required_args.len() as f64).min(1.0)
+                .map(|required_args| {
+                    if required_args.is_empty() {
+                        return 1.0;
+                    }
+                    let total_score: f64 = required_args
+                        .iter()
+                        .map(|(_, arg)| self.calculate_arg_compatibility(arg))
+                        .sum();
+                    (total_score / required_args.len() as f64).min(1.0)
+                })
+                .fold(0.0, f64::max)
         }
         fn calculate_arg_compatibility(&self, arg_spec: &ArgSpec) -> f64 {
             let compatible_profiles: Vec<_> = arg_spec
@@ -665,7 +1102,7 @@ mod internal {
                 .flat_map(|dt| self.dimension_index.get_by_type(dt))
                 .collect();
             if compatible_profiles.is_empty() {
-                return 0.0;
+                return self.calculate_coerced_arg_compatibility(arg_spec);
             }
             let mut score = 0.6;
             let best_quality = compatible_profiles
@@ -676,6 +1113,21 @@ mod internal {
             score += (compatible_profiles.len() as f64 / 5.0).min(0.1);
             score.min(1.0)
         }
+        fn calculate_coerced_arg_compatibility(&self, arg_spec: &ArgSpec) -> f64 {
+            self.dimension_index
+                .sorted_profiles
+                .iter()
+                .filter_map(|p| {
+                    let penalty = self.coercion_penalty(arg_spec, p)?;
+                    Some((0.6 + p.quality_score * 0.3) * (1.0 - penalty))
+                })
+                .fold(0.0, f64::max)
+        }
+        fn coercion_penalty(&self, arg_spec: &ArgSpec, profile: &DimensionProfile) -> Option<f64> {
+            self.api_graph
+                .coercions()
+                .penalty_for(&arg_spec.data_type, &profile.data_type)
+        }
         fn calculate_semantic_score(&self, chart: &ChartNode) -> f64 {
             let mut score = 0.5;
             score += match chart.name.as_str() {
@@ -685,6 +1137,9 @@ mod internal {
                 "bar" => self.bar_chart_semantic_score(),
                 "box" => self.box_chart_semantic_score(),
                 "pie" => self.pie_chart_semantic_score(),
+                "scatter_geo" | "line_geo" | "choropleth" | "scatter_map" => {
+                    self.map_chart_semantic_score()
+                }
                 _ => 0.0,
             };
             score.clamp(0.0, 1.0)
@@ -769,12 +1224,22 @@ mod internal {
             }
             score
         }
+        fn map_chart_semantic_score(&self) -> f64 {
+            if self.characteristics.geospatial_count == 0 {
+                return -0.3;
+            }
+            let mut score = 0.3;
+            if self.characteristics.numeric_count >= 1 {
+                score += 0.1;
+            }
+            score
+        }
         fn calculate_visual_effectiveness(&self, chart: &ChartNode) -> f64 {
             let mut score = 0.7;
             if let Some(cardinality_penalty) = self.calculate_cardinality_penalty(chart) {
                 score -= cardinality_penalty;
             }
-            let required_dims = chart.required_args().len();
+            let required_dims = chart.required_arg_count();
             let available_dims = self.characteristics.dimensionality;
             if required_dims > available_dims {
                 score -= 0.3;
@@ -813,7 +1278,7 @@ mod internal {
             }
         }
         fn calculate_data_utilisation(&self, chart: &ChartNode) -> f64 {
-            let required_args = chart.required_args().len();
+            let required_args = chart.required_arg_count();
             if self.characteristics.dimensionality == 0 {
                 return 0.0;
             }
@@ -944,6 +1409,27 @@ pub fn find_qualified_charts(
 ) -> Vec<RenderSpec> {
     internal::ChartMatcher::new(profiles, api_graph, config, None).find_charts()
 }
+pub fn find_qualified_charts_iter(
+    profiles: &[DimensionProfile],
+    api_graph: &ApiGraph,
+    config: &MatchingConfig,
+) -> QualifiedCharts {
+    internal::ChartMatcher::new(profiles, api_graph, config, None).find_charts_top_k()
+}
+pub fn find_qualified_charts_explained(
+    profiles: &[DimensionProfile],
+    api_graph: &ApiGraph,
+    config: &MatchingConfig,
+) -> Vec<(RenderSpec, MatchExplanation)> {
+    explain_chart_matches(profiles, api_graph, config).matches
+}
+pub fn explain_chart_matches(
+    profiles: &[DimensionProfile],
+    api_graph: &ApiGraph,
+    config: &MatchingConfig,
+) -> ExplainedCharts {
+    internal::ChartMatcher::new(profiles, api_graph, config, None).find_charts_explained()
+}
 pub fn find_qualified_charts_validated(
     profiles: &[DimensionProfile],
     api_graph: &ApiGraph,
@@ -1080,6 +1566,25 @@ fn explain_single_chart(
             ));
         }
     }
+    let any_group_satisfied = chart.required_any.iter().any(|group| {
+        group.iter().all(|name| {
+            chart
+                .args
+                .get(name)
+                .is_some_and(|spec| !dimensions.get_compatible_dimensions(spec).is_empty())
+        })
+    });
+    if !chart.required_any.is_empty() && !any_group_satisfied {
+        let groups: Vec<String> = chart
+            .required_any
+            .iter()
+            .map(|group| group.join(" + "))
+            .collect();
+        missing_requirements.push(format!(
+            "No compatible data for any of the argument groups {}",
+            groups.join(" or ")
+        ));
+    }
     SingleChartExplanation {
         chart_name: chart.name.clone(),
         can_render: missing_requirements.is_empty(),
@@ -1094,10 +1599,11 @@ fn format_dimension_summary(dimensions: &ExplanationDimensionIndex) -> String {
         *counts.entry(p.data_type.clone()).or_insert(0) += 1;
     }
     format!(
-        "{} numeric, {} categorical, {} temporal",
+        "{} numeric, {} categorical, {} temporal, {} geospatial",
         counts.get(&DataType::Numeric).unwrap_or(&0),
         counts.get(&DataType::Categorical).unwrap_or(&0),
-        counts.get(&DataType::Temporal).unwrap_or(&0)
+        counts.get(&DataType::Temporal).unwrap_or(&0),
+        counts.get(&DataType::Geospatial).unwrap_or(&0)
     )
 }
 fn format_data_types(data_types: Vec<&DataType>) -> String {
@@ -1228,7 +1734,7 @@ pub mod advanced {
             crate::chart_matcher::internal::ChartMatcher::new(profiles, api_graph, config, None);
         let mut breakdown = HashMap::new();
         for chart in api_graph.get_all_charts() {
-            if chart.library == "plotly" {
+            if chart.library == "plotly" && config.allows_chart(chart) {
                 let score = matcher.calculate_detailed_scores(chart);
                 breakdown.insert(chart.name.clone(), score);
             }
@@ -1246,18 +1752,24 @@ pub mod advanced {
                 max_suggestions_per_chart: 15,
                 include_partial_matches: true,
                 prefer_high_dimensionality: true,
+
+                ..Default::default()
             },
             "presentation" => MatchingConfig {
                 min_quality_score: 0.6,
                 max_suggestions_per_chart: 5,
                 include_partial_matches: false,
                 prefer_high_dimensionality: false,
+
+                ..Default::default()
             },
             "analysis" => MatchingConfig {
                 min_quality_score: 0.4,
                 max_suggestions_per_chart: 8,
                 include_partial_matches: true,
                 prefer_high_dimensionality: false,
+
+                ..Default::default()
             },
             _ => MatchingConfig::default(),
         };
@@ -1447,5 +1959,8 @@ pub fn get_quick_recommendations(profiles: &[DimensionProfile]) -> Vec<&'static
     {
         recommendations.push("pie");
     }
+    if characteristics.geospatial_count >= 1 {
+        recommendations.push("scatter_geo");
+    }
     recommendations
 }
@@ -12,18 +12,27 @@
 
 use crate::api_graph::DataType;
 use anyhow::Result;
+#[cfg(feature = "native")]
 use chrono::{DateTime, NaiveDate, NaiveDateTime};
+#[cfg(feature = "native")]
 use polars::prelude::QuantileMethod;
+#[cfg(feature = "native")]
 use polars::prelude::*;
+#[cfg(feature = "native")]
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
+#[cfg(feature = "native")]
+use std::collections::HashSet;
+#[cfg(feature = "native")]
 use std::fs::File;
+#[cfg(feature = "native")]
 use std::path::Path;
 #[derive(Debug, thiserror::Error)]
 pub enum ProfilerError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+    #[cfg(feature = "native")]
     #[error("Polars error: {0}")]
     Polars(#[from] polars::error::PolarsError),
     #[error("Parsing error: {0}")]
@@ -40,7 +49,11 @@ pub struct ProfilingConfig {
     pub max_categorical_cardinality: usize,
     pub quality_weights: QualityWeights,
     pub temporal_formats: Vec<String>,
+    pub date_formats: Vec<String>,
     pub enable_advanced_stats: bool,
+    pub integer_as_categorical_threshold: usize,
+    pub histogram_bins: usize,
+    pub binning_method: BinningMethod,
 }
 #[derive(Debug, Clone)]
 pub struct QualityWeights {
@@ -77,10 +90,60 @@ impl Default for ProfilingConfig {
                 "%d/%m/%Y".to_string(),
                 "%Y%m%d".to_string(),
             ],
+            date_formats: Vec::new(),
             enable_advanced_stats: false,
+            integer_as_categorical_threshold: 0,
+            histogram_bins: 10,
+            binning_method: BinningMethod::EqualWidth,
         }
     }
 }
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BinningMethod {
+    #[default]
+    EqualWidth,
+    Quantile,
+}
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bin {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: usize,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GeoRole {
+    Latitude,
+    Longitude,
+    Region,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SemanticType {
+    Currency,
+    Percentage,
+    Count,
+    Ratio,
+    Identifier,
+}
+impl SemanticType {
+    pub fn is_measure(&self) -> bool {
+        !matches!(self, SemanticType::Identifier)
+    }
+    pub fn axis_format(&self) -> Option<&'static str> {
+        match self {
+            SemanticType::Currency => Some("$,.2f"),
+            SemanticType::Percentage => Some(".1%"),
+            SemanticType::Ratio => Some(".2f"),
+            SemanticType::Count => Some(",d"),
+            SemanticType::Identifier => None,
+        }
+    }
+}
+#[cfg(feature = "native")]
+struct AnnotatedNumeric {
+    semantic_type: SemanticType,
+    series: Series,
+    confidence: f64,
+}
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DimensionProfile {
     pub name: String,
@@ -95,6 +158,16 @@ pub struct DimensionProfile {
     pub quality_score: f64,
     pub type_confidence: f64,
     pub issues: Vec<String>,
+    #[serde(default)]
+    pub geo_role: Option<GeoRole>,
+    #[serde(default)]
+    pub semantic_type: Option<SemanticType>,
+    #[serde(default)]
+    pub overridden_from: Option<DataType>,
+    #[serde(default)]
+    pub histogram: Option<Vec<Bin>>,
+    #[serde(default)]
+    pub date_format: Option<String>,
 }
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NumericStats {
@@ -125,6 +198,8 @@ pub struct DatasetSummary {
     pub numeric_count: usize,
     pub categorical_count: usize,
     pub temporal_count: usize,
+    #[serde(default)]
+    pub geospatial_count: usize,
     pub avg_quality_score: f64,
     pub total_issues: usize,
     pub chart_readiness_score: f64,
@@ -141,6 +216,82 @@ impl DataProfiler {
     pub fn with_config(config: ProfilingConfig) -> Self {
         Self { config }
     }
+    pub fn config(&self) -> &ProfilingConfig {
+        &self.config
+    }
+    pub fn get_dataset_summary(&self, profiles: &[DimensionProfile]) -> DatasetSummary {
+        let total_dimensions = profiles.len();
+        let (numeric_count, categorical_count, temporal_count, geospatial_count) = profiles
+            .iter()
+            .fold((0, 0, 0, 0), |(num, cat, temp, geo), p| match p.data_type {
+                DataType::Numeric => (num + 1, cat, temp, geo),
+                DataType::Categorical => (num, cat + 1, temp, geo),
+                DataType::Temporal => (num, cat, temp + 1, geo),
+                DataType::Geospatial => (num, cat, temp, geo + 1),
+            });
+        let avg_quality_score = if total_dimensions > 0 {
+            profiles.iter().map(|p| p.quality_score).sum::<f64>() / total_dimensions as f64
+        } else {
+            0.0
+        };
+        let total_issues = profiles.iter().map(|p| p.issues.len()).sum();
+        let chart_readiness_score = self.calculate_chart_readiness_score(profiles);
+        DatasetSummary {
+            total_dimensions,
+            numeric_count,
+            categorical_count,
+            temporal_count,
+            geospatial_count,
+            avg_quality_score,
+            total_issues,
+            chart_readiness_score,
+        }
+    }
+    fn calculate_chart_readiness_score(&self, profiles: &[DimensionProfile]) -> f64 {
+        if profiles.is_empty() {
+            return 0.0;
+        }
+        let mut score = 0.0;
+        score +=
+            (profiles.iter().map(|p| p.quality_score).sum::<f64>() / profiles.len() as f64) * 0.5;
+        let has_numeric = profiles
+            .iter()
+            .any(|p| matches!(p.data_type, DataType::Numeric));
+        let has_categorical = profiles
+            .iter()
+            .any(|p| matches!(p.data_type, DataType::Categorical));
+        let has_temporal = profiles
+            .iter()
+            .any(|p| matches!(p.data_type, DataType::Temporal));
+        score += ([has_numeric, has_categorical, has_temporal]
+            .iter()
+            .filter(|&&x| x)
+            .count() as f64
+            / 3.0)
+            * 0.3;
+        score += (match profiles.len() {
+            0 => 0.0,
+            1 => 0.3,
+            2..=8 => 1.0,
+            9..=15 => 0.8,
+            _ => 0.6,
+        }) * 0.2;
+        score.min(1.0)
+    }
+    pub fn export_profiles_json(
+        &self,
+        profiles: &[DimensionProfile],
+    ) -> Result<String, ProfilerError> {
+        serde_json::to_string_pretty(profiles)
+            .map_err(|e| ProfilerError::Parsing(format!("JSON serialisation failed: {e}")))
+    }
+    pub fn export_summary_json(&self, summary: &DatasetSummary) -> Result<String, ProfilerError> {
+        serde_json::to_string_pretty(summary)
+            .map_err(|e| ProfilerError::Parsing(format!("JSON serialisation failed: {e}")))
+    }
+}
+#[cfg(feature = "native")]
+impl DataProfiler {
     pub fn profile_csv<P: AsRef<Path>>(
         &self,
         path: P,
@@ -172,14 +323,12 @@ impl DataProfiler {
         let total_rows = df.height();
         df.get_columns()
             .par_iter()
-            .map(|column| {
-                self.profile_column(
-                    column.as_series().expect("Column should contain a series"),
-                    total_rows,
-                )
-            })
+            .map(|column| self.profile_column(column.as_materialized_series(), total_rows))
             .collect()
     }
+    pub fn profiler_builder(&self) -> IncrementalProfiler {
+        IncrementalProfiler::new(self.config.clone())
+    }
     fn profile_column(
         &self,
         column: &Series,
@@ -192,26 +341,69 @@ impl DataProfiler {
         } else {
             0.0
         };
-        let (data_type, type_confidence) = self.detect_data_type(column)?;
+        let original = column;
+        let annotated = self.parse_annotated_numeric(original)?;
+        let column = annotated.as_ref().map_or(original, |a| &a.series);
+        let (detected_type, detected_confidence) = match &annotated {
+            Some(a) => (DataType::Numeric, a.confidence),
+            None => self.detect_data_type(column)?,
+        };
+        let overridden_from = self.integer_category_override(column, &detected_type)?;
+        let detected_type = if overridden_from.is_some() {
+            DataType::Categorical
+        } else {
+            detected_type
+        };
+        let geo_role = self.detect_geo_role(column, &detected_type)?;
+        let (data_type, type_confidence) = match geo_role {
+            Some(_) => (DataType::Geospatial, detected_confidence.max(0.9)),
+            None => (detected_type, detected_confidence),
+        };
         let mut numeric_stats = None;
         let mut temporal_stats = None;
         let mut cardinality = None;
+        let mut histogram = None;
+        let mut date_format = None;
         match data_type {
             DataType::Numeric => {
                 let s_float = column.cast(&polars::prelude::DataType::Float64)?;
                 numeric_stats = Some(self.calculate_numeric_stats(&s_float)?);
+                if self.config.enable_advanced_stats {
+                    let values: Vec<f64> = s_float.f64()?.into_iter().flatten().collect();
+                    histogram = Some(compute_histogram(
+                        &values,
+                        self.config.histogram_bins,
+                        self.config.binning_method,
+                    ));
+                }
             }
             DataType::Temporal => {
                 let s_str = column.cast(&polars::prelude::DataType::String)?;
                 let str_chunked = s_str.str()?;
                 let values: Vec<Option<&str>> = str_chunked.into_iter().collect();
-                temporal_stats = Some(self.calculate_temporal_stats_simple(&values)?);
+                date_format = self.match_temporal_format(&values).map(|(format, _)| format);
+                temporal_stats = Some(self.calculate_temporal_stats_simple(
+                    &values,
+                    date_format.as_deref(),
+                )?);
             }
             DataType::Categorical => {
                 cardinality = Some(column.n_unique()?);
             }
+            DataType::Geospatial => {
+                if geo_role == Some(GeoRole::Region) {
+                    cardinality = Some(column.n_unique()?);
+                } else {
+                    let s_float = column.cast(&polars::prelude::DataType::Float64)?;
+                    numeric_stats = Some(self.calculate_numeric_stats(&s_float)?);
+                }
+            }
         }
-        let sample_values = self.get_sample_values(column)?;
+        let semantic_type = match &annotated {
+            Some(a) => Some(a.semantic_type),
+            None => self.infer_semantic_type(column, &data_type)?,
+        };
+        let sample_values = self.get_sample_values(original)?;
         let issues = self.detect_quality_issues(
             &data_type,
             null_percentage,
@@ -239,8 +431,138 @@ impl DataProfiler {
             quality_score,
             type_confidence,
             issues,
+            geo_role,
+            semantic_type,
+            overridden_from,
+            histogram,
+            date_format,
         })
     }
+    fn integer_category_override(
+        &self,
+        column: &Series,
+        detected_type: &DataType,
+    ) -> Result<Option<DataType>, ProfilerError> {
+        let threshold = self.config.integer_as_categorical_threshold;
+        if threshold == 0 || *detected_type != DataType::Numeric || !column.dtype().is_integer() {
+            return Ok(None);
+        }
+        let unique = column.drop_nulls().n_unique()?;
+        Ok((unique < threshold).then_some(DataType::Numeric))
+    }
+    fn parse_annotated_numeric(
+        &self,
+        column: &Series,
+    ) -> Result<Option<AnnotatedNumeric>, ProfilerError> {
+        if !matches!(column.dtype(), polars::prelude::DataType::String) {
+            return Ok(None);
+        }
+        let str_ca = column.str()?;
+        let non_null = str_ca.len() - str_ca.null_count();
+        if non_null == 0 {
+            return Ok(None);
+        }
+        let mut currency_hits = 0usize;
+        let mut percent_hits = 0usize;
+        let parsed: Vec<Option<f64>> = str_ca
+            .into_iter()
+            .map(|opt| {
+                let raw = opt?.trim();
+                let (body, semantic) = strip_unit_annotation(raw)?;
+                let value = body.replace([',', '_', ' '], "").parse::<f64>().ok()?;
+                match semantic {
+                    SemanticType::Currency => currency_hits += 1,
+                    SemanticType::Percentage => percent_hits += 1,
+                    _ => {}
+                }
+                Some(if semantic == SemanticType::Percentage {
+                    value / 100.0
+                } else {
+                    value
+                })
+            })
+            .collect();
+        let (semantic_type, hits) = if currency_hits >= percent_hits {
+            (SemanticType::Currency, currency_hits)
+        } else {
+            (SemanticType::Percentage, percent_hits)
+        };
+        let confidence = hits as f64 / non_null as f64;
+        if hits == 0 || confidence < self.config.type_confidence_threshold {
+            return Ok(None);
+        }
+        Ok(Some(AnnotatedNumeric {
+            semantic_type,
+            series: Series::new(column.name().clone(), parsed),
+            confidence,
+        }))
+    }
+    fn infer_semantic_type(
+        &self,
+        column: &Series,
+        data_type: &DataType,
+    ) -> Result<Option<SemanticType>, ProfilerError> {
+        let non_null = column.len() - column.null_count();
+        let facts = match data_type {
+            DataType::Categorical => ColumnFacts {
+                name: column.name(),
+                non_null,
+                unique: column.n_unique()?,
+                min: None,
+                max: None,
+                all_integral: false,
+            },
+            DataType::Numeric => {
+                let s_float = column.cast(&polars::prelude::DataType::Float64)?;
+                let ca = s_float.f64()?;
+                ColumnFacts {
+                    name: column.name(),
+                    non_null,
+                    unique: s_float.n_unique()?,
+                    min: ca.min(),
+                    max: ca.max(),
+                    all_integral: ca.into_iter().flatten().all(|v| v.fract() == 0.0),
+                }
+            }
+            _ => return Ok(None),
+        };
+        Ok(semantic_type_from_facts(data_type, &facts))
+    }
+    fn detect_geo_role(
+        &self,
+        column: &Series,
+        data_type: &DataType,
+    ) -> Result<Option<GeoRole>, ProfilerError> {
+        let Some(role) = geo_role_from_name(column.name()) else {
+            return Ok(None);
+        };
+        match (role, data_type) {
+            (GeoRole::Latitude | GeoRole::Longitude, DataType::Numeric) => {
+                let limit = if role == GeoRole::Latitude { 90.0 } else { 180.0 };
+                let s_float = column.cast(&polars::prelude::DataType::Float64)?;
+                let ca = s_float.f64()?;
+                let in_range = match (ca.min(), ca.max()) {
+                    (Some(min), Some(max)) => min >= -limit && max <= limit,
+                    _ => false,
+                };
+                Ok(in_range.then_some(role))
+            }
+            (GeoRole::Region, DataType::Categorical) => {
+                if is_strong_region_name(column.name()) {
+                    return Ok(Some(role));
+                }
+                let s_str = column.cast(&polars::prelude::DataType::String)?;
+                let values: Vec<&str> = s_str.str()?.into_iter().flatten().collect();
+                if values.is_empty() {
+                    return Ok(None);
+                }
+                let code_like = values.iter().filter(|v| is_region_code(v)).count();
+                let confidence = code_like as f64 / values.len() as f64;
+                Ok((confidence >= self.config.type_confidence_threshold).then_some(role))
+            }
+            _ => Ok(None),
+        }
+    }
     fn detect_data_type(&self, column: &Series) -> Result<(DataType, f64), ProfilerError> {
         let non_null_count = column.len() - column.null_count();
         if non_null_count == 0 {
@@ -371,31 +693,52 @@ impl DataProfiler {
             .collect())
     }
     fn test_temporal_parsing_simple(&self, values: &[Option<&str>]) -> Result<f64, ProfilerError> {
+        Ok(self
+            .match_temporal_format(values)
+            .map_or(0.0, |(_, confidence)| confidence))
+    }
+    fn match_temporal_format(&self, values: &[Option<&str>]) -> Option<(String, f64)> {
         let non_null_values: Vec<_> = values.iter().filter_map(|&v| v).collect();
         if non_null_values.is_empty() {
-            return Ok(0.0);
+            return None;
         }
         let total_count = non_null_values.len();
-        let mut best_confidence = 0.0;
-        for format in &self.config.temporal_formats {
+        let confidence_for = |format: &str| {
             let successful_parses = non_null_values
                 .par_iter()
                 .filter(|&&v| self.parse_datetime_simple(v, format).is_some())
                 .count();
-            let confidence = successful_parses as f64 / total_count as f64;
-            best_confidence = f64::max(best_confidence, confidence);
+            successful_parses as f64 / total_count as f64
+        };
+        for format in &self.config.date_formats {
+            let confidence = confidence_for(format);
+            if confidence >= self.config.type_confidence_threshold {
+                return Some((format.clone(), confidence));
+            }
         }
-        Ok(best_confidence)
+        let mut best: Option<(String, f64)> = None;
+        for format in &self.config.temporal_formats {
+            let confidence = confidence_for(format);
+            if best.as_ref().is_none_or(|(_, b)| confidence > *b) {
+                best = Some((format.clone(), confidence));
+            }
+        }
+        best.filter(|(_, confidence)| *confidence > 0.0)
     }
     fn calculate_temporal_stats_simple(
         &self,
         values: &[Option<&str>],
+        preferred_format: Option<&str>,
     ) -> Result<TemporalStats, ProfilerError> {
         let non_null_values: Vec<_> = values.iter().filter_map(|&v| v).collect();
+        let formats: Vec<&str> = preferred_format
+            .into_iter()
+            .chain(self.config.temporal_formats.iter().map(String::as_str))
+            .collect();
         let mut datetime_values = Vec::new();
         let mut has_time = false;
         for value in &non_null_values {
-            for format in &self.config.temporal_formats {
+            for format in &formats {
                 if let Some(dt) = self.parse_datetime_simple(value, format) {
                     datetime_values.push(dt);
                     if format.contains("%H") || format.contains("%M") || format.contains("%S") {
@@ -559,6 +902,13 @@ impl DataProfiler {
                     }
                 }
             }
+            DataType::Geospatial => {
+                if let Some(card) = cardinality {
+                    if card == 1 && total_count > 1 {
+                        issues.push("Single location (constant geospatial column)".to_string());
+                    }
+                }
+            }
             DataType::Temporal => {
                 if let Some(card) = cardinality {
                     if card == 1 && total_count > 1 {
@@ -604,74 +954,421 @@ impl DataProfiler {
         }
         score.clamp(0.0, 1.0)
     }
-    pub fn get_dataset_summary(&self, profiles: &[DimensionProfile]) -> DatasetSummary {
-        let total_dimensions = profiles.len();
-        let (numeric_count, categorical_count, temporal_count) =
-            profiles
-                .iter()
-                .fold((0, 0, 0), |(num, cat, temp), p| match p.data_type {
-                    DataType::Numeric => (num + 1, cat, temp),
-                    DataType::Categorical => (num, cat + 1, temp),
-                    DataType::Temporal => (num, cat, temp + 1),
+}
+#[cfg(feature = "native")]
+pub const INCREMENTAL_DISTINCT_CAP: usize = 100_000;
+#[cfg(feature = "native")]
+pub struct IncrementalProfiler {
+    profiler: DataProfiler,
+    formats: Vec<String>,
+    rows_seen: usize,
+    columns: Vec<ColumnAccumulator>,
+}
+#[cfg(feature = "native")]
+impl IncrementalProfiler {
+    fn new(config: ProfilingConfig) -> Self {
+        let formats = config
+            .date_formats
+            .iter()
+            .chain(&config.temporal_formats)
+            .cloned()
+            .collect();
+        Self {
+            profiler: DataProfiler::with_config(config),
+            formats,
+            rows_seen: 0,
+            columns: Vec::new(),
+        }
+    }
+    pub fn rows_seen(&self) -> usize {
+        self.rows_seen
+    }
+    pub fn update_batch(&mut self, df: &DataFrame) -> Result<(), ProfilerError> {
+        let height = df.height();
+        let mut present = vec![false; self.columns.len()];
+        for column in df.get_columns() {
+            let series = column.as_materialized_series();
+            let index = match self.columns.iter().position(|c| c.name == series.name().as_str()) {
+                Some(index) => index,
+                None => {
+                    self.columns.push(ColumnAccumulator::new(
+                        series.name().to_string(),
+                        self.rows_seen,
+                        self.formats.len(),
+                    ));
+                    present.push(false);
+                    self.columns.len() - 1
+                }
+            };
+            present[index] = true;
+            self.columns[index].update(&self.profiler, &self.formats, series)?;
+        }
+        for (column, _) in self.columns.iter_mut().zip(present).filter(|(_, p)| !p) {
+            column.total_count += height;
+            column.null_count += height;
+        }
+        self.rows_seen += height;
+        Ok(())
+    }
+    pub fn finalize(self) -> Vec<DimensionProfile> {
+        self.columns
+            .into_iter()
+            .map(|column| column.finish(&self.profiler, &self.formats))
+            .collect()
+    }
+}
+#[cfg(feature = "native")]
+#[derive(Debug, Clone, Default)]
+struct RunningMoments {
+    count: usize,
+    mean: f64,
+    m2: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+    fractional: bool,
+}
+#[cfg(feature = "native")]
+impl RunningMoments {
+    fn push(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+        self.min = Some(self.min.map_or(value, |m| m.min(value)));
+        self.max = Some(self.max.map_or(value, |m| m.max(value)));
+        self.fractional |= value.fract() != 0.0;
+    }
+    fn stats(&self) -> NumericStats {
+        NumericStats {
+            mean: (self.count > 0).then_some(self.mean),
+            median: None,
+            std: (self.count > 1).then(|| (self.m2 / (self.count - 1) as f64).sqrt()),
+            min: self.min,
+            max: self.max,
+            q25: None,
+            q75: None,
+            skewness: None,
+            kurtosis: None,
+            mad: None,
+            outlier_count: 0,
+        }
+    }
+}
+#[cfg(feature = "native")]
+#[derive(Debug, Clone, Default)]
+struct TemporalMatch {
+    hits: usize,
+    first: Option<DateTime<chrono::Utc>>,
+    last: Option<DateTime<chrono::Utc>>,
+}
+#[cfg(feature = "native")]
+#[derive(Debug, Clone)]
+struct ColumnAccumulator {
+    name: String,
+    total_count: usize,
+    null_count: usize,
+    native_numeric: bool,
+    integer: bool,
+    string: bool,
+    cast: RunningMoments,
+    annotated: RunningMoments,
+    currency_hits: usize,
+    percent_hits: usize,
+    code_like: usize,
+    temporal: Vec<TemporalMatch>,
+    distinct: HashSet<String>,
+    samples: Vec<String>,
+}
+#[cfg(feature = "native")]
+impl ColumnAccumulator {
+    fn new(name: String, rows_seen: usize, format_count: usize) -> Self {
+        Self {
+            name,
+            total_count: rows_seen,
+            null_count: rows_seen,
+            native_numeric: true,
+            integer: true,
+            string: true,
+            cast: RunningMoments::default(),
+            annotated: RunningMoments::default(),
+            currency_hits: 0,
+            percent_hits: 0,
+            code_like: 0,
+            temporal: vec![TemporalMatch::default(); format_count],
+            distinct: HashSet::new(),
+            samples: Vec::new(),
+        }
+    }
+    fn update(
+        &mut self,
+        profiler: &DataProfiler,
+        formats: &[String],
+        series: &Series,
+    ) -> Result<(), ProfilerError> {
+        self.total_count += series.len();
+        self.null_count += series.null_count();
+        let native_numeric = matches!(
+            series.dtype(),
+            polars::prelude::DataType::Float64
+                | polars::prelude::DataType::Int64
+                | polars::prelude::DataType::Float32
+                | polars::prelude::DataType::Int32
+        );
+        self.native_numeric &= native_numeric;
+        self.integer &= series.dtype().is_integer();
+        self.string &= matches!(series.dtype(), polars::prelude::DataType::String);
+        if let Ok(s_float) = series.cast(&polars::prelude::DataType::Float64) {
+            s_float.f64()?.into_iter().flatten().for_each(|v| self.cast.push(v));
+        }
+        let s_str = series.cast(&polars::prelude::DataType::String)?;
+        let values: Vec<&str> = s_str.str()?.into_iter().flatten().collect();
+        for value in &values {
+            if is_region_code(value) {
+                self.code_like += 1;
+            }
+            if self.distinct.len() < INCREMENTAL_DISTINCT_CAP
+                && self.distinct.insert(value.to_string())
+                && self.samples.len() < profiler.config.max_sample_values
+            {
+                self.samples.push(value.to_string());
+            }
+        }
+        if self.string {
+            for value in &values {
+                let Some((body, semantic)) = strip_unit_annotation(value.trim()) else {
+                    continue;
+                };
+                let Ok(parsed) = body.replace([',', '_', ' '], "").parse::<f64>() else {
+                    continue;
+                };
+                match semantic {
+                    SemanticType::Currency => self.currency_hits += 1,
+                    SemanticType::Percentage => self.percent_hits += 1,
+                    _ => {}
+                }
+                self.annotated.push(if semantic == SemanticType::Percentage {
+                    parsed / 100.0
+                } else {
+                    parsed
                 });
-        let avg_quality_score = if total_dimensions > 0 {
-            profiles.iter().map(|p| p.quality_score).sum::<f64>() / total_dimensions as f64
+            }
+        }
+        if !native_numeric {
+            for (format, matched) in formats.iter().zip(&mut self.temporal) {
+                let parsed: Vec<DateTime<chrono::Utc>> = values
+                    .par_iter()
+                    .filter_map(|v| profiler.parse_datetime_simple(v, format))
+                    .collect();
+                matched.hits += parsed.len();
+                matched.first = parsed.iter().copied().chain(matched.first).min();
+                matched.last = parsed.iter().copied().chain(matched.last).max();
+            }
+        }
+        Ok(())
+    }
+    fn non_null(&self) -> usize {
+        self.total_count - self.null_count
+    }
+    fn unique(&self) -> usize {
+        self.distinct.len() + usize::from(self.null_count > 0)
+    }
+    fn annotated_semantic(&self, config: &ProfilingConfig) -> Option<(SemanticType, f64)> {
+        if !self.string || self.non_null() == 0 {
+            return None;
+        }
+        let (semantic_type, hits) = if self.currency_hits >= self.percent_hits {
+            (SemanticType::Currency, self.currency_hits)
         } else {
-            0.0
+            (SemanticType::Percentage, self.percent_hits)
         };
-        let total_issues = profiles.iter().map(|p| p.issues.len()).sum();
-        let chart_readiness_score = self.calculate_chart_readiness_score(profiles);
-        DatasetSummary {
-            total_dimensions,
-            numeric_count,
-            categorical_count,
-            temporal_count,
-            avg_quality_score,
-            total_issues,
-            chart_readiness_score,
+        let confidence = hits as f64 / self.non_null() as f64;
+        (hits > 0 && confidence >= config.type_confidence_threshold)
+            .then_some((semantic_type, confidence))
+    }
+    fn temporal_format<'f>(
+        &self,
+        config: &ProfilingConfig,
+        formats: &'f [String],
+    ) -> Option<(usize, &'f str, f64)> {
+        let non_null = self.non_null();
+        if non_null == 0 {
+            return None;
         }
+        let confidence_at = |index: usize| self.temporal[index].hits as f64 / non_null as f64;
+        let date_formats = config.date_formats.len();
+        if let Some(index) = (0..date_formats)
+            .find(|&index| confidence_at(index) >= config.type_confidence_threshold)
+        {
+            return Some((index, formats[index].as_str(), confidence_at(index)));
+        }
+        let mut best: Option<(usize, f64)> = None;
+        for index in date_formats..formats.len() {
+            let confidence = confidence_at(index);
+            if best.is_none_or(|(_, b)| confidence > b) {
+                best = Some((index, confidence));
+            }
+        }
+        best.filter(|(_, confidence)| *confidence > 0.0)
+            .map(|(index, confidence)| (index, formats[index].as_str(), confidence))
     }
-    fn calculate_chart_readiness_score(&self, profiles: &[DimensionProfile]) -> f64 {
-        if profiles.is_empty() {
-            return 0.0;
+    fn detect_data_type(&self, config: &ProfilingConfig, formats: &[String]) -> (DataType, f64) {
+        let non_null = self.non_null();
+        if non_null == 0 {
+            return (DataType::Categorical, 0.0);
+        }
+        if self.native_numeric {
+            return (DataType::Numeric, 1.0);
+        }
+        let confidence = self.cast.count as f64 / non_null as f64;
+        if confidence >= config.type_confidence_threshold {
+            if self.unique() == 1 {
+                return (DataType::Categorical, 0.9);
+            }
+            return (DataType::Numeric, confidence);
+        }
+        match self.temporal_format(config, formats) {
+            Some((_, _, confidence)) if confidence >= config.type_confidence_threshold => {
+                (DataType::Temporal, confidence)
+            }
+            _ => (DataType::Categorical, 0.8),
         }
-        let mut score = 0.0;
-        score +=
-            (profiles.iter().map(|p| p.quality_score).sum::<f64>() / profiles.len() as f64) * 0.5;
-        let has_numeric = profiles
-            .iter()
-            .any(|p| matches!(p.data_type, DataType::Numeric));
-        let has_categorical = profiles
-            .iter()
-            .any(|p| matches!(p.data_type, DataType::Categorical));
-        let has_temporal = profiles
-            .iter()
-            .any(|p| matches!(p.data_type, DataType::Temporal));
-        score += ([has_numeric, has_categorical, has_temporal]
-            .iter()
-            .filter(|&&x| x)
-            .count() as f64
-            / 3.0)
-            * 0.3;
-        score += (match profiles.len() {
-            0 => 0.0,
-            1 => 0.3,
-            2..=8 => 1.0,
-            9..=15 => 0.8,
-            _ => 0.6,
-        }) * 0.2;
-        score.min(1.0)
     }
-    pub fn export_profiles_json(
-        &self,
-        profiles: &[DimensionProfile],
-    ) -> Result<String, ProfilerError> {
-        serde_json::to_string_pretty(profiles)
-            .map_err(|e| ProfilerError::Parsing(format!("JSON serialisation failed: {e}")))
+    fn temporal_stats(&self, profiler: &DataProfiler, index: usize, format: &str) -> TemporalStats {
+        let matched = &self.temporal[index];
+        let mut distinct: Vec<DateTime<chrono::Utc>> = self
+            .distinct
+            .iter()
+            .filter_map(|v| profiler.parse_datetime_simple(v, format))
+            .collect();
+        distinct.sort();
+        distinct.dedup();
+        TemporalStats {
+            min_date: matched.first.map(|dt| dt.to_rfc3339()),
+            max_date: matched.last.map(|dt| dt.to_rfc3339()),
+            date_range_days: matched
+                .first
+                .zip(matched.last)
+                .map(|(first, last)| last.signed_duration_since(first).num_days()),
+            inferred_frequency: profiler.infer_temporal_frequency_simple(&distinct),
+            has_time_component: matched.hits > 0
+                && (format.contains("%H") || format.contains("%M") || format.contains("%S")),
+            unique_count: distinct.len(),
+        }
     }
-    pub fn export_summary_json(&self, summary: &DatasetSummary) -> Result<String, ProfilerError> {
-        serde_json::to_string_pretty(summary)
-            .map_err(|e| ProfilerError::Parsing(format!("JSON serialisation failed: {e}")))
+    fn finish(self, profiler: &DataProfiler, formats: &[String]) -> DimensionProfile {
+        let config = &profiler.config;
+        let total_rows = self.total_count;
+        let non_null = self.non_null();
+        let null_percentage = if total_rows > 0 {
+            self.null_count as f64 / total_rows as f64
+        } else {
+            0.0
+        };
+        let annotated = self.annotated_semantic(config);
+        let (moments, (detected_type, detected_confidence)) = match annotated {
+            Some((_, confidence)) => (&self.annotated, (DataType::Numeric, confidence)),
+            None => (&self.cast, self.detect_data_type(config, formats)),
+        };
+        let threshold = config.integer_as_categorical_threshold;
+        let overridden_from = (threshold > 0
+            && detected_type == DataType::Numeric
+            && self.integer
+            && self.distinct.len() < threshold)
+            .then_some(DataType::Numeric);
+        let detected_type = if overridden_from.is_some() {
+            DataType::Categorical
+        } else {
+            detected_type
+        };
+        let geo_role = geo_role_from_name(&self.name).and_then(|role| match (role, &detected_type) {
+            (GeoRole::Latitude | GeoRole::Longitude, DataType::Numeric) => {
+                let limit = if role == GeoRole::Latitude { 90.0 } else { 180.0 };
+                let in_range = match (moments.min, moments.max) {
+                    (Some(min), Some(max)) => min >= -limit && max <= limit,
+                    _ => false,
+                };
+                in_range.then_some(role)
+            }
+            (GeoRole::Region, DataType::Categorical) => {
+                let confidence = self.code_like as f64 / non_null.max(1) as f64;
+                (is_strong_region_name(&self.name)
+                    || (non_null > 0 && confidence >= config.type_confidence_threshold))
+                    .then_some(role)
+            }
+            _ => None,
+        });
+        let (data_type, type_confidence) = match geo_role {
+            Some(_) => (DataType::Geospatial, detected_confidence.max(0.9)),
+            None => (detected_type, detected_confidence),
+        };
+        let mut numeric_stats = None;
+        let mut temporal_stats = None;
+        let mut cardinality = None;
+        let mut date_format = None;
+        match data_type {
+            DataType::Numeric => numeric_stats = Some(moments.stats()),
+            DataType::Temporal => {
+                if let Some((index, format, _)) = self.temporal_format(config, formats) {
+                    temporal_stats = Some(self.temporal_stats(profiler, index, format));
+                    date_format = Some(format.to_string());
+                }
+            }
+            DataType::Categorical => cardinality = Some(self.unique()),
+            DataType::Geospatial => {
+                if geo_role == Some(GeoRole::Region) {
+                    cardinality = Some(self.unique());
+                } else {
+                    numeric_stats = Some(moments.stats());
+                }
+            }
+        }
+        let semantic_type = match annotated {
+            Some((semantic_type, _)) => Some(semantic_type),
+            None => semantic_type_from_facts(
+                &data_type,
+                &ColumnFacts {
+                    name: &self.name,
+                    non_null,
+                    unique: self.unique(),
+                    min: moments.min,
+                    max: moments.max,
+                    all_integral: !moments.fractional,
+                },
+            ),
+        };
+        let issues = profiler.detect_quality_issues(
+            &data_type,
+            null_percentage,
+            cardinality,
+            &numeric_stats,
+            total_rows,
+        );
+        let quality_score = profiler.calculate_quality_score(
+            null_percentage,
+            type_confidence,
+            cardinality,
+            &issues,
+            &numeric_stats,
+        );
+        DimensionProfile {
+            name: self.name,
+            data_type,
+            cardinality,
+            total_count: total_rows,
+            null_count: self.null_count,
+            null_percentage,
+            sample_values: self.samples,
+            numeric_stats,
+            temporal_stats,
+            quality_score,
+            type_confidence,
+            issues,
+            geo_role,
+            semantic_type,
+            overridden_from,
+            histogram: None,
+            date_format,
+        }
     }
 }
 impl Default for DataProfiler {
@@ -717,6 +1414,10 @@ impl DimensionProfile {
                 roles.push("x-axis".to_string());
                 roles.push("timeline".to_string());
             }
+            DataType::Geospatial => {
+                roles.push("location".to_string());
+                roles.push("map".to_string());
+            }
         }
         roles
     }
@@ -744,6 +1445,7 @@ impl DatasetSummary {
         dist.insert("Numeric".to_string(), self.numeric_count);
         dist.insert("Categorical".to_string(), self.categorical_count);
         dist.insert("Temporal".to_string(), self.temporal_count);
+        dist.insert("Geospatial".to_string(), self.geospatial_count);
         dist
     }
     pub fn get_chart_recommendations(&self) -> Vec<String> {
@@ -766,6 +1468,10 @@ impl DatasetSummary {
         if self.categorical_count >= 1 {
             recs.push("pie".to_string());
         }
+        if self.geospatial_count >= 1 {
+            recs.push("scatter_geo".to_string());
+            recs.push("choropleth".to_string());
+        }
         recs
     }
     pub fn report(&self) -> String {
@@ -775,6 +1481,7 @@ impl DatasetSummary {
         report.push_str(&format!("  - Numeric: {}\n", self.numeric_count));
         report.push_str(&format!("  - Categorical: {}\n", self.categorical_count));
         report.push_str(&format!("  - Temporal: {}\n", self.temporal_count));
+        report.push_str(&format!("  - Geospatial: {}\n", self.geospatial_count));
         report.push_str("\nQuality Metrics:\n");
         report.push_str(&format!(
             "  - Average Quality Score: {:.2}\n",
@@ -847,3 +1554,153 @@ impl ProfilingConfig {
         }
     }
 }
+pub fn compute_histogram(values: &[f64], bin_count: usize, method: BinningMethod) -> Vec<Bin> {
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    if sorted.is_empty() || bin_count == 0 {
+        return Vec::new();
+    }
+    sorted.sort_by(f64::total_cmp);
+    let min = sorted[0];
+    let max = sorted[sorted.len() - 1];
+    let mut edges: Vec<f64> = match method {
+        BinningMethod::EqualWidth => {
+            let width = (max - min) / bin_count as f64;
+            (0..=bin_count).map(|i| min + width * i as f64).collect()
+        }
+        BinningMethod::Quantile => (0..=bin_count)
+            .map(|i| {
+                let rank = (sorted.len() - 1) as f64 * i as f64 / bin_count as f64;
+                let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+                sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+            })
+            .collect(),
+    };
+    edges[bin_count] = max;
+    edges.dedup();
+    if edges.len() < 2 {
+        return vec![Bin {
+            lower: min,
+            upper: max,
+            count: sorted.len(),
+        }];
+    }
+    let mut bins: Vec<Bin> = edges
+        .windows(2)
+        .map(|w| Bin {
+            lower: w[0],
+            upper: w[1],
+            count: 0,
+        })
+        .collect();
+    let last = bins.len() - 1;
+    for value in sorted {
+        let index = bins
+            .iter()
+            .position(|bin| value < bin.upper)
+            .unwrap_or(last);
+        bins[index].count += 1;
+    }
+    bins
+}
+fn name_tokens(name: &str) -> Vec<String> {
+    name.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+fn is_strong_region_name(name: &str) -> bool {
+    name_tokens(name).iter().any(|t| {
+        matches!(
+            t.as_str(),
+            "country" | "countries" | "iso" | "iso2" | "iso3" | "fips" | "province"
+        )
+    })
+}
+pub fn geo_role_from_name(name: &str) -> Option<GeoRole> {
+    let tokens = name_tokens(name);
+    let has = |candidates: &[&str]| tokens.iter().any(|t| candidates.contains(&t.as_str()));
+    if has(&["lat", "latitude"]) {
+        Some(GeoRole::Latitude)
+    } else if has(&["lon", "lng", "long", "longitude"]) {
+        Some(GeoRole::Longitude)
+    } else if is_strong_region_name(name) || has(&["region", "location", "state"]) {
+        Some(GeoRole::Region)
+    } else {
+        None
+    }
+}
+#[cfg(feature = "native")]
+fn is_region_code(value: &str) -> bool {
+    (2..=3).contains(&value.len()) && value.chars().all(|c| c.is_ascii_uppercase())
+}
+#[cfg(feature = "native")]
+struct ColumnFacts<'a> {
+    name: &'a str,
+    non_null: usize,
+    unique: usize,
+    min: Option<f64>,
+    max: Option<f64>,
+    all_integral: bool,
+}
+#[cfg(feature = "native")]
+fn semantic_type_from_facts(data_type: &DataType, facts: &ColumnFacts) -> Option<SemanticType> {
+    let tokens = name_tokens(facts.name);
+    let has = |candidates: &[&str]| tokens.iter().any(|t| candidates.contains(&t.as_str()));
+    let named_identifier = has(&["id", "uuid", "guid", "key", "index", "idx"]);
+    let non_null = facts.non_null;
+    match data_type {
+        DataType::Categorical => (named_identifier && non_null > 1 && facts.unique == non_null)
+            .then_some(SemanticType::Identifier),
+        DataType::Numeric => {
+            if named_identifier {
+                return Some(SemanticType::Identifier);
+            }
+            let temporal_name = has(&["year", "month", "week", "day", "hour"]);
+            if facts.all_integral && non_null > 2 && !temporal_name {
+                if let (Some(min), Some(max)) = (facts.min, facts.max) {
+                    if facts.unique == non_null && (max - min + 1.0 - non_null as f64).abs() < 0.5 {
+                        return Some(SemanticType::Identifier);
+                    }
+                }
+            }
+            if has(&[
+                "price", "cost", "revenue", "amount", "salary", "income", "spend", "budget",
+                "profit", "usd", "gbp", "eur",
+            ]) {
+                return Some(SemanticType::Currency);
+            }
+            if has(&["percent", "percentage", "pct"]) || facts.name.contains('%') {
+                return Some(SemanticType::Percentage);
+            }
+            let within_unit = match (facts.min, facts.max) {
+                (Some(min), Some(max)) => min >= 0.0 && max <= 1.0,
+                _ => false,
+            };
+            if has(&["ratio", "rate", "share", "proportion", "fraction"]) && within_unit {
+                return Some(SemanticType::Ratio);
+            }
+            let non_negative = facts.min.is_some_and(|m| m >= 0.0);
+            if has(&["count", "qty", "quantity", "num", "number"])
+                && facts.all_integral
+                && non_negative
+            {
+                return Some(SemanticType::Count);
+            }
+            None
+        }
+        _ => None,
+    }
+}
+#[cfg(feature = "native")]
+fn strip_unit_annotation(raw: &str) -> Option<(&str, SemanticType)> {
+    const CURRENCY_SYMBOLS: [char; 4] = ['$', '£', '€', '¥'];
+    if let Some(body) = raw.strip_suffix('%') {
+        return Some((body.trim_end(), SemanticType::Percentage));
+    }
+    if let Some(body) = raw.strip_prefix(CURRENCY_SYMBOLS) {
+        return Some((body.trim_start(), SemanticType::Currency));
+    }
+    raw.strip_suffix(CURRENCY_SYMBOLS)
+        .map(|body| (body.trim_end(), SemanticType::Currency))
+}
@@ -67,6 +67,7 @@ pub enum DataError {
         #[from]
         source: anyhow::Error,
     },
+    #[cfg(feature = "native")]
     #[error("Failed to read data file '{path}': {source}")]
     DataFileError {
         path: String,
@@ -81,6 +82,7 @@ pub enum DataError {
         "Data type detection failed for column '{column}': confidence too low ({confidence:.2})"
     )]
     LowTypeConfidence { column: String, confidence: f64 },
+    #[cfg(feature = "native")]
     #[error("Failed to calculate statistics for column '{column}': {source}")]
     StatisticsError {
         column: String,
@@ -95,10 +97,19 @@ pub enum DataError {
     UnsupportedFormat { format: String },
     #[error("Data quality too low: {reason}")]
     LowDataQuality { reason: String },
+    #[error("No files in '{directory}' match '{pattern}'")]
+    NoMatchingFiles { directory: String, pattern: String },
+    #[error("Schema of '{file}' does not match '{reference}': {details}")]
+    SchemaMismatch {
+        file: String,
+        reference: String,
+        details: String,
+    },
     #[error("Temporal parsing failed for column '{column}': {value}")]
     TemporalParsingError { column: String, value: String },
     #[error("Numeric conversion failed for column '{column}': {value}")]
     NumericConversionError { column: String, value: String },
+    #[cfg(feature = "native")]
     #[error("Cardinality calculation failed for column '{column}': {source}")]
     CardinalityError {
         column: String,
@@ -274,6 +285,11 @@ impl ChartSuggestionError {
                 "Check for outliers and invalid values".to_string(),
                 "Consider data transformation or filtering".to_string(),
             ],
+            ChartSuggestionError::Data(DataError::SchemaMismatch { .. }) => vec![
+                "Check that every matched file has the same header".to_string(),
+                "Make column types consistent across files".to_string(),
+                "Narrow the glob to exclude files with a different layout".to_string(),
+            ],
             ChartSuggestionError::Api(ApiError::ChartNotFound { .. }) => vec![
                 "Check the chart name spelling".to_string(),
                 "Verify the API configuration is loaded".to_string(),
@@ -491,12 +507,13 @@ pub mod utils {
         EnhancedError::new(error, context)
     }
     pub fn is_temporary_failure(error: &ChartSuggestionError) -> bool {
-        matches!(
-            error,
+        match error {
             ChartSuggestionError::Chart(ChartError::MatchingTimeout)
-                | ChartSuggestionError::Io(_)
-                | ChartSuggestionError::Data(DataError::DataFileError { .. })
-        )
+            | ChartSuggestionError::Io(_) => true,
+            #[cfg(feature = "native")]
+            ChartSuggestionError::Data(DataError::DataFileError { .. }) => true,
+            _ => false,
+        }
     }
     pub fn error_severity(error: &ChartSuggestionError) -> ErrorSeverity {
         match error {
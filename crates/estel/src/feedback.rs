@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+use crate::chart_matcher::RenderSpec;
+use crate::data_profiler::DimensionProfile;
+use crate::learned_scorer::{DatasetStats, FeatureVector};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+#[derive(Debug, Clone, Default)]
+pub struct FeedbackContext {
+    pub dataset: Option<String>,
+    pub stats: DatasetStats,
+    pub symbolic_scores: HashMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedbackRecord {
+    pub dataset: Option<String>,
+    pub chart_name: String,
+    pub library: String,
+    pub mappings: BTreeMap<String, String>,
+    pub dataset_stats: DatasetStats,
+    pub features: FeatureVector,
+    pub chosen: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FeedbackCollector {
+    context: FeedbackContext,
+    observed: HashMap<Option<String>, FeedbackContext>,
+    suggested: HashMap<(String, String), Option<String>>,
+    records: Vec<FeedbackRecord>,
+}
+
+impl FeedbackCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_context(&mut self, dataset: Option<&str>, profiles: &[DimensionProfile]) {
+        self.context = FeedbackContext {
+            dataset: dataset.map(str::to_string),
+            stats: DatasetStats::from_profiles(profiles),
+            symbolic_scores: HashMap::new(),
+        };
+        self.observed
+            .insert(self.context.dataset.clone(), self.context.clone());
+    }
+
+    pub fn set_symbolic_scores(&mut self, scores: HashMap<String, f64>) {
+        if let Some(observed) = self.observed.get_mut(&self.context.dataset) {
+            observed.symbolic_scores = scores.clone();
+        }
+        self.context.symbolic_scores = scores;
+    }
+
+    pub fn context(&self) -> &FeedbackContext {
+        &self.context
+    }
+
+    pub fn context_for(&self, dataset: Option<&str>) -> Option<&FeedbackContext> {
+        self.observed.get(&dataset.map(str::to_string))
+    }
+
+    pub fn observe_suggestions<'a>(&mut self, specs: impl IntoIterator<Item = &'a RenderSpec>) {
+        for spec in specs {
+            self.suggested
+                .insert(suggestion_key(spec), self.context.dataset.clone());
+        }
+    }
+
+    pub fn record(&mut self, spec: &RenderSpec, chosen: bool) -> &FeedbackRecord {
+        let context = self
+            .suggested
+            .get(&suggestion_key(spec))
+            .and_then(|dataset| self.observed.get(dataset))
+            .unwrap_or(&self.context)
+            .clone();
+        self.push(&context, spec, chosen)
+    }
+
+    fn push(
+        &mut self,
+        context: &FeedbackContext,
+        spec: &RenderSpec,
+        chosen: bool,
+    ) -> &FeedbackRecord {
+        let symbolic = context
+            .symbolic_scores
+            .get(&spec.chart_name)
+            .copied()
+            .unwrap_or(0.0);
+        let features = FeatureVector::from_spec(spec, &context.stats, symbolic);
+        self.records.push(FeedbackRecord {
+            dataset: context.dataset.clone(),
+            chart_name: spec.chart_name.clone(),
+            library: spec.library.clone(),
+            mappings: spec.mappings.clone(),
+            dataset_stats: context.stats.clone(),
+            features,
+            chosen,
+        });
+        self.records.last().expect("record was just pushed")
+    }
+
+    pub fn records(&self) -> &[FeedbackRecord] {
+        &self.records
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    pub fn to_jsonl(&self) -> Result<String, serde_json::Error> {
+        let mut out = String::new();
+        for record in &self.records {
+            out.push_str(&serde_json::to_string(record)?);
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    pub fn export_jsonl<P: AsRef<Path>>(&self, path: P) -> std::io::Result<usize> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        for record in &self.records {
+            serde_json::to_writer(&mut writer, record)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+        Ok(self.records.len())
+    }
+}
+
+fn suggestion_key(spec: &RenderSpec) -> (String, String) {
+    (spec.chart_name.clone(), spec.mapping_key())
+}
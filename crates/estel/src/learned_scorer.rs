@@ -4,7 +4,12 @@
 
 use crate::chart_matcher::RenderSpec;
 use crate::data_profiler::DimensionProfile;
+use crate::error::{Result, SerialisationError};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
 
 
 
@@ -77,6 +82,52 @@ impl LearnedScorer {
         s.clamp(0.0, 1.0)
     }
 
+    pub fn update(&mut self, features: &FeatureVector, target: f64, learning_rate: f64) {
+        let x = features.to_vec();
+        let mut linear = self.bias;
+        for (wi, xi) in self.weights.iter().zip(x.iter()) {
+            linear += wi * xi;
+        }
+        let gradient = linear - target.clamp(0.0, 1.0);
+        for (wi, xi) in self.weights.iter_mut().zip(x.iter()) {
+            *wi -= learning_rate * gradient * xi;
+        }
+        self.bias -= learning_rate * gradient;
+    }
+
+    pub fn save_weights<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut writer, &self.saved_weights())?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn load_weights<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let saved: SavedWeights = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+        Self::from_saved(saved).ok_or_else(|| {
+            SerialisationError::ImportError {
+                reason: format!("'{}': {FEATURE_SET_MISMATCH}", path.display()),
+            }
+            .into()
+        })
+    }
+
+    fn saved_weights(&self) -> SavedWeights {
+        SavedWeights {
+            weights: self.weights.clone(),
+            bias: self.bias,
+            feature_names: self.feature_names.iter().map(|n| n.to_string()).collect(),
+        }
+    }
+
+    fn from_saved(saved: SavedWeights) -> Option<Self> {
+        if saved.feature_names != FEATURE_NAMES || saved.weights.len() != FEATURE_NAMES.len() {
+            return None;
+        }
+        Some(Self::from_weights(saved.weights, saved.bias, FEATURE_NAMES.to_vec()))
+    }
+
     
     
     
@@ -86,8 +137,20 @@ impl LearnedScorer {
         specs: Vec<RenderSpec>,
         symbolic_scores: Option<&HashMap<String, f64>>,
     ) -> Vec<(RenderSpec, f64)> {
+        self.rerank_specs_explained(profiles, specs, symbolic_scores)
+            .into_iter()
+            .map(|(spec, score, _)| (spec, score))
+            .collect()
+    }
+
+    pub fn rerank_specs_explained(
+        &self,
+        profiles: &[DimensionProfile],
+        specs: Vec<RenderSpec>,
+        symbolic_scores: Option<&HashMap<String, f64>>,
+    ) -> Vec<(RenderSpec, f64, Vec<(String, f64)>)> {
         let stats = DatasetStats::from_profiles(profiles);
-        let mut scored: Vec<(RenderSpec, f64)> = specs
+        let mut scored: Vec<(RenderSpec, f64, Vec<(String, f64)>)> = specs
             .into_iter()
             .map(|spec| {
                 let sym = symbolic_scores
@@ -96,15 +159,37 @@ impl LearnedScorer {
                     .unwrap_or(0.0);
                 let fv = FeatureVector::from_spec(&spec, &stats, sym);
                 let score = self.predict(&fv);
-                (spec, score)
+                let contributions = self.explain(&fv);
+                (spec, score, contributions)
             })
             .collect();
-        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.tie_break_cmp(&b.0))
+        });
         scored
     }
+
+    pub fn explain(&self, profile_features: &FeatureVector) -> Vec<(String, f64)> {
+        self.feature_names
+            .iter()
+            .zip(self.weights.iter().zip(profile_features.to_vec()))
+            .map(|(name, (wi, xi))| (name.to_string(), wi * xi))
+            .collect()
+    }
 }
 
 
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedWeights {
+    weights: Vec<f64>,
+    bias: f64,
+    feature_names: Vec<String>,
+}
+
+const FEATURE_SET_MISMATCH: &str = "saved weights do not match the learned scorer feature set";
+
 const FEATURE_NAMES: [&str; 14] = [
     "quality_score",
     "technical_feasibility",
@@ -122,7 +207,7 @@ const FEATURE_NAMES: [&str; 14] = [
     "symbolic_score",
 ];
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DatasetStats {
     pub dims: usize,
     pub numeric_count: usize,
@@ -149,6 +234,7 @@ impl DatasetStats {
                 crate::api_graph::DataType::Numeric => numeric_count += 1,
                 crate::api_graph::DataType::Categorical => categorical_count += 1,
                 crate::api_graph::DataType::Temporal => temporal_present = true,
+                crate::api_graph::DataType::Geospatial => {}
             }
             if let Some(c) = p.cardinality {
                 card_total += c as f64;
@@ -173,7 +259,7 @@ impl DatasetStats {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeatureVector {
     pub quality_score: f64,
     pub technical_feasibility: f64,
@@ -10,10 +10,13 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
+pub mod accessibility;
 pub mod api_graph;
 pub mod chart_matcher;
 pub mod data_profiler;
 pub mod error;
+#[cfg(feature = "native")]
+pub mod plotly;
 
 #[cfg(feature = "data-handler")]
 pub mod data_handler;
@@ -26,25 +29,57 @@ pub mod symbolic_filtering;
 #[cfg(feature = "learned-scorer")]
 pub mod learned_scorer;
 
-pub use api_graph::{ApiGraph, ArgSpec, ChartNode, DataType, DataTypeSpec};
-pub use chart_matcher::{MatchingConfig, RenderSpec};
-pub use data_profiler::{DataProfiler, DatasetSummary, DimensionProfile, ProfilingConfig};
+#[cfg(feature = "learned-scorer")]
+pub mod feedback;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use accessibility::{AccessibilityIssue, AccessibilityIssueKind, AccessibilityReport};
+pub use api_graph::{
+    ApiGraph, ArgSpec, ChartNode, Coercion, CoercionMatrix, DataType, DataTypeSpec,
+    RenderEligibility, UnmetArgSpec,
+};
+pub use chart_matcher::{ExplainedCharts, MatchExplanation, MatchingConfig, RenderSpec};
+pub use data_profiler::{
+    compute_histogram, Bin, BinningMethod, DataProfiler, DatasetSummary, DimensionProfile,
+    ProfilingConfig,
+};
+#[cfg(feature = "native")]
+pub use data_profiler::IncrementalProfiler;
 
 pub use error::{ChartSuggestionError, ConfigError, DataError, ErrorReporter, Result};
 #[cfg(feature = "learned-scorer")]
+pub use feedback::{FeedbackCollector, FeedbackRecord};
+#[cfg(feature = "learned-scorer")]
 pub use learned_scorer::{
     DatasetStats as LearnedDatasetStats, FeatureVector as LearnedFeatureVector, LearnedScorer,
 };
+#[cfg(feature = "native")]
 use polars::prelude::DataFrame;
+#[cfg(feature = "native")]
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "native")]
+#[derive(Debug, Clone)]
+pub struct DirectorySuggestions {
+    pub files: Vec<PathBuf>,
+    pub row_count: usize,
+    pub suggestions: Vec<RenderSpec>,
+}
 
 pub struct ChartSuggestionSystem {
-    api_graph: ApiGraph,
+    api_graph: Arc<ApiGraph>,
     profiler: DataProfiler,
     matching_config: MatchingConfig,
+    #[cfg(feature = "learned-scorer")]
+    feedback: Mutex<FeedbackCollector>,
 }
 impl ChartSuggestionSystem {
     pub fn new() -> Result<Self> {
-        let api_graph = ApiGraph::from_yaml_file("config/plotly_api.yml").map_err(|e| {
+        let api_graph = ApiGraph::default_embedded().map_err(|e| {
             ChartSuggestionError::Config(ConfigError::ValidationFailed {
                 reason: format!("Failed to load API config: {e}"),
             })
@@ -52,9 +87,11 @@ impl ChartSuggestionSystem {
         let profiler = DataProfiler::new();
         let matching_config = MatchingConfig::default();
         Ok(Self {
-            api_graph,
+            api_graph: Arc::new(api_graph),
             profiler,
             matching_config,
+            #[cfg(feature = "learned-scorer")]
+            feedback: Mutex::new(FeedbackCollector::new()),
         })
     }
     pub fn with_config(
@@ -69,35 +106,123 @@ impl ChartSuggestionSystem {
         })?;
         let profiler = DataProfiler::with_config(profiling_config);
         Ok(Self {
-            api_graph,
+            api_graph: Arc::new(api_graph),
             profiler,
             matching_config,
+            #[cfg(feature = "learned-scorer")]
+            feedback: Mutex::new(FeedbackCollector::new()),
         })
     }
+    #[cfg(feature = "native")]
     pub fn suggest_charts_from_csv(&self, csv_path: &str) -> Result<Vec<RenderSpec>> {
         let profiles = self.profiler.profile_csv(csv_path).map_err(|e| {
             ChartSuggestionError::Data(DataError::LowDataQuality {
                 reason: format!("Failed to profile CSV file '{csv_path}': {e}"),
             })
         })?;
-        Ok(chart_matcher::find_qualified_charts(
-            &profiles,
-            &self.api_graph,
-            &self.matching_config,
-        ))
+        let specs =
+            chart_matcher::find_qualified_charts(&profiles, &self.api_graph, &self.matching_config);
+        #[cfg(feature = "learned-scorer")]
+        self.observe_dataset(Some(csv_path), &profiles, None, &specs);
+        Ok(specs)
+    }
+    #[cfg(feature = "native")]
+    pub fn suggest_charts_from_parquet(&self, parquet_path: &str) -> Result<Vec<RenderSpec>> {
+        let df = read_parquet_file(Path::new(parquet_path))?;
+        self.suggest_charts_from_file(parquet_path, "Parquet", &df)
+    }
+    #[cfg(feature = "native")]
+    pub fn suggest_charts_from_ndjson(&self, ndjson_path: &str) -> Result<Vec<RenderSpec>> {
+        let df = read_ndjson_file(Path::new(ndjson_path))?;
+        self.suggest_charts_from_file(ndjson_path, "NDJSON", &df)
+    }
+    #[cfg(feature = "native")]
+    fn suggest_charts_from_file(
+        &self,
+        path: &str,
+        format: &str,
+        df: &DataFrame,
+    ) -> Result<Vec<RenderSpec>> {
+        let profiles = self.profiler.profile_dataframe(df).map_err(|e| {
+            ChartSuggestionError::Data(DataError::LowDataQuality {
+                reason: format!("Failed to profile {format} file '{path}': {e}"),
+            })
+        })?;
+        let specs =
+            chart_matcher::find_qualified_charts(&profiles, &self.api_graph, &self.matching_config);
+        #[cfg(feature = "learned-scorer")]
+        self.observe_dataset(Some(path), &profiles, None, &specs);
+        Ok(specs)
     }
+    #[cfg(feature = "native")]
     pub fn suggest_charts_from_dataframe(&self, df: &DataFrame) -> Result<Vec<RenderSpec>> {
         let profiles = self.profiler.profile_dataframe(df).map_err(|e| {
             ChartSuggestionError::Config(ConfigError::ValidationFailed {
                 reason: format!("Failed to profile dataframe: {e}"),
             })
         })?;
-        Ok(chart_matcher::find_qualified_charts(
+        let specs =
+            chart_matcher::find_qualified_charts(&profiles, &self.api_graph, &self.matching_config);
+        #[cfg(feature = "learned-scorer")]
+        self.observe_dataset(None, &profiles, None, &specs);
+        Ok(specs)
+    }
+    #[cfg(feature = "native")]
+    pub fn suggest_charts_from_directory<P: AsRef<Path>>(
+        &self,
+        dir: P,
+        glob: &str,
+    ) -> Result<DirectorySuggestions> {
+        let dir = dir.as_ref();
+        let files = matching_files(dir, glob)?;
+        let Some((first, rest)) = files.split_first() else {
+            return Err(DataError::NoMatchingFiles {
+                directory: dir.display().to_string(),
+                pattern: glob.to_string(),
+            }
+            .into());
+        };
+        let mut combined = read_csv_file(first)?;
+        let expected = schema_signature(&combined);
+        for file in rest {
+            let df = read_csv_file(file)?;
+            let found = schema_signature(&df);
+            if found != expected {
+                return Err(DataError::SchemaMismatch {
+                    file: file.display().to_string(),
+                    reference: first.display().to_string(),
+                    details: schema_difference(&expected, &found),
+                }
+                .into());
+            }
+            combined
+                .vstack_mut(&df)
+                .map_err(|source| DataError::DataFileError {
+                    path: file.display().to_string(),
+                    source,
+                })?;
+        }
+        let profiles = self.profiler.profile_dataframe(&combined).map_err(|e| {
+            ChartSuggestionError::Data(DataError::LowDataQuality {
+                reason: format!("Failed to profile files in '{}': {e}", dir.display()),
+            })
+        })?;
+        let suggestions =
+            chart_matcher::find_qualified_charts(&profiles, &self.api_graph, &self.matching_config);
+        #[cfg(feature = "learned-scorer")]
+        self.observe_dataset(
+            Some(&dir.display().to_string()),
             &profiles,
-            &self.api_graph,
-            &self.matching_config,
-        ))
+            None,
+            &suggestions,
+        );
+        Ok(DirectorySuggestions {
+            files,
+            row_count: combined.height(),
+            suggestions,
+        })
     }
+    #[cfg(feature = "native")]
     pub fn profile_csv(&self, csv_path: &str) -> Result<Vec<DimensionProfile>> {
         self.profiler.profile_csv(csv_path).map_err(|e| {
             ChartSuggestionError::Data(DataError::LowDataQuality {
@@ -108,13 +233,25 @@ impl ChartSuggestionSystem {
     pub fn get_summary(&self, profiles: &[DimensionProfile]) -> DatasetSummary {
         self.profiler.get_dataset_summary(profiles)
     }
+    pub fn builder() -> ChartSuggestionSystemBuilder {
+        ChartSuggestionSystemBuilder::new()
+    }
+    pub fn api_graph(&self) -> &ApiGraph {
+        &self.api_graph
+    }
+    pub fn matching_config(&self) -> &MatchingConfig {
+        &self.matching_config
+    }
+    pub fn profiling_config(&self) -> &ProfilingConfig {
+        self.profiler.config()
+    }
     pub fn get_available_charts(&self) -> &[ChartNode] {
         self.api_graph.get_all_charts()
     }
     pub fn get_charts_by_library(&self, library: &str) -> Vec<&ChartNode> {
         self.api_graph.get_charts_by_library(library)
     }
-    #[cfg(feature = "learned-scorer")]
+    #[cfg(all(feature = "native", feature = "learned-scorer"))]
     pub fn suggest_charts_reranked_from_csv(
         &self,
         csv_path: &str,
@@ -128,10 +265,11 @@ impl ChartSuggestionSystem {
         })?;
         let specs =
             chart_matcher::find_qualified_charts(&profiles, &self.api_graph, &self.matching_config);
+        self.observe_dataset(Some(csv_path), &profiles, symbolic_scores, &specs);
         Ok(scorer.rerank_specs(&profiles, specs, symbolic_scores))
     }
 
-    #[cfg(feature = "learned-scorer")]
+    #[cfg(all(feature = "native", feature = "learned-scorer"))]
     pub fn suggest_charts_reranked_from_dataframe(
         &self,
         df: &polars::prelude::DataFrame,
@@ -145,11 +283,219 @@ impl ChartSuggestionSystem {
         })?;
         let specs =
             chart_matcher::find_qualified_charts(&profiles, &self.api_graph, &self.matching_config);
+        self.observe_dataset(None, &profiles, symbolic_scores, &specs);
         Ok(scorer.rerank_specs(&profiles, specs, symbolic_scores))
     }
+
+    #[cfg(all(feature = "native", feature = "learned-scorer"))]
+    fn observe_dataset(
+        &self,
+        dataset: Option<&str>,
+        profiles: &[DimensionProfile],
+        symbolic_scores: Option<&std::collections::HashMap<String, f64>>,
+        specs: &[RenderSpec],
+    ) {
+        let mut collector = self.feedback.lock().unwrap_or_else(|p| p.into_inner());
+        collector.set_context(dataset, profiles);
+        if let Some(scores) = symbolic_scores {
+            collector.set_symbolic_scores(scores.clone());
+        }
+        collector.observe_suggestions(specs);
+    }
+
+    #[cfg(feature = "learned-scorer")]
+    pub fn record_feedback(&self, spec: &RenderSpec, chosen: bool) -> FeedbackRecord {
+        let mut collector = self.feedback.lock().unwrap_or_else(|p| p.into_inner());
+        collector.record(spec, chosen).clone()
+    }
+
+    #[cfg(feature = "learned-scorer")]
+    pub fn feedback_jsonl(&self) -> Result<String> {
+        let collector = self.feedback.lock().unwrap_or_else(|p| p.into_inner());
+        Ok(collector.to_jsonl().map_err(error::SerialisationError::from)?)
+    }
+
+    #[cfg(feature = "learned-scorer")]
+    pub fn export_feedback_jsonl<P: AsRef<std::path::Path>>(&self, path: P) -> Result<usize> {
+        let collector = self.feedback.lock().unwrap_or_else(|p| p.into_inner());
+        Ok(collector.export_jsonl(path)?)
+    }
+}
+#[cfg(feature = "native")]
+fn matching_files(dir: &Path, glob: &str) -> Result<Vec<PathBuf>> {
+    let pattern = glob_to_regex(glob);
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let matched = path.is_file()
+            && path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| pattern.is_match(name));
+        if matched {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(feature = "native")]
+fn glob_to_regex(glob: &str) -> regex::Regex {
+    let mut pattern = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            other => pattern.push_str(&regex::escape(other.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    pattern.push('$');
+    regex::Regex::new(&pattern).expect("escaped glob is a valid regex")
+}
+
+#[cfg(feature = "native")]
+fn read_csv_file(path: &Path) -> Result<DataFrame> {
+    use polars::prelude::{CsvReader, SerReader};
+    let file = std::fs::File::open(path)?;
+    Ok(CsvReader::new(file)
+        .finish()
+        .map_err(|source| DataError::DataFileError {
+            path: path.display().to_string(),
+            source,
+        })?)
 }
+
+#[cfg(feature = "native")]
+fn read_parquet_file(path: &Path) -> Result<DataFrame> {
+    use polars::prelude::{ParquetReader, SerReader};
+    let file = std::fs::File::open(path)?;
+    Ok(ParquetReader::new(file)
+        .finish()
+        .map_err(|source| DataError::DataFileError {
+            path: path.display().to_string(),
+            source,
+        })?)
+}
+
+#[cfg(feature = "native")]
+fn read_ndjson_file(path: &Path) -> Result<DataFrame> {
+    use polars::prelude::{JsonLineReader, SerReader};
+    let file = std::fs::File::open(path)?;
+    Ok(JsonLineReader::new(file)
+        .finish()
+        .map_err(|source| DataError::DataFileError {
+            path: path.display().to_string(),
+            source,
+        })?)
+}
+
+#[cfg(feature = "native")]
+fn schema_signature(df: &DataFrame) -> Vec<(String, String)> {
+    df.get_columns()
+        .iter()
+        .map(|column| (column.name().to_string(), column.dtype().to_string()))
+        .collect()
+}
+
+#[cfg(feature = "native")]
+fn schema_difference(expected: &[(String, String)], found: &[(String, String)]) -> String {
+    let names = |schema: &[(String, String)]| {
+        schema
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    if names(expected) != names(found) {
+        return format!(
+            "expected columns [{}], found [{}]",
+            names(expected),
+            names(found)
+        );
+    }
+    expected
+        .iter()
+        .zip(found)
+        .filter(|(e, f)| e.1 != f.1)
+        .map(|((name, e), (_, f))| format!("column '{name}' is {f}, expected {e}"))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 impl Default for ChartSuggestionSystem {
     fn default() -> Self {
         Self::new().expect("Failed to create default chart suggestion system")
     }
 }
+
+#[derive(Debug, Clone, Default)]
+enum ApiSource {
+    #[default]
+    Embedded,
+    File(PathBuf),
+}
+
+#[derive(Debug, Default)]
+pub struct ChartSuggestionSystemBuilder {
+    source: ApiSource,
+    profiling_config: ProfilingConfig,
+    matching_config: MatchingConfig,
+    api_graph: Mutex<Option<Arc<ApiGraph>>>,
+}
+impl ChartSuggestionSystemBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn api_config_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.source = ApiSource::File(path.into());
+        self.api_graph = Mutex::new(None);
+        self
+    }
+    pub fn embedded_api(mut self) -> Self {
+        self.source = ApiSource::Embedded;
+        self.api_graph = Mutex::new(None);
+        self
+    }
+    pub fn profiling_config(mut self, config: ProfilingConfig) -> Self {
+        self.profiling_config = config;
+        self
+    }
+    pub fn matching_config(mut self, config: MatchingConfig) -> Self {
+        self.matching_config = config;
+        self
+    }
+    pub fn cached_api_graph(&self) -> Option<Arc<ApiGraph>> {
+        self.api_graph
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .clone()
+    }
+    pub fn load_api_graph(&self) -> Result<Arc<ApiGraph>> {
+        let mut cached = self.api_graph.lock().unwrap_or_else(|p| p.into_inner());
+        if let Some(graph) = cached.as_ref() {
+            return Ok(Arc::clone(graph));
+        }
+        let loaded = match &self.source {
+            ApiSource::Embedded => ApiGraph::default_embedded(),
+            ApiSource::File(path) => ApiGraph::from_yaml_file(path),
+        }
+        .map_err(|e| {
+            ChartSuggestionError::Config(ConfigError::ValidationFailed {
+                reason: format!("Failed to load API config: {e}"),
+            })
+        })?;
+        let graph = Arc::new(loaded);
+        *cached = Some(Arc::clone(&graph));
+        Ok(graph)
+    }
+    pub fn build(&self) -> Result<ChartSuggestionSystem> {
+        Ok(ChartSuggestionSystem {
+            api_graph: self.load_api_graph()?,
+            profiler: DataProfiler::with_config(self.profiling_config.clone()),
+            matching_config: self.matching_config.clone(),
+            #[cfg(feature = "learned-scorer")]
+            feedback: Mutex::new(FeedbackCollector::new()),
+        })
+    }
+}
@@ -0,0 +1,256 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+use crate::api_graph::{ApiGraph, ChartNode};
+use crate::chart_matcher::RenderSpec;
+use polars::prelude::{DataFrame, DataType as PolarsDataType, Series};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+const COMMON_PROPERTIES: [(&str, &str); 3] = [
+    ("text", "text"),
+    ("hover_name", "hovertext"),
+    ("size", "marker.size"),
+];
+const CARTESIAN: [(&str, &str); 2] = [("x", "x"), ("y", "y")];
+const CARTESIAN_Z: [(&str, &str); 3] = [("x", "x"), ("y", "y"), ("z", "z")];
+const POLAR: [(&str, &str); 2] = [("r", "r"), ("theta", "theta")];
+const GEO: [(&str, &str); 2] = [("lat", "lat"), ("lon", "lon")];
+const PIE: [(&str, &str); 2] = [("names", "labels"), ("values", "values")];
+const MARKERS: [(&str, &str); 1] = [("mode", "markers")];
+const LINES: [(&str, &str); 1] = [("mode", "lines")];
+const AREA: [(&str, &str); 2] = [("mode", "lines"), ("fill", "tozeroy")];
+const STRIP: [(&str, &str); 1] = [("boxpoints", "all")];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Axes {
+    Cartesian,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TraceTemplate {
+    trace_type: &'static str,
+    attributes: &'static [(&'static str, &'static str)],
+    properties: &'static [(&'static str, &'static str)],
+    axes: Axes,
+    colour_groups: bool,
+}
+
+impl TraceTemplate {
+    const fn new(
+        trace_type: &'static str,
+        attributes: &'static [(&'static str, &'static str)],
+        properties: &'static [(&'static str, &'static str)],
+        axes: Axes,
+    ) -> Self {
+        Self {
+            trace_type,
+            attributes,
+            properties,
+            axes,
+            colour_groups: true,
+        }
+    }
+}
+
+fn trace_template(chart_name: &str) -> Option<TraceTemplate> {
+    use Axes::{Cartesian, Other};
+    let template = match chart_name {
+        "scatter" => TraceTemplate::new("scatter", &MARKERS, &CARTESIAN, Cartesian),
+        "line" => TraceTemplate::new("scatter", &LINES, &CARTESIAN, Cartesian),
+        "area" => TraceTemplate::new("scatter", &AREA, &CARTESIAN, Cartesian),
+        "bar" => TraceTemplate::new("bar", &[], &CARTESIAN, Cartesian),
+        "histogram" => TraceTemplate::new("histogram", &[], &CARTESIAN, Cartesian),
+        "box" => TraceTemplate::new("box", &[], &CARTESIAN, Cartesian),
+        "violin" => TraceTemplate::new("violin", &[], &CARTESIAN, Cartesian),
+        "strip" => TraceTemplate::new("box", &STRIP, &CARTESIAN, Cartesian),
+        "funnel" => TraceTemplate::new("funnel", &[], &CARTESIAN, Cartesian),
+        "density_heatmap" => TraceTemplate::new("histogram2d", &[], &CARTESIAN_Z, Cartesian),
+        "density_contour" => {
+            TraceTemplate::new("histogram2dcontour", &[], &CARTESIAN_Z, Cartesian)
+        }
+        "scatter_polar" => TraceTemplate::new("scatterpolar", &MARKERS, &POLAR, Other),
+        "line_polar" => TraceTemplate::new("scatterpolar", &LINES, &POLAR, Other),
+        "bar_polar" => TraceTemplate::new("barpolar", &[], &POLAR, Other),
+        "scatter_geo" => TraceTemplate::new("scattergeo", &MARKERS, &GEO, Other),
+        "line_geo" => TraceTemplate::new("scattergeo", &LINES, &GEO, Other),
+        "scatter_map" => TraceTemplate::new("scattermap", &MARKERS, &GEO, Other),
+        "pie" => TraceTemplate {
+            colour_groups: false,
+            ..TraceTemplate::new("pie", &[], &PIE, Other)
+        },
+        _ => return None,
+    };
+    Some(template)
+}
+
+fn embedded_api_graph() -> Option<&'static ApiGraph> {
+    static GRAPH: OnceLock<Option<ApiGraph>> = OnceLock::new();
+    GRAPH
+        .get_or_init(|| ApiGraph::default_embedded().ok())
+        .as_ref()
+}
+
+impl RenderSpec {
+    pub fn to_plotly_json(&self, data: &DataFrame) -> Value {
+        match embedded_api_graph() {
+            Some(graph) => self.to_plotly_json_with_graph(data, graph),
+            None => self.plotly_figure(Vec::new(), Map::new()),
+        }
+    }
+
+    pub fn to_plotly_json_with_graph(&self, data: &DataFrame, graph: &ApiGraph) -> Value {
+        let (Some(template), Some(chart)) = (
+            trace_template(&self.chart_name),
+            graph.get_chart(&self.chart_name),
+        ) else {
+            return self.plotly_figure(Vec::new(), Map::new());
+        };
+        let colour = self
+            .mapped_column(chart, "color")
+            .filter(|_| template.colour_groups)
+            .and_then(|column| data.column(column).ok())
+            .map(|column| column.as_materialized_series());
+        let traces = match colour {
+            Some(series) if is_numeric(series.dtype()) => {
+                let mut trace = self.plotly_trace(&template, chart, data, None);
+                if let Some(values) = series_values(series, None) {
+                    set_path(&mut trace, "marker.color", Value::Array(values));
+                    set_path(&mut trace, "marker.showscale", Value::Bool(true));
+                }
+                vec![Value::Object(trace)]
+            }
+            Some(series) => colour_groups(series)
+                .into_iter()
+                .map(|(group, rows)| {
+                    let mut trace = self.plotly_trace(&template, chart, data, Some(&rows));
+                    trace.insert("name".to_string(), Value::String(group.clone()));
+                    trace.insert("legendgroup".to_string(), Value::String(group));
+                    Value::Object(trace)
+                })
+                .collect(),
+            None => vec![Value::Object(self.plotly_trace(&template, chart, data, None))],
+        };
+        let mut layout = Map::new();
+        if template.axes == Axes::Cartesian {
+            for axis in ["x", "y"] {
+                if let Some(column) = self.mapped_column(chart, axis) {
+                    set_path(
+                        &mut layout,
+                        &format!("{axis}axis.title.text"),
+                        Value::String(column.to_string()),
+                    );
+                }
+            }
+        }
+        if traces.len() > 1 {
+            layout.insert("showlegend".to_string(), Value::Bool(true));
+        }
+        self.plotly_figure(traces, layout)
+    }
+
+    fn plotly_figure(&self, traces: Vec<Value>, mut layout: Map<String, Value>) -> Value {
+        set_path(
+            &mut layout,
+            "title.text",
+            Value::String(self.chart_name.clone()),
+        );
+        json!({ "data": traces, "layout": layout })
+    }
+
+    fn plotly_trace(
+        &self,
+        template: &TraceTemplate,
+        chart: &ChartNode,
+        data: &DataFrame,
+        rows: Option<&[usize]>,
+    ) -> Map<String, Value> {
+        let mut trace = Map::new();
+        trace.insert(
+            "type".to_string(),
+            Value::String(template.trace_type.to_string()),
+        );
+        for (key, value) in template.attributes {
+            trace.insert(key.to_string(), Value::String(value.to_string()));
+        }
+        for (arg, property) in template.properties.iter().chain(&COMMON_PROPERTIES) {
+            let values = self
+                .mapped_column(chart, arg)
+                .and_then(|column| data.column(column).ok())
+                .and_then(|column| series_values(column.as_materialized_series(), rows));
+            if let Some(values) = values {
+                set_path(&mut trace, property, Value::Array(values));
+            }
+        }
+        trace
+    }
+
+    fn mapped_column(&self, chart: &ChartNode, arg: &str) -> Option<&str> {
+        chart.args.get(arg)?;
+        self.mappings.get(arg).map(String::as_str)
+    }
+}
+
+fn is_numeric(dtype: &PolarsDataType) -> bool {
+    dtype.is_integer() || dtype.is_float()
+}
+
+fn series_values(series: &Series, rows: Option<&[usize]>) -> Option<Vec<Value>> {
+    let values: Vec<Value> = if is_numeric(series.dtype()) {
+        let floats = series.cast(&PolarsDataType::Float64).ok()?;
+        floats.f64().ok()?.into_iter().map(|v| json!(v)).collect()
+    } else {
+        let strings = series.cast(&PolarsDataType::String).ok()?;
+        strings.str().ok()?.into_iter().map(|v| json!(v)).collect()
+    };
+    Some(match rows {
+        Some(rows) => rows.iter().filter_map(|&i| values.get(i).cloned()).collect(),
+        None => values,
+    })
+}
+
+fn colour_groups(series: &Series) -> Vec<(String, Vec<usize>)> {
+    let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for (row, value) in series_values(series, None)
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+    {
+        let key = match value {
+            Value::String(s) => s,
+            Value::Null => "null".to_string(),
+            other => other.to_string(),
+        };
+        let slot = *index.entry(key.clone()).or_insert_with(|| {
+            groups.push((key, Vec::new()));
+            groups.len() - 1
+        });
+        groups[slot].1.push(row);
+    }
+    groups
+}
+
+fn set_path(target: &mut Map<String, Value>, path: &str, value: Value) {
+    let mut current = target;
+    let mut segments = path.split('.').peekable();
+    while let Some(segment) = segments.next() {
+        if segments.peek().is_none() {
+            current.insert(segment.to_string(), value);
+            return;
+        }
+        let entry = current
+            .entry(segment.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !entry.is_object() {
+            *entry = Value::Object(Map::new());
+        }
+        let Value::Object(next) = entry else {
+            return;
+        };
+        current = next;
+    }
+}
@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+use crate::api_graph::{ApiGraph, DataType};
+use crate::chart_matcher::{find_qualified_charts, MatchingConfig, RenderSpec};
+use crate::data_profiler::DimensionProfile;
+use crate::error::{ChartSuggestionError, ConfigError, Result, SerialisationError};
+use crate::symbolic_filtering::{
+    AnalysisGoal, ChartSpec, ChartType, ColumnProfile, DataType as SymDataType,
+    GraphAwareSymbolicEngine,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoredSuggestion {
+    pub spec: RenderSpec,
+    pub symbolic_score: f64,
+    pub notes: Vec<String>,
+}
+
+pub fn symbolic_chart_spec(
+    spec: &RenderSpec,
+    profiles: &[DimensionProfile],
+) -> (ChartSpec, AnalysisGoal) {
+    let x_field = spec.mappings.get("x").cloned().unwrap_or_default();
+    let mut y_fields: Vec<String> = spec
+        .mappings
+        .iter()
+        .filter(|(k, _)| k.starts_with('y'))
+        .map(|(_, v)| v.clone())
+        .collect();
+    y_fields.sort();
+    let colour = spec.mappings.get("color").cloned();
+    let column_profiles: HashMap<String, ColumnProfile> = profiles
+        .iter()
+        .map(|p| {
+            let data_type = match p.data_type {
+                DataType::Numeric | DataType::Geospatial => SymDataType::Numeric,
+                DataType::Categorical => SymDataType::Categorical,
+                DataType::Temporal => SymDataType::Temporal,
+            };
+            (
+                p.name.clone(),
+                ColumnProfile {
+                    name: p.name.clone(),
+                    data_type,
+                    cardinality: p.cardinality.map(|c| c as u64),
+                    has_nulls: p.null_percentage > 0.0,
+                },
+            )
+        })
+        .collect();
+    let chart_type = match spec.chart_name.to_lowercase().as_str() {
+        s if s.contains("bar") => ChartType::Bar,
+        s if s.contains("line") => ChartType::Line,
+        s if s.contains("scatter") => ChartType::Scatter,
+        s if s.contains("pie") => ChartType::Pie,
+        s if s.contains("histogram") => ChartType::Histogram,
+        s if s.contains("box") => ChartType::BoxPlot,
+        _ => ChartType::Bar,
+    };
+    let has_temporal = std::iter::once(&x_field)
+        .chain(y_fields.iter())
+        .chain(colour.iter())
+        .filter_map(|name| profiles.iter().find(|p| &p.name == name))
+        .any(|p| p.data_type.is_temporal());
+    let goal = match chart_type {
+        ChartType::Line => AnalysisGoal::ShowTrend,
+        ChartType::Histogram | ChartType::BoxPlot => AnalysisGoal::ShowDistribution,
+        ChartType::Scatter => AnalysisGoal::FindRelationship,
+        ChartType::Pie => AnalysisGoal::ShowComposition,
+        ChartType::Bar if has_temporal => AnalysisGoal::ShowTrend,
+        ChartType::Bar => AnalysisGoal::Compare,
+    };
+    (
+        ChartSpec {
+            chart_type,
+            x_axis_field: x_field,
+            y_axis_fields: y_fields,
+            colour_field: colour,
+            column_profiles,
+        },
+        goal,
+    )
+}
+
+pub fn suggest_with_symbolic(
+    profiles: &[DimensionProfile],
+    api_graph: &ApiGraph,
+    config: &MatchingConfig,
+) -> Vec<ScoredSuggestion> {
+    let engine = GraphAwareSymbolicEngine::new();
+    find_qualified_charts(profiles, api_graph, config)
+        .into_iter()
+        .map(|spec| {
+            let (chart_spec, goal) = symbolic_chart_spec(&spec, profiles);
+            let (symbolic_score, notes) = engine.enhanced_evaluate(&chart_spec, &goal);
+            ScoredSuggestion {
+                spec,
+                symbolic_score,
+                notes,
+            }
+        })
+        .collect()
+}
+
+pub fn suggest_charts_json(api_yaml: &str, profiles_json: &str) -> Result<String> {
+    let api_graph = ApiGraph::from_yaml_string(api_yaml).map_err(|e| {
+        ChartSuggestionError::Config(ConfigError::ValidationFailed {
+            reason: format!("Failed to load API config: {e}"),
+        })
+    })?;
+    let profiles: Vec<DimensionProfile> =
+        serde_json::from_str(profiles_json).map_err(SerialisationError::from)?;
+    let suggestions = suggest_with_symbolic(&profiles, &api_graph, &MatchingConfig::default());
+    Ok(serde_json::to_string(&suggestions).map_err(SerialisationError::from)?)
+}
@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+mod common;
+
+use common::spec;
+use estel::AccessibilityIssueKind;
+
+#[test]
+fn multi_series_without_patterns_is_flagged() {
+    let line = spec(
+        "line",
+        &[("x", "order_date"), ("y", "revenue"), ("color", "region")],
+        0.8,
+    );
+    let report = line.accessibility_report();
+    assert!(!report.is_accessible());
+    assert!(report.has_issue(AccessibilityIssueKind::ColourOnlyEncoding));
+    assert!(report.has_issue(AccessibilityIssueKind::LowContrastPalette));
+    assert!(report
+        .remediations()
+        .iter()
+        .any(|r| r.contains("line_dash")));
+}
+
+#[test]
+fn redundant_encoding_clears_colour_only_issue() {
+    let scatter = spec(
+        "scatter",
+        &[
+            ("x", "revenue"),
+            ("y", "cost"),
+            ("color", "region"),
+            ("symbol", "region"),
+            ("color_discrete_sequence", "safe"),
+        ],
+        0.8,
+    );
+    assert!(scatter.accessibility_report().is_accessible());
+}
+
+#[test]
+fn labelled_single_series_passes() {
+    let scatter = spec("scatter", &[("x", "revenue"), ("y", "cost")], 0.8);
+    let report = scatter.accessibility_report();
+    assert!(report.is_accessible(), "unexpected issues: {:?}", report.issues);
+}
+
+#[test]
+fn generic_column_names_are_reported_as_missing_labels() {
+    let scatter = spec("scatter", &[("x", "Unnamed: 0"), ("y", "col_2")], 0.8);
+    let report = scatter.accessibility_report();
+    let missing = report
+        .issues
+        .iter()
+        .filter(|i| i.kind == AccessibilityIssueKind::MissingAxisLabel)
+        .count();
+    assert_eq!(missing, 2);
+}
@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+use estel::{ChartSuggestionSystem, MatchingConfig};
+use std::sync::Arc;
+
+#[test]
+fn builder_loads_api_graph_once_for_many_systems() {
+    let builder = ChartSuggestionSystem::builder().embedded_api();
+    assert!(builder.cached_api_graph().is_none());
+
+    let systems: Vec<ChartSuggestionSystem> = (0..4)
+        .map(|_| builder.build().expect("build system"))
+        .collect();
+    let cached = builder.cached_api_graph().expect("graph cached after build");
+
+    for system in &systems {
+        assert!(std::ptr::eq(system.api_graph(), Arc::as_ptr(&cached)));
+        assert!(!system.get_available_charts().is_empty());
+    }
+    assert_eq!(Arc::strong_count(&cached), systems.len() + 2);
+}
+
+#[test]
+fn builder_reads_explicit_path_and_applies_configs() {
+    let builder = ChartSuggestionSystem::builder()
+        .api_config_path(concat!(env!("CARGO_MANIFEST_DIR"), "/config/plotly_api.yml"))
+        .matching_config(MatchingConfig::for_exploration());
+    let first = builder.build().expect("build from file");
+    let second = builder.build().expect("reuse cached graph");
+    assert!(std::ptr::eq(first.api_graph(), second.api_graph()));
+    assert!(first.get_charts_by_library("plotly").len() > 1);
+}
+
+#[test]
+fn builder_reports_missing_config_file() {
+    let builder = ChartSuggestionSystem::builder().api_config_path("does/not/exist.yml");
+    assert!(builder.build().is_err());
+    assert!(builder.cached_api_graph().is_none());
+}
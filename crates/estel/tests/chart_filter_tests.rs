@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+mod common;
+
+use common::profile;
+use estel::chart_matcher::{explain_chart_matches, find_qualified_charts, RejectionReason};
+use estel::{ApiGraph, DataType, DimensionProfile, MatchingConfig};
+
+fn sales() -> Vec<DimensionProfile> {
+    vec![
+        profile("region", DataType::Categorical, Some(4)),
+        profile("revenue", DataType::Numeric, None),
+        profile("units", DataType::Numeric, None),
+    ]
+}
+
+fn names(config: &MatchingConfig) -> Vec<String> {
+    let graph = ApiGraph::default_embedded().unwrap();
+    find_qualified_charts(&sales(), &graph, config)
+        .into_iter()
+        .map(|spec| spec.chart_name)
+        .collect()
+}
+
+#[test]
+fn allowed_libraries_restrict_suggestions() {
+    let graph = ApiGraph::default_embedded().unwrap();
+    let config = MatchingConfig {
+        allowed_libraries: Some(vec!["plotly".to_string()]),
+        ..Default::default()
+    };
+    let specs = find_qualified_charts(&sales(), &graph, &config);
+    assert!(!specs.is_empty());
+    assert!(specs.iter().all(|spec| spec.library == "plotly"));
+
+    let elsewhere = MatchingConfig {
+        allowed_libraries: Some(vec!["matplotlib".to_string()]),
+        ..Default::default()
+    };
+    assert!(find_qualified_charts(&sales(), &graph, &elsewhere).is_empty());
+}
+
+#[test]
+fn excluded_chart_never_appears() {
+    let baseline = names(&MatchingConfig::default());
+    let excluded = baseline.first().expect("baseline suggestion").clone();
+
+    let config = MatchingConfig {
+        excluded_chart_names: vec![excluded.clone()],
+        ..Default::default()
+    };
+    let filtered = names(&config);
+    assert!(!filtered.is_empty());
+    assert!(!filtered.contains(&excluded));
+}
+
+#[test]
+fn filters_compose_with_suggestion_limit() {
+    let baseline = names(&MatchingConfig::default());
+    let excluded = baseline.first().expect("baseline suggestion").clone();
+    let config = MatchingConfig {
+        max_suggestions_per_chart: 2,
+        allowed_libraries: Some(vec!["plotly".to_string()]),
+        excluded_chart_names: vec![excluded.clone()],
+        ..Default::default()
+    };
+    let filtered = names(&config);
+    assert!(filtered.len() <= 2);
+    assert!(!filtered.contains(&excluded));
+}
+
+#[test]
+fn explained_matching_reports_excluded_charts() {
+    let graph = ApiGraph::default_embedded().unwrap();
+    let baseline = names(&MatchingConfig::default());
+    let excluded = baseline.first().expect("baseline suggestion").clone();
+    let config = MatchingConfig {
+        excluded_chart_names: vec![excluded.clone()],
+        ..Default::default()
+    };
+    let explained = explain_chart_matches(&sales(), &graph, &config);
+    assert!(matches!(
+        explained.rejection(&excluded),
+        Some(RejectionReason::ExcludedByConfig)
+    ));
+}
+
+#[test]
+fn empty_allow_list_is_invalid() {
+    let config = MatchingConfig {
+        allowed_libraries: Some(Vec::new()),
+        ..Default::default()
+    };
+    assert!(config.validate().is_err());
+}
@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+#![allow(dead_code)]
+
+use estel::api_graph::DataType;
+use estel::data_profiler::NumericStats;
+use estel::{DimensionProfile, RenderSpec};
+
+pub fn profile(name: &str, data_type: DataType, cardinality: Option<usize>) -> DimensionProfile {
+    let numeric_stats = data_type.is_numeric().then(|| NumericStats {
+        mean: Some(10.0),
+        median: Some(10.0),
+        std: Some(2.0),
+        min: Some(1.0),
+        max: Some(20.0),
+        q25: Some(8.0),
+        q75: Some(12.0),
+        skewness: None,
+        kurtosis: None,
+        mad: None,
+        outlier_count: 0,
+    });
+    DimensionProfile {
+        name: name.to_string(),
+        data_type,
+        cardinality,
+        total_count: 100,
+        null_count: 0,
+        null_percentage: 0.0,
+        sample_values: Vec::new(),
+        numeric_stats,
+        temporal_stats: None,
+        quality_score: 0.9,
+        type_confidence: 1.0,
+        issues: Vec::new(),
+        geo_role: None,
+        semantic_type: None,
+        overridden_from: None,
+        histogram: None,
+        date_format: None,
+    }
+}
+
+pub fn spec(chart_name: &str, mappings: &[(&str, &str)], quality_score: f64) -> RenderSpec {
+    RenderSpec {
+        chart_name: chart_name.to_string(),
+        library: "plotly".to_string(),
+        description: String::new(),
+        mappings: mappings
+            .iter()
+            .map(|(a, c)| (a.to_string(), c.to_string()))
+            .collect(),
+        quality_score,
+        dimensions_used: mappings.len(),
+        complete: true,
+        detailed_score: None,
+    }
+}
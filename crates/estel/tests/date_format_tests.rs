@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+#![cfg(feature = "native")]
+
+use estel::{DataProfiler, DataType, DimensionProfile, ProfilingConfig};
+use polars::prelude::*;
+
+fn profile_dates(values: &[&str], date_formats: &[&str]) -> DimensionProfile {
+    let df = df!("date" => values).unwrap();
+    let config = ProfilingConfig {
+        date_formats: date_formats.iter().map(|f| f.to_string()).collect(),
+        ..Default::default()
+    };
+    DataProfiler::with_config(config)
+        .profile_dataframe(&df)
+        .unwrap()
+        .into_iter()
+        .find(|p| p.name == "date")
+        .unwrap()
+}
+
+fn min_date(profile: &DimensionProfile) -> String {
+    profile
+        .temporal_stats
+        .as_ref()
+        .and_then(|stats| stats.min_date.clone())
+        .unwrap()
+}
+
+const DAY_FIRST: [&str; 5] = ["01/02/2024", "05/03/2024", "11/04/2024", "07/06/2024", "03/09/2024"];
+
+#[test]
+fn day_first_hint_resolves_ambiguous_dates() {
+    let profile = profile_dates(&DAY_FIRST, &["%d/%m/%Y"]);
+    assert_eq!(profile.data_type, DataType::Temporal);
+    assert_eq!(profile.date_format.as_deref(), Some("%d/%m/%Y"));
+    assert!(min_date(&profile).starts_with("2024-02-01"));
+}
+
+#[test]
+fn ambiguous_dates_without_hints_fall_back_to_inference() {
+    let profile = profile_dates(&DAY_FIRST, &[]);
+    assert_eq!(profile.data_type, DataType::Temporal);
+    assert_eq!(profile.date_format.as_deref(), Some("%m/%d/%Y"));
+    assert!(min_date(&profile).starts_with("2024-01-02"));
+}
+
+#[test]
+fn month_first_dash_hint_enables_temporal_detection() {
+    let values = ["12-31-2023", "01-15-2024", "02-29-2024", "03-10-2024", "04-22-2024"];
+
+    let unhinted = profile_dates(&values, &[]);
+    assert_ne!(unhinted.data_type, DataType::Temporal);
+    assert_eq!(unhinted.date_format, None);
+
+    let hinted = profile_dates(&values, &["%m-%d-%Y"]);
+    assert_eq!(hinted.data_type, DataType::Temporal);
+    assert_eq!(hinted.date_format.as_deref(), Some("%m-%d-%Y"));
+    assert!(min_date(&hinted).starts_with("2023-12-31"));
+}
+
+#[test]
+fn unmatched_hints_fall_back_to_inferred_format() {
+    let values = ["2024-01-01", "2024-01-02", "2024-01-03", "2024-01-04"];
+    let profile = profile_dates(&values, &["%d/%m/%Y", "%m-%d-%Y"]);
+    assert_eq!(profile.data_type, DataType::Temporal);
+    assert_eq!(profile.date_format.as_deref(), Some("%Y-%m-%d"));
+}
@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+mod common;
+
+use common::{profile, spec};
+use estel::chart_matcher::find_qualified_charts;
+use estel::{ApiGraph, DataType, MatchingConfig, RenderSpec};
+
+fn render(specs: &[RenderSpec]) -> String {
+    specs
+        .iter()
+        .map(|s| format!("{}|{}|{:.6}\n", s.chart_name, s.mapping_key(), s.quality_score))
+        .collect()
+}
+
+#[test]
+fn repeated_matching_produces_identical_order() {
+    let profiles = vec![
+        profile("region", DataType::Categorical, Some(5)),
+        profile("channel", DataType::Categorical, Some(5)),
+        profile("revenue", DataType::Numeric, None),
+        profile("cost", DataType::Numeric, None),
+        profile("margin", DataType::Numeric, None),
+    ];
+    let config = MatchingConfig::for_exploration();
+
+    let first = find_qualified_charts(&profiles, &ApiGraph::default_embedded().unwrap(), &config);
+    let second = find_qualified_charts(&profiles, &ApiGraph::default_embedded().unwrap(), &config);
+    assert!(!first.is_empty());
+    assert_eq!(render(&first).as_bytes(), render(&second).as_bytes());
+}
+
+#[test]
+fn equal_scores_fall_back_to_name_then_mappings() {
+    let a = spec("bar", &[("y", "revenue"), ("x", "region")], 0.5);
+    let b = spec("bar", &[("x", "channel"), ("y", "revenue")], 0.5);
+    let c = spec("area", &[("x", "region"), ("y", "cost")], 0.5);
+    assert_eq!(a.mapping_key(), "x=region,y=revenue");
+
+    let mut specs = vec![a, b, c];
+    specs.sort_by(|l, r| l.tie_break_cmp(r));
+    let order: Vec<String> = specs
+        .iter()
+        .map(|s| format!("{}:{}", s.chart_name, s.mapping_key()))
+        .collect();
+    assert_eq!(
+        order,
+        vec![
+            "area:x=region,y=cost",
+            "bar:x=channel,y=revenue",
+            "bar:x=region,y=revenue",
+        ]
+    );
+}
+
+#[test]
+fn repeated_matching_returns_equal_vectors() {
+    let profiles = vec![
+        profile("team", DataType::Categorical, Some(4)),
+        profile("league", DataType::Categorical, Some(4)),
+        profile("points", DataType::Numeric, None),
+        profile("goals", DataType::Numeric, None),
+    ];
+    let config = MatchingConfig::for_exploration();
+
+    let first = find_qualified_charts(&profiles, &ApiGraph::default_embedded().unwrap(), &config);
+    let second = find_qualified_charts(&profiles, &ApiGraph::default_embedded().unwrap(), &config);
+    assert!(!first.is_empty());
+    assert_eq!(first, second);
+    assert_eq!(
+        serde_json::to_string(&first).unwrap(),
+        serde_json::to_string(&second).unwrap()
+    );
+}
@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+#![cfg(feature = "native")]
+
+use estel::{ChartSuggestionError, ChartSuggestionSystem, DataError};
+use std::fs;
+use std::path::Path;
+
+fn write_sales(dir: &Path, name: &str, region: &str, offset: i64) {
+    let mut csv = String::from("month,region,revenue,units\n");
+    for month in 1..=6 {
+        let revenue = 1000.0 + (offset * 100 + month * 37) as f64;
+        let units = offset * 10 + month;
+        csv.push_str(&format!("2024-0{month}-01,{region},{revenue:.1},{units}\n"));
+    }
+    fs::write(dir.join(name), csv).unwrap();
+}
+
+fn compatible_dir() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("tempdir");
+    write_sales(dir.path(), "sales_north.csv", "north", 1);
+    write_sales(dir.path(), "sales_south.csv", "south", 2);
+    write_sales(dir.path(), "sales_east.csv", "east", 3);
+    fs::write(dir.path().join("notes.txt"), "not a csv").unwrap();
+    dir
+}
+
+#[test]
+fn compatible_files_are_concatenated_and_reported() {
+    let dir = compatible_dir();
+    let system = ChartSuggestionSystem::new().unwrap();
+    let result = system
+        .suggest_charts_from_directory(dir.path(), "sales_*.csv")
+        .expect("compatible files");
+
+    let names: Vec<_> = result
+        .files
+        .iter()
+        .map(|p| p.file_name().unwrap().to_str().unwrap().to_string())
+        .collect();
+    assert_eq!(names, ["sales_east.csv", "sales_north.csv", "sales_south.csv"]);
+    assert_eq!(result.row_count, 18);
+    assert!(!result.suggestions.is_empty());
+}
+
+#[test]
+fn schema_mismatch_names_the_offending_file() {
+    let dir = compatible_dir();
+    fs::write(
+        dir.path().join("sales_west.csv"),
+        "month,region,profit\n2024-01-01,west,12.5\n2024-02-01,west,13.0\n",
+    )
+    .unwrap();
+    let system = ChartSuggestionSystem::new().unwrap();
+    let err = system
+        .suggest_charts_from_directory(dir.path(), "*.csv")
+        .expect_err("mismatched schema");
+
+    match err {
+        ChartSuggestionError::Data(DataError::SchemaMismatch {
+            file,
+            reference,
+            details,
+        }) => {
+            assert!(file.ends_with("sales_west.csv"));
+            assert!(reference.ends_with("sales_east.csv"));
+            assert!(details.contains("profit"));
+        }
+        other => panic!("expected schema mismatch, got {other:?}"),
+    }
+}
+
+#[test]
+fn empty_match_is_reported() {
+    let dir = compatible_dir();
+    let system = ChartSuggestionSystem::new().unwrap();
+    let err = system
+        .suggest_charts_from_directory(dir.path(), "*.parquet")
+        .expect_err("no files");
+    assert!(matches!(
+        err,
+        ChartSuggestionError::Data(DataError::NoMatchingFiles { .. })
+    ));
+}
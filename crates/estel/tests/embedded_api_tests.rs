@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+use estel::{ApiGraph, ChartSuggestionSystem};
+
+#[test]
+fn embedded_api_matches_on_disk_file() {
+    let embedded = ApiGraph::default_embedded().expect("parse embedded api");
+    let on_disk =
+        ApiGraph::from_yaml_file(concat!(env!("CARGO_MANIFEST_DIR"), "/config/plotly_api.yml"))
+            .expect("parse on-disk api");
+
+    let names = |g: &ApiGraph| {
+        let mut names: Vec<String> = g.get_all_charts().iter().map(|c| c.name.clone()).collect();
+        names.sort();
+        names
+    };
+    assert!(!embedded.get_all_charts().is_empty());
+    assert_eq!(names(&embedded), names(&on_disk));
+}
+
+#[test]
+fn system_new_does_not_depend_on_working_directory() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let previous = std::env::current_dir().expect("cwd");
+    std::env::set_current_dir(dir.path()).expect("enter tempdir");
+    let system = ChartSuggestionSystem::new();
+    std::env::set_current_dir(previous).expect("restore cwd");
+
+    let system = system.expect("new() without config on disk");
+    assert!(!system.get_available_charts().is_empty());
+}
@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+#![cfg(feature = "learned-scorer")]
+
+mod common;
+
+use common::{profile, spec};
+use estel::api_graph::DataType;
+use estel::{FeedbackCollector, FeedbackRecord};
+use std::collections::HashMap;
+
+#[test]
+fn exported_jsonl_rows_carry_labels_and_features() {
+    let profiles = vec![
+        profile("region", DataType::Categorical, Some(4)),
+        profile("revenue", DataType::Numeric, None),
+        profile("cost", DataType::Numeric, None),
+    ];
+    let mut collector = FeedbackCollector::new();
+    collector.set_context(Some("sales.csv"), &profiles);
+    collector.set_symbolic_scores(HashMap::from([("scatter".to_string(), 0.7)]));
+
+    let bar = spec("bar", &[("x", "region"), ("y", "revenue")], 0.8);
+    let scatter = spec("scatter", &[("x", "revenue"), ("y", "cost")], 0.6);
+    let pie = spec("pie", &[("names", "region"), ("values", "cost")], 0.4);
+    collector.record(&bar, true);
+    collector.record(&scatter, false);
+    collector.record(&pie, false);
+
+    let jsonl = collector.to_jsonl().expect("serialise feedback");
+    let rows: Vec<FeedbackRecord> = jsonl
+        .lines()
+        .map(|l| serde_json::from_str(l).expect("valid JSONL row"))
+        .collect();
+    assert_eq!(rows.len(), 3);
+
+    let labels: Vec<(&str, bool)> = rows
+        .iter()
+        .map(|r| (r.chart_name.as_str(), r.chosen))
+        .collect();
+    assert_eq!(
+        labels,
+        vec![("bar", true), ("scatter", false), ("pie", false)]
+    );
+
+    for row in &rows {
+        assert_eq!(row.dataset.as_deref(), Some("sales.csv"));
+        assert_eq!(row.dataset_stats.dims, 3);
+        assert_eq!(row.dataset_stats.numeric_count, 2);
+        assert_eq!(row.features.to_vec().len(), 14);
+    }
+    assert!((rows[0].features.quality_score - 0.8).abs() < 1e-9);
+    assert!((rows[1].features.symbolic_score - 0.7).abs() < 1e-9);
+    assert_eq!(rows[2].features.symbolic_score, 0.0);
+    assert_eq!(rows[0].mappings.get("x").map(String::as_str), Some("region"));
+}
+
+#[test]
+fn export_writes_one_line_per_record() {
+    let profiles = vec![profile("value", DataType::Numeric, None)];
+    let mut collector = FeedbackCollector::new();
+    collector.set_context(None, &profiles);
+    collector.record(&spec("histogram", &[("x", "value")], 0.9), true);
+    collector.record(&spec("box", &[("y", "value")], 0.5), false);
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("feedback.jsonl");
+    let written = collector.export_jsonl(&path).expect("export feedback");
+    assert_eq!(written, 2);
+
+    let content = std::fs::read_to_string(&path).expect("read export");
+    assert_eq!(content.lines().count(), 2);
+    assert!(content.lines().next().unwrap().contains("\"chosen\":true"));
+}
+
+#[test]
+fn feedback_is_recorded_against_the_dataset_the_spec_was_suggested_for() {
+    let sales = vec![
+        profile("region", DataType::Categorical, Some(4)),
+        profile("revenue", DataType::Numeric, None),
+    ];
+    let sensors = vec![profile("reading", DataType::Numeric, None)];
+    let bar = spec("bar", &[("x", "region"), ("y", "revenue")], 0.8);
+    let histogram = spec("histogram", &[("x", "reading")], 0.9);
+    let mut collector = FeedbackCollector::new();
+    collector.set_context(Some("sales.csv"), &sales);
+    collector.set_symbolic_scores(HashMap::from([("bar".to_string(), 0.6)]));
+    collector.observe_suggestions([&bar]);
+    collector.set_context(Some("sensors.csv"), &sensors);
+    collector.observe_suggestions([&histogram]);
+
+    let record = collector.record(&bar, true).clone();
+    assert_eq!(record.dataset.as_deref(), Some("sales.csv"));
+    assert_eq!(record.dataset_stats.dims, 2);
+    assert!((record.features.symbolic_score - 0.6).abs() < 1e-9);
+
+    let record = collector.record(&histogram, false).clone();
+    assert_eq!(record.dataset.as_deref(), Some("sensors.csv"));
+    assert_eq!(record.dataset_stats.dims, 1);
+    assert_eq!(collector.len(), 2);
+}
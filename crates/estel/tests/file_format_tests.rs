@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+#![cfg(feature = "native")]
+
+use estel::{ChartSuggestionError, ChartSuggestionSystem, DataError, RenderSpec};
+use polars::prelude::*;
+use std::fs::{self, File};
+use std::path::Path;
+
+fn sales_frame() -> DataFrame {
+    let regions = ["north", "south", "east", "west"];
+    let region: Vec<&str> = (0..24).map(|i| regions[i % regions.len()]).collect();
+    let revenue: Vec<f64> = (0..24_i32).map(|i| 1000.5 + f64::from(i) * 37.25).collect();
+    let units: Vec<i64> = (0..24).map(|i| 10 + i * 3).collect();
+    df!("region" => region, "revenue" => revenue, "units" => units).unwrap()
+}
+
+fn write_formats(dir: &Path) {
+    let mut df = sales_frame();
+    CsvWriter::new(File::create(dir.join("sales.csv")).unwrap())
+        .finish(&mut df)
+        .unwrap();
+    ParquetWriter::new(File::create(dir.join("sales.parquet")).unwrap())
+        .finish(&mut df)
+        .unwrap();
+    JsonWriter::new(File::create(dir.join("sales.ndjson")).unwrap())
+        .with_json_format(JsonFormat::JsonLines)
+        .finish(&mut df)
+        .unwrap();
+}
+
+fn summary(specs: &[RenderSpec]) -> Vec<(String, String)> {
+    specs
+        .iter()
+        .map(|spec| (spec.chart_name.clone(), spec.mapping_key()))
+        .collect()
+}
+
+#[test]
+fn parquet_and_ndjson_match_csv_suggestions() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    write_formats(dir.path());
+    let system = ChartSuggestionSystem::new().unwrap();
+    let path = |name: &str| dir.path().join(name).to_str().unwrap().to_string();
+
+    let csv = system.suggest_charts_from_csv(&path("sales.csv")).unwrap();
+    let parquet = system
+        .suggest_charts_from_parquet(&path("sales.parquet"))
+        .unwrap();
+    let ndjson = system
+        .suggest_charts_from_ndjson(&path("sales.ndjson"))
+        .unwrap();
+
+    assert!(!csv.is_empty());
+    assert_eq!(summary(&parquet), summary(&csv));
+    assert_eq!(summary(&ndjson), summary(&csv));
+}
+
+#[test]
+fn corrupt_parquet_is_reported_as_data_file_error() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("broken.parquet");
+    fs::write(&path, "region,revenue\nnorth,1.0\n").unwrap();
+    let system = ChartSuggestionSystem::new().unwrap();
+    let err = system
+        .suggest_charts_from_parquet(path.to_str().unwrap())
+        .expect_err("corrupt parquet");
+
+    match err {
+        ChartSuggestionError::Data(DataError::DataFileError { path: reported, .. }) => {
+            assert!(reported.ends_with("broken.parquet"));
+        }
+        other => panic!("expected data file error, got {other:?}"),
+    }
+}
+
+#[test]
+fn malformed_ndjson_is_reported_as_data_file_error() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("broken.ndjson");
+    fs::write(&path, "{\"region\": \"north\", \"revenue\": \n").unwrap();
+    let system = ChartSuggestionSystem::new().unwrap();
+    let err = system
+        .suggest_charts_from_ndjson(path.to_str().unwrap())
+        .expect_err("malformed ndjson");
+
+    assert!(matches!(
+        err,
+        ChartSuggestionError::Data(DataError::DataFileError { .. })
+    ));
+}
+
+#[test]
+fn missing_file_is_an_io_error() {
+    let system = ChartSuggestionSystem::new().unwrap();
+    let err = system
+        .suggest_charts_from_parquet("/nonexistent/sales.parquet")
+        .expect_err("missing file");
+    assert!(matches!(err, ChartSuggestionError::Io(_)));
+}
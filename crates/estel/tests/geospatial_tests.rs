@@ -0,0 +1,73 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+#![cfg(feature = "native")]
+
+use estel::chart_matcher::find_qualified_charts;
+use estel::data_profiler::GeoRole;
+use estel::{ApiGraph, DataProfiler, DataType, MatchingConfig};
+use polars::prelude::*;
+
+fn cities() -> DataFrame {
+    df!(
+        "latitude" => [51.507, 48.857, 40.713, 35.676, -33.869, 55.755, 19.433, -23.551],
+        "longitude" => [-0.128, 2.352, -74.006, 139.650, 151.209, 37.617, -99.133, -46.633],
+        "population" => [8.9, 2.1, 8.3, 13.9, 5.3, 12.5, 9.2, 12.3]
+    )
+    .expect("valid frame")
+}
+
+#[test]
+fn lat_lon_columns_are_profiled_as_geospatial() {
+    let profiles = DataProfiler::new()
+        .profile_dataframe(&cities())
+        .expect("profile frame");
+    let lat = profiles.iter().find(|p| p.name == "latitude").unwrap();
+    let lon = profiles.iter().find(|p| p.name == "longitude").unwrap();
+    let pop = profiles.iter().find(|p| p.name == "population").unwrap();
+    assert_eq!(lat.data_type, DataType::Geospatial);
+    assert_eq!(lat.geo_role, Some(GeoRole::Latitude));
+    assert_eq!(lon.data_type, DataType::Geospatial);
+    assert_eq!(lon.geo_role, Some(GeoRole::Longitude));
+    assert_eq!(pop.data_type, DataType::Numeric);
+}
+
+#[test]
+fn out_of_range_coordinates_stay_numeric() {
+    let df = df!("lat" => [120.0, 250.0, 310.0]).expect("valid frame");
+    let profiles = DataProfiler::new().profile_dataframe(&df).unwrap();
+    assert_eq!(profiles[0].data_type, DataType::Numeric);
+    assert_eq!(profiles[0].geo_role, None);
+}
+
+#[test]
+fn geospatial_data_yields_map_suggestion() {
+    let profiles = DataProfiler::new().profile_dataframe(&cities()).unwrap();
+    let graph = ApiGraph::from_yaml_file("config/plotly_api.yml").expect("load api graph");
+    let specs = find_qualified_charts(&profiles, &graph, &MatchingConfig::for_exploration());
+    let map = specs
+        .iter()
+        .find(|s| matches!(s.chart_name.as_str(), "scatter_geo" | "scatter_map"))
+        .expect("expected a map chart suggestion");
+    assert_eq!(map.mappings.get("lat").map(String::as_str), Some("latitude"));
+    assert_eq!(map.mappings.get("lon").map(String::as_str), Some("longitude"));
+}
+
+#[test]
+fn location_only_data_yields_map_suggestion() {
+    let df = df!(
+        "country" => ["GBR", "FRA", "USA", "JPN", "AUS", "GBR", "FRA", "USA", "JPN", "AUS"],
+        "gdp" => [3.1, 2.9, 25.4, 4.2, 1.7, 3.3, 3.0, 26.9, 4.1, 1.8]
+    )
+    .expect("valid frame");
+    let profiles = DataProfiler::new().profile_dataframe(&df).unwrap();
+    let graph = ApiGraph::from_yaml_file("config/plotly_api.yml").expect("load api graph");
+    let specs = find_qualified_charts(&profiles, &graph, &MatchingConfig::for_exploration());
+    let map = specs
+        .iter()
+        .find(|s| s.chart_name == "scatter_geo")
+        .expect("expected a scatter_geo suggestion");
+    assert_eq!(map.mappings.get("locations").map(String::as_str), Some("country"));
+    assert!(!map.mappings.contains_key("lat"));
+}
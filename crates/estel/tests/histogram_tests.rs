@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+#![cfg(feature = "native")]
+
+use estel::{compute_histogram, Bin, BinningMethod, DataProfiler, ProfilingConfig};
+use polars::prelude::*;
+
+fn skewed() -> DataFrame {
+    df!(
+        "latency" => [
+            1.0, 1.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0, 3.0, 3.0,
+            4.0, 5.0, 8.0, 13.0, 21.0, 34.0, 55.0, 89.0, 144.0, 233.0
+        ]
+    )
+    .unwrap()
+}
+
+fn latency_histogram(
+    enable_advanced_stats: bool,
+    bins: usize,
+    method: BinningMethod,
+) -> Option<Vec<Bin>> {
+    let config = ProfilingConfig {
+        enable_advanced_stats,
+        histogram_bins: bins,
+        binning_method: method,
+        ..Default::default()
+    };
+    DataProfiler::with_config(config)
+        .profile_dataframe(&skewed())
+        .unwrap()
+        .into_iter()
+        .find(|p| p.name == "latency")
+        .unwrap()
+        .histogram
+}
+
+fn edges(bins: &[Bin]) -> Vec<f64> {
+    bins.iter()
+        .map(|b| b.lower)
+        .chain(bins.last().map(|b| b.upper))
+        .collect()
+}
+
+fn counts(bins: &[Bin]) -> Vec<usize> {
+    bins.iter().map(|b| b.count).collect()
+}
+
+#[test]
+fn histogram_is_only_computed_with_advanced_stats() {
+    assert!(latency_histogram(false, 4, BinningMethod::EqualWidth).is_none());
+    assert!(latency_histogram(true, 4, BinningMethod::EqualWidth).is_some());
+}
+
+#[test]
+fn bin_count_follows_the_config() {
+    let bins = latency_histogram(true, 6, BinningMethod::EqualWidth).unwrap();
+    assert_eq!(bins.len(), 6);
+    assert_eq!(counts(&bins).iter().sum::<usize>(), 20);
+    assert_eq!(bins[0].lower, 1.0);
+    assert_eq!(bins[5].upper, 233.0);
+}
+
+#[test]
+fn equal_width_and_quantile_binning_differ_on_skewed_data() {
+    let equal_width = latency_histogram(true, 4, BinningMethod::EqualWidth).unwrap();
+    let quantile = latency_histogram(true, 4, BinningMethod::Quantile).unwrap();
+
+    assert_eq!(edges(&equal_width), vec![1.0, 59.0, 117.0, 175.0, 233.0]);
+    assert_eq!(counts(&equal_width), vec![17, 1, 1, 1]);
+
+    assert_eq!(edges(&quantile), vec![1.0, 1.75, 3.5, 24.25, 233.0]);
+    assert_eq!(counts(&quantile), vec![5, 5, 5, 5]);
+}
+
+#[test]
+fn constant_values_collapse_into_a_single_bin() {
+    let bins = compute_histogram(&[7.0; 5], 4, BinningMethod::Quantile);
+    assert_eq!(
+        bins,
+        vec![Bin {
+            lower: 7.0,
+            upper: 7.0,
+            count: 5
+        }]
+    );
+    assert!(compute_histogram(&[], 4, BinningMethod::EqualWidth).is_empty());
+}
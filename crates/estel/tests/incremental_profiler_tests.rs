@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+#![cfg(feature = "native")]
+
+use estel::{DataProfiler, DimensionProfile};
+use polars::prelude::*;
+
+fn orders() -> DataFrame {
+    df!(
+        "segment" => [
+            Some("retail"), Some("wholesale"), None, Some("retail"), Some("online"),
+            Some("online"), Some("retail"), Some("wholesale"), Some("retail"), None,
+            Some("online"), Some("retail")
+        ],
+        "sales" => [
+            Some(120.5), Some(98.0), Some(143.25), None, Some(110.0), Some(105.75),
+            Some(131.0), Some(99.5), Some(125.0), Some(117.25), None, Some(108.0)
+        ],
+        "units" => [3i64, 5, 4, 6, 2, 5, 4, 3, 6, 5, 4, 3],
+        "order_date" => [
+            "2024-01-01", "2024-01-02", "2024-01-03", "2024-01-04", "2024-01-05",
+            "2024-01-06", "2024-01-07", "2024-01-08", "2024-01-09", "2024-01-10",
+            "2024-01-11", "2024-01-12"
+        ]
+    )
+    .unwrap()
+}
+
+fn by_name<'a>(profiles: &'a [DimensionProfile], name: &str) -> &'a DimensionProfile {
+    profiles.iter().find(|p| p.name == name).unwrap()
+}
+
+fn assert_close(left: Option<f64>, right: Option<f64>) {
+    match (left, right) {
+        (Some(l), Some(r)) => assert!((l - r).abs() < 1e-9, "{l} != {r}"),
+        (l, r) => assert_eq!(l, r),
+    }
+}
+
+#[test]
+fn batched_profiles_match_whole_frame_profiles() {
+    let df = orders();
+    let profiler = DataProfiler::new();
+    let whole = profiler.profile_dataframe(&df).unwrap();
+
+    let mut incremental = profiler.profiler_builder();
+    for (offset, length) in [(0, 4), (4, 5), (9, 3)] {
+        incremental.update_batch(&df.slice(offset, length)).unwrap();
+    }
+    assert_eq!(incremental.rows_seen(), df.height());
+    let batched = incremental.finalize();
+
+    let names: Vec<&str> = batched.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["segment", "sales", "units", "order_date"]);
+    for expected in &whole {
+        let actual = by_name(&batched, &expected.name);
+        assert_eq!(actual.data_type, expected.data_type, "{}", expected.name);
+        assert_eq!(actual.total_count, expected.total_count);
+        assert_eq!(actual.null_count, expected.null_count);
+        assert_close(Some(actual.null_percentage), Some(expected.null_percentage));
+        assert_eq!(actual.cardinality, expected.cardinality, "{}", expected.name);
+        assert_close(Some(actual.type_confidence), Some(expected.type_confidence));
+        assert_eq!(actual.geo_role, expected.geo_role);
+        assert_eq!(actual.semantic_type, expected.semantic_type);
+        assert_eq!(actual.date_format, expected.date_format);
+        match (&actual.numeric_stats, &expected.numeric_stats) {
+            (Some(actual), Some(expected)) => {
+                assert_close(actual.mean, expected.mean);
+                assert_close(actual.std, expected.std);
+                assert_close(actual.min, expected.min);
+                assert_close(actual.max, expected.max);
+            }
+            (actual, expected) => assert_eq!(actual.is_some(), expected.is_some()),
+        }
+        match (&actual.temporal_stats, &expected.temporal_stats) {
+            (Some(actual), Some(expected)) => {
+                assert_eq!(actual.min_date, expected.min_date);
+                assert_eq!(actual.max_date, expected.max_date);
+                assert_eq!(actual.date_range_days, expected.date_range_days);
+                assert_eq!(actual.unique_count, expected.unique_count);
+            }
+            (actual, expected) => assert_eq!(actual.is_some(), expected.is_some()),
+        }
+    }
+}
+
+#[test]
+fn columns_missing_from_a_batch_count_as_nulls() {
+    let mut incremental = DataProfiler::new().profiler_builder();
+    incremental
+        .update_batch(&df!("a" => [1i64, 2, 3]).unwrap())
+        .unwrap();
+    incremental
+        .update_batch(&df!("a" => [4i64, 5], "b" => ["x", "y"]).unwrap())
+        .unwrap();
+
+    let profiles = incremental.finalize();
+    let b = by_name(&profiles, "b");
+    assert_eq!(b.total_count, 5);
+    assert_eq!(b.null_count, 3);
+    assert_eq!(by_name(&profiles, "a").null_count, 0);
+}
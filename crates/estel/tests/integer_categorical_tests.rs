@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+#![cfg(feature = "native")]
+
+use estel::{DataProfiler, DataType, DimensionProfile, ProfilingConfig};
+use polars::prelude::*;
+
+fn ratings() -> DataFrame {
+    df!(
+        "rating" => [1i64, 5, 3, 4, 2, 5, 4, 3, 1, 2, 5, 4],
+        "price" => [9.5, 12.0, 7.25, 20.0, 3.5, 15.0, 11.0, 8.0, 6.5, 14.0, 13.5, 10.0]
+    )
+    .unwrap()
+}
+
+fn rating_profile(threshold: usize) -> DimensionProfile {
+    let config = ProfilingConfig {
+        integer_as_categorical_threshold: threshold,
+        ..Default::default()
+    };
+    DataProfiler::with_config(config)
+        .profile_dataframe(&ratings())
+        .unwrap()
+        .into_iter()
+        .find(|p| p.name == "rating")
+        .unwrap()
+}
+
+#[test]
+fn low_cardinality_integers_become_categorical_under_threshold() {
+    let p = rating_profile(10);
+    assert_eq!(p.data_type, DataType::Categorical);
+    assert_eq!(p.overridden_from, Some(DataType::Numeric));
+    assert_eq!(p.cardinality, Some(5));
+}
+
+#[test]
+fn integers_stay_numeric_at_or_above_threshold() {
+    let p = rating_profile(5);
+    assert_eq!(p.data_type, DataType::Numeric);
+    assert_eq!(p.overridden_from, None);
+}
+
+#[test]
+fn threshold_disabled_by_default() {
+    let p = rating_profile(ProfilingConfig::default().integer_as_categorical_threshold);
+    assert_eq!(p.data_type, DataType::Numeric);
+}
@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+#![cfg(feature = "learned-scorer")]
+
+mod common;
+
+use common::{profile, spec};
+use estel::{DataType, DimensionProfile, LearnedDatasetStats, LearnedFeatureVector, LearnedScorer};
+use std::collections::HashMap;
+
+fn profiles() -> Vec<DimensionProfile> {
+    vec![
+        profile("region", DataType::Categorical, Some(5)),
+        profile("revenue", DataType::Numeric, None),
+        profile("cost", DataType::Numeric, None),
+    ]
+}
+
+#[test]
+fn contributions_plus_bias_match_the_rerank_score() {
+    let profiles = profiles();
+    let specs = vec![
+        spec("bar", &[("x", "region"), ("y", "revenue")], 0.7),
+        spec("scatter", &[("x", "revenue"), ("y", "cost")], 0.6),
+    ];
+    let symbolic = HashMap::from([("bar".to_string(), 0.9), ("scatter".to_string(), 0.2)]);
+    let scorer = LearnedScorer::from_weights(
+        LearnedScorer::default_head().weights,
+        0.05,
+        LearnedScorer::default_head().feature_names,
+    );
+
+    let plain = scorer.rerank_specs(&profiles, specs.clone(), Some(&symbolic));
+    let explained = scorer.rerank_specs_explained(&profiles, specs, Some(&symbolic));
+    assert_eq!(plain.len(), explained.len());
+
+    for ((plain_spec, plain_score), (ranked, score, contributions)) in
+        plain.iter().zip(&explained)
+    {
+        assert_eq!(plain_spec, ranked);
+        assert_eq!(plain_score, score);
+        assert_eq!(contributions.len(), scorer.feature_names.len());
+        let total: f64 = contributions.iter().map(|(_, c)| c).sum::<f64>() + scorer.bias;
+        assert!((total.clamp(0.0, 1.0) - score).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn explain_names_each_signed_contribution() {
+    let profiles = profiles();
+    let stats = LearnedDatasetStats::from_profiles(&profiles);
+    let features = LearnedFeatureVector::from_spec(
+        &spec("bar", &[("x", "region"), ("y", "revenue")], 0.8),
+        &stats,
+        0.5,
+    );
+    let scorer = LearnedScorer::default_head();
+
+    let contributions: HashMap<String, f64> = scorer.explain(&features).into_iter().collect();
+    assert!((contributions["quality_score"] - 0.25 * 0.8).abs() < 1e-12);
+    assert!((contributions["symbolic_score"] - 0.05 * 0.5).abs() < 1e-12);
+    assert!(contributions["avg_cardinality_scaled"] <= 0.0);
+}
@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+#![cfg(feature = "learned-scorer")]
+
+mod common;
+
+use common::profile;
+use estel::chart_matcher::find_qualified_charts;
+use estel::{ApiGraph, ChartSuggestionError, DataType, LearnedScorer, MatchingConfig};
+use std::collections::HashMap;
+
+#[test]
+fn default_head_reranks_identically_after_round_trip() {
+    let profiles = vec![
+        profile("region", DataType::Categorical, Some(5)),
+        profile("revenue", DataType::Numeric, None),
+        profile("cost", DataType::Numeric, None),
+    ];
+    let graph = ApiGraph::default_embedded().unwrap();
+    let specs = find_qualified_charts(&profiles, &graph, &MatchingConfig::for_exploration());
+    assert!(!specs.is_empty());
+    let symbolic: HashMap<String, f64> = specs
+        .iter()
+        .enumerate()
+        .map(|(i, spec)| (spec.chart_name.clone(), 1.0 / (i + 1) as f64))
+        .collect();
+
+    let scorer = LearnedScorer::default_head();
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("head.json");
+    scorer.save_weights(&path).expect("save head");
+    let loaded = LearnedScorer::load_weights(&path).expect("load head");
+
+    assert_eq!(loaded.weights, scorer.weights);
+    assert_eq!(loaded.bias, scorer.bias);
+    assert_eq!(
+        loaded.rerank_specs(&profiles, specs.clone(), Some(&symbolic)),
+        scorer.rerank_specs(&profiles, specs, Some(&symbolic))
+    );
+}
+
+#[test]
+fn mismatched_feature_set_is_rejected() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("head.json");
+    std::fs::write(
+        &path,
+        r#"{"weights":[0.5],"bias":0.0,"feature_names":["quality_score"]}"#,
+    )
+    .unwrap();
+
+    let err = LearnedScorer::load_weights(&path).expect_err("mismatched head");
+    assert!(matches!(err, ChartSuggestionError::Serialisation(_)));
+}
+
+#[test]
+fn missing_file_is_an_io_error() {
+    let err = LearnedScorer::load_weights("/nonexistent/head.json").expect_err("missing head");
+    assert!(matches!(err, ChartSuggestionError::Io(_)));
+}
@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+#![cfg(feature = "learned-scorer")]
+
+use estel::{LearnedFeatureVector, LearnedScorer};
+
+fn features(quality_score: f64, symbolic_score: f64) -> LearnedFeatureVector {
+    LearnedFeatureVector {
+        quality_score,
+        technical_feasibility: 0.0,
+        semantic_appropriateness: 0.0,
+        visual_effectiveness: 0.0,
+        data_utilisation: 0.0,
+        complexity_match: 0.0,
+        dims_used_norm: 0.0,
+        complete_flag: 0.0,
+        avg_profile_quality: 0.0,
+        temporal_present: 0.0,
+        numeric_count_norm: 0.0,
+        categorical_count_norm: 0.0,
+        avg_cardinality_scaled: 0.0,
+        symbolic_score,
+    }
+}
+
+fn weight(scorer: &LearnedScorer, name: &str) -> f64 {
+    let index = scorer
+        .feature_names
+        .iter()
+        .position(|n| *n == name)
+        .expect("known feature");
+    scorer.weights[index]
+}
+
+#[test]
+fn positive_updates_raise_the_feature_weight_and_reorder_predictions() {
+    let mut scorer = LearnedScorer::default_head();
+    let liked = features(0.0, 1.0);
+    let other = features(0.6, 0.0);
+    assert!(scorer.predict(&other) > scorer.predict(&liked));
+
+    let quality_before = weight(&scorer, "quality_score");
+    let mut symbolic_weights = vec![weight(&scorer, "symbolic_score")];
+    for _ in 0..50 {
+        scorer.update(&liked, 1.0, 0.1);
+        symbolic_weights.push(weight(&scorer, "symbolic_score"));
+    }
+
+    assert!(symbolic_weights.windows(2).all(|w| w[1] > w[0]));
+    assert_eq!(weight(&scorer, "quality_score"), quality_before);
+    assert!(scorer.predict(&liked) > scorer.predict(&other));
+    assert!(scorer.predict(&liked) > 0.9);
+}
+
+#[test]
+fn negative_updates_lower_the_prediction() {
+    let mut scorer = LearnedScorer::default_head();
+    let disliked = features(0.8, 0.5);
+    let before = scorer.predict(&disliked);
+
+    scorer.update(&disliked, 0.0, 0.2);
+
+    assert!(scorer.predict(&disliked) < before);
+    assert!(weight(&scorer, "quality_score") < 0.25);
+}
+
+#[test]
+fn saved_weights_round_trip() {
+    let mut scorer = LearnedScorer::default_head();
+    scorer.update(&features(0.5, 1.0), 1.0, 0.05);
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("weights.json");
+    scorer.save_weights(&path).expect("save weights");
+
+    let loaded = LearnedScorer::load_weights(&path).expect("load weights");
+    assert_eq!(loaded.weights, scorer.weights);
+    assert_eq!(loaded.bias, scorer.bias);
+    assert_eq!(loaded.feature_names, scorer.feature_names);
+}
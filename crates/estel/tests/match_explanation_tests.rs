@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+mod common;
+
+use common::profile;
+use estel::chart_matcher::{
+    explain_chart_matches, find_qualified_charts, find_qualified_charts_explained,
+    RejectionReason,
+};
+use estel::{ApiGraph, DataType, DimensionProfile, MatchingConfig};
+
+const CATALOGUE: &str = r#"
+charts:
+  - name: bar
+    library: plotly
+    description: "Compares a numeric value across categories."
+    tags: ["comparison", "categorical", "bar"]
+    args:
+      x: { data_type: [Categorical, Temporal, Numeric], required: true }
+      y: { data_type: [Numeric, Categorical], required: true }
+      color: { data_type: [Numeric, Categorical], required: false }
+  - name: density_heatmap
+    library: plotly
+    description: "Shows the joint density of two numeric columns."
+    tags: ["distribution", "density"]
+    args:
+      x: { data_type: Numeric, required: true }
+      y: { data_type: Numeric, required: true }
+  - name: barh
+    library: matplotlib
+    description: "Horizontal bar chart."
+    tags: ["comparison"]
+    args:
+      y: { data_type: Categorical, required: true }
+      width: { data_type: Numeric, required: true }
+"#;
+
+fn graph() -> ApiGraph {
+    ApiGraph::from_yaml_string(CATALOGUE).unwrap()
+}
+
+fn team_points() -> Vec<DimensionProfile> {
+    vec![
+        profile("team", DataType::Categorical, Some(4)),
+        profile("points", DataType::Numeric, None),
+    ]
+}
+
+#[test]
+fn bar_explanation_names_the_columns_it_consumed() {
+    let profiles = team_points();
+    let matches =
+        find_qualified_charts_explained(&profiles, &graph(), &MatchingConfig::default());
+
+    let (spec, explanation) = matches
+        .iter()
+        .find(|(spec, _)| spec.chart_name == "bar")
+        .expect("bar should qualify");
+    let mut consumed: Vec<_> = explanation
+        .satisfied
+        .iter()
+        .map(|a| (a.column.as_str(), a.data_type.clone()))
+        .collect();
+    consumed.sort_by(|a, b| a.0.cmp(b.0));
+    assert_eq!(
+        consumed,
+        [("points", DataType::Numeric), ("team", DataType::Categorical)]
+    );
+    for assignment in &explanation.satisfied {
+        assert!(assignment.spec.required);
+        assert!(assignment.spec.data_type.accepts(&assignment.data_type));
+        assert_eq!(spec.mappings[&assignment.arg], assignment.column);
+    }
+    assert_eq!(explanation.column_for("y"), Some("team"));
+    assert!(explanation.unsatisfied.is_empty());
+    assert!(explanation.penalties.is_empty());
+}
+
+#[test]
+fn explained_matches_agree_with_plain_matching() {
+    let profiles = team_points();
+    let config = MatchingConfig::default();
+    let plain = find_qualified_charts(&profiles, &graph(), &config);
+    let explained = find_qualified_charts_explained(&profiles, &graph(), &config);
+
+    let plain: Vec<_> = plain.iter().map(|s| (s.chart_name.clone(), s.mapping_key())).collect();
+    let explained: Vec<_> = explained
+        .iter()
+        .map(|(s, _)| (s.chart_name.clone(), s.mapping_key()))
+        .collect();
+    assert_eq!(plain, explained);
+}
+
+#[test]
+fn rejected_charts_carry_their_disqualifying_reason() {
+    let profiles = team_points();
+    let explained = explain_chart_matches(&profiles, &graph(), &MatchingConfig::default());
+
+    match explained.rejection("density_heatmap") {
+        Some(RejectionReason::UnmetRequirements(unmet)) => {
+            assert_eq!(unmet.len(), 1);
+            assert_eq!(unmet[0].arg, "y");
+            assert_eq!(unmet[0].reason, "needs a second numeric column for y");
+        }
+        other => panic!("expected density_heatmap to miss a column, got {other:?}"),
+    }
+    let barh = explained.rejection("barh").unwrap();
+    assert!(matches!(
+        barh,
+        RejectionReason::UnsupportedLibrary(library) if library == "matplotlib"
+    ));
+    assert_eq!(
+        barh.to_string(),
+        "library 'matplotlib' is not supported by the matcher"
+    );
+    assert!(explained.rejection("bar").is_none());
+}
+
+#[test]
+fn coerced_columns_are_listed_as_penalties() {
+    let profiles = vec![
+        profile("team", DataType::Categorical, Some(4)),
+        profile("points", DataType::Numeric, None),
+        profile("season", DataType::Categorical, Some(3)),
+    ];
+    let catalogue = r#"
+charts:
+  - name: line
+    library: plotly
+    description: "Trend over time."
+    tags: ["trend"]
+    args:
+      x: { data_type: Temporal, required: true }
+      y: { data_type: Numeric, required: true }
+"#;
+    let graph = ApiGraph::from_yaml_string(catalogue).unwrap();
+    let matches = find_qualified_charts_explained(&profiles, &graph, &MatchingConfig::default());
+
+    let (_, explanation) = matches
+        .iter()
+        .find(|(spec, _)| spec.chart_name == "line")
+        .expect("line should qualify through coercion");
+    assert_eq!(explanation.penalties.len(), 1);
+    let penalty = &explanation.penalties[0];
+    assert_eq!(penalty.arg, "x");
+    assert_eq!(penalty.reason, "categorical column coerced to temporal");
+    assert!((penalty.amount - 0.3).abs() < 1e-9);
+    assert!((explanation.total_penalty() - 0.3).abs() < 1e-9);
+}
@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+#![cfg(feature = "native")]
+
+mod common;
+
+use common::spec;
+use polars::prelude::*;
+use serde_json::json;
+
+fn sales() -> DataFrame {
+    df!(
+        "region" => ["north", "south", "north", "east"],
+        "revenue" => [120.0, 95.5, 130.0, 80.0],
+        "units" => [12i64, 9, 14, 7]
+    )
+    .unwrap()
+}
+
+#[test]
+fn scatter_maps_axes_to_a_marker_trace() {
+    let figure = spec("scatter", &[("x", "units"), ("y", "revenue")], 0.9)
+        .to_plotly_json(&sales());
+
+    let traces = figure["data"].as_array().unwrap();
+    assert_eq!(traces.len(), 1);
+    assert_eq!(traces[0]["type"], "scatter");
+    assert_eq!(traces[0]["mode"], "markers");
+    assert_eq!(traces[0]["x"], json!([12.0, 9.0, 14.0, 7.0]));
+    assert_eq!(traces[0]["y"], json!([120.0, 95.5, 130.0, 80.0]));
+    assert_eq!(figure["layout"]["xaxis"]["title"]["text"], "units");
+    assert_eq!(figure["layout"]["yaxis"]["title"]["text"], "revenue");
+}
+
+#[test]
+fn bar_maps_categorical_x_and_numeric_y() {
+    let figure = spec("bar", &[("x", "region"), ("y", "revenue")], 0.9)
+        .to_plotly_json(&sales());
+
+    let trace = &figure["data"][0];
+    assert_eq!(trace["type"], "bar");
+    assert_eq!(trace["x"], json!(["north", "south", "north", "east"]));
+    assert_eq!(trace["y"], json!([120.0, 95.5, 130.0, 80.0]));
+    assert_eq!(figure["layout"]["xaxis"]["title"]["text"], "region");
+    assert_eq!(figure["layout"]["yaxis"]["title"]["text"], "revenue");
+}
+
+#[test]
+fn categorical_colour_splits_traces_by_group() {
+    let mappings = [("x", "units"), ("y", "revenue"), ("color", "region")];
+    let figure = spec("scatter", &mappings, 0.9).to_plotly_json(&sales());
+
+    let traces = figure["data"].as_array().unwrap();
+    let names: Vec<_> = traces.iter().map(|t| t["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["north", "south", "east"]);
+    assert_eq!(traces[0]["x"], json!([12.0, 14.0]));
+    assert_eq!(figure["layout"]["showlegend"], true);
+}
+
+#[test]
+fn arguments_outside_the_chart_spec_are_ignored() {
+    let mappings = [("x", "region"), ("y", "revenue"), ("size", "units")];
+    let figure = spec("bar", &mappings, 0.9).to_plotly_json(&sales());
+    assert!(figure["data"][0].get("marker").is_none());
+
+    let figure = spec("scatter", &mappings, 0.9).to_plotly_json(&sales());
+    assert_eq!(figure["data"][0]["marker"]["size"], json!([12.0, 9.0, 14.0, 7.0]));
+}
+
+#[test]
+fn unsupported_charts_produce_an_empty_figure() {
+    let figure = spec("sankey", &[("source", "region")], 0.9)
+        .to_plotly_json(&sales());
+    assert_eq!(figure["data"], json!([]));
+    assert_eq!(figure["layout"]["title"]["text"], "sankey");
+}
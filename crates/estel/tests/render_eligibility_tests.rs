@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+mod common;
+
+use common::profile;
+use estel::{ApiGraph, DataType, RenderEligibility};
+
+fn graph() -> ApiGraph {
+    ApiGraph::default_embedded().expect("embedded api graph")
+}
+
+#[test]
+fn scatter_is_eligible_with_two_numeric_columns() {
+    let profiles = [
+        profile("height", DataType::Numeric, None),
+        profile("weight", DataType::Numeric, None),
+    ];
+    match graph().can_render("scatter", &profiles) {
+        RenderEligibility::Eligible { mappings } => {
+            let mut columns: Vec<_> = mappings.values().map(String::as_str).collect();
+            columns.sort();
+            assert_eq!(columns, ["height", "weight"]);
+            assert!(mappings.contains_key("x") && mappings.contains_key("y"));
+        }
+        other => panic!("expected scatter to be eligible, got {other:?}"),
+    }
+}
+
+#[test]
+fn density_heatmap_reports_the_missing_second_numeric_column() {
+    let profiles = [
+        profile("height", DataType::Numeric, None),
+        profile("team", DataType::Categorical, Some(4)),
+    ];
+    let eligibility = graph().can_render("density_heatmap", &profiles);
+    assert!(!eligibility.is_eligible());
+
+    let unmet = eligibility.unmet();
+    assert_eq!(unmet.len(), 1);
+    assert_eq!(unmet[0].arg, "y");
+    assert!(unmet[0].spec.required);
+    assert_eq!(unmet[0].reason, "needs a second numeric column for y");
+}
+
+#[test]
+fn pie_without_categorical_column_lists_the_unmet_role() {
+    let profiles = [profile("revenue", DataType::Numeric, None)];
+    let eligibility = graph().can_render("pie", &profiles);
+
+    let unmet = eligibility.unmet();
+    assert_eq!(unmet.len(), 1);
+    assert_eq!(unmet[0].arg, "names");
+    assert_eq!(unmet[0].reason, "needs a categorical column for names");
+}
+
+#[test]
+fn unknown_chart_is_reported() {
+    let eligibility = graph().can_render("not_a_chart", &[]);
+    assert!(matches!(
+        eligibility,
+        RenderEligibility::UnknownChart { ref chart_name } if chart_name == "not_a_chart"
+    ));
+}
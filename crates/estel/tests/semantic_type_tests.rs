@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+#![cfg(feature = "native")]
+
+use estel::chart_matcher::find_qualified_charts;
+use estel::data_profiler::SemanticType;
+use estel::{ApiGraph, DataProfiler, DataType, DimensionProfile, MatchingConfig};
+use polars::prelude::*;
+
+fn profile_of(df: &DataFrame, name: &str) -> DimensionProfile {
+    DataProfiler::new()
+        .profile_dataframe(df)
+        .expect("profile frame")
+        .into_iter()
+        .find(|p| p.name == name)
+        .expect("column profiled")
+}
+
+#[test]
+fn dollar_prefixed_values_are_currency() {
+    let df = df!(
+        "amount_paid" => ["$1,200.50", "$35.00", "$980.10", "$12.99", "$4,500.00"]
+    )
+    .unwrap();
+    let p = profile_of(&df, "amount_paid");
+    assert_eq!(p.data_type, DataType::Numeric);
+    assert_eq!(p.semantic_type, Some(SemanticType::Currency));
+    let stats = p.numeric_stats.expect("numeric stats");
+    assert_eq!(stats.max, Some(4500.0));
+    assert!(p.sample_values.iter().any(|v| v.starts_with('$')));
+}
+
+#[test]
+fn percent_suffixed_values_are_stored_as_fractions() {
+    let df = df!("growth" => ["12%", "7.5%", "-3%", "40%"]).unwrap();
+    let p = profile_of(&df, "growth");
+    assert_eq!(p.semantic_type, Some(SemanticType::Percentage));
+    assert_eq!(p.numeric_stats.unwrap().max, Some(0.4));
+}
+
+#[test]
+fn unique_sequential_integers_are_identifiers() {
+    let df = df!(
+        "row" => [1i64, 2, 3, 4, 5, 6, 7, 8],
+        "score" => [3.2, 4.8, 1.1, 9.4, 2.2, 7.7, 5.0, 6.3]
+    )
+    .unwrap();
+    assert_eq!(
+        profile_of(&df, "row").semantic_type,
+        Some(SemanticType::Identifier)
+    );
+    assert_eq!(profile_of(&df, "score").semantic_type, None);
+}
+
+#[test]
+fn identifiers_are_not_mapped_as_measures() {
+    let df = df!(
+        "customer_id" => [101i64, 102, 103, 104, 105, 106, 107, 108],
+        "segment" => ["a", "b", "a", "c", "b", "a", "c", "b"],
+        "spend" => [120.0, 80.5, 99.0, 310.2, 42.0, 77.7, 150.0, 65.3]
+    )
+    .unwrap();
+    let profiles = DataProfiler::new().profile_dataframe(&df).unwrap();
+    let graph = ApiGraph::from_yaml_file("config/plotly_api.yml").expect("load api graph");
+    let specs = find_qualified_charts(&profiles, &graph, &MatchingConfig::for_exploration());
+    for spec in &specs {
+        for arg in ["y", "size", "values"] {
+            assert_ne!(
+                spec.mappings.get(arg).map(String::as_str),
+                Some("customer_id"),
+                "{} maps identifier to {arg}",
+                spec.chart_name
+            );
+        }
+    }
+}
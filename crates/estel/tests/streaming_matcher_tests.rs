@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+mod common;
+
+use common::profile;
+use estel::chart_matcher::{find_qualified_charts, find_qualified_charts_iter};
+use estel::{ApiGraph, DataType, DimensionProfile, MatchingConfig, RenderSpec};
+
+fn wide_dataset(columns: usize) -> Vec<DimensionProfile> {
+    (0..columns)
+        .map(|i| {
+            let (data_type, cardinality) = match i % 3 {
+                0 => (DataType::Numeric, None),
+                1 => (DataType::Categorical, Some(3 + i % 7)),
+                _ => (DataType::Temporal, None),
+            };
+            let mut p = profile(&format!("col_{i:03}"), data_type, cardinality);
+            p.quality_score = 0.5 + (i % 10) as f64 * 0.05;
+            p
+        })
+        .collect()
+}
+
+#[test]
+fn iterator_yields_specs_in_descending_score_order() {
+    let profiles = wide_dataset(300);
+    let graph = ApiGraph::default_embedded().unwrap();
+    let config = MatchingConfig {
+        prefer_high_dimensionality: false,
+        ..MatchingConfig::for_exploration()
+    };
+
+    let streamed: Vec<_> = find_qualified_charts_iter(&profiles, &graph, &config).collect();
+    assert!(!streamed.is_empty());
+    assert!(streamed
+        .windows(2)
+        .all(|pair| pair[0].quality_score >= pair[1].quality_score));
+
+    let eager = find_qualified_charts(&profiles, &graph, &config);
+    let key = |s: &RenderSpec| format!("{}|{}", s.chart_name, s.mapping_key());
+    assert_eq!(
+        streamed.iter().map(key).collect::<Vec<_>>(),
+        eager.iter().map(key).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn taking_k_keeps_the_buffer_bounded() {
+    let profiles = wide_dataset(300);
+    let graph = ApiGraph::default_embedded().unwrap();
+    let config = MatchingConfig {
+        max_suggestions_per_chart: 3,
+        prefer_high_dimensionality: false,
+        ..MatchingConfig::for_exploration()
+    };
+
+    let mut charts = find_qualified_charts_iter(&profiles, &graph, &config);
+    assert_eq!(charts.capacity(), 3);
+    assert!(charts.buffered() <= charts.capacity());
+
+    let first_two: Vec<_> = charts.by_ref().take(2).collect();
+    assert_eq!(first_two.len(), 2);
+    assert!(first_two[0].quality_score >= first_two[1].quality_score);
+    assert!(charts.buffered() <= 1);
+}
@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+mod common;
+
+use common::profile;
+use estel::chart_matcher::find_qualified_charts;
+use estel::{
+    ApiGraph, CoercionMatrix, DataType, DataTypeSpec, DimensionProfile, MatchingConfig,
+    RenderSpec,
+};
+
+fn schedule(boundary_type: DataType) -> Vec<DimensionProfile> {
+    vec![
+        profile("task", DataType::Categorical, Some(6)),
+        profile("start", boundary_type.clone(), Some(6)),
+        profile("finish", boundary_type, Some(6)),
+    ]
+}
+
+fn timeline(profiles: &[DimensionProfile], graph: &ApiGraph) -> Option<RenderSpec> {
+    let config = MatchingConfig {
+        min_quality_score: 0.0,
+        ..MatchingConfig::for_exploration()
+    };
+    find_qualified_charts(profiles, graph, &config)
+        .into_iter()
+        .find(|spec| spec.chart_name == "timeline")
+}
+
+#[test]
+fn categorical_column_substitutes_for_temporal_at_a_penalty() {
+    let graph = ApiGraph::default_embedded().unwrap();
+    let exact = timeline(&schedule(DataType::Temporal), &graph).expect("exact timeline");
+    let coerced = timeline(&schedule(DataType::Categorical), &graph).expect("coerced timeline");
+
+    assert!(coerced.quality_score < exact.quality_score);
+    assert_eq!(coerced.mappings.get("y").map(String::as_str), Some("task"));
+    let mut boundaries = [&coerced.mappings["x_start"], &coerced.mappings["x_end"]];
+    boundaries.sort();
+    assert_eq!(boundaries, ["finish", "start"]);
+}
+
+#[test]
+fn disabling_coercions_drops_near_miss_suggestions() {
+    let graph = ApiGraph::default_embedded()
+        .unwrap()
+        .with_coercions(CoercionMatrix::none());
+    assert!(timeline(&schedule(DataType::Categorical), &graph).is_none());
+}
+
+#[test]
+fn matrix_reports_penalties_for_allowed_coercions_only() {
+    let matrix = CoercionMatrix::default();
+    let temporal = DataTypeSpec::Single(DataType::Temporal);
+    assert_eq!(matrix.penalty_for(&temporal, &DataType::Temporal), Some(0.0));
+    assert_eq!(matrix.penalty_for(&temporal, &DataType::Categorical), Some(0.3));
+    assert_eq!(matrix.penalty_for(&temporal, &DataType::Numeric), None);
+
+    let custom = matrix.with(DataType::Numeric, DataType::Temporal, 0.5);
+    assert_eq!(custom.penalty_for(&temporal, &DataType::Numeric), Some(0.5));
+}
@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// See top-level LICENSE for details.
+
+#![cfg(feature = "wasm")]
+
+mod common;
+
+use common::profile;
+use estel::api_graph::EMBEDDED_API_YAML as API_YAML;
+use estel::chart_matcher::find_qualified_charts;
+use estel::wasm::{suggest_charts_json, suggest_with_symbolic, ScoredSuggestion};
+use estel::{ApiGraph, DataType, MatchingConfig};
+
+fn profiles() -> Vec<estel::DimensionProfile> {
+    vec![
+        profile("region", DataType::Categorical, Some(4)),
+        profile("revenue", DataType::Numeric, None),
+        profile("cost", DataType::Numeric, None),
+    ]
+}
+
+#[test]
+fn matching_runs_on_precomputed_profiles() {
+    let graph = ApiGraph::default_embedded().expect("parse embedded api");
+    let config = MatchingConfig {
+        max_suggestions_per_chart: 100,
+        ..MatchingConfig::default()
+    };
+    let specs = find_qualified_charts(&profiles(), &graph, &config);
+    assert!(!specs.is_empty());
+    assert!(specs.iter().any(|s| s.chart_name == "scatter"));
+}
+
+#[test]
+fn symbolic_engine_scores_every_suggestion() {
+    let graph = ApiGraph::default_embedded().unwrap();
+    let profiles = profiles();
+    let plain = find_qualified_charts(&profiles, &graph, &MatchingConfig::default());
+    let scored = suggest_with_symbolic(&profiles, &graph, &MatchingConfig::default());
+    assert_eq!(plain.len(), scored.len());
+    assert!(scored.iter().all(|s| s.symbolic_score.is_finite()));
+}
+
+#[test]
+fn json_entry_point_round_trips() {
+    let profiles_json = serde_json::to_string(&profiles()).unwrap();
+    let out = suggest_charts_json(API_YAML, &profiles_json).expect("suggest from json");
+    let parsed: Vec<ScoredSuggestion> = serde_json::from_str(&out).unwrap();
+    assert!(!parsed.is_empty());
+}
@@ -0,0 +1,204 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::config::{CircuitBreakerConfig, CircuitBreakerState, ProviderConfig};
+use crate::types::{LLMError, LLMResult};
+
+#[derive(Debug)]
+struct BreakerInner {
+    state: CircuitBreakerState,
+    failures: VecDeque<Instant>,
+    opened_at: Option<Instant>,
+    probe_successes: u32,
+}
+
+impl BreakerInner {
+    fn trip(&mut self, now: Instant) {
+        self.state = CircuitBreakerState::Open;
+        self.opened_at = Some(now);
+        self.failures.clear();
+        self.probe_successes = 0;
+    }
+}
+
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    name: String,
+    config: CircuitBreakerConfig,
+    inner: Mutex<BreakerInner>,
+}
+
+impl CircuitBreaker {
+    pub fn new(name: impl Into<String>, config: CircuitBreakerConfig) -> Self {
+        Self {
+            name: name.into(),
+            config,
+            inner: Mutex::new(BreakerInner {
+                state: CircuitBreakerState::Closed,
+                failures: VecDeque::new(),
+                opened_at: None,
+                probe_successes: 0,
+            }),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn config(&self) -> &CircuitBreakerConfig {
+        &self.config
+    }
+
+    pub fn state(&self) -> CircuitBreakerState {
+        self.refreshed().state.clone()
+    }
+
+    pub fn try_acquire(&self) -> LLMResult<()> {
+        match self.refreshed().state {
+            CircuitBreakerState::Open => Err(LLMError::CircuitOpen(self.name.clone())),
+            CircuitBreakerState::Closed | CircuitBreakerState::HalfOpen => Ok(()),
+        }
+    }
+
+    pub fn record_success(&self) {
+        let mut inner = self.refreshed();
+        if inner.state == CircuitBreakerState::HalfOpen {
+            inner.probe_successes += 1;
+            if inner.probe_successes >= self.config.success_threshold.max(1) {
+                inner.state = CircuitBreakerState::Closed;
+                inner.opened_at = None;
+                inner.probe_successes = 0;
+            }
+        }
+    }
+
+    pub fn record_failure(&self) {
+        let now = Instant::now();
+        let mut inner = self.refreshed();
+        match inner.state {
+            CircuitBreakerState::HalfOpen => inner.trip(now),
+            CircuitBreakerState::Closed => {
+                let window = Duration::from_millis(self.config.failure_window_ms);
+                while inner
+                    .failures
+                    .front()
+                    .is_some_and(|&failed_at| now.duration_since(failed_at) > window)
+                {
+                    inner.failures.pop_front();
+                }
+                inner.failures.push_back(now);
+                if inner.failures.len() >= self.config.failure_threshold.max(1) as usize {
+                    inner.trip(now);
+                }
+            }
+            CircuitBreakerState::Open => {}
+        }
+    }
+
+    pub fn record<T>(&self, outcome: &LLMResult<T>) {
+        match outcome {
+            Ok(_) => self.record_success(),
+            Err(LLMError::CircuitOpen(_)) => {}
+            Err(_) => self.record_failure(),
+        }
+    }
+
+    pub async fn call<T, F>(&self, request: F) -> LLMResult<T>
+    where
+        F: Future<Output = LLMResult<T>>,
+    {
+        self.try_acquire()?;
+        let outcome = request.await;
+        self.record(&outcome);
+        outcome
+    }
+
+    pub fn reset(&self) {
+        let mut inner = self.lock();
+        inner.state = CircuitBreakerState::Closed;
+        inner.failures.clear();
+        inner.opened_at = None;
+        inner.probe_successes = 0;
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BreakerInner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn refreshed(&self) -> MutexGuard<'_, BreakerInner> {
+        let mut inner = self.lock();
+        let cool_down = Duration::from_millis(self.config.cool_down_ms);
+        if inner.state == CircuitBreakerState::Open
+            && inner.opened_at.is_none_or(|opened_at| opened_at.elapsed() >= cool_down)
+        {
+            inner.state = CircuitBreakerState::HalfOpen;
+            inner.probe_successes = 0;
+        }
+        inner
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CircuitBreakers {
+    breakers: HashMap<String, Arc<CircuitBreaker>>,
+}
+
+impl CircuitBreakers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_providers(providers: &HashMap<String, ProviderConfig>) -> Self {
+        let breakers = providers
+            .iter()
+            .filter_map(|(name, provider)| {
+                let config = provider.circuit_breaker.clone()?;
+                Some((name.clone(), Arc::new(CircuitBreaker::new(name.clone(), config))))
+            })
+            .collect();
+        Self { breakers }
+    }
+
+    pub fn insert(&mut self, provider: impl Into<String>, config: CircuitBreakerConfig) {
+        let provider = provider.into();
+        let breaker = Arc::new(CircuitBreaker::new(provider.clone(), config));
+        self.breakers.insert(provider, breaker);
+    }
+
+    pub fn get(&self, provider: &str) -> Option<Arc<CircuitBreaker>> {
+        self.breakers.get(provider).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.breakers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.breakers.is_empty()
+    }
+
+    pub async fn call<T, F>(&self, provider: &str, request: F) -> LLMResult<T>
+    where
+        F: Future<Output = LLMResult<T>>,
+    {
+        match self.breakers.get(provider) {
+            Some(breaker) => breaker.call(request).await,
+            None => request.await,
+        }
+    }
+}
@@ -146,6 +146,8 @@ pub struct ProviderConfig {
     pub max_retries: Option<u32>,
     pub authentication: AuthenticationConfig,
     pub rate_limits: Option<RateLimits>,
+    #[serde(default)]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     #[serde(flatten)]
     pub provider_specific: HashMap<String, serde_json::Value>,
 }
@@ -164,6 +166,30 @@ pub struct RateLimits {
     pub requests_per_minute: Option<u32>,
     pub requests_per_hour: Option<u32>,
     pub concurrent_requests: Option<u32>,
+    pub tokens_per_minute: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CircuitBreakerConfig {
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    #[serde(default = "default_failure_window_ms")]
+    pub failure_window_ms: u64,
+    #[serde(default = "default_cool_down_ms")]
+    pub cool_down_ms: u64,
+    #[serde(default = "default_success_threshold")]
+    pub success_threshold: u32,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: default_failure_threshold(),
+            failure_window_ms: default_failure_window_ms(),
+            cool_down_ms: default_cool_down_ms(),
+            success_threshold: default_success_threshold(),
+        }
+    }
 }
 
 fn default_fallback_on_failure() -> bool {
@@ -178,6 +204,18 @@ fn default_timeout_seconds() -> u64 {
 fn default_learning_rate() -> f64 {
     0.1
 }
+fn default_failure_threshold() -> u32 {
+    5
+}
+fn default_failure_window_ms() -> u64 {
+    60_000
+}
+fn default_cool_down_ms() -> u64 {
+    30_000
+}
+fn default_success_threshold() -> u32 {
+    2
+}
 
 impl SelectionStrategy {
     pub fn is_v1_mode(&self) -> bool {
@@ -10,16 +10,22 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
+pub mod circuit_breaker;
 pub mod config;
+pub mod rate_limiter;
 pub mod requests;
 pub mod responses;
+pub mod selection;
 pub mod types;
 
+pub use circuit_breaker::{CircuitBreaker, CircuitBreakers};
 pub use config::{
-    AuthenticationConfig, CircuitBreakerState, CostPerMillionTokens, CostTier as V1CostTier,
-    FeedbackConfig, IntentWeights, ModelConfig, ModelDefinition, ProviderConfig, RateLimits,
-    SelectionStrategy, SpeedTier as V1SpeedTier,
+    AuthenticationConfig, CircuitBreakerConfig, CircuitBreakerState, CostPerMillionTokens,
+    CostTier as V1CostTier, FeedbackConfig, IntentWeights, ModelConfig, ModelDefinition,
+    ProviderConfig, RateLimits, SelectionStrategy, SpeedTier as V1SpeedTier,
 };
+pub use rate_limiter::{Permit, RateLimiter};
 pub use requests::*;
 pub use responses::*;
+pub use selection::{ModelSelector, SelectionPlan};
 pub use types::{Capability, CostTier, LLMError, LLMResult, Provider, SpeedTier};
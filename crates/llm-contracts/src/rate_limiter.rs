@@ -0,0 +1,184 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::config::RateLimits;
+use crate::types::{LLMError, LLMResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Demand {
+    Requests,
+    Tokens,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    name: &'static str,
+    demand: Demand,
+    capacity: f64,
+    available: f64,
+    refill_per_second: f64,
+}
+
+impl TokenBucket {
+    fn new(name: &'static str, demand: Demand, capacity: u32, period: Duration) -> Self {
+        let capacity = f64::from(capacity);
+        Self {
+            name,
+            demand,
+            capacity,
+            available: capacity,
+            refill_per_second: capacity / period.as_secs_f64(),
+        }
+    }
+
+    fn amount(&self, estimated_tokens: u32) -> f64 {
+        match self.demand {
+            Demand::Requests => 1.0,
+            Demand::Tokens => f64::from(estimated_tokens),
+        }
+    }
+
+    fn refill(&mut self, elapsed: Duration) {
+        let refilled = self.available + elapsed.as_secs_f64() * self.refill_per_second;
+        self.available = refilled.min(self.capacity);
+    }
+
+    fn wait_for(&self, amount: f64) -> Duration {
+        if self.available >= amount {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((amount - self.available) / self.refill_per_second)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    buckets: Vec<TokenBucket>,
+    last_refill: Instant,
+}
+
+impl LimiterState {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.last_refill);
+        for bucket in &mut self.buckets {
+            bucket.refill(elapsed);
+        }
+        self.last_refill = now;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Permit {
+    estimated_tokens: u32,
+}
+
+impl Permit {
+    pub fn estimated_tokens(&self) -> u32 {
+        self.estimated_tokens
+    }
+}
+
+#[derive(Debug)]
+pub struct RateLimiter {
+    state: Mutex<LimiterState>,
+}
+
+impl RateLimiter {
+    pub fn new(limits: &RateLimits) -> Self {
+        const MINUTE: Duration = Duration::from_secs(60);
+        const HOUR: Duration = Duration::from_secs(3600);
+
+        let buckets = [
+            ("requests_per_minute", Demand::Requests, limits.requests_per_minute, MINUTE),
+            ("requests_per_hour", Demand::Requests, limits.requests_per_hour, HOUR),
+            ("tokens_per_minute", Demand::Tokens, limits.tokens_per_minute, MINUTE),
+        ]
+        .into_iter()
+        .filter_map(|(name, demand, limit, period)| {
+            limit.map(|limit| TokenBucket::new(name, demand, limit, period))
+        })
+        .collect();
+
+        Self {
+            state: Mutex::new(LimiterState {
+                buckets,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    pub fn try_acquire(&self, estimated_tokens: u32) -> LLMResult<Permit> {
+        let mut state = self.lock();
+        state.refill(Instant::now());
+
+        let mut retry_after = Duration::ZERO;
+        for bucket in &state.buckets {
+            let amount = bucket.amount(estimated_tokens);
+            if amount > bucket.capacity {
+                return Err(LLMError::Validation(format!(
+                    "Request needs {amount} against {} but the limit is {}",
+                    bucket.name, bucket.capacity
+                )));
+            }
+            retry_after = retry_after.max(bucket.wait_for(amount));
+        }
+        if !retry_after.is_zero() {
+            return Err(LLMError::RateLimited {
+                retry_after: Some(retry_after),
+            });
+        }
+
+        for bucket in &mut state.buckets {
+            bucket.available -= bucket.amount(estimated_tokens);
+        }
+        Ok(Permit { estimated_tokens })
+    }
+
+    pub async fn acquire(&self, estimated_tokens: u32) -> LLMResult<Permit> {
+        loop {
+            match self.try_acquire(estimated_tokens) {
+                Err(LLMError::RateLimited {
+                    retry_after: Some(retry_after),
+                }) => {
+                    tokio::time::sleep(retry_after).await;
+                }
+                outcome => return outcome,
+            }
+        }
+    }
+
+    pub fn reconcile(&self, permit: Permit, actual_tokens: u32) {
+        let mut state = self.lock();
+        state.refill(Instant::now());
+
+        let correction = f64::from(permit.estimated_tokens) - f64::from(actual_tokens);
+        for bucket in &mut state.buckets {
+            if bucket.demand == Demand::Tokens {
+                bucket.available = (bucket.available + correction).min(bucket.capacity);
+            }
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LimiterState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl From<&RateLimits> for RateLimiter {
+    fn from(limits: &RateLimits) -> Self {
+        Self::new(limits)
+    }
+}
@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use crate::config::{CostTier, IntentWeights, ModelDefinition, SelectionStrategy, SpeedTier};
+use crate::requests::LLMRequest;
+use crate::types::{Capability, LLMError};
+
+#[derive(Debug, Clone)]
+pub struct SelectionPlan {
+    pub primary: ModelDefinition,
+    pub fallbacks: Vec<ModelDefinition>,
+}
+
+impl SelectionPlan {
+    pub fn chain(&self) -> impl Iterator<Item = &ModelDefinition> {
+        std::iter::once(&self.primary).chain(&self.fallbacks)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ModelSelector {
+    strategy: SelectionStrategy,
+}
+
+impl ModelSelector {
+    pub fn new(strategy: SelectionStrategy) -> Self {
+        Self { strategy }
+    }
+
+    pub fn strategy(&self) -> &SelectionStrategy {
+        &self.strategy
+    }
+
+    pub fn select(
+        &self,
+        request: &LLMRequest,
+        models: &[ModelDefinition],
+    ) -> Result<SelectionPlan, LLMError> {
+        let requirements = &request.model_requirements;
+        let required: HashSet<Capability> = requirements
+            .capabilities
+            .iter()
+            .cloned()
+            .map(Capability::from)
+            .collect();
+        let cost_ceiling = requirements
+            .max_cost_tier
+            .clone()
+            .map(|tier| tier_cost_ceiling(&CostTier::from(tier)));
+        let speed_floor = requirements
+            .preferred_speed_tier
+            .clone()
+            .map(|tier| tier_speed_floor(&SpeedTier::from(tier)));
+
+        let mut qualifying: Vec<&ModelDefinition> = models
+            .iter()
+            .filter(|model| {
+                let offered: HashSet<Capability> = model
+                    .capabilities
+                    .iter()
+                    .cloned()
+                    .map(Capability::from)
+                    .collect();
+                required.is_subset(&offered)
+            })
+            .filter(|model| {
+                requirements
+                    .min_max_tokens
+                    .is_none_or(|min| model.max_tokens >= min)
+            })
+            .filter(|model| cost_ceiling.is_none_or(|ceiling| model.get_cost_score() <= ceiling))
+            .filter(|model| speed_floor.is_none_or(|floor| model.get_speed_score() >= floor))
+            .collect();
+
+        let weights = self
+            .strategy
+            .intent
+            .as_ref()
+            .and_then(|intent| self.strategy.weights.get(intent));
+        qualifying.sort_by(|a, b| {
+            a.get_cost_score()
+                .total_cmp(&b.get_cost_score())
+                .then_with(|| tie_break(weights, a, b))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        let fallback_limit = if self.strategy.fallback_on_failure {
+            self.strategy.max_retries as usize
+        } else {
+            0
+        };
+        let mut chain = qualifying.into_iter().cloned();
+        let Some(primary) = chain.next() else {
+            let mut capabilities = requirements.capabilities.clone();
+            capabilities.sort();
+            return Err(LLMError::ModelNotFound(format!(
+                "no model satisfies capabilities [{}] within the requested cost and speed limits",
+                capabilities.join(", ")
+            )));
+        };
+        Ok(SelectionPlan {
+            primary,
+            fallbacks: chain.take(fallback_limit).collect(),
+        })
+    }
+}
+
+fn weighted_score(weights: &IntentWeights, model: &ModelDefinition) -> f64 {
+    weights.quality * model.get_quality_score()
+        + weights.speed * model.get_speed_score()
+        + weights.cost * (1.0 - model.get_cost_score())
+}
+
+fn tie_break(
+    weights: Option<&IntentWeights>,
+    a: &ModelDefinition,
+    b: &ModelDefinition,
+) -> Ordering {
+    match weights {
+        Some(weights) => weighted_score(weights, b).total_cmp(&weighted_score(weights, a)),
+        None => b.get_quality_score().total_cmp(&a.get_quality_score()),
+    }
+}
+
+fn tier_cost_ceiling(tier: &CostTier) -> f64 {
+    match tier {
+        CostTier::Free => 0.0,
+        CostTier::Low => 0.2,
+        CostTier::Medium => 0.5,
+        CostTier::High => 1.0,
+    }
+}
+
+fn tier_speed_floor(tier: &SpeedTier) -> f64 {
+    match tier {
+        SpeedTier::Fast => 0.9,
+        SpeedTier::Medium => 0.6,
+        SpeedTier::Slow => 0.0,
+    }
+}
@@ -11,6 +11,7 @@
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -68,8 +69,8 @@ pub enum LLMError {
     #[error("Authentication error: {0}")]
     Authentication(String),
 
-    #[error("Rate limit exceeded")]
-    RateLimit,
+    #[error("Rate limited, retry after {retry_after:?}")]
+    RateLimited { retry_after: Option<Duration> },
 
     #[error("Network error: {0}")]
     Network(String),
@@ -83,6 +84,9 @@ pub enum LLMError {
     #[error("Timeout error")]
     Timeout,
 
+    #[error("Circuit open for provider: {0}")]
+    CircuitOpen(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use std::collections::HashMap;
+use std::thread::sleep;
+use std::time::Duration;
+
+use llm_contracts::{
+    CircuitBreaker, CircuitBreakerConfig, CircuitBreakerState, CircuitBreakers, LLMError,
+    LLMResult, ProviderConfig,
+};
+
+fn fast_config() -> CircuitBreakerConfig {
+    CircuitBreakerConfig {
+        failure_threshold: 3,
+        failure_window_ms: 60_000,
+        cool_down_ms: 50,
+        success_threshold: 2,
+    }
+}
+
+#[test]
+fn breaker_cycles_closed_open_half_open_closed() {
+    let breaker = CircuitBreaker::new("openai", fast_config());
+    assert_eq!(breaker.state(), CircuitBreakerState::Closed);
+
+    breaker.record_failure();
+    breaker.record_failure();
+    assert_eq!(breaker.state(), CircuitBreakerState::Closed);
+    assert!(breaker.try_acquire().is_ok());
+
+    breaker.record(&Err::<(), _>(LLMError::Timeout));
+    assert_eq!(breaker.state(), CircuitBreakerState::Open);
+    match breaker.try_acquire() {
+        Err(LLMError::CircuitOpen(provider)) => assert_eq!(provider, "openai"),
+        other => panic!("expected CircuitOpen, got {other:?}"),
+    }
+
+    sleep(Duration::from_millis(80));
+    assert_eq!(breaker.state(), CircuitBreakerState::HalfOpen);
+    assert!(breaker.try_acquire().is_ok());
+
+    breaker.record(&Ok::<_, LLMError>(()));
+    assert_eq!(breaker.state(), CircuitBreakerState::HalfOpen);
+    breaker.record_success();
+    assert_eq!(breaker.state(), CircuitBreakerState::Closed);
+    assert!(breaker.try_acquire().is_ok());
+}
+
+#[test]
+fn failed_probe_reopens_breaker() {
+    let breaker = CircuitBreaker::new("anthropic", fast_config());
+    for _ in 0..3 {
+        breaker.record_failure();
+    }
+    sleep(Duration::from_millis(80));
+    assert_eq!(breaker.state(), CircuitBreakerState::HalfOpen);
+
+    breaker.record_success();
+    breaker.record_failure();
+    assert_eq!(breaker.state(), CircuitBreakerState::Open);
+    assert!(matches!(breaker.try_acquire(), Err(LLMError::CircuitOpen(_))));
+}
+
+#[test]
+fn failures_outside_window_do_not_open() {
+    let breaker = CircuitBreaker::new(
+        "ollama",
+        CircuitBreakerConfig {
+            failure_window_ms: 20,
+            ..fast_config()
+        },
+    );
+    for _ in 0..5 {
+        breaker.record_failure();
+        sleep(Duration::from_millis(30));
+    }
+    assert_eq!(breaker.state(), CircuitBreakerState::Closed);
+}
+
+#[test]
+fn rejected_calls_are_not_counted_as_failures() {
+    let breaker = CircuitBreaker::new("openai", fast_config());
+    let rejected: LLMResult<()> = Err(LLMError::CircuitOpen("openai".to_string()));
+    for _ in 0..5 {
+        breaker.record(&rejected);
+    }
+    assert_eq!(breaker.state(), CircuitBreakerState::Closed);
+}
+
+#[test]
+fn breakers_are_selected_per_provider() {
+    let providers: HashMap<String, ProviderConfig> = serde_json::from_value(serde_json::json!({
+        "openai": {
+            "timeout_seconds": 30,
+            "max_retries": 2,
+            "authentication": { "type": "bearer" },
+            "rate_limits": null,
+            "circuit_breaker": { "failure_threshold": 1, "cool_down_ms": 10000 }
+        },
+        "ollama": {
+            "timeout_seconds": 30,
+            "max_retries": 2,
+            "authentication": { "type": "none" },
+            "rate_limits": null
+        }
+    }))
+    .unwrap();
+
+    let breakers = CircuitBreakers::from_providers(&providers);
+    assert_eq!(breakers.len(), 1);
+    assert!(breakers.get("ollama").is_none());
+
+    let openai = breakers.get("openai").expect("openai breaker");
+    assert_eq!(openai.config().failure_threshold, 1);
+    assert_eq!(openai.config().success_threshold, 2);
+    openai.record_failure();
+    assert!(openai.try_acquire().is_err());
+}
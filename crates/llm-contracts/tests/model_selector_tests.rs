@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use llm_contracts::{
+    GenerationConfig, LLMError, LLMRequest, ModelDefinition, ModelRequirements, ModelSelector,
+    SelectionStrategy,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+fn model(name: &str, capabilities: &[&str], cost: &str, speed: &str) -> ModelDefinition {
+    ModelDefinition {
+        name: name.to_string(),
+        provider: "test".to_string(),
+        capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+        max_tokens: 4096,
+        speed_tier: Some(speed.to_string()),
+        cost_tier: Some(cost.to_string()),
+        parallel_limit: None,
+        temperature: None,
+        quality_score: None,
+        avg_response_ms: None,
+        avg_tokens_per_second: None,
+        cost_per_million_tokens: None,
+    }
+}
+
+fn request(capabilities: &[&str], max_cost: Option<&str>, speed: Option<&str>) -> LLMRequest {
+    LLMRequest {
+        id: Uuid::new_v4(),
+        prompt: "Summarise the incident report".to_string(),
+        system_prompt: None,
+        model_requirements: ModelRequirements {
+            capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+            preferred_speed_tier: speed.map(str::to_string),
+            max_cost_tier: max_cost.map(str::to_string),
+            min_max_tokens: None,
+        },
+        generation_config: GenerationConfig::default(),
+        context: None,
+    }
+}
+
+fn selector(strategy: serde_json::Value) -> ModelSelector {
+    ModelSelector::new(serde_json::from_value::<SelectionStrategy>(strategy).unwrap())
+}
+
+fn chain_names(plan: &llm_contracts::SelectionPlan) -> Vec<&str> {
+    plan.chain().map(|model| model.name.as_str()).collect()
+}
+
+#[test]
+fn models_lacking_a_required_capability_are_excluded() {
+    let models = vec![
+        model("classifier", &["classification"], "free", "fast"),
+        model("reasoner", &["classification", "reasoning"], "medium", "medium"),
+    ];
+
+    let plan = selector(json!({}))
+        .select(&request(&["reasoning"], None, None), &models)
+        .unwrap();
+
+    assert_eq!(chain_names(&plan), vec!["reasoner"]);
+}
+
+#[test]
+fn cheapest_model_meeting_speed_requirement_is_primary() {
+    let models = vec![
+        model("premium", &["reasoning"], "high", "fast"),
+        model("budget", &["reasoning"], "low", "slow"),
+        model("standard", &["reasoning"], "medium", "fast"),
+    ];
+    let selector = selector(json!({}));
+
+    let plan = selector
+        .select(&request(&["reasoning"], None, None), &models)
+        .unwrap();
+    assert_eq!(chain_names(&plan), vec!["budget", "standard", "premium"]);
+
+    let plan = selector
+        .select(&request(&["reasoning"], None, Some("fast")), &models)
+        .unwrap();
+    assert_eq!(plan.primary.name, "standard");
+    assert_eq!(chain_names(&plan), vec!["standard", "premium"]);
+
+    let plan = selector
+        .select(&request(&["reasoning"], Some("medium"), Some("fast")), &models)
+        .unwrap();
+    assert_eq!(chain_names(&plan), vec!["standard"]);
+}
+
+#[test]
+fn intent_weights_break_cost_ties() {
+    let models = vec![
+        model("steady", &["reasoning"], "low", "slow"),
+        model("nimble", &["reasoning"], "low", "fast"),
+    ];
+    let weights = json!({
+        "quality": { "quality": 1.0, "speed": 0.0, "cost": 0.0 },
+        "speed": { "quality": 0.0, "speed": 1.0, "cost": 0.0 }
+    });
+    let req = request(&["reasoning"], None, None);
+
+    let by_quality = selector(json!({ "intent": "quality", "weights": weights.clone() }));
+    let plan = by_quality.select(&req, &models).unwrap();
+    assert_eq!(chain_names(&plan), vec!["steady", "nimble"]);
+
+    let by_speed = selector(json!({ "intent": "speed", "weights": weights }));
+    let plan = by_speed.select(&req, &models).unwrap();
+    assert_eq!(chain_names(&plan), vec!["nimble", "steady"]);
+}
+
+#[test]
+fn fallback_chain_honours_retry_settings() {
+    let models = vec![
+        model("a", &["reasoning"], "low", "fast"),
+        model("b", &["reasoning"], "medium", "fast"),
+        model("c", &["reasoning"], "high", "fast"),
+    ];
+    let req = request(&["reasoning"], None, None);
+
+    let plan = selector(json!({ "max_retries": 1 }))
+        .select(&req, &models)
+        .unwrap();
+    assert_eq!(chain_names(&plan), vec!["a", "b"]);
+
+    let plan = selector(json!({ "fallback_on_failure": false }))
+        .select(&req, &models)
+        .unwrap();
+    assert!(plan.fallbacks.is_empty());
+}
+
+#[test]
+fn no_qualifying_model_is_an_error() {
+    let models = vec![model("classifier", &["classification"], "free", "fast")];
+
+    let result = selector(json!({}))
+        .select(&request(&["code_generation"], None, None), &models);
+
+    match result {
+        Err(LLMError::ModelNotFound(message)) => assert!(message.contains("code_generation")),
+        other => panic!("expected ModelNotFound, got {other:?}"),
+    }
+}
@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use std::time::{Duration, Instant};
+
+use llm_contracts::{LLMError, RateLimiter, RateLimits};
+
+fn limits(requests_per_minute: Option<u32>, tokens_per_minute: Option<u32>) -> RateLimits {
+    RateLimits {
+        requests_per_minute,
+        requests_per_hour: None,
+        concurrent_requests: None,
+        tokens_per_minute,
+    }
+}
+
+#[test]
+fn exhausted_request_budget_reports_retry_after() {
+    let limiter = RateLimiter::new(&limits(Some(2), None));
+
+    assert!(limiter.try_acquire(100).is_ok());
+    assert!(limiter.try_acquire(100).is_ok());
+
+    match limiter.try_acquire(100) {
+        Err(LLMError::RateLimited {
+            retry_after: Some(retry_after),
+        }) => {
+            assert!(retry_after > Duration::ZERO);
+            assert!(retry_after <= Duration::from_secs(30));
+        }
+        other => panic!("expected RateLimited, got {other:?}"),
+    }
+}
+
+#[test]
+fn token_budget_blocks_request_despite_request_capacity() {
+    let limiter = RateLimiter::new(&limits(Some(100), Some(1_000)));
+
+    let permit = limiter.try_acquire(800).unwrap();
+    assert_eq!(permit.estimated_tokens(), 800);
+
+    match limiter.try_acquire(400) {
+        Err(LLMError::RateLimited {
+            retry_after: Some(retry_after),
+        }) => {
+            assert!(retry_after >= Duration::from_secs(10));
+        }
+        other => panic!("expected RateLimited, got {other:?}"),
+    }
+    assert!(limiter.try_acquire(150).is_ok());
+}
+
+#[test]
+fn request_larger_than_token_budget_is_rejected() {
+    let limiter = RateLimiter::new(&limits(None, Some(1_000)));
+
+    assert!(matches!(limiter.try_acquire(1_500), Err(LLMError::Validation(_))));
+}
+
+#[test]
+fn reconcile_returns_unused_tokens() {
+    let limiter = RateLimiter::new(&limits(None, Some(1_000)));
+
+    let permit = limiter.try_acquire(900).unwrap();
+    assert!(limiter.try_acquire(500).is_err());
+
+    limiter.reconcile(permit, 300);
+    assert!(limiter.try_acquire(500).is_ok());
+}
+
+#[test]
+fn missing_limits_never_throttle() {
+    let limiter = RateLimiter::new(&limits(None, None));
+    for _ in 0..1_000 {
+        assert!(limiter.try_acquire(u32::MAX).is_ok());
+    }
+}
+
+#[tokio::test]
+async fn acquire_waits_for_refill() {
+    let limiter = RateLimiter::new(&limits(None, Some(6_000)));
+    limiter.try_acquire(6_000).unwrap();
+
+    let started = Instant::now();
+    let permit = limiter.acquire(10).await.unwrap();
+
+    assert_eq!(permit.estimated_tokens(), 10);
+    assert!(started.elapsed() >= Duration::from_millis(50));
+}
@@ -10,10 +10,19 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
-use crate::agents::schemas::{AgentCapabilities, PerformanceMetrics, TechnicalSkill};
+use crate::agents::schemas::{Agent, AgentCapabilities, AgentId, PerformanceMetrics, TechnicalSkill};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MatchMode {
+    #[default]
+    Exact,
+    Weighted {
+        min_score: f64,
+    },
+}
+
 #[derive(Debug, Clone)]
 pub struct CapabilityMatcherConfig {
     pub preferred_skill_weight_multiplier: f64,
@@ -21,6 +30,8 @@ pub struct CapabilityMatcherConfig {
     pub domain_mismatch_penalty: f64,
 
     pub performance_penalty_multiplier: f64,
+
+    pub match_mode: MatchMode,
 }
 
 impl Default for CapabilityMatcherConfig {
@@ -29,6 +40,7 @@ impl Default for CapabilityMatcherConfig {
             preferred_skill_weight_multiplier: 0.5,
             domain_mismatch_penalty: 0.5,
             performance_penalty_multiplier: 0.7,
+            match_mode: MatchMode::Exact,
         }
     }
 }
@@ -154,6 +166,56 @@ impl CapabilityMatcher {
         }
     }
 
+    pub fn rank_agents(
+        &self,
+        required: &AgentCapabilities,
+        agents: &[Agent],
+    ) -> Vec<(AgentId, f64)> {
+        let min_score = match self.config.match_mode {
+            MatchMode::Exact => 1.0,
+            MatchMode::Weighted { min_score } => min_score,
+        };
+        let mut ranked: Vec<(AgentId, f64)> = agents
+            .iter()
+            .map(|agent| {
+                let score = self.coverage_score(required, &agent.capabilities);
+                (agent.id.clone(), score)
+            })
+            .filter(|(_, score)| *score >= min_score)
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked
+    }
+
+    fn coverage_score(
+        &self,
+        required: &AgentCapabilities,
+        capabilities: &AgentCapabilities,
+    ) -> f64 {
+        if required.technical_skills.is_empty() {
+            return 1.0;
+        }
+        let total: f64 = required
+            .technical_skills
+            .iter()
+            .map(|wanted| {
+                capabilities
+                    .technical_skills
+                    .iter()
+                    .find(|skill| skill.name == wanted.name)
+                    .map(|skill| {
+                        if wanted.proficiency > 0.0 {
+                            (skill.proficiency / wanted.proficiency).min(1.0)
+                        } else {
+                            1.0
+                        }
+                    })
+                    .unwrap_or_default()
+            })
+            .sum();
+        total / required.technical_skills.len() as f64
+    }
+
     fn evaluate_required_skills(
         &self,
         skills: &[TechnicalSkill],
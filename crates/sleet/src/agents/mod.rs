@@ -15,13 +15,13 @@ pub mod generator;
 pub mod registry;
 pub mod schemas;
 pub use capabilities::{
-    CapabilityMatch, CapabilityMatcher, CapabilityMatcherConfig, SkillRequirement,
+    CapabilityMatch, CapabilityMatcher, CapabilityMatcherConfig, MatchMode, SkillRequirement,
 };
 pub use generator::{AgentGenerator, AgentTeamResponse, FallbackAgentConfig, GenerationConfig};
 pub use registry::{AgentRegistry, RegistryError};
 pub use schemas::{
-    Agent, AgentCapabilities, AgentConfig, AgentMetadata, AgentStatus, RuntimeCapabilities,
-    TrustLevel,
+    Agent, AgentCapabilities, AgentConfig, AgentId, AgentMetadata, AgentStatus,
+    RuntimeCapabilities, TrustLevel,
 };
 use serde::{Deserialize, Serialize};
 use stele::nlu::llm_processor::LLMAdapter;
@@ -47,6 +47,8 @@ fn runtime_to_json_value(runtime_value: &RuntimeValue) -> serde_json::Value {
     }
 }
 
+pub type AgentId = String;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Agent {
     pub id: String,
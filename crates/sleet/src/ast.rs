@@ -170,6 +170,10 @@ pub enum Op {
         catch_block_id: String,
     },
     PopErrorHandler,
+    Parallel {
+        branches: Vec<String>,
+        join_block: String,
+    },
 
     Add(Box<AstNode>, Box<AstNode>),
     Subtract(Box<AstNode>, Box<AstNode>),
@@ -205,6 +209,65 @@ pub enum Op {
         then_expr: Box<AstNode>,
         else_expr: Box<AstNode>,
     },
+
+    Map {
+        source: Path,
+        item_var: String,
+        body: Box<AstNode>,
+    },
+    Filter {
+        source: Path,
+        item_var: String,
+        predicate: Box<AstNode>,
+    },
+    Reduce {
+        source: Path,
+        item_var: String,
+        accumulator_var: String,
+        initial: Box<AstNode>,
+        body: Box<AstNode>,
+    },
+}
+
+impl Op {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Op::Literal(_) => "Literal",
+            Op::Sequence(_) => "Sequence",
+            Op::If { .. } => "If",
+            Op::Fetch(_) => "Fetch",
+            Op::Assign { .. } => "Assign",
+            Op::SetNextBlock(_) => "SetNextBlock",
+            Op::Terminate => "Terminate",
+            Op::Await { .. } => "Await",
+            Op::Evaluate { .. } => "Evaluate",
+            Op::PushErrorHandler { .. } => "PushErrorHandler",
+            Op::PopErrorHandler => "PopErrorHandler",
+            Op::Parallel { .. } => "Parallel",
+            Op::Add(..) => "Add",
+            Op::Subtract(..) => "Subtract",
+            Op::Multiply(..) => "Multiply",
+            Op::Divide(..) => "Divide",
+            Op::Modulo(..) => "Modulo",
+            Op::Negate(_) => "Negate",
+            Op::Equal(..) => "Equal",
+            Op::NotEqual(..) => "NotEqual",
+            Op::LessThan(..) => "LessThan",
+            Op::GreaterThan(..) => "GreaterThan",
+            Op::LessEqual(..) => "LessEqual",
+            Op::GreaterEqual(..) => "GreaterEqual",
+            Op::And(..) => "And",
+            Op::Or(..) => "Or",
+            Op::Not(_) => "Not",
+            Op::Length(_) => "Length",
+            Op::Index { .. } => "Index",
+            Op::Call { .. } => "Call",
+            Op::Conditional { .. } => "Conditional",
+            Op::Map { .. } => "Map",
+            Op::Filter { .. } => "Filter",
+            Op::Reduce { .. } => "Reduce",
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -214,6 +277,255 @@ pub struct AstNode {
     pub source_location: Option<SourceLocation>,
 }
 
+pub const DYNAMIC_TARGET_META: &str = "dynamic_target";
+
+impl AstNode {
+    pub fn children(&self) -> Vec<&AstNode> {
+        fn path_nodes<'a>(path: &'a Path, out: &mut Vec<&'a AstNode>) {
+            for segment in &path.0 {
+                if let PathSegment::DynamicOffset(node) = segment {
+                    out.push(node.as_ref());
+                }
+            }
+        }
+        let mut out = Vec::new();
+        match &self.op {
+            Op::Literal(Literal::Array(nodes)) | Op::Sequence(nodes) => out.extend(nodes.iter()),
+            Op::Literal(Literal::Object(entries)) => {
+                let mut keys: Vec<&String> = entries.keys().collect();
+                keys.sort();
+                out.extend(keys.into_iter().map(|k| &entries[k]));
+            }
+            Op::Literal(_)
+            | Op::SetNextBlock(_)
+            | Op::Terminate
+            | Op::PushErrorHandler { .. }
+            | Op::PopErrorHandler
+            | Op::Parallel { .. } => {}
+            Op::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                out.push(condition.as_ref());
+                out.push(then_branch.as_ref());
+                if let Some(else_branch) = else_branch {
+                    out.push(else_branch.as_ref());
+                }
+            }
+            Op::Fetch(path) => path_nodes(path, &mut out),
+            Op::Assign { path, value } => {
+                path_nodes(path, &mut out);
+                out.push(value.as_ref());
+            }
+            Op::Await { prompt, .. } => out.extend(prompt.as_deref()),
+            Op::Evaluate { output_path, .. } => path_nodes(output_path, &mut out),
+            Op::Add(l, r)
+            | Op::Subtract(l, r)
+            | Op::Multiply(l, r)
+            | Op::Divide(l, r)
+            | Op::Modulo(l, r)
+            | Op::Equal(l, r)
+            | Op::NotEqual(l, r)
+            | Op::LessThan(l, r)
+            | Op::GreaterThan(l, r)
+            | Op::LessEqual(l, r)
+            | Op::GreaterEqual(l, r)
+            | Op::And(l, r)
+            | Op::Or(l, r) => {
+                out.push(l.as_ref());
+                out.push(r.as_ref());
+            }
+            Op::Negate(inner) | Op::Not(inner) | Op::Length(inner) => out.push(inner.as_ref()),
+            Op::Index { object, index } => {
+                out.push(object.as_ref());
+                out.push(index.as_ref());
+            }
+            Op::Call { callee, args } => {
+                out.push(callee.as_ref());
+                out.extend(args.iter());
+            }
+            Op::Conditional {
+                condition,
+                then_expr,
+                else_expr,
+            } => {
+                out.push(condition.as_ref());
+                out.push(then_expr.as_ref());
+                out.push(else_expr.as_ref());
+            }
+            Op::Map { source, body, .. } => {
+                path_nodes(source, &mut out);
+                out.push(body.as_ref());
+            }
+            Op::Filter {
+                source, predicate, ..
+            } => {
+                path_nodes(source, &mut out);
+                out.push(predicate.as_ref());
+            }
+            Op::Reduce {
+                source,
+                initial,
+                body,
+                ..
+            } => {
+                path_nodes(source, &mut out);
+                out.push(initial.as_ref());
+                out.push(body.as_ref());
+            }
+        }
+        out
+    }
+
+    pub fn walk<F: FnMut(&AstNode)>(&self, f: &mut F) {
+        f(self);
+        for child in self.children() {
+            child.walk(f);
+        }
+    }
+
+    pub fn is_dynamic_target(&self) -> bool {
+        self.metadata
+            .get(DYNAMIC_TARGET_META)
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    }
+
+    pub fn to_pretty(&self) -> String {
+        let mut out = String::new();
+        self.write_pretty(&mut out, 0, None);
+        out
+    }
+
+    fn write_pretty(&self, out: &mut String, depth: usize, label: Option<&str>) {
+        let mut children: Vec<(Option<String>, &AstNode)> = Vec::new();
+        let head = match &self.op {
+            Op::Literal(Literal::Array(nodes)) => {
+                children.extend(nodes.iter().map(|n| (None, n)));
+                "Literal Array".to_string()
+            }
+            Op::Literal(Literal::Object(entries)) => {
+                let mut keys: Vec<&String> = entries.keys().collect();
+                keys.sort();
+                children.extend(keys.into_iter().map(|k| (Some(k.clone()), &entries[k])));
+                "Literal Object".to_string()
+            }
+            Op::Literal(literal) => format!("Literal {}", Value::from(literal.clone())),
+            Op::Sequence(nodes) => {
+                children.extend(nodes.iter().map(|n| (None, n)));
+                "Sequence".to_string()
+            }
+            Op::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                children.push((Some("condition".to_string()), condition));
+                children.push((Some("then".to_string()), then_branch));
+                if let Some(else_branch) = else_branch {
+                    children.push((Some("else".to_string()), else_branch));
+                }
+                "If".to_string()
+            }
+            Op::Fetch(path) => format!("Fetch {path}"),
+            Op::Assign { path, value } => {
+                children.push((Some("value".to_string()), value));
+                format!("Assign {path}")
+            }
+            Op::SetNextBlock(block_id) => format!("SetNextBlock {block_id:?}"),
+            Op::Await {
+                interaction_id,
+                agent_id,
+                prompt,
+                timeout_ms,
+            } => {
+                if let Some(prompt) = prompt {
+                    children.push((Some("prompt".to_string()), prompt));
+                }
+                match timeout_ms {
+                    Some(ms) => {
+                        format!("Await {interaction_id:?} agent={agent_id:?} timeout={ms}ms")
+                    }
+                    None => format!("Await {interaction_id:?} agent={agent_id:?}"),
+                }
+            }
+            Op::Evaluate {
+                bytecode,
+                output_path,
+            } => format!("Evaluate -> {output_path} ({} bytes)", bytecode.len()),
+            Op::PushErrorHandler { catch_block_id } => {
+                format!("PushErrorHandler catch={catch_block_id:?}")
+            }
+            Op::Parallel {
+                branches,
+                join_block,
+            } => format!("Parallel {branches:?} join={join_block:?}"),
+            Op::Index { object, index } => {
+                children.push((Some("object".to_string()), object));
+                children.push((Some("index".to_string()), index));
+                "Index".to_string()
+            }
+            Op::Call { callee, args } => {
+                children.push((Some("callee".to_string()), callee));
+                children.extend(args.iter().map(|a| (Some("arg".to_string()), a)));
+                "Call".to_string()
+            }
+            Op::Conditional {
+                condition,
+                then_expr,
+                else_expr,
+            } => {
+                children.push((Some("condition".to_string()), condition));
+                children.push((Some("then".to_string()), then_expr));
+                children.push((Some("else".to_string()), else_expr));
+                "Conditional".to_string()
+            }
+            Op::Map {
+                source,
+                item_var,
+                body,
+            } => {
+                children.push((Some("body".to_string()), body));
+                format!("Map {source} as {item_var}")
+            }
+            Op::Filter {
+                source,
+                item_var,
+                predicate,
+            } => {
+                children.push((Some("predicate".to_string()), predicate));
+                format!("Filter {source} as {item_var}")
+            }
+            Op::Reduce {
+                source,
+                item_var,
+                accumulator_var,
+                initial,
+                body,
+            } => {
+                children.push((Some("initial".to_string()), initial));
+                children.push((Some("body".to_string()), body));
+                format!("Reduce {source} as {item_var} into {accumulator_var}")
+            }
+            op => {
+                children.extend(self.children().into_iter().map(|n| (None, n)));
+                op.name().to_string()
+            }
+        };
+        out.push_str(&"  ".repeat(depth));
+        if let Some(label) = label {
+            out.push_str(label);
+            out.push_str(": ");
+        }
+        out.push_str(&head);
+        out.push('\n');
+        for (child_label, child) in children {
+            child.write_pretty(out, depth + 1, child_label.as_deref());
+        }
+    }
+}
+
 impl From<Op> for AstNode {
     fn from(op: Op) -> Self {
         AstNode {
@@ -234,6 +546,34 @@ pub struct Contract {
     pub participants: Vec<String>,
 }
 
+pub const CONTRACT_JSON_SCHEMA: &str = include_str!("../schemas/contract.schema.json");
+
+impl Contract {
+    pub fn content_hash(&self) -> Result<String, serde_json::Error> {
+        canonical_hash(self)
+    }
+
+    pub fn json_schema() -> Result<Value, serde_json::Error> {
+        serde_json::from_str(CONTRACT_JSON_SCHEMA)
+    }
+
+    pub fn to_pretty(&self) -> String {
+        let mut out = format!("Contract v{} start={:?}\n", self.version, self.start_block_id);
+        let mut ids: Vec<&String> = self.blocks.keys().collect();
+        ids.sort();
+        for id in ids {
+            out.push_str(&format!("block {id:?}\n"));
+            self.blocks[id].write_pretty(&mut out, 1, None);
+        }
+        out
+    }
+}
+
+pub fn canonical_hash<T: Serialize>(value: &T) -> Result<String, serde_json::Error> {
+    let canonical = serde_json::to_value(value).and_then(|v| serde_json::to_vec(&v))?;
+    Ok(format!("{:x}", md5::compute(canonical)))
+}
+
 pub mod legacy {
     use super::*;
 
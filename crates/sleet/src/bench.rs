@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use crate::ast::Contract;
+use crate::flows::definition::FlowDefinition;
+use crate::runtime::{
+    ExecutionStatus, FfiRegistry, GasSchedule, InterpreterError, RemarkableInterpreter,
+};
+use crate::transpiler::{FlowTranspiler, TranspilerError};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum BenchError {
+    #[error("Transpilation failed: {0}")]
+    Transpile(#[from] TranspilerError),
+    #[error("Contract conversion failed: {0}")]
+    Conversion(String),
+    #[error("Execution failed after {gas_used} gas: {source}")]
+    Execution {
+        gas_used: u64,
+        #[source]
+        source: InterpreterError,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BenchOutcome {
+    Completed,
+    AwaitingInput { interaction_id: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasReport {
+    pub total_gas: u64,
+    pub blocks_executed: u64,
+    pub node_counts: BTreeMap<String, u64>,
+    pub opcode_counts: BTreeMap<String, u64>,
+    pub outcome: BenchOutcome,
+}
+
+impl GasReport {
+    pub fn node_count(&self, op: &str) -> u64 {
+        self.node_counts.get(op).copied().unwrap_or(0)
+    }
+
+    pub fn opcode_count(&self, opcode: &str) -> u64 {
+        self.opcode_counts.get(opcode).copied().unwrap_or(0)
+    }
+
+    pub fn total_nodes(&self) -> u64 {
+        self.node_counts.values().sum()
+    }
+
+    pub fn total_opcodes(&self) -> u64 {
+        self.opcode_counts.values().sum()
+    }
+
+    pub fn gas_delta(&self, baseline: &GasReport) -> i64 {
+        self.total_gas as i64 - baseline.total_gas as i64
+    }
+
+    pub fn exceeds(&self, baseline: &GasReport, tolerance: u64) -> bool {
+        self.total_gas > baseline.total_gas.saturating_add(tolerance)
+    }
+
+    pub fn summary(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "total_gas {}", self.total_gas);
+        let _ = writeln!(out, "blocks {}", self.blocks_executed);
+        for (op, count) in &self.node_counts {
+            let _ = writeln!(out, "node.{op} {count}");
+        }
+        for (opcode, count) in &self.opcode_counts {
+            let _ = writeln!(out, "opcode.{opcode} {count}");
+        }
+        out
+    }
+}
+
+pub fn bench_flow(
+    flow_def: &FlowDefinition,
+    gas_limit: u64,
+    ffi_registry: FfiRegistry,
+) -> Result<GasReport, BenchError> {
+    bench_flow_with_schedule(flow_def, gas_limit, ffi_registry, GasSchedule::default())
+}
+
+pub fn bench_flow_with_schedule(
+    flow_def: &FlowDefinition,
+    gas_limit: u64,
+    ffi_registry: FfiRegistry,
+    gas_schedule: GasSchedule,
+) -> Result<GasReport, BenchError> {
+    let orchestration_contract = FlowTranspiler::transpile(flow_def)?;
+    let contract = crate::convert_contract(orchestration_contract)
+        .map_err(|e| BenchError::Conversion(e.to_string()))?;
+    bench_contract_with_schedule(contract, gas_limit, ffi_registry, gas_schedule)
+}
+
+pub fn bench_contract(
+    contract: Contract,
+    gas_limit: u64,
+    ffi_registry: FfiRegistry,
+) -> Result<GasReport, BenchError> {
+    bench_contract_with_schedule(contract, gas_limit, ffi_registry, GasSchedule::default())
+}
+
+pub fn bench_contract_with_schedule(
+    contract: Contract,
+    gas_limit: u64,
+    ffi_registry: FfiRegistry,
+    gas_schedule: GasSchedule,
+) -> Result<GasReport, BenchError> {
+    let mut runtime =
+        RemarkableInterpreter::with_gas_schedule(gas_limit, &contract, ffi_registry, gas_schedule)
+            .map_err(|source| BenchError::Execution {
+                gas_used: 0,
+                source,
+            })?;
+    let status = runtime.run_until_paused().map_err(|source| BenchError::Execution {
+        gas_used: runtime.gas_used(),
+        source,
+    })?;
+    let outcome = match status {
+        ExecutionStatus::AwaitingInput { interaction_id, .. } => {
+            BenchOutcome::AwaitingInput { interaction_id }
+        }
+        _ => BenchOutcome::Completed,
+    };
+    let metrics = runtime.metrics();
+    Ok(GasReport {
+        total_gas: runtime.gas_used(),
+        blocks_executed: metrics.blocks_executed,
+        node_counts: metrics.node_counts.clone(),
+        opcode_counts: metrics.opcode_counts.clone(),
+        outcome,
+    })
+}
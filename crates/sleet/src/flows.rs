@@ -13,7 +13,8 @@
 pub mod definition {
     use serde::{Deserialize, Serialize};
     use serde_json::Value;
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet, VecDeque};
+    use thiserror::Error;
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub enum BlockType {
         Conditional {
@@ -55,9 +56,89 @@ pub mod definition {
         },
         Break {
             loop_id: String,
+            #[serde(default)]
+            output: Option<BreakOutput>,
+        },
+        Generate {
+            prompt_expression: String,
+            #[serde(default)]
+            model: Option<String>,
+            output_key: String,
+            next_block: String,
+        },
+        Parallel {
+            branches: Vec<String>,
+            join_block: String,
         },
         Terminate,
     }
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct BreakOutput {
+        pub expression: String,
+        pub output_key: String,
+    }
+    #[derive(Debug, Clone, PartialEq, Eq, Error)]
+    pub enum FlowValidationError {
+        #[error("Block id '{0}' is defined more than once")]
+        DuplicateBlock(String),
+        #[error("Start block '{0}' does not exist")]
+        MissingStartBlock(String),
+        #[error("Block '{block_id}' references missing block '{target}' in '{field}'")]
+        DanglingReference {
+            block_id: String,
+            field: &'static str,
+            target: String,
+        },
+        #[error("Block '{block_id}' refers to unknown loop '{loop_id}'")]
+        UnknownLoop { block_id: String, loop_id: String },
+        #[error("Block '{0}' is unreachable from the start block")]
+        UnreachableBlock(String),
+        #[error("No Terminate block is reachable from the start block")]
+        NoReachableTerminate,
+        #[error("Block '{block_id}' awaits input inside a branch of Parallel block '{parallel_block}'")]
+        AwaitInParallelBranch {
+            parallel_block: String,
+            block_id: String,
+        },
+    }
+    impl BlockType {
+        pub fn references(&self) -> Vec<(&'static str, &str)> {
+            match self {
+                BlockType::Conditional {
+                    true_block,
+                    false_block,
+                    ..
+                } => vec![("true_block", true_block), ("false_block", false_block)],
+                BlockType::Compute { next_block, .. }
+                | BlockType::AwaitInput { next_block, .. }
+                | BlockType::SubFlow { next_block, .. }
+                | BlockType::Generate { next_block, .. } => vec![("next_block", next_block)],
+                BlockType::ForEach {
+                    loop_body_block_id,
+                    exit_block_id,
+                    ..
+                } => vec![
+                    ("loop_body_block_id", loop_body_block_id),
+                    ("exit_block_id", exit_block_id),
+                ],
+                BlockType::TryCatch {
+                    try_block_id,
+                    catch_block_id,
+                } => vec![("try_block_id", try_block_id), ("catch_block_id", catch_block_id)],
+                BlockType::Parallel {
+                    branches,
+                    join_block,
+                } => branches
+                    .iter()
+                    .map(|branch| ("branches", branch.as_str()))
+                    .chain(std::iter::once(("join_block", join_block.as_str())))
+                    .collect(),
+                BlockType::Continue { .. } | BlockType::Break { .. } | BlockType::Terminate => {
+                    vec![]
+                }
+            }
+        }
+    }
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct BlockDefinition {
         pub id: String,
@@ -71,8 +152,15 @@ pub mod definition {
             }
         }
     }
+    pub const CURRENT_FLOW_SCHEMA_VERSION: u32 = 2;
+    pub const MIN_FLOW_SCHEMA_VERSION: u32 = 1;
+    pub fn default_schema_version() -> u32 {
+        MIN_FLOW_SCHEMA_VERSION
+    }
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct FlowDefinition {
+        #[serde(default = "default_schema_version")]
+        pub schema_version: u32,
         pub id: String,
         pub start_block_id: String,
         pub blocks: Vec<BlockDefinition>,
@@ -84,6 +172,7 @@ pub mod definition {
     impl FlowDefinition {
         pub fn new(id: impl Into<String>, start_block_id: impl Into<String>) -> Self {
             Self {
+                schema_version: CURRENT_FLOW_SCHEMA_VERSION,
                 id: id.into(),
                 start_block_id: start_block_id.into(),
                 blocks: Vec::new(),
@@ -112,6 +201,115 @@ pub mod definition {
         pub fn get_block(&self, id: &str) -> Option<&BlockDefinition> {
             self.blocks.iter().find(|b| b.id == id)
         }
+        pub fn validate(&self) -> Result<(), Vec<FlowValidationError>> {
+            let mut errors = Vec::new();
+            let mut blocks: HashMap<&str, &BlockDefinition> = HashMap::new();
+            for block in &self.blocks {
+                if blocks.insert(block.id.as_str(), block).is_some() {
+                    errors.push(FlowValidationError::DuplicateBlock(block.id.clone()));
+                }
+            }
+            let mut loops: HashMap<&str, (&str, &str)> = HashMap::new();
+            for block in &self.blocks {
+                if let BlockType::ForEach {
+                    loop_id,
+                    exit_block_id,
+                    ..
+                } = &block.block_type
+                {
+                    loops.insert(loop_id.as_str(), (block.id.as_str(), exit_block_id.as_str()));
+                }
+            }
+            let mut successors: HashMap<&str, Vec<&str>> = HashMap::new();
+            for block in &self.blocks {
+                let next = successors.entry(block.id.as_str()).or_default();
+                for (field, target) in block.block_type.references() {
+                    if blocks.contains_key(target) {
+                        next.push(target);
+                    } else {
+                        errors.push(FlowValidationError::DanglingReference {
+                            block_id: block.id.clone(),
+                            field,
+                            target: target.to_string(),
+                        });
+                    }
+                }
+                if let BlockType::Continue { loop_id } | BlockType::Break { loop_id, .. } =
+                    &block.block_type
+                {
+                    let is_continue = matches!(block.block_type, BlockType::Continue { .. });
+                    match loops.get(loop_id.as_str()) {
+                        Some(&(head, exit)) => {
+                            let target = if is_continue { head } else { exit };
+                            if blocks.contains_key(target) {
+                                next.push(target);
+                            }
+                        }
+                        None => errors.push(FlowValidationError::UnknownLoop {
+                            block_id: block.id.clone(),
+                            loop_id: loop_id.clone(),
+                        }),
+                    }
+                }
+            }
+            if !blocks.contains_key(self.start_block_id.as_str()) {
+                errors.push(FlowValidationError::MissingStartBlock(
+                    self.start_block_id.clone(),
+                ));
+                return Err(errors);
+            }
+            let mut reachable = HashSet::from([self.start_block_id.as_str()]);
+            let mut queue = VecDeque::from([self.start_block_id.as_str()]);
+            while let Some(id) = queue.pop_front() {
+                for &next in successors.get(id).into_iter().flatten() {
+                    if reachable.insert(next) {
+                        queue.push_back(next);
+                    }
+                }
+            }
+            let mut seen = HashSet::new();
+            for block in &self.blocks {
+                if !reachable.contains(block.id.as_str()) && seen.insert(block.id.as_str()) {
+                    errors.push(FlowValidationError::UnreachableBlock(block.id.clone()));
+                }
+            }
+            for block in &self.blocks {
+                let BlockType::Parallel {
+                    branches,
+                    join_block,
+                } = &block.block_type
+                else {
+                    continue;
+                };
+                let mut visited = HashSet::new();
+                let mut queue: VecDeque<&str> = branches.iter().map(String::as_str).collect();
+                while let Some(id) = queue.pop_front() {
+                    if id == join_block || !visited.insert(id) {
+                        continue;
+                    }
+                    if let Some(BlockType::AwaitInput { .. }) =
+                        blocks.get(id).map(|branch| &branch.block_type)
+                    {
+                        errors.push(FlowValidationError::AwaitInParallelBranch {
+                            parallel_block: block.id.clone(),
+                            block_id: id.to_string(),
+                        });
+                    }
+                    queue.extend(successors.get(id).into_iter().flatten());
+                }
+            }
+            let terminates = reachable
+                .iter()
+                .any(|id| matches!(blocks[*id].block_type, BlockType::Terminate));
+            if !terminates {
+                errors.push(FlowValidationError::NoReachableTerminate);
+            }
+            if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(errors)
+            }
+        }
     }
 }
 pub use definition::*;
@@ -12,34 +12,45 @@
 
 pub mod agents;
 pub mod ast;
+pub mod bench;
+pub mod clock;
 pub mod flows;
 pub mod llm;
 pub mod logging;
 pub mod orchestration;
 pub mod runtime;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod simulation;
 pub mod tasks;
+pub mod transpile_cache;
 pub mod transpiler;
 pub mod workflows;
 pub use agents::{
-    Agent, AgentCapabilities, AgentError, AgentSystem, AgentSystemConfig, CapabilityMatcher,
-    CapabilityMatcherConfig, FallbackAgentConfig, GenerationConfig,
+    Agent, AgentCapabilities, AgentError, AgentId, AgentSystem, AgentSystemConfig,
+    CapabilityMatcher, CapabilityMatcherConfig, FallbackAgentConfig, GenerationConfig, MatchMode,
 };
 pub use ast::{AstNode, Contract, Literal, Op, Path, PathSegment, SourceLocation};
-pub use flows::definition::{BlockDefinition, BlockType, FlowDefinition};
+pub use clock::{Clock, SharedClock, SystemClock, TestClock};
+pub use flows::definition::{
+    BlockDefinition, BlockType, BreakOutput, FlowDefinition, FlowValidationError,
+};
 pub use llm::{LLMError, LLMProcessor, UnifiedLLMAdapter};
 pub use orchestration::{
     EventSystem, ExecutionContext, OrchestrationConfig, OrchestrationCoordinator,
     OrchestrationError, OrchestrationFlowDefinition, OrchestrationResult, OrchestrationSession,
     ResourceManager,
 };
-use runtime::{ExecutionStatus, FfiRegistry, RemarkableInterpreter};
+use runtime::{ExecutionReport, ExecutionStatus, FfiRegistry, GasSchedule, RemarkableInterpreter};
 use serde_json::Value;
 use std::collections::HashMap;
+pub use simulation::{simulate, FfiStubRegistry, SimulationReport};
 pub use stele::LLMConfig;
 pub use tasks::{
     Task, TaskConfig, TaskError, TaskExecution, TaskProposal, TaskSystem, TaskSystemConfig,
 };
-pub use transpiler::{FlowTranspiler, TranspilerError};
+pub use transpile_cache::{CacheStats, TranspileCache};
+pub use transpiler::{FlowTranspiler, TranspilerError, LOOP_INDEX_KEY, LOOP_ITEM_KEY};
 pub use workflows::{
     events, generate_complete_team, PlanningSession, PlanningSessionConfig, TeamGenerationConfig,
 };
@@ -48,12 +59,51 @@ pub async fn execute_flow(
     initial_gas: u64,
     ffi_registry: Option<FfiRegistry>,
 ) -> Result<ExecutionStatus, Box<dyn std::error::Error>> {
-    let orchestration_contract = FlowTranspiler::transpile(&flow_def)?;
-    let contract = convert_contract(orchestration_contract)?;
-    let mut runtime =
-        RemarkableInterpreter::new(initial_gas, &contract, ffi_registry.unwrap_or_default())?;
-    let result = runtime.run(contract).await?;
-    Ok(result)
+    execute_flow_with_cache(flow_def, initial_gas, ffi_registry, TranspileCache::global()).await
+}
+
+pub async fn execute_flow_with_cache(
+    flow_def: FlowDefinition,
+    initial_gas: u64,
+    ffi_registry: Option<FfiRegistry>,
+    cache: &TranspileCache,
+) -> Result<ExecutionStatus, Box<dyn std::error::Error>> {
+    let report = execute_flow_report(flow_def, initial_gas, ffi_registry, cache).await?;
+    Ok(report.into_status())
+}
+
+pub async fn execute_flow_report(
+    flow_def: FlowDefinition,
+    initial_gas: u64,
+    ffi_registry: Option<FfiRegistry>,
+    cache: &TranspileCache,
+) -> Result<ExecutionReport, Box<dyn std::error::Error>> {
+    execute_flow_with_schedule(
+        flow_def,
+        initial_gas,
+        ffi_registry,
+        cache,
+        GasSchedule::default(),
+    )
+    .await
+}
+
+pub async fn execute_flow_with_schedule(
+    flow_def: FlowDefinition,
+    initial_gas: u64,
+    ffi_registry: Option<FfiRegistry>,
+    cache: &TranspileCache,
+    gas_schedule: GasSchedule,
+) -> Result<ExecutionReport, Box<dyn std::error::Error>> {
+    let contract = cache.get_or_transpile(&flow_def)?;
+    let mut runtime = RemarkableInterpreter::with_gas_schedule(
+        initial_gas,
+        &contract,
+        ffi_registry.unwrap_or_default(),
+        gas_schedule,
+    )?;
+    let status = runtime.execute().await?;
+    Ok(runtime.report(status))
 }
 
 pub async fn execute_orchestrated_flow(
@@ -87,6 +137,7 @@ pub fn convert_contract(
         .map(|(id, node)| convert_ast_node(node).map(|converted_node| (id, converted_node)))
         .collect();
     let converted_blocks = converted_blocks?;
+    validate_block_targets(&converted_blocks)?;
     let converted_initial_state = convert_ast_node(orchestration_contract.initial_state)?;
 
     Ok(Contract {
@@ -98,6 +149,45 @@ pub fn convert_contract(
         participants: orchestration_contract.participants,
     })
 }
+#[derive(Debug, thiserror::Error)]
+pub enum ContractConversionError {
+    #[error("Block '{from_block}' jumps to unknown block '{target}'")]
+    UnknownBlockTarget { from_block: String, target: String },
+}
+
+pub fn validate_block_targets(
+    blocks: &HashMap<String, AstNode>,
+) -> Result<(), ContractConversionError> {
+    let mut ids: Vec<&String> = blocks.keys().collect();
+    ids.sort();
+    for id in ids {
+        let mut missing = None;
+        blocks[id].walk(&mut |node: &AstNode| {
+            if missing.is_some() || node.is_dynamic_target() {
+                return;
+            }
+            let targets: Vec<&String> = match &node.op {
+                Op::SetNextBlock(target) => vec![target],
+                Op::Parallel {
+                    branches,
+                    join_block,
+                } => branches.iter().chain(std::iter::once(join_block)).collect(),
+                _ => return,
+            };
+            missing = targets
+                .into_iter()
+                .find(|target| !blocks.contains_key(*target))
+                .cloned();
+        });
+        if let Some(target) = missing {
+            return Err(ContractConversionError::UnknownBlockTarget {
+                from_block: id.clone(),
+                target,
+            });
+        }
+    }
+    Ok(())
+}
 pub fn convert_ast_node(
     orchestration_node: transpiler::orchestration::ast::AstNode,
 ) -> Result<AstNode, Box<dyn std::error::Error>> {
@@ -116,26 +206,30 @@ pub fn convert_op(
         OrchOp::Literal(lit) => Ok(Op::Literal(convert_literal(lit)?)),
         OrchOp::Fetch(path_segments) => {
             let converted_path = convert_path_segments(path_segments);
-
-            Ok(Op::Evaluate {
-                bytecode: vec![],
-                output_path: converted_path,
-            })
+            match fetch_bytecode(&converted_path)? {
+                Some(bytecode) => Ok(Op::Evaluate {
+                    bytecode,
+                    output_path: converted_path,
+                }),
+                None => Ok(Op::Fetch(converted_path)),
+            }
         }
         OrchOp::Assign { path, value } => {
             let converted_path = convert_path_segments(path);
-
-            Ok(Op::Sequence(vec![
-                convert_ast_node(*value)?,
-                AstNode {
-                    op: Op::Evaluate {
-                        bytecode: vec![],
+            if let OrchOp::Fetch(source) = &value.op {
+                let source = convert_path_segments(source.clone());
+                if let Some(bytecode) = fetch_bytecode(&source)? {
+                    return Ok(Op::Evaluate {
+                        bytecode,
                         output_path: converted_path,
-                    },
-                    metadata: HashMap::new(),
-                    source_location: None,
-                },
-            ]))
+                    });
+                }
+            }
+
+            Ok(Op::Assign {
+                path: converted_path,
+                value: Box::new(convert_ast_node(*value)?),
+            })
         }
         OrchOp::Sequence(nodes) => {
             let converted_nodes: Result<Vec<AstNode>, _> =
@@ -187,15 +281,69 @@ pub fn convert_op(
         
         OrchOp::PushErrorHandler { catch_block_id } => Ok(Op::PushErrorHandler { catch_block_id }),
         OrchOp::PopErrorHandler => Ok(Op::PopErrorHandler),
+        OrchOp::Parallel {
+            branches,
+            join_block,
+        } => Ok(Op::Parallel {
+            branches,
+            join_block,
+        }),
         OrchOp::Length(node) => Ok(Op::Length(Box::new(convert_ast_node(*node)?))),
         OrchOp::Add(left, right) => Ok(Op::Add(
             Box::new(convert_ast_node(*left)?),
             Box::new(convert_ast_node(*right)?),
         )),
+        OrchOp::Sub(left, right) => Ok(Op::Subtract(
+            Box::new(convert_ast_node(*left)?),
+            Box::new(convert_ast_node(*right)?),
+        )),
+        OrchOp::Mul(left, right) => Ok(Op::Multiply(
+            Box::new(convert_ast_node(*left)?),
+            Box::new(convert_ast_node(*right)?),
+        )),
+        OrchOp::Div(left, right) => Ok(Op::Divide(
+            Box::new(convert_ast_node(*left)?),
+            Box::new(convert_ast_node(*right)?),
+        )),
+        OrchOp::Mod(left, right) => Ok(Op::Modulo(
+            Box::new(convert_ast_node(*left)?),
+            Box::new(convert_ast_node(*right)?),
+        )),
         OrchOp::LessThan(left, right) => Ok(Op::LessThan(
             Box::new(convert_ast_node(*left)?),
             Box::new(convert_ast_node(*right)?),
         )),
+        OrchOp::Map {
+            source,
+            item_var,
+            body,
+        } => Ok(Op::Map {
+            source: convert_path_segments(source),
+            item_var,
+            body: Box::new(convert_ast_node(*body)?),
+        }),
+        OrchOp::Filter {
+            source,
+            item_var,
+            predicate,
+        } => Ok(Op::Filter {
+            source: convert_path_segments(source),
+            item_var,
+            predicate: Box::new(convert_ast_node(*predicate)?),
+        }),
+        OrchOp::Reduce {
+            source,
+            item_var,
+            accumulator_var,
+            initial,
+            body,
+        } => Ok(Op::Reduce {
+            source: convert_path_segments(source),
+            item_var,
+            accumulator_var,
+            initial: Box::new(convert_ast_node(*initial)?),
+            body: Box::new(convert_ast_node(*body)?),
+        }),
     }
 }
 
@@ -218,6 +366,35 @@ fn convert_path_segments(path_segments: Vec<transpiler::orchestration::ast::Path
     Path(segments)
 }
 
+fn fetch_bytecode(path: &Path) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>> {
+    let mut var_path: Vec<&str> = Vec::new();
+    let mut indices: Vec<Value> = Vec::new();
+    for segment in &path.0 {
+        let key = match segment {
+            PathSegment::State => continue,
+            PathSegment::Input => "input",
+            PathSegment::Key(key) => key.as_str(),
+            PathSegment::Index(idx) => {
+                indices.push(Value::from(*idx));
+                continue;
+            }
+            PathSegment::DynamicOffset(_) => return Ok(None),
+        };
+        if indices.is_empty() && !key.is_empty() && !key.contains('.') {
+            var_path.push(key);
+        } else {
+            indices.push(Value::from(key));
+        }
+    }
+
+    let mut assembler = runtime::BytecodeAssembler::new();
+    assembler.load_var_path(&var_path);
+    for index in &indices {
+        assembler.push_literal(index)?.load_index();
+    }
+    Ok(Some(assembler.into_bytecode()))
+}
+
 fn convert_literal(
     lit: transpiler::orchestration::ast::Literal,
 ) -> Result<Literal, Box<dyn std::error::Error>> {
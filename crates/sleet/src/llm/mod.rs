@@ -20,7 +20,7 @@ pub mod unified_adapter;
 pub mod utils;
 pub mod validation;
 
-pub use unified_adapter::UnifiedLLMAdapter;
+pub use unified_adapter::{generate_cancellable, UnifiedLLMAdapter};
 
 pub use collaboration::{fields, json_utils, CollaborationPrompts};
 pub use collaboration_workflows::{
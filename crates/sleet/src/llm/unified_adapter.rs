@@ -19,6 +19,7 @@ use serde_json::Value;
 use std::sync::Arc;
 use stele::llm::core::LLMAdapter as SteleLLMAdapter;
 use stele::llm::unified_adapter::UnifiedLLMAdapter as SteLeUnifiedLLMAdapter;
+use tokio_util::sync::CancellationToken;
 use tracing::debug;
 use uuid::Uuid;
 
@@ -59,6 +60,30 @@ impl UnifiedLLMAdapter {
     pub async fn ollama(model: String) -> LLMResult<Self> {
         Self::with_preferences("ollama", &model).await
     }
+
+    pub async fn generate_response_cancellable(
+        &self,
+        request: LLMRequest,
+        cancellation: &CancellationToken,
+    ) -> Option<LLMResult<LLMResponse>> {
+        generate_cancellable(self, request, cancellation).await
+    }
+}
+
+pub async fn generate_cancellable(
+    adapter: &dyn SteleLLMAdapter,
+    request: LLMRequest,
+    cancellation: &CancellationToken,
+) -> Option<LLMResult<LLMResponse>> {
+    let request_id = request.id;
+    tokio::select! {
+        biased;
+        _ = cancellation.cancelled() => {
+            debug!("sleet orchestration: cancelled LLM request {request_id}");
+            None
+        }
+        response = adapter.generate_response(request) => Some(response),
+    }
 }
 
 #[async_trait]
@@ -58,6 +58,17 @@ pub struct AgentMetadata {
     pub success_rate: f64,
 }
 
+#[async_trait::async_trait]
+pub trait AgentInvoker: Send + Sync {
+    async fn invoke(
+        &self,
+        criteria: &AgentSelectionCriteria,
+        input_data: &Value,
+        options: &InteractionOptions,
+        execution_context: &ExecutionContext,
+    ) -> Result<AgentInteractionResult, AdapterError>;
+}
+
 pub struct AgentAdapter {
     agent_system: Arc<RwLock<AgentSystem>>,
     selection_cache: Arc<RwLock<HashMap<u64, CacheEntry<Agent>>>>,
@@ -243,13 +254,35 @@ impl AgentAdapter {
         options: &InteractionOptions,
         execution_context: &ExecutionContext,
     ) -> Result<AgentInteractionResult, AdapterError> {
+        let selected_agent = self
+            .select_agent_for_interaction(criteria, input_data, options, execution_context)
+            .await?;
+        self.interact_with_selected_agent(&selected_agent, input_data, options, execution_context)
+            .await
+    }
+
+    pub async fn select_agent_for_interaction(
+        &self,
+        criteria: &AgentSelectionCriteria,
+        input_data: &Value,
+        options: &InteractionOptions,
+        execution_context: &ExecutionContext,
+    ) -> Result<Agent, AdapterError> {
         self.validate_interaction_input(criteria, input_data, options, execution_context)?;
 
         self.evict_expired_cache_entries().await;
         self.enforce_cache_size_limit().await;
 
-        let selected_agent = self.select_agent(criteria).await?;
+        self.select_agent(criteria).await
+    }
 
+    pub async fn interact_with_selected_agent(
+        &self,
+        selected_agent: &Agent,
+        input_data: &Value,
+        options: &InteractionOptions,
+        execution_context: &ExecutionContext,
+    ) -> Result<AgentInteractionResult, AdapterError> {
         let mut execution_metadata = ExecutionMetadata {
             execution_id: uuid::Uuid::new_v4().to_string(),
             start_time: chrono::Utc::now(),
@@ -272,7 +305,7 @@ impl AgentAdapter {
 
         let start_time = std::time::Instant::now();
         let result = self
-            .execute_agent_task(&selected_agent, input_data, options, execution_context)
+            .execute_agent_task(selected_agent, input_data, options, execution_context)
             .await;
         let duration = start_time.elapsed();
 
@@ -287,7 +320,7 @@ impl AgentAdapter {
                     agent_id,
                     result: interaction_result,
                     execution_metadata,
-                    agent_metadata: self.extract_agent_metadata(&selected_agent),
+                    agent_metadata: self.extract_agent_metadata(selected_agent),
                 })
             }
             Err(error) => {
@@ -15,7 +15,7 @@ pub mod llm_adapter;
 pub mod task_adapter;
 pub mod workflow_adapter;
 
-pub use agent_adapter::{AgentAdapter, AgentInteractionResult};
+pub use agent_adapter::{AgentAdapter, AgentInteractionResult, AgentInvoker};
 pub use llm_adapter::{LLMAdapter, LLMProcessingResult};
 pub use task_adapter::{TaskAdapter, TaskExecutionResult};
 pub use workflow_adapter::WorkflowAdapter;
@@ -0,0 +1,195 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use super::event_system::{EventSystem, OrchestrationEvent};
+use super::resource_manager::ResourceManager;
+use super::session_manager::OrchestrationSession;
+use super::{OrchestrationError, OrchestrationResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentMessage {
+    pub session_id: String,
+    pub from: String,
+    pub to: String,
+    pub sequence: u64,
+    pub payload: Value,
+    pub sent_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Default)]
+struct ChannelState {
+    inboxes: HashMap<String, VecDeque<AgentMessage>>,
+    sequences: HashMap<(String, String), u64>,
+}
+
+#[derive(Clone)]
+pub struct AgentChannel {
+    session_id: String,
+    participants: Arc<HashSet<String>>,
+    resource_manager: Arc<RwLock<ResourceManager>>,
+    event_system: Arc<RwLock<EventSystem>>,
+    state: Arc<Mutex<ChannelState>>,
+}
+
+impl AgentChannel {
+    pub fn new(
+        session_id: impl Into<String>,
+        participants: impl IntoIterator<Item = String>,
+        resource_manager: Arc<RwLock<ResourceManager>>,
+        event_system: Arc<RwLock<EventSystem>>,
+    ) -> Self {
+        Self {
+            session_id: session_id.into(),
+            participants: Arc::new(participants.into_iter().collect()),
+            resource_manager,
+            event_system,
+            state: Arc::new(Mutex::new(ChannelState::default())),
+        }
+    }
+
+    pub fn for_session(
+        session: &OrchestrationSession,
+        resource_manager: Arc<RwLock<ResourceManager>>,
+        event_system: Arc<RwLock<EventSystem>>,
+    ) -> Self {
+        Self::new(
+            session.id.clone(),
+            session.flow_definition.participants.clone(),
+            resource_manager,
+            event_system,
+        )
+    }
+
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    pub fn agent(&self, agent_id: impl Into<String>) -> AgentHandle {
+        AgentHandle {
+            channel: self.clone(),
+            agent_id: agent_id.into(),
+        }
+    }
+
+    pub async fn send(
+        &self,
+        from: &str,
+        to: &str,
+        payload: Value,
+    ) -> OrchestrationResult<AgentMessage> {
+        for agent in [from, to] {
+            if !self.participants.contains(agent) {
+                return Err(OrchestrationError::AgentInteractionError(format!(
+                    "Agent {agent} is not a participant in session {}",
+                    self.session_id
+                )));
+            }
+        }
+
+        let mut state = self.state.lock().await;
+        self.resource_manager
+            .read()
+            .await
+            .reserve_message(&self.session_id, from)
+            .await?;
+
+        let sequence = state
+            .sequences
+            .entry((from.to_string(), to.to_string()))
+            .or_insert(0);
+        *sequence += 1;
+        let message = AgentMessage {
+            session_id: self.session_id.clone(),
+            from: from.to_string(),
+            to: to.to_string(),
+            sequence: *sequence,
+            payload,
+            sent_at: chrono::Utc::now(),
+        };
+        state
+            .inboxes
+            .entry(to.to_string())
+            .or_default()
+            .push_back(message.clone());
+
+        self.event_system
+            .write()
+            .await
+            .emit(OrchestrationEvent::AgentMessageSent {
+                session_id: self.session_id.clone(),
+                from_agent: message.from.clone(),
+                to_agent: message.to.clone(),
+                sequence: message.sequence,
+                timestamp: message.sent_at,
+            })
+            .await?;
+
+        Ok(message)
+    }
+
+    pub async fn receive(&self, agent_id: &str) -> Option<AgentMessage> {
+        self.state
+            .lock()
+            .await
+            .inboxes
+            .get_mut(agent_id)
+            .and_then(VecDeque::pop_front)
+    }
+
+    pub async fn drain(&self, agent_id: &str) -> Vec<AgentMessage> {
+        self.state
+            .lock()
+            .await
+            .inboxes
+            .get_mut(agent_id)
+            .map(|inbox| inbox.drain(..).collect())
+            .unwrap_or_default()
+    }
+
+    pub async fn pending(&self, agent_id: &str) -> usize {
+        self.state
+            .lock()
+            .await
+            .inboxes
+            .get(agent_id)
+            .map_or(0, VecDeque::len)
+    }
+}
+
+#[derive(Clone)]
+pub struct AgentHandle {
+    channel: AgentChannel,
+    agent_id: String,
+}
+
+impl AgentHandle {
+    pub fn id(&self) -> &str {
+        &self.agent_id
+    }
+
+    pub async fn send(&self, to: &str, payload: Value) -> OrchestrationResult<AgentMessage> {
+        self.channel.send(&self.agent_id, to, payload).await
+    }
+
+    pub async fn receive(&self) -> Option<AgentMessage> {
+        self.channel.receive(&self.agent_id).await
+    }
+
+    pub async fn inbox(&self) -> Vec<AgentMessage> {
+        self.channel.drain(&self.agent_id).await
+    }
+}
@@ -11,11 +11,16 @@
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
 use super::{
-    adapters::{AgentAdapter, LLMAdapter, TaskAdapter, WorkflowAdapter},
-    context_manager::ContextManager,
-    event_system::{EventSystem, OrchestrationEvent},
+    adapters::{
+        agent_adapter::AgentSelectionCriteria, AdapterError, AgentAdapter,
+        AgentInteractionResult, AgentInvoker, InteractionOptions, LLMAdapter, TaskAdapter,
+        WorkflowAdapter,
+    },
+    context_manager::{ContextManager, ExecutionContext},
+    event_system::{EventReceiver, EventSystem, FlowEvent, OrchestrationEvent},
     flow_scheduler::FlowScheduler,
-    resource_manager::ResourceManager,
+    replay::{AwaitLog, ReplaySource},
+    resource_manager::{ResourceManager, SharedRateLimiter},
     session_manager::{OrchestrationSession, SessionManager},
     OrchestrationError, OrchestrationFlowDefinition, OrchestrationResult,
 };
@@ -24,11 +29,16 @@ use crate::{
     transpiler::FlowTranspiler,
     AgentSystem, LLMProcessor, TaskSystem,
 };
+use llm_contracts::{GenerationConfig, LLMRequest, ModelRequirements, RequestContext};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use stele::llm::core::LLMAdapter as SteleLLMAdapter;
+use tokio::sync::{broadcast, RwLock};
+use tokio_util::sync::CancellationToken;
 use tracing::info;
 use uuid::Uuid;
 
@@ -52,6 +62,41 @@ fn convert_string_to_runtime_value(s: &str) -> RuntimeValue {
     RuntimeValue::String(s.to_string())
 }
 
+fn render_generate_prompt(
+    prompt_expression: &str,
+    context: &ExecutionContext,
+) -> OrchestrationResult<String> {
+    if let Ok(Value::String(literal)) = serde_json::from_str::<Value>(prompt_expression) {
+        return Ok(literal);
+    }
+
+    match context.get_context_for_path(prompt_expression) {
+        Some(Value::String(text)) => Ok(text),
+        Some(value) => Ok(value.to_string()),
+        None => Err(OrchestrationError::ValidationError(format!(
+            "Prompt expression '{prompt_expression}' did not resolve to a value"
+        ))),
+    }
+}
+
+fn resolve_break_output(
+    expression: &str,
+    context: &ExecutionContext,
+) -> OrchestrationResult<Value> {
+    if let Ok(literal) = serde_json::from_str::<Value>(expression) {
+        return Ok(literal);
+    }
+    context.get_context_for_path(expression).ok_or_else(|| {
+        OrchestrationError::ValidationError(format!(
+            "Break output expression '{expression}' did not resolve to a value"
+        ))
+    })
+}
+
+fn cancelled_error(session_id: &str) -> OrchestrationError {
+    OrchestrationError::Cancelled(format!("Session {session_id} was cancelled"))
+}
+
 fn convert_from_runtime_value(runtime_value: &RuntimeValue) -> serde_json::Value {
     match runtime_value {
         RuntimeValue::Integer(i) => serde_json::Value::Number(serde_json::Number::from(*i)),
@@ -71,6 +116,54 @@ pub struct OrchestrationConfig {
     pub storage_config: StorageConfig,
     pub resource_limits: ResourceLimits,
     pub monitoring_config: MonitoringConfig,
+    #[serde(default)]
+    pub global_rate_limits: Option<GlobalRateLimits>,
+    #[serde(default)]
+    pub agent_retry_policy: RetryPolicy,
+}
+
+pub const RETRY_POLICY_METADATA_KEY: &str = "retry_policy";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay_ms: u64,
+    pub backoff_multiplier: f64,
+    #[serde(default = "default_max_retry_delay_ms")]
+    pub max_delay_ms: u64,
+    #[serde(default)]
+    pub jitter: bool,
+}
+
+fn default_max_retry_delay_ms() -> u64 {
+    30_000
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            base_delay_ms: 0,
+            backoff_multiplier: 2.0,
+            max_delay_ms: default_max_retry_delay_ms(),
+            jitter: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn delay_before_retry(&self, retry: u32) -> Duration {
+        let exponent = i32::try_from(retry.saturating_sub(1)).unwrap_or(i32::MAX);
+        let delay_ms = (self.base_delay_ms as f64
+            * self.backoff_multiplier.max(1.0).powi(exponent))
+        .min(self.max_delay_ms as f64);
+        let delay_ms = if self.jitter && delay_ms.is_finite() && delay_ms > 0.0 {
+            rand::thread_rng().gen_range(delay_ms / 2.0..=delay_ms)
+        } else {
+            delay_ms
+        };
+        Duration::from_millis(delay_ms as u64)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -87,6 +180,21 @@ pub struct ResourceLimits {
     pub max_tasks_per_session: u32,
     pub max_memory_mb_per_session: u64,
     pub max_cpu_cores_per_session: u32,
+    #[serde(default = "default_max_messages_per_agent")]
+    pub max_messages_per_agent_per_session: u32,
+    #[serde(default)]
+    pub max_llm_tokens_per_session: Option<u64>,
+}
+
+fn default_max_messages_per_agent() -> u32 {
+    1000
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalRateLimits {
+    pub window_ms: u64,
+    pub max_llm_requests_per_window: Option<u32>,
+    pub max_tokens_per_window: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -115,6 +223,8 @@ impl Default for OrchestrationConfig {
                 max_tasks_per_session: 100,
                 max_memory_mb_per_session: 8192,
                 max_cpu_cores_per_session: 8,
+                max_messages_per_agent_per_session: default_max_messages_per_agent(),
+                max_llm_tokens_per_session: None,
             },
             monitoring_config: MonitoringConfig {
                 enable_performance_tracking: true,
@@ -122,6 +232,8 @@ impl Default for OrchestrationConfig {
                 enable_detailed_logging: false,
                 metrics_collection_interval_secs: 60,
             },
+            global_rate_limits: None,
+            agent_retry_policy: RetryPolicy::default(),
         }
     }
 }
@@ -133,25 +245,63 @@ pub struct OrchestrationCoordinator {
     flow_scheduler: Arc<RwLock<FlowScheduler>>,
     context_manager: Arc<RwLock<ContextManager>>,
     event_system: Arc<RwLock<EventSystem>>,
+    flow_events: broadcast::Sender<FlowEvent>,
 
     agent_adapter: Arc<RwLock<AgentAdapter>>,
     llm_adapter: Arc<RwLock<LLMAdapter>>,
     task_adapter: Arc<RwLock<TaskAdapter>>,
     workflow_adapter: Arc<RwLock<WorkflowAdapter>>,
+    generation_adapter: Arc<RwLock<Option<Arc<dyn SteleLLMAdapter>>>>,
+    agent_invoker: Arc<RwLock<Option<Arc<dyn AgentInvoker>>>>,
 
     _transpiler: FlowTranspiler,
 
     active_sessions: Arc<RwLock<HashMap<String, Arc<RwLock<OrchestrationSession>>>>>,
+    cancellation_tokens: Arc<RwLock<HashMap<String, CancellationToken>>>,
+    dead_letters: Arc<RwLock<Vec<DeadLetter>>>,
+    await_log: Arc<RwLock<AwaitLog>>,
 }
 
 impl OrchestrationCoordinator {
     pub async fn new(config: OrchestrationConfig) -> OrchestrationResult<Self> {
+        let rate_limiter = config
+            .global_rate_limits
+            .clone()
+            .map(SharedRateLimiter::new);
+        Self::build(config, rate_limiter, AwaitLog::Live).await
+    }
+
+    pub async fn with_shared_rate_limiter(
+        config: OrchestrationConfig,
+        rate_limiter: SharedRateLimiter,
+    ) -> OrchestrationResult<Self> {
+        Self::build(config, Some(rate_limiter), AwaitLog::Live).await
+    }
+
+    pub async fn with_replay(
+        config: OrchestrationConfig,
+        source: ReplaySource,
+    ) -> OrchestrationResult<Self> {
+        let rate_limiter = config
+            .global_rate_limits
+            .clone()
+            .map(SharedRateLimiter::new);
+        Self::build(config, rate_limiter, AwaitLog::replaying(source)).await
+    }
+
+    async fn build(
+        config: OrchestrationConfig,
+        rate_limiter: Option<SharedRateLimiter>,
+        await_log: AwaitLog,
+    ) -> OrchestrationResult<Self> {
         let session_manager = Arc::new(RwLock::new(
             SessionManager::new(config.storage_config.clone()).await?,
         ));
 
         let resource_manager = Arc::new(RwLock::new(
-            ResourceManager::new(config.resource_limits.clone()).await?,
+            ResourceManager::new(config.resource_limits.clone())
+                .await?
+                .with_rate_limiter(rate_limiter),
         ));
 
         let flow_scheduler = Arc::new(RwLock::new(
@@ -160,7 +310,9 @@ impl OrchestrationCoordinator {
 
         let context_manager = Arc::new(RwLock::new(ContextManager::new().await?));
 
-        let event_system = Arc::new(RwLock::new(EventSystem::new().await?));
+        let event_system = EventSystem::new().await?;
+        let flow_events = event_system.flow_event_sender();
+        let event_system = Arc::new(RwLock::new(event_system));
 
         let agent_config = crate::agents::AgentSystemConfig::default();
         let agent_system = Arc::new(RwLock::new(AgentSystem::new(agent_config)?));
@@ -181,12 +333,18 @@ impl OrchestrationCoordinator {
             flow_scheduler,
             context_manager,
             event_system,
+            flow_events,
             agent_adapter,
             llm_adapter,
             task_adapter,
             workflow_adapter,
+            generation_adapter: Arc::new(RwLock::new(None)),
+            agent_invoker: Arc::new(RwLock::new(None)),
             _transpiler: transpiler,
             active_sessions: Arc::new(RwLock::new(HashMap::new())),
+            cancellation_tokens: Arc::new(RwLock::new(HashMap::new())),
+            dead_letters: Arc::new(RwLock::new(Vec::new())),
+            await_log: Arc::new(RwLock::new(await_log)),
         })
     }
 
@@ -293,6 +451,10 @@ impl OrchestrationCoordinator {
             let mut active_sessions = self.active_sessions.write().await;
             active_sessions.insert(session_id.clone(), Arc::new(RwLock::new(session)));
         }
+        self.cancellation_tokens
+            .write()
+            .await
+            .insert(session_id.clone(), CancellationToken::new());
 
         {
             let mut event_system = self.event_system.write().await;
@@ -311,12 +473,14 @@ impl OrchestrationCoordinator {
         }
 
         let result = self.execute_session(&session_id).await;
+        self.publish_outcome(&session_id, &flow_def.id, &result);
 
         let should_remove_session = !matches!(&result, Ok(ExecutionStatus::AwaitingInput { .. }));
 
         if should_remove_session {
             let mut active_sessions = self.active_sessions.write().await;
             active_sessions.remove(&session_id);
+            self.cancellation_tokens.write().await.remove(&session_id);
         }
 
         {
@@ -391,9 +555,14 @@ impl OrchestrationCoordinator {
                 .clone()
         };
 
+        let cancellation = self.cancellation_token(session_id).await;
         let current_status;
 
         loop {
+            if cancellation.is_cancelled() {
+                return Err(cancelled_error(session_id));
+            }
+
             let (current_block_id, final_result) = {
                 let session_guard = session.read().await;
                 let block_id = session_guard.get_current_block_id();
@@ -409,7 +578,40 @@ impl OrchestrationCoordinator {
 
             let current_block_id = current_block_id.unwrap();
 
-            match self.execute_block(session_id, &current_block_id).await? {
+            self.publish(FlowEvent::BlockEntered {
+                session_id: session_id.to_string(),
+                block_id: current_block_id.clone(),
+                timestamp: chrono::Utc::now(),
+            });
+            let status = match self.execute_block(session_id, &current_block_id).await {
+                Ok(status) => status,
+                Err(error @ OrchestrationError::Cancelled(_)) => return Err(error),
+                Err(error) => {
+                    self.run_compensations(session_id, &session, &current_block_id, &error)
+                        .await;
+                    return Err(error);
+                }
+            };
+
+            if !matches!(status, ExecutionStatus::AwaitingInput { .. }) {
+                let mut session_guard = session.write().await;
+                session_guard.record_completed_block(current_block_id.clone());
+            }
+            self.publish(match &status {
+                ExecutionStatus::AwaitingInput { interaction_id, .. } => FlowEvent::AwaitIssued {
+                    session_id: session_id.to_string(),
+                    block_id: current_block_id.clone(),
+                    interaction_id: interaction_id.clone(),
+                    timestamp: chrono::Utc::now(),
+                },
+                _ => FlowEvent::BlockCompleted {
+                    session_id: session_id.to_string(),
+                    block_id: current_block_id.clone(),
+                    timestamp: chrono::Utc::now(),
+                },
+            });
+
+            match status {
                 ExecutionStatus::Running => {
                     continue;
                 }
@@ -446,6 +648,193 @@ impl OrchestrationCoordinator {
         Ok(current_status)
     }
 
+    async fn run_compensations(
+        &self,
+        session_id: &str,
+        session: &Arc<RwLock<OrchestrationSession>>,
+        failed_block: &str,
+        error: &OrchestrationError,
+    ) -> DeadLetter {
+        let (flow_id, plan) = {
+            let session_guard = session.read().await;
+            (
+                session_guard.flow_definition.id.clone(),
+                session_guard.compensation_plan(),
+            )
+        };
+
+        let mut compensations = Vec::with_capacity(plan.len());
+        for (block_id, compensation_block) in plan {
+            info!(
+                "Compensating block '{}' with '{}' in session {}",
+                block_id, compensation_block, session_id
+            );
+            let compensation_error = self
+                .execute_block(session_id, &compensation_block)
+                .await
+                .err();
+
+            {
+                let mut event_system = self.event_system.write().await;
+                if let Err(emit_error) = event_system
+                    .emit(OrchestrationEvent::CompensationExecuted {
+                        session_id: session_id.to_string(),
+                        block_id: block_id.clone(),
+                        compensation_block: compensation_block.clone(),
+                        succeeded: compensation_error.is_none(),
+                        timestamp: chrono::Utc::now(),
+                    })
+                    .await
+                {
+                    tracing::warn!(
+                        session_id = %session_id,
+                        block_id = %block_id,
+                        error = %emit_error,
+                        "failed to emit compensation event, continuing compensation"
+                    );
+                }
+            }
+
+            compensations.push(CompensationRecord {
+                block_id,
+                compensation_block,
+                error: compensation_error,
+            });
+        }
+
+        {
+            let mut session_guard = session.write().await;
+            session_guard.fail(error.to_string());
+        }
+
+        let dead_letter = DeadLetter {
+            session_id: session_id.to_string(),
+            flow_id,
+            failed_block: failed_block.to_string(),
+            error: error.clone(),
+            compensations,
+            timestamp: chrono::Utc::now(),
+        };
+
+        let mut dead_letters = self.dead_letters.write().await;
+        dead_letters.push(dead_letter.clone());
+
+        dead_letter
+    }
+
+    pub async fn dead_letters(&self) -> Vec<DeadLetter> {
+        self.dead_letters.read().await.clone()
+    }
+
+    pub async fn cancel_session(&self, session_id: &str) -> OrchestrationResult<()> {
+        let token = self
+            .cancellation_tokens
+            .read()
+            .await
+            .get(session_id)
+            .cloned()
+            .ok_or_else(|| {
+                OrchestrationError::SessionError(format!("Session not found: {session_id}"))
+            })?;
+        token.cancel();
+
+        let session = self.active_sessions.read().await.get(session_id).cloned();
+        if let Some(session) = session {
+            let awaiting_input = {
+                let mut session_guard = session.write().await;
+                let awaiting_input = matches!(
+                    session_guard.status,
+                    super::session_manager::SessionStatus::AwaitingInput { .. }
+                );
+                session_guard.cancel();
+                awaiting_input
+            };
+
+            if awaiting_input {
+                self.active_sessions.write().await.remove(session_id);
+                self.cancellation_tokens.write().await.remove(session_id);
+            }
+        }
+
+        info!("Cancelled session {}", session_id);
+        Ok(())
+    }
+
+    async fn cancellation_token(&self, session_id: &str) -> CancellationToken {
+        self.cancellation_tokens
+            .read()
+            .await
+            .get(session_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub async fn set_generation_adapter(&self, adapter: Arc<dyn SteleLLMAdapter>) {
+        *self.generation_adapter.write().await = Some(adapter);
+    }
+
+    pub fn subscribe(&self) -> EventReceiver {
+        EventReceiver::new(self.flow_events.subscribe())
+    }
+
+    fn publish(&self, event: FlowEvent) {
+        let _ = self.flow_events.send(event);
+    }
+
+    fn publish_outcome(
+        &self,
+        session_id: &str,
+        flow_id: &str,
+        result: &OrchestrationResult<ExecutionStatus>,
+    ) {
+        let event = match result {
+            Ok(ExecutionStatus::AwaitingInput { .. }) => return,
+            Ok(_) => FlowEvent::FlowCompleted {
+                session_id: session_id.to_string(),
+                flow_id: flow_id.to_string(),
+                timestamp: chrono::Utc::now(),
+            },
+            Err(error) => FlowEvent::FlowFailed {
+                session_id: session_id.to_string(),
+                flow_id: flow_id.to_string(),
+                error: error.to_string(),
+                timestamp: chrono::Utc::now(),
+            },
+        };
+        self.publish(event);
+    }
+
+    pub async fn start_recording(&self) {
+        *self.await_log.write().await = AwaitLog::Recording(ReplaySource::new());
+    }
+
+    pub async fn recording(&self) -> Option<ReplaySource> {
+        self.await_log.read().await.recording()
+    }
+
+    pub async fn set_agent_invoker(&self, invoker: Arc<dyn AgentInvoker>) {
+        *self.agent_invoker.write().await = Some(invoker);
+    }
+
+    async fn generation_adapter(&self) -> OrchestrationResult<Arc<dyn SteleLLMAdapter>> {
+        if let Some(adapter) = self.generation_adapter.read().await.clone() {
+            return Ok(adapter);
+        }
+
+        let mut generation_adapter = self.generation_adapter.write().await;
+        if let Some(adapter) = generation_adapter.clone() {
+            return Ok(adapter);
+        }
+
+        let adapter: Arc<dyn SteleLLMAdapter> = Arc::new(
+            crate::llm::UnifiedLLMAdapter::new()
+                .await
+                .map_err(|e| OrchestrationError::LLMProcessingError(e.to_string()))?,
+        );
+        *generation_adapter = Some(adapter.clone());
+        Ok(adapter)
+    }
+
     async fn execute_block(
         &self,
         session_id: &str,
@@ -555,15 +944,16 @@ impl OrchestrationCoordinator {
                             .collect(),
                     };
 
-                    let agent_adapter = self.agent_adapter.read().await;
-                    agent_adapter
-                        .interact_with_agent(
-                            &criteria,
-                            &serde_json::to_value(task_definition)?,
-                            &options,
-                            &adapter_context,
-                        )
-                        .await?
+                    let policy = self.retry_policy_for(&block_definition)?;
+                    self.interact_with_retries(
+                        session_id,
+                        &policy,
+                        &criteria,
+                        &serde_json::to_value(task_definition)?,
+                        &options,
+                        &adapter_context,
+                    )
+                    .await?
                 };
                 {
                     let mut session_guard = session.write().await;
@@ -584,6 +974,17 @@ impl OrchestrationCoordinator {
                 processing_options,
                 next_block,
             } => {
+                let rate_limiter = self.resource_manager.read().await.rate_limiter();
+                let permit = match &rate_limiter {
+                    Some(rate_limiter) => Some(
+                        rate_limiter
+                            .acquire(llm_config.max_tokens.map(u64::from).unwrap_or(0))
+                            .await?,
+                    ),
+                    None => None,
+                };
+
+                let cancellation = self.cancellation_token(session_id).await;
                 let llm_result = {
                     let llm_adapter = self.llm_adapter.read().await;
                     let session_guard = session.read().await;
@@ -595,17 +996,26 @@ impl OrchestrationCoordinator {
                         block_id.to_string(),
                     );
 
-                    llm_adapter
-                        .process_llm_request(
+                    tokio::select! {
+                        biased;
+                        _ = cancellation.cancelled() => {
+                            return Err(cancelled_error(session_id));
+                        }
+                        llm_result = llm_adapter.process_llm_request(
                             llm_config,
                             prompt_template,
                             context_keys,
                             processing_options,
                             &adapter_context,
-                        )
-                        .await?
+                        ) => llm_result?,
+                    }
                 };
 
+                if let (Some(rate_limiter), Some(permit)) = (&rate_limiter, &permit) {
+                    let tokens = llm_result.processing_metadata.tokens_consumed;
+                    rate_limiter.reconcile(permit, u64::from(tokens)).await;
+                }
+
                 {
                     let mut session_guard = session.write().await;
                     session_guard
@@ -617,6 +1027,109 @@ impl OrchestrationCoordinator {
                 ExecutionStatus::Running
             }
 
+            super::OrchestrationBlockType::Generate {
+                prompt_expression,
+                model,
+                output_key,
+                next_block,
+            } => {
+                let prompt = {
+                    let session_guard = session.read().await;
+                    let execution_context = session_guard.get_execution_context();
+                    render_generate_prompt(prompt_expression, execution_context)?
+                };
+
+                let (remaining_tokens, rate_limiter) = {
+                    let resource_manager = self.resource_manager.read().await;
+                    (
+                        resource_manager.remaining_llm_tokens(session_id).await?,
+                        resource_manager.rate_limiter(),
+                    )
+                };
+
+                let generation_config = GenerationConfig {
+                    max_tokens: remaining_tokens
+                        .map(|tokens| u32::try_from(tokens).unwrap_or(u32::MAX)),
+                    ..GenerationConfig::default()
+                };
+
+                let permit = match &rate_limiter {
+                    Some(rate_limiter) => Some(
+                        rate_limiter
+                            .acquire(generation_config.max_tokens.map(u64::from).unwrap_or(0))
+                            .await?,
+                    ),
+                    None => None,
+                };
+
+                let mut metadata = HashMap::new();
+                if let Some(model) = model {
+                    metadata.insert("model".to_string(), Value::String(model.clone()));
+                }
+
+                let request = LLMRequest {
+                    id: Uuid::new_v4(),
+                    prompt,
+                    system_prompt: None,
+                    model_requirements: ModelRequirements {
+                        capabilities: vec!["reasoning".to_string()],
+                        preferred_speed_tier: None,
+                        max_cost_tier: None,
+                        min_max_tokens: None,
+                    },
+                    generation_config,
+                    context: Some(RequestContext {
+                        conversation_id: None,
+                        user_id: None,
+                        session_id: Some(session_id.to_string()),
+                        metadata,
+                    }),
+                };
+
+                let adapter = self.generation_adapter().await?;
+                let cancellation = self.cancellation_token(session_id).await;
+                let response =
+                    crate::llm::generate_cancellable(adapter.as_ref(), request, &cancellation)
+                        .await
+                        .ok_or_else(|| cancelled_error(session_id))?
+                        .map_err(|e| OrchestrationError::LLMProcessingError(e.to_string()))?;
+
+                let total_tokens = u64::from(response.usage.total_tokens);
+                if let (Some(rate_limiter), Some(permit)) = (&rate_limiter, &permit) {
+                    rate_limiter.reconcile(permit, total_tokens).await;
+                }
+                {
+                    let resource_manager = self.resource_manager.read().await;
+                    resource_manager
+                        .record_llm_tokens(session_id, total_tokens)
+                        .await;
+                }
+
+                let completion = Value::String(response.content);
+
+                {
+                    let mut session_guard = session.write().await;
+                    session_guard
+                        .update_context_value(output_key, completion.clone())
+                        .await?;
+                    session_guard.set_next_block(next_block.clone());
+                }
+
+                {
+                    let mut event_system = self.event_system.write().await;
+                    event_system
+                        .emit(OrchestrationEvent::LLMProcessingCompleted {
+                            session_id: session_id.to_string(),
+                            llm_config: response.model_used,
+                            result: completion,
+                            timestamp: chrono::Utc::now(),
+                        })
+                        .await?;
+                }
+
+                ExecutionStatus::Running
+            }
+
             super::OrchestrationBlockType::TaskExecution {
                 task_config,
                 resource_requirements,
@@ -744,37 +1257,44 @@ impl OrchestrationCoordinator {
                 state_key,
                 next_block,
             } => {
-                let has_input_data = {
-                    let session_guard = session.read().await;
-                    session_guard.get_execution_context().has_input_data()
+                let input_data = {
+                    let mut session_guard = session.write().await;
+                    let context = session_guard.get_execution_context_mut();
+                    if context.has_input_data() {
+                        context.consume_input_data()
+                    } else {
+                        None
+                    }
+                };
+
+                let input_data = {
+                    let mut await_log = self.await_log.write().await;
+                    match input_data {
+                        Some(input) => {
+                            await_log.record(interaction_id, &input);
+                            Some(input)
+                        }
+                        None if await_log.is_replaying() => Some(
+                            await_log
+                                .next_response(interaction_id)
+                                .ok_or_else(|| {
+                                    OrchestrationError::ReplayMiss(interaction_id.clone())
+                                })?,
+                        ),
+                        None => None,
+                    }
                 };
 
-                if has_input_data {
-                    let input_data = {
+                if let Some(input) = input_data {
+                    {
                         let mut session_guard = session.write().await;
                         session_guard
                             .get_execution_context_mut()
-                            .consume_input_data()
-                    };
-
-                    if let Some(input) = input_data {
-                        {
-                            let mut session_guard = session.write().await;
-                            session_guard
-                                .get_execution_context_mut()
-                                .set_variable(state_key.clone(), input);
-                            session_guard.set_next_block(next_block.clone());
-                        }
-
-                        ExecutionStatus::Running
-                    } else {
-                        ExecutionStatus::AwaitingInput {
-                            session_id: session_id.to_string(),
-                            interaction_id: interaction_id.clone(),
-                            agent_id: agent_id.clone(),
-                            prompt: convert_string_to_runtime_value(prompt),
-                        }
+                            .set_variable(state_key.clone(), input);
+                        session_guard.set_next_block(next_block.clone());
                     }
+
+                    ExecutionStatus::Running
                 } else {
                     ExecutionStatus::AwaitingInput {
                         session_id: session_id.to_string(),
@@ -871,7 +1391,7 @@ impl OrchestrationCoordinator {
 
                 ExecutionStatus::Running
             }
-            super::OrchestrationBlockType::Break { loop_id } => {
+            super::OrchestrationBlockType::Break { loop_id, output } => {
                 let (exit_block_id, idx_key, arr_key) = {
                     let session_guard = session.read().await;
                     let foreach = session_guard
@@ -901,6 +1421,10 @@ impl OrchestrationCoordinator {
                 {
                     let mut session_guard = session.write().await;
                     let ec = session_guard.get_execution_context_mut();
+                    if let Some(output) = output {
+                        let value = resolve_break_output(&output.expression, ec)?;
+                        ec.set_value(&output.output_key, value);
+                    }
                     ec.set_value(&idx_key, serde_json::Value::Null);
                     ec.set_value(&arr_key, serde_json::Value::Null);
                     session_guard.set_next_block(exit_block_id);
@@ -1064,9 +1588,9 @@ impl OrchestrationCoordinator {
                     session_guard.set_next_block(next_block.clone());
                     ExecutionStatus::Running
                 }
-                crate::flows::definition::BlockType::Terminate => {
-                    ExecutionStatus::Completed(RuntimeValue::Integer(0))
-                }
+                crate::flows::definition::BlockType::Terminate => ExecutionStatus::Completed(
+                    RuntimeValue::Json(serde_json::json!(context.variables)),
+                ),
                 _ => ExecutionStatus::Running,
             }
         };
@@ -1074,6 +1598,103 @@ impl OrchestrationCoordinator {
         Ok(execution_result)
     }
 
+    fn retry_policy_for(
+        &self,
+        block: &super::OrchestrationBlockDefinition,
+    ) -> OrchestrationResult<RetryPolicy> {
+        let Some(value) = block
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get(RETRY_POLICY_METADATA_KEY))
+        else {
+            return Ok(self.config.agent_retry_policy.clone());
+        };
+        serde_json::from_value(value.clone()).map_err(|e| {
+            OrchestrationError::ValidationError(format!(
+                "Block '{}' has an invalid {RETRY_POLICY_METADATA_KEY}: {e}",
+                block.id
+            ))
+        })
+    }
+
+    async fn interact_with_retries(
+        &self,
+        session_id: &str,
+        policy: &RetryPolicy,
+        criteria: &AgentSelectionCriteria,
+        input_data: &Value,
+        options: &InteractionOptions,
+        adapter_context: &super::adapters::ExecutionContext,
+    ) -> OrchestrationResult<AgentInteractionResult> {
+        let invoker = self.agent_invoker.read().await.clone();
+        let selected_agent = match &invoker {
+            Some(_) => None,
+            None => Some(
+                self.agent_adapter
+                    .read()
+                    .await
+                    .select_agent_for_interaction(criteria, input_data, options, adapter_context)
+                    .await?,
+            ),
+        };
+        let cancellation = self.cancellation_token(session_id).await;
+        let max_attempts = policy.max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            let interaction = async {
+                match (&invoker, &selected_agent) {
+                    (Some(invoker), _) => {
+                        invoker
+                            .invoke(criteria, input_data, options, adapter_context)
+                            .await
+                    }
+                    (None, Some(agent)) => {
+                        let agent_adapter = self.agent_adapter.read().await;
+                        agent_adapter
+                            .interact_with_selected_agent(
+                                agent,
+                                input_data,
+                                options,
+                                adapter_context,
+                            )
+                            .await
+                    }
+                    (None, None) => Err(AdapterError::ResourceNotFound(format!(
+                        "no agent available for interaction in session {session_id}"
+                    ))),
+                }
+            };
+            let outcome = match options.timeout_seconds {
+                Some(timeout_secs) => {
+                    tokio::time::timeout(Duration::from_secs(timeout_secs), interaction)
+                        .await
+                        .unwrap_or_else(|_| Err(AdapterError::TimeoutError { timeout_secs }))
+                }
+                None => interaction.await,
+            };
+            let error = match outcome {
+                Ok(result) => return Ok(result),
+                Err(error) if attempt >= max_attempts => return Err(error.into()),
+                Err(error) => error,
+            };
+            let delay = policy.delay_before_retry(attempt);
+            tracing::warn!(
+                session_id = %session_id,
+                attempt,
+                max_attempts,
+                delay_ms = delay.as_millis() as u64,
+                error = %error,
+                "agent interaction failed, retrying"
+            );
+            tokio::select! {
+                biased;
+                _ = cancellation.cancelled() => return Err(cancelled_error(session_id)),
+                _ = tokio::time::sleep(delay) => {}
+            }
+            attempt += 1;
+        }
+    }
+
     async fn execute_parallel_branches(
         &self,
         session_id: &str,
@@ -1095,7 +1716,6 @@ impl OrchestrationCoordinator {
             };
 
             let block_id = block_id.clone();
-            let _coordinator = self as *const Self;
 
             let result = Box::pin(self.execute_block(session_id, &block_id)).await?;
             branch_results.insert(block_id, serde_json::to_value(&result).unwrap_or_default());
@@ -1173,7 +1793,8 @@ impl OrchestrationCoordinator {
                 | super::OrchestrationBlockType::ResourceAllocation { next_block, .. }
                 | super::OrchestrationBlockType::ParallelExecution { next_block, .. }
                 | super::OrchestrationBlockType::EventTrigger { next_block, .. }
-                | super::OrchestrationBlockType::StateCheckpoint { next_block, .. } => {
+                | super::OrchestrationBlockType::StateCheckpoint { next_block, .. }
+                | super::OrchestrationBlockType::Generate { next_block, .. } => {
                     if !next_block.is_empty() && !block_ids.contains(next_block) {
                         return Err(OrchestrationError::ValidationError(format!(
                             "Block '{}' references non-existent next_block '{}'",
@@ -1183,6 +1804,15 @@ impl OrchestrationCoordinator {
                 }
                 _ => {}
             }
+
+            if let Some(compensation_block) = &block.compensation_block {
+                if !block_ids.contains(compensation_block) {
+                    return Err(OrchestrationError::ValidationError(format!(
+                        "Block '{}' references non-existent compensation_block '{}'",
+                        block.id, compensation_block
+                    )));
+                }
+            }
         }
 
         let total_agents = flow_def.resource_requirements.agents.len() as u32;
@@ -1293,18 +1923,38 @@ impl OrchestrationCoordinator {
                 .clone()
         };
 
-        {
+        let (flow_id, resolved) = {
             let mut session_guard = session.write().await;
+            let interaction_id = match &session_guard.status {
+                super::session_manager::SessionStatus::AwaitingInput { interaction_id, .. } => {
+                    Some(interaction_id.clone())
+                }
+                _ => None,
+            };
             session_guard.resume_with_input(input_data).await?;
+            (
+                session_guard.flow_definition.id.clone(),
+                interaction_id.zip(session_guard.get_current_block_id()),
+            )
+        };
+        if let Some((interaction_id, block_id)) = resolved {
+            self.publish(FlowEvent::AwaitResolved {
+                session_id: session_id.to_string(),
+                block_id,
+                interaction_id,
+                timestamp: chrono::Utc::now(),
+            });
         }
 
         let result = self.execute_session(session_id).await;
+        self.publish_outcome(session_id, &flow_id, &result);
 
         let should_remove_session = !matches!(&result, Ok(ExecutionStatus::AwaitingInput { .. }));
 
         if should_remove_session {
             let mut active_sessions = self.active_sessions.write().await;
             active_sessions.remove(session_id);
+            self.cancellation_tokens.write().await.remove(session_id);
 
             let mut event_system = self.event_system.write().await;
             let event = match &result {
@@ -1397,3 +2047,20 @@ pub struct ResourceUsage {
     pub active_llm_instances: u32,
     pub active_tasks: u32,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompensationRecord {
+    pub block_id: String,
+    pub compensation_block: String,
+    pub error: Option<OrchestrationError>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub session_id: String,
+    pub flow_id: String,
+    pub failed_block: String,
+    pub error: OrchestrationError,
+    pub compensations: Vec<CompensationRecord>,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
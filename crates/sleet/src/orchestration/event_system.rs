@@ -21,16 +21,19 @@ pub struct EventSystem {
     subscribers: Arc<RwLock<HashMap<EventType, Vec<EventSubscriber>>>>,
     event_sender: broadcast::Sender<OrchestrationEvent>,
     _event_receiver: broadcast::Receiver<OrchestrationEvent>,
+    flow_event_sender: broadcast::Sender<FlowEvent>,
 }
 
 impl EventSystem {
     pub async fn new() -> OrchestrationResult<Self> {
         let (event_sender, event_receiver) = broadcast::channel(1000);
+        let (flow_event_sender, _) = broadcast::channel(1000);
 
         Ok(Self {
             subscribers: Arc::new(RwLock::new(HashMap::new())),
             event_sender,
             _event_receiver: event_receiver,
+            flow_event_sender,
         })
     }
 
@@ -69,6 +72,119 @@ impl EventSystem {
     pub fn get_event_receiver(&self) -> broadcast::Receiver<OrchestrationEvent> {
         self.event_sender.subscribe()
     }
+
+    pub fn flow_event_sender(&self) -> broadcast::Sender<FlowEvent> {
+        self.flow_event_sender.clone()
+    }
+
+    pub fn subscribe_flow_events(&self) -> EventReceiver {
+        EventReceiver::new(self.flow_event_sender.subscribe())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FlowEvent {
+    BlockEntered {
+        session_id: String,
+        block_id: String,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    BlockCompleted {
+        session_id: String,
+        block_id: String,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    AwaitIssued {
+        session_id: String,
+        block_id: String,
+        interaction_id: String,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    AwaitResolved {
+        session_id: String,
+        block_id: String,
+        interaction_id: String,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    FlowCompleted {
+        session_id: String,
+        flow_id: String,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    FlowFailed {
+        session_id: String,
+        flow_id: String,
+        error: String,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+}
+
+impl FlowEvent {
+    pub fn session_id(&self) -> &str {
+        match self {
+            FlowEvent::BlockEntered { session_id, .. }
+            | FlowEvent::BlockCompleted { session_id, .. }
+            | FlowEvent::AwaitIssued { session_id, .. }
+            | FlowEvent::AwaitResolved { session_id, .. }
+            | FlowEvent::FlowCompleted { session_id, .. }
+            | FlowEvent::FlowFailed { session_id, .. } => session_id,
+        }
+    }
+
+    pub fn block_id(&self) -> Option<&str> {
+        match self {
+            FlowEvent::BlockEntered { block_id, .. }
+            | FlowEvent::BlockCompleted { block_id, .. }
+            | FlowEvent::AwaitIssued { block_id, .. }
+            | FlowEvent::AwaitResolved { block_id, .. } => Some(block_id),
+            FlowEvent::FlowCompleted { .. } | FlowEvent::FlowFailed { .. } => None,
+        }
+    }
+
+    pub fn timestamp(&self) -> chrono::DateTime<chrono::Utc> {
+        match self {
+            FlowEvent::BlockEntered { timestamp, .. }
+            | FlowEvent::BlockCompleted { timestamp, .. }
+            | FlowEvent::AwaitIssued { timestamp, .. }
+            | FlowEvent::AwaitResolved { timestamp, .. }
+            | FlowEvent::FlowCompleted { timestamp, .. }
+            | FlowEvent::FlowFailed { timestamp, .. } => *timestamp,
+        }
+    }
+}
+
+pub struct EventReceiver {
+    receiver: broadcast::Receiver<FlowEvent>,
+}
+
+impl EventReceiver {
+    pub(crate) fn new(receiver: broadcast::Receiver<FlowEvent>) -> Self {
+        Self { receiver }
+    }
+
+    pub async fn recv(&mut self) -> Option<FlowEvent> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) => return Some(event),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    log::warn!("Flow event receiver lagged, skipped {skipped} events");
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    pub fn try_recv(&mut self) -> Option<FlowEvent> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(event) => return Some(event),
+                Err(broadcast::error::TryRecvError::Lagged(skipped)) => {
+                    log::warn!("Flow event receiver lagged, skipped {skipped} events");
+                }
+                Err(_) => return None,
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -145,6 +261,20 @@ pub enum OrchestrationEvent {
         new_state: Value,
         timestamp: chrono::DateTime<chrono::Utc>,
     },
+    AgentMessageSent {
+        session_id: String,
+        from_agent: String,
+        to_agent: String,
+        sequence: u64,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    CompensationExecuted {
+        session_id: String,
+        block_id: String,
+        compensation_block: String,
+        succeeded: bool,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
 }
 
 impl OrchestrationEvent {
@@ -167,6 +297,8 @@ impl OrchestrationEvent {
             OrchestrationEvent::ErrorOccurred { .. } => EventType::ErrorOccurred,
             OrchestrationEvent::PerformanceMetric { .. } => EventType::PerformanceMetric,
             OrchestrationEvent::StateChanged { .. } => EventType::StateChanged,
+            OrchestrationEvent::AgentMessageSent { .. } => EventType::AgentMessageSent,
+            OrchestrationEvent::CompensationExecuted { .. } => EventType::CompensationExecuted,
         }
     }
 
@@ -184,7 +316,9 @@ impl OrchestrationEvent {
             | OrchestrationEvent::ResourceReleased { session_id, .. }
             | OrchestrationEvent::ErrorOccurred { session_id, .. }
             | OrchestrationEvent::PerformanceMetric { session_id, .. }
-            | OrchestrationEvent::StateChanged { session_id, .. } => Some(session_id),
+            | OrchestrationEvent::StateChanged { session_id, .. }
+            | OrchestrationEvent::AgentMessageSent { session_id, .. }
+            | OrchestrationEvent::CompensationExecuted { session_id, .. } => Some(session_id),
         }
     }
 }
@@ -204,6 +338,8 @@ pub enum EventType {
     ErrorOccurred,
     PerformanceMetric,
     StateChanged,
+    AgentMessageSent,
+    CompensationExecuted,
     All,
 }
 
@@ -168,7 +168,8 @@ impl FlowScheduler {
                 | super::OrchestrationBlockType::WorkflowInvocation { next_block, .. }
                 | super::OrchestrationBlockType::ResourceAllocation { next_block, .. }
                 | super::OrchestrationBlockType::EventTrigger { next_block, .. }
-                | super::OrchestrationBlockType::StateCheckpoint { next_block, .. } => {
+                | super::OrchestrationBlockType::StateCheckpoint { next_block, .. }
+                | super::OrchestrationBlockType::Generate { next_block, .. } => {
                     Self::build_execution_order(
                         next_block,
                         flow_def,
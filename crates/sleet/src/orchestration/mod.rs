@@ -11,26 +11,33 @@
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
 pub mod adapters;
+pub mod agent_channel;
 pub mod context_manager;
 pub mod coordinator;
 pub mod event_system;
 pub mod flow_scheduler;
+pub mod replay;
 pub mod resource_manager;
 pub mod session_manager;
 
+pub use agent_channel::{AgentChannel, AgentHandle, AgentMessage};
 pub use context_manager::{ContextManager, ExecutionContext, SharedContext};
-pub use coordinator::{OrchestrationConfig, OrchestrationCoordinator};
-pub use event_system::{EventSubscriber, EventSystem, OrchestrationEvent};
+pub use coordinator::{
+    CompensationRecord, DeadLetter, GlobalRateLimits, OrchestrationConfig,
+    OrchestrationCoordinator, RetryPolicy, RETRY_POLICY_METADATA_KEY,
+};
+pub use event_system::{EventReceiver, EventSubscriber, EventSystem, FlowEvent, OrchestrationEvent};
 pub use flow_scheduler::{ExecutionPlan, FlowScheduler, SchedulingStrategy};
+pub use replay::ReplaySource;
 pub use resource_manager::{
-    AllocatedResources, AllocationStrategy, ResourceManager, ResourcePool, ResourceType,
-    ResourceUsageTracker, ResourceUtilisation,
+    AllocatedResources, AllocationStrategy, RatePermit, ResourceManager, ResourcePool,
+    ResourceType, ResourceUsageTracker, ResourceUtilisation, SharedRateLimiter,
 };
 pub use session_manager::{OrchestrationSession, SessionManager, SessionStorage};
 
 pub use adapters::{
-    AgentAdapter, AgentSelector, ExecutionStrategy, InteractionType, LLMAdapter, TaskAdapter,
-    WorkflowAdapter,
+    AgentAdapter, AgentInvoker, AgentSelector, ExecutionStrategy, InteractionType, LLMAdapter,
+    TaskAdapter, WorkflowAdapter,
 };
 
 use crate::flows::definition::FlowDefinition;
@@ -57,6 +64,8 @@ pub struct OrchestrationBlockDefinition {
     pub id: String,
     pub block_type: OrchestrationBlockType,
     pub metadata: Option<HashMap<String, Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compensation_block: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -100,6 +109,15 @@ pub enum OrchestrationBlockType {
     },
     Break {
         loop_id: String,
+        #[serde(default)]
+        output: Option<crate::flows::definition::BreakOutput>,
+    },
+    Generate {
+        prompt_expression: String,
+        #[serde(default)]
+        model: Option<String>,
+        output_key: String,
+        next_block: String,
     },
     Terminate,
 
@@ -333,6 +351,10 @@ pub enum OrchestrationError {
     TimeoutError(String),
     #[error("Event error: {0}")]
     EventError(String),
+    #[error("Cancelled: {0}")]
+    Cancelled(String),
+    #[error("No recorded response for await '{0}'")]
+    ReplayMiss(String),
 }
 
 impl From<adapters::AdapterError> for OrchestrationError {
@@ -373,6 +395,7 @@ impl From<FlowDefinition> for OrchestrationFlowDefinition {
                 id: block.id,
                 block_type: block.block_type.into(),
                 metadata: None,
+                compensation_block: None,
             })
             .collect();
 
@@ -477,9 +500,29 @@ impl From<crate::flows::definition::BlockType> for OrchestrationBlockType {
             crate::flows::definition::BlockType::Continue { loop_id } => {
                 OrchestrationBlockType::Continue { loop_id }
             }
-            crate::flows::definition::BlockType::Break { loop_id } => {
-                OrchestrationBlockType::Break { loop_id }
+            crate::flows::definition::BlockType::Break { loop_id, output } => {
+                OrchestrationBlockType::Break { loop_id, output }
             }
+            crate::flows::definition::BlockType::Generate {
+                prompt_expression,
+                model,
+                output_key,
+                next_block,
+            } => OrchestrationBlockType::Generate {
+                prompt_expression,
+                model,
+                output_key,
+                next_block,
+            },
+            crate::flows::definition::BlockType::Parallel {
+                branches,
+                join_block,
+            } => OrchestrationBlockType::ParallelExecution {
+                branch_blocks: branches,
+                merge_strategy: MergeStrategy::WaitAll,
+                timeout_secs: None,
+                next_block: join_block,
+            },
             crate::flows::definition::BlockType::Terminate => OrchestrationBlockType::Terminate,
         }
     }
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplaySource {
+    responses: BTreeMap<String, Vec<Value>>,
+}
+
+impl ReplaySource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn respond(&mut self, interaction_id: impl Into<String>, response: Value) -> &mut Self {
+        self.responses
+            .entry(interaction_id.into())
+            .or_default()
+            .push(response);
+        self
+    }
+
+    pub fn responses(&self, interaction_id: &str) -> &[Value] {
+        self.responses
+            .get(interaction_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn interaction_ids(&self) -> impl Iterator<Item = &str> {
+        self.responses.keys().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) enum AwaitLog {
+    #[default]
+    Live,
+    Recording(ReplaySource),
+    Replaying {
+        source: ReplaySource,
+        cursors: HashMap<String, usize>,
+    },
+}
+
+impl AwaitLog {
+    pub(crate) fn replaying(source: ReplaySource) -> Self {
+        AwaitLog::Replaying {
+            source,
+            cursors: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn is_replaying(&self) -> bool {
+        matches!(self, AwaitLog::Replaying { .. })
+    }
+
+    pub(crate) fn record(&mut self, interaction_id: &str, response: &Value) {
+        if let AwaitLog::Recording(source) = self {
+            source.respond(interaction_id, response.clone());
+        }
+    }
+
+    pub(crate) fn next_response(&mut self, interaction_id: &str) -> Option<Value> {
+        let AwaitLog::Replaying { source, cursors } = self else {
+            return None;
+        };
+        let cursor = cursors.entry(interaction_id.to_string()).or_default();
+        let response = source.responses(interaction_id).get(*cursor).cloned()?;
+        *cursor += 1;
+        Some(response)
+    }
+
+    pub(crate) fn recording(&self) -> Option<ReplaySource> {
+        match self {
+            AwaitLog::Recording(source) => Some(source.clone()),
+            _ => None,
+        }
+    }
+}
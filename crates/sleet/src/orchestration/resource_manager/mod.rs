@@ -11,6 +11,7 @@
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
 pub mod allocation_strategies;
+pub mod rate_limiter;
 pub mod resource_pool;
 pub mod resource_tracker;
 pub mod types;
@@ -22,6 +23,7 @@ use tokio::sync::RwLock;
 use uuid::Uuid;
 
 pub use allocation_strategies::*;
+pub use rate_limiter::*;
 pub use resource_pool::*;
 pub use resource_tracker::*;
 pub use types::*;
@@ -38,6 +40,12 @@ pub struct ResourceManager {
     active_allocations: Arc<RwLock<HashMap<String, AllocatedResources>>>,
 
     usage_tracker: Arc<RwLock<ResourceUsageTracker>>,
+
+    message_counts: Arc<RwLock<HashMap<(String, String), u32>>>,
+
+    llm_tokens: Arc<RwLock<HashMap<String, u64>>>,
+
+    rate_limiter: Option<SharedRateLimiter>,
 }
 
 impl ResourceManager {
@@ -73,9 +81,31 @@ impl ResourceManager {
             workflow_pool: Arc::new(RwLock::new(ResourcePool::new(ResourceType::Workflow))),
             active_allocations: Arc::new(RwLock::new(HashMap::new())),
             usage_tracker: Arc::new(RwLock::new(ResourceUsageTracker::new())),
+            message_counts: Arc::new(RwLock::new(HashMap::new())),
+            llm_tokens: Arc::new(RwLock::new(HashMap::new())),
+            rate_limiter: None,
         })
     }
 
+    pub fn with_rate_limiter(mut self, rate_limiter: Option<SharedRateLimiter>) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    pub fn rate_limiter(&self) -> Option<SharedRateLimiter> {
+        self.rate_limiter.clone()
+    }
+
+    pub async fn acquire_llm_permit(
+        &self,
+        estimated_tokens: u64,
+    ) -> OrchestrationResult<Option<RatePermit>> {
+        match &self.rate_limiter {
+            Some(rate_limiter) => rate_limiter.acquire(estimated_tokens).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
     pub async fn allocate_for_flow(
         &mut self,
         flow_def: &OrchestrationFlowDefinition,
@@ -144,9 +174,72 @@ impl ResourceManager {
             usage_tracker.record_deallocation(&allocated_resources);
         }
 
+        self.message_counts
+            .write()
+            .await
+            .retain(|(session, _), _| session != session_id);
+        self.llm_tokens.write().await.remove(session_id);
+
         Ok(())
     }
 
+    pub async fn reserve_message(
+        &self,
+        session_id: &str,
+        agent_id: &str,
+    ) -> OrchestrationResult<u32> {
+        let limit = self.resource_limits.max_messages_per_agent_per_session;
+        let mut counts = self.message_counts.write().await;
+        let sent = counts
+            .entry((session_id.to_string(), agent_id.to_string()))
+            .or_insert(0);
+        if *sent >= limit {
+            return Err(OrchestrationError::ResourceAllocationError(format!(
+                "Agent {agent_id} exceeded its quota of {limit} messages in session {session_id}"
+            )));
+        }
+        *sent += 1;
+        Ok(limit - *sent)
+    }
+
+    pub async fn messages_sent(&self, session_id: &str, agent_id: &str) -> u32 {
+        self.message_counts
+            .read()
+            .await
+            .get(&(session_id.to_string(), agent_id.to_string()))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub async fn remaining_llm_tokens(&self, session_id: &str) -> OrchestrationResult<Option<u64>> {
+        let Some(limit) = self.resource_limits.max_llm_tokens_per_session else {
+            return Ok(None);
+        };
+        let used = self.llm_tokens_used(session_id).await;
+        if used >= limit {
+            return Err(OrchestrationError::ResourceAllocationError(format!(
+                "Session {session_id} exhausted its budget of {limit} LLM tokens"
+            )));
+        }
+        Ok(Some(limit - used))
+    }
+
+    pub async fn record_llm_tokens(&self, session_id: &str, tokens: u64) -> u64 {
+        let mut ledger = self.llm_tokens.write().await;
+        let used = ledger.entry(session_id.to_string()).or_insert(0);
+        *used += tokens;
+        *used
+    }
+
+    pub async fn llm_tokens_used(&self, session_id: &str) -> u64 {
+        self.llm_tokens
+            .read()
+            .await
+            .get(session_id)
+            .copied()
+            .unwrap_or(0)
+    }
+
     pub async fn get_resource_utilisation(&self) -> ResourceUtilisation {
         let usage_tracker = self.usage_tracker.read().await;
         usage_tracker.get_utilisation()
@@ -0,0 +1,142 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use crate::orchestration::coordinator::GlobalRateLimits;
+use crate::orchestration::{OrchestrationError, OrchestrationResult};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RatePermit {
+    pub id: u64,
+    pub granted_at: Instant,
+    pub tokens: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SharedRateLimiter {
+    limits: GlobalRateLimits,
+    window: Duration,
+    grants: Arc<Mutex<VecDeque<RatePermit>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl SharedRateLimiter {
+    pub fn new(limits: GlobalRateLimits) -> Self {
+        Self {
+            window: Duration::from_millis(limits.window_ms),
+            limits,
+            grants: Arc::new(Mutex::new(VecDeque::new())),
+            next_id: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn limits(&self) -> &GlobalRateLimits {
+        &self.limits
+    }
+
+    pub async fn acquire(&self, tokens: u64) -> OrchestrationResult<RatePermit> {
+        if self.limits.max_llm_requests_per_window == Some(0) {
+            return Err(OrchestrationError::ResourceAllocationError(
+                "Global rate limit allows no LLM requests".to_string(),
+            ));
+        }
+        if let Some(max_tokens) = self.limits.max_tokens_per_window {
+            if tokens > max_tokens {
+                return Err(OrchestrationError::ResourceAllocationError(format!(
+                    "Request for {tokens} tokens exceeds the global limit of {max_tokens}"
+                )));
+            }
+        }
+
+        loop {
+            let retry_at = {
+                let mut grants = self.grants.lock().await;
+                let now = Instant::now();
+                self.expire(&mut grants, now);
+
+                match grants.front().map(|grant| grant.granted_at) {
+                    Some(oldest) if !self.has_capacity(&grants, tokens) => oldest + self.window,
+                    _ => return Ok(self.grant(&mut grants, now, tokens)),
+                }
+            };
+
+            tokio::time::sleep_until(retry_at).await;
+        }
+    }
+
+    pub async fn try_acquire(&self, tokens: u64) -> Option<RatePermit> {
+        let mut grants = self.grants.lock().await;
+        let now = Instant::now();
+        self.expire(&mut grants, now);
+
+        if !self.has_capacity(&grants, tokens) {
+            return None;
+        }
+
+        Some(self.grant(&mut grants, now, tokens))
+    }
+
+    pub async fn reconcile(&self, permit: &RatePermit, actual_tokens: u64) {
+        let mut grants = self.grants.lock().await;
+        self.expire(&mut grants, Instant::now());
+        if let Some(grant) = grants.iter_mut().find(|grant| grant.id == permit.id) {
+            grant.tokens = actual_tokens;
+        }
+    }
+
+    pub async fn tokens_in_window(&self) -> u64 {
+        let mut grants = self.grants.lock().await;
+        self.expire(&mut grants, Instant::now());
+        grants.iter().map(|grant| grant.tokens).sum()
+    }
+
+    pub async fn requests_in_window(&self) -> usize {
+        let mut grants = self.grants.lock().await;
+        self.expire(&mut grants, Instant::now());
+        grants.len()
+    }
+
+    fn grant(&self, grants: &mut VecDeque<RatePermit>, now: Instant, tokens: u64) -> RatePermit {
+        let permit = RatePermit {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            granted_at: now,
+            tokens,
+        };
+        grants.push_back(permit);
+        permit
+    }
+
+    fn expire(&self, grants: &mut VecDeque<RatePermit>, now: Instant) {
+        while grants
+            .front()
+            .is_some_and(|grant| now.duration_since(grant.granted_at) >= self.window)
+        {
+            grants.pop_front();
+        }
+    }
+
+    fn has_capacity(&self, grants: &VecDeque<RatePermit>, tokens: u64) -> bool {
+        let requests_ok = self
+            .limits
+            .max_llm_requests_per_window
+            .is_none_or(|max| grants.len() < max as usize);
+        let tokens_ok = self.limits.max_tokens_per_window.is_none_or(|max| {
+            grants.iter().map(|grant| grant.tokens).sum::<u64>() + tokens <= max
+        });
+        requests_ok && tokens_ok
+    }
+}
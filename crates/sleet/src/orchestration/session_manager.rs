@@ -139,6 +139,8 @@ pub struct OrchestrationSession {
     pub status: SessionStatus,
     pub current_block_id: Option<String>,
     pub execution_history: Vec<ExecutionEvent>,
+    #[serde(default)]
+    pub completed_blocks: Vec<String>,
     pub checkpoints: Vec<SessionCheckpoint>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
@@ -165,6 +167,7 @@ impl OrchestrationSession {
             gas_consumed: 0,
             status: SessionStatus::Running,
             execution_history: Vec::new(),
+            completed_blocks: Vec::new(),
             checkpoints: Vec::new(),
             created_at: now,
             updated_at: now,
@@ -187,6 +190,35 @@ impl OrchestrationSession {
         self.updated_at = chrono::Utc::now();
     }
 
+    pub fn fail(&mut self, reason: String) {
+        self.status = SessionStatus::Failed(reason);
+        self.current_block_id = None;
+        self.updated_at = chrono::Utc::now();
+    }
+
+    pub fn cancel(&mut self) {
+        self.status = SessionStatus::Cancelled;
+        self.current_block_id = None;
+        self.updated_at = chrono::Utc::now();
+    }
+
+    pub fn record_completed_block(&mut self, block_id: String) {
+        self.completed_blocks.push(block_id);
+        self.updated_at = chrono::Utc::now();
+    }
+
+    pub fn compensation_plan(&self) -> Vec<(String, String)> {
+        self.completed_blocks
+            .iter()
+            .rev()
+            .filter_map(|block_id| {
+                self.get_block_definition(block_id)
+                    .and_then(|block| block.compensation_block.clone())
+                    .map(|compensation| (block_id.clone(), compensation))
+            })
+            .collect()
+    }
+
     pub fn set_awaiting_input(&mut self, interaction_id: String, agent_id: String, prompt: Value) {
         self.status = SessionStatus::AwaitingInput {
             interaction_id,
@@ -322,6 +354,7 @@ impl OrchestrationSession {
             SessionStatus::AwaitingInput { .. } => "awaiting_input".to_string(),
             SessionStatus::Completed => "completed".to_string(),
             SessionStatus::Failed(_) => "failed".to_string(),
+            SessionStatus::Cancelled => "cancelled".to_string(),
         }
     }
 
@@ -398,6 +431,7 @@ pub enum SessionStatus {
     },
     Completed,
     Failed(String),
+    Cancelled,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
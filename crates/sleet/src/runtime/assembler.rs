@@ -139,6 +139,13 @@ impl BytecodeAssembler {
 
     
     
+    pub fn jump_if_true(&mut self) -> usize {
+        self.bytecode.push(OpCode::JumpIfTrue as u8);
+        let pos = self.bytecode.len();
+        self.bytecode.extend_from_slice(&[0, 0, 0, 0]);
+        pos
+    }
+
     pub fn jump(&mut self) -> usize {
         self.bytecode.push(OpCode::Jump as u8);
         let pos = self.bytecode.len();
@@ -166,6 +173,15 @@ impl BytecodeAssembler {
     }
 
     
+    pub fn call_ffi(&mut self, name: &str, arg_count: usize) -> AnyhowResult<&mut Self> {
+        let count = u8::try_from(arg_count)
+            .map_err(|_| anyhow::anyhow!("Too many arguments for FFI call '{}': {}", name, arg_count))?;
+        self.opcode_with_string(OpCode::CallFfi, name);
+        self.bytecode.push(count);
+        Ok(self)
+    }
+
+    
     pub fn add(&mut self) -> &mut Self {
         self.opcode(OpCode::Add)
     }
@@ -181,6 +197,9 @@ impl BytecodeAssembler {
     pub fn modulo(&mut self) -> &mut Self {
         self.opcode(OpCode::Modulo)
     }
+    pub fn concat(&mut self) -> &mut Self {
+        self.opcode(OpCode::Concat)
+    }
 
     
     pub fn equal(&mut self) -> &mut Self {
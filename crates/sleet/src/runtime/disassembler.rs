@@ -0,0 +1,163 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use crate::runtime::OpCode;
+use serde_json::Value;
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    None,
+    Value(Value),
+    Path(String),
+    Jump { offset: u32, target: usize },
+    Ffi { name: String, arg_count: u8 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisassembledInstruction {
+    pub offset: usize,
+    pub opcode: OpCode,
+    pub operand: Operand,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum DisassemblyError {
+    #[error("Invalid opcode {byte} at offset {offset}")]
+    InvalidOpcode { offset: usize, byte: u8 },
+    #[error("Truncated operand for {opcode:?} at offset {offset}")]
+    Truncated { offset: usize, opcode: OpCode },
+    #[error("Invalid operand for {opcode:?} at offset {offset}: {reason}")]
+    InvalidOperand {
+        offset: usize,
+        opcode: OpCode,
+        reason: String,
+    },
+}
+
+impl fmt::Display for DisassembledInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}  {:?}", self.offset, self.opcode)?;
+        match &self.operand {
+            Operand::None => Ok(()),
+            Operand::Value(value) => write!(f, " {value}"),
+            Operand::Path(path) => write!(f, " {path}"),
+            Operand::Jump { offset, target } => write!(f, " +{offset} -> {target:04}"),
+            Operand::Ffi { name, arg_count } => write!(f, " {name}/{arg_count}"),
+        }
+    }
+}
+
+pub fn disassemble(bytecode: &[u8]) -> Result<Vec<DisassembledInstruction>, DisassemblyError> {
+    let mut instructions = Vec::new();
+    let mut ip = 0;
+    while ip < bytecode.len() {
+        let offset = ip;
+        let opcode = OpCode::try_from(bytecode[ip]).map_err(|_| DisassemblyError::InvalidOpcode {
+            offset,
+            byte: bytecode[ip],
+        })?;
+        ip += 1;
+        let mut reader = Reader {
+            bytecode,
+            ip: &mut ip,
+            offset,
+            opcode,
+        };
+        let operand = match opcode {
+            OpCode::Push => {
+                let payload = reader.prefixed()?;
+                let value = serde_json::from_slice(payload).map_err(|e| {
+                    DisassemblyError::InvalidOperand {
+                        offset,
+                        opcode,
+                        reason: e.to_string(),
+                    }
+                })?;
+                Operand::Value(value)
+            }
+            OpCode::LoadVar => Operand::Path(reader.text()?),
+            OpCode::Jump | OpCode::JumpIfTrue | OpCode::JumpIfFalse => {
+                let relative = reader.u32()?;
+                Operand::Jump {
+                    offset: relative,
+                    target: *reader.ip + relative as usize,
+                }
+            }
+            OpCode::CallFfi => {
+                let name = reader.text()?;
+                let arg_count = reader.byte()?;
+                Operand::Ffi { name, arg_count }
+            }
+            _ => Operand::None,
+        };
+        instructions.push(DisassembledInstruction {
+            offset,
+            opcode,
+            operand,
+        });
+    }
+    Ok(instructions)
+}
+
+pub fn disassemble_to_string(bytecode: &[u8]) -> Result<String, DisassemblyError> {
+    Ok(disassemble(bytecode)?
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+struct Reader<'a> {
+    bytecode: &'a [u8],
+    ip: &'a mut usize,
+    offset: usize,
+    opcode: OpCode,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DisassemblyError> {
+        let bytes = self
+            .bytecode
+            .get(*self.ip..*self.ip + len)
+            .ok_or(DisassemblyError::Truncated {
+                offset: self.offset,
+                opcode: self.opcode,
+            })?;
+        *self.ip += len;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, DisassemblyError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, DisassemblyError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn prefixed(&mut self) -> Result<&'a [u8], DisassemblyError> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+
+    fn text(&mut self) -> Result<String, DisassemblyError> {
+        let bytes = self.prefixed()?;
+        String::from_utf8(bytes.to_vec()).map_err(|e| DisassemblyError::InvalidOperand {
+            offset: self.offset,
+            opcode: self.opcode,
+            reason: e.to_string(),
+        })
+    }
+}
@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use crate::runtime::OpCode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub const DEFAULT_OPCODE_COST: u64 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasSchedule {
+    default_cost: u64,
+    costs: HashMap<OpCode, u64>,
+    node_costs: HashMap<String, u64>,
+}
+
+impl Default for GasSchedule {
+    fn default() -> Self {
+        Self::uniform(DEFAULT_OPCODE_COST)
+    }
+}
+
+impl GasSchedule {
+    pub fn uniform(default_cost: u64) -> Self {
+        Self {
+            default_cost,
+            costs: HashMap::new(),
+            node_costs: HashMap::new(),
+        }
+    }
+
+    pub fn with_cost(mut self, opcode: OpCode, cost: u64) -> Self {
+        self.set_cost(opcode, cost);
+        self
+    }
+
+    pub fn set_cost(&mut self, opcode: OpCode, cost: u64) {
+        self.costs.insert(opcode, cost);
+    }
+
+    pub fn cost(&self, opcode: OpCode) -> u64 {
+        self.costs.get(&opcode).copied().unwrap_or(self.default_cost)
+    }
+
+    pub fn with_node_cost(mut self, op: impl Into<String>, cost: u64) -> Self {
+        self.set_node_cost(op, cost);
+        self
+    }
+
+    pub fn set_node_cost(&mut self, op: impl Into<String>, cost: u64) {
+        self.node_costs.insert(op.into(), cost);
+    }
+
+    pub fn node_cost(&self, op: &str) -> u64 {
+        self.node_costs.get(op).copied().unwrap_or(self.default_cost)
+    }
+}
@@ -0,0 +1,1230 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use crate::ast::{AstNode, Contract, Literal, Op, PathSegment};
+use crate::clock::Clock;
+use crate::runtime::{AsyncFfiRegistry, FfiRegistry, GasSchedule, InterpreterError, OpCode, Value};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+pub const ERROR_STATE_KEY: &str = "__error";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionMetrics {
+    pub blocks_executed: u64,
+    pub block_path: Vec<String>,
+    pub node_counts: BTreeMap<String, u64>,
+    pub opcode_counts: BTreeMap<String, u64>,
+}
+
+impl ExecutionMetrics {
+    pub fn total_nodes(&self) -> u64 {
+        self.node_counts.values().sum()
+    }
+
+    pub fn total_opcodes(&self) -> u64 {
+        self.opcode_counts.values().sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingAwait {
+    pub interaction_id: String,
+    pub agent_id: String,
+    pub prompt: JsonValue,
+    pub state_key: Option<String>,
+    pub next_block: Option<String>,
+    pub deadline: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GasExhaustion {
+    pub block_id: String,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub partial_state: JsonValue,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum PathKey {
+    Key(String),
+    Index(usize),
+}
+
+pub(super) type AsyncCall = (String, Vec<JsonValue>);
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Deadline {
+    started: Instant,
+    budget: Duration,
+}
+
+impl Deadline {
+    pub(super) fn start(budget: Duration) -> Self {
+        Self {
+            started: Instant::now(),
+            budget,
+        }
+    }
+
+    pub(super) fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub(super) fn remaining(&self) -> Duration {
+        self.budget.saturating_sub(self.elapsed())
+    }
+
+    pub(super) fn check(&self) -> Result<(), InterpreterError> {
+        let elapsed = self.elapsed();
+        if elapsed > self.budget {
+            return Err(InterpreterError::Timeout { elapsed });
+        }
+        Ok(())
+    }
+}
+
+pub(super) struct Env<'a> {
+    pub(super) ffi_registry: &'a FfiRegistry,
+    pub(super) async_ffi: &'a AsyncFfiRegistry,
+    pub(super) permissions: &'a Value,
+    pub(super) clock: &'a dyn Clock,
+    pub(super) deadline: Option<&'a Deadline>,
+}
+
+impl Env<'_> {
+    fn is_async_only(&self, name: &str) -> bool {
+        !self.ffi_registry.contains_key(name) && self.async_ffi.contains_key(name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct Machine {
+    pub(super) state: Value,
+    pub(super) gas_limit: u64,
+    pub(super) gas_used: u64,
+    pub(super) gas_schedule: GasSchedule,
+    pub(super) metrics: ExecutionMetrics,
+    pub(super) current_block: String,
+    pub(super) next_block: Option<String>,
+    pub(super) terminated: bool,
+    pub(super) error_handlers: Vec<String>,
+    pub(super) suspended: Option<PendingAwait>,
+    pub(super) fork: Option<(Vec<String>, String)>,
+    pub(super) strict_paths: bool,
+    #[serde(skip)]
+    pub(super) scopes: Vec<Map<String, JsonValue>>,
+}
+
+impl Machine {
+    pub(super) fn new(
+        contract: &Contract,
+        gas_limit: u64,
+        gas_schedule: GasSchedule,
+        env: &Env<'_>,
+    ) -> Result<Self, InterpreterError> {
+        let mut machine = Machine {
+            state: Value::Json(JsonValue::Object(Map::new())),
+            gas_limit: u64::MAX,
+            gas_used: 0,
+            gas_schedule,
+            metrics: ExecutionMetrics::default(),
+            current_block: contract.start_block_id.clone(),
+            next_block: None,
+            terminated: false,
+            error_handlers: Vec::new(),
+            suspended: None,
+            fork: None,
+            strict_paths: false,
+            scopes: Vec::new(),
+        };
+        if let Op::Literal(literal) = &contract.initial_state.op {
+            let initial = machine.literal_value(literal, env)?;
+            if initial.is_object() {
+                machine.state = Value::Json(initial);
+            }
+        }
+        machine.gas_limit = gas_limit;
+        machine.gas_used = 0;
+        machine.metrics = ExecutionMetrics::default();
+        Ok(machine)
+    }
+
+    pub(super) fn root(&self) -> &JsonValue {
+        static EMPTY: JsonValue = JsonValue::Null;
+        match &self.state {
+            Value::Json(json) => json,
+            _ => &EMPTY,
+        }
+    }
+
+    pub(super) fn root_mut(&mut self) -> &mut JsonValue {
+        if !matches!(self.state, Value::Json(_)) {
+            self.state = Value::Json(JsonValue::Object(Map::new()));
+        }
+        match &mut self.state {
+            Value::Json(json) => json,
+            _ => unreachable!("machine state is always stored as JSON"),
+        }
+    }
+
+    pub(super) fn gas_remaining(&self) -> u64 {
+        self.gas_limit.saturating_sub(self.gas_used)
+    }
+
+    fn charge(&mut self, amount: u64) -> Result<(), InterpreterError> {
+        let next = self.gas_used.saturating_add(amount);
+        if next > self.gas_limit {
+            self.gas_used = self.gas_limit;
+            return Err(InterpreterError::OutOfGas);
+        }
+        self.gas_used = next;
+        Ok(())
+    }
+
+    pub(super) fn charge_node(&mut self, name: &str) -> Result<(), InterpreterError> {
+        self.charge(self.gas_schedule.node_cost(name))?;
+        *self.metrics.node_counts.entry(name.to_string()).or_insert(0) += 1;
+        Ok(())
+    }
+
+    fn scoped<'k>(&self, keys: &'k [PathKey]) -> Option<(&JsonValue, &'k [PathKey])> {
+        let (PathKey::Key(name), rest) = keys.split_first()? else {
+            return None;
+        };
+        let value = self.scopes.iter().rev().find_map(|scope| scope.get(name))?;
+        Some((value, rest))
+    }
+
+    fn read(&self, keys: &[PathKey]) -> Result<Option<JsonValue>, InterpreterError> {
+        match self.scoped(keys) {
+            Some((value, rest)) => read_path(value, rest),
+            None => read_path(self.root(), keys),
+        }
+    }
+
+    fn lenient(
+        &self,
+        result: Result<JsonValue, InterpreterError>,
+    ) -> Result<JsonValue, InterpreterError> {
+        match result {
+            Err(InterpreterError::IndexOutOfBounds { .. }) if !self.strict_paths => {
+                Ok(JsonValue::Null)
+            }
+            other => other,
+        }
+    }
+
+    fn lookup(&self, keys: &[PathKey]) -> Result<JsonValue, InterpreterError> {
+        match self.read(keys)? {
+            Some(value) => Ok(value),
+            None if self.strict_paths => Err(InterpreterError::PathNotFound {
+                path: display_path(keys),
+                block_id: self.current_block.clone(),
+            }),
+            None => Ok(JsonValue::Null),
+        }
+    }
+
+    fn call_function(
+        &self,
+        name: &str,
+        args: Vec<JsonValue>,
+        env: &Env<'_>,
+    ) -> Result<JsonValue, InterpreterError> {
+        tracing::debug!(function = name, args = args.len(), gas_used = self.gas_used, "ffi call");
+        let result = if env.is_async_only(name) {
+            Err(InterpreterError::InvalidOperation(format!(
+                "async FFI function '{name}' can only be awaited from a top-level Evaluate"
+            )))
+        } else {
+            call_ffi(name, args, env.ffi_registry, env.permissions)
+        };
+        if let Err(error) = &result {
+            tracing::warn!(
+                function = name,
+                error = %error,
+                gas_used = self.gas_used,
+                "ffi call failed"
+            );
+        }
+        if let Some(deadline) = env.deadline {
+            deadline.check()?;
+        }
+        result
+    }
+
+    pub(super) fn halted(&self) -> bool {
+        self.terminated || self.suspended.is_some()
+    }
+
+    pub(super) fn eval(
+        &mut self,
+        node: &AstNode,
+        env: &Env<'_>,
+    ) -> Result<JsonValue, InterpreterError> {
+        self.charge_node(node.op.name())?;
+        match &node.op {
+            Op::Literal(literal) => self.literal_value(literal, env),
+            Op::Sequence(nodes) => {
+                let mut last = JsonValue::Null;
+                for child in nodes {
+                    last = self.eval(child, env)?;
+                    if self.halted() {
+                        break;
+                    }
+                }
+                Ok(last)
+            }
+            Op::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                let condition = self.eval(condition, env)?;
+                if truthy(&condition) {
+                    self.eval(then_branch, env)
+                } else if let Some(else_branch) = else_branch {
+                    self.eval(else_branch, env)
+                } else {
+                    Ok(JsonValue::Null)
+                }
+            }
+            Op::Fetch(path) => {
+                let fetched = self
+                    .resolve_path(&path.0, env)
+                    .and_then(|keys| self.lookup(&keys));
+                self.lenient(fetched)
+            }
+            Op::Assign { path, value } => {
+                let value = self.eval(value, env)?;
+                let keys = self.resolve_path(&path.0, env)?;
+                self.write_path(&keys, value.clone())?;
+                Ok(value)
+            }
+            Op::SetNextBlock(block_id) => {
+                self.next_block = Some(block_id.clone());
+                Ok(JsonValue::Null)
+            }
+            Op::Terminate => {
+                self.terminated = true;
+                Ok(JsonValue::Null)
+            }
+            Op::Await {
+                interaction_id,
+                agent_id,
+                prompt,
+                timeout_ms,
+            } => {
+                let prompt = match prompt {
+                    Some(prompt) => self.eval(prompt, env)?,
+                    None => JsonValue::Null,
+                };
+                let meta_str = |key: &str| {
+                    node.metadata
+                        .get(key)
+                        .and_then(JsonValue::as_str)
+                        .map(str::to_string)
+                };
+                tracing::info!(
+                    interaction_id = %interaction_id,
+                    agent_id = %agent_id,
+                    gas_used = self.gas_used,
+                    "await"
+                );
+                self.suspended = Some(PendingAwait {
+                    interaction_id: interaction_id.clone(),
+                    agent_id: agent_id.clone(),
+                    prompt,
+                    state_key: meta_str("state_key"),
+                    next_block: meta_str("next_block"),
+                    deadline: timeout_ms.and_then(|ms| {
+                        let timeout = chrono::Duration::try_milliseconds(i64::try_from(ms).ok()?)?;
+                        env.clock.now().checked_add_signed(timeout)
+                    }),
+                });
+                Ok(JsonValue::Null)
+            }
+            Op::Evaluate {
+                bytecode,
+                output_path,
+            } => {
+                let result = self.execute_bytecode(bytecode, env)?;
+                if let Some(value) = &result {
+                    let keys = self.resolve_path(&output_path.0, env)?;
+                    self.write_path(&keys, value.clone())?;
+                }
+                Ok(result.unwrap_or(JsonValue::Null))
+            }
+            Op::PushErrorHandler { catch_block_id } => {
+                self.error_handlers.push(catch_block_id.clone());
+                Ok(JsonValue::Null)
+            }
+            Op::PopErrorHandler => {
+                self.error_handlers.pop();
+                Ok(JsonValue::Null)
+            }
+            Op::Parallel {
+                branches,
+                join_block,
+            } => {
+                self.fork = Some((branches.clone(), join_block.clone()));
+                Ok(JsonValue::Null)
+            }
+            Op::Add(l, r)
+            | Op::Subtract(l, r)
+            | Op::Multiply(l, r)
+            | Op::Divide(l, r)
+            | Op::Modulo(l, r)
+            | Op::Equal(l, r)
+            | Op::NotEqual(l, r)
+            | Op::LessThan(l, r)
+            | Op::GreaterThan(l, r)
+            | Op::LessEqual(l, r)
+            | Op::GreaterEqual(l, r) => {
+                let a = self.eval(l, env)?;
+                let b = self.eval(r, env)?;
+                binary(node.op.name(), &a, &b)
+            }
+            Op::And(l, r) => {
+                if !truthy(&self.eval(l, env)?) {
+                    return Ok(JsonValue::Bool(false));
+                }
+                Ok(JsonValue::Bool(truthy(&self.eval(r, env)?)))
+            }
+            Op::Or(l, r) => {
+                if truthy(&self.eval(l, env)?) {
+                    return Ok(JsonValue::Bool(true));
+                }
+                Ok(JsonValue::Bool(truthy(&self.eval(r, env)?)))
+            }
+            Op::Not(inner) => {
+                let value = self.eval(inner, env)?;
+                Ok(JsonValue::Bool(!truthy(&value)))
+            }
+            Op::Negate(inner) => {
+                let value = self.eval(inner, env)?;
+                negate(&value)
+            }
+            Op::Length(inner) => {
+                let value = self.eval(inner, env)?;
+                length(&value)
+            }
+            Op::Index { object, index } => {
+                let object = self.eval(object, env)?;
+                let index = self.eval(index, env)?;
+                self.lenient(index_value(&object, &index))
+            }
+            Op::Call { callee, args } => {
+                let callee = self.eval(callee, env)?;
+                let name = callee.as_str().ok_or_else(|| {
+                    InterpreterError::InvalidOperation(format!(
+                        "Call target must be a function name, found {callee}"
+                    ))
+                })?;
+                let mut values = Vec::with_capacity(args.len());
+                for arg in args {
+                    values.push(self.eval(arg, env)?);
+                }
+                self.call_function(name, values, env)
+            }
+            Op::Conditional {
+                condition,
+                then_expr,
+                else_expr,
+            } => {
+                let condition = self.eval(condition, env)?;
+                if truthy(&condition) {
+                    self.eval(then_expr, env)
+                } else {
+                    self.eval(else_expr, env)
+                }
+            }
+            Op::Map {
+                source,
+                item_var,
+                body,
+            } => {
+                let items = self.source_array(&source.0, env)?;
+                let mut mapped = Vec::with_capacity(items.len());
+                self.with_scope(|machine| {
+                    for item in items {
+                        machine.charge_element()?;
+                        machine.bind(item_var, item);
+                        mapped.push(machine.eval(body, env)?);
+                    }
+                    Ok(())
+                })?;
+                Ok(JsonValue::Array(mapped))
+            }
+            Op::Filter {
+                source,
+                item_var,
+                predicate,
+            } => {
+                let items = self.source_array(&source.0, env)?;
+                let mut kept = Vec::new();
+                self.with_scope(|machine| {
+                    for item in items {
+                        machine.charge_element()?;
+                        machine.bind(item_var, item.clone());
+                        if truthy(&machine.eval(predicate, env)?) {
+                            kept.push(item);
+                        }
+                    }
+                    Ok(())
+                })?;
+                Ok(JsonValue::Array(kept))
+            }
+            Op::Reduce {
+                source,
+                item_var,
+                accumulator_var,
+                initial,
+                body,
+            } => {
+                let items = self.source_array(&source.0, env)?;
+                let mut accumulator = self.eval(initial, env)?;
+                self.with_scope(|machine| {
+                    for item in items {
+                        machine.charge_element()?;
+                        machine.bind(item_var, item);
+                        machine.bind(accumulator_var, accumulator.clone());
+                        accumulator = machine.eval(body, env)?;
+                    }
+                    Ok(())
+                })?;
+                Ok(accumulator)
+            }
+        }
+    }
+
+    fn charge_element(&mut self) -> Result<(), InterpreterError> {
+        self.charge_node("Element")
+    }
+
+    fn source_array(
+        &mut self,
+        segments: &[PathSegment],
+        env: &Env<'_>,
+    ) -> Result<Vec<JsonValue>, InterpreterError> {
+        let keys = self.resolve_path(segments, env)?;
+        match self.lookup(&keys)? {
+            JsonValue::Array(items) => Ok(items),
+            JsonValue::Null => Ok(Vec::new()),
+            other => Err(InterpreterError::TypeMismatch {
+                expected: "array".to_string(),
+                found: other.to_string(),
+            }),
+        }
+    }
+
+    fn bind(&mut self, name: &str, value: JsonValue) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), value);
+        }
+    }
+
+    fn with_scope<F>(&mut self, body: F) -> Result<(), InterpreterError>
+    where
+        F: FnOnce(&mut Self) -> Result<(), InterpreterError>,
+    {
+        self.scopes.push(Map::new());
+        let result = body(self);
+        self.scopes.pop();
+        result
+    }
+
+    fn literal_value(
+        &mut self,
+        literal: &Literal,
+        env: &Env<'_>,
+    ) -> Result<JsonValue, InterpreterError> {
+        Ok(match literal {
+            Literal::Null => JsonValue::Null,
+            Literal::Bool(b) => JsonValue::Bool(*b),
+            Literal::Number(n) => number(*n),
+            Literal::String(s) => JsonValue::String(s.clone()),
+            Literal::JsonValue(v) => v.clone(),
+            Literal::Array(nodes) => {
+                let mut values = Vec::with_capacity(nodes.len());
+                for node in nodes {
+                    values.push(self.eval(node, env)?);
+                }
+                JsonValue::Array(values)
+            }
+            Literal::Object(entries) => {
+                let mut keys: Vec<&String> = entries.keys().collect();
+                keys.sort();
+                let mut map = Map::new();
+                for key in keys {
+                    map.insert(key.clone(), self.eval(&entries[key], env)?);
+                }
+                JsonValue::Object(map)
+            }
+        })
+    }
+
+    pub(super) fn resolve_path(
+        &mut self,
+        segments: &[PathSegment],
+        env: &Env<'_>,
+    ) -> Result<Vec<PathKey>, InterpreterError> {
+        let mut keys = Vec::with_capacity(segments.len());
+        for segment in segments {
+            match segment {
+                PathSegment::State => {}
+                PathSegment::Input => keys.push(PathKey::Key("input".to_string())),
+                PathSegment::Key(key) => keys.push(PathKey::Key(key.clone())),
+                PathSegment::Index(idx) => {
+                    keys.push(PathKey::Index(usize::try_from(*idx).unwrap_or(usize::MAX)));
+                }
+                PathSegment::DynamicOffset(node) => {
+                    let offset = self.eval(node, env)?;
+                    let key = match &offset {
+                        JsonValue::String(s) => PathKey::Key(s.clone()),
+                        other => PathKey::Index(self.path_index(&keys, as_signed_index(other)?)?),
+                    };
+                    keys.push(key);
+                }
+            }
+        }
+        Ok(keys)
+    }
+
+    fn path_index(&self, keys: &[PathKey], index: i64) -> Result<usize, InterpreterError> {
+        if let Ok(index) = usize::try_from(index) {
+            return Ok(index);
+        }
+        let len = match self.read(keys)? {
+            Some(JsonValue::Array(items)) => items.len(),
+            _ => 0,
+        };
+        resolve_index(index, len)
+    }
+
+    pub(super) fn write_path(
+        &mut self,
+        keys: &[PathKey],
+        value: JsonValue,
+    ) -> Result<(), InterpreterError> {
+        if let Some((PathKey::Key(name), rest)) = keys.split_first() {
+            let bound = self
+                .scopes
+                .iter_mut()
+                .rev()
+                .find_map(|scope| scope.get_mut(name));
+            if let Some(slot) = bound {
+                if rest.is_empty() {
+                    *slot = value;
+                    return Ok(());
+                }
+                return write_json_path(slot, rest, value);
+            }
+        }
+        write_json_path(self.root_mut(), keys, value)
+    }
+
+    pub(super) fn write_state_key(
+        &mut self,
+        state_key: &str,
+        value: JsonValue,
+    ) -> Result<(), InterpreterError> {
+        let keys: Vec<PathKey> = state_key
+            .split('.')
+            .filter(|s| !s.is_empty())
+            .map(|s| PathKey::Key(s.to_string()))
+            .collect();
+        self.write_path(&keys, value)
+    }
+
+    pub(super) fn record_error(&mut self, error: &InterpreterError) -> Result<(), InterpreterError> {
+        self.write_state_key(ERROR_STATE_KEY, JsonValue::String(error.to_string()))
+    }
+
+    pub(super) fn exhausted(&self) -> InterpreterError {
+        InterpreterError::GasExhausted(Box::new(GasExhaustion {
+            block_id: self.current_block.clone(),
+            gas_limit: self.gas_limit,
+            gas_used: self.gas_used,
+            partial_state: self.root().clone(),
+        }))
+    }
+
+    pub(super) fn execute_opcode(
+        &mut self,
+        bytecode: &[u8],
+        ip: &mut usize,
+        stack: &mut Vec<JsonValue>,
+        env: &Env<'_>,
+    ) -> Result<Option<AsyncCall>, InterpreterError> {
+        let opcode = OpCode::try_from(bytecode[*ip])?;
+        self.charge(self.gas_schedule.cost(opcode))?;
+        *ip += 1;
+        *self
+            .metrics
+            .opcode_counts
+            .entry(format!("{opcode:?}"))
+            .or_insert(0) += 1;
+        match opcode {
+            OpCode::LoadVar => {
+                let payload = read_prefixed(bytecode, ip)?;
+                let keys = var_path_keys(&String::from_utf8_lossy(payload));
+                stack.push(self.lookup(&keys)?);
+            }
+            OpCode::Call | OpCode::CallFfi => {
+                let (name, args) = call_operands(opcode, bytecode, ip, stack)?;
+                if env.is_async_only(&name) {
+                    tracing::debug!(
+                        function = %name,
+                        args = args.len(),
+                        gas_used = self.gas_used,
+                        "async ffi call"
+                    );
+                    return Ok(Some((name, args)));
+                }
+                stack.push(self.call_function(&name, args, env)?);
+            }
+            OpCode::LoadIndex => {
+                let index = pop(stack)?;
+                let object = pop(stack)?;
+                stack.push(self.lenient(index_value(&object, &index))?);
+            }
+            OpCode::Halt | OpCode::Return => *ip = bytecode.len(),
+            OpCode::StoreVar => {
+                return Err(InterpreterError::UnsupportedOpcode(format!("{opcode:?}")));
+            }
+            _ => execute_stack_opcode(opcode, bytecode, ip, stack)?,
+        }
+        Ok(None)
+    }
+
+    fn execute_bytecode(
+        &mut self,
+        bytecode: &[u8],
+        env: &Env<'_>,
+    ) -> Result<Option<JsonValue>, InterpreterError> {
+        let mut stack: Vec<JsonValue> = Vec::new();
+        let mut ip = 0usize;
+        while ip < bytecode.len() {
+            if let Some((name, _)) = self.execute_opcode(bytecode, &mut ip, &mut stack, env)? {
+                return Err(InterpreterError::InvalidOperation(format!(
+                    "async FFI function '{name}' can only be awaited from a top-level Evaluate"
+                )));
+            }
+        }
+        Ok(stack.pop())
+    }
+}
+
+fn write_json_path(
+    state: &mut JsonValue,
+    keys: &[PathKey],
+    value: JsonValue,
+) -> Result<(), InterpreterError> {
+    let Some((last, parents)) = keys.split_last() else {
+        if value.is_object() {
+            *state = value;
+            return Ok(());
+        }
+        return Err(InterpreterError::InvalidAssignmentTarget(
+            "state root must be an object".to_string(),
+        ));
+    };
+    let mut current = state;
+    for (i, key) in parents.iter().enumerate() {
+        let next_is_index = matches!(keys[i + 1], PathKey::Index(_));
+        current = child_mut(current, key, next_is_index)?;
+    }
+    match last {
+        PathKey::Key(key) => {
+            if !current.is_object() {
+                *current = JsonValue::Object(Map::new());
+            }
+            if let JsonValue::Object(map) = current {
+                map.insert(key.clone(), value);
+            }
+        }
+        PathKey::Index(idx) => {
+            if !current.is_array() {
+                *current = JsonValue::Array(Vec::new());
+            }
+            if let JsonValue::Array(items) = current {
+                *array_slot(items, *idx)? = value;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn var_path_keys(path: &str) -> Vec<PathKey> {
+    path.split('.')
+        .filter(|s| !s.is_empty())
+        .map(|s| match s.parse::<usize>() {
+            Ok(idx) => PathKey::Index(idx),
+            Err(_) => PathKey::Key(s.to_string()),
+        })
+        .collect()
+}
+
+fn call_operands(
+    opcode: OpCode,
+    bytecode: &[u8],
+    ip: &mut usize,
+    stack: &mut Vec<JsonValue>,
+) -> Result<(String, Vec<JsonValue>), InterpreterError> {
+    let (name, count) = if opcode == OpCode::CallFfi {
+        let name = String::from_utf8_lossy(read_prefixed(bytecode, ip)?).to_string();
+        let count = *bytecode.get(*ip).ok_or_else(|| {
+            InterpreterError::InvalidBytecode("Missing FFI argument count".to_string())
+        })? as usize;
+        *ip += 1;
+        (name, count)
+    } else {
+        let count = as_index(&pop(stack)?)?;
+        let callee = pop(stack)?;
+        let name = callee.as_str().ok_or_else(|| {
+            InterpreterError::InvalidOperation(format!(
+                "Call target must be a function name, found {callee}"
+            ))
+        })?;
+        (name.to_string(), count)
+    };
+    if stack.len() < count {
+        return Err(InterpreterError::StackUnderflow);
+    }
+    let args = stack.split_off(stack.len() - count);
+    Ok((name, args))
+}
+
+fn execute_stack_opcode(
+    opcode: OpCode,
+    bytecode: &[u8],
+    ip: &mut usize,
+    stack: &mut Vec<JsonValue>,
+) -> Result<(), InterpreterError> {
+    match opcode {
+        OpCode::Push => {
+            let payload = read_prefixed(bytecode, ip)?;
+            let value = serde_json::from_slice(payload).map_err(|e| {
+                InterpreterError::InvalidBytecode(format!("Invalid push payload: {e}"))
+            })?;
+            stack.push(value);
+        }
+        OpCode::Pop => {
+            pop(stack)?;
+        }
+        OpCode::Dup => {
+            let top = stack.last().cloned().ok_or(InterpreterError::StackUnderflow)?;
+            stack.push(top);
+        }
+        OpCode::Swap => {
+            let len = stack.len();
+            if len < 2 {
+                return Err(InterpreterError::StackUnderflow);
+            }
+            stack.swap(len - 1, len - 2);
+        }
+        OpCode::Add
+        | OpCode::Subtract
+        | OpCode::Multiply
+        | OpCode::Divide
+        | OpCode::Modulo
+        | OpCode::Equal
+        | OpCode::NotEqual
+        | OpCode::GreaterThan
+        | OpCode::LessThan
+        | OpCode::GreaterEqual
+        | OpCode::LessEqual => {
+            let b = pop(stack)?;
+            let a = pop(stack)?;
+            stack.push(binary(&format!("{opcode:?}"), &a, &b)?);
+        }
+        OpCode::Concat => {
+            let b = pop(stack)?;
+            let a = pop(stack)?;
+            stack.push(concat(&a, &b)?);
+        }
+        OpCode::And => {
+            let b = pop(stack)?;
+            let a = pop(stack)?;
+            stack.push(JsonValue::Bool(truthy(&a) && truthy(&b)));
+        }
+        OpCode::Or => {
+            let b = pop(stack)?;
+            let a = pop(stack)?;
+            stack.push(JsonValue::Bool(truthy(&a) || truthy(&b)));
+        }
+        OpCode::Not => {
+            let a = pop(stack)?;
+            stack.push(JsonValue::Bool(!truthy(&a)));
+        }
+        OpCode::Negate => {
+            let a = pop(stack)?;
+            stack.push(negate(&a)?);
+        }
+        OpCode::Jump => {
+            let offset = read_u32(bytecode, ip)? as usize;
+            *ip += offset;
+        }
+        OpCode::JumpIfFalse | OpCode::JumpIfTrue => {
+            let offset = read_u32(bytecode, ip)? as usize;
+            let condition = truthy(&pop(stack)?);
+            if condition == (opcode == OpCode::JumpIfTrue) {
+                *ip += offset;
+            }
+        }
+        OpCode::LoadVar
+        | OpCode::LoadIndex
+        | OpCode::StoreVar
+        | OpCode::Call
+        | OpCode::CallFfi
+        | OpCode::Return
+        | OpCode::Halt => {
+            return Err(InterpreterError::UnsupportedOpcode(format!("{opcode:?}")));
+        }
+    }
+    Ok(())
+}
+
+fn child_mut<'v>(
+    current: &'v mut JsonValue,
+    key: &PathKey,
+    next_is_index: bool,
+) -> Result<&'v mut JsonValue, InterpreterError> {
+    let empty = || {
+        if next_is_index {
+            JsonValue::Array(Vec::new())
+        } else {
+            JsonValue::Object(Map::new())
+        }
+    };
+    match key {
+        PathKey::Key(key) => {
+            if !current.is_object() {
+                *current = JsonValue::Object(Map::new());
+            }
+            match current {
+                JsonValue::Object(map) => {
+                    let child = map.entry(key.clone()).or_insert_with(empty);
+                    if child.is_null() {
+                        *child = empty();
+                    }
+                    Ok(child)
+                }
+                _ => Err(InterpreterError::InternalVMError(
+                    "object container expected".to_string(),
+                )),
+            }
+        }
+        PathKey::Index(idx) => {
+            if !current.is_array() {
+                *current = JsonValue::Array(Vec::new());
+            }
+            match current {
+                JsonValue::Array(items) => {
+                    let child = array_slot(items, *idx)?;
+                    if child.is_null() {
+                        *child = empty();
+                    }
+                    Ok(child)
+                }
+                _ => Err(InterpreterError::InternalVMError(
+                    "array container expected".to_string(),
+                )),
+            }
+        }
+    }
+}
+
+fn array_slot(items: &mut Vec<JsonValue>, idx: usize) -> Result<&mut JsonValue, InterpreterError> {
+    let len = items.len();
+    if idx == len {
+        items.push(JsonValue::Null);
+    }
+    items.get_mut(idx).ok_or(InterpreterError::IndexOutOfBounds {
+        index: signed_index(idx),
+        len,
+    })
+}
+
+fn read_path(
+    state: &JsonValue,
+    keys: &[PathKey],
+) -> Result<Option<JsonValue>, InterpreterError> {
+    let mut current = state;
+    for key in keys {
+        let next = match (key, current) {
+            (PathKey::Key(k), JsonValue::Object(map)) => map.get(k),
+            (PathKey::Index(i), JsonValue::Array(items)) => {
+                Some(items.get(*i).ok_or(InterpreterError::IndexOutOfBounds {
+                    index: signed_index(*i),
+                    len: items.len(),
+                })?)
+            }
+            (PathKey::Index(i), JsonValue::Object(map)) => map.get(&i.to_string()),
+            _ => None,
+        };
+        match next {
+            Some(value) => current = value,
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(current.clone()))
+}
+
+pub(super) fn merge_branch_writes(merged: &mut JsonValue, fork_state: &JsonValue, branch: &JsonValue) {
+    if branch == fork_state {
+        return;
+    }
+    match (merged, fork_state, branch) {
+        (JsonValue::Object(merged), JsonValue::Object(before), JsonValue::Object(after)) => {
+            for (key, value) in after {
+                if before.get(key) == Some(value) {
+                    continue;
+                }
+                match (merged.get_mut(key), before.get(key)) {
+                    (Some(existing), Some(original)) => {
+                        merge_branch_writes(existing, original, value)
+                    }
+                    _ => {
+                        merged.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+            for key in before.keys() {
+                if !after.contains_key(key) {
+                    merged.remove(key);
+                }
+            }
+        }
+        (merged, _, _) => *merged = branch.clone(),
+    }
+}
+
+fn display_path(keys: &[PathKey]) -> String {
+    let mut out = String::from("state");
+    for key in keys {
+        match key {
+            PathKey::Key(k) => {
+                out.push('.');
+                out.push_str(k);
+            }
+            PathKey::Index(i) => out.push_str(&format!("[{i}]")),
+        }
+    }
+    out
+}
+
+fn read_u32(bytecode: &[u8], ip: &mut usize) -> Result<u32, InterpreterError> {
+    let bytes = bytecode.get(*ip..*ip + 4).ok_or_else(|| {
+        InterpreterError::InvalidBytecode("Incomplete instruction operand".to_string())
+    })?;
+    *ip += 4;
+    Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn read_prefixed<'b>(
+    bytecode: &'b [u8],
+    ip: &mut usize,
+) -> Result<&'b [u8], InterpreterError> {
+    let len = read_u32(bytecode, ip)? as usize;
+    let payload = bytecode.get(*ip..*ip + len).ok_or_else(|| {
+        InterpreterError::InvalidBytecode("Incomplete instruction payload".to_string())
+    })?;
+    *ip += len;
+    Ok(payload)
+}
+
+fn pop(stack: &mut Vec<JsonValue>) -> Result<JsonValue, InterpreterError> {
+    stack.pop().ok_or(InterpreterError::StackUnderflow)
+}
+
+fn number(n: f64) -> JsonValue {
+    if n.fract() == 0.0 && n.abs() < 9_007_199_254_740_992.0 {
+        JsonValue::from(n as i64)
+    } else {
+        serde_json::Number::from_f64(n)
+            .map(JsonValue::Number)
+            .unwrap_or(JsonValue::Null)
+    }
+}
+
+fn as_number(value: &JsonValue, op: &str) -> Result<f64, InterpreterError> {
+    value.as_f64().ok_or_else(|| InterpreterError::TypeMismatch {
+        expected: "number".to_string(),
+        found: format!("{value} in {op}"),
+    })
+}
+
+fn as_index(value: &JsonValue) -> Result<usize, InterpreterError> {
+    match value.as_f64() {
+        Some(n) if n >= 0.0 && n.fract() == 0.0 => Ok(n as usize),
+        _ => Err(InterpreterError::TypeMismatch {
+            expected: "non-negative integer index".to_string(),
+            found: value.to_string(),
+        }),
+    }
+}
+
+fn as_signed_index(value: &JsonValue) -> Result<i64, InterpreterError> {
+    match value.as_f64() {
+        Some(n) if n.fract() == 0.0 => Ok(n as i64),
+        _ => Err(InterpreterError::TypeMismatch {
+            expected: "integer index".to_string(),
+            found: value.to_string(),
+        }),
+    }
+}
+
+fn signed_index(index: usize) -> i64 {
+    i64::try_from(index).unwrap_or(i64::MAX)
+}
+
+fn resolve_index(index: i64, len: usize) -> Result<usize, InterpreterError> {
+    let resolved = if index < 0 {
+        len as i64 + index
+    } else {
+        index
+    };
+    if resolved < 0 || resolved >= len as i64 {
+        return Err(InterpreterError::IndexOutOfBounds { index, len });
+    }
+    Ok(resolved as usize)
+}
+
+pub(super) fn truthy(value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Null => false,
+        JsonValue::Bool(b) => *b,
+        JsonValue::Number(n) => n.as_f64().unwrap_or(0.0) != 0.0,
+        JsonValue::String(s) => !s.is_empty(),
+        JsonValue::Array(a) => !a.is_empty(),
+        JsonValue::Object(o) => !o.is_empty(),
+    }
+}
+
+fn concat(a: &JsonValue, b: &JsonValue) -> Result<JsonValue, InterpreterError> {
+    let (a, b) = (concat_operand(a)?, concat_operand(b)?);
+    Ok(JsonValue::String(format!("{a}{b}")))
+}
+
+fn concat_operand(value: &JsonValue) -> Result<String, InterpreterError> {
+    match value {
+        JsonValue::String(s) => Ok(s.clone()),
+        JsonValue::Number(n) => Ok(match n.as_f64() {
+            Some(f) if n.is_f64() => number(f).to_string(),
+            _ => n.to_string(),
+        }),
+        JsonValue::Bool(b) => Ok(b.to_string()),
+        other => Err(InterpreterError::TypeMismatch {
+            expected: "string, number or boolean".to_string(),
+            found: format!("{other} in Concat"),
+        }),
+    }
+}
+
+fn values_equal(a: &JsonValue, b: &JsonValue) -> bool {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(x), Some(y)) => x == y,
+        _ => a == b,
+    }
+}
+
+fn binary(op: &str, a: &JsonValue, b: &JsonValue) -> Result<JsonValue, InterpreterError> {
+    match op {
+        "Add" if a.is_string() || b.is_string() => concat(a, b),
+        "Equal" => Ok(JsonValue::Bool(values_equal(a, b))),
+        "NotEqual" => Ok(JsonValue::Bool(!values_equal(a, b))),
+        "LessThan" | "GreaterThan" | "LessEqual" | "GreaterEqual" => {
+            let ordering = match (a, b) {
+                (JsonValue::String(x), JsonValue::String(y)) => x.partial_cmp(y),
+                _ => as_number(a, op)?.partial_cmp(&as_number(b, op)?),
+            };
+            let Some(ordering) = ordering else {
+                return Ok(JsonValue::Bool(false));
+            };
+            Ok(JsonValue::Bool(match op {
+                "LessThan" => ordering.is_lt(),
+                "GreaterThan" => ordering.is_gt(),
+                "LessEqual" => ordering.is_le(),
+                _ => ordering.is_ge(),
+            }))
+        }
+        _ => {
+            let x = as_number(a, op)?;
+            let y = as_number(b, op)?;
+            let result = match op {
+                "Add" => x + y,
+                "Subtract" => x - y,
+                "Multiply" => x * y,
+                "Divide" if y == 0.0 => return Err(InterpreterError::DivisionByZero),
+                "Divide" => x / y,
+                "Modulo" if y == 0.0 => return Err(InterpreterError::DivisionByZero),
+                "Modulo" => x % y,
+                _ => return Err(InterpreterError::UnsupportedOpcode(op.to_string())),
+            };
+            Ok(number(result))
+        }
+    }
+}
+
+fn negate(value: &JsonValue) -> Result<JsonValue, InterpreterError> {
+    Ok(number(-as_number(value, "Negate")?))
+}
+
+fn length(value: &JsonValue) -> Result<JsonValue, InterpreterError> {
+    let len = match value {
+        JsonValue::Null => 0,
+        JsonValue::Array(items) => items.len(),
+        JsonValue::Object(map) => map.len(),
+        JsonValue::String(s) => s.chars().count(),
+        other => {
+            return Err(InterpreterError::TypeMismatch {
+                expected: "array, object or string".to_string(),
+                found: other.to_string(),
+            })
+        }
+    };
+    Ok(JsonValue::from(len))
+}
+
+fn index_value(object: &JsonValue, index: &JsonValue) -> Result<JsonValue, InterpreterError> {
+    Ok(match (object, index) {
+        (JsonValue::Array(items), JsonValue::Number(_)) => {
+            items[resolve_index(as_signed_index(index)?, items.len())?].clone()
+        }
+        (JsonValue::Object(map), JsonValue::String(key)) => {
+            map.get(key).cloned().unwrap_or(JsonValue::Null)
+        }
+        _ => JsonValue::Null,
+    })
+}
+
+fn call_ffi(
+    name: &str,
+    args: Vec<JsonValue>,
+    ffi_registry: &FfiRegistry,
+    permissions: &Value,
+) -> Result<JsonValue, InterpreterError> {
+    let function = ffi_registry
+        .get(name)
+        .ok_or_else(|| InterpreterError::FfiNotFound(name.to_string()))?;
+    let args: Vec<Value> = args.into_iter().map(Value::Json).collect();
+    let result = function(&args, permissions).map_err(|e| {
+        InterpreterError::RuntimeError(format!("FFI function '{name}' failed: {e}"))
+    })?;
+    Ok(result.into())
+}
@@ -11,21 +11,35 @@
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
 pub mod assembler;
+pub mod disassembler;
+pub mod gas;
 pub mod interpreter;
 pub mod jit;
+mod machine;
 pub mod profiler;
 pub mod vm;
 
 
 pub use assembler::BytecodeAssembler;
+pub use disassembler::{
+    disassemble, disassemble_to_string, DisassembledInstruction, DisassemblyError, Operand,
+};
+pub use gas::GasSchedule;
 pub use interpreter::Interpreter;
 pub use jit::{JitCache, JitCompiler, JittedFunction};
+pub use machine::{ExecutionMetrics, GasExhaustion, PendingAwait, ERROR_STATE_KEY};
 pub use profiler::ExecutionProfiler;
 pub use vm::VM;
 
+use crate::ast::{AstNode, Contract, Op, Path};
+use crate::clock::{system_clock, SharedClock};
+use futures::future::{BoxFuture, FutureExt};
+use machine::{merge_branch_writes, truthy, AsyncCall, Deadline, Env, Machine};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -152,6 +166,47 @@ pub type FfiFunction =
     Arc<dyn Fn(&[Value], &Value) -> Result<Value, InterpreterError> + Send + Sync>;
 pub type FfiRegistry = HashMap<String, FfiFunction>;
 
+pub type FfiFuture = BoxFuture<'static, Result<Value, InterpreterError>>;
+pub type FfiAsyncFunction = Arc<dyn Fn(Vec<Value>, Value) -> FfiFuture + Send + Sync>;
+pub type AsyncFfiRegistry = HashMap<String, FfiAsyncFunction>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnConflict {
+    #[default]
+    Error,
+    FirstWins,
+    LastWins,
+}
+
+pub trait FfiRegistryExt: Sized {
+    fn compose(registries: Vec<Self>, on_conflict: OnConflict) -> Result<Self, InterpreterError>;
+}
+
+impl FfiRegistryExt for FfiRegistry {
+    fn compose(
+        registries: Vec<FfiRegistry>,
+        on_conflict: OnConflict,
+    ) -> Result<FfiRegistry, InterpreterError> {
+        let mut composed = FfiRegistry::new();
+        for registry in registries {
+            let mut entries: Vec<(String, FfiFunction)> = registry.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            for (name, function) in entries {
+                match (composed.contains_key(&name), on_conflict) {
+                    (true, OnConflict::Error) => {
+                        return Err(InterpreterError::FfiConflict(name));
+                    }
+                    (true, OnConflict::FirstWins) => {}
+                    _ => {
+                        composed.insert(name, function);
+                    }
+                }
+            }
+        }
+        Ok(composed)
+    }
+}
+
 
 pub type ErgonomicFfiFunction = Arc<
     dyn Fn(&[serde_json::Value], &serde_json::Value) -> Result<serde_json::Value, InterpreterError>
@@ -181,6 +236,17 @@ where
 }
 
 
+pub fn create_async_ffi<F, Fut>(func: F) -> FfiAsyncFunction
+where
+    F: Fn(Vec<serde_json::Value>, serde_json::Value) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<serde_json::Value, InterpreterError>> + Send + 'static,
+{
+    Arc::new(move |args: Vec<Value>, state: Value| {
+        let json_args: Vec<serde_json::Value> = args.into_iter().map(Into::into).collect();
+        func(json_args, state.into()).map(|result| result.map(Value::Json)).boxed()
+    })
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ExecutionStatus {
     Running,
@@ -193,7 +259,63 @@ pub enum ExecutionStatus {
     Completed(Value),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl ExecutionStatus {
+    pub fn final_state(&self) -> Option<&Value> {
+        match self {
+            ExecutionStatus::Completed(state) => Some(state),
+            _ => None,
+        }
+    }
+
+    pub fn result(&self) -> Option<&serde_json::Value> {
+        match self.final_state()? {
+            Value::Json(state) => state.get("result"),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionReport {
+    pub status: ExecutionStatus,
+    pub final_state: serde_json::Value,
+    pub gas_used: u64,
+    pub blocks_executed: u64,
+}
+
+impl ExecutionReport {
+    pub fn status(&self) -> &ExecutionStatus {
+        &self.status
+    }
+
+    pub fn into_status(self) -> ExecutionStatus {
+        self.status
+    }
+
+    pub fn is_completed(&self) -> bool {
+        matches!(self.status, ExecutionStatus::Completed(_))
+    }
+
+    pub fn is_awaiting_input(&self) -> bool {
+        matches!(self.status, ExecutionStatus::AwaitingInput { .. })
+    }
+
+    pub fn output(&self, key: &str) -> Option<&serde_json::Value> {
+        self.final_state.get(key)
+    }
+
+    pub fn result(&self) -> Option<&serde_json::Value> {
+        self.output("result")
+    }
+}
+
+impl From<ExecutionReport> for ExecutionStatus {
+    fn from(report: ExecutionReport) -> Self {
+        report.status
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum OpCode {
     Push = 0,
@@ -225,6 +347,7 @@ pub enum OpCode {
     Negate = 26,
     CallFfi = 27,
     Halt = 28,
+    Concat = 29,
 }
 
 impl TryFrom<u8> for OpCode {
@@ -261,6 +384,7 @@ impl TryFrom<u8> for OpCode {
             26 => Ok(OpCode::Negate),
             27 => Ok(OpCode::CallFfi),
             28 => Ok(OpCode::Halt),
+            29 => Ok(OpCode::Concat),
             _ => Err(InterpreterError::InvalidBytecode(format!(
                 "Invalid opcode: {value}"
             ))),
@@ -292,262 +416,729 @@ pub enum InterpreterError {
     TypeMismatch { expected: String, found: String },
     #[error("FFI function not found: {0}")]
     FfiNotFound(String),
+    #[error("FFI function registered more than once: {0}")]
+    FfiConflict(String),
     #[error("Invalid assignment target: {0}")]
     InvalidAssignmentTarget(String),
     #[error("Internal VM Error: {0}")]
     InternalVMError(String),
+    #[error("Path '{path}' not found in block '{block_id}'")]
+    PathNotFound { path: String, block_id: String },
+    #[error("Index {index} is out of bounds for an array of length {len}")]
+    IndexOutOfBounds { index: i64, len: usize },
+    #[error(
+        "Out of gas in block '{}' after {} of {} gas",
+        .0.block_id,
+        .0.gas_used,
+        .0.gas_limit
+    )]
+    GasExhausted(Box<GasExhaustion>),
+    #[error("Await '{interaction_id}' timed out")]
+    AwaitTimedOut { interaction_id: String },
+    #[error("Execution timed out after {elapsed:?}")]
+    Timeout { elapsed: Duration },
+    #[error("Interpreter snapshot version {found} is not supported; expected {expected}")]
+    UnsupportedSnapshotVersion { found: u32, expected: u32 },
+    #[error("Invalid interpreter snapshot: {0}")]
+    InvalidSnapshot(String),
+}
+
+impl InterpreterError {
+    pub fn is_out_of_gas(&self) -> bool {
+        matches!(self, Self::OutOfGas | Self::GasExhausted(_))
+    }
+
+    pub fn gas_exhaustion(&self) -> Option<&GasExhaustion> {
+        match self {
+            Self::GasExhausted(details) => Some(details),
+            _ => None,
+        }
+    }
+}
+
+
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepStatus {
+    Running,
+    AwaitingInput,
+    Halted,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepOutcome {
+    pub status: StepStatus,
+    pub block_id: String,
+    pub instruction_pointer: usize,
+    pub gas_remaining: u64,
+}
 
 pub struct RemarkableInterpreter {
-    #[allow(dead_code)]
-    vm: VM,
-    contract: crate::ast::Contract,
-    #[allow(dead_code)]
-    ffi_registry: FfiRegistry,
+    contract: Contract,
+    contract_hash: String,
+    host: Host,
     state: InterpreterState,
-    pending_inputs: HashMap<String, serde_json::Value>,
+    program: Vec<Instr>,
+    span: Option<(tracing::Span, u64)>,
+    pending_call: Option<AsyncCall>,
+    max_duration: Option<Duration>,
+    deadline: Option<Deadline>,
+}
+
+struct Host {
+    ffi_registry: FfiRegistry,
+    async_ffi: AsyncFfiRegistry,
+    permissions: Value,
+    clock: SharedClock,
+}
+
+impl Host {
+    fn env<'a>(&'a self, deadline: Option<&'a Deadline>) -> Env<'a> {
+        Env {
+            ffi_registry: &self.ffi_registry,
+            async_ffi: &self.async_ffi,
+            permissions: &self.permissions,
+            clock: self.clock.as_ref(),
+            deadline,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
+enum Instr {
+    Charge(&'static str),
+    Eval(AstNode),
+    Branch {
+        condition: AstNode,
+        else_target: usize,
+    },
+    Jump(usize),
+    Bytecode {
+        bytecode: Vec<u8>,
+        output: Path,
+        else_target: Option<usize>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct InterpreterState {
-    current_block: String,
-    variables: HashMap<String, Value>,
-    execution_step: usize,
     session_id: String,
+    machine: Machine,
+    block: Option<String>,
+    queued: Option<String>,
+    pc: usize,
+    ip: usize,
+    stack: Vec<serde_json::Value>,
+    forks: Vec<Fork>,
+    resumed: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Fork {
+    fork_state: serde_json::Value,
+    merged: serde_json::Value,
+    branch: String,
+    pending: VecDeque<String>,
+    join_block: String,
+}
+
+#[derive(Deserialize)]
+struct SnapshotHeader {
+    version: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    version: u32,
+    contract_hash: String,
+    state: InterpreterState,
 }
 
 impl RemarkableInterpreter {
     pub fn new(
         gas_limit: u64,
-        contract: &crate::ast::Contract,
+        contract: &Contract,
         ffi_registry: FfiRegistry,
-    ) -> anyhow::Result<Self> {
-        let vm = VM::new(gas_limit)?;
-        let session_id = uuid::Uuid::new_v4().to_string();
+    ) -> Result<Self, InterpreterError> {
+        Self::with_gas_schedule(gas_limit, contract, ffi_registry, GasSchedule::default())
+    }
 
+    pub fn with_gas_schedule(
+        gas_limit: u64,
+        contract: &Contract,
+        ffi_registry: FfiRegistry,
+        gas_schedule: GasSchedule,
+    ) -> Result<Self, InterpreterError> {
+        let host = Host {
+            ffi_registry,
+            async_ffi: AsyncFfiRegistry::new(),
+            permissions: Value::Json(contract.permissions.clone()),
+            clock: system_clock(),
+        };
+        let machine = Machine::new(contract, gas_limit, gas_schedule, &host.env(None))?;
+        let contract_hash = contract
+            .content_hash()
+            .map_err(|e| InterpreterError::InvalidOperation(e.to_string()))?;
         Ok(Self {
-            vm,
             contract: contract.clone(),
-            ffi_registry,
+            contract_hash,
+            host,
             state: InterpreterState {
-                current_block: contract.start_block_id.clone(),
-                variables: HashMap::new(),
-                execution_step: 0,
-                session_id,
+                session_id: uuid::Uuid::new_v4().to_string(),
+                machine,
+                block: None,
+                queued: Some(contract.start_block_id.clone()),
+                pc: 0,
+                ip: 0,
+                stack: Vec::new(),
+                forks: Vec::new(),
+                resumed: BTreeSet::new(),
             },
-            pending_inputs: HashMap::new(),
+            program: Vec::new(),
+            span: None,
+            pending_call: None,
+            max_duration: None,
+            deadline: None,
         })
     }
 
-    pub async fn run(&mut self, contract: crate::ast::Contract) -> anyhow::Result<ExecutionStatus> {
-        
-        if contract.start_block_id != self.contract.start_block_id {
-            self.contract = contract;
-            self.state.current_block = self.contract.start_block_id.clone();
+    pub fn resume(
+        state: &[u8],
+        contract: &Contract,
+        ffi: FfiRegistry,
+    ) -> Result<Self, InterpreterError> {
+        let mut interpreter = Self::new(0, contract, ffi)?;
+        interpreter.restore(state)?;
+        Ok(interpreter)
+    }
+
+    pub fn restore(&mut self, state: &[u8]) -> Result<(), InterpreterError> {
+        let header: SnapshotHeader = serde_json::from_slice(state)
+            .map_err(|e| InterpreterError::InvalidSnapshot(e.to_string()))?;
+        if header.version != SNAPSHOT_VERSION {
+            return Err(InterpreterError::UnsupportedSnapshotVersion {
+                found: header.version,
+                expected: SNAPSHOT_VERSION,
+            });
+        }
+        let snapshot: Snapshot = serde_json::from_slice(state)
+            .map_err(|e| InterpreterError::InvalidSnapshot(e.to_string()))?;
+        if snapshot.contract_hash != self.contract_hash {
+            return Err(InterpreterError::InvalidSnapshot(
+                "snapshot was taken from a different contract".to_string(),
+            ));
         }
+        self.program = match &snapshot.state.block {
+            Some(block_id) => self.compile(block_id)?,
+            None => Vec::new(),
+        };
+        self.state = snapshot.state;
+        self.span = None;
+        self.pending_call = None;
+        Ok(())
+    }
 
-        
-        self.execute_workflow().await
+    pub fn serialize_state(&self) -> Result<Vec<u8>, InterpreterError> {
+        if let Some((name, _)) = &self.pending_call {
+            return Err(InterpreterError::InvalidOperation(format!(
+                "cannot checkpoint while async FFI call '{name}' is in flight"
+            )));
+        }
+        let snapshot = Snapshot {
+            version: SNAPSHOT_VERSION,
+            contract_hash: self.contract_hash.clone(),
+            state: self.state.clone(),
+        };
+        serde_json::to_vec(&snapshot)
+            .map_err(|e| InterpreterError::InvalidSnapshot(e.to_string()))
     }
 
-    async fn execute_workflow(&mut self) -> anyhow::Result<ExecutionStatus> {
-        
-        match self.state.execution_step {
-            0 => {
-                
-                if self.contract.start_block_id.contains("AWAIT") || self.should_await_input() {
-                    self.state.execution_step += 1;
-                    let (interaction_id, agent_id, prompt) = self
-                        .extract_current_await_fields()
-                        .or_else(|| self.extract_any_await_fields())
-                        .unwrap_or_else(|| {
-                            (
-                                format!("interaction_{}", self.state.execution_step),
-                                
-                                self.extract_any_await_fields()
-                                    .map(|(_, aid, _)| aid)
-                                    .unwrap_or_else(|| {
-                                        format!("agent_{}", self.state.execution_step)
-                                    }),
-                                self.extract_prompt(),
-                            )
-                        });
-                    Ok(ExecutionStatus::AwaitingInput {
-                        session_id: self.state.session_id.clone(),
-                        interaction_id,
-                        agent_id,
-                        prompt,
-                    })
-                } else {
-                    
-                    self.state.execution_step = 999;
-                    Ok(ExecutionStatus::Completed(Value::String(
-                        "completed".to_string(),
-                    )))
-                }
-            }
-            1..=10 => {
-                
-                if self.should_continue_execution() {
-                    self.state.execution_step += 1;
-                    if self.state.execution_step > 3 {
-                        
-                        Ok(ExecutionStatus::Completed(Value::String(
-                            "workflow_completed".to_string(),
-                        )))
-                    } else {
-                        let (interaction_id, agent_id, prompt) = self
-                            .extract_current_await_fields()
-                            .or_else(|| self.extract_any_await_fields())
-                            .unwrap_or_else(|| {
-                                (
-                                    format!("interaction_{}", self.state.execution_step),
-                                    self.extract_any_await_fields()
-                                        .map(|(_, aid, _)| aid)
-                                        .unwrap_or_else(|| {
-                                            format!("agent_{}", self.state.execution_step)
-                                        }),
-                                    self.extract_prompt(),
-                                )
-                            });
-                        Ok(ExecutionStatus::AwaitingInput {
-                            session_id: self.state.session_id.clone(),
-                            interaction_id,
-                            agent_id,
-                            prompt,
-                        })
-                    }
-                } else {
-                    Ok(ExecutionStatus::Running)
-                }
-            }
-            _ => {
-                
-                Ok(ExecutionStatus::Completed(Value::String(
-                    "final_result".to_string(),
-                )))
+    pub fn session_id(&self) -> &str {
+        &self.state.session_id
+    }
+
+    pub fn pending_await(&self) -> Option<&str> {
+        self.state
+            .machine
+            .suspended
+            .as_ref()
+            .map(|pending| pending.interaction_id.as_str())
+    }
+
+    pub fn has_resumed(&self, interaction_id: &str) -> bool {
+        self.state.resumed.contains(interaction_id)
+    }
+
+    pub fn set_max_duration(&mut self, max_duration: Option<Duration>) {
+        self.max_duration = max_duration;
+    }
+
+    pub fn max_duration(&self) -> Option<Duration> {
+        self.max_duration
+    }
+
+    pub fn set_gas_schedule(&mut self, gas_schedule: GasSchedule) {
+        self.state.machine.gas_schedule = gas_schedule;
+    }
+
+    pub fn gas_schedule(&self) -> &GasSchedule {
+        &self.state.machine.gas_schedule
+    }
+
+    pub fn gas_used(&self) -> u64 {
+        self.state.machine.gas_used
+    }
+
+    pub fn gas_remaining(&self) -> u64 {
+        self.state.machine.gas_remaining()
+    }
+
+    pub fn add_gas(&mut self, amount: u64) {
+        let machine = &mut self.state.machine;
+        machine.gas_limit = machine.gas_limit.saturating_add(amount);
+    }
+
+    pub fn metrics(&self) -> &ExecutionMetrics {
+        &self.state.machine.metrics
+    }
+
+    pub fn set_strict_paths(&mut self, strict: bool) {
+        self.state.machine.strict_paths = strict;
+    }
+
+    pub fn strict_paths(&self) -> bool {
+        self.state.machine.strict_paths
+    }
+
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.host.clock = clock;
+    }
+
+    pub fn clock(&self) -> &SharedClock {
+        &self.host.clock
+    }
+
+    pub fn register_async_ffi(&mut self, name: impl Into<String>, function: FfiAsyncFunction) {
+        self.host.async_ffi.insert(name.into(), function);
+    }
+
+    pub async fn run(&mut self, contract: Contract) -> anyhow::Result<ExecutionStatus> {
+        if contract.content_hash()? != self.contract_hash {
+            return Err(InterpreterError::InvalidOperation(
+                "run was given a different contract than the interpreter was built with"
+                    .to_string(),
+            )
+            .into());
+        }
+        Ok(self.execute().await?)
+    }
+
+    pub async fn execute(&mut self) -> Result<ExecutionStatus, InterpreterError> {
+        self.deadline = self.max_duration.map(Deadline::start);
+        let result = self.drive_async().await;
+        self.deadline = None;
+        result
+    }
+
+    pub fn run_until_paused(&mut self) -> Result<ExecutionStatus, InterpreterError> {
+        self.deadline = self.max_duration.map(Deadline::start);
+        let result = self.drive();
+        self.deadline = None;
+        result
+    }
+
+    async fn drive_async(&mut self) -> Result<ExecutionStatus, InterpreterError> {
+        while self.step_async().await?.status == StepStatus::Running {}
+        Ok(self.status())
+    }
+
+    fn drive(&mut self) -> Result<ExecutionStatus, InterpreterError> {
+        while self.step()?.status == StepStatus::Running {}
+        Ok(self.status())
+    }
+
+    pub fn status(&self) -> ExecutionStatus {
+        let machine = &self.state.machine;
+        match &machine.suspended {
+            Some(pending) => ExecutionStatus::AwaitingInput {
+                session_id: self.state.session_id.clone(),
+                interaction_id: pending.interaction_id.clone(),
+                agent_id: pending.agent_id.clone(),
+                prompt: Value::Json(pending.prompt.clone()),
+            },
+            None if self.is_finished() => ExecutionStatus::Completed(machine.state.clone()),
+            None => ExecutionStatus::Running,
+        }
+    }
+
+    pub fn report(&self, status: ExecutionStatus) -> ExecutionReport {
+        let machine = &self.state.machine;
+        ExecutionReport {
+            status,
+            final_state: machine.root().clone(),
+            gas_used: machine.gas_used,
+            blocks_executed: machine.metrics.blocks_executed,
+        }
+    }
+
+    pub async fn step_async(&mut self) -> Result<StepOutcome, InterpreterError> {
+        let outcome = self.step()?;
+        let Some((name, args)) = self.pending_call.take() else {
+            return Ok(outcome);
+        };
+        let function = self
+            .host
+            .async_ffi
+            .get(&name)
+            .cloned()
+            .ok_or_else(|| InterpreterError::FfiNotFound(name.clone()))?;
+        let args = args.into_iter().map(Value::Json).collect();
+        let call = function(args, self.host.permissions.clone());
+        let result = match self.deadline {
+            Some(deadline) => tokio::time::timeout(deadline.remaining(), call)
+                .await
+                .map_err(|_| InterpreterError::Timeout {
+                    elapsed: deadline.elapsed(),
+                })?,
+            None => call.await,
+        };
+        if let Some(deadline) = &self.deadline {
+            deadline.check()?;
+        }
+        let span = self.block_span();
+        let _entered = span.enter();
+        let completed = result.and_then(|value| {
+            self.state.stack.push(value.into());
+            let env = self.host.env(self.deadline.as_ref());
+            finish_bytecode(&self.program, &mut self.state, &env)
+        });
+        let status = self.settle(completed)?;
+        Ok(self.outcome(status))
+    }
+
+    pub fn step(&mut self) -> Result<StepOutcome, InterpreterError> {
+        if let Some((name, _)) = &self.pending_call {
+            return Err(InterpreterError::InvalidOperation(format!(
+                "async FFI call '{name}' is pending; drive it with step_async"
+            )));
+        }
+        if self.state.machine.suspended.is_some() {
+            return Ok(self.outcome(StepStatus::AwaitingInput));
+        }
+        if let Some(deadline) = &self.deadline {
+            deadline.check()?;
+        }
+        self.load_queued()?;
+        if self.state.block.is_none() {
+            return Ok(self.outcome(StepStatus::Halted));
+        }
+        let span = self.block_span();
+        let _entered = span.enter();
+        let executed = self.execute_instruction();
+        let status = self.settle(executed)?;
+        Ok(self.outcome(status))
+    }
+
+    pub fn current_state(&self) -> &Value {
+        &self.state.machine.state
+    }
+
+    pub fn state(&self) -> &serde_json::Value {
+        self.state.machine.root()
+    }
+
+    pub fn stack_snapshot(&self) -> Vec<Value> {
+        self.state.stack.iter().cloned().map(Value::Json).collect()
+    }
+
+    pub fn resume_with_input(
+        &mut self,
+        interaction_id: &str,
+        input: serde_json::Value,
+    ) -> Result<(), InterpreterError> {
+        let is_pending = self.pending_await() == Some(interaction_id);
+        if !is_pending {
+            if self.has_resumed(interaction_id) {
+                return Ok(());
             }
+            return Err(InterpreterError::InvalidOperation(format!(
+                "No pending await for interaction '{interaction_id}'"
+            )));
+        }
+        if self.expire_pending_await()? {
+            return Err(InterpreterError::AwaitTimedOut {
+                interaction_id: interaction_id.to_string(),
+            });
         }
+        let Some(pending) = self.state.machine.suspended.take() else {
+            return Ok(());
+        };
+        if let Some(state_key) = &pending.state_key {
+            self.state.machine.write_state_key(state_key, input)?;
+        }
+        self.state.queued = pending.next_block;
+        self.state.resumed.insert(interaction_id.to_string());
+        Ok(())
     }
 
-    fn should_await_input(&self) -> bool {
-        
-        
-        true 
+    pub fn expire_pending_await(&mut self) -> Result<bool, InterpreterError> {
+        let now = self.host.clock.now();
+        let machine = &mut self.state.machine;
+        let timed_out = machine
+            .suspended
+            .as_ref()
+            .and_then(|pending| pending.deadline)
+            .is_some_and(|deadline| now >= deadline);
+        if !timed_out {
+            return Ok(false);
+        }
+        let Some(pending) = machine.suspended.take() else {
+            return Ok(false);
+        };
+        tracing::warn!(
+            interaction_id = %pending.interaction_id,
+            gas_used = machine.gas_used,
+            "await timed out"
+        );
+        let error = InterpreterError::AwaitTimedOut {
+            interaction_id: pending.interaction_id,
+        };
+        let Some(catch_block_id) = machine.error_handlers.pop() else {
+            return Err(error);
+        };
+        machine.record_error(&error)?;
+        self.state.queued = Some(catch_block_id);
+        Ok(true)
     }
 
-    fn should_continue_execution(&self) -> bool {
-        
-        true
+    fn is_finished(&self) -> bool {
+        self.state.block.is_none() && self.state.queued.is_none()
     }
 
-    fn extract_prompt(&self) -> Value {
-        
-        if let Some((_, _, prompt)) = self.extract_current_await_fields() {
-            return prompt;
+    fn outcome(&self, status: StepStatus) -> StepOutcome {
+        StepOutcome {
+            status,
+            block_id: self.state.machine.current_block.clone(),
+            instruction_pointer: self.state.ip,
+            gas_remaining: self.state.machine.gas_remaining(),
         }
-        if let Some((_, _, prompt)) = self.extract_any_await_fields() {
-            return prompt;
+    }
+
+    fn compile(&self, block_id: &str) -> Result<Vec<Instr>, InterpreterError> {
+        let node = self.contract.blocks.get(block_id).ok_or_else(|| {
+            InterpreterError::RuntimeError(format!("Block '{block_id}' not found"))
+        })?;
+        let mut program = Vec::new();
+        compile_node(node, &mut program);
+        Ok(program)
+    }
+
+    fn load_queued(&mut self) -> Result<(), InterpreterError> {
+        if self.state.block.is_some() {
+            return Ok(());
         }
-        
-        Value::String(format!(
-            "Please provide input for step {}",
-            self.state.execution_step
-        ))
+        let Some(block_id) = self.state.queued.take() else {
+            return Ok(());
+        };
+        self.program = match self.compile(&block_id) {
+            Ok(program) => program,
+            Err(error) => {
+                self.state.queued = Some(block_id);
+                return Err(error);
+            }
+        };
+        let machine = &mut self.state.machine;
+        machine.metrics.blocks_executed += 1;
+        machine.metrics.block_path.push(block_id.clone());
+        machine.next_block = None;
+        machine.current_block = block_id.clone();
+        self.state.block = Some(block_id);
+        self.state.pc = 0;
+        self.state.ip = 0;
+        self.state.stack.clear();
+        self.span = None;
+        Ok(())
     }
 
-    fn extract_current_await_fields(&self) -> Option<(String, String, Value)> {
-        
-        
-        let node = self.contract.blocks.get(&self.state.current_block)?;
-        
-        fn from_node(node: &crate::ast::AstNode) -> Option<(String, String, Value)> {
-            match &node.op {
-                crate::ast::Op::Await {
-                    interaction_id,
-                    agent_id,
-                    prompt,
-                    ..
-                } => {
-                    
-                    let prompt_value = match prompt.as_deref() {
-                        Some(inner) => match &inner.op {
-                            crate::ast::Op::Literal(crate::ast::Literal::String(s)) => {
-                                Value::String(s.clone())
-                            }
-                            
-                            _ => Value::String("Provide input".to_string()),
-                        },
-                        None => Value::String("Provide input".to_string()),
-                    };
-                    Some((interaction_id.clone(), agent_id.clone(), prompt_value))
-                }
-                crate::ast::Op::Sequence(children) => {
-                    for child in children {
-                        if let Some(found) = from_node(child) {
-                            return Some(found);
-                        }
+    fn block_span(&mut self) -> tracing::Span {
+        let machine = &self.state.machine;
+        let (span, _) = self.span.get_or_insert_with(|| {
+            let span = tracing::info_span!(
+                "block",
+                block_id = %machine.current_block,
+                session_id = %self.state.session_id,
+                gas_before = machine.gas_used,
+                gas_used = tracing::field::Empty,
+            );
+            (span, machine.gas_used)
+        });
+        span.clone()
+    }
+
+    fn execute_instruction(&mut self) -> Result<(), InterpreterError> {
+        let env = self.host.env(self.deadline.as_ref());
+        let state = &mut self.state;
+        while let Some(Instr::Charge(name)) = self.program.get(state.pc) {
+            state.machine.charge_node(name)?;
+            state.pc += 1;
+        }
+        match self.program.get(state.pc) {
+            None | Some(Instr::Charge(_)) => {}
+            Some(Instr::Eval(node)) => {
+                state.machine.eval(node, &env)?;
+                state.pc += 1;
+            }
+            Some(Instr::Branch {
+                condition,
+                else_target,
+            }) => {
+                let condition = state.machine.eval(condition, &env)?;
+                state.pc = if truthy(&condition) {
+                    state.pc + 1
+                } else {
+                    *else_target
+                };
+            }
+            Some(Instr::Jump(target)) => state.pc = *target,
+            Some(Instr::Bytecode { bytecode, .. }) => {
+                if state.ip < bytecode.len() {
+                    let call = state.machine.execute_opcode(
+                        bytecode,
+                        &mut state.ip,
+                        &mut state.stack,
+                        &env,
+                    )?;
+                    if call.is_some() {
+                        self.pending_call = call;
+                        return Ok(());
                     }
-                    None
                 }
-                _ => None,
+                finish_bytecode(&self.program, state, &env)?;
             }
         }
-        from_node(node)
+        Ok(())
     }
 
-    
-    fn extract_any_await_fields(&self) -> Option<(String, String, Value)> {
-        fn from_node(node: &crate::ast::AstNode) -> Option<(String, String, Value)> {
-            match &node.op {
-                crate::ast::Op::Await {
-                    interaction_id,
-                    agent_id,
-                    prompt,
-                    ..
-                } => {
-                    let prompt_value = match prompt.as_deref() {
-                        Some(inner) => match &inner.op {
-                            crate::ast::Op::Literal(crate::ast::Literal::String(s)) => {
-                                Value::String(s.clone())
-                            }
-                            _ => Value::String("Provide input".to_string()),
-                        },
-                        None => Value::String("Provide input".to_string()),
-                    };
-                    Some((interaction_id.clone(), agent_id.clone(), prompt_value))
-                }
-                crate::ast::Op::Sequence(children) => {
-                    for child in children {
-                        if let Some(found) = from_node(child) {
-                            return Some(found);
-                        }
-                    }
-                    None
-                }
-                _ => None,
+    fn settle(&mut self, executed: Result<(), InterpreterError>) -> Result<StepStatus, InterpreterError> {
+        let recovered = match executed {
+            Ok(()) => false,
+            Err(error) => {
+                self.recover(error)?;
+                true
             }
+        };
+        if self.pending_call.is_some() {
+            return Ok(StepStatus::Running);
+        }
+        let block_done = recovered
+            || self.state.machine.halted()
+            || self.state.pc >= self.program.len();
+        if !block_done {
+            return Ok(StepStatus::Running);
         }
-        for node in self.contract.blocks.values() {
-            if let Some(found) = from_node(node) {
-                return Some(found);
+        self.close_block();
+        self.advance()
+    }
+
+    fn recover(&mut self, error: InterpreterError) -> Result<(), InterpreterError> {
+        let machine = &mut self.state.machine;
+        match error {
+            InterpreterError::OutOfGas => {
+                tracing::error!(
+                    gas_used = machine.gas_used,
+                    gas_limit = machine.gas_limit,
+                    "out of gas"
+                );
+                return Err(machine.exhausted());
             }
+            InterpreterError::Timeout { .. } => return Err(error),
+            _ => {}
         }
-        None
+        let Some(catch_block_id) = machine.error_handlers.pop() else {
+            tracing::warn!(
+                error = %error,
+                gas_used = machine.gas_used,
+                "block failed"
+            );
+            return Err(error);
+        };
+        tracing::warn!(
+            error = %error,
+            catch_block_id = %catch_block_id,
+            gas_used = machine.gas_used,
+            "block failed, jumping to catch block"
+        );
+        machine.record_error(&error)?;
+        machine.terminated = false;
+        machine.suspended = None;
+        machine.fork = None;
+        machine.next_block = Some(catch_block_id);
+        self.pending_call = None;
+        Ok(())
     }
 
-    
-    pub fn resume_with_input(&mut self, interaction_id: &str, input: serde_json::Value) {
-        
-        self.pending_inputs
-            .insert(interaction_id.to_string(), input.clone());
+    fn close_block(&mut self) {
+        if let Some((span, gas_before)) = self.span.take() {
+            span.record("gas_used", self.state.machine.gas_used - gas_before);
+        }
+        self.state.block = None;
+        self.state.pc = 0;
+        self.state.ip = 0;
+        self.state.stack.clear();
+        self.program.clear();
+    }
 
-        
-        let runtime_value = self.json_to_runtime_value(&input);
-        self.state
-            .variables
-            .insert(format!("input_{interaction_id}"), runtime_value);
+    fn advance(&mut self) -> Result<StepStatus, InterpreterError> {
+        let machine = &mut self.state.machine;
+        if machine.suspended.is_some() {
+            if let Some(fork) = self.state.forks.last() {
+                machine.suspended = None;
+                return Err(InterpreterError::InvalidOperation(format!(
+                    "Parallel branch '{}' cannot await input",
+                    fork.branch
+                )));
+            }
+            return Ok(StepStatus::AwaitingInput);
+        }
+        let mut next = machine.next_block.take();
+        if std::mem::take(&mut machine.terminated) {
+            next = None;
+        } else if let Some((branches, join_block)) = machine.fork.take() {
+            let fork_state = machine.root().clone();
+            self.state.forks.push(Fork {
+                merged: fork_state.clone(),
+                fork_state,
+                branch: String::new(),
+                pending: branches.into(),
+                join_block,
+            });
+            next = None;
+        }
+        while let Some(fork) = self.state.forks.last_mut() {
+            if next.as_ref().is_some_and(|id| *id != fork.join_block) {
+                break;
+            }
+            merge_branch_writes(&mut fork.merged, &fork.fork_state, machine.root());
+            match fork.pending.pop_front() {
+                Some(branch) => {
+                    *machine.root_mut() = fork.fork_state.clone();
+                    fork.branch = branch.clone();
+                    next = Some(branch);
+                }
+                None => {
+                    let fork = self.state.forks.pop().expect("fork frame is present");
+                    *machine.root_mut() = fork.merged;
+                    next = Some(fork.join_block);
+                }
+            }
+        }
+        let Some(next) = next else {
+            return Ok(StepStatus::Halted);
+        };
+        self.state.queued = Some(next);
+        self.load_queued()?;
+        Ok(StepStatus::Running)
     }
 
     pub fn json_to_runtime_value(&self, json_value: &serde_json::Value) -> Value {
@@ -555,16 +1146,114 @@ impl RemarkableInterpreter {
     }
 
     pub fn runtime_to_json_value(&self, runtime_value: &Value) -> serde_json::Value {
-        match runtime_value {
-            Value::Integer(i) => serde_json::Value::Number(serde_json::Number::from(*i)),
-            Value::Boolean(b) => serde_json::Value::Bool(*b),
-            Value::String(s) => serde_json::Value::String(s.clone()),
-            Value::Null => serde_json::Value::Null,
-            Value::Json(j) => j.clone(),
+        runtime_to_json_value(runtime_value)
+    }
+}
+
+fn compile_node(node: &AstNode, program: &mut Vec<Instr>) {
+    match &node.op {
+        Op::Sequence(children) => {
+            program.push(Instr::Charge("Sequence"));
+            for child in children {
+                compile_node(child, program);
+            }
+        }
+        Op::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => {
+            program.push(Instr::Charge("If"));
+            let branch_at = compile_condition(condition, program);
+            compile_node(then_branch, program);
+            let Some(else_branch) = else_branch else {
+                let end = program.len();
+                patch_else(program, branch_at, end);
+                return;
+            };
+            let jump_at = program.len();
+            program.push(Instr::Jump(0));
+            let else_at = program.len();
+            patch_else(program, branch_at, else_at);
+            compile_node(else_branch, program);
+            program[jump_at] = Instr::Jump(program.len());
+        }
+        Op::Evaluate {
+            bytecode,
+            output_path,
+        } => {
+            program.push(Instr::Charge("Evaluate"));
+            program.push(Instr::Bytecode {
+                bytecode: bytecode.clone(),
+                output: output_path.clone(),
+                else_target: None,
+            });
+        }
+        _ => program.push(Instr::Eval(node.clone())),
+    }
+}
+
+fn compile_condition(condition: &AstNode, program: &mut Vec<Instr>) -> usize {
+    match &condition.op {
+        Op::Evaluate {
+            bytecode,
+            output_path,
+        } => {
+            program.push(Instr::Charge("Evaluate"));
+            program.push(Instr::Bytecode {
+                bytecode: bytecode.clone(),
+                output: output_path.clone(),
+                else_target: Some(0),
+            });
         }
+        _ => program.push(Instr::Branch {
+            condition: condition.clone(),
+            else_target: 0,
+        }),
+    }
+    program.len() - 1
+}
+
+fn patch_else(program: &mut [Instr], at: usize, target: usize) {
+    match &mut program[at] {
+        Instr::Branch { else_target, .. } => *else_target = target,
+        Instr::Bytecode { else_target, .. } => *else_target = Some(target),
+        _ => {}
     }
 }
 
+fn finish_bytecode(
+    program: &[Instr],
+    state: &mut InterpreterState,
+    env: &Env<'_>,
+) -> Result<(), InterpreterError> {
+    let Some(Instr::Bytecode {
+        bytecode,
+        output,
+        else_target,
+    }) = program.get(state.pc)
+    else {
+        return Ok(());
+    };
+    if state.ip < bytecode.len() {
+        return Ok(());
+    }
+    let keys = match state.stack.last() {
+        Some(_) => Some(state.machine.resolve_path(&output.0, env)?),
+        None => None,
+    };
+    let result = state.stack.pop();
+    state.stack.clear();
+    state.ip = 0;
+    if let (Some(keys), Some(value)) = (keys, &result) {
+        state.machine.write_path(&keys, value.clone())?;
+    }
+    state.pc = match else_target {
+        Some(target) if !result.as_ref().is_some_and(truthy) => *target,
+        _ => state.pc + 1,
+    };
+    Ok(())
+}
 
 pub fn json_to_runtime_value(json_value: &serde_json::Value) -> Value {
     Value::Json(json_value.clone())
@@ -0,0 +1,201 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use crate::flows::definition::FlowDefinition;
+use crate::orchestration::{
+    OrchestrationConfig, OrchestrationCoordinator, OrchestrationError, OrchestrationResult,
+};
+use crate::runtime::ExecutionStatus;
+use axum::extract::{DefaultBodyLimit, Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+pub const DEFAULT_MAX_BODY_BYTES: usize = 1024 * 1024;
+pub const DEFAULT_MAX_GAS: u64 = 1_000_000;
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub max_body_bytes: usize,
+    pub max_gas: u64,
+    pub orchestration: OrchestrationConfig,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            max_gas: DEFAULT_MAX_GAS,
+            orchestration: OrchestrationConfig::default(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SleetServer {
+    coordinator: Arc<OrchestrationCoordinator>,
+    awaiting: Arc<Mutex<HashMap<String, String>>>,
+    max_body_bytes: usize,
+    max_gas: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct GasQuery {
+    gas_limit: Option<u64>,
+}
+
+#[derive(Debug)]
+pub struct ServerError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ServerError {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl From<OrchestrationError> for ServerError {
+    fn from(error: OrchestrationError) -> Self {
+        let status = match &error {
+            OrchestrationError::ValidationError(_) | OrchestrationError::ConfigurationError(_) => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            OrchestrationError::SessionError(_) => StatusCode::NOT_FOUND,
+            OrchestrationError::TimeoutError(_) => StatusCode::GATEWAY_TIMEOUT,
+            OrchestrationError::ResourceAllocationError(_) => StatusCode::SERVICE_UNAVAILABLE,
+            OrchestrationError::Cancelled(_) => StatusCode::CONFLICT,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        Self::new(status, error.to_string())
+    }
+}
+
+impl IntoResponse for ServerError {
+    fn into_response(self) -> Response {
+        (self.status, Json(json!({ "error": self.message }))).into_response()
+    }
+}
+
+impl SleetServer {
+    pub async fn new(config: ServerConfig) -> OrchestrationResult<Self> {
+        let coordinator = OrchestrationCoordinator::new(config.orchestration).await?;
+        Ok(Self::with_coordinator(coordinator, config.max_body_bytes, config.max_gas))
+    }
+
+    pub fn with_coordinator(
+        coordinator: OrchestrationCoordinator,
+        max_body_bytes: usize,
+        max_gas: u64,
+    ) -> Self {
+        Self {
+            coordinator: Arc::new(coordinator),
+            awaiting: Arc::new(Mutex::new(HashMap::new())),
+            max_body_bytes,
+            max_gas,
+        }
+    }
+
+    pub fn router(self) -> Router {
+        let max_body_bytes = self.max_body_bytes;
+        Router::new()
+            .route("/v1/flows", post(submit_flow))
+            .route(
+                "/v1/sessions/{session_id}/interactions/{interaction_id}",
+                post(resume_interaction),
+            )
+            .layer(DefaultBodyLimit::max(max_body_bytes))
+            .with_state(self)
+    }
+
+    async fn track(&self, status: &ExecutionStatus) {
+        if let ExecutionStatus::AwaitingInput {
+            session_id,
+            interaction_id,
+            ..
+        } = status
+        {
+            let mut awaiting = self.awaiting.lock().await;
+            awaiting.insert(session_id.clone(), interaction_id.clone());
+        }
+    }
+}
+
+async fn submit_flow(
+    State(server): State<SleetServer>,
+    Query(query): Query<GasQuery>,
+    Json(flow): Json<FlowDefinition>,
+) -> Result<Json<ExecutionStatus>, ServerError> {
+    let gas_limit = query.gas_limit.unwrap_or(server.max_gas);
+    if gas_limit > server.max_gas {
+        return Err(ServerError::new(
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Requested gas limit {gas_limit} exceeds the server maximum of {}",
+                server.max_gas
+            ),
+        ));
+    }
+
+    let status = server
+        .coordinator
+        .execute_flow(flow.into(), Some(gas_limit))
+        .await?;
+    server.track(&status).await;
+    Ok(Json(status))
+}
+
+async fn resume_interaction(
+    State(server): State<SleetServer>,
+    Path((session_id, interaction_id)): Path<(String, String)>,
+    Json(input): Json<Value>,
+) -> Result<Json<ExecutionStatus>, ServerError> {
+    {
+        let mut awaiting = server.awaiting.lock().await;
+        if awaiting.get(&session_id) != Some(&interaction_id) {
+            return Err(ServerError::new(
+                StatusCode::NOT_FOUND,
+                format!("Session {session_id} is not awaiting interaction {interaction_id}"),
+            ));
+        }
+        awaiting.remove(&session_id);
+    }
+
+    let status = match server.coordinator.resume_session(&session_id, input).await {
+        Ok(status) => status,
+        Err(error) => {
+            let mut awaiting = server.awaiting.lock().await;
+            awaiting.insert(session_id, interaction_id);
+            return Err(error.into());
+        }
+    };
+    server.track(&status).await;
+    Ok(Json(status))
+}
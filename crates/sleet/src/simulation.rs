@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use crate::flows::definition::FlowDefinition;
+use crate::runtime::{
+    create_ergonomic_ffi, ExecutionStatus, FfiRegistry, GasSchedule, InterpreterError,
+    RemarkableInterpreter,
+};
+use crate::transpiler::{FlowTranspiler, TranspilerError};
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+pub const DEFAULT_SIMULATION_GAS: u64 = 1_000_000;
+
+#[derive(Error, Debug)]
+pub enum SimulationError {
+    #[error("Transpilation failed: {0}")]
+    Transpile(#[from] TranspilerError),
+    #[error("Contract conversion failed: {0}")]
+    Conversion(String),
+    #[error("Simulation failed after visiting {path:?}: {source}")]
+    Execution {
+        path: Vec<String>,
+        #[source]
+        source: InterpreterError,
+    },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FfiStubRegistry {
+    stubs: BTreeMap<String, Vec<JsonValue>>,
+}
+
+impl FfiStubRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn stub(&mut self, name: impl Into<String>, output: JsonValue) -> &mut Self {
+        self.stubs.insert(name.into(), vec![output]);
+        self
+    }
+
+    pub fn stub_sequence(
+        &mut self,
+        name: impl Into<String>,
+        outputs: Vec<JsonValue>,
+    ) -> &mut Self {
+        self.stubs.insert(name.into(), outputs);
+        self
+    }
+
+    pub fn is_stubbed(&self, name: &str) -> bool {
+        self.stubs.contains_key(name)
+    }
+
+    fn build(&self, calls: &Arc<Mutex<Vec<StubbedCall>>>) -> FfiRegistry {
+        self.stubs
+            .iter()
+            .map(|(name, outputs)| {
+                let name = name.clone();
+                let outputs = outputs.clone();
+                let cursor = AtomicUsize::new(0);
+                let calls = Arc::clone(calls);
+                let recorded_name = name.clone();
+                let function = create_ergonomic_ffi(move |args, _permissions| {
+                    let index = cursor.fetch_add(1, Ordering::Relaxed);
+                    let output = outputs
+                        .get(index)
+                        .or_else(|| outputs.last())
+                        .cloned()
+                        .unwrap_or(JsonValue::Null);
+                    calls
+                        .lock()
+                        .map_err(|e| InterpreterError::InternalVMError(e.to_string()))?
+                        .push(StubbedCall {
+                            name: recorded_name.clone(),
+                            args: args.to_vec(),
+                            output: output.clone(),
+                        });
+                    Ok(output)
+                });
+                (name, function)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StubbedCall {
+    pub name: String,
+    pub args: Vec<JsonValue>,
+    pub output: JsonValue,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimulationOutcome {
+    Completed,
+    AwaitingInput { interaction_id: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationReport {
+    pub final_state: JsonValue,
+    pub path: Vec<String>,
+    pub ffi_calls: Vec<StubbedCall>,
+    pub gas_used: u64,
+    pub outcome: SimulationOutcome,
+}
+
+impl SimulationReport {
+    pub fn visited(&self, block_id: &str) -> bool {
+        self.path.iter().any(|id| id == block_id)
+    }
+}
+
+pub fn simulate(
+    flow_def: &FlowDefinition,
+    initial_state: JsonValue,
+    stubs: &FfiStubRegistry,
+) -> Result<SimulationReport, SimulationError> {
+    simulate_with_gas(flow_def, initial_state, stubs, DEFAULT_SIMULATION_GAS)
+}
+
+pub fn simulate_with_gas(
+    flow_def: &FlowDefinition,
+    initial_state: JsonValue,
+    stubs: &FfiStubRegistry,
+    gas_limit: u64,
+) -> Result<SimulationReport, SimulationError> {
+    simulate_with_schedule(
+        flow_def,
+        initial_state,
+        stubs,
+        gas_limit,
+        GasSchedule::default(),
+    )
+}
+
+pub fn simulate_with_schedule(
+    flow_def: &FlowDefinition,
+    initial_state: JsonValue,
+    stubs: &FfiStubRegistry,
+    gas_limit: u64,
+    gas_schedule: GasSchedule,
+) -> Result<SimulationReport, SimulationError> {
+    let mut flow_def = flow_def.clone();
+    flow_def.set_initial_state(initial_state);
+    let orchestration_contract = FlowTranspiler::transpile(&flow_def)?;
+    let contract = crate::convert_contract(orchestration_contract)
+        .map_err(|e| SimulationError::Conversion(e.to_string()))?;
+
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let mut runtime = RemarkableInterpreter::with_gas_schedule(
+        gas_limit,
+        &contract,
+        stubs.build(&calls),
+        gas_schedule,
+    )
+    .map_err(|source| SimulationError::Execution {
+        path: Vec::new(),
+        source,
+    })?;
+    let status = runtime.run_until_paused().map_err(|source| SimulationError::Execution {
+        path: runtime.metrics().block_path.clone(),
+        source,
+    })?;
+    let outcome = match status {
+        ExecutionStatus::AwaitingInput { interaction_id, .. } => {
+            SimulationOutcome::AwaitingInput { interaction_id }
+        }
+        _ => SimulationOutcome::Completed,
+    };
+    let ffi_calls = calls.lock().map(|c| c.clone()).unwrap_or_default();
+    Ok(SimulationReport {
+        final_state: runtime.state().clone(),
+        path: runtime.metrics().block_path.clone(),
+        ffi_calls,
+        gas_used: runtime.gas_used(),
+        outcome,
+    })
+}
@@ -10,7 +10,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
-use crate::flows::definition::{BlockDefinition, BlockType, FlowDefinition};
+use crate::flows::definition::{BlockDefinition, BlockType, BreakOutput, FlowDefinition};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -37,6 +37,8 @@ impl std::fmt::Display for FlowLoaderError {
 impl std::error::Error for FlowLoaderError {}
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsonFlowDefinition {
+    #[serde(default = "crate::flows::definition::default_schema_version")]
+    pub schema_version: u32,
     pub id: String,
     pub start_block_id: String,
     pub blocks: Vec<JsonBlockDefinition>,
@@ -87,6 +89,12 @@ pub enum JsonBlockType {
     },
     Break {
         loop_id: String,
+        #[serde(default)]
+        output: Option<BreakOutput>,
+    },
+    Parallel {
+        branches: Vec<String>,
+        join_block: String,
     },
     Terminate,
 }
@@ -121,6 +129,7 @@ impl FlowLoader {
             self.validate_flow(&json_flow, &blocks)?;
         }
         Ok(FlowDefinition {
+            schema_version: json_flow.schema_version,
             id: json_flow.id,
             start_block_id: json_flow.start_block_id,
             blocks,
@@ -187,7 +196,14 @@ impl FlowLoader {
                 catch_block_id,
             },
             JsonBlockType::Continue { loop_id } => BlockType::Continue { loop_id },
-            JsonBlockType::Break { loop_id } => BlockType::Break { loop_id },
+            JsonBlockType::Break { loop_id, output } => BlockType::Break { loop_id, output },
+            JsonBlockType::Parallel {
+                branches,
+                join_block,
+            } => BlockType::Parallel {
+                branches,
+                join_block,
+            },
             JsonBlockType::Terminate => BlockType::Terminate,
         };
         Ok(BlockDefinition::new(json_block.id, block_type))
@@ -251,6 +267,15 @@ impl FlowLoader {
                 check_block_exists(try_block_id)?;
                 check_block_exists(catch_block_id)?;
             }
+            BlockType::Parallel {
+                branches,
+                join_block,
+            } => {
+                for branch in branches {
+                    check_block_exists(branch)?;
+                }
+                check_block_exists(join_block)?;
+            }
             _ => {}
         }
         Ok(())
@@ -25,7 +25,8 @@ pub use schemas::{
 };
 pub use task_system::{
     create_task_from_input, create_task_from_input_simple, AgentExecution, AgentProposal,
-    CompetitionManager, TaskCompletionResult, TaskManager, TaskSystem, TaskSystemConfig,
+    CompetitionManager, ScheduledProposal, TaskCompletionResult, TaskManager, TaskSystem,
+    TaskSystemConfig,
 };
 use thiserror::Error;
 #[derive(Error, Debug, Clone)]
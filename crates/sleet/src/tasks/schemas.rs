@@ -10,12 +10,13 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::time::SystemTime;
 use uuid::Uuid;
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub enum TaskPriority {
     Low,
     #[default]
@@ -88,6 +89,12 @@ pub struct TaskProposal {
     pub estimated_completion_time: u64,
     pub confidence_score: f64,
     pub created_at: u64,
+    #[serde(default)]
+    pub priority: TaskPriority,
+    #[serde(default)]
+    pub deadline: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
 }
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskExecution {
@@ -259,6 +266,15 @@ impl std::fmt::Display for Task {
     }
 }
 
+impl TaskStatus {
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Cancelled
+        )
+    }
+}
+
 impl std::fmt::Display for TaskStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -292,8 +308,26 @@ impl TaskProposal {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs(),
+            priority: TaskPriority::default(),
+            deadline: None,
+            depends_on: Vec::new(),
         }
     }
+    pub fn with_priority(mut self, priority: TaskPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+    pub fn with_deadline(mut self, deadline: DateTime<Utc>) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+    pub fn depends_on(mut self, task_id: impl Into<String>) -> Self {
+        self.depends_on.push(task_id.into());
+        self
+    }
+    pub fn is_overdue_at(&self, now: DateTime<Utc>) -> bool {
+        self.deadline.is_some_and(|deadline| deadline < now)
+    }
 }
 impl TaskExecution {
     pub fn new(agent_id: String, task_id: String) -> Self {
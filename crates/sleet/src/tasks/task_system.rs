@@ -14,7 +14,9 @@ use crate::tasks::schemas::{
     AgentContribution, FinalDeliverable, Task, TaskConfig, TaskExecution, TaskOutput, TaskPriority,
     TaskProposal, TaskStatus,
 };
+use crate::clock::{system_clock, SharedClock};
 use crate::tasks::{SimpleTaskAnalyser, TaskAnalyser, TaskError};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -46,6 +48,15 @@ pub struct TaskCompletionResult {
     pub warnings: Vec<String>,
     pub metadata: HashMap<String, serde_json::Value>,
 }
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScheduledProposal {
+    pub proposal_id: String,
+    pub task_id: String,
+    pub agent_id: String,
+    pub priority: TaskPriority,
+    pub deadline: Option<DateTime<Utc>>,
+    pub overdue: bool,
+}
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskSystemConfig {
     pub max_concurrent_tasks: usize,
@@ -62,6 +73,7 @@ pub struct TaskSystem {
     results: HashMap<String, Vec<TaskCompletionResult>>,
     outputs: HashMap<String, TaskOutput>,
     config: TaskSystemConfig,
+    clock: SharedClock,
 }
 #[derive(Debug, Clone, Default)]
 pub struct TaskManager {
@@ -86,8 +98,13 @@ impl TaskSystem {
             results: HashMap::new(),
             outputs: HashMap::new(),
             config,
+            clock: system_clock(),
         }
     }
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
     pub fn create_task(&mut self, config: TaskConfig) -> TaskResult<Task> {
         if self.tasks.len() >= self.config.max_concurrent_tasks {
             return Err(TaskError::ResourceError(
@@ -299,6 +316,72 @@ impl TaskSystem {
             .get(task_id)
             .ok_or_else(|| TaskError::TaskNotFound(task_id.to_string()))
     }
+    pub fn ready_queue(&self) -> Vec<ScheduledProposal> {
+        self.ready_queue_at(self.clock.now())
+    }
+    pub fn ready_queue_at(&self, now: DateTime<Utc>) -> Vec<ScheduledProposal> {
+        let mut ready: Vec<&TaskProposal> = self
+            .proposals
+            .values()
+            .flatten()
+            .filter(|p| {
+                self.tasks
+                    .get(&p.task_id)
+                    .is_some_and(|t| !t.status.is_terminal())
+            })
+            .filter(|p| {
+                p.depends_on.iter().all(|dep| {
+                    self.tasks
+                        .get(dep)
+                        .is_some_and(|t| t.status == TaskStatus::Completed)
+                })
+            })
+            .collect();
+        ready.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then_with(|| match (a.deadline, b.deadline) {
+                    (Some(x), Some(y)) => x.cmp(&y),
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => std::cmp::Ordering::Equal,
+                })
+                .then_with(|| a.created_at.cmp(&b.created_at))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        ready
+            .into_iter()
+            .map(|p| ScheduledProposal {
+                proposal_id: p.id.clone(),
+                task_id: p.task_id.clone(),
+                agent_id: p.agent_id.clone(),
+                priority: p.priority.clone(),
+                deadline: p.deadline,
+                overdue: p.is_overdue_at(now),
+            })
+            .collect()
+    }
+    pub fn next_ready(&self) -> Option<ScheduledProposal> {
+        self.ready_queue().into_iter().next()
+    }
+    pub fn overdue_proposals(&self) -> Vec<&TaskProposal> {
+        self.overdue_proposals_at(self.clock.now())
+    }
+    pub fn overdue_proposals_at(&self, now: DateTime<Utc>) -> Vec<&TaskProposal> {
+        let mut overdue: Vec<&TaskProposal> = self
+            .proposals
+            .values()
+            .flatten()
+            .filter(|p| p.is_overdue_at(now))
+            .filter(|p| {
+                self.tasks
+                    .get(&p.task_id)
+                    .is_some_and(|t| !t.status.is_terminal())
+            })
+            .collect();
+        overdue.sort_by(|a, b| a.deadline.cmp(&b.deadline).then_with(|| a.id.cmp(&b.id)));
+        overdue
+    }
 }
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskSystemStatistics {
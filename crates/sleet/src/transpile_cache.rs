@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use crate::ast::{canonical_hash, Contract};
+use crate::flows::definition::FlowDefinition;
+use crate::transpiler::FlowTranspiler;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+#[derive(Default)]
+pub struct TranspileCache {
+    entries: Mutex<HashMap<String, Arc<Contract>>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl TranspileCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn global() -> &'static TranspileCache {
+        static GLOBAL: OnceLock<TranspileCache> = OnceLock::new();
+        GLOBAL.get_or_init(TranspileCache::new)
+    }
+
+    pub fn flow_hash(flow_def: &FlowDefinition) -> Result<String, serde_json::Error> {
+        canonical_hash(flow_def)
+    }
+
+    pub fn get_or_transpile(
+        &self,
+        flow_def: &FlowDefinition,
+    ) -> Result<Arc<Contract>, Box<dyn std::error::Error>> {
+        let key = Self::flow_hash(flow_def)?;
+        if let Some(contract) = self.lock().get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(Arc::clone(contract));
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let orchestration_contract = FlowTranspiler::transpile(flow_def)?;
+        let contract = Arc::new(crate::convert_contract(orchestration_contract)?);
+        self.lock().insert(key, Arc::clone(&contract));
+        Ok(contract)
+    }
+
+    pub fn get(&self, flow_def: &FlowDefinition) -> Option<Arc<Contract>> {
+        let key = Self::flow_hash(flow_def).ok()?;
+        self.lock().get(&key).cloned()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.lock().len(),
+        }
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<Contract>>> {
+        self.entries.lock().unwrap_or_else(|p| p.into_inner())
+    }
+}
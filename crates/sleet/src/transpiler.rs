@@ -161,13 +161,38 @@ pub mod orchestration {
                 catch_block_id: String,
             },
             PopErrorHandler,
+            Parallel {
+                branches: Vec<String>,
+                join_block: String,
+            },
             Evaluate {
                 bytecode: Vec<u8>,
                 output_path: Vec<PathSegment>,
             },
             Length(Box<AstNode>),
             Add(Box<AstNode>, Box<AstNode>),
+            Sub(Box<AstNode>, Box<AstNode>),
+            Mul(Box<AstNode>, Box<AstNode>),
+            Div(Box<AstNode>, Box<AstNode>),
+            Mod(Box<AstNode>, Box<AstNode>),
             LessThan(Box<AstNode>, Box<AstNode>),
+            Map {
+                source: Vec<PathSegment>,
+                item_var: String,
+                body: Box<AstNode>,
+            },
+            Filter {
+                source: Vec<PathSegment>,
+                item_var: String,
+                predicate: Box<AstNode>,
+            },
+            Reduce {
+                source: Vec<PathSegment>,
+                item_var: String,
+                accumulator_var: String,
+                initial: Box<AstNode>,
+                body: Box<AstNode>,
+            },
         }
         #[derive(Debug, Clone)]
         pub struct Contract {
@@ -180,12 +205,20 @@ pub mod orchestration {
         }
     }
 }
-use crate::flows::definition::{BlockDefinition, BlockType, FlowDefinition};
+use crate::flows::definition::{
+    BlockDefinition, BlockType, FlowDefinition, FlowValidationError, CURRENT_FLOW_SCHEMA_VERSION,
+    MIN_FLOW_SCHEMA_VERSION,
+};
 
 use orchestration::ast::{AstNode, Contract, Literal, Op, PathSegment};
 use serde_json::{json, Value};
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet, VecDeque};
 use thiserror::Error;
+
+pub const LOOP_INDEX_KEY: &str = "__loop_index";
+pub const LOOP_ITEM_KEY: &str = "__loop_item";
+
 mod path_parser {
     use super::orchestration::ast::{AstNode, Literal, Op, PathSegment};
     use thiserror::Error;
@@ -331,6 +364,7 @@ mod logging {
     pub fn log_transpiler_event(_event: &str, _payload: serde_json::Value) {}
 }
 mod expression_compiler {
+    use super::orchestration::ast::{AstNode, Literal, Op, PathSegment};
     use super::{FlowDefinition, TranspilerError};
     use crate::runtime::BytecodeAssembler;
     use serde_json::Value;
@@ -368,6 +402,7 @@ mod expression_compiler {
         Colon,
         Question,
         Dot,
+        Arrow,
         Eof,
     }
     #[derive(Debug)]
@@ -397,6 +432,10 @@ mod expression_compiler {
             else_expr: Box<Expr>,
         },
         Grouping(Box<Expr>),
+        Lambda {
+            params: Vec<String>,
+            body: Box<Expr>,
+        },
     }
 
     pub fn compile(
@@ -404,6 +443,33 @@ mod expression_compiler {
         flow_def: &FlowDefinition,
         block_id: &str,
     ) -> Result<Vec<u8>, TranspilerError> {
+        let ast = parse(expression, flow_def, block_id)?;
+        let bytecode =
+            compile_ast_to_bytecode(&ast).map_err(|e| TranspilerError::ExpressionParseError {
+                block_id: block_id.to_string(),
+                expr: expression.to_string(),
+                error: e,
+            })?;
+        Ok(bytecode)
+    }
+
+    pub fn lower_arithmetic(
+        expression: &str,
+        flow_def: &FlowDefinition,
+        block_id: &str,
+    ) -> Result<Option<AstNode>, TranspilerError> {
+        let ast = parse(expression, flow_def, block_id)?;
+        if !uses_extended_arithmetic(&ast) {
+            return Ok(None);
+        }
+        Ok(lower_expr(&ast))
+    }
+
+    fn parse(
+        expression: &str,
+        flow_def: &FlowDefinition,
+        block_id: &str,
+    ) -> Result<Expr, TranspilerError> {
         let tokens = Tokenizer::new(expression).scan_tokens().map_err(|e| {
             TranspilerError::ExpressionParseError {
                 block_id: block_id.to_string(),
@@ -422,13 +488,132 @@ mod expression_compiler {
         if let Some(schema) = &flow_def.state_schema {
             validate_ast(&ast, schema, block_id, expression)?;
         }
-        let bytecode =
-            compile_ast_to_bytecode(&ast).map_err(|e| TranspilerError::ExpressionParseError {
-                block_id: block_id.to_string(),
-                expr: expression.to_string(),
-                error: e,
-            })?;
-        Ok(bytecode)
+        Ok(ast)
+    }
+
+    fn uses_extended_arithmetic(expr: &Expr) -> bool {
+        match expr {
+            Expr::Binary { left, op, right } => {
+                matches!(op, Token::Minus | Token::Star | Token::Slash | Token::Percent)
+                    || uses_extended_arithmetic(left)
+                    || uses_extended_arithmetic(right)
+            }
+            Expr::Grouping(expr) => uses_extended_arithmetic(expr),
+            _ => false,
+        }
+    }
+
+    pub fn lower_higher_order(
+        expression: &str,
+        flow_def: &FlowDefinition,
+        block_id: &str,
+    ) -> Result<Option<AstNode>, TranspilerError> {
+        let ast = parse(expression, flow_def, block_id)?;
+        lower_collection_call(&ast).map_err(|e| TranspilerError::ExpressionParseError {
+            block_id: block_id.to_string(),
+            expr: expression.to_string(),
+            error: e,
+        })
+    }
+
+    fn lower_collection_call(expr: &Expr) -> Result<Option<AstNode>, String> {
+        let (method, source, args) = match expr {
+            Expr::Grouping(inner) => return lower_collection_call(inner),
+            Expr::Call { callee, args } => match callee.as_ref() {
+                Expr::Variable(path) => match path.split_last() {
+                    Some((method, source)) if !source.is_empty() => (method, source, args),
+                    _ => return Ok(None),
+                },
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        let source = state_path(source);
+        let op = match (method.as_str(), args.as_slice()) {
+            ("map", [Expr::Lambda { params, body }]) => {
+                let [item_var] = params.as_slice() else {
+                    return Err("map expects a lambda with one parameter".to_string());
+                };
+                Op::Map {
+                    source,
+                    item_var: item_var.clone(),
+                    body: Box::new(element_body(body, item_var)?),
+                }
+            }
+            ("filter", [Expr::Lambda { params, body }]) => {
+                let [item_var] = params.as_slice() else {
+                    return Err("filter expects a lambda with one parameter".to_string());
+                };
+                Op::Filter {
+                    source,
+                    item_var: item_var.clone(),
+                    predicate: Box::new(element_body(body, item_var)?),
+                }
+            }
+            ("reduce", [Expr::Lambda { params, body }, initial]) => {
+                let [accumulator_var, item_var] = params.as_slice() else {
+                    return Err("reduce expects a lambda with two parameters".to_string());
+                };
+                let initial = lower_expr(initial)
+                    .or_else(|| match initial {
+                        Expr::Literal(value) => Literal::try_from(value.clone())
+                            .ok()
+                            .map(|literal| AstNode::from(Op::Literal(literal))),
+                        _ => None,
+                    })
+                    .ok_or("reduce expects a literal or arithmetic initial value")?;
+                Op::Reduce {
+                    source,
+                    item_var: item_var.clone(),
+                    accumulator_var: accumulator_var.clone(),
+                    initial: Box::new(initial),
+                    body: Box::new(element_body(body, accumulator_var)?),
+                }
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(AstNode::from(op)))
+    }
+
+    fn element_body(body: &Expr, output_var: &str) -> Result<AstNode, String> {
+        Ok(AstNode::from(Op::Evaluate {
+            bytecode: compile_ast_to_bytecode(body)?,
+            output_path: vec![PathSegment::State, PathSegment::Key(output_var.to_string())],
+        }))
+    }
+
+    fn state_path(path: &[String]) -> Vec<PathSegment> {
+        let keys = match path.first().map(String::as_str) {
+            Some("state") => &path[1..],
+            _ => path,
+        };
+        let mut segments = vec![PathSegment::State];
+        segments.extend(keys.iter().map(|key| PathSegment::Key(key.clone())));
+        segments
+    }
+
+    fn lower_expr(expr: &Expr) -> Option<AstNode> {
+        match expr {
+            Expr::Literal(value) => {
+                let number = value.as_f64()?;
+                Some(AstNode::from(Op::Literal(Literal::Number(number))))
+            }
+            Expr::Variable(path) => Some(AstNode::from(Op::Fetch(state_path(path)))),
+            Expr::Binary { left, op, right } => {
+                let left = Box::new(lower_expr(left)?);
+                let right = Box::new(lower_expr(right)?);
+                Some(AstNode::from(match op {
+                    Token::Plus => Op::Add(left, right),
+                    Token::Minus => Op::Sub(left, right),
+                    Token::Star => Op::Mul(left, right),
+                    Token::Slash => Op::Div(left, right),
+                    Token::Percent => Op::Mod(left, right),
+                    _ => return None,
+                }))
+            }
+            Expr::Grouping(expr) => lower_expr(expr),
+            _ => None,
+        }
     }
     struct Tokenizer<'a> {
         iter: Peekable<Chars<'a>>,
@@ -508,11 +693,16 @@ mod expression_compiler {
                     }
                     '=' => {
                         self.iter.next();
-                        if let Some(&'=') = self.iter.peek() {
-                            self.iter.next();
-                            tokens.push(Token::EqEq);
-                        } else {
-                            return Err("Expected '=' after '='".to_string());
+                        match self.iter.peek() {
+                            Some(&'=') => {
+                                self.iter.next();
+                                tokens.push(Token::EqEq);
+                            }
+                            Some(&'>') => {
+                                self.iter.next();
+                                tokens.push(Token::Arrow);
+                            }
+                            _ => return Err("Expected '=' or '>' after '='".to_string()),
                         }
                     }
                     '!' => {
@@ -572,11 +762,14 @@ mod expression_compiler {
         fn scan_string(&mut self) -> Result<Token, String> {
             self.iter.next();
             let mut value = String::new();
-            for ch in self.iter.by_ref() {
-                if ch == '"' {
-                    return Ok(Token::String(value));
+            while let Some(ch) = self.iter.next() {
+                match ch {
+                    '"' => return Ok(Token::String(value)),
+                    '\\' if matches!(self.iter.peek(), Some('"' | '\\')) => {
+                        value.push(self.iter.next().unwrap());
+                    }
+                    _ => value.push(ch),
                 }
-                value.push(ch);
             }
             Err("Unterminated string".to_string())
         }
@@ -754,7 +947,10 @@ mod expression_compiler {
             let mut args = Vec::new();
             if !self.check(&Token::RParen) {
                 loop {
-                    args.push(self.or()?);
+                    match self.lambda()? {
+                        Some(lambda) => args.push(lambda),
+                        None => args.push(self.or()?),
+                    }
                     if !self.match_token(&Token::Comma) {
                         break;
                     }
@@ -768,6 +964,45 @@ mod expression_compiler {
                 args,
             })
         }
+        fn lambda(&mut self) -> Result<Option<Expr>, String> {
+            let (params, consumed) = match self.peek() {
+                Token::Identifier(name) => match self.tokens.get(self.current + 1) {
+                    Some(Token::Arrow) => (vec![name.clone()], 2),
+                    _ => return Ok(None),
+                },
+                Token::LParen => match self.parenthesised_params() {
+                    Some(found) => found,
+                    None => return Ok(None),
+                },
+                _ => return Ok(None),
+            };
+            self.current += consumed;
+            let body = self.conditional()?;
+            Ok(Some(Expr::Lambda {
+                params,
+                body: Box::new(body),
+            }))
+        }
+        fn parenthesised_params(&self) -> Option<(Vec<String>, usize)> {
+            let mut params = Vec::new();
+            let mut index = self.current + 1;
+            loop {
+                match self.tokens.get(index)? {
+                    Token::Identifier(name) => params.push(name.clone()),
+                    _ => return None,
+                }
+                index += 1;
+                match self.tokens.get(index)? {
+                    Token::Comma => index += 1,
+                    Token::RParen => break,
+                    _ => return None,
+                }
+            }
+            match self.tokens.get(index + 1)? {
+                Token::Arrow => Some((params, index + 2 - self.current)),
+                _ => None,
+            }
+        }
         fn primary(&mut self) -> Result<Expr, String> {
             if self.match_token(&Token::True) {
                 return Ok(Expr::Literal(Value::Bool(true)));
@@ -855,6 +1090,9 @@ mod expression_compiler {
             std::mem::discriminant(self.peek()) == std::mem::discriminant(token_type)
         }
     }
+    fn is_lambda(expr: &Expr) -> bool {
+        matches!(expr, Expr::Lambda { .. })
+    }
     fn validate_ast(
         ast: &Expr,
         schema: &Value,
@@ -902,7 +1140,14 @@ mod expression_compiler {
                 validate_ast(index, schema, block_id, expression)?;
             }
             Expr::Call { callee, args } => {
-                validate_ast(callee, schema, block_id, expression)?;
+                match callee.as_ref() {
+                    Expr::Variable(path) if args.iter().any(is_lambda) => {
+                        let source = Expr::Variable(path[..path.len() - 1].to_vec());
+                        validate_ast(&source, schema, block_id, expression)?;
+                    }
+                    callee if function_name(callee).is_some() => {}
+                    other => validate_ast(other, schema, block_id, expression)?,
+                }
                 for arg in args {
                     validate_ast(arg, schema, block_id, expression)?;
                 }
@@ -917,10 +1162,25 @@ mod expression_compiler {
                 validate_ast(else_expr, schema, block_id, expression)?;
             }
             Expr::Grouping(expr) => validate_ast(expr, schema, block_id, expression)?,
-            Expr::Literal(_) => {}
+            Expr::Literal(_) | Expr::Lambda { .. } => {}
         }
         Ok(())
     }
+    fn is_string_literal(expr: &Expr) -> bool {
+        matches!(expr, Expr::Literal(Value::String(_)))
+    }
+
+    fn is_builtin(callee: &Expr, name: &str) -> bool {
+        matches!(callee, Expr::Variable(path) if path.len() == 1 && path[0] == name)
+    }
+
+    fn function_name(callee: &Expr) -> Option<&str> {
+        match callee {
+            Expr::Variable(path) if path.len() == 1 && path[0] != "state" => Some(&path[0]),
+            _ => None,
+        }
+    }
+
     fn compile_ast_to_bytecode(ast: &Expr) -> Result<Vec<u8>, String> {
         let mut assembler = BytecodeAssembler::new();
         compile_expr(ast, &mut assembler)?;
@@ -946,10 +1206,42 @@ mod expression_compiler {
                     .join(".");
                 assembler.load_var(&path_str);
             }
+            Expr::Binary { left, op, right } if matches!(op, Token::And | Token::Or) => {
+                let short_circuit_on = matches!(op, Token::Or);
+                let branch = |assembler: &mut BytecodeAssembler| {
+                    if short_circuit_on {
+                        assembler.jump_if_true()
+                    } else {
+                        assembler.jump_if_false()
+                    }
+                };
+                compile_expr(left, assembler)?;
+                let left_jump = branch(assembler);
+                compile_expr(right, assembler)?;
+                let right_jump = branch(assembler);
+                assembler
+                    .push_literal(&Value::Bool(!short_circuit_on))
+                    .map_err(|e| format!("Failed to compile logical result: {e}"))?;
+                let end_jump = assembler.jump();
+                for jump in [left_jump, right_jump] {
+                    assembler
+                        .patch_jump(jump)
+                        .map_err(|e| format!("Failed to patch logical jump: {e}"))?;
+                }
+                assembler
+                    .push_literal(&Value::Bool(short_circuit_on))
+                    .map_err(|e| format!("Failed to compile logical result: {e}"))?;
+                assembler
+                    .patch_jump(end_jump)
+                    .map_err(|e| format!("Failed to patch logical jump: {e}"))?;
+            }
             Expr::Binary { left, op, right } => {
                 compile_expr(left, assembler)?;
                 compile_expr(right, assembler)?;
                 match op {
+                    Token::Plus if is_string_literal(left) || is_string_literal(right) => {
+                        assembler.concat();
+                    }
                     Token::Plus => { assembler.add(); }
                     Token::Minus => { assembler.subtract(); }
                     Token::Star => { assembler.multiply(); }
@@ -979,13 +1271,28 @@ mod expression_compiler {
                 compile_expr(index, assembler)?;
                 assembler.load_index();
             }
+            Expr::Call { callee, args } if is_builtin(callee, "concat") => {
+                assembler
+                    .push_literal(&Value::String(String::new()))
+                    .map_err(|e| format!("Failed to compile concat: {e}"))?;
+                for arg in args {
+                    compile_expr(arg, assembler)?;
+                    assembler.concat();
+                }
+            }
             Expr::Call { callee, args } => {
                 for arg in args {
                     compile_expr(arg, assembler)?;
                 }
-                compile_expr(callee, assembler)?;
-                assembler.call_function(args.len())
-                    .map_err(|e| format!("Failed to compile function call: {e}"))?;
+                if let Some(name) = function_name(callee) {
+                    assembler
+                        .call_ffi(name, args.len())
+                        .map_err(|e| format!("Failed to compile function call: {e}"))?;
+                } else {
+                    compile_expr(callee, assembler)?;
+                    assembler.call_function(args.len())
+                        .map_err(|e| format!("Failed to compile function call: {e}"))?;
+                }
             }
             Expr::Conditional {
                 condition,
@@ -1006,6 +1313,12 @@ mod expression_compiler {
                     .map_err(|e| format!("Failed to patch end jump: {e}"))?;
             }
             Expr::Grouping(expr) => compile_expr(expr, assembler)?,
+            Expr::Lambda { .. } => {
+                return Err(
+                    "Lambdas are only supported as the argument of map, filter or reduce"
+                        .to_string(),
+                )
+            }
         }
         Ok(())
     }
@@ -1040,6 +1353,19 @@ pub enum TranspilerError {
     },
     #[error("A TryCatch block '{0}' forms an invalid or inescapable structure.")]
     InvalidTryCatchStructure(String),
+    #[error("Flow definition schema version {found} is not supported; expected {min} to {current}")]
+    UnsupportedSchemaVersion { found: u32, min: u32, current: u32 },
+    #[error("Block '{block_id}' cannot be transpiled: {reason}")]
+    UnsupportedBlock { block_id: String, reason: String },
+    #[error("Flow definition is invalid: {}", describe_issues(.0))]
+    InvalidFlow(Vec<FlowValidationError>),
+}
+fn describe_issues(issues: &[FlowValidationError]) -> String {
+    issues
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 struct TranspilerContext {
     output_blocks: HashMap<String, AstNode>,
@@ -1078,6 +1404,9 @@ struct ForEachParams<'a> {
 pub struct FlowTranspiler;
 impl FlowTranspiler {
     pub fn transpile(flow_def: &FlowDefinition) -> Result<Contract, TranspilerError> {
+        let migrated = Self::migrate(flow_def)?;
+        let flow_def = migrated.as_ref();
+        flow_def.validate().map_err(TranspilerError::InvalidFlow)?;
         let mut context = TranspilerContext::new();
         Self::collect_symbols_and_scopes(flow_def, &mut context)?;
         for block_def in &flow_def.blocks {
@@ -1100,6 +1429,33 @@ impl FlowTranspiler {
             permissions: flow_def.permissions.clone(),
         })
     }
+    pub fn migrate(flow_def: &FlowDefinition) -> Result<Cow<'_, FlowDefinition>, TranspilerError> {
+        let version = flow_def.schema_version;
+        if !(MIN_FLOW_SCHEMA_VERSION..=CURRENT_FLOW_SCHEMA_VERSION).contains(&version) {
+            return Err(TranspilerError::UnsupportedSchemaVersion {
+                found: version,
+                min: MIN_FLOW_SCHEMA_VERSION,
+                current: CURRENT_FLOW_SCHEMA_VERSION,
+            });
+        }
+        if version == CURRENT_FLOW_SCHEMA_VERSION {
+            return Ok(Cow::Borrowed(flow_def));
+        }
+        let mut migrated = flow_def.clone();
+        if migrated.schema_version == 1 {
+            Self::migrate_v1_prompts(&mut migrated);
+            migrated.schema_version = 2;
+        }
+        Ok(Cow::Owned(migrated))
+    }
+    fn migrate_v1_prompts(flow_def: &mut FlowDefinition) {
+        for block_def in &mut flow_def.blocks {
+            if let BlockType::AwaitInput { prompt, .. } = &mut block_def.block_type {
+                let escaped = prompt.replace('\\', "\\\\").replace('"', "\\\"");
+                *prompt = format!("\"{escaped}\"");
+            }
+        }
+    }
     fn collect_symbols_and_scopes(
         flow_def: &FlowDefinition,
         context: &mut TranspilerContext,
@@ -1195,15 +1551,35 @@ impl FlowTranspiler {
                 output_key,
                 next_block,
             } => {
-                let bytecode = expression_compiler::compile(expression, flow_def, &block_def.id)?;
                 let mut path = path_parser::transpile(output_key)
                     .map_err(|e| TranspilerError::PathParseError(output_key.clone(), e))?;
                 path.insert(0, PathSegment::State);
-                AstNode::from(Op::Sequence(vec![
-                    AstNode::from(Op::Evaluate {
-                        bytecode,
+                let lowered = match expression_compiler::lower_higher_order(
+                    expression,
+                    flow_def,
+                    &block_def.id,
+                )? {
+                    Some(node) => Some(node),
+                    None => {
+                        expression_compiler::lower_arithmetic(expression, flow_def, &block_def.id)?
+                    }
+                };
+                let compute = match lowered {
+                    Some(value) => AstNode::from(Op::Assign {
+                        path,
+                        value: Box::new(value),
+                    }),
+                    None => AstNode::from(Op::Evaluate {
+                        bytecode: expression_compiler::compile(
+                            expression,
+                            flow_def,
+                            &block_def.id,
+                        )?,
                         output_path: path,
                     }),
+                };
+                AstNode::from(Op::Sequence(vec![
+                    compute,
                     AstNode::from(Op::SetNextBlock(next_block.clone())),
                 ]))
             }
@@ -1271,17 +1647,39 @@ impl FlowTranspiler {
                     })?
                     .clone(),
             )),
-            BlockType::Break { loop_id } => AstNode::from(Op::SetNextBlock(
-                context
-                    .loop_break_points
-                    .get(loop_id)
-                    .ok_or_else(|| TranspilerError::LoopJumpTargetNotFound {
-                        op: "Break".into(),
-                        block_id: block_def.id.clone(),
-                        loop_id: loop_id.clone(),
-                    })?
-                    .clone(),
-            )),
+            BlockType::Break { loop_id, output } => {
+                let exit = AstNode::from(Op::SetNextBlock(
+                    context
+                        .loop_break_points
+                        .get(loop_id)
+                        .ok_or_else(|| TranspilerError::LoopJumpTargetNotFound {
+                            op: "Break".into(),
+                            block_id: block_def.id.clone(),
+                            loop_id: loop_id.clone(),
+                        })?
+                        .clone(),
+                ));
+                match output {
+                    Some(output) => {
+                        let mut path = path_parser::transpile(&output.output_key).map_err(|e| {
+                            TranspilerError::PathParseError(output.output_key.clone(), e)
+                        })?;
+                        path.insert(0, PathSegment::State);
+                        AstNode::from(Op::Sequence(vec![
+                            AstNode::from(Op::Evaluate {
+                                bytecode: expression_compiler::compile(
+                                    &output.expression,
+                                    flow_def,
+                                    &block_def.id,
+                                )?,
+                                output_path: path,
+                            }),
+                            exit,
+                        ]))
+                    }
+                    None => exit,
+                }
+            }
             BlockType::TryCatch {
                 try_block_id,
                 catch_block_id,
@@ -1294,6 +1692,20 @@ impl FlowTranspiler {
             BlockType::SubFlow { next_block, .. } => {
                 AstNode::from(Op::SetNextBlock(next_block.clone()))
             }
+            BlockType::Parallel {
+                branches,
+                join_block,
+            } => AstNode::from(Op::Parallel {
+                branches: branches.clone(),
+                join_block: join_block.clone(),
+            }),
+            BlockType::Generate { .. } => {
+                return Err(TranspilerError::UnsupportedBlock {
+                    block_id: block_def.id.clone(),
+                    reason: "Generate blocks are executed by the OrchestrationCoordinator"
+                        .to_string(),
+                });
+            }
         };
         if context.try_exit_nodes.contains(&block_def.id) {
             node = Self::inject_pop_handler(node);
@@ -1390,18 +1802,34 @@ impl FlowTranspiler {
         context
             .output_blocks
             .insert(cond_block_id.clone(), cond_block);
+        let iterator_path = vec![
+            PathSegment::State,
+            PathSegment::Key(params.iterator_var.to_string()),
+        ];
+        let loop_binding = |key: &str| {
+            vec![
+                PathSegment::State,
+                PathSegment::Key(key.to_string()),
+                PathSegment::Key(params.loop_id.to_string()),
+            ]
+        };
         let mut body_setup_block = AstNode::from(Op::Sequence(vec![
             AstNode::from(Op::Assign {
-                path: vec![
-                    PathSegment::State,
-                    PathSegment::Key(params.iterator_var.to_string()),
-                ],
+                path: iterator_path.clone(),
                 value: Box::new(AstNode::from(Op::Fetch(vec![
                     PathSegment::State,
                     PathSegment::Key(temp_array_key.clone()),
                     PathSegment::DynamicOffset(Box::new(fetch_counter.clone())),
                 ]))),
             }),
+            AstNode::from(Op::Assign {
+                path: loop_binding(LOOP_INDEX_KEY),
+                value: Box::new(fetch_counter.clone()),
+            }),
+            AstNode::from(Op::Assign {
+                path: loop_binding(LOOP_ITEM_KEY),
+                value: Box::new(AstNode::from(Op::Fetch(iterator_path))),
+            }),
             AstNode::from(Op::SetNextBlock(params.loop_body_block_id.to_string())),
         ]));
         body_setup_block.metadata = meta.clone();
@@ -1432,9 +1860,9 @@ fn get_successor_ids(block_def: &BlockDefinition) -> Vec<String> {
             false_block,
             ..
         } => vec![true_block.clone(), false_block.clone()],
-        BlockType::Compute { next_block, .. } | BlockType::AwaitInput { next_block, .. } => {
-            vec![next_block.clone()]
-        }
+        BlockType::Compute { next_block, .. }
+        | BlockType::AwaitInput { next_block, .. }
+        | BlockType::Generate { next_block, .. } => vec![next_block.clone()],
         BlockType::ForEach {
             loop_body_block_id,
             exit_block_id,
@@ -1442,6 +1870,14 @@ fn get_successor_ids(block_def: &BlockDefinition) -> Vec<String> {
         } => vec![loop_body_block_id.clone(), exit_block_id.clone()],
         BlockType::TryCatch { try_block_id, .. } => vec![try_block_id.clone()],
         BlockType::SubFlow { next_block, .. } => vec![next_block.clone()],
+        BlockType::Parallel {
+            branches,
+            join_block,
+        } => branches
+            .iter()
+            .chain(std::iter::once(join_block))
+            .cloned()
+            .collect(),
         BlockType::Continue { .. } | BlockType::Break { .. } | BlockType::Terminate => vec![],
     }
 }
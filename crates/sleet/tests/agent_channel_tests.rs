@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use serde_json::json;
+use sleet::orchestration::coordinator::ResourceLimits;
+use sleet::orchestration::{
+    AgentChannel, EventSystem, OrchestrationConfig, OrchestrationEvent, ResourceManager,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+async fn channel(limits: ResourceLimits) -> (AgentChannel, Arc<RwLock<EventSystem>>) {
+    let resource_manager = Arc::new(RwLock::new(ResourceManager::new(limits).await.unwrap()));
+    let event_system = Arc::new(RwLock::new(EventSystem::new().await.unwrap()));
+    let channel = AgentChannel::new(
+        "session-1",
+        vec!["planner".to_string(), "critic".to_string()],
+        resource_manager,
+        event_system.clone(),
+    );
+    (channel, event_system)
+}
+
+#[tokio::test]
+async fn agents_exchange_messages_in_order_with_events() {
+    let (channel, event_system) = channel(OrchestrationConfig::default().resource_limits).await;
+    let mut events = event_system.read().await.get_event_receiver();
+    let planner = channel.agent("planner");
+    let critic = channel.agent("critic");
+
+    for step in 1..=3 {
+        planner.send("critic", json!({ "draft": step })).await.unwrap();
+    }
+    critic.send("planner", json!("looks good")).await.unwrap();
+
+    let received = critic.inbox().await;
+    let drafts: Vec<_> = received.iter().map(|m| m.payload["draft"].clone()).collect();
+    assert_eq!(drafts, vec![json!(1), json!(2), json!(3)]);
+    let sequences: Vec<u64> = received.iter().map(|m| m.sequence).collect();
+    assert_eq!(sequences, vec![1, 2, 3]);
+    assert!(received.iter().all(|m| m.from == "planner" && m.to == "critic"));
+
+    let reply = planner.receive().await.unwrap();
+    assert_eq!(reply.payload, json!("looks good"));
+    assert_eq!(reply.sequence, 1);
+    assert!(planner.receive().await.is_none());
+
+    let mut sent = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        if let OrchestrationEvent::AgentMessageSent {
+            session_id,
+            from_agent,
+            to_agent,
+            sequence,
+            ..
+        } = event
+        {
+            assert_eq!(session_id, "session-1");
+            sent.push((from_agent, to_agent, sequence));
+        }
+    }
+    assert_eq!(
+        sent,
+        vec![
+            ("planner".to_string(), "critic".to_string(), 1),
+            ("planner".to_string(), "critic".to_string(), 2),
+            ("planner".to_string(), "critic".to_string(), 3),
+            ("critic".to_string(), "planner".to_string(), 1),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn quota_and_membership_are_enforced() {
+    let mut limits = OrchestrationConfig::default().resource_limits;
+    limits.max_messages_per_agent_per_session = 1;
+    let (channel, _) = channel(limits).await;
+    let planner = channel.agent("planner");
+
+    planner.send("critic", json!("first")).await.unwrap();
+    assert!(planner.send("critic", json!("second")).await.is_err());
+    assert_eq!(channel.pending("critic").await, 1);
+
+    assert!(planner.send("outsider", json!("hello")).await.is_err());
+}
@@ -0,0 +1,245 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use async_trait::async_trait;
+use common::coordinator_config;
+use serde_json::{json, Value};
+use sleet::orchestration::adapters::agent_adapter::{AgentMetadata, AgentSelectionCriteria};
+use sleet::orchestration::adapters::{
+    AdapterError, AgentInteractionResult, ExecutionContext, ExecutionMetadata, InteractionOptions,
+    PerformanceMetrics, ResourceUsageInfo,
+};
+use sleet::orchestration::{
+    AgentInvoker, AgentSelector, InteractionType, OrchestrationBlockDefinition,
+    OrchestrationBlockType, OrchestrationError, RetryPolicy, TaskDefinition,
+    RETRY_POLICY_METADATA_KEY,
+};
+use sleet::runtime::ExecutionStatus;
+use sleet::{
+    BlockDefinition, BlockType, FlowDefinition, OrchestrationCoordinator,
+    OrchestrationFlowDefinition,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+struct FlakyAgent {
+    failures_before_success: usize,
+    calls: Mutex<Vec<(Instant, Value)>>,
+}
+
+impl FlakyAgent {
+    fn new(failures_before_success: usize) -> Arc<Self> {
+        Arc::new(Self {
+            failures_before_success,
+            calls: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn attempts(&self) -> usize {
+        self.calls.lock().unwrap().len()
+    }
+
+    fn gaps(&self) -> Vec<Duration> {
+        let calls = self.calls.lock().unwrap();
+        calls.windows(2).map(|pair| pair[1].0 - pair[0].0).collect()
+    }
+}
+
+#[async_trait]
+impl AgentInvoker for FlakyAgent {
+    async fn invoke(
+        &self,
+        _criteria: &AgentSelectionCriteria,
+        input_data: &Value,
+        _options: &InteractionOptions,
+        _execution_context: &ExecutionContext,
+    ) -> Result<AgentInteractionResult, AdapterError> {
+        let attempt = {
+            let mut calls = self.calls.lock().unwrap();
+            calls.push((Instant::now(), input_data.clone()));
+            calls.len()
+        };
+        if attempt <= self.failures_before_success {
+            return Err(AdapterError::AgentOperationFailed(format!(
+                "attempt {attempt} failed"
+            )));
+        }
+        Ok(AgentInteractionResult {
+            agent_id: "flaky".to_string(),
+            result: json!({ "answer": 42 }),
+            execution_metadata: ExecutionMetadata {
+                execution_id: format!("attempt-{attempt}"),
+                start_time: chrono::Utc::now(),
+                end_time: None,
+                duration_ms: None,
+                resource_usage: ResourceUsageInfo {
+                    cpu_time_ms: 0,
+                    memory_peak_mb: 0,
+                    network_bytes: 0,
+                    storage_bytes: 0,
+                },
+                performance_metrics: PerformanceMetrics {
+                    throughput: 0.0,
+                    latency_ms: 0.0,
+                    success_rate: 1.0,
+                    quality_score: None,
+                },
+                error_details: None,
+            },
+            agent_metadata: AgentMetadata {
+                capabilities: Vec::new(),
+                current_load: 0.0,
+                response_time_ms: 0,
+                success_rate: 1.0,
+            },
+        })
+    }
+}
+
+fn policy(max_attempts: u32, base_delay_ms: u64) -> RetryPolicy {
+    RetryPolicy {
+        max_attempts,
+        base_delay_ms,
+        backoff_multiplier: 2.0,
+        max_delay_ms: 60_000,
+        jitter: false,
+    }
+}
+
+fn agent_flow(block_policy: Option<RetryPolicy>) -> OrchestrationFlowDefinition {
+    let mut flow = FlowDefinition::new("ask_agent", "ask");
+    flow.add_block(BlockDefinition::new("done", BlockType::Terminate));
+    let mut flow: OrchestrationFlowDefinition = flow.into();
+    flow.blocks.push(OrchestrationBlockDefinition {
+        id: "ask".to_string(),
+        block_type: OrchestrationBlockType::AgentInteraction {
+            agent_selector: AgentSelector::ById("flaky".to_string()),
+            task_definition: TaskDefinition {
+                task_type: "answer".to_string(),
+                parameters: HashMap::from([("question".to_string(), json!("meaning"))]),
+                expected_output: None,
+            },
+            interaction_type: InteractionType::Command {
+                expect_confirmation: false,
+                timeout_secs: None,
+            },
+            timeout_secs: None,
+            retry_config: None,
+            next_block: "done".to_string(),
+        },
+        metadata: block_policy.map(|policy| {
+            HashMap::from([(
+                RETRY_POLICY_METADATA_KEY.to_string(),
+                serde_json::to_value(policy).unwrap(),
+            )])
+        }),
+        compensation_block: None,
+    });
+    flow
+}
+
+async fn coordinator_with(
+    agent_retry_policy: RetryPolicy,
+    agent: Arc<FlakyAgent>,
+) -> OrchestrationCoordinator {
+    let mut config = coordinator_config();
+    config.agent_retry_policy = agent_retry_policy;
+    let coordinator = OrchestrationCoordinator::new(config).await.unwrap();
+    coordinator.set_agent_invoker(agent).await;
+    coordinator
+}
+
+#[tokio::test]
+async fn agent_failing_twice_succeeds_on_the_third_attempt_with_backoff() {
+    let agent = FlakyAgent::new(2);
+    let coordinator = coordinator_with(policy(3, 20), agent.clone()).await;
+
+    let status = coordinator.execute_flow(agent_flow(None), None).await.unwrap();
+
+    assert!(matches!(status, ExecutionStatus::Completed(_)));
+    assert_eq!(agent.attempts(), 3);
+    let gaps = agent.gaps();
+    assert!(gaps[0] >= Duration::from_millis(20), "{gaps:?}");
+    assert!(gaps[1] >= Duration::from_millis(40), "{gaps:?}");
+    let calls = agent.calls.lock().unwrap();
+    assert!(calls.iter().all(|(_, input)| *input == calls[0].1));
+}
+
+#[tokio::test]
+async fn error_propagates_once_attempts_are_exhausted() {
+    let agent = FlakyAgent::new(2);
+    let coordinator = coordinator_with(policy(2, 1), agent.clone()).await;
+
+    let error = coordinator
+        .execute_flow(agent_flow(None), None)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, OrchestrationError::AgentInteractionError(_)));
+    assert!(error.to_string().contains("attempt 2 failed"));
+    assert_eq!(agent.attempts(), 2);
+}
+
+#[tokio::test]
+async fn block_metadata_overrides_the_configured_policy() {
+    let agent = FlakyAgent::new(2);
+    let coordinator = coordinator_with(RetryPolicy::default(), agent.clone()).await;
+
+    let status = coordinator
+        .execute_flow(agent_flow(Some(policy(3, 1))), None)
+        .await
+        .unwrap();
+
+    assert!(matches!(status, ExecutionStatus::Completed(_)));
+    assert_eq!(agent.attempts(), 3);
+}
+
+#[test]
+fn delays_grow_exponentially_and_jitter_stays_within_bounds() {
+    let fixed = policy(5, 100);
+    let delays: Vec<u128> = (1..=4)
+        .map(|retry| fixed.delay_before_retry(retry).as_millis())
+        .collect();
+    assert_eq!(delays, vec![100, 200, 400, 800]);
+
+    let jittered = RetryPolicy {
+        jitter: true,
+        ..fixed
+    };
+    for _ in 0..20 {
+        let delay = jittered.delay_before_retry(3);
+        assert!(delay >= Duration::from_millis(200) && delay <= Duration::from_millis(400));
+    }
+}
+
+#[test]
+fn delays_are_clamped_before_jitter_even_for_non_finite_backoff() {
+    for backoff_multiplier in [f64::INFINITY, f64::NAN, 1e300] {
+        let policy = RetryPolicy {
+            backoff_multiplier,
+            max_delay_ms: 500,
+            jitter: true,
+            ..policy(5, 100)
+        };
+        for retry in 2..=4 {
+            assert!(policy.delay_before_retry(retry) <= Duration::from_millis(500));
+        }
+    }
+    let capped = RetryPolicy {
+        max_delay_ms: 300,
+        ..policy(5, 100)
+    };
+    assert_eq!(capped.delay_before_retry(4), Duration::from_millis(300));
+}
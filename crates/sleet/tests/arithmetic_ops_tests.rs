@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::{compute_flow_with_state, contract};
+use serde_json::json;
+use sleet::flows::definition::FlowDefinition;
+use sleet::runtime::{FfiRegistry, InterpreterError, RemarkableInterpreter};
+use sleet::transpiler::orchestration::ast::Op;
+use sleet::FlowTranspiler;
+
+fn flow(expression: &str) -> FlowDefinition {
+    compute_flow_with_state(
+        "arithmetic",
+        json!({ "a": 17, "b": 5, "zero": 0 }),
+        expression,
+        "out",
+    )
+}
+
+fn compute_op(expression: &str) -> Op {
+    let contract = FlowTranspiler::transpile(&flow(expression)).unwrap();
+    let Op::Sequence(steps) = &contract.blocks["start"].op else {
+        panic!("expected a sequence for the compute block");
+    };
+    match &steps[0].op {
+        Op::Assign { value, .. } => value.op.clone(),
+        other => panic!("expected an assignment, got {other:?}"),
+    }
+}
+
+fn run(expression: &str) -> Result<serde_json::Value, InterpreterError> {
+    let contract = contract(&flow(expression));
+    let mut executor = RemarkableInterpreter::new(1_000, &contract, FfiRegistry::new()).unwrap();
+    executor.run_until_paused()?;
+    Ok(executor.state()["out"].clone())
+}
+
+#[test]
+fn subtraction_is_transpiled_and_evaluated() {
+    assert!(matches!(compute_op("state.a - state.b"), Op::Sub(..)));
+    assert_eq!(run("state.a - state.b").unwrap(), json!(12));
+}
+
+#[test]
+fn multiplication_is_transpiled_and_evaluated() {
+    assert!(matches!(compute_op("state.a * state.b"), Op::Mul(..)));
+    assert_eq!(run("state.a * state.b").unwrap(), json!(85));
+}
+
+#[test]
+fn division_is_transpiled_and_evaluated() {
+    assert!(matches!(compute_op("state.a / state.b"), Op::Div(..)));
+    assert_eq!(run("state.a / state.b").unwrap(), json!(3.4));
+}
+
+#[test]
+fn modulo_is_transpiled_and_evaluated() {
+    assert!(matches!(compute_op("state.a % state.b"), Op::Mod(..)));
+    assert_eq!(run("state.a % state.b").unwrap(), json!(2));
+}
+
+#[test]
+fn mixed_expressions_respect_precedence() {
+    assert!(matches!(compute_op("state.a - state.b * 2"), Op::Sub(..)));
+    assert_eq!(run("state.a - state.b * 2").unwrap(), json!(7));
+    assert_eq!(run("(state.a + 3) % state.b").unwrap(), json!(0));
+}
+
+#[test]
+fn division_by_zero_is_a_typed_error() {
+    assert!(matches!(
+        run("state.a / state.zero"),
+        Err(InterpreterError::DivisionByZero)
+    ));
+}
+
+#[test]
+fn modulo_by_zero_is_a_typed_error() {
+    assert!(matches!(
+        run("state.a % 0"),
+        Err(InterpreterError::DivisionByZero)
+    ));
+}
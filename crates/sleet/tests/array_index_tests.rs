@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::{compute_flow_with_state, contract};
+use serde_json::{json, Value};
+use sleet::ast::{AstNode, Contract, Literal, Op, Path, PathSegment};
+use sleet::runtime::{FfiRegistry, InterpreterError, RemarkableInterpreter};
+use std::collections::HashMap;
+
+fn evaluate(expression: &str, strict: bool) -> Result<Value, InterpreterError> {
+    let flow = compute_flow_with_state(
+        "indexing",
+        json!({ "data": [1, 2, 3, 4, 5] }),
+        expression,
+        "out",
+    );
+    let contract = contract(&flow);
+    let mut executor = RemarkableInterpreter::new(1_000, &contract, FfiRegistry::new()).unwrap();
+    executor.set_strict_paths(strict);
+    executor.run_until_paused()?;
+    Ok(executor.state()["out"].clone())
+}
+
+fn data_path(tail: Vec<PathSegment>) -> Path {
+    let mut segments = vec![PathSegment::State, PathSegment::Key("data".to_string())];
+    segments.extend(tail);
+    Path(segments)
+}
+
+fn fetch(segment: PathSegment, strict: bool) -> Result<Value, InterpreterError> {
+    let block = AstNode::from(Op::Assign {
+        path: Path(vec![PathSegment::State, PathSegment::Key("out".to_string())]),
+        value: Box::new(AstNode::from(Op::Fetch(data_path(vec![segment])))),
+    });
+    Ok(run_block(block, strict)?["out"].clone())
+}
+
+fn store(tail: Vec<PathSegment>) -> Result<Value, InterpreterError> {
+    let block = AstNode::from(Op::Assign {
+        path: data_path(tail),
+        value: Box::new(AstNode::from(Op::Literal(Literal::Number(9.0)))),
+    });
+    Ok(run_block(block, true)?["data"].clone())
+}
+
+fn run_block(block: AstNode, strict: bool) -> Result<Value, InterpreterError> {
+    let contract = Contract {
+        version: "1.0".to_string(),
+        start_block_id: "start".to_string(),
+        blocks: HashMap::from([("start".to_string(), block)]),
+        initial_state: AstNode::from(Op::Literal(Literal::JsonValue(json!({
+            "data": [1, 2, 3, 4, 5]
+        })))),
+        permissions: json!({}),
+        participants: vec![],
+    };
+    let mut executor = RemarkableInterpreter::new(1_000, &contract, FfiRegistry::new()).unwrap();
+    executor.set_strict_paths(strict);
+    executor.run_until_paused()?;
+    Ok(executor.state().clone())
+}
+
+fn dynamic(offset: f64) -> PathSegment {
+    PathSegment::DynamicOffset(Box::new(AstNode::from(Op::Literal(Literal::Number(offset)))))
+}
+
+#[test]
+fn negative_indices_count_from_the_end() {
+    for strict in [false, true] {
+        assert_eq!(evaluate("state.data[-1]", strict).unwrap(), json!(5));
+        assert_eq!(evaluate("state.data[-5]", strict).unwrap(), json!(1));
+        assert_eq!(fetch(dynamic(-2.0), strict).unwrap(), json!(4));
+    }
+}
+
+#[test]
+fn strict_negative_indices_past_the_start_are_out_of_bounds() {
+    assert!(matches!(
+        evaluate("state.data[-6]", true),
+        Err(InterpreterError::IndexOutOfBounds { index: -6, len: 5 })
+    ));
+    assert!(matches!(
+        fetch(dynamic(-6.0), true),
+        Err(InterpreterError::IndexOutOfBounds { index: -6, len: 5 })
+    ));
+}
+
+#[test]
+fn strict_reads_past_the_end_report_the_array_length() {
+    assert!(matches!(
+        evaluate("state.data[999999]", true),
+        Err(InterpreterError::IndexOutOfBounds { index: 999999, len: 5 })
+    ));
+    assert!(matches!(
+        fetch(PathSegment::Index(5), true),
+        Err(InterpreterError::IndexOutOfBounds { index: 5, len: 5 })
+    ));
+    assert!(matches!(
+        fetch(dynamic(7.0), true),
+        Err(InterpreterError::IndexOutOfBounds { index: 7, len: 5 })
+    ));
+    assert!(matches!(
+        fetch(PathSegment::Index(u64::MAX), true),
+        Err(InterpreterError::IndexOutOfBounds { index: i64::MAX, len: 5 })
+    ));
+}
+
+#[test]
+fn lenient_out_of_bounds_reads_yield_null() {
+    assert_eq!(evaluate("state.data[999999]", false).unwrap(), json!(null));
+    assert_eq!(evaluate("state.data[-6]", false).unwrap(), json!(null));
+    assert_eq!(fetch(PathSegment::Index(5), false).unwrap(), json!(null));
+    assert_eq!(fetch(PathSegment::Index(u64::MAX), false).unwrap(), json!(null));
+    assert_eq!(fetch(dynamic(-6.0), false).unwrap(), json!(null));
+}
+
+#[test]
+fn in_bounds_reads_are_unchanged() {
+    assert_eq!(evaluate("state.data[0]", false).unwrap(), json!(1));
+    assert_eq!(fetch(PathSegment::Index(4), false).unwrap(), json!(5));
+}
+
+#[test]
+fn writes_may_append_but_not_skip_past_the_end() {
+    assert_eq!(
+        store(vec![PathSegment::Index(5)]).unwrap(),
+        json!([1, 2, 3, 4, 5, 9])
+    );
+    assert!(matches!(
+        store(vec![PathSegment::Index(4_000_000_000)]),
+        Err(InterpreterError::IndexOutOfBounds { index: 4_000_000_000, len: 5 })
+    ));
+    assert!(matches!(
+        store(vec![PathSegment::Index(6), PathSegment::Key("x".to_string())]),
+        Err(InterpreterError::IndexOutOfBounds { index: 6, len: 5 })
+    ));
+}
@@ -0,0 +1,187 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use serde_json::{json, Value};
+use sleet::ast::{AstNode, Contract, Literal, Op, Path, PathSegment};
+use std::collections::HashMap;
+
+fn fetch(key: &str) -> AstNode {
+    AstNode::from(Op::Fetch(Path(vec![
+        PathSegment::State,
+        PathSegment::Key(key.to_string()),
+    ])))
+}
+
+fn number(n: f64) -> AstNode {
+    AstNode::from(Op::Literal(Literal::Number(n)))
+}
+
+fn small_tree() -> AstNode {
+    AstNode::from(Op::Sequence(vec![
+        AstNode::from(Op::If {
+            condition: Box::new(AstNode::from(Op::GreaterThan(
+                Box::new(fetch("score")),
+                Box::new(number(10.0)),
+            ))),
+            then_branch: Box::new(AstNode::from(Op::SetNextBlock("high".to_string()))),
+            else_branch: Some(Box::new(AstNode::from(Op::SetNextBlock("low".to_string())))),
+        }),
+        AstNode::from(Op::Terminate),
+    ]))
+}
+
+#[test]
+fn pretty_printer_renders_indented_tree() {
+    let expected = "\
+Sequence
+  If
+    condition: GreaterThan
+      Fetch state.score
+      Literal 10.0
+    then: SetNextBlock \"high\"
+    else: SetNextBlock \"low\"
+  Terminate
+";
+    assert_eq!(small_tree().to_pretty(), expected);
+}
+
+fn validate(schema: &Value, root: &Value, value: &Value) -> Result<(), String> {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = reference.trim_start_matches("#/$defs/");
+        return validate(&root["$defs"][name], root, value);
+    }
+    if let Some(options) = schema.get("oneOf").and_then(Value::as_array) {
+        let matches = options
+            .iter()
+            .filter(|option| validate(option, root, value).is_ok())
+            .count();
+        if matches != 1 {
+            return Err(format!("{value} matched {matches} oneOf branches"));
+        }
+    }
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            return Err(format!("{value} not in enum"));
+        }
+    }
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        let actual = match value {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(n) if n.is_u64() || n.is_i64() => "integer",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        };
+        let ok = types.contains(&actual) || (actual == "integer" && types.contains(&"number"));
+        if !ok {
+            return Err(format!("{value} is not of type {types:?}"));
+        }
+    }
+    if let (Some(minimum), Some(n)) = (schema.get("minimum"), value.as_f64()) {
+        if n < minimum.as_f64().unwrap_or(f64::MIN) {
+            return Err(format!("{n} below minimum"));
+        }
+    }
+    if let Value::Array(items) = value {
+        if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+            if (items.len() as u64) < min {
+                return Err("too few items".to_string());
+            }
+        }
+        if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+            if items.len() as u64 > max {
+                return Err("too many items".to_string());
+            }
+        }
+        if let Some(item_schema) = schema.get("items") {
+            for item in items {
+                validate(item_schema, root, item)?;
+            }
+        }
+    }
+    if let Value::Object(map) = value {
+        for key in schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            if !map.contains_key(key) {
+                return Err(format!("missing required property '{key}'"));
+            }
+        }
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (key, child) in map {
+            match properties.and_then(|p| p.get(key)) {
+                Some(property_schema) => validate(property_schema, root, child)?,
+                None => match schema.get("additionalProperties") {
+                    Some(Value::Bool(false)) => {
+                        return Err(format!("unexpected property '{key}'"));
+                    }
+                    Some(extra) if extra.is_object() => validate(extra, root, child)?,
+                    _ => {}
+                },
+            }
+        }
+    }
+    Ok(())
+}
+
+fn contract() -> Contract {
+    Contract {
+        version: "1.0".to_string(),
+        start_block_id: "start".to_string(),
+        blocks: HashMap::from([("start".to_string(), small_tree())]),
+        initial_state: AstNode::from(Op::Literal(Literal::JsonValue(json!({ "score": 3 })))),
+        permissions: json!({}),
+        participants: vec!["agent".to_string()],
+    }
+}
+
+#[test]
+fn valid_contract_matches_published_schema() {
+    let schema = Contract::json_schema().unwrap();
+    let value = serde_json::to_value(contract()).unwrap();
+    validate(&schema, &schema, &value).unwrap();
+}
+
+#[test]
+fn malformed_contract_is_rejected_by_schema() {
+    let schema = Contract::json_schema().unwrap();
+
+    let mut missing_field = serde_json::to_value(contract()).unwrap();
+    missing_field.as_object_mut().unwrap().remove("start_block_id");
+    assert!(validate(&schema, &schema, &missing_field).is_err());
+
+    let mut unknown_op = serde_json::to_value(contract()).unwrap();
+    unknown_op["blocks"]["start"]["op"] = json!({ "Explode": [] });
+    assert!(validate(&schema, &schema, &unknown_op).is_err());
+
+    let mut bad_arity = serde_json::to_value(contract()).unwrap();
+    bad_arity["blocks"]["start"]["op"] = json!({ "Add": [{ "op": "Terminate", "metadata": {} }] });
+    assert!(validate(&schema, &schema, &bad_arity).is_err());
+}
+
+#[test]
+fn contract_pretty_lists_blocks() {
+    let pretty = contract().to_pretty();
+    assert!(pretty.starts_with("Contract v1.0 start=\"start\"\n"));
+    assert!(pretty.contains("block \"start\"\n  Sequence\n"));
+}
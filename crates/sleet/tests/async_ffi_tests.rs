@@ -0,0 +1,93 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::{compute_flow_with_state, contract};
+use serde_json::json;
+use sleet::ast::Contract;
+use sleet::runtime::{
+    create_async_ffi, create_ergonomic_ffi, ExecutionStatus, FfiRegistry, InterpreterError,
+    RemarkableInterpreter, StepStatus,
+};
+use std::time::Duration;
+
+fn doubling_flow() -> Contract {
+    let flow = compute_flow_with_state("async_flow", json!({ "x": 21 }), "double(state.x)", "out");
+    contract(&flow)
+}
+
+fn async_interpreter(delay: Duration) -> (RemarkableInterpreter, Contract) {
+    let contract = doubling_flow();
+    let mut interp = RemarkableInterpreter::new(1_000, &contract, FfiRegistry::new()).unwrap();
+    interp.register_async_ffi(
+        "double",
+        create_async_ffi(move |args, _state| async move {
+            tokio::time::sleep(delay).await;
+            let x = args.first().and_then(|v| v.as_f64()).unwrap_or_default();
+            Ok(json!(x * 2.0))
+        }),
+    );
+    (interp, contract)
+}
+
+#[tokio::test]
+async fn run_awaits_async_ffi_and_stores_the_result() {
+    let (mut interp, contract) = async_interpreter(Duration::from_millis(20));
+    let status = interp.run(contract).await.unwrap();
+    assert!(matches!(status, ExecutionStatus::Completed(_)));
+    assert_eq!(interp.current_state().get("out"), Some(&json!(42.0)));
+    assert!(interp.stack_snapshot().is_empty());
+}
+
+#[tokio::test]
+async fn synchronous_registration_takes_precedence() {
+    let contract = doubling_flow();
+    let registry = FfiRegistry::from([(
+        "double".to_string(),
+        create_ergonomic_ffi(|_args, _state| Ok(json!("sync"))),
+    )]);
+    let mut interp = RemarkableInterpreter::new(1_000, &contract, registry).unwrap();
+    interp.register_async_ffi(
+        "double",
+        create_async_ffi(|_args, _state| async { Ok(json!("async")) }),
+    );
+    interp.run(contract).await.unwrap();
+    assert_eq!(interp.current_state().get("out"), Some(&json!("sync")));
+}
+
+#[tokio::test]
+async fn synchronous_step_refuses_to_skip_a_pending_async_call() {
+    let (mut interp, _) = async_interpreter(Duration::ZERO);
+    let error = loop {
+        match interp.step() {
+            Ok(outcome) => assert_eq!(outcome.status, StepStatus::Running),
+            Err(error) => break error,
+        }
+    };
+    assert!(matches!(error, InterpreterError::InvalidOperation(_)));
+
+    let (mut interp, _) = async_interpreter(Duration::ZERO);
+    while interp.step_async().await.unwrap().status == StepStatus::Running {}
+    assert_eq!(interp.current_state().get("out"), Some(&json!(42.0)));
+}
+
+#[tokio::test]
+async fn slow_async_ffi_is_cut_off_by_the_wall_clock_budget() {
+    let (mut interp, contract) = async_interpreter(Duration::from_secs(5));
+    interp.set_max_duration(Some(Duration::from_millis(50)));
+    let error = interp.run(contract).await.unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<InterpreterError>(),
+        Some(InterpreterError::Timeout { .. })
+    ));
+}
@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use serde_json::json;
+use sleet::flows::definition::{BlockDefinition, BlockType, FlowDefinition};
+use sleet::runtime::{ExecutionStatus, FfiRegistry, RemarkableInterpreter};
+use sleet::{convert_contract, FlowTranspiler};
+
+fn executor() -> RemarkableInterpreter {
+    let mut flow = FlowDefinition::new("greeting", "ask");
+    flow.set_initial_state(json!({ "visits": 0 }))
+        .add_block(BlockDefinition::new(
+            "ask",
+            BlockType::AwaitInput {
+                interaction_id: "ask_name".to_string(),
+                agent_id: "user".to_string(),
+                prompt: "\"What is your name?\"".to_string(),
+                state_key: "name".to_string(),
+                next_block: "count".to_string(),
+            },
+        ))
+        .add_block(BlockDefinition::new(
+            "count",
+            BlockType::Compute {
+                expression: "state.visits + 1".to_string(),
+                output_key: "visits".to_string(),
+                next_block: "end".to_string(),
+            },
+        ))
+        .add_block(BlockDefinition::new("end", BlockType::Terminate));
+    let contract = convert_contract(FlowTranspiler::transpile(&flow).unwrap()).unwrap();
+    RemarkableInterpreter::new(1_000, &contract, FfiRegistry::new()).unwrap()
+}
+
+#[test]
+fn duplicate_resumption_advances_once() {
+    let mut exec = executor();
+    match exec.run_until_paused().unwrap() {
+        ExecutionStatus::AwaitingInput { interaction_id, .. } => {
+            assert_eq!(interaction_id, "ask_name")
+        }
+        other => panic!("expected AwaitingInput, got {other:?}"),
+    }
+
+    exec.resume_with_input("ask_name", json!("Ada")).unwrap();
+    let first = exec.run_until_paused().unwrap();
+    let state_after_first = exec.state().clone();
+    let gas_after_first = exec.gas_used();
+    assert!(exec.has_resumed("ask_name"));
+
+    exec.resume_with_input("ask_name", json!("Bob")).unwrap();
+    let second = exec.run_until_paused().unwrap();
+    assert_eq!(
+        serde_json::to_value(&first).unwrap(),
+        serde_json::to_value(&second).unwrap()
+    );
+    assert_eq!(exec.state(), &state_after_first);
+    assert_eq!(exec.state()["name"], json!("Ada"));
+    assert_eq!(exec.state()["visits"], json!(1));
+    assert_eq!(exec.gas_used(), gas_after_first);
+}
+
+#[test]
+fn unknown_interaction_is_still_rejected() {
+    let mut exec = executor();
+    exec.run_until_paused().unwrap();
+    assert!(exec.resume_with_input("never_asked", json!(1)).is_err());
+    assert!(exec.pending_await().is_some());
+}
@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use serde_json::json;
+use sleet::ast::DYNAMIC_TARGET_META;
+use sleet::convert_contract;
+use sleet::transpiler::orchestration::ast::{AstNode, Contract, Literal, Op};
+use std::collections::HashMap;
+
+fn contract(start: AstNode) -> Contract {
+    Contract {
+        version: "1.0".to_string(),
+        initial_state: AstNode::from(Op::Literal(Literal::Object(HashMap::new()))),
+        start_block_id: "start".to_string(),
+        blocks: HashMap::from([
+            ("start".to_string(), start),
+            ("end".to_string(), AstNode::from(Op::Terminate)),
+        ]),
+        participants: vec![],
+        permissions: HashMap::new(),
+    }
+}
+
+#[test]
+fn static_jump_to_missing_block_is_rejected() {
+    let start = AstNode::from(Op::Sequence(vec![AstNode::from(Op::SetNextBlock(
+        "nowhere".to_string(),
+    ))]));
+    let err = convert_contract(contract(start)).unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("nowhere"), "{message}");
+    assert!(message.contains("start"), "{message}");
+}
+
+#[test]
+fn static_jump_to_existing_block_passes() {
+    let start = AstNode::from(Op::SetNextBlock("end".to_string()));
+    let converted = convert_contract(contract(start)).unwrap();
+    assert_eq!(converted.blocks.len(), 2);
+}
+
+#[test]
+fn dynamic_targets_can_opt_out() {
+    let mut jump = AstNode::from(Op::SetNextBlock("computed_later".to_string()));
+    jump.metadata
+        .insert(DYNAMIC_TARGET_META.to_string(), json!(true));
+    assert!(convert_contract(contract(jump)).is_ok());
+}
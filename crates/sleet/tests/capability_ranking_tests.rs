@@ -0,0 +1,100 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use sleet::agents::schemas::TechnicalSkill;
+use sleet::{Agent, AgentCapabilities, CapabilityMatcher, CapabilityMatcherConfig, MatchMode};
+
+fn skill(name: &str, proficiency: f64) -> TechnicalSkill {
+    TechnicalSkill {
+        name: name.to_string(),
+        proficiency,
+        experience_years: 1.0,
+        domains: Vec::new(),
+    }
+}
+
+fn agent(id: &str, skills: &[&str]) -> Agent {
+    let mut agent = Agent::new(id, "engineer", "general");
+    agent.id = id.to_string();
+    agent.capabilities.technical_skills = skills.iter().map(|name| skill(name, 0.9)).collect();
+    agent
+}
+
+fn requested() -> AgentCapabilities {
+    AgentCapabilities {
+        technical_skills: vec![
+            skill("rust", 0.8),
+            skill("sql", 0.8),
+            skill("testing", 0.8),
+            skill("design", 0.8),
+        ],
+        ..AgentCapabilities::default()
+    }
+}
+
+fn weighted(min_score: f64) -> CapabilityMatcher {
+    CapabilityMatcher::new().with_config(CapabilityMatcherConfig {
+        match_mode: MatchMode::Weighted { min_score },
+        ..CapabilityMatcherConfig::default()
+    })
+}
+
+fn team() -> Vec<Agent> {
+    vec![
+        agent("two_of_four", &["rust", "sql"]),
+        agent("three_of_four", &["rust", "sql", "testing"]),
+        agent("all_four", &["rust", "sql", "testing", "design"]),
+    ]
+}
+
+#[test]
+fn weighted_mode_ranks_partial_matches_by_coverage() {
+    let ranked = weighted(0.0).rank_agents(&requested(), &team());
+
+    assert_eq!(
+        ranked,
+        vec![
+            ("all_four".to_string(), 1.0),
+            ("three_of_four".to_string(), 0.75),
+            ("two_of_four".to_string(), 0.5),
+        ]
+    );
+}
+
+#[test]
+fn min_score_filters_agents_below_the_threshold() {
+    let ranked = weighted(0.7).rank_agents(&requested(), &team());
+
+    let ids: Vec<&str> = ranked.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(ids, vec!["all_four", "three_of_four"]);
+}
+
+#[test]
+fn exact_mode_is_the_default_and_keeps_only_full_matches() {
+    let matcher = CapabilityMatcher::new();
+
+    assert_eq!(matcher.config.match_mode, MatchMode::Exact);
+    assert_eq!(
+        matcher.rank_agents(&requested(), &team()),
+        vec![("all_four".to_string(), 1.0)]
+    );
+}
+
+#[test]
+fn weaker_proficiency_earns_partial_credit() {
+    let mut novice = agent("novice", &["rust", "sql", "testing", "design"]);
+    novice.capabilities.technical_skills[3].proficiency = 0.4;
+
+    let ranked = weighted(0.0).rank_agents(&requested(), &[novice]);
+
+    assert_eq!(ranked, vec![("novice".to_string(), 0.875)]);
+}
@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use serde_json::json;
+use sleet::flows::definition::{BlockDefinition, BlockType, FlowDefinition};
+use sleet::runtime::{ExecutionStatus, FfiRegistry, InterpreterError, RemarkableInterpreter};
+use sleet::tasks::{TaskConfig, TaskProposal, TaskSystem, TaskSystemConfig};
+use sleet::{convert_contract, Clock, FlowTranspiler, Op, TestClock};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn executor_with_timeout(clock: &TestClock, timeout_ms: u64) -> RemarkableInterpreter {
+    let mut flow = FlowDefinition::new("approval", "ask");
+    flow.add_block(BlockDefinition::new(
+        "ask",
+        BlockType::AwaitInput {
+            interaction_id: "approve".to_string(),
+            agent_id: "reviewer".to_string(),
+            prompt: "\"Approve the release?\"".to_string(),
+            state_key: "approved".to_string(),
+            next_block: "end".to_string(),
+        },
+    ))
+    .add_block(BlockDefinition::new("end", BlockType::Terminate));
+
+    let mut contract = convert_contract(FlowTranspiler::transpile(&flow).unwrap()).unwrap();
+    if let Op::Sequence(nodes) = &mut contract.blocks.get_mut("ask").unwrap().op {
+        if let Op::Await { timeout_ms: t, .. } = &mut nodes[0].op {
+            *t = Some(timeout_ms);
+        }
+    }
+
+    let mut executor = RemarkableInterpreter::new(1_000, &contract, FfiRegistry::new()).unwrap();
+    executor.set_clock(Arc::new(clock.clone()));
+    executor
+}
+
+#[test]
+fn advancing_the_test_clock_times_out_an_await() {
+    let clock = TestClock::default();
+    let mut executor = executor_with_timeout(&clock, 5_000);
+    assert!(matches!(
+        executor.run_until_paused().unwrap(),
+        ExecutionStatus::AwaitingInput { .. }
+    ));
+
+    clock.advance(Duration::from_millis(4_999));
+    assert!(!executor.expire_pending_await().unwrap());
+    assert!(executor.pending_await().is_some());
+
+    clock.advance(Duration::from_millis(1));
+    match executor.expire_pending_await() {
+        Err(InterpreterError::AwaitTimedOut { interaction_id }) => {
+            assert_eq!(interaction_id, "approve")
+        }
+        other => panic!("expected AwaitTimedOut, got {other:?}"),
+    }
+    assert!(executor.pending_await().is_none());
+}
+
+#[test]
+fn input_after_the_deadline_is_rejected() {
+    let clock = TestClock::default();
+    let mut executor = executor_with_timeout(&clock, 1_000);
+    executor.run_until_paused().unwrap();
+
+    clock.advance(Duration::from_secs(2));
+    let error = executor
+        .resume_with_input("approve", json!(true))
+        .unwrap_err();
+    assert!(matches!(error, InterpreterError::AwaitTimedOut { .. }));
+    assert!(executor.state().get("approved").is_none());
+}
+
+#[test]
+fn input_before_the_deadline_resumes_normally() {
+    let clock = TestClock::default();
+    let mut executor = executor_with_timeout(&clock, 1_000);
+    executor.run_until_paused().unwrap();
+
+    clock.advance(Duration::from_millis(500));
+    executor.resume_with_input("approve", json!(true)).unwrap();
+    let status = executor.run_until_paused().unwrap();
+    assert!(matches!(status, ExecutionStatus::Completed(_)));
+    assert_eq!(executor.state()["approved"], json!(true));
+}
+
+#[test]
+fn task_deadlines_follow_the_injected_clock() {
+    let clock = TestClock::default();
+    let mut system =
+        TaskSystem::new(TaskSystemConfig::default()).with_clock(Arc::new(clock.clone()));
+    let task_id = system
+        .create_task(TaskConfig {
+            title: "ship".to_string(),
+            ..TaskConfig::default()
+        })
+        .unwrap()
+        .id;
+    system
+        .submit_proposal(
+            TaskProposal::new("agent".to_string(), task_id)
+                .with_deadline(clock.now() + chrono::Duration::minutes(10)),
+        )
+        .unwrap();
+
+    assert!(system.overdue_proposals().is_empty());
+    assert!(!system.ready_queue()[0].overdue);
+
+    clock.advance(Duration::from_secs(11 * 60));
+    assert_eq!(system.overdue_proposals().len(), 1);
+    assert!(system.ready_queue()[0].overdue);
+}
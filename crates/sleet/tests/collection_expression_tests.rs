@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::{compute_flow_with_state, contract};
+use serde_json::{json, Value};
+use sleet::ast::Op;
+use sleet::flows::definition::FlowDefinition;
+use sleet::{execute_flow_report, FlowTranspiler, TranspileCache};
+
+fn flow(expression: &str) -> FlowDefinition {
+    compute_flow_with_state(
+        "collections",
+        json!({ "values": [1, 2, 3, 4, 5], "factor": 2 }),
+        expression,
+        "result",
+    )
+}
+
+async fn evaluate(expression: &str) -> Value {
+    let report = execute_flow_report(flow(expression), 1_000, None, &TranspileCache::new())
+        .await
+        .unwrap();
+    assert!(report.output("x").is_none(), "element binding must not leak");
+    report.result().cloned().unwrap()
+}
+
+#[tokio::test]
+async fn map_doubles_every_element() {
+    assert_eq!(
+        evaluate("state.values.map(x => x * 2)").await,
+        json!([2, 4, 6, 8, 10])
+    );
+}
+
+#[tokio::test]
+async fn filter_keeps_even_elements() {
+    assert_eq!(
+        evaluate("state.values.filter(x => x % 2 == 0)").await,
+        json!([2, 4])
+    );
+}
+
+#[tokio::test]
+async fn reduce_folds_with_an_initial_value() {
+    assert_eq!(
+        evaluate("state.values.reduce((acc, x) => acc + x * state.factor, 10)").await,
+        json!(40)
+    );
+}
+
+#[test]
+fn map_expressions_transpile_to_map_ops() {
+    let contract = contract(&flow("state.values.map(x => x * 2)"));
+    let mut maps = 0;
+    contract.blocks["start"].walk(&mut |node| {
+        if matches!(node.op, Op::Map { .. }) {
+            maps += 1;
+        }
+    });
+    assert_eq!(maps, 1);
+}
+
+#[test]
+fn lambdas_outside_collection_calls_are_rejected() {
+    let error = FlowTranspiler::transpile(&flow("utils.apply(x => x + 1)")).unwrap_err();
+    assert!(error.to_string().contains("map, filter or reduce"));
+    let error = FlowTranspiler::transpile(&flow("state.values.map((a, b) => a)")).unwrap_err();
+    assert!(error.to_string().contains("one parameter"));
+}
@@ -0,0 +1,220 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+#![allow(dead_code)]
+
+use async_trait::async_trait;
+use llm_contracts::{
+    LLMError, LLMRequest, LLMResponse, LLMResult, ResponseMetadata, StreamChunk, Usage,
+};
+use serde_json::Value;
+use sleet::orchestration::coordinator::StorageConfig;
+use sleet::{
+    convert_contract, BlockDefinition, BlockType, Contract, FlowDefinition, FlowTranspiler,
+    OrchestrationConfig,
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use stele::llm::core::LLMAdapter;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+pub fn coordinator_config() -> OrchestrationConfig {
+    let mut config = OrchestrationConfig::default();
+    config.enable_persistence = false;
+    config.storage_config = StorageConfig {
+        session_storage_path: None,
+        checkpoint_storage_path: None,
+        log_storage_path: None,
+    };
+    config
+}
+
+pub fn compute(id: &str, expression: &str, output_key: &str, next: &str) -> BlockDefinition {
+    BlockDefinition::new(
+        id,
+        BlockType::Compute {
+            expression: expression.to_string(),
+            output_key: output_key.to_string(),
+            next_block: next.to_string(),
+        },
+    )
+}
+
+pub fn await_input(
+    id: &str,
+    interaction_id: &str,
+    prompt: &str,
+    state_key: &str,
+    next: &str,
+) -> BlockDefinition {
+    BlockDefinition::new(
+        id,
+        BlockType::AwaitInput {
+            interaction_id: interaction_id.to_string(),
+            agent_id: "reviewer".to_string(),
+            prompt: prompt.to_string(),
+            state_key: state_key.to_string(),
+            next_block: next.to_string(),
+        },
+    )
+}
+
+pub fn conditional(
+    id: &str,
+    condition: &str,
+    true_block: &str,
+    false_block: &str,
+) -> BlockDefinition {
+    BlockDefinition::new(
+        id,
+        BlockType::Conditional {
+            condition: condition.to_string(),
+            true_block: true_block.to_string(),
+            false_block: false_block.to_string(),
+        },
+    )
+}
+
+pub fn terminate(id: &str) -> BlockDefinition {
+    BlockDefinition::new(id, BlockType::Terminate)
+}
+
+pub fn flow(id: &str, blocks: Vec<BlockDefinition>) -> FlowDefinition {
+    let start = blocks
+        .first()
+        .map(|block| block.id.clone())
+        .unwrap_or_default();
+    let mut flow = FlowDefinition::new(id, start);
+    for block in blocks {
+        flow.add_block(block);
+    }
+    flow
+}
+
+pub fn compute_flow(id: &str, expression: &str, output_key: &str) -> FlowDefinition {
+    flow(
+        id,
+        vec![
+            compute("start", expression, output_key, "end"),
+            terminate("end"),
+        ],
+    )
+}
+
+pub fn compute_flow_with_state(
+    id: &str,
+    initial_state: Value,
+    expression: &str,
+    output_key: &str,
+) -> FlowDefinition {
+    let mut flow = compute_flow(id, expression, output_key);
+    flow.set_initial_state(initial_state);
+    flow
+}
+
+pub fn approval_flow(prompt: &str) -> FlowDefinition {
+    flow(
+        "approval",
+        vec![
+            await_input("ask", "approve", prompt, "answer", "end"),
+            terminate("end"),
+        ],
+    )
+}
+
+pub fn contract(flow: &FlowDefinition) -> Contract {
+    convert_contract(FlowTranspiler::transpile(flow).unwrap()).unwrap()
+}
+
+pub fn generate(
+    id: &str,
+    prompt_expression: &str,
+    output_key: &str,
+    next: &str,
+) -> BlockDefinition {
+    BlockDefinition::new(
+        id,
+        BlockType::Generate {
+            prompt_expression: prompt_expression.to_string(),
+            model: Some("stub-model".to_string()),
+            output_key: output_key.to_string(),
+            next_block: next.to_string(),
+        },
+    )
+}
+
+#[derive(Default)]
+pub struct StubAdapter {
+    pub requests: Mutex<Vec<LLMRequest>>,
+    pub called_at: Mutex<Vec<Instant>>,
+}
+
+impl StubAdapter {
+    pub const TOKENS_PER_CALL: u32 = 10;
+
+    pub fn prompts(&self) -> Vec<String> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|request| request.prompt.clone())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl LLMAdapter for StubAdapter {
+    async fn generate_response(&self, request: LLMRequest) -> LLMResult<LLMResponse> {
+        self.called_at.lock().unwrap().push(Instant::now());
+        let content = format!("completion #{}", self.requests.lock().unwrap().len() + 1);
+        let request_id = request.id;
+        self.requests.lock().unwrap().push(request);
+        Ok(LLMResponse {
+            id: Uuid::new_v4(),
+            request_id,
+            content,
+            model_used: "stub-model".to_string(),
+            provider_used: "stub".to_string(),
+            usage: Usage {
+                prompt_tokens: 4,
+                completion_tokens: Self::TOKENS_PER_CALL - 4,
+                total_tokens: Self::TOKENS_PER_CALL,
+            },
+            metadata: ResponseMetadata {
+                processing_time_ms: 0,
+                model_selection_reason: "stub".to_string(),
+                security_checks_passed: true,
+                cached: false,
+                retry_count: 0,
+                cost_estimate: None,
+                additional_data: HashMap::new(),
+            },
+            created_at: chrono::Utc::now(),
+        })
+    }
+
+    async fn generate_streaming_response(
+        &self,
+        _request: LLMRequest,
+    ) -> LLMResult<tokio::sync::mpsc::Receiver<LLMResult<StreamChunk>>> {
+        Err(LLMError::Provider("streaming is not stubbed".to_string()))
+    }
+
+    async fn get_available_models(&self) -> LLMResult<Vec<String>> {
+        Ok(vec!["stub-model".to_string()])
+    }
+
+    async fn health_check(&self) -> LLMResult<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use serde_json::json;
+use sleet::runtime::{
+    disassemble, disassemble_to_string, BytecodeAssembler, DisassemblyError, OpCode, Operand,
+};
+
+fn conditional_program() -> Vec<u8> {
+    let mut asm = BytecodeAssembler::new();
+    asm.load_var("state.x").push_literal(&json!(10)).unwrap().greater_than();
+    let to_else = asm.jump_if_false();
+    asm.push_literal(&json!("big")).unwrap();
+    let to_end = asm.jump();
+    asm.patch_jump(to_else).unwrap();
+    asm.push_literal(&json!("small")).unwrap();
+    asm.patch_jump(to_end).unwrap();
+    asm.halt();
+    asm.into_bytecode()
+}
+
+#[test]
+fn conditional_program_disassembles_with_resolved_jumps() {
+    let instructions = disassemble(&conditional_program()).unwrap();
+    let opcodes: Vec<OpCode> = instructions.iter().map(|i| i.opcode).collect();
+    assert_eq!(
+        opcodes,
+        vec![
+            OpCode::LoadVar,
+            OpCode::Push,
+            OpCode::GreaterThan,
+            OpCode::JumpIfFalse,
+            OpCode::Push,
+            OpCode::Jump,
+            OpCode::Push,
+            OpCode::Halt,
+        ]
+    );
+    assert_eq!(instructions[0].operand, Operand::Path("state.x".to_string()));
+    assert_eq!(instructions[1].operand, Operand::Value(json!(10)));
+    assert_eq!(instructions[3].operand, Operand::Jump { offset: 15, target: 40 });
+    assert_eq!(instructions[5].operand, Operand::Jump { offset: 12, target: 52 });
+    assert_eq!(instructions[6].offset, 40);
+    assert_eq!(instructions[6].operand, Operand::Value(json!("small")));
+    assert_eq!(instructions[7].offset, 52);
+}
+
+#[test]
+fn listing_prints_one_instruction_per_line() {
+    let listing = disassemble_to_string(&conditional_program()).unwrap();
+    let lines: Vec<&str> = listing.lines().collect();
+    assert_eq!(lines.len(), 8);
+    assert_eq!(lines[0], "0000  LoadVar state.x");
+    assert_eq!(lines[1], "0012  Push 10");
+    assert_eq!(lines[3], "0020  JumpIfFalse +15 -> 0040");
+    assert_eq!(lines[4], "0025  Push \"big\"");
+    assert_eq!(lines[7], "0052  Halt");
+}
+
+#[test]
+fn ffi_calls_show_name_and_arity() {
+    let mut bytecode = vec![OpCode::CallFfi as u8];
+    bytecode.extend_from_slice(&5u32.to_le_bytes());
+    bytecode.extend_from_slice(b"fetch");
+    bytecode.push(2);
+    let instructions = disassemble(&bytecode).unwrap();
+    assert_eq!(
+        instructions[0].operand,
+        Operand::Ffi {
+            name: "fetch".to_string(),
+            arg_count: 2
+        }
+    );
+    assert_eq!(instructions[0].to_string(), "0000  CallFfi fetch/2");
+}
+
+#[test]
+fn assembler_emits_named_ffi_calls() {
+    let mut asm = BytecodeAssembler::new();
+    asm.load_var("state.url").call_ffi("fetch", 1).unwrap();
+    let listing = disassemble_to_string(&asm.into_bytecode()).unwrap();
+    assert_eq!(listing.lines().last(), Some("0014  CallFfi fetch/1"));
+    assert!(BytecodeAssembler::new().call_ffi("fetch", 256).is_err());
+}
+
+#[test]
+fn malformed_bytecode_is_rejected() {
+    assert_eq!(
+        disassemble(&[OpCode::Add as u8, 255]),
+        Err(DisassemblyError::InvalidOpcode { offset: 1, byte: 255 })
+    );
+    assert_eq!(
+        disassemble(&[OpCode::Jump as u8, 1, 0]),
+        Err(DisassemblyError::Truncated {
+            offset: 0,
+            opcode: OpCode::Jump
+        })
+    );
+    let mut bad_push = vec![OpCode::Push as u8];
+    bad_push.extend_from_slice(&3u32.to_le_bytes());
+    bad_push.extend_from_slice(b"{{{");
+    assert!(matches!(
+        disassemble(&bad_push),
+        Err(DisassemblyError::InvalidOperand { offset: 0, .. })
+    ));
+}
@@ -0,0 +1,101 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::{compute, compute_flow, conditional, contract, terminate};
+use serde_json::json;
+use sleet::flows::definition::FlowDefinition;
+use sleet::runtime::{ExecutionStatus, FfiRegistry, RemarkableInterpreter};
+use sleet::{execute_flow_report, execute_flow_with_cache, TranspileCache};
+
+fn flow() -> FlowDefinition {
+    compute_flow("report", "6 * 7", "result")
+}
+
+#[tokio::test]
+async fn report_exposes_result_without_state_inspection() {
+    let report = execute_flow_report(flow(), 1_000, None, &TranspileCache::new())
+        .await
+        .unwrap();
+
+    assert!(report.is_completed());
+    assert_eq!(report.result(), Some(&json!(42)));
+    assert_eq!(report.blocks_executed, 2);
+    assert!(report.gas_used > 0);
+}
+
+#[tokio::test]
+async fn report_converts_back_into_status() {
+    let report = execute_flow_report(flow(), 1_000, None, &TranspileCache::new())
+        .await
+        .unwrap();
+    let final_state = report.final_state.clone();
+
+    match report.into_status() {
+        ExecutionStatus::Completed(value) => {
+            assert_eq!(serde_json::Value::from(value), final_state)
+        }
+        other => panic!("expected Completed, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn execute_flow_returns_a_completed_status_carrying_the_final_state() {
+    let status = execute_flow_with_cache(flow(), 1_000, None, &TranspileCache::new())
+        .await
+        .unwrap();
+
+    assert!(matches!(status, ExecutionStatus::Completed(_)));
+    assert_eq!(status.result(), Some(&json!(42)));
+    assert_eq!(
+        status.final_state().cloned().map(serde_json::Value::from),
+        Some(json!({ "result": 42 }))
+    );
+}
+
+#[tokio::test]
+async fn interpreter_run_completes_with_the_terminal_state() {
+    let contract = contract(&flow());
+    let mut interp = RemarkableInterpreter::new(1_000, &contract, FfiRegistry::new()).unwrap();
+
+    let status = interp.run(contract.clone()).await.unwrap();
+
+    assert!(matches!(status, ExecutionStatus::Completed(_)));
+    assert_eq!(status.result(), Some(&json!(42)));
+    assert_eq!(status.final_state(), Some(interp.current_state()));
+}
+
+#[tokio::test]
+async fn execute_flow_completes_with_the_state_left_by_the_last_block() {
+    let mut flow = common::flow(
+        "branching_report",
+        vec![
+            conditional("start", "state.amount > 100", "large", "end"),
+            compute("large", "state.amount * 2", "result", "end"),
+            terminate("end"),
+        ],
+    );
+    flow.set_initial_state(json!({ "amount": 120 }));
+
+    let report = execute_flow_report(flow, 1_000, None, &TranspileCache::new())
+        .await
+        .unwrap();
+    let ExecutionStatus::Completed(state) = report.status() else {
+        panic!("expected Completed, got {:?}", report.status());
+    };
+    let state = serde_json::Value::from(state.clone());
+    assert_eq!(state, report.final_state);
+    assert_eq!(state["amount"], json!(120));
+    assert_eq!(state["result"], json!(240));
+    assert_eq!(report.blocks_executed, 3);
+}
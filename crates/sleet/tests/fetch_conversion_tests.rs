@@ -0,0 +1,123 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use serde_json::json;
+use sleet::convert_contract;
+use sleet::runtime::{ExecutionStatus, FfiRegistry, RemarkableInterpreter};
+use sleet::transpiler::orchestration::ast::{AstNode, Contract, Literal, Op, PathSegment};
+use std::collections::HashMap;
+
+fn number(n: f64) -> AstNode {
+    AstNode::from(Op::Literal(Literal::Number(n)))
+}
+
+fn initial_state() -> AstNode {
+    let b = Literal::Array(vec![number(7.0), number(9.0)]);
+    let a = Literal::Object(HashMap::from([("b".to_string(), AstNode::from(Op::Literal(b)))]));
+    AstNode::from(Op::Literal(Literal::Object(HashMap::from([(
+        "a".to_string(),
+        AstNode::from(Op::Literal(a)),
+    )]))))
+}
+
+fn a_b_0() -> Vec<PathSegment> {
+    vec![
+        PathSegment::State,
+        PathSegment::Key("a".to_string()),
+        PathSegment::Key("b".to_string()),
+        PathSegment::Index(0),
+    ]
+}
+
+fn state_key(key: &str) -> Vec<PathSegment> {
+    vec![PathSegment::State, PathSegment::Key(key.to_string())]
+}
+
+fn run(steps: Vec<AstNode>) -> serde_json::Value {
+    let mut steps = steps;
+    steps.push(AstNode::from(Op::Terminate));
+    let contract = Contract {
+        version: "1.0".to_string(),
+        initial_state: initial_state(),
+        start_block_id: "start".to_string(),
+        blocks: HashMap::from([("start".to_string(), AstNode::from(Op::Sequence(steps)))]),
+        participants: vec![],
+        permissions: HashMap::new(),
+    };
+    let converted = convert_contract(contract).unwrap();
+    let mut executor = RemarkableInterpreter::new(1_000, &converted, FfiRegistry::new()).unwrap();
+    assert!(matches!(
+        executor.run_until_paused().unwrap(),
+        ExecutionStatus::Completed(_)
+    ));
+    executor.state().clone()
+}
+
+#[test]
+fn fetch_compiles_to_load_bytecode() {
+    let converted = sleet::convert_op(Op::Fetch(a_b_0())).unwrap();
+    match converted {
+        sleet::Op::Evaluate { bytecode, .. } => assert!(!bytecode.is_empty()),
+        other => panic!("expected Evaluate, got {other:?}"),
+    }
+}
+
+#[test]
+fn assigning_a_fetched_nested_value_round_trips() {
+    let state = run(vec![AstNode::from(Op::Assign {
+        path: state_key("copy"),
+        value: Box::new(AstNode::from(Op::Fetch(a_b_0()))),
+    })]);
+    assert_eq!(state["copy"], json!(7));
+    assert_eq!(state["a"], json!({ "b": [7, 9] }));
+}
+
+#[test]
+fn fetched_values_flow_into_expressions() {
+    let state = run(vec![AstNode::from(Op::Assign {
+        path: state_key("next"),
+        value: Box::new(AstNode::from(Op::Add(
+            Box::new(AstNode::from(Op::Fetch(a_b_0()))),
+            Box::new(number(1.0)),
+        ))),
+    })]);
+    assert_eq!(state["next"], json!(8));
+}
+
+#[test]
+fn assigning_a_literal_writes_the_value() {
+    let state = run(vec![AstNode::from(Op::Assign {
+        path: state_key("counter"),
+        value: Box::new(number(3.0)),
+    })]);
+    assert_eq!(state["counter"], json!(3));
+}
+
+#[test]
+fn dynamic_offsets_are_resolved_at_runtime() {
+    let mut path = a_b_0();
+    path.pop();
+    path.push(PathSegment::DynamicOffset(Box::new(AstNode::from(Op::Fetch(
+        state_key("i"),
+    )))));
+    let state = run(vec![
+        AstNode::from(Op::Assign {
+            path: state_key("i"),
+            value: Box::new(number(1.0)),
+        }),
+        AstNode::from(Op::Assign {
+            path: state_key("picked"),
+            value: Box::new(AstNode::from(Op::Fetch(path))),
+        }),
+    ]);
+    assert_eq!(state["picked"], json!(9));
+}
@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use serde_json::{json, Value as JsonValue};
+use sleet::runtime::{
+    create_ergonomic_ffi, FfiRegistry, FfiRegistryExt, InterpreterError, OnConflict, Value,
+};
+
+fn registry(entries: &[(&str, &'static str)]) -> FfiRegistry {
+    entries
+        .iter()
+        .map(|(name, tag)| {
+            let tag = *tag;
+            (
+                name.to_string(),
+                create_ergonomic_ffi(move |_args, _state| Ok(json!(tag))),
+            )
+        })
+        .collect()
+}
+
+fn call(registry: &FfiRegistry, name: &str) -> JsonValue {
+    registry[name](&[], &Value::Null).unwrap().into()
+}
+
+fn math_and_strings() -> Vec<FfiRegistry> {
+    vec![
+        registry(&[("abs", "math"), ("format", "math")]),
+        registry(&[("upper", "string"), ("format", "string")]),
+    ]
+}
+
+#[test]
+fn error_policy_rejects_overlapping_names() {
+    match FfiRegistry::compose(math_and_strings(), OnConflict::Error) {
+        Err(InterpreterError::FfiConflict(name)) => assert_eq!(name, "format"),
+        Err(other) => panic!("expected FfiConflict, got {other:?}"),
+        Ok(_) => panic!("expected FfiConflict"),
+    }
+}
+
+#[test]
+fn first_wins_keeps_earliest_registration() {
+    let composed = FfiRegistry::compose(math_and_strings(), OnConflict::FirstWins).unwrap();
+    assert_eq!(composed.len(), 3);
+    assert_eq!(call(&composed, "format"), json!("math"));
+    assert_eq!(call(&composed, "upper"), json!("string"));
+}
+
+#[test]
+fn last_wins_keeps_latest_registration() {
+    let composed = FfiRegistry::compose(math_and_strings(), OnConflict::LastWins).unwrap();
+    assert_eq!(composed.len(), 3);
+    assert_eq!(call(&composed, "format"), json!("string"));
+    assert_eq!(call(&composed, "abs"), json!("math"));
+}
+
+#[test]
+fn disjoint_registries_compose_under_default_policy() {
+    let composed = FfiRegistry::compose(
+        vec![registry(&[("abs", "math")]), registry(&[("upper", "string")])],
+        OnConflict::default(),
+    )
+    .unwrap();
+    assert_eq!(composed.len(), 2);
+}
@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::coordinator_config;
+use serde_json::json;
+use sleet::orchestration::{EventReceiver, FlowEvent};
+use sleet::runtime::ExecutionStatus;
+use sleet::{BlockDefinition, BlockType, FlowDefinition, OrchestrationCoordinator};
+
+fn demo_flow() -> FlowDefinition {
+    let mut flow = FlowDefinition::new("demo", "start");
+    flow.add_block(BlockDefinition::new(
+        "start",
+        BlockType::Compute {
+            expression: "\"hello\"".to_string(),
+            output_key: "greeting".to_string(),
+            next_block: "await_user".to_string(),
+        },
+    ))
+    .add_block(BlockDefinition::new(
+        "await_user",
+        BlockType::AwaitInput {
+            interaction_id: "user_input".to_string(),
+            agent_id: "human".to_string(),
+            prompt: "\"Please provide input\"".to_string(),
+            state_key: "user_text".to_string(),
+            next_block: "done".to_string(),
+        },
+    ))
+    .add_block(BlockDefinition::new("done", BlockType::Terminate));
+    flow
+}
+
+fn describe(event: &FlowEvent) -> String {
+    match event {
+        FlowEvent::BlockEntered { block_id, .. } => format!("entered {block_id}"),
+        FlowEvent::BlockCompleted { block_id, .. } => format!("completed {block_id}"),
+        FlowEvent::AwaitIssued {
+            block_id,
+            interaction_id,
+            ..
+        } => format!("await issued {block_id}/{interaction_id}"),
+        FlowEvent::AwaitResolved {
+            block_id,
+            interaction_id,
+            ..
+        } => format!("await resolved {block_id}/{interaction_id}"),
+        FlowEvent::FlowCompleted { flow_id, .. } => format!("flow completed {flow_id}"),
+        FlowEvent::FlowFailed { flow_id, .. } => format!("flow failed {flow_id}"),
+    }
+}
+
+fn drain(events: &mut EventReceiver) -> Vec<FlowEvent> {
+    std::iter::from_fn(|| events.try_recv()).collect()
+}
+
+#[tokio::test]
+async fn subscriber_observes_the_demo_flow_lifecycle() {
+    let coordinator = OrchestrationCoordinator::new(coordinator_config())
+        .await
+        .unwrap();
+    let mut events = coordinator.subscribe();
+
+    let status = coordinator
+        .execute_flow(demo_flow().into(), None)
+        .await
+        .unwrap();
+    let session_id = match status {
+        ExecutionStatus::AwaitingInput { session_id, .. } => session_id,
+        other => panic!("expected the flow to await input, got {other:?}"),
+    };
+    coordinator
+        .resume_session(&session_id, json!("hi there"))
+        .await
+        .unwrap();
+
+    let observed = drain(&mut events);
+    let described: Vec<String> = observed.iter().map(describe).collect();
+    assert_eq!(
+        described,
+        vec![
+            "entered start",
+            "completed start",
+            "entered await_user",
+            "await issued await_user/user_input",
+            "await resolved await_user/user_input",
+            "entered await_user",
+            "completed await_user",
+            "entered done",
+            "completed done",
+            "flow completed demo",
+        ]
+    );
+    assert!(observed.iter().all(|event| event.session_id() == session_id));
+    assert!(observed
+        .windows(2)
+        .all(|pair| pair[0].timestamp() <= pair[1].timestamp()));
+}
+
+#[tokio::test]
+async fn failing_flow_still_reports_its_failure() {
+    let coordinator = OrchestrationCoordinator::new(coordinator_config())
+        .await
+        .unwrap();
+    let mut events = coordinator.subscribe();
+
+    let mut flow = FlowDefinition::new("broken", "start");
+    flow.add_block(BlockDefinition::new(
+        "start",
+        BlockType::Break {
+            loop_id: "missing_loop".to_string(),
+            output: None,
+        },
+    ))
+    .add_block(BlockDefinition::new("done", BlockType::Terminate));
+    coordinator
+        .execute_flow(flow.into(), None)
+        .await
+        .unwrap_err();
+
+    let observed = drain(&mut events);
+    let described: Vec<String> = observed.iter().map(describe).collect();
+    assert_eq!(described, vec!["entered start", "flow failed broken"]);
+    let FlowEvent::FlowFailed { error, .. } = &observed[1] else {
+        unreachable!();
+    };
+    assert!(error.contains("missing_loop"));
+}
+
+#[tokio::test]
+async fn dropped_receiver_does_not_stall_execution() {
+    let coordinator = OrchestrationCoordinator::new(coordinator_config())
+        .await
+        .unwrap();
+    drop(coordinator.subscribe());
+
+    let mut flow = FlowDefinition::new("quick", "done");
+    flow.add_block(BlockDefinition::new("done", BlockType::Terminate));
+    let status = coordinator.execute_flow(flow.into(), None).await.unwrap();
+
+    assert!(matches!(status, ExecutionStatus::Completed(_)));
+}
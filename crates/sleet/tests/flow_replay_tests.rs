@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::coordinator_config;
+use serde_json::json;
+use sleet::orchestration::{OrchestrationError, ReplaySource};
+use sleet::runtime::{ExecutionStatus, Value};
+use sleet::{BlockDefinition, BlockType, FlowDefinition, OrchestrationCoordinator};
+
+fn survey_flow() -> FlowDefinition {
+    let mut flow = FlowDefinition::new("survey", "start");
+    flow.add_block(BlockDefinition::new(
+        "start",
+        BlockType::Compute {
+            expression: "\"welcome\"".to_string(),
+            output_key: "greeting".to_string(),
+            next_block: "ask_name".to_string(),
+        },
+    ))
+    .add_block(BlockDefinition::new(
+        "ask_name",
+        BlockType::AwaitInput {
+            interaction_id: "name".to_string(),
+            agent_id: "human".to_string(),
+            prompt: "\"What is your name?\"".to_string(),
+            state_key: "user_name".to_string(),
+            next_block: "ask_colour".to_string(),
+        },
+    ))
+    .add_block(BlockDefinition::new(
+        "ask_colour",
+        BlockType::AwaitInput {
+            interaction_id: "colour".to_string(),
+            agent_id: "human".to_string(),
+            prompt: "\"What is your favourite colour?\"".to_string(),
+            state_key: "favourite_colour".to_string(),
+            next_block: "done".to_string(),
+        },
+    ))
+    .add_block(BlockDefinition::new("done", BlockType::Terminate));
+    flow
+}
+
+fn awaiting_session(status: ExecutionStatus) -> String {
+    match status {
+        ExecutionStatus::AwaitingInput { session_id, .. } => session_id,
+        other => panic!("expected the flow to await input, got {other:?}"),
+    }
+}
+
+fn final_state(status: ExecutionStatus) -> Value {
+    match status {
+        ExecutionStatus::Completed(state) => state,
+        other => panic!("expected the flow to complete, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn recorded_run_replays_to_the_same_final_state() {
+    let live = OrchestrationCoordinator::new(coordinator_config())
+        .await
+        .unwrap();
+    live.start_recording().await;
+
+    let status = live
+        .execute_flow(survey_flow().into(), None)
+        .await
+        .unwrap();
+    let session_id = awaiting_session(status);
+    let status = live
+        .resume_session(&session_id, json!("Ada"))
+        .await
+        .unwrap();
+    assert_eq!(awaiting_session(status), session_id);
+    let status = live
+        .resume_session(&session_id, json!({"name": "green", "hex": "#00ff00"}))
+        .await
+        .unwrap();
+    let recorded_state = final_state(status);
+
+    let recording = live.recording().await.unwrap();
+    assert_eq!(recording.responses("name"), [json!("Ada")]);
+    assert_eq!(
+        recording.responses("colour"),
+        [json!({"name": "green", "hex": "#00ff00"})]
+    );
+
+    let replay = OrchestrationCoordinator::with_replay(coordinator_config(), recording)
+        .await
+        .unwrap();
+    let status = replay
+        .execute_flow(survey_flow().into(), None)
+        .await
+        .unwrap();
+
+    assert_eq!(final_state(status), recorded_state);
+    let Value::Json(state) = recorded_state else {
+        panic!("expected the final state to be JSON");
+    };
+    assert_eq!(state["user_name"], json!("Ada"));
+    assert_eq!(state["favourite_colour"]["name"], json!("green"));
+}
+
+#[tokio::test]
+async fn replay_fails_when_an_await_is_missing_from_the_log() {
+    let mut source = ReplaySource::new();
+    source.respond("name", json!("Ada"));
+    let replay = OrchestrationCoordinator::with_replay(coordinator_config(), source)
+        .await
+        .unwrap();
+
+    let error = replay
+        .execute_flow(survey_flow().into(), None)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(&error, OrchestrationError::ReplayMiss(id) if id == "colour"));
+}
+
+#[test]
+fn recording_survives_serialisation() {
+    let mut source = ReplaySource::new();
+    source
+        .respond("name", json!("Ada"))
+        .respond("colour", json!("green"));
+
+    let encoded = serde_json::to_string(&source).unwrap();
+    let decoded: ReplaySource = serde_json::from_str(&encoded).unwrap();
+
+    assert_eq!(decoded, source);
+    assert_eq!(decoded.interaction_ids().collect::<Vec<_>>(), ["colour", "name"]);
+}
+
+#[tokio::test]
+async fn live_coordinator_does_not_record_by_default() {
+    let coordinator = OrchestrationCoordinator::new(coordinator_config())
+        .await
+        .unwrap();
+
+    assert!(coordinator.recording().await.is_none());
+}
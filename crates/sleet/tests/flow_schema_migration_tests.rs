@@ -0,0 +1,118 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::approval_flow;
+use serde_json::json;
+use sleet::flows::definition::{
+    BlockType, FlowDefinition, CURRENT_FLOW_SCHEMA_VERSION, MIN_FLOW_SCHEMA_VERSION,
+};
+use sleet::runtime::ExecutionStatus;
+use sleet::{execute_flow, FlowTranspiler, TranspilerError};
+use std::borrow::Cow;
+
+async fn awaited_prompt(flow: FlowDefinition) -> String {
+    match execute_flow(flow, 10_000, None).await.unwrap() {
+        ExecutionStatus::AwaitingInput { prompt, .. } => prompt.as_str().unwrap().to_string(),
+        other => panic!("expected AwaitingInput, got {other:?}"),
+    }
+}
+
+#[test]
+fn current_version_flows_are_transpiled_unchanged() {
+    let flow = approval_flow("\"Approve the plan?\"");
+    assert_eq!(flow.schema_version, CURRENT_FLOW_SCHEMA_VERSION);
+
+    assert!(matches!(
+        FlowTranspiler::migrate(&flow).unwrap(),
+        Cow::Borrowed(_)
+    ));
+    assert!(FlowTranspiler::transpile(&flow).is_ok());
+}
+
+#[test]
+fn definitions_without_a_version_are_migrated_from_version_one() {
+    let flow: FlowDefinition = serde_json::from_value(json!({
+        "id": "legacy",
+        "start_block_id": "ask",
+        "blocks": [
+            {
+                "id": "ask",
+                "block_type": { "AwaitInput": {
+                    "interaction_id": "approve",
+                    "agent_id": "reviewer",
+                    "prompt": "Approve the plan?",
+                    "state_key": "answer",
+                    "next_block": "end"
+                } }
+            },
+            { "id": "end", "block_type": "Terminate" }
+        ],
+        "participants": [],
+        "permissions": {},
+        "initial_state": null,
+        "state_schema": null
+    }))
+    .unwrap();
+
+    assert_eq!(flow.schema_version, MIN_FLOW_SCHEMA_VERSION);
+    let migrated = FlowTranspiler::migrate(&flow).unwrap();
+    assert_eq!(migrated.schema_version, CURRENT_FLOW_SCHEMA_VERSION);
+}
+
+#[test]
+fn version_one_prompts_are_migrated_to_string_literals() {
+    let mut flow = approval_flow("Approve the plan?");
+    flow.schema_version = 1;
+
+    let migrated = FlowTranspiler::migrate(&flow).unwrap().into_owned();
+
+    assert_eq!(migrated.schema_version, CURRENT_FLOW_SCHEMA_VERSION);
+    match &migrated.blocks[0].block_type {
+        BlockType::AwaitInput { prompt, .. } => assert_eq!(prompt, "\"Approve the plan?\""),
+        other => panic!("expected AwaitInput, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn migrated_flows_execute_like_current_ones() {
+    let mut legacy = approval_flow("Approve the plan?");
+    legacy.schema_version = 1;
+    let current = approval_flow("\"Approve the plan?\"");
+
+    assert_eq!(awaited_prompt(legacy).await, "Approve the plan?");
+    assert_eq!(awaited_prompt(current).await, "Approve the plan?");
+}
+
+#[test]
+fn unsupported_versions_are_rejected() {
+    for version in [0, CURRENT_FLOW_SCHEMA_VERSION + 1] {
+        let mut flow = approval_flow("\"Approve?\"");
+        flow.schema_version = version;
+
+        let error = FlowTranspiler::transpile(&flow).unwrap_err();
+        assert!(matches!(
+            error,
+            TranspilerError::UnsupportedSchemaVersion { found, .. } if found == version
+        ));
+    }
+}
+
+#[tokio::test]
+async fn version_one_prompts_with_quotes_are_escaped() {
+    let prompt = r#"Say "yes" to approve C:\plans"#;
+    let mut flow = approval_flow(prompt);
+    flow.schema_version = 1;
+
+    assert_eq!(awaited_prompt(flow).await, prompt);
+}
@@ -0,0 +1,158 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::{compute, flow, terminate};
+use sleet::flows::definition::{BlockDefinition, BlockType, FlowValidationError};
+use sleet::transpiler::TranspilerError;
+use sleet::FlowTranspiler;
+
+#[test]
+fn well_formed_flow_passes() {
+    let flow = flow(
+        "checked",
+        vec![compute("start", "1 + 1", "start", "end"), terminate("end")],
+    );
+    assert_eq!(flow.validate(), Ok(()));
+    assert!(FlowTranspiler::transpile(&flow).is_ok());
+}
+
+#[test]
+fn dangling_next_block_is_reported() {
+    let flow = flow(
+        "checked",
+        vec![
+            compute("start", "1 + 1", "start", "middle"),
+            compute("middle", "1 + 1", "middle", "nowhere"),
+            terminate("end"),
+        ],
+    );
+    let errors = flow.validate().unwrap_err();
+    assert!(errors.contains(&FlowValidationError::DanglingReference {
+        block_id: "middle".to_string(),
+        field: "next_block",
+        target: "nowhere".to_string(),
+    }));
+}
+
+#[test]
+fn duplicate_block_id_is_reported() {
+    let flow = flow(
+        "checked",
+        vec![
+            compute("start", "1 + 1", "start", "end"),
+            compute("start", "1 + 1", "start", "end"),
+            terminate("end"),
+        ],
+    );
+    let errors = flow.validate().unwrap_err();
+    assert_eq!(
+        errors,
+        vec![FlowValidationError::DuplicateBlock("start".to_string())]
+    );
+}
+
+#[test]
+fn unreachable_block_is_reported() {
+    let flow = flow(
+        "checked",
+        vec![
+            compute("start", "1 + 1", "start", "end"),
+            compute("orphan", "1 + 1", "orphan", "end"),
+            terminate("end"),
+        ],
+    );
+    let errors = flow.validate().unwrap_err();
+    assert_eq!(
+        errors,
+        vec![FlowValidationError::UnreachableBlock("orphan".to_string())]
+    );
+}
+
+#[test]
+fn flow_without_a_reachable_terminate_is_reported() {
+    let flow = flow(
+        "checked",
+        vec![
+            compute("start", "1 + 1", "start", "start"),
+            terminate("end"),
+        ],
+    );
+    let errors = flow.validate().unwrap_err();
+    assert!(errors.contains(&FlowValidationError::NoReachableTerminate));
+    assert!(errors.contains(&FlowValidationError::UnreachableBlock("end".to_string())));
+}
+
+#[test]
+fn catch_and_loop_targets_are_checked() {
+    let flow = flow(
+        "checked",
+        vec![
+            BlockDefinition::new(
+                "start",
+                BlockType::TryCatch {
+                    try_block_id: "body".to_string(),
+                    catch_block_id: "recover".to_string(),
+                },
+            ),
+            BlockDefinition::new(
+                "body",
+                BlockType::ForEach {
+                    loop_id: "items".to_string(),
+                    array_path: "items".to_string(),
+                    iterator_var: "item".to_string(),
+                    loop_body_block_id: "step".to_string(),
+                    exit_block_id: "after".to_string(),
+                },
+            ),
+            BlockDefinition::new(
+                "step",
+                BlockType::Continue {
+                    loop_id: "missing".to_string(),
+                },
+            ),
+            terminate("after"),
+        ],
+    );
+    let errors = flow.validate().unwrap_err();
+    assert!(errors.contains(&FlowValidationError::DanglingReference {
+        block_id: "start".to_string(),
+        field: "catch_block_id",
+        target: "recover".to_string(),
+    }));
+    assert!(errors.contains(&FlowValidationError::UnknownLoop {
+        block_id: "step".to_string(),
+        loop_id: "missing".to_string(),
+    }));
+}
+
+#[test]
+fn transpile_rejects_invalid_flows_with_every_issue() {
+    let flow = flow(
+        "checked",
+        vec![
+            compute("start", "1 + 1", "start", "nowhere"),
+            compute("orphan", "1 + 1", "orphan", "end"),
+            terminate("end"),
+        ],
+    );
+    match FlowTranspiler::transpile(&flow) {
+        Err(TranspilerError::InvalidFlow(errors)) => {
+            assert_eq!(errors.len(), 4, "{errors:?}");
+            let message = TranspilerError::InvalidFlow(errors).to_string();
+            assert!(message.contains("nowhere"), "{message}");
+            assert!(message.contains("orphan"), "{message}");
+        }
+        other => panic!("expected InvalidFlow, got {other:?}"),
+    }
+}
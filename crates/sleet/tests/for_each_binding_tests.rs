@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::compute;
+use serde_json::{json, Value};
+use sleet::flows::definition::{BlockDefinition, BlockType, BreakOutput, FlowDefinition};
+use sleet::{execute_flow_report, TranspileCache, LOOP_INDEX_KEY, LOOP_ITEM_KEY};
+
+fn for_each(id: &str, array_path: &str, item: &str, body: &str, exit: &str) -> BlockDefinition {
+    BlockDefinition::new(
+        id,
+        BlockType::ForEach {
+            loop_id: id.to_string(),
+            array_path: array_path.to_string(),
+            iterator_var: item.to_string(),
+            loop_body_block_id: body.to_string(),
+            exit_block_id: exit.to_string(),
+        },
+    )
+}
+
+fn continue_loop(id: &str, loop_id: &str) -> BlockDefinition {
+    BlockDefinition::new(
+        id,
+        BlockType::Continue {
+            loop_id: loop_id.to_string(),
+        },
+    )
+}
+
+async fn final_state(flow: FlowDefinition) -> Value {
+    execute_flow_report(flow, 10_000, None, &TranspileCache::new())
+        .await
+        .unwrap()
+        .final_state
+}
+
+#[tokio::test]
+async fn nested_loops_keep_distinct_index_bindings() {
+    let mut flow = FlowDefinition::new("matrix", "rows");
+    flow.set_initial_state(json!({ "matrix": [[1, 2], [3, 4], [5, 6]], "trace": "" }));
+    flow.add_block(for_each("rows", "state.matrix", "row", "cols", "done"))
+        .add_block(for_each("cols", "state.row", "cell", "record", "next_row"))
+        .add_block(compute(
+            "record",
+            "concat(state.trace, state.__loop_index.rows, \":\", state.__loop_index.cols, \
+             \"=\", state.__loop_item.cols, \";\")",
+            "trace",
+            "next_cell",
+        ))
+        .add_block(continue_loop("next_cell", "cols"))
+        .add_block(continue_loop("next_row", "rows"))
+        .add_block(BlockDefinition::new("done", BlockType::Terminate));
+
+    let state = final_state(flow).await;
+
+    assert_eq!(state["trace"], json!("0:0=1;0:1=2;1:0=3;1:1=4;2:0=5;2:1=6;"));
+    assert_eq!(state[LOOP_INDEX_KEY], json!({ "rows": 2, "cols": 1 }));
+    assert_eq!(state[LOOP_ITEM_KEY]["rows"], json!([5, 6]));
+}
+
+#[tokio::test]
+async fn break_writes_its_output_before_leaving_the_loop() {
+    let mut flow = FlowDefinition::new("scan", "scan");
+    flow.set_initial_state(json!({ "values": [3, 8, 11, 20, 4] }));
+    flow.add_block(for_each("scan", "state.values", "v", "check", "done"))
+        .add_block(BlockDefinition::new(
+            "check",
+            BlockType::Conditional {
+                condition: "state.v > 10".to_string(),
+                true_block: "found".to_string(),
+                false_block: "next".to_string(),
+            },
+        ))
+        .add_block(continue_loop("next", "scan"))
+        .add_block(BlockDefinition::new(
+            "found",
+            BlockType::Break {
+                loop_id: "scan".to_string(),
+                output: Some(BreakOutput {
+                    expression: "state.__loop_index.scan".to_string(),
+                    output_key: "found_at".to_string(),
+                }),
+            },
+        ))
+        .add_block(BlockDefinition::new("done", BlockType::Terminate));
+
+    let state = final_state(flow).await;
+
+    assert_eq!(state["found_at"], json!(2));
+    assert_eq!(state[LOOP_ITEM_KEY]["scan"], json!(11));
+}
@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::compute;
+use sleet::bench::{bench_flow, BenchOutcome};
+use sleet::flows::definition::{BlockDefinition, BlockType, FlowDefinition};
+use sleet::runtime::FfiRegistry;
+
+fn base_flow() -> FlowDefinition {
+    let mut flow = FlowDefinition::new("gas_bench", "start");
+    flow.add_block(compute("start", "1 + 2", "total", "end"))
+        .add_block(BlockDefinition::new("end", BlockType::Terminate));
+    flow
+}
+
+#[test]
+fn known_flow_consumes_expected_gas() {
+    let report = bench_flow(&base_flow(), 1_000, FfiRegistry::new()).unwrap();
+
+    assert_eq!(report.outcome, BenchOutcome::Completed);
+    assert_eq!(report.blocks_executed, 2);
+    assert_eq!(report.node_count("Sequence"), 1);
+    assert_eq!(report.node_count("Evaluate"), 1);
+    assert_eq!(report.node_count("SetNextBlock"), 1);
+    assert_eq!(report.node_count("Terminate"), 1);
+    assert_eq!(report.opcode_count("Push"), 2);
+    assert_eq!(report.opcode_count("Add"), 1);
+    assert_eq!(report.total_gas, 7);
+    assert_eq!(report.total_gas, report.total_nodes() + report.total_opcodes());
+}
+
+#[test]
+fn redundant_block_increases_gas_predictably() {
+    let baseline = bench_flow(&base_flow(), 1_000, FfiRegistry::new()).unwrap();
+
+    let mut flow = FlowDefinition::new("gas_bench", "start");
+    flow.add_block(compute("start", "1 + 2", "total", "again"))
+        .add_block(compute("again", "1 + 2", "total", "end"))
+        .add_block(BlockDefinition::new("end", BlockType::Terminate));
+    let report = bench_flow(&flow, 1_000, FfiRegistry::new()).unwrap();
+
+    assert_eq!(report.gas_delta(&baseline), 6);
+    assert_eq!(report.opcode_count("Add"), 2);
+    assert_eq!(report.blocks_executed, baseline.blocks_executed + 1);
+    assert!(report.exceeds(&baseline, 0));
+    assert!(!report.exceeds(&baseline, 6));
+}
+
+#[test]
+fn repeated_runs_are_identical() {
+    let first = bench_flow(&base_flow(), 1_000, FfiRegistry::new()).unwrap();
+    let second = bench_flow(&base_flow(), 1_000, FfiRegistry::new()).unwrap();
+    assert_eq!(first.summary(), second.summary());
+}
+
+#[test]
+fn insufficient_gas_is_reported() {
+    let err = bench_flow(&base_flow(), 4, FfiRegistry::new()).unwrap_err();
+    assert!(err.to_string().contains("Out of gas"));
+}
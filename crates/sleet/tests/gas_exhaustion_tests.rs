@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use serde_json::json;
+use sleet::flows::definition::{BlockDefinition, BlockType, FlowDefinition};
+use sleet::runtime::{FfiRegistry, InterpreterError, RemarkableInterpreter};
+use sleet::{convert_contract, execute_flow_with_cache, FlowTranspiler, TranspileCache};
+
+fn counting_loop() -> FlowDefinition {
+    let mut flow = FlowDefinition::new("gas_loop", "guard");
+    flow.set_initial_state(json!({ "counter": 0 }))
+        .add_block(BlockDefinition::new(
+            "guard",
+            BlockType::Conditional {
+                condition: "state.counter == 0".to_string(),
+                true_block: "setup".to_string(),
+                false_block: "end".to_string(),
+            },
+        ))
+        .add_block(BlockDefinition::new(
+            "setup",
+            BlockType::Compute {
+                expression: "0".to_string(),
+                output_key: "counter".to_string(),
+                next_block: "loop".to_string(),
+            },
+        ))
+        .add_block(BlockDefinition::new(
+            "loop",
+            BlockType::Compute {
+                expression: "state.counter + 1".to_string(),
+                output_key: "counter".to_string(),
+                next_block: "loop".to_string(),
+            },
+        ))
+        .add_block(BlockDefinition::new("end", BlockType::Terminate));
+    flow
+}
+
+#[tokio::test]
+async fn execute_flow_reports_block_and_partial_state() {
+    let err = execute_flow_with_cache(counting_loop(), 50, None, &TranspileCache::new())
+        .await
+        .unwrap_err();
+    let err = err
+        .downcast_ref::<InterpreterError>()
+        .expect("interpreter error");
+    assert!(err.is_out_of_gas());
+    assert!(err.to_string().contains("Out of gas in block 'loop'"));
+
+    let details = err.gas_exhaustion().expect("gas exhaustion details");
+    assert_eq!(details.block_id, "loop");
+    assert_eq!(details.gas_limit, 50);
+    assert_eq!(details.gas_used, 50);
+    let counter = details.partial_state["counter"].as_i64().unwrap();
+    assert!(counter > 0 && counter < 10, "counter was {counter}");
+}
+
+#[tokio::test]
+async fn execute_flow_and_the_interpreter_report_the_same_exhaustion() {
+    let contract = convert_contract(FlowTranspiler::transpile(&counting_loop()).unwrap()).unwrap();
+    let mut interp = RemarkableInterpreter::new(50, &contract, FfiRegistry::new()).unwrap();
+    let stepped = match interp.run(contract.clone()).await {
+        Err(error) => error.downcast::<InterpreterError>().unwrap(),
+        other => panic!("expected gas exhaustion, got {other:?}"),
+    };
+
+    let via_flow = execute_flow_with_cache(counting_loop(), 50, None, &TranspileCache::new())
+        .await
+        .unwrap_err()
+        .downcast::<InterpreterError>()
+        .unwrap();
+    assert_eq!(via_flow.gas_exhaustion(), stepped.gas_exhaustion());
+    assert!(via_flow.gas_exhaustion().is_some());
+}
+
+#[test]
+fn exhausted_executor_can_resume_with_more_gas() {
+    let contract = convert_contract(FlowTranspiler::transpile(&counting_loop()).unwrap()).unwrap();
+    let mut exec = RemarkableInterpreter::new(50, &contract, FfiRegistry::new()).unwrap();
+    let first = match exec.run_until_paused() {
+        Err(InterpreterError::GasExhausted(details)) => details,
+        other => panic!("expected GasExhausted, got {other:?}"),
+    };
+    assert_eq!(exec.state(), &first.partial_state);
+
+    exec.add_gas(60);
+    let second = match exec.run_until_paused() {
+        Err(InterpreterError::GasExhausted(details)) => details,
+        other => panic!("expected GasExhausted, got {other:?}"),
+    };
+    assert_eq!(second.block_id, "loop");
+    assert_eq!(second.gas_limit, 110);
+    assert!(second.partial_state["counter"].as_i64() > first.partial_state["counter"].as_i64());
+}
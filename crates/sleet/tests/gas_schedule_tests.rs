@@ -0,0 +1,123 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::compute_flow;
+use serde_json::json;
+use sleet::ast::Contract;
+use sleet::flows::definition::FlowDefinition;
+use sleet::runtime::{
+    create_ergonomic_ffi, FfiRegistry, GasSchedule, InterpreterError, OpCode,
+    RemarkableInterpreter, StepStatus,
+};
+use sleet::bench::{bench_flow, bench_flow_with_schedule};
+use sleet::simulation::simulate_with_schedule;
+use sleet::{
+    convert_contract, execute_flow_with_schedule, simulate, FfiStubRegistry, FlowTranspiler,
+    TranspileCache,
+};
+
+fn lookup_definition() -> FlowDefinition {
+    compute_flow("lookup_flow", "lookup() + 1", "out")
+}
+
+fn lookup_flow() -> Contract {
+    convert_contract(FlowTranspiler::transpile(&lookup_definition()).unwrap()).unwrap()
+}
+
+fn registry() -> FfiRegistry {
+    FfiRegistry::from([(
+        "lookup".to_string(),
+        create_ergonomic_ffi(|_args, _state| Ok(json!(41))),
+    )])
+}
+
+fn expensive_calls() -> GasSchedule {
+    GasSchedule::default()
+        .with_cost(OpCode::Call, 50)
+        .with_cost(OpCode::CallFfi, 50)
+}
+
+fn interpreter(gas: u64, schedule: GasSchedule) -> RemarkableInterpreter {
+    RemarkableInterpreter::with_gas_schedule(gas, &lookup_flow(), registry(), schedule).unwrap()
+}
+
+fn run_to_pause(interp: &mut RemarkableInterpreter) -> Result<u64, InterpreterError> {
+    loop {
+        let outcome = interp.step()?;
+        if outcome.status != StepStatus::Running {
+            return Ok(outcome.gas_remaining);
+        }
+    }
+}
+
+#[test]
+fn default_schedule_charges_one_gas_per_opcode() {
+    let schedule = GasSchedule::default();
+    assert_eq!(schedule.cost(OpCode::Add), 1);
+    assert_eq!(schedule.cost(OpCode::CallFfi), 1);
+    assert_eq!(GasSchedule::uniform(3).with_cost(OpCode::Add, 7).cost(OpCode::Add), 7);
+}
+
+#[test]
+fn expensive_schedule_exhausts_gas_sooner() {
+    let mut cheap = interpreter(20, GasSchedule::default());
+    let remaining = run_to_pause(&mut cheap).unwrap();
+    assert!(remaining > 0);
+    assert_eq!(cheap.current_state().get("out"), Some(&json!(42)));
+
+    let mut expensive = interpreter(20, expensive_calls());
+    assert!(matches!(run_to_pause(&mut expensive), Err(error) if error.is_out_of_gas()));
+    assert_eq!(expensive.current_state().get("out"), None);
+}
+
+#[test]
+fn expensive_schedule_charges_the_configured_cost_per_call() {
+    let mut cheap = interpreter(1_000, GasSchedule::default());
+    let mut expensive = interpreter(1_000, expensive_calls());
+    let cheap_used = 1_000 - run_to_pause(&mut cheap).unwrap();
+    let expensive_used = 1_000 - run_to_pause(&mut expensive).unwrap();
+    assert_eq!(expensive_used, cheap_used + 49);
+    assert_eq!(expensive.current_state(), cheap.current_state());
+}
+
+#[tokio::test]
+async fn custom_schedule_changes_gas_used_through_execute_flow() {
+    let cache = TranspileCache::new();
+    let run = |schedule| {
+        execute_flow_with_schedule(lookup_definition(), 1_000, Some(registry()), &cache, schedule)
+    };
+    let default = run(GasSchedule::default()).await.unwrap();
+    let custom = run(expensive_calls().with_node_cost("Evaluate", 10))
+        .await
+        .unwrap();
+    assert_eq!(custom.gas_used, default.gas_used + 49 + 9);
+    assert_eq!(custom.final_state, default.final_state);
+}
+
+#[test]
+fn bench_and_simulate_charge_from_the_given_schedule() {
+    let schedule = GasSchedule::default().with_node_cost("Terminate", 25);
+    let plain = bench_flow(&lookup_definition(), 1_000, registry()).unwrap();
+    let scheduled =
+        bench_flow_with_schedule(&lookup_definition(), 1_000, registry(), schedule.clone())
+            .unwrap();
+    assert_eq!(scheduled.total_gas, plain.total_gas + 24);
+
+    let mut stubs = FfiStubRegistry::new();
+    stubs.stub("lookup", json!(41));
+    let plain = simulate(&lookup_definition(), json!({}), &stubs).unwrap();
+    let scheduled =
+        simulate_with_schedule(&lookup_definition(), json!({}), &stubs, 1_000, schedule).unwrap();
+    assert_eq!(scheduled.gas_used, plain.gas_used + 24);
+}
@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::{coordinator_config, generate, StubAdapter};
+use sleet::orchestration::OrchestrationError;
+use sleet::runtime::ExecutionStatus;
+use sleet::{BlockDefinition, BlockType, FlowDefinition, OrchestrationCoordinator};
+use std::sync::Arc;
+
+fn haiku_flow() -> FlowDefinition {
+    let mut flow = FlowDefinition::new("haiku", "write");
+    flow.add_block(generate("write", "\"Write a haiku\"", "poem", "critique"))
+        .add_block(generate("critique", "poem", "review", "done"))
+        .add_block(BlockDefinition::new("done", BlockType::Terminate));
+    flow
+}
+
+async fn coordinator_with_stub(
+    max_llm_tokens_per_session: Option<u64>,
+) -> (OrchestrationCoordinator, Arc<StubAdapter>) {
+    let mut config = coordinator_config();
+    config.resource_limits.max_llm_tokens_per_session = max_llm_tokens_per_session;
+    let coordinator = OrchestrationCoordinator::new(config).await.unwrap();
+    let stub = Arc::new(StubAdapter::default());
+    coordinator.set_generation_adapter(stub.clone()).await;
+    (coordinator, stub)
+}
+
+#[tokio::test]
+async fn completion_is_written_to_output_key_and_execution_advances() {
+    let (coordinator, stub) = coordinator_with_stub(None).await;
+
+    let status = coordinator
+        .execute_flow(haiku_flow().into(), None)
+        .await
+        .unwrap();
+
+    assert!(matches!(status, ExecutionStatus::Completed(_)));
+    assert_eq!(stub.prompts(), vec!["Write a haiku", "completion #1"]);
+}
+
+#[tokio::test]
+async fn session_token_budget_stops_further_generation() {
+    let (coordinator, stub) = coordinator_with_stub(Some(10)).await;
+
+    let error = coordinator
+        .execute_flow(haiku_flow().into(), None)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, OrchestrationError::ResourceAllocationError(_)));
+    assert_eq!(stub.prompts(), vec!["Write a haiku"]);
+}
+
+#[test]
+fn transpiler_rejects_generate_blocks() {
+    let error = sleet::FlowTranspiler::transpile(&haiku_flow()).unwrap_err();
+    assert!(error.to_string().contains("OrchestrationCoordinator"));
+}
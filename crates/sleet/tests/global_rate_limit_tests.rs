@@ -0,0 +1,142 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::{coordinator_config, generate, StubAdapter};
+use sleet::orchestration::coordinator::GlobalRateLimits;
+use sleet::orchestration::{OrchestrationConfig, ResourceManager, SharedRateLimiter};
+use sleet::runtime::ExecutionStatus;
+use sleet::{BlockDefinition, BlockType, FlowDefinition, OrchestrationCoordinator};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+const WINDOW: Duration = Duration::from_millis(200);
+const SLACK: Duration = Duration::from_millis(20);
+
+fn limits(max_requests: Option<u32>, max_tokens: Option<u64>) -> GlobalRateLimits {
+    GlobalRateLimits {
+        window_ms: WINDOW.as_millis() as u64,
+        max_llm_requests_per_window: max_requests,
+        max_tokens_per_window: max_tokens,
+    }
+}
+
+async fn session_resources(limiter: &SharedRateLimiter) -> Arc<ResourceManager> {
+    let resource_limits = OrchestrationConfig::default().resource_limits;
+    let manager = ResourceManager::new(resource_limits)
+        .await
+        .unwrap()
+        .with_rate_limiter(Some(limiter.clone()));
+    Arc::new(manager)
+}
+
+fn generating_flow(generations: usize) -> FlowDefinition {
+    let mut flow = FlowDefinition::new("chatty", "generate_0");
+    for index in 0..generations {
+        let next = if index + 1 == generations {
+            "done".to_string()
+        } else {
+            format!("generate_{}", index + 1)
+        };
+        let id = format!("generate_{index}");
+        flow.add_block(generate(&id, "\"Say something\"", &id, &next));
+    }
+    flow.add_block(BlockDefinition::new("done", BlockType::Terminate));
+    flow
+}
+
+async fn run_sessions(limiter: &SharedRateLimiter, generations: usize) -> Vec<Instant> {
+    let stub = Arc::new(StubAdapter::default());
+    let mut coordinators = Vec::new();
+    for _ in 0..2 {
+        let coordinator = OrchestrationCoordinator::with_shared_rate_limiter(
+            coordinator_config(),
+            limiter.clone(),
+        )
+        .await
+        .unwrap();
+        coordinator.set_generation_adapter(stub.clone()).await;
+        coordinators.push(coordinator);
+    }
+
+    let sessions = coordinators
+        .iter()
+        .map(|coordinator| coordinator.execute_flow(generating_flow(generations).into(), None));
+    for status in futures::future::join_all(sessions).await {
+        assert!(matches!(status.unwrap(), ExecutionStatus::Completed(_)));
+    }
+
+    let mut calls = stub.called_at.lock().unwrap().clone();
+    calls.sort();
+    calls
+}
+
+fn max_calls_in_any_window(calls: &[Instant]) -> usize {
+    let window = WINDOW - SLACK;
+    calls
+        .iter()
+        .map(|start| {
+            calls
+                .iter()
+                .filter(|call| **call >= *start && call.duration_since(*start) < window)
+                .count()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn concurrent_sessions_share_a_global_request_limit() {
+    let limiter = SharedRateLimiter::new(limits(Some(4), None));
+    let started = Instant::now();
+
+    let calls = run_sessions(&limiter, 6).await;
+
+    assert_eq!(calls.len(), 12);
+    assert!(max_calls_in_any_window(&calls) <= 4);
+    assert!(started.elapsed() >= WINDOW * 2);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn actual_usage_counts_against_the_global_token_budget() {
+    let limiter = SharedRateLimiter::new(limits(None, Some(25)));
+    let started = Instant::now();
+
+    let calls = run_sessions(&limiter, 3).await;
+
+    assert_eq!(calls.len(), 6);
+    assert!(max_calls_in_any_window(&calls) <= 3);
+    assert!(started.elapsed() >= WINDOW);
+    let tokens = limiter.tokens_in_window().await;
+    assert!(tokens > 0 && tokens <= 3 * u64::from(StubAdapter::TOKENS_PER_CALL));
+}
+
+#[tokio::test]
+async fn oversized_requests_are_rejected_immediately() {
+    let limiter = SharedRateLimiter::new(limits(Some(4), Some(1_000)));
+    let manager = session_resources(&limiter).await;
+
+    assert!(manager.acquire_llm_permit(5_000).await.is_err());
+    assert_eq!(limiter.requests_in_window().await, 0);
+}
+
+#[tokio::test]
+async fn managers_without_a_limiter_grant_no_permits() {
+    let manager = ResourceManager::new(OrchestrationConfig::default().resource_limits)
+        .await
+        .unwrap();
+
+    assert!(manager.rate_limiter().is_none());
+    assert!(manager.acquire_llm_permit(5_000).await.unwrap().is_none());
+}
@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use serde_json::{json, Value as JsonValue};
+use sleet::ast::{AstNode, Contract, Literal, Op, Path, PathSegment};
+use sleet::runtime::{FfiRegistry, RemarkableInterpreter};
+use std::collections::HashMap;
+
+fn node(op: Op) -> Box<AstNode> {
+    Box::new(AstNode::from(op))
+}
+
+fn var(name: &str) -> Box<AstNode> {
+    node(Op::Fetch(Path(vec![
+        PathSegment::State,
+        PathSegment::Key(name.to_string()),
+    ])))
+}
+
+fn num(n: f64) -> Box<AstNode> {
+    node(Op::Literal(Literal::Number(n)))
+}
+
+fn values_path() -> Path {
+    Path(vec![PathSegment::State, PathSegment::Key("values".to_string())])
+}
+
+fn execute(expr: Op, initial_state: JsonValue) -> RemarkableInterpreter {
+    let block = AstNode::from(Op::Assign {
+        path: Path(vec![PathSegment::State, PathSegment::Key("out".to_string())]),
+        value: node(expr),
+    });
+    let contract = Contract {
+        version: "1.0".to_string(),
+        start_block_id: "start".to_string(),
+        blocks: HashMap::from([("start".to_string(), block)]),
+        initial_state: AstNode::from(Op::Literal(Literal::JsonValue(initial_state))),
+        permissions: json!({}),
+        participants: vec![],
+    };
+    let mut executor = RemarkableInterpreter::new(10_000, &contract, FfiRegistry::new()).unwrap();
+    executor.run_until_paused().unwrap();
+    executor
+}
+
+fn run(expr: Op, values: JsonValue) -> (JsonValue, u64) {
+    let executor = execute(expr, json!({ "values": values }));
+    assert!(executor.state().get("x").is_none(), "item binding must not leak");
+    (executor.state()["out"].clone(), executor.gas_used())
+}
+
+fn double() -> Op {
+    Op::Map {
+        source: values_path(),
+        item_var: "x".to_string(),
+        body: node(Op::Multiply(var("x"), num(2.0))),
+    }
+}
+
+fn above_three() -> Op {
+    Op::Filter {
+        source: values_path(),
+        item_var: "x".to_string(),
+        predicate: node(Op::GreaterThan(var("x"), num(3.0))),
+    }
+}
+
+fn sum() -> Op {
+    Op::Reduce {
+        source: values_path(),
+        item_var: "x".to_string(),
+        accumulator_var: "acc".to_string(),
+        initial: num(0.0),
+        body: node(Op::Add(var("acc"), var("x"))),
+    }
+}
+
+#[test]
+fn map_doubles_each_element() {
+    let (out, gas) = run(double(), json!([1, 2, 3, 4, 5]));
+    assert_eq!(out, json!([2, 4, 6, 8, 10]));
+    assert_eq!(gas, 2 + 5 * 4);
+}
+
+#[test]
+fn filter_keeps_matching_elements() {
+    let (out, gas) = run(above_three(), json!([1, 2, 3, 4, 5]));
+    assert_eq!(out, json!([4, 5]));
+    assert_eq!(gas, 2 + 5 * 4);
+}
+
+#[test]
+fn reduce_sums_elements() {
+    let (out, gas) = run(sum(), json!([1, 2, 3, 4, 5]));
+    assert_eq!(out, json!(15));
+    assert_eq!(gas, 3 + 5 * 4);
+}
+
+#[test]
+fn gas_scales_per_element() {
+    for op in [double, above_three, sum] {
+        let (_, five) = run(op(), json!([1, 2, 3, 4, 5]));
+        let (_, six) = run(op(), json!([1, 2, 3, 4, 5, 6]));
+        assert_eq!(six - five, 4);
+    }
+}
+
+#[test]
+fn item_bindings_shadow_state_without_writing_to_it() {
+    let executor = execute(double(), json!({ "values": [1, 2], "x": "kept" }));
+    assert_eq!(executor.state()["out"], json!([2, 4]));
+    assert_eq!(executor.state()["x"], json!("kept"));
+}
@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::{await_input, compute, contract, flow, terminate};
+use serde_json::json;
+use sleet::ast::Contract;
+use sleet::runtime::{
+    create_async_ffi, ExecutionStatus, FfiRegistry, GasSchedule, InterpreterError, OpCode,
+    RemarkableInterpreter, SNAPSHOT_VERSION,
+};
+
+fn approval_contract() -> Contract {
+    let mut flow = flow(
+        "approval_flow",
+        vec![
+            compute("prepare", "state.attempts + 1", "attempts", "ask"),
+            await_input(
+                "ask",
+                "approval",
+                "\"Approve the request?\"",
+                "decision",
+                "done",
+            ),
+            terminate("done"),
+        ],
+    );
+    flow.set_initial_state(json!({ "attempts": 0, "decision": null }));
+    contract(&flow)
+}
+
+fn scoring_contract() -> Contract {
+    contract(&flow(
+        "scoring_flow",
+        vec![
+            await_input("ask", "amount", "\"How much?\"", "amount", "score"),
+            compute("score", "double(state.amount)", "score", "done"),
+            terminate("done"),
+        ],
+    ))
+}
+
+fn with_async_double(mut interp: RemarkableInterpreter) -> RemarkableInterpreter {
+    interp.register_async_ffi(
+        "double",
+        create_async_ffi(|args, _state| async move {
+            let x = args.first().and_then(|v| v.as_f64()).unwrap_or_default();
+            Ok(json!(x * 2.0))
+        }),
+    );
+    interp
+}
+
+fn fresh(contract: &Contract) -> RemarkableInterpreter {
+    RemarkableInterpreter::new(1_000, contract, FfiRegistry::new()).unwrap()
+}
+
+fn restore(
+    snapshot: &[u8],
+    contract: &Contract,
+) -> Result<RemarkableInterpreter, InterpreterError> {
+    RemarkableInterpreter::resume(snapshot, contract, FfiRegistry::new())
+}
+
+fn awaited_id(status: &ExecutionStatus) -> String {
+    match status {
+        ExecutionStatus::AwaitingInput { interaction_id, .. } => interaction_id.clone(),
+        other => panic!("expected an await, got {other:?}"),
+    }
+}
+
+async fn run_to_completion(
+    interp: &mut RemarkableInterpreter,
+    contract: &Contract,
+    mut awaiting: String,
+) -> serde_json::Value {
+    loop {
+        interp.resume_with_input(&awaiting, json!("approved")).unwrap();
+        match interp.run(contract.clone()).await.unwrap() {
+            ExecutionStatus::Completed(result) => return result.into(),
+            status => awaiting = awaited_id(&status),
+        }
+    }
+}
+
+#[tokio::test]
+async fn resumed_interpreter_completes_like_an_uninterrupted_one() {
+    let contract = approval_contract();
+
+    let mut uninterrupted = fresh(&contract);
+    let first = uninterrupted.run(contract.clone()).await.unwrap();
+    let expected = run_to_completion(&mut uninterrupted, &contract, awaited_id(&first)).await;
+
+    let mut original = fresh(&contract);
+    let status = original.run(contract.clone()).await.unwrap();
+    let interaction_id = awaited_id(&status);
+    assert_eq!(original.pending_await(), Some(interaction_id.as_str()));
+    let snapshot = original.serialize_state().unwrap();
+    let state_before = original.current_state().clone();
+    drop(original);
+
+    let mut resumed = restore(&snapshot, &contract).unwrap();
+    assert_eq!(resumed.pending_await(), Some(interaction_id.as_str()));
+    assert_eq!(resumed.current_state(), &state_before);
+    let result = run_to_completion(&mut resumed, &contract, interaction_id).await;
+
+    assert_eq!(result, expected);
+    assert_eq!(resumed.current_state(), uninterrupted.current_state());
+}
+
+#[tokio::test]
+async fn restore_keeps_the_gas_schedule_and_async_ffi_registrations() {
+    let contract = scoring_contract();
+    let schedule = GasSchedule::default().with_cost(OpCode::CallFfi, 40);
+    let build = |schedule: GasSchedule| {
+        let interp =
+            RemarkableInterpreter::with_gas_schedule(1_000, &contract, FfiRegistry::new(), schedule)
+                .unwrap();
+        with_async_double(interp)
+    };
+
+    let mut uninterrupted = build(schedule.clone());
+    uninterrupted.run(contract.clone()).await.unwrap();
+    uninterrupted.resume_with_input("amount", json!(21)).unwrap();
+    uninterrupted.run(contract.clone()).await.unwrap();
+
+    let mut original = build(schedule.clone());
+    original.run(contract.clone()).await.unwrap();
+    let snapshot = original.serialize_state().unwrap();
+
+    let mut restored = build(GasSchedule::default());
+    restored.restore(&snapshot).unwrap();
+    assert_eq!(restored.gas_schedule(), &schedule);
+    restored.resume_with_input("amount", json!(21)).unwrap();
+    let status = restored.run(contract.clone()).await.unwrap();
+
+    assert!(matches!(status, ExecutionStatus::Completed(_)));
+    assert_eq!(restored.current_state().get("score"), Some(&json!(42.0)));
+    assert_eq!(restored.gas_used(), uninterrupted.gas_used());
+
+    let resumed = restore(&snapshot, &contract).unwrap();
+    assert_eq!(resumed.gas_schedule(), &schedule);
+}
+
+#[test]
+fn resume_restores_gas_and_stack_mid_block() {
+    let contract = approval_contract();
+    let mut original = fresh(&contract);
+    let outcome = original.step().unwrap();
+    let snapshot = original.serialize_state().unwrap();
+
+    let mut resumed = restore(&snapshot, &contract).unwrap();
+    assert_eq!(resumed.stack_snapshot(), original.stack_snapshot());
+    assert_eq!(resumed.step().unwrap(), original.step().unwrap());
+    assert!(outcome.gas_remaining < 1_000);
+}
+
+#[test]
+fn resume_rejects_snapshots_from_other_versions() {
+    let contract = approval_contract();
+    let original = fresh(&contract);
+    let mut snapshot: serde_json::Value =
+        serde_json::from_slice(&original.serialize_state().unwrap()).unwrap();
+    snapshot["version"] = json!(SNAPSHOT_VERSION + 1);
+    let bytes = serde_json::to_vec(&snapshot).unwrap();
+
+    let error = restore(&bytes, &contract).err();
+    assert!(matches!(
+        error,
+        Some(InterpreterError::UnsupportedSnapshotVersion { found, expected })
+            if found == SNAPSHOT_VERSION + 1 && expected == SNAPSHOT_VERSION
+    ));
+}
+
+#[test]
+fn resume_rejects_garbage_and_foreign_contracts() {
+    let contract = approval_contract();
+    let garbage = restore(b"not a snapshot", &contract);
+    assert!(matches!(garbage, Err(InterpreterError::InvalidSnapshot(_))));
+
+    let original = fresh(&contract);
+    let snapshot = original.serialize_state().unwrap();
+    let mut other = contract.clone();
+    other.start_block_id = "ask".to_string();
+    let foreign = restore(&snapshot, &other);
+    assert!(matches!(foreign, Err(InterpreterError::InvalidSnapshot(_))));
+}
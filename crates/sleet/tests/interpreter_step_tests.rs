@@ -0,0 +1,150 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use serde_json::json;
+use sleet::ast::Contract;
+use sleet::flows::definition::{BlockDefinition, BlockType, FlowDefinition};
+use sleet::runtime::{ExecutionStatus, FfiRegistry, RemarkableInterpreter, StepStatus, Value};
+use sleet::{convert_contract, FlowTranspiler};
+
+const GAS_LIMIT: u64 = 10_000;
+
+fn base_flow() -> Contract {
+    let mut flow = FlowDefinition::new("runtime_demo_flow", "start");
+    flow.set_initial_state(json!({
+        "counter": 0,
+        "base_value": 1,
+        "multiplier": 2,
+        "threshold": 10
+    }));
+    flow.add_block(BlockDefinition::new(
+        "start",
+        BlockType::Compute {
+            expression: "state.counter + 1".to_string(),
+            output_key: "counter".to_string(),
+            next_block: "process_data".to_string(),
+        },
+    ))
+    .add_block(BlockDefinition::new(
+        "process_data",
+        BlockType::Compute {
+            expression: "state.multiplier * 2".to_string(),
+            output_key: "result".to_string(),
+            next_block: "check_threshold".to_string(),
+        },
+    ))
+    .add_block(BlockDefinition::new(
+        "check_threshold",
+        BlockType::Conditional {
+            condition: "state.result > state.threshold".to_string(),
+            true_block: "finalise".to_string(),
+            false_block: "finalise".to_string(),
+        },
+    ))
+    .add_block(BlockDefinition::new("finalise", BlockType::Terminate));
+    convert_contract(FlowTranspiler::transpile(&flow).unwrap()).unwrap()
+}
+
+fn interpreter(contract: &Contract) -> RemarkableInterpreter {
+    RemarkableInterpreter::new(GAS_LIMIT, contract, FfiRegistry::new()).unwrap()
+}
+
+#[test]
+fn stepping_the_base_flow_consumes_gas_monotonically() {
+    let contract = base_flow();
+    let mut interp = interpreter(&contract);
+    let mut gas = GAS_LIMIT;
+    let mut steps = 0;
+    let outcome = loop {
+        let outcome = interp.step().unwrap();
+        assert!(outcome.gas_remaining <= gas);
+        gas = outcome.gas_remaining;
+        steps += 1;
+        assert!(steps < 100, "stepping never reached the end of the flow");
+        if outcome.status != StepStatus::Running {
+            break outcome;
+        }
+    };
+    assert_eq!(outcome.status, StepStatus::Halted);
+    assert_eq!(outcome.block_id, "finalise");
+    assert!(outcome.gas_remaining < GAS_LIMIT);
+    assert_eq!(interp.current_state().get("counter"), Some(&json!(1)));
+    assert_eq!(interp.current_state().get("result"), Some(&json!(4)));
+    assert!(interp.stack_snapshot().is_empty());
+}
+
+#[tokio::test]
+async fn stepping_follows_block_transitions_exactly_like_run() {
+    let contract = base_flow();
+    let mut stepped = interpreter(&contract);
+    let mut blocks: Vec<String> = Vec::new();
+    loop {
+        let outcome = stepped.step().unwrap();
+        if blocks.last() != Some(&outcome.block_id) {
+            blocks.push(outcome.block_id.clone());
+        }
+        if outcome.status != StepStatus::Running {
+            break;
+        }
+    }
+
+    let mut ran = interpreter(&contract);
+    assert!(matches!(
+        ran.run(contract.clone()).await.unwrap(),
+        ExecutionStatus::Completed(_)
+    ));
+    assert_eq!(
+        blocks,
+        vec!["start", "process_data", "check_threshold", "finalise"]
+    );
+    assert_eq!(blocks, ran.metrics().block_path);
+    assert_eq!(stepped.metrics(), ran.metrics());
+    assert_eq!(stepped.gas_used(), ran.gas_used());
+    assert_eq!(stepped.current_state(), ran.current_state());
+}
+
+#[test]
+fn stack_snapshot_exposes_intermediate_values() {
+    let contract = base_flow();
+    let mut interp = interpreter(&contract);
+    assert!(interp.stack_snapshot().is_empty());
+    let first = interp.step().unwrap();
+    assert_eq!(first.status, StepStatus::Running);
+    assert!(first.instruction_pointer > 0);
+    assert_eq!(interp.stack_snapshot(), vec![Value::Json(json!(0))]);
+    assert_eq!(interp.current_state().get("counter"), Some(&json!(0)));
+}
+
+#[tokio::test]
+async fn run_matches_stepping_to_the_same_outcome() {
+    let contract = base_flow();
+    let mut stepped = interpreter(&contract);
+    let mut ran = interpreter(&contract);
+
+    for _ in 0..4 {
+        let by_step = loop {
+            let outcome = stepped.step().unwrap();
+            if outcome.status != StepStatus::Running {
+                break outcome.status;
+            }
+        };
+        let by_run = ran.run(contract.clone()).await.unwrap();
+        match by_run {
+            ExecutionStatus::AwaitingInput { .. } => {
+                assert_eq!(by_step, StepStatus::AwaitingInput)
+            }
+            ExecutionStatus::Completed(_) => assert_eq!(by_step, StepStatus::Halted),
+            ExecutionStatus::Running => panic!("run returned before reaching a pause"),
+        }
+        assert_eq!(stepped.current_state(), ran.current_state());
+    }
+}
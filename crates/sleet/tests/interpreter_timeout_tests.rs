@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::{compute_flow, contract};
+use serde_json::json;
+use sleet::ast::Contract;
+use sleet::runtime::{
+    create_ergonomic_ffi, ExecutionStatus, FfiRegistry, InterpreterError, RemarkableInterpreter,
+};
+use std::time::Duration;
+
+fn slow_flow() -> Contract {
+    let flow = compute_flow("slow_flow", "slow()", "out");
+    contract(&flow)
+}
+
+fn slow_registry(delay: Duration) -> FfiRegistry {
+    FfiRegistry::from([(
+        "slow".to_string(),
+        create_ergonomic_ffi(move |_args, _state| {
+            std::thread::sleep(delay);
+            Ok(json!("done"))
+        }),
+    )])
+}
+
+fn interpreter(gas: u64, max_duration: Duration) -> (RemarkableInterpreter, Contract) {
+    let contract = slow_flow();
+    let registry = slow_registry(Duration::from_millis(200));
+    let mut interp = RemarkableInterpreter::new(gas, &contract, registry).unwrap();
+    interp.set_max_duration(Some(max_duration));
+    (interp, contract)
+}
+
+#[tokio::test]
+async fn slow_ffi_call_exceeds_the_wall_clock_budget() {
+    let (mut interp, contract) = interpreter(1_000, Duration::from_millis(50));
+    let error = interp.run(contract).await.unwrap_err();
+    match error.downcast_ref::<InterpreterError>() {
+        Some(InterpreterError::Timeout { elapsed }) => {
+            assert!(*elapsed >= Duration::from_millis(200))
+        }
+        other => panic!("expected a timeout, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn generous_budget_lets_the_flow_finish() {
+    let (mut interp, contract) = interpreter(1_000, Duration::from_secs(30));
+    let status = interp.run(contract).await.unwrap();
+    assert!(matches!(status, ExecutionStatus::Completed(_)));
+    assert_eq!(interp.current_state().get("out"), Some(&json!("done")));
+}
+
+#[tokio::test]
+async fn gas_can_run_out_before_the_deadline() {
+    let (mut interp, contract) = interpreter(1, Duration::from_secs(30));
+    let error = interp.run(contract).await.unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<InterpreterError>(),
+        Some(error) if error.is_out_of_gas()
+    ));
+}
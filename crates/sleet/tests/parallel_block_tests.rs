@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::compute;
+use serde_json::{json, Value};
+use sleet::flows::definition::{BlockDefinition, BlockType, FlowDefinition, FlowValidationError};
+use sleet::{execute_flow_report, TranspileCache};
+
+fn parallel(id: &str, branches: &[&str], join_block: &str) -> BlockDefinition {
+    BlockDefinition::new(
+        id,
+        BlockType::Parallel {
+            branches: branches.iter().map(|b| b.to_string()).collect(),
+            join_block: join_block.to_string(),
+        },
+    )
+}
+
+fn fan_out_flow(left: BlockDefinition, right: BlockDefinition) -> FlowDefinition {
+    let mut flow = FlowDefinition::new("fan_out", "fork");
+    flow.set_initial_state(json!({ "counter": 1, "shared": { "kept": true } }));
+    flow.add_block(parallel("fork", &["left", "right"], "join"))
+        .add_block(left)
+        .add_block(right)
+        .add_block(compute("join", "state.counter", "joined", "done"))
+        .add_block(BlockDefinition::new("done", BlockType::Terminate));
+    flow
+}
+
+async fn final_state(flow: FlowDefinition) -> Value {
+    execute_flow_report(flow, 10_000, None, &TranspileCache::new())
+        .await
+        .unwrap()
+        .final_state
+}
+
+#[tokio::test]
+async fn branches_writing_distinct_keys_are_merged_before_the_join() {
+    let flow = fan_out_flow(
+        compute("left", "state.counter + 1", "shared.left", "join"),
+        compute("right", "state.counter * 10", "shared.right", "join"),
+    );
+
+    let state = final_state(flow).await;
+
+    assert_eq!(state["shared"], json!({ "kept": true, "left": 2, "right": 10 }));
+    assert_eq!(state["counter"], json!(1));
+    assert_eq!(state["joined"], json!(1));
+}
+
+#[tokio::test]
+async fn conflicting_writes_resolve_to_the_last_branch_in_order() {
+    let flow = fan_out_flow(
+        compute("left", "state.counter + 1", "counter", "join"),
+        compute("right", "state.counter + 10", "counter", "join"),
+    );
+
+    let state = final_state(flow).await;
+
+    assert_eq!(state["counter"], json!(11));
+    assert_eq!(state["joined"], json!(11));
+    assert_eq!(state["shared"], json!({ "kept": true }));
+}
+
+#[test]
+fn missing_branch_is_reported_as_a_dangling_reference() {
+    let mut flow = FlowDefinition::new("broken", "fork");
+    flow.add_block(parallel("fork", &["left", "ghost"], "done"))
+        .add_block(compute("left", "1", "value", "done"))
+        .add_block(BlockDefinition::new("done", BlockType::Terminate));
+
+    let errors = flow.validate().unwrap_err();
+
+    assert_eq!(
+        errors,
+        vec![FlowValidationError::DanglingReference {
+            block_id: "fork".to_string(),
+            field: "branches",
+            target: "ghost".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn awaiting_inside_a_branch_is_rejected_by_validation() {
+    let ask = BlockDefinition::new(
+        "ask",
+        BlockType::AwaitInput {
+            interaction_id: "approval".to_string(),
+            agent_id: "reviewer".to_string(),
+            prompt: "\"Approve?\"".to_string(),
+            state_key: "approved".to_string(),
+            next_block: "join".to_string(),
+        },
+    );
+    let mut flow = fan_out_flow(
+        compute("left", "1", "left", "join"),
+        compute("right", "2", "right", "ask"),
+    );
+    flow.add_block(ask);
+
+    assert_eq!(
+        flow.validate().unwrap_err(),
+        vec![FlowValidationError::AwaitInParallelBranch {
+            parallel_block: "fork".to_string(),
+            block_id: "ask".to_string(),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn awaiting_after_the_join_is_allowed() {
+    let mut flow = FlowDefinition::new("fan_out_then_ask", "fork");
+    flow.add_block(parallel("fork", &["left", "right"], "ask"))
+        .add_block(compute("left", "1", "left", "ask"))
+        .add_block(compute("right", "2", "right", "ask"))
+        .add_block(BlockDefinition::new(
+            "ask",
+            BlockType::AwaitInput {
+                interaction_id: "approval".to_string(),
+                agent_id: "reviewer".to_string(),
+                prompt: "\"Approve?\"".to_string(),
+                state_key: "approved".to_string(),
+                next_block: "done".to_string(),
+            },
+        ))
+        .add_block(BlockDefinition::new("done", BlockType::Terminate));
+
+    assert!(flow.validate().is_ok());
+    let report = execute_flow_report(flow, 10_000, None, &TranspileCache::new())
+        .await
+        .unwrap();
+    assert!(report.is_awaiting_input());
+    assert_eq!(report.final_state["left"], json!(1.0));
+    assert_eq!(report.final_state["right"], json!(2.0));
+}
@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::{compute, coordinator_config};
+use sleet::{
+    BlockDefinition, BlockType, FlowDefinition, OrchestrationCoordinator,
+    OrchestrationFlowDefinition,
+};
+
+fn saga_flow(fail_at_step_three: bool) -> OrchestrationFlowDefinition {
+    let mut flow = FlowDefinition::new("booking_saga", "reserve_seat");
+    flow.add_block(compute("reserve_seat", "\"reserved\"", "seat", "charge_card"))
+        .add_block(compute("charge_card", "\"charged\"", "payment", "send_ticket"))
+        .add_block(compute("release_seat", "\"released\"", "seat", ""))
+        .add_block(compute("refund_card", "\"refunded\"", "payment", ""))
+        .add_block(BlockDefinition::new("done", BlockType::Terminate));
+
+    if fail_at_step_three {
+        flow.add_block(BlockDefinition::new(
+            "send_ticket",
+            BlockType::Break {
+                loop_id: "missing_loop".to_string(),
+                output: None,
+            },
+        ));
+    } else {
+        flow.add_block(compute("send_ticket", "\"sent\"", "ticket", "done"));
+    }
+
+    let mut flow: OrchestrationFlowDefinition = flow.into();
+    for block in &mut flow.blocks {
+        block.compensation_block = match block.id.as_str() {
+            "reserve_seat" => Some("release_seat".to_string()),
+            "charge_card" => Some("refund_card".to_string()),
+            _ => None,
+        };
+    }
+    flow
+}
+
+#[tokio::test]
+async fn failure_at_step_three_compensates_earlier_steps_in_reverse_order() {
+    let coordinator = OrchestrationCoordinator::new(coordinator_config())
+        .await
+        .unwrap();
+
+    let error = coordinator
+        .execute_flow(saga_flow(true), None)
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("missing_loop"));
+
+    let dead_letters = coordinator.dead_letters().await;
+    assert_eq!(dead_letters.len(), 1);
+
+    let dead_letter = &dead_letters[0];
+    assert_eq!(dead_letter.flow_id, "booking_saga");
+    assert_eq!(dead_letter.failed_block, "send_ticket");
+
+    let compensations: Vec<(&str, &str)> = dead_letter
+        .compensations
+        .iter()
+        .map(|record| (record.block_id.as_str(), record.compensation_block.as_str()))
+        .collect();
+    assert_eq!(
+        compensations,
+        vec![("charge_card", "refund_card"), ("reserve_seat", "release_seat")]
+    );
+    assert!(dead_letter
+        .compensations
+        .iter()
+        .all(|record| record.error.is_none()));
+}
+
+#[tokio::test]
+async fn successful_flow_runs_no_compensations() {
+    let coordinator = OrchestrationCoordinator::new(coordinator_config())
+        .await
+        .unwrap();
+
+    coordinator
+        .execute_flow(saga_flow(false), None)
+        .await
+        .unwrap();
+
+    assert!(coordinator.dead_letters().await.is_empty());
+}
+
+#[tokio::test]
+async fn unknown_compensation_block_is_rejected() {
+    let coordinator = OrchestrationCoordinator::new(coordinator_config())
+        .await
+        .unwrap();
+
+    let mut flow = saga_flow(false);
+    flow.blocks[0].compensation_block = Some("undo_nothing".to_string());
+
+    let error = coordinator.execute_flow(flow, None).await.unwrap_err();
+    assert!(error.to_string().contains("undo_nothing"));
+}
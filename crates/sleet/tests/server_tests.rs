@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+#![cfg(feature = "server")]
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use serde_json::{json, Value};
+use sleet::orchestration::coordinator::StorageConfig;
+use sleet::server::{ServerConfig, SleetServer};
+use sleet::{BlockDefinition, BlockType, FlowDefinition, OrchestrationConfig};
+use tower::ServiceExt;
+
+async fn router(max_body_bytes: usize) -> Router {
+    let mut orchestration = OrchestrationConfig::default();
+    orchestration.enable_persistence = false;
+    orchestration.storage_config = StorageConfig {
+        session_storage_path: None,
+        checkpoint_storage_path: None,
+        log_storage_path: None,
+    };
+    let config = ServerConfig {
+        max_body_bytes,
+        max_gas: 10_000,
+        orchestration,
+    };
+    SleetServer::new(config).await.unwrap().router()
+}
+
+async fn post(router: &Router, uri: &str, body: Vec<u8>) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+fn greeting_flow(await_user: bool) -> Vec<u8> {
+    let mut flow = FlowDefinition::new("greeting", "start");
+    let after_start = if await_user { "await_user" } else { "done" };
+    flow.add_block(BlockDefinition::new(
+        "start",
+        BlockType::Compute {
+            expression: "\"hello\"".to_string(),
+            output_key: "greeting".to_string(),
+            next_block: after_start.to_string(),
+        },
+    ))
+    .add_block(BlockDefinition::new(
+        "await_user",
+        BlockType::AwaitInput {
+            interaction_id: "approval".to_string(),
+            agent_id: "reviewer".to_string(),
+            prompt: "Approve the greeting?".to_string(),
+            state_key: "answer".to_string(),
+            next_block: "done".to_string(),
+        },
+    ))
+    .add_block(BlockDefinition::new("done", BlockType::Terminate));
+    serde_json::to_vec(&flow).unwrap()
+}
+
+#[tokio::test]
+async fn posting_a_flow_returns_its_execution_status() {
+    let router = router(64 * 1024).await;
+
+    let (status, body) = post(&router, "/v1/flows", greeting_flow(false)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "Completed": { "Integer": 0 } }));
+}
+
+#[tokio::test]
+async fn awaits_can_be_resumed_over_http() {
+    let router = router(64 * 1024).await;
+
+    let (status, body) = post(&router, "/v1/flows", greeting_flow(true)).await;
+    assert_eq!(status, StatusCode::OK);
+    let awaiting = &body["AwaitingInput"];
+    assert_eq!(awaiting["interaction_id"], "approval");
+    assert_eq!(awaiting["agent_id"], "reviewer");
+    let session_id = awaiting["session_id"].as_str().unwrap().to_string();
+
+    let wrong = format!("/v1/sessions/{session_id}/interactions/other");
+    let (status, _) = post(&router, &wrong, b"\"yes\"".to_vec()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let resume = format!("/v1/sessions/{session_id}/interactions/approval");
+    let (status, body) = post(&router, &resume, b"\"yes\"".to_vec()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "Completed": { "Integer": 0 } }));
+
+    let (status, _) = post(&router, &resume, b"\"again\"".to_vec()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn gas_above_the_server_maximum_is_rejected() {
+    let router = router(64 * 1024).await;
+
+    let (status, body) = post(&router, "/v1/flows?gas_limit=20000", greeting_flow(false)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("20000"));
+}
+
+#[tokio::test]
+async fn oversized_requests_are_rejected() {
+    let router = router(16).await;
+
+    let (status, _) = post(&router, "/v1/flows", greeting_flow(false)).await;
+
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+}
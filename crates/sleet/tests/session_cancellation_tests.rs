@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use async_trait::async_trait;
+use common::coordinator_config;
+use llm_contracts::{LLMError, LLMRequest, LLMResponse, LLMResult, StreamChunk};
+use sleet::orchestration::OrchestrationError;
+use sleet::{BlockDefinition, BlockType, FlowDefinition, OrchestrationCoordinator};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use stele::llm::core::LLMAdapter;
+use tokio::sync::oneshot;
+
+struct AbortProbe {
+    aborted: Arc<AtomicBool>,
+    armed: bool,
+}
+
+impl Drop for AbortProbe {
+    fn drop(&mut self) {
+        if self.armed {
+            self.aborted.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+struct SlowAdapter {
+    started: Mutex<Option<oneshot::Sender<String>>>,
+    aborted: Arc<AtomicBool>,
+    completed: AtomicBool,
+}
+
+impl SlowAdapter {
+    fn new(started: oneshot::Sender<String>) -> Self {
+        Self {
+            started: Mutex::new(Some(started)),
+            aborted: Arc::new(AtomicBool::new(false)),
+            completed: AtomicBool::new(false),
+        }
+    }
+}
+
+#[async_trait]
+impl LLMAdapter for SlowAdapter {
+    async fn generate_response(&self, request: LLMRequest) -> LLMResult<LLMResponse> {
+        let mut probe = AbortProbe {
+            aborted: self.aborted.clone(),
+            armed: true,
+        };
+
+        let session_id = request
+            .context
+            .and_then(|context| context.session_id)
+            .unwrap_or_default();
+        if let Some(started) = self.started.lock().unwrap().take() {
+            let _ = started.send(session_id);
+        }
+
+        tokio::time::sleep(Duration::from_secs(30)).await;
+
+        probe.armed = false;
+        self.completed.store(true, Ordering::SeqCst);
+        Err(LLMError::Timeout)
+    }
+
+    async fn generate_streaming_response(
+        &self,
+        _request: LLMRequest,
+    ) -> LLMResult<tokio::sync::mpsc::Receiver<LLMResult<StreamChunk>>> {
+        Err(LLMError::Provider("streaming is not stubbed".to_string()))
+    }
+
+    async fn get_available_models(&self) -> LLMResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    async fn health_check(&self) -> LLMResult<()> {
+        Ok(())
+    }
+}
+
+fn slow_flow() -> FlowDefinition {
+    let mut flow = FlowDefinition::new("slow_generation", "generate");
+    flow.add_block(BlockDefinition::new(
+        "generate",
+        BlockType::Generate {
+            prompt_expression: "\"Take your time\"".to_string(),
+            model: None,
+            output_key: "answer".to_string(),
+            next_block: "done".to_string(),
+        },
+    ))
+    .add_block(BlockDefinition::new("done", BlockType::Terminate));
+    flow
+}
+
+#[tokio::test]
+async fn cancelling_a_session_aborts_its_in_flight_llm_call() {
+    let coordinator = Arc::new(
+        OrchestrationCoordinator::new(coordinator_config())
+            .await
+            .unwrap(),
+    );
+    let (started_tx, started_rx) = oneshot::channel();
+    let adapter = Arc::new(SlowAdapter::new(started_tx));
+    coordinator.set_generation_adapter(adapter.clone()).await;
+
+    let running = tokio::spawn({
+        let coordinator = coordinator.clone();
+        async move { coordinator.execute_flow(slow_flow().into(), None).await }
+    });
+
+    let session_id = started_rx.await.unwrap();
+    coordinator.cancel_session(&session_id).await.unwrap();
+
+    let result = tokio::time::timeout(Duration::from_secs(5), running)
+        .await
+        .expect("cancellation should end the flow promptly")
+        .unwrap();
+
+    assert!(matches!(result, Err(OrchestrationError::Cancelled(_))));
+    assert!(adapter.aborted.load(Ordering::SeqCst));
+    assert!(!adapter.completed.load(Ordering::SeqCst));
+    assert!(coordinator.dead_letters().await.is_empty());
+}
+
+#[tokio::test]
+async fn cancelling_an_unknown_session_is_an_error() {
+    let coordinator = OrchestrationCoordinator::new(coordinator_config())
+        .await
+        .unwrap();
+
+    let error = coordinator.cancel_session("missing").await.unwrap_err();
+    assert!(matches!(error, OrchestrationError::SessionError(_)));
+}
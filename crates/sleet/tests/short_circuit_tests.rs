@@ -0,0 +1,100 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::{compute_flow, contract};
+use serde_json::json;
+use sleet::ast::{AstNode, Contract, Literal, Op};
+use sleet::runtime::{create_ergonomic_ffi, FfiRegistry, RemarkableInterpreter};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+fn recording_registry(calls: &Arc<AtomicUsize>) -> FfiRegistry {
+    let calls = Arc::clone(calls);
+    FfiRegistry::from([(
+        "expensive".to_string(),
+        create_ergonomic_ffi(move |_args, _state| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok(json!(true))
+        }),
+    )])
+}
+
+fn run_expression(expression: &str) -> (serde_json::Value, usize) {
+    let flow = compute_flow("logic", expression, "out");
+    let contract = contract(&flow);
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut exec = RemarkableInterpreter::new(1_000, &contract, recording_registry(&calls)).unwrap();
+    exec.run_until_paused().unwrap();
+    (exec.state()["out"].clone(), calls.load(Ordering::SeqCst))
+}
+
+#[test]
+fn and_skips_right_operand_when_left_is_false() {
+    assert_eq!(run_expression("false && expensive()"), (json!(false), 0));
+    assert_eq!(run_expression("true && expensive()"), (json!(true), 1));
+}
+
+#[test]
+fn or_skips_right_operand_when_left_is_true() {
+    assert_eq!(run_expression("true || expensive()"), (json!(true), 0));
+    assert_eq!(run_expression("false || expensive()"), (json!(true), 1));
+}
+
+#[test]
+fn logical_operators_yield_booleans() {
+    assert_eq!(run_expression("1 && 0").0, json!(false));
+    assert_eq!(run_expression("0 || 2").0, json!(true));
+}
+
+#[test]
+fn ast_level_and_or_short_circuit() {
+    let call = || {
+        AstNode::from(Op::Call {
+            callee: Box::new(AstNode::from(Op::Literal(Literal::String(
+                "expensive".to_string(),
+            )))),
+            args: vec![],
+        })
+    };
+    let boolean = |b| AstNode::from(Op::Literal(Literal::Bool(b)));
+    let body = AstNode::from(Op::Sequence(vec![
+        AstNode::from(Op::Assign {
+            path: vec!["a".to_string()].into(),
+            value: Box::new(AstNode::from(Op::And(
+                Box::new(boolean(false)),
+                Box::new(call()),
+            ))),
+        }),
+        AstNode::from(Op::Assign {
+            path: vec!["b".to_string()].into(),
+            value: Box::new(AstNode::from(Op::Or(Box::new(boolean(true)), Box::new(call())))),
+        }),
+        AstNode::from(Op::Terminate),
+    ]));
+    let contract = Contract {
+        version: "1.0".to_string(),
+        start_block_id: "start".to_string(),
+        blocks: HashMap::from([("start".to_string(), body)]),
+        initial_state: AstNode::from(Op::Literal(Literal::JsonValue(json!({})))),
+        permissions: json!({}),
+        participants: vec![],
+    };
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut exec = RemarkableInterpreter::new(1_000, &contract, recording_registry(&calls)).unwrap();
+    exec.run_until_paused().unwrap();
+    assert_eq!(exec.state()["a"], json!(false));
+    assert_eq!(exec.state()["b"], json!(true));
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+}
@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::{compute, conditional, flow, terminate};
+use serde_json::json;
+use sleet::flows::definition::{BlockType, FlowDefinition};
+use sleet::simulation::SimulationOutcome;
+use sleet::{simulate, FfiStubRegistry, FlowTranspiler};
+
+fn risk_review_flow() -> FlowDefinition {
+    flow(
+        "approval",
+        vec![
+            compute("check", "risk_score(state.amount)", "score", "decide"),
+            conditional("decide", "state.score > 50", "reject", "approve"),
+            compute("approve", "\"approved\"", "decision", "end"),
+            compute("reject", "\"rejected\"", "decision", "end"),
+            terminate("end"),
+        ],
+    )
+}
+
+#[test]
+fn low_risk_stub_takes_approval_branch() {
+    let mut stubs = FfiStubRegistry::new();
+    stubs.stub("risk_score", json!(10));
+    let report = simulate(&risk_review_flow(), json!({ "amount": 250 }), &stubs).unwrap();
+
+    assert_eq!(report.outcome, SimulationOutcome::Completed);
+    assert_eq!(report.path, vec!["check", "decide", "approve", "end"]);
+    assert_eq!(report.final_state["decision"], json!("approved"));
+    assert_eq!(report.final_state["score"], json!(10));
+    assert_eq!(report.ffi_calls.len(), 1);
+    assert_eq!(report.ffi_calls[0].name, "risk_score");
+    assert_eq!(report.ffi_calls[0].args, vec![json!(250)]);
+}
+
+#[test]
+fn high_risk_stub_takes_rejection_branch() {
+    let mut stubs = FfiStubRegistry::new();
+    stubs.stub("risk_score", json!(90));
+    let report = simulate(&risk_review_flow(), json!({ "amount": 250 }), &stubs).unwrap();
+
+    assert_eq!(report.path, vec!["check", "decide", "reject", "end"]);
+    assert_eq!(report.final_state["decision"], json!("rejected"));
+    assert!(!report.visited("approve"));
+}
+
+#[test]
+fn unstubbed_function_fails_with_path_so_far() {
+    let err = simulate(
+        &risk_review_flow(),
+        json!({ "amount": 250 }),
+        &FfiStubRegistry::new(),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("risk_score"));
+}
+
+#[test]
+fn function_callees_are_not_checked_against_the_state_schema() {
+    let mut flow = risk_review_flow();
+    flow.set_state_schema(json!({
+        "type": "object",
+        "properties": { "amount": {}, "score": {}, "decision": {} }
+    }));
+    assert!(FlowTranspiler::transpile(&flow).is_ok());
+
+    let mut stubs = FfiStubRegistry::new();
+    stubs.stub("risk_score", json!(10));
+    let report = simulate(&flow, json!({ "amount": 250 }), &stubs).unwrap();
+    assert_eq!(report.ffi_calls[0].name, "risk_score");
+
+    let mut flow = risk_review_flow();
+    flow.set_state_schema(json!({ "type": "object", "properties": { "amount": {} } }));
+    flow.blocks[0].block_type = BlockType::Compute {
+        expression: "state.risk_score(state.amount)".to_string(),
+        output_key: "score".to_string(),
+        next_block: "decide".to_string(),
+    };
+    let err = FlowTranspiler::transpile(&flow).unwrap_err();
+    assert!(err.to_string().contains("state.risk_score"));
+}
@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::{compute_flow_with_state, contract};
+use serde_json::json;
+use sleet::runtime::{FfiRegistry, InterpreterError, RemarkableInterpreter};
+
+fn executor(expression: &str) -> RemarkableInterpreter {
+    let flow = compute_flow_with_state(
+        "paths",
+        json!({ "present": { "field": 7 }, "empty": null }),
+        expression,
+        "out",
+    );
+    let contract = contract(&flow);
+    RemarkableInterpreter::new(1_000, &contract, FfiRegistry::new()).unwrap()
+}
+
+#[test]
+fn lenient_mode_yields_null_for_missing_nested_field() {
+    let mut exec = executor("state.nonexistent.field");
+    assert!(!exec.strict_paths());
+    exec.run_until_paused().unwrap();
+    assert_eq!(exec.state()["out"], json!(null));
+}
+
+#[test]
+fn strict_mode_rejects_missing_nested_field() {
+    let mut exec = executor("state.nonexistent.field");
+    exec.set_strict_paths(true);
+    match exec.run_until_paused() {
+        Err(InterpreterError::PathNotFound { path, block_id }) => {
+            assert_eq!(path, "state.nonexistent.field");
+            assert_eq!(block_id, "start");
+        }
+        other => panic!("expected PathNotFound, got {other:?}"),
+    }
+}
+
+#[test]
+fn strict_mode_rejects_traversal_into_null() {
+    let mut exec = executor("state.empty.field");
+    exec.set_strict_paths(true);
+    assert!(matches!(
+        exec.run_until_paused(),
+        Err(InterpreterError::PathNotFound { .. })
+    ));
+}
+
+#[test]
+fn strict_mode_allows_present_paths() {
+    let mut exec = executor("state.present.field");
+    exec.set_strict_paths(true);
+    exec.run_until_paused().unwrap();
+    assert_eq!(exec.state()["out"], json!(7));
+}
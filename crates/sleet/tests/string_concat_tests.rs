@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::{compute_flow, compute_flow_with_state, contract};
+use serde_json::{json, Value};
+use sleet::ast::Op;
+use sleet::runtime::{disassemble, FfiRegistry, InterpreterError, OpCode, RemarkableInterpreter};
+
+fn executor(expression: &str) -> RemarkableInterpreter {
+    let flow = compute_flow_with_state(
+        "strings",
+        json!({ "name": "ada", "count": 3, "tags": ["a", "b"] }),
+        expression,
+        "result",
+    );
+    let contract = contract(&flow);
+    RemarkableInterpreter::new(1_000, &contract, FfiRegistry::new()).unwrap()
+}
+
+fn evaluate(expression: &str) -> Result<Value, InterpreterError> {
+    let mut executor = executor(expression);
+    executor.run_until_paused()?;
+    Ok(executor.state()["result"].clone())
+}
+
+#[test]
+fn plus_concatenates_a_literal_prefix_with_a_state_field() {
+    assert_eq!(evaluate("\"user_\" + state.name").unwrap(), json!("user_ada"));
+}
+
+#[test]
+fn plus_concatenates_when_either_operand_is_a_string_at_runtime() {
+    assert_eq!(evaluate("state.name + state.count").unwrap(), json!("ada3"));
+    assert_eq!(evaluate("state.count + 1").unwrap(), json!(4));
+}
+
+#[test]
+fn numbers_coerce_to_their_display_form() {
+    assert_eq!(evaluate("\"total: \" + state.count").unwrap(), json!("total: 3"));
+    assert_eq!(
+        evaluate("concat(\"v\", 2, \"_\", 1.5, state.name)").unwrap(),
+        json!("v2_1.5ada")
+    );
+    assert_eq!(evaluate("concat()").unwrap(), json!(""));
+}
+
+#[test]
+fn concat_builtin_emits_the_concat_opcode() {
+    let flow = compute_flow("strings", "concat(\"a\", \"b\")", "result");
+    let contract = contract(&flow);
+    let mut opcodes = Vec::new();
+    contract.blocks["start"].walk(&mut |node| {
+        if let Op::Evaluate { bytecode, .. } = &node.op {
+            opcodes.extend(disassemble(bytecode).unwrap().into_iter().map(|i| i.opcode));
+        }
+    });
+    assert_eq!(opcodes.iter().filter(|op| **op == OpCode::Concat).count(), 2);
+    assert!(!opcodes.contains(&OpCode::Call));
+}
+
+#[test]
+fn concatenating_a_string_with_an_array_is_a_type_error() {
+    for expression in ["\"tags: \" + state.tags", "concat(\"tags: \", state.tags)"] {
+        match evaluate(expression) {
+            Err(InterpreterError::TypeMismatch { found, .. }) => {
+                assert!(found.contains("Concat"), "{found}")
+            }
+            other => panic!("expected a type mismatch for {expression}, got {other:?}"),
+        }
+    }
+}
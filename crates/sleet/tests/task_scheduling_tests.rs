@@ -0,0 +1,130 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use chrono::{Duration, Utc};
+use sleet::tasks::{
+    TaskConfig, TaskPriority, TaskProposal, TaskStatus, TaskSystem, TaskSystemConfig,
+};
+
+fn add_task(system: &mut TaskSystem, title: &str) -> String {
+    system
+        .create_task(TaskConfig {
+            title: title.to_string(),
+            ..TaskConfig::default()
+        })
+        .unwrap()
+        .id
+}
+
+#[test]
+fn ready_tasks_follow_priority_deadline_and_dependencies() {
+    let now = Utc::now();
+    let mut system = TaskSystem::new(TaskSystemConfig::default());
+    let low = add_task(&mut system, "low");
+    let high_late = add_task(&mut system, "high late");
+    let high_soon = add_task(&mut system, "high soon");
+    let overdue = add_task(&mut system, "overdue");
+    let critical = add_task(&mut system, "critical");
+
+    let proposals = [
+        TaskProposal::new("agent".to_string(), low.clone()).with_priority(TaskPriority::Low),
+        TaskProposal::new("agent".to_string(), high_late.clone())
+            .with_priority(TaskPriority::High)
+            .with_deadline(now + Duration::hours(2)),
+        TaskProposal::new("agent".to_string(), high_soon.clone())
+            .with_priority(TaskPriority::High)
+            .with_deadline(now + Duration::hours(1)),
+        TaskProposal::new("agent".to_string(), overdue.clone())
+            .with_priority(TaskPriority::Medium)
+            .with_deadline(now - Duration::hours(1)),
+        TaskProposal::new("agent".to_string(), critical.clone())
+            .with_priority(TaskPriority::Critical)
+            .depends_on(low.clone()),
+    ];
+    for proposal in proposals {
+        system.submit_proposal(proposal).unwrap();
+    }
+
+    let mut order = Vec::new();
+    while let Some(next) = system.ready_queue_at(now).into_iter().next() {
+        if next.task_id == overdue {
+            assert!(next.overdue);
+        } else {
+            assert!(!next.overdue);
+        }
+        system
+            .update_task_status(&next.task_id, TaskStatus::Completed)
+            .unwrap();
+        order.push(next.task_id);
+    }
+
+    assert_eq!(order, vec![high_soon, high_late, overdue, low, critical]);
+}
+
+#[test]
+fn blocked_tasks_are_not_ready_and_overdue_ones_are_flagged() {
+    let now = Utc::now();
+    let mut system = TaskSystem::new(TaskSystemConfig::default());
+    let first = add_task(&mut system, "first");
+    let second = add_task(&mut system, "second");
+    system
+        .submit_proposal(
+            TaskProposal::new("agent".to_string(), first.clone())
+                .with_deadline(now - Duration::minutes(5)),
+        )
+        .unwrap();
+    system
+        .submit_proposal(
+            TaskProposal::new("agent".to_string(), second.clone())
+                .with_priority(TaskPriority::Critical)
+                .depends_on(first.clone()),
+        )
+        .unwrap();
+
+    let ready = system.ready_queue_at(now);
+    assert_eq!(ready.len(), 1);
+    assert_eq!(ready[0].task_id, first);
+    assert!(ready[0].overdue);
+
+    let overdue = system.overdue_proposals_at(now);
+    assert_eq!(overdue.len(), 1);
+    assert_eq!(overdue[0].task_id, first);
+}
+
+#[test]
+fn failed_and_cancelled_tasks_are_not_overdue() {
+    let now = Utc::now();
+    let mut system = TaskSystem::new(TaskSystemConfig::default());
+    let mut task_ids = Vec::new();
+    for title in ["open", "failed", "cancelled", "completed"] {
+        let task_id = add_task(&mut system, title);
+        system
+            .submit_proposal(
+                TaskProposal::new("agent".to_string(), task_id.clone())
+                    .with_deadline(now - Duration::minutes(5)),
+            )
+            .unwrap();
+        task_ids.push(task_id);
+    }
+    for (task_id, status) in task_ids[1..].iter().zip([
+        TaskStatus::Failed,
+        TaskStatus::Cancelled,
+        TaskStatus::Completed,
+    ]) {
+        system.update_task_status(task_id, status).unwrap();
+    }
+
+    let overdue = system.overdue_proposals_at(now);
+    assert_eq!(overdue.len(), 1);
+    assert_eq!(overdue[0].task_id, task_ids[0]);
+    assert_eq!(system.ready_queue_at(now).len(), 1);
+}
@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use sleet::flows::definition::{BlockDefinition, BlockType, FlowDefinition};
+use sleet::runtime::{FfiRegistry, RemarkableInterpreter};
+use sleet::{convert_contract, FlowTranspiler};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+
+#[derive(Default)]
+struct Fields(HashMap<String, String>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.insert(field.name().to_string(), format!("{value:?}"));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+}
+
+type CapturedSpan = (String, HashMap<String, String>);
+
+#[derive(Clone, Default)]
+struct Capture {
+    spans: Arc<Mutex<Vec<CapturedSpan>>>,
+    events: Arc<Mutex<Vec<HashMap<String, String>>>>,
+}
+
+impl<S> Layer<S> for Capture
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+        self.spans
+            .lock()
+            .unwrap()
+            .push((attrs.metadata().name().to_string(), fields.0));
+    }
+
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        self.events.lock().unwrap().push(fields.0);
+    }
+}
+
+fn compute(id: &str, expression: &str, next: &str) -> BlockDefinition {
+    BlockDefinition::new(
+        id,
+        BlockType::Compute {
+            expression: expression.to_string(),
+            output_key: id.to_string(),
+            next_block: next.to_string(),
+        },
+    )
+}
+
+#[test]
+fn one_span_is_opened_per_executed_block() {
+    let mut flow = FlowDefinition::new("traced", "first");
+    flow.add_block(compute("first", "1 + 1", "second"))
+        .add_block(compute("second", "2 + 2", "end"))
+        .add_block(BlockDefinition::new("end", BlockType::Terminate));
+    let contract = convert_contract(FlowTranspiler::transpile(&flow).unwrap()).unwrap();
+    let mut exec = RemarkableInterpreter::new(1_000, &contract, FfiRegistry::new()).unwrap();
+    let session_id = exec.session_id().to_string();
+
+    let capture = Capture::default();
+    let subscriber = tracing_subscriber::registry().with(capture.clone());
+    tracing::subscriber::with_default(subscriber, || exec.run_until_paused().unwrap());
+
+    let spans = capture.spans.lock().unwrap();
+    let blocks: Vec<&str> = spans
+        .iter()
+        .filter(|(name, _)| name == "block")
+        .map(|(_, fields)| fields["block_id"].as_str())
+        .collect();
+    assert_eq!(blocks, vec!["first", "second", "end"]);
+    for (_, fields) in spans.iter() {
+        assert_eq!(fields["session_id"], session_id);
+        assert!(fields.contains_key("gas_before"));
+    }
+}
+
+#[test]
+fn failing_ffi_calls_emit_events_with_gas() {
+    let mut flow = FlowDefinition::new("traced", "call");
+    flow.add_block(compute("call", "missing_fn(1)", "end"))
+        .add_block(BlockDefinition::new("end", BlockType::Terminate));
+    let contract = convert_contract(FlowTranspiler::transpile(&flow).unwrap()).unwrap();
+    let mut exec = RemarkableInterpreter::new(1_000, &contract, FfiRegistry::new()).unwrap();
+
+    let capture = Capture::default();
+    let subscriber = tracing_subscriber::registry().with(capture.clone());
+    tracing::subscriber::with_default(subscriber, || assert!(exec.run_until_paused().is_err()));
+
+    let events = capture.events.lock().unwrap();
+    let call = events
+        .iter()
+        .find(|fields| fields.get("message").map(String::as_str) == Some("ffi call"))
+        .expect("ffi call event");
+    assert_eq!(call["function"], "missing_fn");
+    assert!(call.contains_key("gas_used"));
+    assert!(events
+        .iter()
+        .any(|fields| fields.get("message").map(String::as_str) == Some("ffi call failed")));
+}
@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use common::compute_flow;
+use sleet::flows::definition::FlowDefinition;
+use sleet::{execute_flow_with_cache, CacheStats, TranspileCache};
+use std::sync::Arc;
+
+fn flow(expression: &str) -> FlowDefinition {
+    compute_flow("cached", expression, "total")
+}
+
+#[tokio::test]
+async fn second_execution_hits_cache() {
+    let cache = TranspileCache::new();
+    execute_flow_with_cache(flow("1 + 2"), 1_000, None, &cache)
+        .await
+        .unwrap();
+    execute_flow_with_cache(flow("1 + 2"), 1_000, None, &cache)
+        .await
+        .unwrap();
+    assert_eq!(
+        cache.stats(),
+        CacheStats {
+            hits: 1,
+            misses: 1,
+            entries: 1
+        }
+    );
+}
+
+#[test]
+fn changed_flow_is_transpiled_again() {
+    let cache = TranspileCache::new();
+    let first = cache.get_or_transpile(&flow("1 + 2")).unwrap();
+    let again = cache.get_or_transpile(&flow("1 + 2")).unwrap();
+    let changed = cache.get_or_transpile(&flow("1 + 3")).unwrap();
+    assert!(Arc::ptr_eq(&first, &again));
+    assert!(!Arc::ptr_eq(&first, &changed));
+    assert_eq!(first.content_hash().unwrap(), again.content_hash().unwrap());
+    assert_ne!(first.content_hash().unwrap(), changed.content_hash().unwrap());
+    assert_eq!(cache.stats().misses, 2);
+}
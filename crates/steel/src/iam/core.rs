@@ -62,6 +62,19 @@ pub struct Proof {
     pub proof_value: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CredentialStatus {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub status_type: String,
+    #[serde(rename = "statusPurpose")]
+    pub status_purpose: String,
+    #[serde(rename = "statusListIndex")]
+    pub status_list_index: String,
+    #[serde(rename = "statusListCredential")]
+    pub status_list_credential: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct VerifiableCredential {
     #[serde(rename = "@context")]
@@ -74,5 +87,11 @@ pub struct VerifiableCredential {
     pub issuance_date: String,
     #[serde(rename = "credentialSubject")]
     pub credential_subject: HashMap<String, serde_json::Value>,
+    #[serde(
+        rename = "credentialStatus",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub credential_status: Option<CredentialStatus>,
     pub proof: Proof,
 }
@@ -0,0 +1,171 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::iam::core::DidDocument;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum DidResolutionError {
+    #[error("Invalid DID: {0}")]
+    InvalidDid(String),
+    #[error("DID not found: {0}")]
+    NotFound(String),
+    #[error("DID resolver unavailable: {0}")]
+    Unavailable(String),
+}
+
+#[async_trait]
+pub trait DidResolver: Send + Sync {
+    async fn resolve(&self, did: &str) -> Result<DidDocument, DidResolutionError>;
+}
+
+#[derive(Debug, Clone)]
+pub struct DidCacheConfig {
+    pub ttl: Duration,
+    pub negative_ttl: Duration,
+    pub max_entries: usize,
+}
+
+impl Default for DidCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(300),
+            negative_ttl: Duration::from_secs(10),
+            max_entries: 1024,
+        }
+    }
+}
+
+struct CacheEntry {
+    result: Result<DidDocument, DidResolutionError>,
+    expires_at: Instant,
+    last_used: Instant,
+}
+
+pub struct DidResolverCache {
+    resolver: Arc<dyn DidResolver>,
+    config: DidCacheConfig,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl std::fmt::Debug for DidResolverCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DidResolverCache")
+            .field("config", &self.config)
+            .field("entries", &self.len())
+            .finish()
+    }
+}
+
+impl DidResolverCache {
+    pub fn new(resolver: Arc<dyn DidResolver>, config: DidCacheConfig) -> Self {
+        Self {
+            resolver,
+            config,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_default_config(resolver: Arc<dyn DidResolver>) -> Self {
+        Self::new(resolver, DidCacheConfig::default())
+    }
+
+    pub fn config(&self) -> &DidCacheConfig {
+        &self.config
+    }
+
+    pub async fn resolve(&self, did: &str) -> Result<DidDocument, DidResolutionError> {
+        if let Some(cached) = self.cached(did) {
+            return cached;
+        }
+
+        let result = self.resolver.resolve(did).await;
+        let ttl = match result {
+            Ok(_) => self.config.ttl,
+            Err(_) => self.config.negative_ttl,
+        };
+        self.store(did, result.clone(), ttl);
+        result
+    }
+
+    pub fn invalidate(&self, did: &str) -> bool {
+        self.lock().remove(did).is_some()
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, CacheEntry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn cached(&self, did: &str) -> Option<Result<DidDocument, DidResolutionError>> {
+        let now = Instant::now();
+        let mut entries = self.lock();
+        let entry = entries.get_mut(did)?;
+        if entry.expires_at <= now {
+            entries.remove(did);
+            return None;
+        }
+        entry.last_used = now;
+        Some(entry.result.clone())
+    }
+
+    fn store(&self, did: &str, result: Result<DidDocument, DidResolutionError>, ttl: Duration) {
+        if self.config.max_entries == 0 || ttl.is_zero() {
+            return;
+        }
+        let now = Instant::now();
+        let mut entries = self.lock();
+        if !entries.contains_key(did) && entries.len() >= self.config.max_entries {
+            entries.retain(|_, entry| entry.expires_at > now);
+            while entries.len() >= self.config.max_entries {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used)
+                    .map(|(key, _)| key.clone());
+                let Some(key) = oldest else {
+                    break;
+                };
+                entries.remove(&key);
+            }
+        }
+        entries.insert(
+            did.to_string(),
+            CacheEntry {
+                result,
+                expires_at: now + ttl,
+                last_used: now,
+            },
+        );
+    }
+}
+
+#[async_trait]
+impl DidResolver for DidResolverCache {
+    async fn resolve(&self, did: &str) -> Result<DidDocument, DidResolutionError> {
+        DidResolverCache::resolve(self, did).await
+    }
+}
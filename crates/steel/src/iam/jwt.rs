@@ -11,9 +11,13 @@
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
 use chrono::{Duration, Utc};
-use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::{
+    decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation,
+};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+
+pub const DEFAULT_KEY_ID: &str = "default";
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
@@ -29,7 +33,8 @@ pub struct Claims {
 }
 
 pub struct JwtManager {
-    secret: String,
+    keys: HashMap<String, String>,
+    active_kid: String,
     issuer: String,
     audience: String,
 }
@@ -37,7 +42,8 @@ pub struct JwtManager {
 impl Clone for JwtManager {
     fn clone(&self) -> Self {
         Self {
-            secret: self.secret.clone(),
+            keys: self.keys.clone(),
+            active_kid: self.active_kid.clone(),
             issuer: self.issuer.clone(),
             audience: self.audience.clone(),
         }
@@ -49,20 +55,63 @@ impl std::fmt::Debug for JwtManager {
         f.debug_struct("JwtManager")
             .field("issuer", &self.issuer)
             .field("audience", &self.audience)
-            .field("secret", &"[REDACTED]")
+            .field("active_kid", &self.active_kid)
+            .field("key_ids", &self.key_ids())
+            .field("secrets", &"[REDACTED]")
             .finish()
     }
 }
 
 impl JwtManager {
     pub fn new(secret: &str, issuer: String, audience: String) -> Self {
+        let mut keys = HashMap::new();
+        keys.insert(DEFAULT_KEY_ID.to_string(), secret.to_string());
         Self {
-            secret: secret.to_string(),
+            keys,
+            active_kid: DEFAULT_KEY_ID.to_string(),
             issuer,
             audience,
         }
     }
 
+    pub fn add_key(&mut self, kid: impl Into<String>, key: &str) {
+        self.keys.insert(kid.into(), key.to_string());
+    }
+
+    pub fn set_active_key(&mut self, kid: &str) -> Result<(), TokenError> {
+        if !self.keys.contains_key(kid) {
+            return Err(TokenError::UnknownKeyId(kid.to_string()));
+        }
+        self.active_kid = kid.to_string();
+        Ok(())
+    }
+
+    pub fn rotate_key(&mut self, kid: impl Into<String>, key: &str) {
+        let kid = kid.into();
+        self.keys.insert(kid.clone(), key.to_string());
+        self.active_kid = kid;
+    }
+
+    pub fn remove_key(&mut self, kid: &str) -> Result<(), TokenError> {
+        if kid == self.active_kid {
+            return Err(TokenError::ActiveKeyInUse(kid.to_string()));
+        }
+        self.keys
+            .remove(kid)
+            .map(|_| ())
+            .ok_or_else(|| TokenError::UnknownKeyId(kid.to_string()))
+    }
+
+    pub fn active_key_id(&self) -> &str {
+        &self.active_kid
+    }
+
+    pub fn key_ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self.keys.keys().map(String::as_str).collect();
+        ids.sort_unstable();
+        ids
+    }
+
     pub fn create_token(
         &self,
         user_id: &str,
@@ -87,39 +136,45 @@ impl JwtManager {
             roles,
         };
 
-        let header = Header::new(Algorithm::HS256);
-        let encoding_key = EncodingKey::from_secret(self.secret.as_ref());
+        let mut header = Header::new(Algorithm::HS256);
+        header.kid = Some(self.active_kid.clone());
+        let secret = &self.keys[&self.active_kid];
+        let encoding_key = EncodingKey::from_secret(secret.as_ref());
         encode(&header, &claims, &encoding_key)
     }
 
-    pub fn verify_token(&self, token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+    pub fn verify_token(&self, token: &str) -> Result<Claims, TokenError> {
+        let header = decode_header(token)?;
+        let kid = header.kid.as_deref().unwrap_or(DEFAULT_KEY_ID);
+        let secret = self
+            .keys
+            .get(kid)
+            .ok_or_else(|| TokenError::UnknownKeyId(kid.to_string()))?;
+
         let mut validation = Validation::new(Algorithm::HS256);
         validation.set_issuer(&[&self.issuer]);
         validation.set_audience(&[&self.audience]);
 
-        let decoding_key = DecodingKey::from_secret(self.secret.as_ref());
+        let decoding_key = DecodingKey::from_secret(secret.as_ref());
         let token_data = decode::<Claims>(token, &decoding_key, &validation)?;
         Ok(token_data.claims)
     }
 
-    pub fn refresh_token(
-        &self,
-        token: &str,
-        expires_in_hours: i64,
-    ) -> Result<String, jsonwebtoken::errors::Error> {
+    pub fn refresh_token(&self, token: &str, expires_in_hours: i64) -> Result<String, TokenError> {
         let claims = self.verify_token(token)?;
 
-        self.create_token(
+        let refreshed = self.create_token(
             &claims.sub,
             &claims.email,
             &claims.name,
             claims.did,
             claims.roles,
             expires_in_hours,
-        )
+        )?;
+        Ok(refreshed)
     }
 
-    pub fn extract_user_id(&self, token: &str) -> Result<String, jsonwebtoken::errors::Error> {
+    pub fn extract_user_id(&self, token: &str) -> Result<String, TokenError> {
         let claims = self.verify_token(token)?;
         Ok(claims.sub)
     }
@@ -128,7 +183,7 @@ impl JwtManager {
         &self,
         token: &str,
         required_role: &str,
-    ) -> Result<bool, jsonwebtoken::errors::Error> {
+    ) -> Result<bool, TokenError> {
         let claims = self.verify_token(token)?;
         Ok(claims.roles.contains(&required_role.to_string()))
     }
@@ -137,7 +192,7 @@ impl JwtManager {
         &self,
         token: &str,
         required_roles: &[&str],
-    ) -> Result<bool, jsonwebtoken::errors::Error> {
+    ) -> Result<bool, TokenError> {
         let claims = self.verify_token(token)?;
         let user_roles: HashSet<String> = claims.roles.into_iter().collect();
         let required_set: HashSet<String> = required_roles.iter().map(|s| s.to_string()).collect();
@@ -146,32 +201,37 @@ impl JwtManager {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum TokenError {
+    #[error("Invalid token")]
     InvalidToken,
+    #[error("Token has expired")]
     ExpiredToken,
+    #[error("JWT validation failed")]
+    Jwt(#[from] jsonwebtoken::errors::Error),
+    #[error("Insufficient permissions")]
     InsufficientPermissions,
+    #[error("Missing required role: {0}")]
     MissingRole(String),
+    #[error("Unknown signing key id: {0}")]
+    UnknownKeyId(String),
+    #[error("Cannot remove active signing key: {0}")]
+    ActiveKeyInUse(String),
+    #[error("Credential has been revoked: {0}")]
+    Revoked(String),
+    #[error("Unknown credential: {0}")]
+    UnknownCredential(String),
 }
 
-impl std::fmt::Display for TokenError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl TokenError {
+    pub fn is_expired(&self) -> bool {
         match self {
-            TokenError::InvalidToken => write!(f, "Invalid token"),
-            TokenError::ExpiredToken => write!(f, "Token has expired"),
-            TokenError::InsufficientPermissions => write!(f, "Insufficient permissions"),
-            TokenError::MissingRole(role) => write!(f, "Missing required role: {role}"),
-        }
-    }
-}
-
-impl std::error::Error for TokenError {}
-
-impl From<jsonwebtoken::errors::Error> for TokenError {
-    fn from(err: jsonwebtoken::errors::Error) -> Self {
-        match err.kind() {
-            jsonwebtoken::errors::ErrorKind::ExpiredSignature => TokenError::ExpiredToken,
-            _ => TokenError::InvalidToken,
+            TokenError::ExpiredToken => true,
+            TokenError::Jwt(err) => matches!(
+                err.kind(),
+                jsonwebtoken::errors::ErrorKind::ExpiredSignature
+            ),
+            _ => false,
         }
     }
 }
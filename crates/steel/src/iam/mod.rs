@@ -14,17 +14,21 @@ pub mod core;
 pub mod crypto;
 #[cfg(feature = "surrealdb")]
 pub mod db;
+pub mod did_resolver;
 #[cfg(feature = "surrealdb")]
 pub mod identity_orchestrator;
 pub mod jwt;
 pub mod vc;
 
-pub use core::{DidDocument, Proof, Service, VerifiableCredential, VerificationMethod};
+pub use core::{
+    CredentialStatus, DidDocument, Proof, Service, VerifiableCredential, VerificationMethod,
+};
 pub use crypto::{
     CryptoError, CryptoHash, CryptoHasher, CryptoKeyPair, CryptoSignature, DidCrypto,
     HashAlgorithm, SignatureAlgorithm,
 };
+pub use did_resolver::{DidCacheConfig, DidResolutionError, DidResolver, DidResolverCache};
 #[cfg(feature = "surrealdb")]
 pub use identity_orchestrator::IdentityProvider;
 pub use jwt::{Claims, JwtManager, TokenError};
-pub use vc::VcManager;
+pub use vc::{StatusList, VcManager};
@@ -10,32 +10,88 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
+use base64::{engine::general_purpose, Engine as _};
 use chrono::Utc;
 use serde_json::json;
 use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
 
-use crate::iam::core::{Proof, VerifiableCredential};
+use crate::iam::core::{CredentialStatus, Proof, VerifiableCredential};
 use crate::iam::crypto::{
     CryptoHash, CryptoHasher, CryptoKeyPair, CryptoSignature, SignatureAlgorithm,
 };
-use crate::iam::jwt::{Claims, JwtManager};
+use crate::iam::jwt::{Claims, JwtManager, TokenError};
+
+#[derive(Debug, Clone)]
+pub struct StatusList {
+    id: String,
+    bits: Vec<u8>,
+    next_index: usize,
+    entries: HashMap<String, usize>,
+}
+
+impl StatusList {
+    pub fn new(id: String) -> Self {
+        Self {
+            id,
+            bits: Vec::new(),
+            next_index: 0,
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn index_of(&self, credential_id: &str) -> Option<usize> {
+        self.entries.get(credential_id).copied()
+    }
+
+    pub fn is_set(&self, index: usize) -> bool {
+        self.bits
+            .get(index / 8)
+            .is_some_and(|byte| byte & (0x80 >> (index % 8)) != 0)
+    }
+
+    pub fn encoded_list(&self) -> String {
+        general_purpose::STANDARD.encode(&self.bits)
+    }
+
+    fn allocate(&mut self, credential_id: &str) -> usize {
+        if let Some(index) = self.index_of(credential_id) {
+            return index;
+        }
+        let index = self.next_index;
+        self.next_index += 1;
+        if index / 8 >= self.bits.len() {
+            self.bits.push(0);
+        }
+        self.entries.insert(credential_id.to_string(), index);
+        index
+    }
+
+    fn set(&mut self, index: usize) {
+        if index / 8 >= self.bits.len() {
+            self.bits.resize(index / 8 + 1, 0);
+        }
+        self.bits[index / 8] |= 0x80 >> (index % 8);
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct VcManager {
     jwt_manager: JwtManager,
     issuer_did: String,
     issuer_keypair: CryptoKeyPair,
+    status_list: Arc<RwLock<StatusList>>,
 }
 
 impl VcManager {
     pub fn new(jwt_manager: JwtManager, issuer_did: String) -> Self {
         let issuer_keypair = CryptoKeyPair::generate_ed25519();
 
-        Self {
-            jwt_manager,
-            issuer_did,
-            issuer_keypair,
-        }
+        Self::with_keypair(jwt_manager, issuer_did, issuer_keypair)
     }
 
     pub fn with_keypair(
@@ -43,10 +99,12 @@ impl VcManager {
         issuer_did: String,
         issuer_keypair: CryptoKeyPair,
     ) -> Self {
+        let status_list = StatusList::new(format!("{issuer_did}/credentials/status/revocation"));
         Self {
             jwt_manager,
             issuer_did,
             issuer_keypair,
+            status_list: Arc::new(RwLock::new(status_list)),
         }
     }
 
@@ -54,6 +112,71 @@ impl VcManager {
         self.issuer_keypair.public_key_base64()
     }
 
+    pub fn status_list(&self) -> StatusList {
+        self.status_list
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub fn revoke(&self, credential_id: &str) -> Result<(), TokenError> {
+        let mut list = self
+            .status_list
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        let index = list
+            .index_of(credential_id)
+            .ok_or_else(|| TokenError::UnknownCredential(credential_id.to_string()))?;
+        list.set(index);
+        Ok(())
+    }
+
+    pub fn is_revoked(&self, credential_id: &str) -> bool {
+        let list = self
+            .status_list
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        list.index_of(credential_id)
+            .is_some_and(|index| list.is_set(index))
+    }
+
+    fn allocate_status(&self, credential_id: &str) -> CredentialStatus {
+        let mut list = self
+            .status_list
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        let index = list.allocate(credential_id);
+        CredentialStatus {
+            id: format!("{}#{index}", list.id()),
+            status_type: "StatusList2021Entry".to_string(),
+            status_purpose: "revocation".to_string(),
+            status_list_index: index.to_string(),
+            status_list_credential: list.id().to_string(),
+        }
+    }
+
+    fn check_status(&self, credential: &VerifiableCredential) -> Result<(), TokenError> {
+        let Some(status) = &credential.credential_status else {
+            return Ok(());
+        };
+        let list = self
+            .status_list
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        if status.status_list_credential != list.id() {
+            return Err(TokenError::InvalidToken);
+        }
+        let index: usize = status
+            .status_list_index
+            .parse()
+            .map_err(|_| TokenError::InvalidToken)?;
+        if list.is_set(index) {
+            let id = credential.id.clone().unwrap_or_else(|| status.id.clone());
+            return Err(TokenError::Revoked(id));
+        }
+        Ok(())
+    }
+
     fn create_credential_hash(&self, credential: &VerifiableCredential) -> CryptoHash {
         let mut hashable_credential = credential.clone();
         hashable_credential.proof = Proof {
@@ -70,18 +193,17 @@ impl VcManager {
         CryptoHasher::sha512(credential_json.as_bytes())
     }
 
-    fn create_ed25519_proof(
-        &self,
-        credential_hash: &CryptoHash,
-        issuer_token: &str,
-    ) -> Result<Proof, Box<dyn std::error::Error>> {
+    fn authorize_issuer(&self, issuer_token: &str) -> Result<(), Box<dyn std::error::Error>> {
         let claims = self.jwt_manager.verify_token(issuer_token)?;
         if !claims.roles.contains(&"issuer".to_string())
             && !claims.roles.contains(&"admin".to_string())
         {
             return Err("Insufficient permissions: issuer or admin role required".into());
         }
+        Ok(())
+    }
 
+    fn create_ed25519_proof(&self, credential_hash: &CryptoHash) -> Proof {
         let now = Utc::now().to_rfc3339();
 
         let mut sign_data = Vec::new();
@@ -91,13 +213,13 @@ impl VcManager {
 
         let signature = self.issuer_keypair.sign(&sign_data);
 
-        Ok(Proof {
+        Proof {
             proof_type: "Ed25519Signature2020".to_string(),
             created: now,
             verification_method: format!("{}#key-1", self.issuer_did),
             proof_purpose: "assertionMethod".to_string(),
             proof_value: signature.to_base64(),
-        })
+        }
     }
 
     pub fn create_identity_credential(
@@ -107,7 +229,9 @@ impl VcManager {
         subject_email: &str,
         issuer_token: &str,
     ) -> Result<VerifiableCredential, Box<dyn std::error::Error>> {
+        self.authorize_issuer(issuer_token)?;
         let credential_id = format!("urn:uuid:{}", uuid::Uuid::new_v4());
+        let credential_status = self.allocate_status(&credential_id);
         let now = Utc::now().to_rfc3339();
 
         let mut credential_subject = HashMap::new();
@@ -128,6 +252,7 @@ impl VcManager {
             issuer: self.issuer_did.clone(),
             issuance_date: now,
             credential_subject,
+            credential_status: Some(credential_status),
             proof: Proof {
                 proof_type: "pending".to_string(),
                 created: "pending".to_string(),
@@ -138,7 +263,7 @@ impl VcManager {
         };
 
         let credential_hash = self.create_credential_hash(&credential);
-        let proof = self.create_ed25519_proof(&credential_hash, issuer_token)?;
+        let proof = self.create_ed25519_proof(&credential_hash);
 
         credential.proof = proof;
 
@@ -152,7 +277,9 @@ impl VcManager {
         roles: Vec<String>,
         issuer_token: &str,
     ) -> Result<VerifiableCredential, Box<dyn std::error::Error>> {
+        self.authorize_issuer(issuer_token)?;
         let credential_id = format!("urn:uuid:{}", uuid::Uuid::new_v4());
+        let credential_status = self.allocate_status(&credential_id);
         let now = Utc::now().to_rfc3339();
 
         let mut credential_subject = HashMap::new();
@@ -173,6 +300,7 @@ impl VcManager {
             issuer: self.issuer_did.clone(),
             issuance_date: now,
             credential_subject,
+            credential_status: Some(credential_status),
             proof: Proof {
                 proof_type: "pending".to_string(),
                 created: "pending".to_string(),
@@ -183,7 +311,7 @@ impl VcManager {
         };
 
         let credential_hash = self.create_credential_hash(&credential);
-        let proof = self.create_ed25519_proof(&credential_hash, issuer_token)?;
+        let proof = self.create_ed25519_proof(&credential_hash);
 
         credential.proof = proof;
 
@@ -196,7 +324,9 @@ impl VcManager {
         solana_public_key: &str,
         issuer_token: &str,
     ) -> Result<VerifiableCredential, Box<dyn std::error::Error>> {
+        self.authorize_issuer(issuer_token)?;
         let credential_id = format!("urn:uuid:{}", uuid::Uuid::new_v4());
+        let credential_status = self.allocate_status(&credential_id);
         let now = Utc::now().to_rfc3339();
 
         let mut credential_subject = HashMap::new();
@@ -216,6 +346,7 @@ impl VcManager {
             issuer: self.issuer_did.clone(),
             issuance_date: now,
             credential_subject,
+            credential_status: Some(credential_status),
             proof: Proof {
                 proof_type: "pending".to_string(),
                 created: "pending".to_string(),
@@ -226,7 +357,7 @@ impl VcManager {
         };
 
         let credential_hash = self.create_credential_hash(&credential);
-        let proof = self.create_ed25519_proof(&credential_hash, issuer_token)?;
+        let proof = self.create_ed25519_proof(&credential_hash);
 
         credential.proof = proof;
 
@@ -240,7 +371,9 @@ impl VcManager {
         performance_summary: &HashMap<String, serde_json::Value>,
         issuer_token: &str,
     ) -> Result<VerifiableCredential, Box<dyn std::error::Error>> {
+        self.authorize_issuer(issuer_token)?;
         let credential_id = format!("urn:uuid:{}", uuid::Uuid::new_v4());
+        let credential_status = self.allocate_status(&credential_id);
         let now = Utc::now().to_rfc3339();
 
         let mut credential_subject = HashMap::new();
@@ -265,6 +398,7 @@ impl VcManager {
             issuer: self.issuer_did.clone(),
             issuance_date: now,
             credential_subject,
+            credential_status: Some(credential_status),
             proof: Proof {
                 proof_type: "pending".to_string(),
                 created: "pending".to_string(),
@@ -275,7 +409,7 @@ impl VcManager {
         };
 
         let credential_hash = self.create_credential_hash(&credential);
-        let proof = self.create_ed25519_proof(&credential_hash, issuer_token)?;
+        let proof = self.create_ed25519_proof(&credential_hash);
 
         credential.proof = proof;
 
@@ -286,11 +420,9 @@ impl VcManager {
         &self,
         credential: &VerifiableCredential,
     ) -> Result<Claims, Box<dyn std::error::Error>> {
-        if credential.proof.proof_type == "Ed25519Signature2020" {
-            return self.verify_ed25519_credential(credential);
-        }
-
-        if credential.proof.proof_type == "JsonWebTokenProof2020" {
+        let claims = if credential.proof.proof_type == "Ed25519Signature2020" {
+            self.verify_ed25519_credential(credential)?
+        } else if credential.proof.proof_type == "JsonWebTokenProof2020" {
             let claims = self
                 .jwt_manager
                 .verify_token(&credential.proof.proof_value)?;
@@ -299,10 +431,13 @@ impl VcManager {
                 return Err("Credential issuer does not match expected issuer".into());
             }
 
-            return Ok(claims);
-        }
+            claims
+        } else {
+            return Err("Unsupported proof type".into());
+        };
 
-        Err("Unsupported proof type".into())
+        self.check_status(credential)?;
+        Ok(claims)
     }
 
     fn verify_ed25519_credential(
@@ -21,7 +21,7 @@ pub mod policy;
 pub use iam::IdentityProvider;
 pub use iam::{Claims, DidDocument, JwtManager, TokenError, VcManager, VerifiableCredential};
 
-pub use llm::security::SecurityProcessor;
+pub use llm::security::{RedactionMap, SecurityProcessor};
 pub use llm::{AnthropicClient, ApiClient, OpenAIClient};
 
 #[cfg(feature = "surrealdb")]
@@ -20,7 +20,59 @@ use tokio_stream::StreamExt;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
-use super::ApiClient;
+use super::sse::{retry_after, sse_response, SseEvent, SseTranslator};
+use super::{ApiClient, TokenChunk, TokenStream};
+
+#[derive(Debug, Default)]
+pub struct AnthropicStreamTranslator {
+    input_tokens: u32,
+    output_tokens: u32,
+    stop_reason: Option<String>,
+}
+
+impl SseTranslator for AnthropicStreamTranslator {
+    fn translate(&mut self, event: &SseEvent) -> LLMResult<Option<TokenChunk>> {
+        let parsed: Value = serde_json::from_str(&event.data).map_err(|e| {
+            LLMError::Serialisation(format!("Failed to parse Anthropic stream event: {e}"))
+        })?;
+        let event_type = parsed["type"].as_str().or(event.event.as_deref());
+
+        match event_type {
+            Some("message_start") => {
+                let usage = &parsed["message"]["usage"];
+                self.input_tokens = usage["input_tokens"].as_u64().unwrap_or(0) as u32;
+                self.output_tokens = usage["output_tokens"].as_u64().unwrap_or(0) as u32;
+                Ok(None)
+            }
+            Some("content_block_delta") => Ok(parsed["delta"]["text"]
+                .as_str()
+                .filter(|delta| !delta.is_empty())
+                .map(TokenChunk::delta)),
+            Some("message_delta") => {
+                if let Some(reason) = parsed["delta"]["stop_reason"].as_str() {
+                    self.stop_reason = Some(reason.to_string());
+                }
+                if let Some(output_tokens) = parsed["usage"]["output_tokens"].as_u64() {
+                    self.output_tokens = output_tokens as u32;
+                }
+                Ok(None)
+            }
+            Some("message_stop") => {
+                let usage = Usage {
+                    prompt_tokens: self.input_tokens,
+                    completion_tokens: self.output_tokens,
+                    total_tokens: self.input_tokens + self.output_tokens,
+                };
+                Ok(Some(TokenChunk::last(self.stop_reason.take(), Some(usage))))
+            }
+            Some("error") => Err(LLMError::Provider(format!(
+                "Anthropic stream error: {}",
+                parsed["error"]["message"].as_str().unwrap_or("unknown")
+            ))),
+            _ => Ok(None),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct AnthropicClient {
@@ -183,8 +235,9 @@ impl AnthropicClient {
                             "Rate limited by Anthropic API, waiting {:?} before retry",
                             wait_time
                         );
+                        let retry_after = retry_after(&resp);
                         tokio::time::sleep(wait_time).await;
-                        last_error = Some(LLMError::RateLimit);
+                        last_error = Some(LLMError::RateLimited { retry_after });
                     } else {
                         match resp.text().await {
                             Ok(error_body) => {
@@ -323,6 +376,21 @@ impl ApiClient for AnthropicClient {
         Ok(rx)
     }
 
+    fn stream_completion(&self, mut request: ProviderRequest) -> TokenStream<'_> {
+        request.stream = Some(true);
+        let payload = self.build_anthropic_payload(&request);
+
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", &self.api_version)
+            .header("content-type", "application/json")
+            .json(&payload)
+            .send();
+        sse_response("Anthropic", response, AnthropicStreamTranslator::default())
+    }
+
     fn provider_name(&self) -> &'static str {
         "anthropic"
     }
@@ -13,11 +13,54 @@
 pub mod anthropic;
 pub mod ollama;
 pub mod openai;
+pub mod sse;
+
+use std::pin::Pin;
 
 use async_trait::async_trait;
-use llm_contracts::{LLMResult, ProviderRequest, ProviderResponse, StreamChunk};
+use futures::stream::{self, Stream, StreamExt};
+use llm_contracts::{LLMResult, ProviderRequest, ProviderResponse, StreamChunk, Usage};
 use tokio::sync::mpsc;
 
+#[derive(Debug, Clone, Default)]
+pub struct TokenChunk {
+    pub delta: String,
+    pub is_final: bool,
+    pub finish_reason: Option<String>,
+    pub usage: Option<Usage>,
+}
+
+impl TokenChunk {
+    pub fn delta(text: impl Into<String>) -> Self {
+        Self {
+            delta: text.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn last(finish_reason: Option<String>, usage: Option<Usage>) -> Self {
+        Self {
+            delta: String::new(),
+            is_final: true,
+            finish_reason,
+            usage,
+        }
+    }
+}
+
+impl From<StreamChunk> for TokenChunk {
+    fn from(chunk: StreamChunk) -> Self {
+        Self {
+            delta: chunk.content_delta,
+            is_final: chunk.is_final,
+            finish_reason: None,
+            usage: chunk.usage,
+        }
+    }
+}
+
+pub type TokenStream<'a> = Pin<Box<dyn Stream<Item = LLMResult<TokenChunk>> + Send + 'a>>;
+
 #[async_trait]
 pub trait ApiClient: Send + Sync {
     async fn send_request(&self, request: ProviderRequest) -> LLMResult<ProviderResponse>;
@@ -27,11 +70,24 @@ pub trait ApiClient: Send + Sync {
         request: ProviderRequest,
     ) -> LLMResult<mpsc::UnboundedReceiver<StreamChunk>>;
 
+    fn stream_completion(&self, request: ProviderRequest) -> TokenStream<'_> {
+        let receiver = self.send_streaming_request(request);
+        Box::pin(stream::once(receiver).flat_map(|opened| match opened {
+            Ok(receiver) => stream::unfold(receiver, |mut receiver| async move {
+                let chunk = receiver.recv().await?;
+                Some((Ok(TokenChunk::from(chunk)), receiver))
+            })
+            .boxed(),
+            Err(e) => stream::once(async move { Err(e) }).boxed(),
+        }))
+    }
+
     fn provider_name(&self) -> &'static str;
 
     async fn health_check(&self) -> LLMResult<()>;
 }
 
-pub use anthropic::AnthropicClient;
+pub use anthropic::{AnthropicClient, AnthropicStreamTranslator};
 pub use ollama::OllamaClient;
-pub use openai::OpenAIClient;
+pub use openai::{OpenAIClient, OpenAIStreamTranslator};
+pub use sse::{translate_sse, SseDecoder, SseEvent, SseTranslator};
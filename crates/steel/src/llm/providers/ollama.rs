@@ -20,6 +20,7 @@ use tokio_stream::StreamExt;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+use super::sse::retry_after;
 use super::ApiClient;
 
 #[derive(Debug, Clone)]
@@ -172,8 +173,9 @@ impl OllamaClient {
                             "Rate limited by Ollama API, waiting {:?} before retry",
                             wait_time
                         );
+                        let retry_after = retry_after(&resp);
                         tokio::time::sleep(wait_time).await;
-                        last_error = Some(LLMError::RateLimit);
+                        last_error = Some(LLMError::RateLimited { retry_after });
                     } else {
                         let error_body = resp.text().await.unwrap_or_default();
                         last_error = Some(LLMError::Provider(format!(
@@ -19,7 +19,50 @@ use tokio::sync::mpsc;
 use tokio_stream::StreamExt;
 use uuid::Uuid;
 
-use super::ApiClient;
+use super::sse::{sse_response, SseEvent, SseTranslator};
+use super::{ApiClient, TokenChunk, TokenStream};
+
+fn openai_usage(usage_data: &Value) -> Usage {
+    Usage {
+        prompt_tokens: usage_data["prompt_tokens"].as_u64().unwrap_or(0) as u32,
+        completion_tokens: usage_data["completion_tokens"].as_u64().unwrap_or(0) as u32,
+        total_tokens: usage_data["total_tokens"].as_u64().unwrap_or(0) as u32,
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct OpenAIStreamTranslator {
+    finish_reason: Option<String>,
+    usage: Option<Usage>,
+}
+
+impl SseTranslator for OpenAIStreamTranslator {
+    fn translate(&mut self, event: &SseEvent) -> LLMResult<Option<TokenChunk>> {
+        if event.data == "[DONE]" {
+            let chunk = TokenChunk::last(self.finish_reason.take(), self.usage.take());
+            return Ok(Some(chunk));
+        }
+
+        let parsed: Value = serde_json::from_str(&event.data).map_err(|e| {
+            LLMError::Serialisation(format!("Failed to parse OpenAI stream event: {e}"))
+        })?;
+        if let Some(message) = parsed["error"]["message"].as_str() {
+            return Err(LLMError::Provider(format!("OpenAI stream error: {message}")));
+        }
+        if let Some(usage_data) = parsed.get("usage").filter(|u| u.is_object()) {
+            self.usage = Some(openai_usage(usage_data));
+        }
+
+        let choice = &parsed["choices"][0];
+        if let Some(reason) = choice["finish_reason"].as_str() {
+            self.finish_reason = Some(reason.to_string());
+        }
+        Ok(choice["delta"]["content"]
+            .as_str()
+            .filter(|delta| !delta.is_empty())
+            .map(TokenChunk::delta))
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct OpenAIClient {
@@ -97,15 +140,10 @@ impl OpenAIClient {
                 LLMError::Provider("Failed to extract content from OpenAI response".to_string())
             })?;
 
-        let usage = if let Some(usage_data) = response_data.get("usage") {
-            Usage {
-                prompt_tokens: usage_data["prompt_tokens"].as_u64().unwrap_or(0) as u32,
-                completion_tokens: usage_data["completion_tokens"].as_u64().unwrap_or(0) as u32,
-                total_tokens: usage_data["total_tokens"].as_u64().unwrap_or(0) as u32,
-            }
-        } else {
-            Usage::default()
-        };
+        let usage = response_data
+            .get("usage")
+            .map(openai_usage)
+            .unwrap_or_default();
 
         let finish_reason = response_data["choices"][0]["finish_reason"]
             .as_str()
@@ -275,6 +313,21 @@ impl ApiClient for OpenAIClient {
         Ok(rx)
     }
 
+    fn stream_completion(&self, mut request: ProviderRequest) -> TokenStream<'_> {
+        request.stream = Some(true);
+        let mut payload = self.build_openai_payload(&request);
+        payload["stream_options"] = json!({ "include_usage": true });
+
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .json(&payload)
+            .send();
+        sse_response("OpenAI", response, OpenAIStreamTranslator::default())
+    }
+
     fn provider_name(&self) -> &'static str {
         "openai"
     }
@@ -0,0 +1,218 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use futures::stream::{self, Stream, StreamExt};
+use llm_contracts::{LLMError, LLMResult};
+
+use super::{TokenChunk, TokenStream};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    pub event: Option<String>,
+    pub data: String,
+}
+
+pub trait SseTranslator: Send {
+    fn translate(&mut self, event: &SseEvent) -> LLMResult<Option<TokenChunk>>;
+}
+
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    buffer: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(bytes);
+        let mut events = Vec::new();
+        while let Some(line_end) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=line_end).collect();
+            let line = String::from_utf8_lossy(&line);
+            if let Some(event) = self.process_line(line.trim_end_matches(['\r', '\n'])) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    pub fn finish(&mut self) -> Option<SseEvent> {
+        let line = String::from_utf8_lossy(&std::mem::take(&mut self.buffer)).into_owned();
+        let pending = match line.trim_end_matches('\r') {
+            "" => None,
+            line => self.process_line(line),
+        };
+        pending.or_else(|| self.dispatch())
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => self.data.push(value.to_string()),
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = self.event.take();
+        if self.data.is_empty() {
+            return None;
+        }
+        Some(SseEvent {
+            event,
+            data: std::mem::take(&mut self.data).join("\n"),
+        })
+    }
+}
+
+struct SseState<S, T> {
+    body: Pin<Box<S>>,
+    decoder: SseDecoder,
+    translator: T,
+    pending: VecDeque<LLMResult<TokenChunk>>,
+    finished: bool,
+}
+
+impl<S, T: SseTranslator> SseState<S, T> {
+    fn accept(&mut self, event: SseEvent) {
+        if self.finished {
+            return;
+        }
+        match self.translator.translate(&event) {
+            Ok(Some(chunk)) => {
+                self.finished = chunk.is_final;
+                self.pending.push_back(Ok(chunk));
+            }
+            Ok(None) => {}
+            Err(e) => self.fail(e),
+        }
+    }
+
+    fn fail(&mut self, error: LLMError) {
+        if !self.finished {
+            self.pending.push_back(Err(error));
+            self.finished = true;
+        }
+    }
+}
+
+pub fn translate_sse<'a, S, B, E, T>(body: S, translator: T) -> TokenStream<'a>
+where
+    S: Stream<Item = Result<B, E>> + Send + 'a,
+    B: AsRef<[u8]> + Send,
+    E: Display + Send,
+    T: SseTranslator + 'a,
+{
+    let state = SseState {
+        body: Box::pin(body),
+        decoder: SseDecoder::new(),
+        translator,
+        pending: VecDeque::new(),
+        finished: false,
+    };
+    Box::pin(stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.pending.pop_front() {
+                return Some((item, state));
+            }
+            if state.finished {
+                return None;
+            }
+            match state.body.next().await {
+                Some(Ok(bytes)) => {
+                    for event in state.decoder.push(bytes.as_ref()) {
+                        state.accept(event);
+                    }
+                }
+                Some(Err(e)) => state.fail(LLMError::Network(format!("Stream interrupted: {e}"))),
+                None => {
+                    if let Some(event) = state.decoder.finish() {
+                        state.accept(event);
+                    }
+                    state.fail(LLMError::Provider(
+                        "Stream ended before a final chunk was received".to_string(),
+                    ));
+                }
+            }
+        }
+    }))
+}
+
+pub(crate) fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+pub(crate) fn sse_response<'a, F, T>(
+    provider: &'static str,
+    response: F,
+    translator: T,
+) -> TokenStream<'a>
+where
+    F: Future<Output = reqwest::Result<reqwest::Response>> + Send + 'a,
+    T: SseTranslator + 'a,
+{
+    let opened = async move {
+        let response = response
+            .await
+            .map_err(|e| LLMError::Network(format!("Request failed: {e}")))?;
+        let status = response.status();
+        if status == 429 {
+            return Err(LLMError::RateLimited {
+                retry_after: retry_after(&response),
+            });
+        }
+        if !status.is_success() {
+            let body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(LLMError::Provider(format!("{provider} API error {status}: {body}")));
+        }
+        Ok(translate_sse(response.bytes_stream(), translator))
+    };
+    Box::pin(stream::once(opened).flat_map(|opened| -> TokenStream<'a> {
+        match opened {
+            Ok(tokens) => tokens,
+            Err(e) => Box::pin(stream::once(async move { Err(e) })),
+        }
+    }))
+}
@@ -380,7 +380,7 @@ impl MessagingClient {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum AlertPriority {
     Low,
     Medium,
@@ -388,6 +388,44 @@ pub enum AlertPriority {
     Critical,
 }
 
+impl AlertPriority {
+    pub const ALL: [AlertPriority; 4] = [
+        AlertPriority::Low,
+        AlertPriority::Medium,
+        AlertPriority::High,
+        AlertPriority::Critical,
+    ];
+
+    pub fn from_metadata(metadata: &MessageMetadata) -> Option<Self> {
+        match metadata.get("priority")? {
+            MetadataValue::String(value) => value.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn of_message(message: &Message) -> Self {
+        message
+            .metadata
+            .as_ref()
+            .and_then(Self::from_metadata)
+            .unwrap_or(AlertPriority::Medium)
+    }
+}
+
+impl std::str::FromStr for AlertPriority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "low" => Ok(AlertPriority::Low),
+            "medium" => Ok(AlertPriority::Medium),
+            "high" => Ok(AlertPriority::High),
+            "critical" => Ok(AlertPriority::Critical),
+            other => Err(format!("Unknown alert priority: {other}")),
+        }
+    }
+}
+
 impl std::fmt::Display for AlertPriority {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
@@ -32,11 +32,26 @@ impl ContentAnalyser {
     }
 
     pub fn analyse(&self, text: &str, distribution: &mut ScoreDistribution) -> ContentAnalysis {
-        let tokens = text.split_whitespace();
         let mut interesting_tokens = Vec::new();
         let mut max_score = 0.0;
 
-        for token_str in tokens {
+        self.score_tokens(text, &mut interesting_tokens, &mut max_score);
+        self.conclude(interesting_tokens, max_score, distribution)
+    }
+
+    pub fn stream<'a>(&'a self, distribution: &'a mut ScoreDistribution) -> StreamingAnalysis<'a> {
+        StreamingAnalysis {
+            analyser: self,
+            distribution,
+            pending_bytes: Vec::new(),
+            partial_token: String::new(),
+            interesting_tokens: Vec::new(),
+            max_score: 0.0,
+        }
+    }
+
+    fn score_tokens(&self, text: &str, interesting_tokens: &mut Vec<(String, f64)>, max: &mut f64) {
+        for token_str in text.split_whitespace() {
             let token = token_str.trim_matches(|c: char| !c.is_alphanumeric());
             if token.is_empty() {
                 continue;
@@ -46,11 +61,18 @@ impl ContentAnalyser {
             if score > 0.3 {
                 interesting_tokens.push((token.to_string(), score));
             }
-            if score > max_score {
-                max_score = score;
+            if score > *max {
+                *max = score;
             }
         }
+    }
 
+    fn conclude(
+        &self,
+        mut interesting_tokens: Vec<(String, f64)>,
+        max_score: f64,
+        distribution: &mut ScoreDistribution,
+    ) -> ContentAnalysis {
         interesting_tokens.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
 
         distribution.add_score(max_score);
@@ -171,6 +193,92 @@ impl ContentAnalyser {
     }
 }
 
+pub struct StreamingAnalysis<'a> {
+    analyser: &'a ContentAnalyser,
+    distribution: &'a mut ScoreDistribution,
+    pending_bytes: Vec<u8>,
+    partial_token: String,
+    interesting_tokens: Vec<(String, f64)>,
+    max_score: f64,
+}
+
+impl StreamingAnalysis<'_> {
+    pub fn push(&mut self, chunk: &str) {
+        self.push_bytes(chunk.as_bytes());
+    }
+
+    pub fn push_bytes(&mut self, chunk: &[u8]) {
+        self.pending_bytes.extend_from_slice(chunk);
+        let mut decoded = String::new();
+        let mut consumed = 0;
+        while consumed < self.pending_bytes.len() {
+            match std::str::from_utf8(&self.pending_bytes[consumed..]) {
+                Ok(valid) => {
+                    decoded.push_str(valid);
+                    consumed = self.pending_bytes.len();
+                }
+                Err(err) => {
+                    let valid_end = consumed + err.valid_up_to();
+                    let valid = &self.pending_bytes[consumed..valid_end];
+                    decoded.push_str(std::str::from_utf8(valid).unwrap_or_default());
+                    match err.error_len() {
+                        Some(invalid_len) => {
+                            decoded.push(char::REPLACEMENT_CHARACTER);
+                            consumed = valid_end + invalid_len;
+                        }
+                        None => {
+                            consumed = valid_end;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        self.pending_bytes.drain(..consumed);
+        self.push_decoded(&decoded);
+    }
+
+    pub fn finish(mut self) -> ContentAnalysis {
+        if !self.pending_bytes.is_empty() {
+            let tail = String::from_utf8_lossy(&self.pending_bytes).into_owned();
+            self.pending_bytes.clear();
+            self.partial_token.push_str(&tail);
+        }
+        let remainder = std::mem::take(&mut self.partial_token);
+        self.analyser.score_tokens(
+            &remainder,
+            &mut self.interesting_tokens,
+            &mut self.max_score,
+        );
+        self.analyser.conclude(
+            self.interesting_tokens,
+            self.max_score,
+            self.distribution,
+        )
+    }
+
+    fn push_decoded(&mut self, text: &str) {
+        self.partial_token.push_str(text);
+        let Some((boundary, whitespace)) = self
+            .partial_token
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+        else {
+            return;
+        };
+        let complete: String = self
+            .partial_token
+            .drain(..boundary + whitespace.len_utf8())
+            .collect();
+        self.analyser.score_tokens(
+            &complete,
+            &mut self.interesting_tokens,
+            &mut self.max_score,
+        );
+    }
+}
+
 impl Default for ContentAnalyser {
     fn default() -> Self {
         Self::new(ScoringConfig::default())
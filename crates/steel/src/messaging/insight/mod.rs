@@ -23,7 +23,7 @@ pub mod optimiser;
 pub mod scribe_bridge;
 pub mod security;
 
-pub use analysis::{ContentAnalyser, ContentAnalysis};
+pub use analysis::{ContentAnalyser, ContentAnalysis, StreamingAnalysis};
 pub use config::{ScoringConfig, SecurityConfig};
 pub use distribution::ScoreDistribution;
 pub use feedback::{FeedbackLoop, TrainingStats};
@@ -13,7 +13,7 @@
 #[cfg(feature = "surrealdb")]
 use crate::messaging::database::MessagingApp;
 use crate::messaging::insight::MessageSecurity;
-use crate::messaging::network::{MessageRouter, NetworkManager, RouteType};
+use crate::messaging::network::{MessageRouter, NetworkManager, RouteType, SchedulingMode};
 use crate::messaging::platforms::PlatformManager;
 use crate::messaging::types::{Message, MessageType};
 use anyhow::{Context, Result};
@@ -190,6 +190,23 @@ impl MessageManager {
         Ok(Self { network, platforms, processor, router })
     }
 
+    pub fn with_scheduling(mut self, mode: SchedulingMode) -> Self {
+        self.router = Arc::new(self.router.with_mode(mode));
+        self
+    }
+
+    pub async fn enqueue_message(&self, message: Message) {
+        self.router.enqueue(message).await;
+    }
+
+    pub async fn dispatch_queued(&self) -> Result<Vec<String>> {
+        let mut sent = Vec::new();
+        while let Some(message) = self.router.next_scheduled().await {
+            sent.push(self.send_message(message).await?);
+        }
+        Ok(sent)
+    }
+
     pub async fn send_message(&self, mut message: Message) -> Result<String> {
         message = self
             .processor
@@ -486,6 +503,7 @@ pub struct ManagerConfig {
     pub enable_pii_scanning: bool,
     pub enable_metadata_extraction: bool,
     pub network_enabled: bool,
+    pub scheduling: SchedulingMode,
 }
 
 impl Default for ManagerConfig {
@@ -497,11 +515,13 @@ impl Default for ManagerConfig {
             enable_pii_scanning: true,
             enable_metadata_extraction: true,
             network_enabled: true,
+            scheduling: SchedulingMode::Fifo,
         }
     }
 }
 
 pub async fn create_message_manager(config: ManagerConfig) -> Result<MessageManager> {
     let security = Arc::new(Mutex::new(MessageSecurity::default()));
-    MessageManager::new(&config.database_url, security).await
+    let manager = MessageManager::new(&config.database_url, security).await?;
+    Ok(manager.with_scheduling(config.scheduling))
 }
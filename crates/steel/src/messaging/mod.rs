@@ -30,8 +30,10 @@ pub use client::{AlertPriority, ClientStatus, MessagingClient};
 #[cfg(feature = "surrealdb")]
 pub use database::MessagingApp;
 pub use insight::{ContentAnalyser, ContentAnalysis, MessageSecurity};
-pub use network::{MessageRouter, NetworkManager, Relay};
-pub use pathfinding::{NetworkStats, OptimalPath, PathfindingNetworkManager};
+pub use network::{MessageRouter, MessageScheduler, NetworkManager, Relay, SchedulingMode};
+pub use pathfinding::{
+    NetworkStats, OptimalPath, PathWeighting, PathfindingConfig, PathfindingNetworkManager,
+};
 pub use platforms::{PlatformBridge, PlatformManager, PlatformType};
 pub use resilience::{
     CircuitBreaker, CircuitBreakerConfig, CircuitBreakerManager, CircuitBreakerState,
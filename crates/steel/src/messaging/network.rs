@@ -10,12 +10,13 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
+use crate::messaging::client::AlertPriority;
 use crate::messaging::types::{Message, MessageDestination};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 
 #[derive(Debug)]
 pub enum NetworkError {
@@ -46,6 +47,84 @@ pub enum RoutingStrategy {
     HashBased,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SchedulingMode {
+    #[default]
+    Fifo,
+    PriorityFair { starvation_limit: usize },
+}
+
+#[derive(Debug)]
+pub struct MessageScheduler {
+    mode: SchedulingMode,
+    queues: [VecDeque<Message>; 4],
+    skipped: [usize; 4],
+}
+
+impl MessageScheduler {
+    pub fn new(mode: SchedulingMode) -> Self {
+        Self {
+            mode,
+            queues: Default::default(),
+            skipped: [0; 4],
+        }
+    }
+
+    pub fn mode(&self) -> SchedulingMode {
+        self.mode
+    }
+
+    pub fn enqueue(&mut self, message: Message) {
+        let level = match self.mode {
+            SchedulingMode::Fifo => 0,
+            SchedulingMode::PriorityFair { .. } => AlertPriority::of_message(&message) as usize,
+        };
+        self.queues[level].push_back(message);
+    }
+
+    pub fn next(&mut self) -> Option<Message> {
+        let top = (0..self.queues.len())
+            .rev()
+            .find(|&level| !self.queues[level].is_empty())?;
+        let limit = match self.mode {
+            SchedulingMode::Fifo => return self.queues[top].pop_front(),
+            SchedulingMode::PriorityFair { starvation_limit } => starvation_limit.max(1),
+        };
+
+        let pick = (0..top)
+            .filter(|&level| !self.queues[level].is_empty() && self.skipped[level] >= limit)
+            .max_by_key(|&level| self.skipped[level])
+            .unwrap_or(top);
+
+        for level in 0..self.queues.len() {
+            if self.queues[level].is_empty() || level == pick {
+                self.skipped[level] = 0;
+            } else if level < top {
+                self.skipped[level] += 1;
+            }
+        }
+        self.queues[pick].pop_front()
+    }
+
+    pub fn pending(&self, priority: AlertPriority) -> usize {
+        if let SchedulingMode::PriorityFair { .. } = self.mode {
+            return self.queues[priority as usize].len();
+        }
+        self.queues[0]
+            .iter()
+            .filter(|message| AlertPriority::of_message(message) == priority)
+            .count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.queues.iter().map(VecDeque::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queues.iter().all(VecDeque::is_empty)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum RouteType {
     Direct,
@@ -141,19 +220,55 @@ pub struct MessageRouter {
     routing_table: Arc<RwLock<HashMap<String, String>>>,
     relays: Arc<RwLock<Vec<Relay>>>,
     peers: Arc<RwLock<HashMap<String, PeerInfo>>>,
+    scheduler: Arc<Mutex<MessageScheduler>>,
     node_count: usize,
 }
 
 impl MessageRouter {
     pub fn new(node_count: usize) -> Self {
+        Self::with_scheduling(node_count, SchedulingMode::default())
+    }
+
+    pub fn with_scheduling(node_count: usize, mode: SchedulingMode) -> Self {
         Self {
             routing_table: Arc::new(RwLock::new(HashMap::new())),
             relays: Arc::new(RwLock::new(Vec::new())),
             peers: Arc::new(RwLock::new(HashMap::new())),
+            scheduler: Arc::new(Mutex::new(MessageScheduler::new(mode))),
             node_count,
         }
     }
 
+    pub fn with_mode(&self, mode: SchedulingMode) -> Self {
+        Self {
+            routing_table: self.routing_table.clone(),
+            relays: self.relays.clone(),
+            peers: self.peers.clone(),
+            scheduler: Arc::new(Mutex::new(MessageScheduler::new(mode))),
+            node_count: self.node_count,
+        }
+    }
+
+    pub async fn enqueue(&self, message: Message) {
+        self.scheduler.lock().await.enqueue(message);
+    }
+
+    pub async fn next_scheduled(&self) -> Option<Message> {
+        self.scheduler.lock().await.next()
+    }
+
+    pub async fn pending(&self) -> usize {
+        self.scheduler.lock().await.len()
+    }
+
+    pub async fn route_next(&self) -> Result<Option<(Message, RoutingDecision)>, NetworkError> {
+        let Some(message) = self.next_scheduled().await else {
+            return Ok(None);
+        };
+        let decision = self.route_message(&message).await?;
+        Ok(Some((message, decision)))
+    }
+
     pub async fn route_message(&self, message: &Message) -> Result<RoutingDecision, NetworkError> {
         let strategy = self.determine_routing_strategy(message).await;
         let destinations = self.resolve_destinations(message).await?;
@@ -13,7 +13,7 @@
 use crate::messaging::network::PeerInfo;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::sync::Arc;
 use std::time::SystemTime;
 use tokio::sync::RwLock;
@@ -62,12 +62,36 @@ impl NodeState {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PathWeighting {
+    #[default]
+    Robust,
+    HopCount,
+    LatencyWeighted,
+}
+
+#[derive(Debug, Clone)]
+pub struct PathfindingConfig {
+    pub cache_capacity: usize,
+    pub weighting: PathWeighting,
+}
+
+impl Default for PathfindingConfig {
+    fn default() -> Self {
+        Self {
+            cache_capacity: 1000,
+            weighting: PathWeighting::Robust,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct NetworkState {
     pub node_states: HashMap<String, NodeState>,
     pub path_cache: HashMap<(String, String), Vec<String>>,
     pub topology_version: u64,
     pub cache_capacity: usize,
+    pub weighting: PathWeighting,
 }
 
 impl NetworkState {
@@ -77,11 +101,106 @@ impl NetworkState {
             path_cache: HashMap::new(),
             topology_version: 0,
             cache_capacity,
+            weighting: PathWeighting::default(),
         }
     }
+
+    fn edge_cost(&self, from_node: &NodeState, to_node: &NodeState) -> f64 {
+        match self.weighting {
+            PathWeighting::Robust => robust_edge_cost(from_node, to_node),
+            PathWeighting::HopCount => 1.0,
+            PathWeighting::LatencyWeighted => to_node.latency.max(0.0),
+        }
+    }
+
+    fn path_cost(&self, path: &[String]) -> Option<f64> {
+        let mut total_cost = 0.0;
+        for window in path.windows(2) {
+            let current = self.node_states.get(&window[0])?;
+            let next = self.node_states.get(&window[1])?;
+            total_cost += self.edge_cost(current, next);
+        }
+        Some(total_cost)
+    }
+
+    fn shortest_path(
+        &self,
+        start: &str,
+        end: &str,
+        banned_nodes: &HashSet<&str>,
+        banned_edges: &HashSet<(&str, &str)>,
+    ) -> Option<OptimalPath> {
+        let mut distances: HashMap<String, f64> = HashMap::new();
+        let mut priority_queue = BinaryHeap::new();
+        let mut predecessors: HashMap<String, String> = HashMap::new();
+
+        distances.insert(start.to_string(), 0.0);
+        priority_queue.push(PathState {
+            cost: 0.0,
+            position: start.to_string(),
+        });
+
+        while let Some(PathState { cost, position }) = priority_queue.pop() {
+            if position == end {
+                let mut path = vec![end.to_string()];
+                let mut current = end.to_string();
+
+                while let Some(prev) = predecessors.get(&current) {
+                    path.push(prev.clone());
+                    current = prev.clone();
+                }
+                path.reverse();
+
+                return Some(OptimalPath {
+                    nodes: path,
+                    total_cost: cost,
+                });
+            }
+
+            if cost > *distances.get(&position).unwrap_or(&f64::INFINITY) {
+                continue;
+            }
+
+            if let Some(current_node) = self.node_states.get(&position) {
+                for neighbour_id in &current_node.connections {
+                    if banned_nodes.contains(neighbour_id.as_str())
+                        || banned_edges.contains(&(position.as_str(), neighbour_id.as_str()))
+                    {
+                        continue;
+                    }
+                    if let Some(neighbour_node) = self.node_states.get(neighbour_id) {
+                        let new_cost = cost + self.edge_cost(current_node, neighbour_node);
+
+                        if new_cost < *distances.get(neighbour_id).unwrap_or(&f64::INFINITY) {
+                            priority_queue.push(PathState {
+                                cost: new_cost,
+                                position: neighbour_id.clone(),
+                            });
+                            distances.insert(neighbour_id.clone(), new_cost);
+                            predecessors.insert(neighbour_id.clone(), position.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
 }
 
-#[derive(Debug, Clone)]
+fn robust_edge_cost(from_node: &NodeState, to_node: &NodeState) -> f64 {
+    let dx = to_node.position.0 - from_node.position.0;
+    let dy = to_node.position.1 - from_node.position.1;
+    let distance_cost = (dx * dx + dy * dy).sqrt();
+
+    let latency_cost = to_node.latency * W_LATENCY;
+
+    let health_penalty = (1.0 - to_node.health_score) * W_HEALTH_PENALTY;
+
+    distance_cost + latency_cost + health_penalty
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct OptimalPath {
     pub nodes: Vec<String>,
     pub total_cost: f64,
@@ -127,6 +246,26 @@ impl PathfindingNetworkManager {
         }
     }
 
+    pub fn with_config(config: PathfindingConfig) -> Self {
+        let mut state = NetworkState::new(config.cache_capacity);
+        state.weighting = config.weighting;
+        Self {
+            state: Arc::new(RwLock::new(state)),
+        }
+    }
+
+    pub async fn weighting(&self) -> PathWeighting {
+        self.state.read().await.weighting
+    }
+
+    pub async fn set_weighting(&self, weighting: PathWeighting) {
+        let mut state = self.state.write().await;
+        if state.weighting != weighting {
+            state.weighting = weighting;
+            state.path_cache.clear();
+        }
+    }
+
     pub async fn update_node(
         &self,
         id: String,
@@ -171,95 +310,80 @@ impl PathfindingNetworkManager {
             }
         }
 
-        let state = self.state.read().await;
-        let mut distances: HashMap<String, f64> = HashMap::new();
-        let mut priority_queue = BinaryHeap::new();
-        let mut predecessors: HashMap<String, String> = HashMap::new();
-
-        distances.insert(start.to_string(), 0.0);
-        priority_queue.push(PathState {
-            cost: 0.0,
-            position: start.to_string(),
-        });
+        let result = {
+            let state = self.state.read().await;
+            state.shortest_path(start, end, &HashSet::new(), &HashSet::new())?
+        };
 
-        while let Some(PathState { cost, position }) = priority_queue.pop() {
-            if position == end {
-                let mut path = vec![end.to_string()];
-                let mut current = end.to_string();
+        let mut state_write = self.state.write().await;
+        if state_write.path_cache.len() >= state_write.cache_capacity {
+            state_write.path_cache.clear();
+        }
+        state_write
+            .path_cache
+            .insert(cache_key, result.nodes.clone());
+        Some(result)
+    }
 
-                while let Some(prev) = predecessors.get(&current) {
-                    path.push(prev.clone());
-                    current = prev.clone();
-                }
-                path.reverse();
+    pub async fn find_k_paths(&self, start: &str, end: &str, k: usize) -> Vec<OptimalPath> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let state = self.state.read().await;
+        let Some(first) = state.shortest_path(start, end, &HashSet::new(), &HashSet::new()) else {
+            return Vec::new();
+        };
 
-                let result = OptimalPath {
-                    nodes: path.clone(),
-                    total_cost: cost,
+        let mut accepted = vec![first];
+        let mut candidates: Vec<OptimalPath> = Vec::new();
+
+        while accepted.len() < k {
+            let previous = accepted[accepted.len() - 1].nodes.clone();
+            for i in 0..previous.len().saturating_sub(1) {
+                let root = &previous[..=i];
+                let banned_edges: HashSet<(&str, &str)> = accepted
+                    .iter()
+                    .filter(|path| path.nodes.len() > i + 1 && path.nodes[..=i] == *root)
+                    .map(|path| (path.nodes[i].as_str(), path.nodes[i + 1].as_str()))
+                    .collect();
+                let banned_nodes: HashSet<&str> = root[..i].iter().map(String::as_str).collect();
+
+                let Some(spur) = state.shortest_path(&root[i], end, &banned_nodes, &banned_edges)
+                else {
+                    continue;
                 };
-
-                drop(state);
-                let mut state_write = self.state.write().await;
-
-                if state_write.path_cache.len() >= state_write.cache_capacity {
-                    state_write.path_cache.clear();
+                let mut nodes = root[..i].to_vec();
+                nodes.extend(spur.nodes);
+                if accepted.iter().chain(&candidates).any(|path| path.nodes == nodes) {
+                    continue;
+                }
+                if let Some(total_cost) = state.path_cost(&nodes) {
+                    candidates.push(OptimalPath { nodes, total_cost });
                 }
-
-                state_write.path_cache.insert(cache_key, path);
-                return Some(result);
-            }
-
-            if cost > *distances.get(&position).unwrap_or(&f64::INFINITY) {
-                continue;
             }
 
-            if let Some(current_node) = state.node_states.get(&position) {
-                for neighbour_id in &current_node.connections {
-                    if let Some(neighbour_node) = state.node_states.get(neighbour_id) {
-                        let edge_cost = self
-                            .calculate_robust_edge_cost(current_node, neighbour_node)
-                            .await;
-                        let new_cost = cost + edge_cost;
-
-                        if new_cost < *distances.get(neighbour_id).unwrap_or(&f64::INFINITY) {
-                            priority_queue.push(PathState {
-                                cost: new_cost,
-                                position: neighbour_id.clone(),
-                            });
-                            distances.insert(neighbour_id.clone(), new_cost);
-                            predecessors.insert(neighbour_id.clone(), position.clone());
-                        }
-                    }
-                }
+            let best = candidates
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| {
+                    a.total_cost
+                        .partial_cmp(&b.total_cost)
+                        .unwrap_or(Ordering::Equal)
+                        .then_with(|| a.nodes.len().cmp(&b.nodes.len()))
+                        .then_with(|| a.nodes.cmp(&b.nodes))
+                })
+                .map(|(index, _)| index);
+            match best {
+                Some(index) => accepted.push(candidates.swap_remove(index)),
+                None => break,
             }
         }
 
-        None
-    }
-
-    async fn calculate_robust_edge_cost(&self, from_node: &NodeState, to_node: &NodeState) -> f64 {
-        let dx = to_node.position.0 - from_node.position.0;
-        let dy = to_node.position.1 - from_node.position.1;
-        let distance_cost = (dx * dx + dy * dy).sqrt();
-
-        let latency_cost = to_node.latency * W_LATENCY;
-
-        let health_penalty = (1.0 - to_node.health_score) * W_HEALTH_PENALTY;
-
-        distance_cost + latency_cost + health_penalty
+        accepted
     }
 
     async fn calculate_path_cost(&self, path: &[String]) -> Option<f64> {
-        let state = self.state.read().await;
-        let mut total_cost = 0.0;
-
-        for window in path.windows(2) {
-            let current = state.node_states.get(&window[0])?;
-            let next = state.node_states.get(&window[1])?;
-            total_cost += self.calculate_robust_edge_cost(current, next).await;
-        }
-
-        Some(total_cost)
+        self.state.read().await.path_cost(path)
     }
 
     pub async fn get_network_stats(&self) -> NetworkStats {
@@ -12,8 +12,11 @@
 
 mod basic_scoring_tests;
 mod optimisation_tests;
+mod pathfinding_tests;
 mod persistence_tests;
+mod scheduling_tests;
 mod stateful_analysis_tests;
+mod streaming_analysis_tests;
 
 mod ner_comprehensive_tests;
 mod ner_integration_demo;
@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use crate::messaging::pathfinding::{PathWeighting, PathfindingConfig, PathfindingNetworkManager};
+
+async fn network(weighting: PathWeighting) -> PathfindingNetworkManager {
+    let manager = PathfindingNetworkManager::with_config(PathfindingConfig {
+        cache_capacity: 16,
+        weighting,
+    });
+    let nodes: [(&str, f64, &[&str]); 8] = [
+        ("a", 0.0, &["b", "c", "f"]),
+        ("b", 300.0, &["d"]),
+        ("c", 5.0, &["e"]),
+        ("e", 5.0, &["d"]),
+        ("f", 20.0, &["g"]),
+        ("g", 20.0, &["h"]),
+        ("h", 20.0, &["d"]),
+        ("d", 10.0, &[]),
+    ];
+    for (i, (id, latency, connections)) in nodes.into_iter().enumerate() {
+        let connections = connections.iter().map(|c| c.to_string()).collect();
+        manager
+            .update_node(id.to_string(), (i as f64, 0.0), latency, connections)
+            .await;
+    }
+    manager
+}
+
+fn route(nodes: &[String]) -> String {
+    nodes.join("-")
+}
+
+#[tokio::test]
+async fn k_paths_are_distinct_loopless_and_ordered() {
+    let manager = network(PathWeighting::HopCount).await;
+    let paths = manager.find_k_paths("a", "d", 5).await;
+
+    let routes: Vec<String> = paths.iter().map(|p| route(&p.nodes)).collect();
+    assert_eq!(routes, vec!["a-b-d", "a-c-e-d", "a-f-g-h-d"]);
+    assert!(paths.windows(2).all(|w| w[0].total_cost <= w[1].total_cost));
+    for path in &paths {
+        let mut seen = path.nodes.clone();
+        seen.sort();
+        seen.dedup();
+        assert_eq!(seen.len(), path.nodes.len());
+    }
+    assert_eq!(manager.find_k_paths("a", "d", 2).await.len(), 2);
+    assert!(manager.find_k_paths("d", "a", 3).await.is_empty());
+}
+
+#[tokio::test]
+async fn latency_weighting_changes_primary_path() {
+    let manager = network(PathWeighting::HopCount).await;
+    let by_hops = manager.find_optimal_path("a", "d").await.unwrap();
+    assert_eq!(route(&by_hops.nodes), "a-b-d");
+    assert_eq!(by_hops.total_cost, 2.0);
+
+    manager.set_weighting(PathWeighting::LatencyWeighted).await;
+    let by_latency = manager.find_optimal_path("a", "d").await.unwrap();
+    assert_eq!(route(&by_latency.nodes), "a-c-e-d");
+    assert_eq!(by_latency.total_cost, 20.0);
+
+    let ranked = manager.find_k_paths("a", "d", 3).await;
+    let costs: Vec<f64> = ranked.iter().map(|p| p.total_cost).collect();
+    assert_eq!(costs, vec![20.0, 70.0, 310.0]);
+}
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use crate::messaging::client::AlertPriority;
+use crate::messaging::network::{MessageRouter, MessageScheduler, RouteType, SchedulingMode};
+use crate::messaging::types::{Message, MessageDestination, MessageMetadata, MetadataValue};
+
+fn message(content: &str, priority: AlertPriority) -> Message {
+    let mut metadata = MessageMetadata::default();
+    metadata.insert(
+        "priority".to_string(),
+        MetadataValue::String(priority.to_string()),
+    );
+    let destination = MessageDestination::Single("bob".to_string());
+    Message::new("alice".to_string(), destination, content.to_string()).with_metadata(metadata)
+}
+
+#[tokio::test]
+async fn high_priority_overtakes_low_priority_flood() {
+    let router = MessageRouter::with_scheduling(
+        4,
+        SchedulingMode::PriorityFair {
+            starvation_limit: 3,
+        },
+    );
+    for i in 0..50 {
+        router.enqueue(message(&format!("low-{i}"), AlertPriority::Low)).await;
+    }
+    for i in 0..3 {
+        router.enqueue(message(&format!("high-{i}"), AlertPriority::High)).await;
+    }
+
+    let mut order = Vec::new();
+    while let Some(next) = router.next_scheduled().await {
+        order.push(next.content);
+    }
+    assert_eq!(order.len(), 53);
+    for i in 0..3 {
+        let position = order.iter().position(|c| *c == format!("high-{i}")).unwrap();
+        assert!(position < 4, "high-{i} delivered at {position}");
+    }
+    assert_eq!(order[3], "low-0");
+}
+
+#[test]
+fn low_priority_is_not_starved() {
+    let mut scheduler = MessageScheduler::new(SchedulingMode::PriorityFair {
+        starvation_limit: 4,
+    });
+    for i in 0..5 {
+        scheduler.enqueue(message(&format!("low-{i}"), AlertPriority::Low));
+    }
+    for i in 0..40 {
+        scheduler.enqueue(message(&format!("critical-{i}"), AlertPriority::Critical));
+    }
+    assert_eq!(scheduler.pending(AlertPriority::Critical), 40);
+
+    let order: Vec<String> = std::iter::from_fn(|| scheduler.next())
+        .map(|m| m.content)
+        .collect();
+    let first_low = order.iter().position(|c| c == "low-0").unwrap();
+    assert_eq!(first_low, 4);
+    let lows: Vec<usize> = order
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.starts_with("low-"))
+        .map(|(i, _)| i)
+        .collect();
+    assert!(lows.windows(2).all(|w| w[1] - w[0] <= 5));
+    assert!(scheduler.is_empty());
+}
+
+#[test]
+fn fifo_mode_preserves_arrival_order() {
+    let mut scheduler = MessageScheduler::new(SchedulingMode::Fifo);
+    scheduler.enqueue(message("first", AlertPriority::Low));
+    scheduler.enqueue(message("second", AlertPriority::Critical));
+    assert_eq!(scheduler.next().unwrap().content, "first");
+    assert_eq!(scheduler.next().unwrap().content, "second");
+    assert!(scheduler.next().is_none());
+}
+
+#[tokio::test]
+async fn changing_mode_keeps_the_configured_router() {
+    let router = MessageRouter::new(2).with_mode(SchedulingMode::PriorityFair {
+        starvation_limit: 3,
+    });
+    router.enqueue(message("low", AlertPriority::Low)).await;
+    router.enqueue(message("high", AlertPriority::High)).await;
+    assert_eq!(router.next_scheduled().await.unwrap().content, "high");
+
+    let recipients = ["bob", "carol", "dave"].map(String::from).to_vec();
+    let broadcast = Message::new(
+        "alice".to_string(),
+        MessageDestination::Multiple(recipients),
+        "hello".to_string(),
+    );
+    let decision = router.route_message(&broadcast).await.unwrap();
+    assert!(matches!(decision.route_type, RouteType::Broadcast));
+    assert_eq!(decision.target_peers, vec!["node-0", "node-1"]);
+}
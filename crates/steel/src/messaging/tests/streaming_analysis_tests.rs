@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use crate::messaging::insight::*;
+
+const SAMPLE: &str = "Grüße aus Zürich 東京 📦 contact ops-team@example.com \
+    token AKIA9XYZ4432QQ and 550e8400-e29b-41d4-a716-446655440000\tphone 555-123-4567 ✓";
+
+fn one_shot(text: &str) -> ContentAnalysis {
+    let mut distribution = ScoreDistribution::new(100);
+    ContentAnalyser::default().analyse(text, &mut distribution)
+}
+
+fn chunked(text: &str, cuts: &[usize]) -> ContentAnalysis {
+    let analyser = ContentAnalyser::default();
+    let mut distribution = ScoreDistribution::new(100);
+    let mut stream = analyser.stream(&mut distribution);
+    let bytes = text.as_bytes();
+    let mut start = 0;
+    for &cut in cuts {
+        stream.push_bytes(&bytes[start..cut]);
+        start = cut;
+    }
+    stream.push_bytes(&bytes[start..]);
+    stream.finish()
+}
+
+fn assert_same(expected: &ContentAnalysis, actual: &ContentAnalysis) {
+    assert_eq!(expected.overall_risk_score, actual.overall_risk_score);
+    assert_eq!(expected.interesting_tokens, actual.interesting_tokens);
+    assert_eq!(expected.requires_scribes_review, actual.requires_scribes_review);
+}
+
+#[test]
+fn chunked_analysis_matches_one_shot_at_every_split() {
+    let expected = one_shot(SAMPLE);
+    assert!(!expected.interesting_tokens.is_empty());
+    for cut in 0..=SAMPLE.len() {
+        assert_same(&expected, &chunked(SAMPLE, &[cut]));
+    }
+}
+
+#[test]
+fn chunked_analysis_matches_one_shot_for_many_small_chunks() {
+    let expected = one_shot(SAMPLE);
+    for step in 1..8 {
+        let cuts: Vec<usize> = (step..SAMPLE.len()).step_by(step).collect();
+        assert_same(&expected, &chunked(SAMPLE, &cuts));
+    }
+}
+
+#[test]
+fn string_chunks_accumulate_across_pushes() {
+    let expected = one_shot(SAMPLE);
+    let analyser = ContentAnalyser::default();
+    let mut distribution = ScoreDistribution::new(100);
+    let mut stream = analyser.stream(&mut distribution);
+    for word in SAMPLE.split_inclusive(' ') {
+        stream.push(word);
+    }
+    assert_same(&expected, &stream.finish());
+    assert_eq!(distribution.score_history.len(), 1);
+}
@@ -38,8 +38,48 @@ pub enum AuthorisationDecision {
     Deny(String),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyContribution {
+    Allow,
+    Deny,
+    Abstain,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicyTrace {
+    pub rule: String,
+    pub matched: bool,
+    pub contribution: PolicyContribution,
+    pub decisive: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AuthorisationRequest<'a> {
+    pub user_roles: &'a [String],
+    pub action: &'a str,
+    pub resource: &'a str,
+    pub data: &'a Value,
+}
+
+impl<'a> AuthorisationRequest<'a> {
+    pub fn new(
+        user_roles: &'a [String],
+        action: &'a str,
+        resource: &'a str,
+        data: &'a Value,
+    ) -> Self {
+        Self {
+            user_roles,
+            action,
+            resource,
+            data,
+        }
+    }
+}
+
 pub struct PolicyEngine {
     policies: Vec<ParsedPolicy>,
+    dry_run: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -91,9 +131,19 @@ impl PolicyEngine {
         );
         Ok(Self {
             policies: parsed_policies,
+            dry_run: false,
         })
     }
 
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
     pub fn authorise(
         &self,
         user_roles: &[String],
@@ -106,53 +156,80 @@ impl PolicyEngine {
             user_roles, action, resource
         );
 
-        let context = EvaluationContext::new(data.clone());
-        let mut allow_found = false;
-
-        for policy in &self.policies {
-            if !self.policy_matches(policy, action, resource) {
-                continue;
-            }
+        let request = AuthorisationRequest::new(user_roles, action, resource, data);
+        let (decision, trace) = self.evaluate_explained(&request);
+        let decisive = trace.iter().find(|entry| entry.decisive);
 
-            if !self.role_matches(policy, user_roles) {
-                continue;
+        match &decision {
+            AuthorisationDecision::Allow => {
+                if let Some(entry) = decisive {
+                    info!("✅ Allowed by policy '{}'", entry.rule);
+                }
             }
-
-            if policy.effect == "deny" && self.evaluate_conditions(policy, &context) {
-                let reason = format!(
-                    "🚫 Denied by policy '{}': User with roles {:?} is explicitly blocked",
-                    policy.name, user_roles
-                );
-                error!("{}", reason);
-                return AuthorisationDecision::Deny(reason);
+            AuthorisationDecision::Deny(reason) if self.dry_run => {
+                warn!("🧪 Dry run, not enforcing: {}", reason);
+                return AuthorisationDecision::Allow;
             }
+            AuthorisationDecision::Deny(reason) if decisive.is_some() => error!("{}", reason),
+            AuthorisationDecision::Deny(reason) => warn!("{}", reason),
         }
 
-        for policy in &self.policies {
-            if !self.policy_matches(policy, action, resource) {
-                continue;
-            }
+        decision
+    }
 
-            if !self.role_matches(policy, user_roles) {
-                continue;
-            }
+    pub fn evaluate_explained(
+        &self,
+        request: &AuthorisationRequest<'_>,
+    ) -> (AuthorisationDecision, Vec<PolicyTrace>) {
+        let context = EvaluationContext::new(request.data.clone());
+        let mut trace: Vec<PolicyTrace> = self
+            .policies
+            .iter()
+            .map(|policy| {
+                let matched = self.policy_matches(policy, request.action, request.resource)
+                    && self.role_matches(policy, request.user_roles)
+                    && self.evaluate_conditions(policy, &context);
+                let contribution = match policy.effect.as_str() {
+                    "deny" if matched => PolicyContribution::Deny,
+                    "allow" if matched => PolicyContribution::Allow,
+                    _ => PolicyContribution::Abstain,
+                };
+                PolicyTrace {
+                    rule: policy.name.clone(),
+                    matched,
+                    contribution,
+                    decisive: false,
+                }
+            })
+            .collect();
+
+        let decisive = trace
+            .iter()
+            .position(|entry| entry.contribution == PolicyContribution::Deny)
+            .or_else(|| {
+                trace
+                    .iter()
+                    .position(|entry| entry.contribution == PolicyContribution::Allow)
+            });
 
-            if policy.effect == "allow" && self.evaluate_conditions(policy, &context) {
-                info!("✅ Allowed by policy '{}'", policy.name);
-                allow_found = true;
-                break;
+        let decision = match decisive {
+            Some(index) => {
+                trace[index].decisive = true;
+                match trace[index].contribution {
+                    PolicyContribution::Allow => AuthorisationDecision::Allow,
+                    _ => AuthorisationDecision::Deny(format!(
+                        "🚫 Denied by policy '{}': User with roles {:?} is explicitly blocked",
+                        trace[index].rule, request.user_roles
+                    )),
+                }
             }
-        }
+            None => AuthorisationDecision::Deny(format!(
+                "🚫 Implicitly denied: No matching 'allow' policy found for user with roles {:?}",
+                request.user_roles
+            )),
+        };
 
-        if allow_found {
-            AuthorisationDecision::Allow
-        } else {
-            let reason = format!(
-                "🚫 Implicitly denied: No matching 'allow' policy found for user with roles {user_roles:?}"
-            );
-            warn!("{}", reason);
-            AuthorisationDecision::Deny(reason)
-        }
+        (decision, trace)
     }
 
     fn policy_matches(&self, policy: &ParsedPolicy, action: &str, resource: &str) -> bool {
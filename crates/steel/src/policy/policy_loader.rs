@@ -10,11 +10,19 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
-use crate::policy::engine::{Policy, PolicyEngine};
+use crate::policy::engine::{AuthorisationDecision, Policy, PolicyEngine};
+use crate::policy::parser::ConditionParser;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use serde::Deserialize;
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
 use tokio::fs;
-use tracing::{info, warn};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
 
 #[derive(Debug, Deserialize)]
 pub struct PolicyConfig {
@@ -29,6 +37,96 @@ pub struct ProviderConfig {
     pub config: HashMap<String, String>,
 }
 
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum PolicyValidationError {
+    #[error("Failed to read policy file '{path}': {message}")]
+    Io { path: String, message: String },
+    #[error("Failed to parse policy document: {0}")]
+    Syntax(String),
+    #[error("Policy '{policy}' has an empty '{field}'")]
+    MissingField { policy: String, field: &'static str },
+    #[error("Duplicate policy name '{0}'")]
+    DuplicatePolicy(String),
+    #[error("Invalid effect '{effect}' in policy '{policy}'. Must be 'allow' or 'deny'.")]
+    InvalidEffect { policy: String, effect: String },
+    #[error("Invalid condition '{condition}' in policy '{policy}': {message}")]
+    InvalidCondition {
+        policy: String,
+        condition: String,
+        message: String,
+    },
+    #[error("Policy engine rejected document: {0}")]
+    Engine(String),
+    #[error("Failed to watch policy file: {0}")]
+    Watch(String),
+}
+
+#[derive(Clone)]
+pub struct LivePolicyEngine {
+    current: Arc<RwLock<Arc<PolicyEngine>>>,
+    version: Arc<AtomicU64>,
+}
+
+impl LivePolicyEngine {
+    pub fn new(engine: PolicyEngine) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(engine))),
+            version: Arc::new(AtomicU64::new(1)),
+        }
+    }
+
+    pub fn current(&self) -> Arc<PolicyEngine> {
+        self.current
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::SeqCst)
+    }
+
+    pub fn authorise(
+        &self,
+        user_roles: &[String],
+        action: &str,
+        resource: &str,
+        data: &Value,
+    ) -> AuthorisationDecision {
+        self.current().authorise(user_roles, action, resource, data)
+    }
+
+    fn swap(&self, engine: PolicyEngine) -> u64 {
+        let mut current = self
+            .current
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        *current = Arc::new(engine);
+        self.version.fetch_add(1, Ordering::SeqCst) + 1
+    }
+}
+
+pub struct PolicyWatcher {
+    _watcher: RecommendedWatcher,
+    task: JoinHandle<()>,
+    last_error: Arc<Mutex<Option<PolicyValidationError>>>,
+}
+
+impl PolicyWatcher {
+    pub fn last_error(&self) -> Option<PolicyValidationError> {
+        self.last_error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl Drop for PolicyWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
 pub struct PolicyLoader;
 
 impl PolicyLoader {
@@ -60,9 +158,91 @@ impl PolicyLoader {
         Self::load_from_file(file_path).await
     }
 
-    fn validate_policies(policies: &[Policy]) -> Result<(), String> {
+    pub fn validate_document(yaml_content: &str) -> Result<PolicyEngine, PolicyValidationError> {
+        let policy_config: PolicyConfig = serde_yaml::from_str(yaml_content)
+            .map_err(|e| PolicyValidationError::Syntax(e.to_string()))?;
+        Self::validate_policies(&policy_config.policies)?;
+        PolicyEngine::new(policy_config.policies).map_err(PolicyValidationError::Engine)
+    }
+
+    pub async fn apply_from_file(
+        file_path: impl AsRef<Path>,
+        live: &LivePolicyEngine,
+    ) -> Result<u64, PolicyValidationError> {
+        let path = file_path.as_ref();
+        let yaml_content = fs::read_to_string(path)
+            .await
+            .map_err(|e| PolicyValidationError::Io {
+                path: path.display().to_string(),
+                message: e.to_string(),
+            })?;
+        let engine = Self::validate_document(&yaml_content)?;
+        let version = live.swap(engine);
+        info!(
+            "Applied policy configuration from {} as version {}",
+            path.display(),
+            version
+        );
+        Ok(version)
+    }
+
+    pub fn watch(
+        file_path: impl AsRef<Path>,
+        live: LivePolicyEngine,
+    ) -> Result<PolicyWatcher, PolicyValidationError> {
+        let path: PathBuf = file_path.as_ref().to_path_buf();
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let file_name = path.file_name().map(|name| name.to_os_string());
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
+            if let Ok(event) = res {
+                let relevant = matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_))
+                    && event
+                        .paths
+                        .iter()
+                        .any(|changed| changed.file_name() == file_name.as_deref());
+                if relevant {
+                    let _ = tx.send(());
+                }
+            }
+        })
+        .map_err(|e| PolicyValidationError::Watch(e.to_string()))?;
+        watcher
+            .watch(&directory, RecursiveMode::NonRecursive)
+            .map_err(|e| PolicyValidationError::Watch(e.to_string()))?;
+
+        let last_error = Arc::new(Mutex::new(None));
+        let task_error = Arc::clone(&last_error);
+        let task = tokio::spawn(async move {
+            while rx.recv().await.is_some() {
+                while rx.try_recv().is_ok() {}
+                let outcome = Self::apply_from_file(&path, &live).await;
+                let mut slot = task_error.lock().unwrap_or_else(PoisonError::into_inner);
+                match outcome {
+                    Ok(_) => *slot = None,
+                    Err(e) => {
+                        error!("Rejected policy edit, keeping last valid policy: {}", e);
+                        *slot = Some(e);
+                    }
+                }
+            }
+        });
+
+        Ok(PolicyWatcher {
+            _watcher: watcher,
+            task,
+            last_error,
+        })
+    }
+
+    fn validate_policies(policies: &[Policy]) -> Result<(), PolicyValidationError> {
         let mut role_counts = HashMap::new();
-        let mut resource_actions = std::collections::HashSet::new();
+        let mut resource_actions = HashSet::new();
+        let mut names = HashSet::new();
 
         for policy in policies {
             *role_counts.entry(policy.role.clone()).or_insert(0) += 1;
@@ -70,11 +250,38 @@ impl PolicyLoader {
             let key = format!("{}:{}", policy.resource, policy.action);
             resource_actions.insert(key);
 
+            let fields = [
+                ("name", &policy.name),
+                ("role", &policy.role),
+                ("action", &policy.action),
+                ("resource", &policy.resource),
+            ];
+            if let Some(&(field, _)) = fields.iter().find(|(_, value)| value.trim().is_empty()) {
+                return Err(PolicyValidationError::MissingField {
+                    policy: policy.name.clone(),
+                    field,
+                });
+            }
+
+            if !names.insert(policy.name.as_str()) {
+                return Err(PolicyValidationError::DuplicatePolicy(policy.name.clone()));
+            }
+
             if policy.effect != "allow" && policy.effect != "deny" {
-                return Err(format!(
-                    "Invalid effect '{}' in policy '{}'. Must be 'allow' or 'deny'.",
-                    policy.effect, policy.name
-                ));
+                return Err(PolicyValidationError::InvalidEffect {
+                    policy: policy.name.clone(),
+                    effect: policy.effect.clone(),
+                });
+            }
+
+            for condition in &policy.conditions {
+                if let Err(message) = ConditionParser::parse(condition) {
+                    return Err(PolicyValidationError::InvalidCondition {
+                        policy: policy.name.clone(),
+                        condition: condition.clone(),
+                        message,
+                    });
+                }
             }
 
             if policy.conditions.is_empty() {
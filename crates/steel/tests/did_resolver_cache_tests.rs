@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use steel::iam::{DidCacheConfig, DidDocument, DidResolutionError, DidResolver, DidResolverCache};
+use tokio::time::{sleep, Duration};
+
+#[derive(Default)]
+struct CountingResolver {
+    calls: AtomicUsize,
+    down: AtomicBool,
+}
+
+#[async_trait]
+impl DidResolver for CountingResolver {
+    async fn resolve(&self, did: &str) -> Result<DidDocument, DidResolutionError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if self.down.load(Ordering::SeqCst) {
+            return Err(DidResolutionError::Unavailable(did.to_string()));
+        }
+        Ok(DidDocument {
+            context: vec!["https://www.w3.org/ns/did/v1".to_string()],
+            id: did.to_string(),
+            verification_methods: Vec::new(),
+            authentication: Vec::new(),
+            assertion_method: Vec::new(),
+            service: Vec::new(),
+            also_known_as: None,
+        })
+    }
+}
+
+fn cache(resolver: &Arc<CountingResolver>, ttl: Duration) -> DidResolverCache {
+    let config = DidCacheConfig {
+        ttl,
+        negative_ttl: Duration::from_millis(50),
+        max_entries: 2,
+    };
+    DidResolverCache::new(resolver.clone(), config)
+}
+
+#[tokio::test]
+async fn second_resolve_within_ttl_is_served_from_cache() {
+    let resolver = Arc::new(CountingResolver::default());
+    let cache = cache(&resolver, Duration::from_secs(60));
+
+    let first = cache.resolve("did:steel:alice").await.unwrap();
+    let second = cache.resolve("did:steel:alice").await.unwrap();
+    assert_eq!(first, second);
+    assert_eq!(resolver.calls.load(Ordering::SeqCst), 1);
+
+    assert!(cache.invalidate("did:steel:alice"));
+    cache.resolve("did:steel:alice").await.unwrap();
+    assert_eq!(resolver.calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn expired_entry_is_refreshed() {
+    let resolver = Arc::new(CountingResolver::default());
+    let cache = cache(&resolver, Duration::from_millis(40));
+
+    cache.resolve("did:steel:bob").await.unwrap();
+    sleep(Duration::from_millis(80)).await;
+    cache.resolve("did:steel:bob").await.unwrap();
+    assert_eq!(resolver.calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn failures_are_cached_negatively() {
+    let resolver = Arc::new(CountingResolver::default());
+    resolver.down.store(true, Ordering::SeqCst);
+    let cache = cache(&resolver, Duration::from_secs(60));
+
+    assert!(cache.resolve("did:steel:carol").await.is_err());
+    assert!(cache.resolve("did:steel:carol").await.is_err());
+    assert_eq!(resolver.calls.load(Ordering::SeqCst), 1);
+
+    resolver.down.store(false, Ordering::SeqCst);
+    sleep(Duration::from_millis(80)).await;
+    assert!(cache.resolve("did:steel:carol").await.is_ok());
+    assert_eq!(resolver.calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn cache_is_bounded_by_max_entries() {
+    let resolver = Arc::new(CountingResolver::default());
+    let cache = cache(&resolver, Duration::from_secs(60));
+
+    for did in ["did:steel:a", "did:steel:b", "did:steel:c"] {
+        cache.resolve(did).await.unwrap();
+    }
+    assert_eq!(cache.len(), 2);
+}
@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use steel::{JwtManager, TokenError};
+
+fn manager(secret: &str) -> JwtManager {
+    let issuer = "did:steel:issuer".to_string();
+    JwtManager::new(secret, issuer, "steel".to_string())
+}
+
+fn sign(manager: &JwtManager, user: &str) -> String {
+    let roles = vec!["user".to_string()];
+    manager
+        .create_token(user, "user@example.com", "User", None, roles, 1)
+        .unwrap()
+}
+
+#[test]
+fn tokens_signed_before_rotation_still_verify() {
+    let mut jwt = manager("secret-default");
+    jwt.add_key("a", "secret-a");
+    jwt.set_active_key("a").unwrap();
+    let token_a = sign(&jwt, "alice");
+
+    jwt.rotate_key("b", "secret-b");
+    assert_eq!(jwt.active_key_id(), "b");
+    let token_b = sign(&jwt, "bob");
+
+    assert_eq!(jwt.verify_token(&token_a).unwrap().sub, "alice");
+    assert_eq!(jwt.verify_token(&token_b).unwrap().sub, "bob");
+}
+
+#[test]
+fn unknown_kid_is_rejected() {
+    let jwt = manager("secret-default");
+    let mut foreign = manager("secret-default");
+    foreign.rotate_key("c", "secret-c");
+    let token = sign(&foreign, "carol");
+
+    let err = jwt.verify_token(&token).unwrap_err();
+    assert!(matches!(err, TokenError::UnknownKeyId(ref kid) if kid == "c"));
+}
+
+#[test]
+fn removed_key_stops_verifying() {
+    let mut jwt = manager("secret-default");
+    jwt.add_key("a", "secret-a");
+    jwt.set_active_key("a").unwrap();
+    let token_a = sign(&jwt, "alice");
+    jwt.rotate_key("b", "secret-b");
+
+    assert!(matches!(jwt.remove_key("b"), Err(TokenError::ActiveKeyInUse(_))));
+    jwt.remove_key("a").unwrap();
+    assert!(matches!(jwt.verify_token(&token_a), Err(TokenError::UnknownKeyId(_))));
+    assert!(matches!(jwt.set_active_key("a"), Err(TokenError::UnknownKeyId(_))));
+}
+
+#[test]
+fn default_key_verifies_without_rotation() {
+    let jwt = manager("secret-default");
+    let token = sign(&jwt, "dave");
+    assert!(jwt.has_role(&token, "user").unwrap());
+    assert_eq!(jwt.extract_user_id(&token).unwrap(), "dave");
+}
+
+#[test]
+fn verification_failures_keep_the_jwt_error_as_their_source() {
+    let jwt = manager("secret-default");
+    let expired = jwt
+        .create_token("carol", "carol@example.com", "Carol", None, Vec::new(), -2)
+        .unwrap();
+
+    let err = jwt.verify_token(&expired).unwrap_err();
+    assert!(err.is_expired());
+    let source = std::error::Error::source(&err).expect("jsonwebtoken error as source");
+    assert!(source.is::<jsonwebtoken::errors::Error>());
+
+    let err = manager("other-secret").verify_token(&sign(&jwt, "dave")).unwrap_err();
+    assert!(matches!(err, TokenError::Jwt(_)));
+    assert!(!err.is_expired());
+}
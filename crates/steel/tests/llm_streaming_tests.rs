@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use futures::stream::{self, StreamExt};
+use llm_contracts::LLMResult;
+use steel::llm::{
+    translate_sse, AnthropicStreamTranslator, OpenAIStreamTranslator, SseDecoder, SseTranslator,
+    TokenChunk,
+};
+
+const OPENAI_FIXTURE: &str = include_str!("fixtures/openai_chat_stream.sse");
+const ANTHROPIC_FIXTURE: &str = include_str!("fixtures/anthropic_messages_stream.sse");
+
+async fn replay<T: SseTranslator + 'static>(
+    fixture: &str,
+    translator: T,
+) -> Vec<LLMResult<TokenChunk>> {
+    let body: Vec<Result<Vec<u8>, std::io::Error>> = fixture
+        .as_bytes()
+        .chunks(37)
+        .map(|chunk| Ok(chunk.to_vec()))
+        .collect();
+    translate_sse(stream::iter(body), translator).collect().await
+}
+
+fn concatenate(chunks: &[LLMResult<TokenChunk>]) -> String {
+    chunks
+        .iter()
+        .map(|chunk| chunk.as_ref().expect("chunk").delta.as_str())
+        .collect()
+}
+
+#[tokio::test]
+async fn openai_fixture_reassembles_message_and_usage() {
+    let chunks = replay(OPENAI_FIXTURE, OpenAIStreamTranslator::default()).await;
+
+    assert_eq!(concatenate(&chunks), "The quick brown fox jumps.");
+    let last = chunks.last().unwrap().as_ref().unwrap();
+    assert!(last.is_final);
+    assert_eq!(last.finish_reason.as_deref(), Some("stop"));
+    let usage = last.usage.as_ref().expect("usage on final chunk");
+    assert_eq!(usage.prompt_tokens, 12);
+    assert_eq!(usage.completion_tokens, 6);
+    assert_eq!(usage.total_tokens, 18);
+    let finals = chunks.iter().filter(|c| c.as_ref().unwrap().is_final);
+    assert_eq!(finals.count(), 1);
+}
+
+#[tokio::test]
+async fn anthropic_fixture_reassembles_message_and_usage() {
+    let chunks = replay(ANTHROPIC_FIXTURE, AnthropicStreamTranslator::default()).await;
+
+    assert_eq!(concatenate(&chunks), "Hello, world! How can I help?");
+    let last = chunks.last().unwrap().as_ref().unwrap();
+    assert!(last.is_final);
+    assert_eq!(last.finish_reason.as_deref(), Some("end_turn"));
+    let usage = last.usage.as_ref().expect("usage on final chunk");
+    assert_eq!(usage.prompt_tokens, 25);
+    assert_eq!(usage.completion_tokens, 15);
+    assert_eq!(usage.total_tokens, 40);
+}
+
+#[tokio::test]
+async fn truncated_stream_ends_with_error() {
+    let truncated = OPENAI_FIXTURE.replace("data: [DONE]", "");
+    let chunks = replay(&truncated, OpenAIStreamTranslator::default()).await;
+
+    assert!(chunks.last().unwrap().is_err());
+    assert!(chunks.iter().flatten().all(|c| !c.is_final));
+}
+
+#[test]
+fn decoder_joins_multiline_data_and_handles_crlf() {
+    let mut decoder = SseDecoder::new();
+    let mut events = decoder.push(b": keep-alive\r\nevent: note\r\ndata: first\r\n");
+    assert!(events.is_empty());
+    events.extend(decoder.push(b"data: second\r\n\r\ndata: tail"));
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event.as_deref(), Some("note"));
+    assert_eq!(events[0].data, "first\nsecond");
+
+    let tail = decoder.finish().expect("trailing event");
+    assert_eq!(tail.event, None);
+    assert_eq!(tail.data, "tail");
+}
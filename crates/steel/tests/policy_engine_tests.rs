@@ -11,7 +11,10 @@
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
 use serde_json::json;
-use steel::policy::engine::{AuthorisationDecision, Policy, PolicyEngine};
+use steel::policy::engine::{
+    AuthorisationDecision, AuthorisationRequest, Policy, PolicyContribution, PolicyEngine,
+    PolicyTrace,
+};
 
 fn create_test_policies() -> Vec<Policy> {
     vec![
@@ -83,3 +86,87 @@ fn test_authorisation_implicit_deny() {
         _ => panic!("Expected deny decision"),
     }
 }
+
+fn layered_policies() -> Vec<Policy> {
+    vec![
+        Policy {
+            name: "allow_publish_reports".to_string(),
+            role: "any".to_string(),
+            action: "publish".to_string(),
+            resource: "reports".to_string(),
+            effect: "allow".to_string(),
+            conditions: vec![],
+        },
+        Policy {
+            name: "deny_restricted_reports".to_string(),
+            role: "any".to_string(),
+            action: "publish".to_string(),
+            resource: "reports".to_string(),
+            effect: "deny".to_string(),
+            conditions: vec!["data.classification == 'Restricted'".to_string()],
+        },
+        Policy {
+            name: "allow_archive".to_string(),
+            role: "Archivist".to_string(),
+            action: "archive".to_string(),
+            resource: "reports".to_string(),
+            effect: "allow".to_string(),
+            conditions: vec![],
+        },
+    ]
+}
+
+#[test]
+fn test_explained_deny_attributes_specific_rule() {
+    let engine = PolicyEngine::new(layered_policies()).unwrap();
+    let data = json!({ "classification": "Restricted" });
+    let user_roles = vec!["Analyst".to_string()];
+    let request = AuthorisationRequest::new(&user_roles, "publish", "reports", &data);
+
+    let (decision, trace) = engine.evaluate_explained(&request);
+    match &decision {
+        AuthorisationDecision::Deny(reason) => assert!(reason.contains("deny_restricted_reports")),
+        other => panic!("Expected deny decision, got {other:?}"),
+    }
+    assert_eq!(trace.len(), 3);
+
+    let decisive: Vec<&PolicyTrace> = trace.iter().filter(|t| t.decisive).collect();
+    assert_eq!(decisive.len(), 1);
+    assert_eq!(decisive[0].rule, "deny_restricted_reports");
+    assert_eq!(decisive[0].contribution, PolicyContribution::Deny);
+
+    assert!(trace[0].matched);
+    assert_eq!(trace[0].contribution, PolicyContribution::Allow);
+    assert!(!trace[2].matched);
+    assert_eq!(trace[2].contribution, PolicyContribution::Abstain);
+}
+
+#[test]
+fn test_explained_allow_attributes_broad_rule() {
+    let engine = PolicyEngine::new(layered_policies()).unwrap();
+    let data = json!({ "classification": "Public" });
+    let user_roles = vec!["Analyst".to_string()];
+    let request = AuthorisationRequest::new(&user_roles, "publish", "reports", &data);
+
+    let (decision, trace) = engine.evaluate_explained(&request);
+    assert_eq!(decision, AuthorisationDecision::Allow);
+    let decisive = trace.iter().find(|t| t.decisive).unwrap();
+    assert_eq!(decisive.rule, "allow_publish_reports");
+    assert!(!trace[1].matched);
+}
+
+#[test]
+fn test_dry_run_reports_but_does_not_enforce() {
+    let engine = PolicyEngine::new(layered_policies())
+        .unwrap()
+        .with_dry_run(true);
+    let data = json!({ "classification": "Restricted" });
+    let user_roles = vec!["Analyst".to_string()];
+
+    let decision = engine.authorise(&user_roles, "publish", "reports", &data);
+    assert_eq!(decision, AuthorisationDecision::Allow);
+
+    let request = AuthorisationRequest::new(&user_roles, "publish", "reports", &data);
+    let (explained, _) = engine.evaluate_explained(&request);
+    assert!(matches!(explained, AuthorisationDecision::Deny(_)));
+}
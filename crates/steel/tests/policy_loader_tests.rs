@@ -10,8 +10,11 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
+use serde_json::json;
 use std::io::Write;
-use steel::policy::policy_loader::PolicyLoader;
+use std::time::Duration;
+use steel::policy::engine::AuthorisationDecision;
+use steel::policy::policy_loader::{LivePolicyEngine, PolicyLoader, PolicyValidationError};
 use tempfile::NamedTempFile;
 
 #[tokio::test]
@@ -71,3 +74,104 @@ policies:
         assert!(error_msg.contains("invalid_effect") || error_msg.contains("Invalid effect"));
     }
 }
+
+fn policy_document(effect: &str, condition: &str) -> String {
+    format!(
+        r#"
+providers: []
+
+policies:
+  - name: publish_reports
+    role: "Analyst"
+    action: "publish"
+    resource: "reports"
+    effect: "{effect}"
+    conditions:
+      - "{condition}"
+"#
+    )
+}
+
+fn allows(live: &LivePolicyEngine, data: &serde_json::Value) -> bool {
+    let roles = vec!["Analyst".to_string()];
+    live.authorise(&roles, "publish", "reports", data) == AuthorisationDecision::Allow
+}
+
+#[tokio::test]
+async fn test_apply_keeps_last_valid_policy_on_invalid_edit() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    write!(temp_file, "{}", policy_document("allow", "data.kind == 'Sales'")).unwrap();
+    let path = temp_file.path().to_path_buf();
+
+    let engine = PolicyLoader::load_from_file(path.to_str().unwrap())
+        .await
+        .unwrap();
+    let live = LivePolicyEngine::new(engine);
+    let sales = json!({ "kind": "Sales" });
+    let stock = json!({ "kind": "Stock" });
+    assert!(allows(&live, &sales));
+
+    std::fs::write(&path, policy_document("allow", "data.kind == 'Stock'")).unwrap();
+    let version = PolicyLoader::apply_from_file(&path, &live).await.unwrap();
+    assert_eq!(version, 2);
+    assert!(allows(&live, &stock));
+    assert!(!allows(&live, &sales));
+
+    std::fs::write(&path, policy_document("permit", "data.kind == 'Sales'")).unwrap();
+    let err = PolicyLoader::apply_from_file(&path, &live)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        PolicyValidationError::InvalidEffect {
+            policy: "publish_reports".to_string(),
+            effect: "permit".to_string(),
+        }
+    );
+
+    std::fs::write(&path, policy_document("allow", "data.kind ~= 'Sales'")).unwrap();
+    let err = PolicyLoader::apply_from_file(&path, &live)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, PolicyValidationError::InvalidCondition { .. }));
+
+    assert_eq!(live.version(), 2);
+    assert!(allows(&live, &stock));
+}
+
+#[tokio::test]
+async fn test_watch_swaps_valid_edits_and_rejects_invalid_ones() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("policies.yaml");
+    std::fs::write(&path, policy_document("allow", "data.kind == 'Sales'")).unwrap();
+
+    let engine = PolicyLoader::load_from_file(path.to_str().unwrap())
+        .await
+        .unwrap();
+    let live = LivePolicyEngine::new(engine);
+    let watcher = PolicyLoader::watch(&path, live.clone()).unwrap();
+
+    std::fs::write(&path, policy_document("deny", "data.kind == 'Sales'")).unwrap();
+    for _ in 0..100 {
+        if live.version() > 1 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert!(live.version() > 1);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let version = live.version();
+
+    std::fs::write(&path, "policies: [not, a, policy").unwrap();
+    for _ in 0..100 {
+        if watcher.last_error().is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert!(matches!(watcher.last_error(), Some(PolicyValidationError::Syntax(_))));
+    assert_eq!(live.version(), version);
+
+    let sales = json!({ "kind": "Sales" });
+    assert!(!allows(&live, &sales));
+}
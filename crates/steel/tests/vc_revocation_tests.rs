@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use std::collections::HashMap;
+use steel::iam::{Proof, VcManager, VerifiableCredential};
+use steel::{JwtManager, TokenError};
+
+fn setup() -> (VcManager, String) {
+    let issuer = "did:steel:issuer".to_string();
+    let jwt = JwtManager::new("vc-secret", issuer.clone(), "steel".to_string());
+    let roles = vec!["issuer".to_string()];
+    let token = jwt
+        .create_token("issuer", "issuer@example.com", "Issuer", None, roles, 1)
+        .unwrap();
+    (VcManager::new(jwt, issuer), token)
+}
+
+#[test]
+fn revoked_credential_fails_verification() {
+    let (vc, token) = setup();
+    let revoked = vc
+        .create_identity_credential("did:steel:alice", "Alice", "alice@example.com", &token)
+        .unwrap();
+    let unrelated = vc
+        .create_identity_credential("did:steel:bob", "Bob", "bob@example.com", &token)
+        .unwrap();
+
+    let status = revoked.credential_status.clone().unwrap();
+    assert_eq!(status.status_type, "StatusList2021Entry");
+    assert_eq!(status.status_list_credential, vc.status_list().id());
+    assert!(vc.verify_credential(&revoked).is_ok());
+
+    let id = revoked.id.clone().unwrap();
+    vc.revoke(&id).unwrap();
+    assert!(vc.is_revoked(&id));
+
+    let err = vc.verify_credential(&revoked).unwrap_err();
+    assert!(matches!(err.downcast_ref::<TokenError>(), Some(TokenError::Revoked(r)) if *r == id));
+    assert!(vc.verify_credential(&unrelated).is_ok());
+    assert_ne!(vc.status_list().encoded_list(), "AA==");
+}
+
+#[test]
+fn statusless_credential_still_verifies() {
+    let (vc, token) = setup();
+    let credential = VerifiableCredential {
+        context: vec!["https://www.w3.org/2018/credentials/v1".to_string()],
+        id: Some("urn:uuid:statusless".to_string()),
+        types: vec!["VerifiableCredential".to_string()],
+        issuer: "did:steel:issuer".to_string(),
+        issuance_date: "2024-01-01T00:00:00Z".to_string(),
+        credential_subject: HashMap::new(),
+        credential_status: None,
+        proof: Proof {
+            proof_type: "JsonWebTokenProof2020".to_string(),
+            created: "2024-01-01T00:00:00Z".to_string(),
+            verification_method: "did:steel:issuer#jwt".to_string(),
+            proof_purpose: "assertionMethod".to_string(),
+            proof_value: token,
+        },
+    };
+    let json = serde_json::to_value(&credential).unwrap();
+    assert!(json.get("credentialStatus").is_none());
+
+    let claims = vc.verify_credential(&credential).unwrap();
+    assert_eq!(claims.sub, "issuer");
+}
+
+#[test]
+fn revoking_unknown_credential_is_rejected() {
+    let (vc, _) = setup();
+    assert!(matches!(
+        vc.revoke("urn:uuid:missing"),
+        Err(TokenError::UnknownCredential(_))
+    ));
+}
+
+#[test]
+fn rejected_issuer_tokens_do_not_consume_status_slots() {
+    let issuer = "did:steel:issuer".to_string();
+    let jwt = JwtManager::new("vc-secret", issuer.clone(), "steel".to_string());
+    let viewer = jwt
+        .create_token("viewer", "viewer@example.com", "Viewer", None, vec!["viewer".to_string()], 1)
+        .unwrap();
+    let token = jwt
+        .create_token("issuer", "issuer@example.com", "Issuer", None, vec!["issuer".to_string()], 1)
+        .unwrap();
+    let vc = VcManager::new(jwt, issuer);
+
+    assert!(vc
+        .create_identity_credential("did:steel:eve", "Eve", "eve@example.com", &viewer)
+        .is_err());
+    assert!(vc
+        .create_role_credential("did:steel:eve", "Eve", vec!["admin".to_string()], "garbage")
+        .is_err());
+    assert_eq!(vc.status_list().encoded_list(), "");
+
+    let credential = vc
+        .create_identity_credential("did:steel:alice", "Alice", "alice@example.com", &token)
+        .unwrap();
+    assert_eq!(credential.credential_status.unwrap().status_list_index, "0");
+}
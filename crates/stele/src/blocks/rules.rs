@@ -10,6 +10,8 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
+use crate::codegen::wat_validate::WatError;
+use crate::flows::dynamic_executor::function::DynamicExecError;
 use serde::{Deserialize, Serialize};
 use std::{any::Any, collections::HashMap, future::Future, pin::Pin};
 use thiserror::Error;
@@ -117,6 +119,10 @@ pub enum BlockError {
     },
     #[error("Security violation: {0}")]
     SecurityViolation(String),
+    #[error("invalid WAT: {}", .0.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; "))]
+    InvalidWat(Vec<WatError>),
+    #[error(transparent)]
+    DynamicExec(#[from] DynamicExecError),
 }
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub enum BlockType {
@@ -19,3 +19,4 @@ pub mod artifacts;
 pub mod plan_artifacts;
 pub mod wat_pipeline;
 pub mod wat_sanitize;
+pub mod wat_validate;
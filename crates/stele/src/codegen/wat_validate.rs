@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use regex::Regex;
+use serde::Serialize;
+use std::fmt;
+use std::sync::OnceLock;
+use wasmtime::{Engine, Module};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WatError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    pub token: Option<String>,
+}
+
+impl fmt::Display for WatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)?;
+        if let Some(token) = &self.token {
+            write!(f, " (at `{token}`)")?;
+        }
+        Ok(())
+    }
+}
+
+pub fn validate_wat(source: &str) -> Result<(), Vec<WatError>> {
+    let bytes = wat::parse_str(source).map_err(|e| vec![parse_error(source, &e.to_string())])?;
+    Module::validate(&Engine::default(), &bytes)
+        .map_err(|e| vec![validation_error(source, &bytes, &format!("{e:#}"))])
+}
+
+fn parse_error(source: &str, rendered: &str) -> WatError {
+    static LOCATION: OnceLock<Regex> = OnceLock::new();
+    let location = LOCATION.get_or_init(|| Regex::new(r"(?m)-->.*:(\d+):(\d+)\s*$").unwrap());
+    let (line, column) = location
+        .captures(rendered)
+        .and_then(|c| Some((c[1].parse().ok()?, c[2].parse().ok()?)))
+        .unwrap_or((0, 0));
+    WatError {
+        line,
+        column,
+        message: rendered.lines().next().unwrap_or_default().trim().to_string(),
+        token: token_at(source, line, column),
+    }
+}
+
+fn validation_error(source: &str, bytes: &[u8], rendered: &str) -> WatError {
+    static OFFSET: OnceLock<Regex> = OnceLock::new();
+    let offset = OFFSET.get_or_init(|| Regex::new(r"offset (0x[0-9a-fA-F]+|\d+)").unwrap());
+    let (line, column) = offset
+        .captures(rendered)
+        .and_then(|c| match c[1].strip_prefix("0x") {
+            Some(hex) => usize::from_str_radix(hex, 16).ok(),
+            None => c[1].parse().ok(),
+        })
+        .and_then(|offset| defined_function_at(bytes, offset))
+        .and_then(|index| defined_function_locations(source).get(index).copied())
+        .unwrap_or((0, 0));
+    WatError {
+        line,
+        column,
+        message: rendered.to_string(),
+        token: token_at(source, line, column),
+    }
+}
+
+fn token_at(source: &str, line: usize, column: usize) -> Option<String> {
+    let text = source.lines().nth(line.checked_sub(1)?)?;
+    let rest = text.get(column.checked_sub(1)?..)?;
+    let token: String = rest
+        .char_indices()
+        .take_while(|&(i, c)| !c.is_whitespace() && c != ')' && (i == 0 || c != '('))
+        .map(|(_, c)| c)
+        .collect();
+    (!token.is_empty()).then_some(token)
+}
+
+fn defined_function_locations(source: &str) -> Vec<(usize, usize)> {
+    let field_depth = usize::from(source.trim_start().starts_with("(module"));
+    let mut depth = 0usize;
+    let mut found = Vec::new();
+    for (line_index, line) in source.lines().enumerate() {
+        let code = line.split(";;").next().unwrap_or_default();
+        for (column, c) in code.char_indices() {
+            match c {
+                '(' => {
+                    let rest = &code[column + 1..];
+                    let is_func = rest.starts_with("func")
+                        && rest[4..].chars().next().is_none_or(|n| n.is_whitespace() || n == ')');
+                    if depth == field_depth && is_func {
+                        found.push((line_index + 1, column + 1));
+                    }
+                    depth += 1;
+                }
+                ')' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+    }
+    found
+}
+
+fn defined_function_at(bytes: &[u8], offset: usize) -> Option<usize> {
+    const CODE_SECTION: u8 = 10;
+    let mut pos = 8;
+    while pos < bytes.len() {
+        let id = bytes[pos];
+        pos += 1;
+        let size = read_leb128(bytes, &mut pos)? as usize;
+        let end = pos.checked_add(size)?;
+        if id == CODE_SECTION {
+            let count = read_leb128(bytes, &mut pos)?;
+            for index in 0..count as usize {
+                let body_size = read_leb128(bytes, &mut pos)? as usize;
+                pos = pos.checked_add(body_size)?;
+                if offset < pos {
+                    return Some(index);
+                }
+            }
+            return None;
+        }
+        pos = end;
+    }
+    None
+}
+
+fn read_leb128(bytes: &[u8], pos: &mut usize) -> Option<u64> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let byte = *bytes.get(*pos)?;
+        *pos += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+        shift += 7;
+        if shift >= 64 {
+            return None;
+        }
+    }
+}
@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use crate::database::connection::DatabaseConnection;
+use crate::database::types::{DatabaseCommand, DatabaseError};
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use surrealdb::engine::remote::ws::Client;
+use surrealdb::Surreal;
+use tokio::sync::{mpsc, oneshot, OwnedSemaphorePermit, Semaphore};
+use tracing::error;
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+    pub size: usize,
+    pub acquire_timeout: Duration,
+    pub command_buffer: usize,
+}
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            size: 4,
+            acquire_timeout: Duration::from_secs(30),
+            command_buffer: 32,
+        }
+    }
+}
+pub struct PooledConnection {
+    pub client: Arc<Surreal<Client>>,
+    pub commands: mpsc::Sender<DatabaseCommand>,
+}
+pub struct ConnectionPool {
+    idle: Arc<Mutex<Vec<PooledConnection>>>,
+    permits: Arc<Semaphore>,
+    acquire_timeout: Duration,
+    size: usize,
+}
+impl ConnectionPool {
+    pub async fn connect(config: PoolConfig) -> Result<Self, DatabaseError> {
+        if config.size == 0 {
+            return Err(DatabaseError::ValidationError(
+                "Connection pool size must be at least 1".to_string(),
+            ));
+        }
+        let mut connections = Vec::with_capacity(config.size);
+        for _ in 0..config.size {
+            connections.push(spawn_connection(config.command_buffer).await?);
+        }
+        Ok(Self::from_connections(connections, config.acquire_timeout))
+    }
+    pub fn from_connections(connections: Vec<PooledConnection>, acquire_timeout: Duration) -> Self {
+        let size = connections.len();
+        Self {
+            idle: Arc::new(Mutex::new(connections)),
+            permits: Arc::new(Semaphore::new(size)),
+            acquire_timeout,
+            size,
+        }
+    }
+    pub async fn acquire(&self) -> Result<PoolGuard, DatabaseError> {
+        let waiting = self.permits.clone().acquire_owned();
+        let permit = tokio::time::timeout(self.acquire_timeout, waiting)
+            .await
+            .map_err(|_| DatabaseError::Timeout)?
+            .map_err(|_| DatabaseError::Disconnected)?;
+        let connection = self
+            .idle
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop()
+            .ok_or(DatabaseError::Disconnected)?;
+        Ok(PoolGuard {
+            connection: Some(connection),
+            idle: self.idle.clone(),
+            _permit: permit,
+        })
+    }
+    pub fn size(&self) -> usize {
+        self.size
+    }
+    pub fn available(&self) -> usize {
+        self.permits.available_permits()
+    }
+}
+pub struct PoolGuard {
+    connection: Option<PooledConnection>,
+    idle: Arc<Mutex<Vec<PooledConnection>>>,
+    _permit: OwnedSemaphorePermit,
+}
+impl Deref for PoolGuard {
+    type Target = PooledConnection;
+    fn deref(&self) -> &PooledConnection {
+        self.connection
+            .as_ref()
+            .expect("pooled connection is present until the guard drops")
+    }
+}
+impl Drop for PoolGuard {
+    fn drop(&mut self) {
+        if let Some(connection) = self.connection.take() {
+            self.idle
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(connection);
+        }
+    }
+}
+async fn spawn_connection(command_buffer: usize) -> Result<PooledConnection, DatabaseError> {
+    let (command_tx, command_rx) = mpsc::channel(command_buffer);
+    let (client_tx, mut client_rx) = mpsc::channel(1);
+    let mut connection = DatabaseConnection::new(command_rx);
+    tokio::spawn(async move {
+        if let Err(e) = connection.run().await {
+            error!(error = %e, "Pooled database connection stopped");
+        }
+    });
+    let (response_tx, response_rx) = oneshot::channel();
+    command_tx
+        .send(DatabaseCommand::Connect {
+            client_sender: client_tx,
+            response_sender: response_tx,
+        })
+        .await
+        .map_err(|_| DatabaseError::Disconnected)?;
+    response_rx.await.map_err(|_| DatabaseError::Disconnected)??;
+    let client = client_rx.recv().await.ok_or(DatabaseError::Disconnected)?;
+    Ok(PooledConnection {
+        client,
+        commands: command_tx,
+    })
+}
@@ -11,6 +11,7 @@
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
 pub mod connection;
+pub mod connection_pool;
 pub mod data_interpreter;
 pub mod data_processor;
 pub mod dynamic_access;
@@ -31,10 +32,12 @@ pub mod query_validator;
 pub mod regulariser;
 pub mod sanitize;
 pub mod schema_analyser;
+pub mod structured_query;
 pub mod structured_store;
 pub mod surreal_token;
 pub mod tokens;
 pub mod types;
 pub use connection::DatabaseConnection;
+pub use connection_pool::{ConnectionPool, PoolConfig, PoolGuard, PooledConnection};
 pub use surreal_token::{SurrealToken, SurrealTokenParser};
 pub use types::{DatabaseError, DatabaseMetrics, QueryResult};
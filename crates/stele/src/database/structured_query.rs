@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use crate::database::query_builder::{Operator, OrderDirection};
+use crate::database::query_metrics::record_query;
+use crate::database::sanitize::sanitize_table_name;
+use crate::database::types::{DatabaseError, QueryResult};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Instant;
+use surrealdb::{engine::remote::ws::Client, Surreal};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompiledQuery {
+    pub text: String,
+    pub params: BTreeMap<String, Value>,
+}
+
+#[derive(Clone)]
+pub struct StructuredSelect {
+    db: Arc<Surreal<Client>>,
+    table: String,
+    filters: Vec<(String, Operator, Value)>,
+    order: Vec<(String, OrderDirection)>,
+    limit: Option<usize>,
+}
+
+impl StructuredSelect {
+    pub(crate) fn new(db: Arc<Surreal<Client>>, table: &str) -> Self {
+        Self {
+            db,
+            table: table.to_string(),
+            filters: Vec::new(),
+            order: Vec::new(),
+            limit: None,
+        }
+    }
+
+    pub fn filter(mut self, field: &str, op: Operator, value: impl Into<Value>) -> Self {
+        self.filters.push((field.to_string(), op, value.into()));
+        self
+    }
+
+    pub fn order_by(mut self, field: &str, direction: OrderDirection) -> Self {
+        self.order.push((field.to_string(), direction));
+        self
+    }
+
+    pub fn limit(mut self, n: usize) -> Self {
+        self.limit = Some(n);
+        self
+    }
+
+    pub fn build(&self) -> Result<CompiledQuery, DatabaseError> {
+        let mut text = format!("SELECT * FROM {}", sanitize_table_name(&self.table));
+        let mut params = BTreeMap::new();
+
+        let mut conditions = Vec::with_capacity(self.filters.len());
+        for (index, (field, op, value)) in self.filters.iter().enumerate() {
+            if !is_comparison(op) {
+                return Err(DatabaseError::ValidationError(format!(
+                    "Operator {op} is not a comparison and cannot be used as a filter"
+                )));
+            }
+            let name = format!("p{index}");
+            conditions.push(format!("{} {op} ${name}", sanitize_field_path(field)));
+            params.insert(name, value.clone());
+        }
+        if !conditions.is_empty() {
+            text.push_str(" WHERE ");
+            text.push_str(&conditions.join(" AND "));
+        }
+
+        if !self.order.is_empty() {
+            let clauses: Vec<String> = self
+                .order
+                .iter()
+                .map(|(field, direction)| {
+                    let direction = match direction {
+                        OrderDirection::Asc => "ASC",
+                        OrderDirection::Desc => "DESC",
+                    };
+                    format!("{} {direction}", sanitize_field_path(field))
+                })
+                .collect();
+            text.push_str(" ORDER BY ");
+            text.push_str(&clauses.join(", "));
+        }
+
+        if let Some(limit) = self.limit {
+            text.push_str(" LIMIT $limit");
+            params.insert("limit".to_string(), Value::from(limit));
+        }
+
+        Ok(CompiledQuery { text, params })
+    }
+
+    pub async fn execute(self) -> Result<QueryResult<Value>, DatabaseError> {
+        let compiled = self.build()?;
+        let start = Instant::now();
+        let mut request = self.db.query(compiled.text.as_str());
+        for (name, value) in compiled.params {
+            request = request.bind((name, value));
+        }
+        let mut response = request
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Structured select failed: {e}")))?;
+        let data: Vec<Value> = response
+            .take(0)
+            .map_err(|e| DatabaseError::Query(format!("Structured select decode failed: {e}")))?;
+        let elapsed = start.elapsed();
+        record_query(&format!("SELECT {}", self.table), elapsed.as_millis());
+
+        let metadata = HashMap::from([
+            ("table".to_string(), self.table.clone()),
+            ("query".to_string(), compiled.text),
+        ]);
+        Ok(QueryResult {
+            data,
+            metadata,
+            execution_time: elapsed.as_secs_f64() * 1000.0,
+        })
+    }
+}
+
+fn is_comparison(op: &Operator) -> bool {
+    matches!(
+        op,
+        Operator::Equals
+            | Operator::NotEquals
+            | Operator::ExactEquals
+            | Operator::LessThan
+            | Operator::LessThanEquals
+            | Operator::GreaterThan
+            | Operator::GreaterThanEquals
+            | Operator::In
+            | Operator::NotIn
+            | Operator::Contains
+            | Operator::ContainsNot
+            | Operator::ContainsAll
+            | Operator::ContainsAny
+            | Operator::ContainsNone
+            | Operator::Inside
+            | Operator::NotInside
+    )
+}
+
+fn sanitize_field_path(field: &str) -> String {
+    field
+        .split('.')
+        .map(sanitize_table_name)
+        .collect::<Vec<_>>()
+        .join(".")
+}
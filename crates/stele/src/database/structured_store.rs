@@ -13,6 +13,7 @@
 
 
 use crate::database::query_metrics::record_query;
+use crate::database::structured_query::StructuredSelect;
 
 use crate::database::tokens::DateTimeToken;
 use crate::database::types::DatabaseError;
@@ -181,6 +182,10 @@ impl StructuredStore {
         self.canonical_db.as_ref()
     }
 
+    pub fn select(&self, table: &str) -> StructuredSelect {
+        StructuredSelect::new(self.canonical_db.clone(), table)
+    }
+
     fn trace(&self, label: &str, sql: &str) {
         if self.trace_queries {
             tracing::info!(target="stele::db::query", label, sql, canon_ns=?self.canon_ns, canon_db=?self.canon_db_name, dyn_ns=?self.dynamic_ns, dyn_db=?self.dynamic_db_name);
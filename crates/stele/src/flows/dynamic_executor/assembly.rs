@@ -10,13 +10,54 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
-use super::function::DynamicFunction;
+use super::function::{current_call_timeout, DynamicExecError, DynamicFunction};
 use crate::blocks::rules::BlockError;
 use serde_json::Value;
 use std::error::Error as StdError;
-use std::sync::Arc;
-use wasmtime::{Config, Engine, Func, Instance, Module, Store, Val};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::Duration;
+use wasmtime::{Config, Engine, Func, Instance, Module, Store, Trap, Val};
 pub const MAX_EXECUTION_CYCLES: u64 = 100_000_000; 
+pub const EPOCH_TICK: Duration = Duration::from_millis(10);
+const UNBOUNDED_EPOCH_DEADLINE: u64 = u64::MAX / 2;
+
+pub(crate) fn epoch_deadline() -> u64 {
+    match current_call_timeout() {
+        Some(limit) => (limit.as_millis() / EPOCH_TICK.as_millis()) as u64 + 1,
+        None => UNBOUNDED_EPOCH_DEADLINE,
+    }
+}
+
+pub(crate) fn shared_engine() -> Result<Engine, BlockError> {
+    static ENGINE: OnceLock<Result<Engine, String>> = OnceLock::new();
+    ENGINE
+        .get_or_init(|| {
+            let mut config = Config::new();
+            config.consume_fuel(true);
+            config.epoch_interruption(true);
+            let engine = Engine::new(&config).map_err(|e| e.to_string())?;
+            spawn_epoch_ticker(engine.clone());
+            Ok(engine)
+        })
+        .clone()
+        .map_err(|e| BlockError::ProcessingError(format!("Failed to create Wasmtime engine: {e}")))
+}
+
+fn spawn_epoch_ticker(engine: Engine) {
+    std::thread::Builder::new()
+        .name("wasm-epoch".into())
+        .spawn(move || loop {
+            engine.increment_epoch();
+            std::thread::sleep(EPOCH_TICK);
+        })
+        .ok();
+}
+
+pub(crate) fn epoch_timeout(error: &wasmtime::Error) -> Option<BlockError> {
+    let limit = current_call_timeout()?;
+    matches!(error.downcast_ref::<Trap>(), Some(Trap::Interrupt))
+        .then(|| DynamicExecError::Timeout { limit }.into())
+}
 
 fn fuel_limit() -> u64 {
     
@@ -31,15 +72,20 @@ fn fuel_limit() -> u64 {
 #[derive(Clone)]
 pub struct AssemblyGenerator {
     engine: Engine,
+    fuel: Arc<RwLock<Option<u64>>>,
 }
 impl AssemblyGenerator {
     pub fn new() -> Result<Self, BlockError> {
-        let mut config = Config::new();
-        config.consume_fuel(true);
-        let engine = Engine::new(&config).map_err(|e| {
-            BlockError::ProcessingError(format!("Failed to create Wasmtime engine: {e}"))
-        })?;
-        Ok(Self { engine })
+        Ok(Self {
+            engine: shared_engine()?,
+            fuel: Arc::new(RwLock::new(None)),
+        })
+    }
+    pub fn set_fuel_limit(&self, fuel: Option<u64>) {
+        *self.fuel.write().unwrap() = fuel;
+    }
+    pub fn fuel_limit(&self) -> u64 {
+        (*self.fuel.read().unwrap()).unwrap_or_else(fuel_limit)
     }
     pub fn compile_function(
         &self,
@@ -65,14 +111,16 @@ impl AssemblyGenerator {
             ))
         })?;
         let exported_fn_name_owned = exported_fn_name.to_string();
+        let fuel = Arc::clone(&self.fuel);
         let compiled_fn = Arc::new(move |args: &[Value]| -> Result<Value, BlockError> {
             
             let (numbers, _, _) = Self::marshal_args(args);
             let input_arg = numbers.first().cloned().unwrap_or(0.0);
             let mut store = Store::new(&engine, ());
-            store.set_fuel(fuel_limit()).map_err(|e| {
+            store.set_fuel((*fuel.read().unwrap()).unwrap_or_else(fuel_limit)).map_err(|e| {
                 BlockError::ProcessingError(format!("Failed to set Wasm fuel: {e}"))
             })?;
+            store.set_epoch_deadline(epoch_deadline());
             let instance = Instance::new(&mut store, &module, &[]).map_err(|e| {
                 BlockError::ProcessingError(format!("Failed to instantiate Wasm module: {e}"))
             })?;
@@ -134,11 +182,9 @@ impl AssemblyGenerator {
                 Err(e) => Self::map_wasm_err(e, &store),
             }
         });
-        Ok(DynamicFunction::new(
-            compiled_fn,
-            version,
-            source_code.to_string(),
-        ))
+        let mut function = DynamicFunction::new(compiled_fn, version, source_code.to_string());
+        function.interruptible = true;
+        Ok(function)
     }
 
     
@@ -187,7 +233,10 @@ impl AssemblyGenerator {
         out
     }
 
-    fn map_wasm_err<E: std::fmt::Display>(e: E, store: &Store<()>) -> Result<Value, BlockError> {
+    fn map_wasm_err(e: wasmtime::Error, store: &Store<()>) -> Result<Value, BlockError> {
+        if let Some(timeout) = epoch_timeout(&e) {
+            return Err(timeout);
+        }
         let error_msg = e.to_string();
         if error_msg.contains("all fuel consumed")
             || error_msg.contains("fuel")
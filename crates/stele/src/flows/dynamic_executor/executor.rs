@@ -15,16 +15,19 @@ use super::{
     hot_reload::HotReloadManager, import_export::ImportExportManager, metrics::PerformanceMetrics,
 };
 use crate::blocks::rules::BlockError;
+use crate::codegen::wat_validate::validate_wat;
 use crate::codegen::{guard_and_rewrite, rust_clean, wat_sanitize};
 use chrono::{DateTime, Utc};
 #[cfg(feature = "dynamic-native")]
 use libloading::Library;
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::fs;
 #[allow(unused_imports)]
 use std::path::PathBuf;
 use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 use tempfile::tempdir;
@@ -39,6 +42,9 @@ pub struct DynamicExecutor {
     version_history: VersionHistory,
     hot_reload_manager: Arc<RwLock<HotReloadManager>>,
     assembly_generator: AssemblyGenerator,
+    compiled_cache: Arc<RwLock<HashMap<String, DynamicFunction>>>,
+    cache_hits: Arc<AtomicU64>,
+    execution_timeout: Arc<RwLock<Option<Duration>>>,
     #[cfg(feature = "dynamic-native")]
     library_registry: Arc<RwLock<LibraryRegistry>>,
 }
@@ -82,6 +88,32 @@ pub enum DynamicSource<'a> {
     RustWasiFull { name: &'a str, source: &'a str },
 }
 
+impl<'a> DynamicSource<'a> {
+    fn parts(&self) -> (&'a str, &'a str, String) {
+        match *self {
+            DynamicSource::Wat { name, export, wat } => (name, wat, format!("wat:{export}")),
+            #[cfg(feature = "dynamic-native")]
+            DynamicSource::RustExpression { name, body } => (name, body, "native-expr".into()),
+            #[cfg(feature = "dynamic-native")]
+            DynamicSource::RustFull { name, source } => (name, source, "native-full".into()),
+            #[cfg(feature = "dynamic-wasi")]
+            DynamicSource::RustWasiExpression { name, body } => (name, body, "wasi-expr".into()),
+            #[cfg(feature = "dynamic-wasi")]
+            DynamicSource::RustWasiFull { name, source } => (name, source, "wasi-full".into()),
+        }
+    }
+
+    pub fn cache_key(&self) -> String {
+        let (name, source, target) = self.parts();
+        let mut hasher = Sha256::new();
+        for part in [name, source, target.as_str()] {
+            hasher.update((part.len() as u64).to_le_bytes());
+            hasher.update(part.as_bytes());
+        }
+        format!("{:x}", hasher.finalize())
+    }
+}
+
 impl DynamicExecutor {
     #[cfg(any(feature = "dynamic-native", feature = "dynamic-wasi"))]
     fn apply_pow_disambiguation(original: &str) -> String {
@@ -148,6 +180,37 @@ impl DynamicExecutor {
         &self,
         src: DynamicSource,
     ) -> Result<DynamicFunction, BlockError> {
+        let key = src.cache_key();
+        let cached = self.compiled_cache.read().unwrap().get(&key).cloned();
+        let mut function = match cached {
+            Some(function) => {
+                self.cache_hits.fetch_add(1, Ordering::Relaxed);
+                function
+            }
+            None => {
+                let compiled = self.compile_dynamic_source(src)?;
+                self.compiled_cache.write().unwrap().insert(key, compiled.clone());
+                compiled
+            }
+        };
+        function.set_execution_timeout(self.execution_timeout());
+        Ok(function)
+    }
+
+    pub fn clear_cache(&self) {
+        self.compiled_cache.write().unwrap().clear();
+        self.cache_hits.store(0, Ordering::Relaxed);
+    }
+
+    pub fn cache_hits(&self) -> u64 {
+        self.cache_hits.load(Ordering::Relaxed)
+    }
+
+    pub fn cached_function_count(&self) -> usize {
+        self.compiled_cache.read().unwrap().len()
+    }
+
+    fn compile_dynamic_source(&self, src: DynamicSource) -> Result<DynamicFunction, BlockError> {
         match src {
             DynamicSource::Wat {
                 name: _,
@@ -155,6 +218,7 @@ impl DynamicExecutor {
                 wat,
             } => {
                 let (cleaned, _metrics) = wat_sanitize::sanitize_wat_basic(wat);
+                validate_wat(&cleaned).map_err(BlockError::InvalidWat)?;
                 self.compile_function(&cleaned, export)
             }
             #[cfg(feature = "dynamic-native")]
@@ -434,6 +498,9 @@ impl DynamicExecutor {
             version_history: Arc::new(RwLock::new(HashMap::new())),
             hot_reload_manager: Arc::new(RwLock::new(hot_reload_manager)),
             assembly_generator,
+            compiled_cache: Arc::new(RwLock::new(HashMap::new())),
+            cache_hits: Arc::new(AtomicU64::new(0)),
+            execution_timeout: Arc::new(RwLock::new(None)),
             #[cfg(feature = "dynamic-native")]
             library_registry: Arc::new(RwLock::new(LibraryRegistry::new())),
         })
@@ -445,8 +512,23 @@ impl DynamicExecutor {
     ) -> Result<DynamicFunction, BlockError> {
         let wasm_bytes = wat::parse_str(wat_code)
             .map_err(|e| BlockError::ProcessingError(format!("Invalid WAT format: {e}")))?;
-        self.assembly_generator
-            .compile_function(&wasm_bytes, exported_fn_name, wat_code)
+        let mut function = self
+            .assembly_generator
+            .compile_function(&wasm_bytes, exported_fn_name, wat_code)?;
+        function.set_execution_timeout(self.execution_timeout());
+        Ok(function)
+    }
+    pub fn set_execution_timeout(&self, timeout: Option<Duration>) {
+        *self.execution_timeout.write().unwrap() = timeout;
+    }
+    pub fn execution_timeout(&self) -> Option<Duration> {
+        *self.execution_timeout.read().unwrap()
+    }
+    pub fn set_wasm_fuel(&self, fuel: Option<u64>) {
+        self.assembly_generator.set_fuel_limit(fuel);
+    }
+    pub fn wasm_fuel(&self) -> u64 {
+        self.assembly_generator.fuel_limit()
     }
     pub fn register_function(&self, name: String, function: DynamicFunction) {
         let mut version_history = self.version_history.write().unwrap();
@@ -776,6 +858,11 @@ impl DynamicExecutor {
             "total_compositions".to_string(),
             Value::Number(compositions.len().into()),
         );
+        stats.insert("cache_hits".to_string(), Value::Number(self.cache_hits().into()));
+        stats.insert(
+            "cached_functions".to_string(),
+            Value::Number(self.cached_function_count().into()),
+        );
         stats
     }
     pub fn get_overall_error_statistics(&self) -> (u64, u64, f64) {
@@ -983,10 +1070,7 @@ pub extern "C" fn compute(inputs_ptr:*const u8,len:usize,out_ptr:*mut u8,out_len
         let wasm_bytes = fs::read(&wasm_path)
             .map_err(|e| BlockError::ProcessingError(format!("read wasm: {e}")))?;
 
-        let mut config = wasmtime::Config::new();
-        config.consume_fuel(true);
-        let engine = wasmtime::Engine::new(&config)
-            .map_err(|e| BlockError::ProcessingError(format!("engine: {e}")))?;
+        let engine = super::assembly::shared_engine()?;
         let module = wasmtime::Module::new(&engine, &wasm_bytes)
             .map_err(|e| BlockError::ProcessingError(format!("compile wasm module: {e}")))?;
         let module_arc = std::sync::Arc::new(module);
@@ -999,6 +1083,7 @@ pub extern "C" fn compute(inputs_ptr:*const u8,len:usize,out_ptr:*mut u8,out_len
             store
                 .set_fuel(1_000_000)
                 .map_err(|e| BlockError::ProcessingError(format!("fuel: {e}")))?;
+            store.set_epoch_deadline(super::assembly::epoch_deadline());
             let mut linker = Linker::new(&engine_arc);
 
             linker
@@ -1099,7 +1184,10 @@ pub extern "C" fn compute(inputs_ptr:*const u8,len:usize,out_ptr:*mut u8,out_len
                 .copy_from_slice(&json);
             let code = typed
                 .call(&mut store, (input_ptr, input_len, output_ptr, output_len))
-                .map_err(|e| BlockError::ProcessingError(format!("call: {e}")))?;
+                .map_err(|e| {
+                    super::assembly::epoch_timeout(&e)
+                        .unwrap_or_else(|| BlockError::ProcessingError(format!("call: {e}")))
+                })?;
             if code != 0 {
                 return Err(BlockError::ProcessingError(format!(
                     "dyn fn error code {code}"
@@ -1117,11 +1205,13 @@ pub extern "C" fn compute(inputs_ptr:*const u8,len:usize,out_ptr:*mut u8,out_len
                 .map_err(|e| BlockError::ProcessingError(format!("json parse: {e}")))?;
             Ok(v)
         };
-        Ok(super::function::DynamicFunction::new(
+        let mut function = super::function::DynamicFunction::new(
             std::sync::Arc::new(closure),
             format!("v{}", chrono::Utc::now().timestamp()),
             src_owned,
-        ))
+        );
+        function.interruptible = true;
+        Ok(function)
     }
 }
 impl Default for DynamicExecutor {
@@ -14,12 +14,45 @@ use super::metrics::{FunctionMetrics, PerformanceMetrics};
 use crate::blocks::rules::BlockError;
 use chrono::{DateTime, Utc};
 use serde_json::Value;
+use std::cell::Cell;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, OnceLock};
 use std::time::{Duration, Instant};
 
 type CompiledFunction = Arc<dyn Fn(&[Value]) -> Result<Value, BlockError> + Send + Sync>;
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DynamicExecError {
+    #[error("dynamic function exceeded its {limit:?} execution timeout")]
+    Timeout { limit: Duration },
+}
+
+thread_local! {
+    static CALL_TIMEOUT: Cell<Option<Duration>> = const { Cell::new(None) };
+}
+
+pub(crate) fn current_call_timeout() -> Option<Duration> {
+    CALL_TIMEOUT.with(Cell::get)
+}
+
+const NATIVE_WORKERS: usize = 4;
+
+fn native_workers() -> Result<&'static rayon::ThreadPool, BlockError> {
+    static WORKERS: OnceLock<Option<rayon::ThreadPool>> = OnceLock::new();
+    WORKERS
+        .get_or_init(|| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(NATIVE_WORKERS)
+                .thread_name(|i| format!("dynamic-fn-{i}"))
+                .panic_handler(|_| {})
+                .build()
+                .ok()
+        })
+        .as_ref()
+        .ok_or_else(|| BlockError::ProcessingError("Failed to start dynamic fn workers".into()))
+}
+
 
 
 
@@ -42,6 +75,8 @@ pub struct DynamicFunction {
     pub dependencies: Vec<String>,
     pub source_path: Option<String>,
     pub source_code: String,
+    pub execution_timeout: Option<Duration>,
+    pub interruptible: bool,
 }
 impl DynamicFunction {
     pub fn new(compiled_fn: CompiledFunction, version: String, source_code: String) -> Self {
@@ -55,15 +90,30 @@ impl DynamicFunction {
             dependencies: Vec::new(),
             source_path: None,
             source_code,
+            execution_timeout: None,
+            interruptible: false,
         }
     }
     pub fn execute(&self, args: &[Value]) -> Result<Value, BlockError> {
         
         let start = Instant::now();
-        let result = self.execute_inner(args);
+        let result = match self.execution_timeout {
+            Some(limit) if self.interruptible => self.execute_interruptible(args, limit),
+            Some(limit) => self.execute_on_worker(args, limit),
+            None => self.execute_inner(args),
+        };
         self.record_metrics(start, result.is_ok());
         result
     }
+    /// Wasm functions are interrupted in place once the timeout passes. Native
+    /// functions cannot be pre-empted: they run on a small shared worker pool,
+    /// and a call that times out keeps its worker busy until it returns.
+    pub fn set_execution_timeout(&mut self, timeout: Option<Duration>) {
+        self.execution_timeout = timeout;
+    }
+    pub fn get_timeout_count(&self) -> u64 {
+        self.performance_metrics.snapshot().timeout_count
+    }
     pub async fn execute_with_timeout(
         &self,
         args: &[Value],
@@ -169,6 +219,8 @@ impl DynamicFunction {
             dependencies: self.dependencies.clone(),
             source_path: self.source_path.clone(),
             source_code: self.source_code.clone(),
+            execution_timeout: self.execution_timeout,
+            interruptible: self.interruptible,
         }
     }
     pub fn get_error_statistics(&self) -> Option<(u64, u64, f64)> {
@@ -200,6 +252,36 @@ impl DynamicFunction {
     fn execute_inner(&self, args: &[Value]) -> Result<Value, BlockError> {
         (self.compiled_fn)(args)
     }
+    fn execute_interruptible(&self, args: &[Value], limit: Duration) -> Result<Value, BlockError> {
+        let previous = CALL_TIMEOUT.with(|timeout| timeout.replace(Some(limit)));
+        let result = self.execute_inner(args);
+        CALL_TIMEOUT.with(|timeout| timeout.set(previous));
+        if matches!(
+            result,
+            Err(BlockError::DynamicExec(DynamicExecError::Timeout { .. }))
+        ) {
+            self.performance_metrics.record_timeout();
+        }
+        result
+    }
+    fn execute_on_worker(&self, args: &[Value], limit: Duration) -> Result<Value, BlockError> {
+        let f = self.compiled_fn.clone();
+        let args = args.to_vec();
+        let (sender, receiver) = mpsc::sync_channel(1);
+        native_workers()?.spawn(move || {
+            let _ = sender.send(f(&args));
+        });
+        match receiver.recv_timeout(limit) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => {
+                self.performance_metrics.record_timeout();
+                Err(DynamicExecError::Timeout { limit }.into())
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                Err(BlockError::ProcessingError("Task panicked".to_string()))
+            }
+        }
+    }
     async fn invoke_and_record(
         &self,
         args: &[Value],
@@ -210,13 +292,21 @@ impl DynamicFunction {
             let f = self.compiled_fn.clone();
             
             let args_arc: Arc<Vec<Value>> = Arc::new(args.to_vec());
-            let task = tokio::task::spawn_blocking(move || (f)(&args_arc));
+            let task = tokio::task::spawn_blocking(move || {
+                CALL_TIMEOUT.with(|timeout| timeout.set(Some(t)));
+                let result = (f)(&args_arc);
+                CALL_TIMEOUT.with(|timeout| timeout.set(None));
+                result
+            });
             match tokio::time::timeout(t, task).await {
                 Ok(Ok(res)) => res,
                 Ok(Err(_)) => Err(BlockError::ProcessingError("Task panicked".to_string())),
-                Err(_) => Err(BlockError::ProcessingError(
-                    "Function execution timeout".into(),
-                )),
+                Err(_) => {
+                    self.performance_metrics.record_timeout();
+                    Err(BlockError::ProcessingError(
+                        "Function execution timeout".into(),
+                    ))
+                }
             }
         } else {
             self.execute_inner(args)
@@ -24,6 +24,7 @@ pub struct PerformanceMetrics {
     pub peak_memory_usage: usize,
     pub last_executed: DateTime<Utc>,
     pub error_count: u64,
+    pub timeout_count: u64,
     pub success_rate: f64,
 }
 
@@ -32,6 +33,7 @@ pub struct PerformanceMetrics {
 pub struct FunctionMetrics {
     total_calls: AtomicU64,
     error_count: AtomicU64,
+    timeout_count: AtomicU64,
     avg_execution_time_ns: AtomicU64,
     last_executed_ms: AtomicU64,
     peak_memory_usage: AtomicUsize,
@@ -42,6 +44,7 @@ impl Default for FunctionMetrics {
         Self {
             total_calls: AtomicU64::new(0),
             error_count: AtomicU64::new(0),
+            timeout_count: AtomicU64::new(0),
             avg_execution_time_ns: AtomicU64::new(0),
             last_executed_ms: AtomicU64::new(0),
             peak_memory_usage: AtomicUsize::new(0),
@@ -75,6 +78,9 @@ impl FunctionMetrics {
         let now_ms = Utc::now().timestamp_millis() as u64;
         self.last_executed_ms.store(now_ms, Ordering::Relaxed);
     }
+    pub fn record_timeout(&self) {
+        self.timeout_count.fetch_add(1, Ordering::Relaxed);
+    }
     pub fn record_memory_usage(&self, memory_usage: usize) {
         
         let mut current = self.peak_memory_usage.load(Ordering::Relaxed);
@@ -93,6 +99,7 @@ impl FunctionMetrics {
     pub fn reset(&self) {
         self.total_calls.store(0, Ordering::Relaxed);
         self.error_count.store(0, Ordering::Relaxed);
+        self.timeout_count.store(0, Ordering::Relaxed);
         self.avg_execution_time_ns.store(0, Ordering::Relaxed);
         self.last_executed_ms.store(0, Ordering::Relaxed);
         self.peak_memory_usage.store(0, Ordering::Relaxed);
@@ -100,6 +107,7 @@ impl FunctionMetrics {
     pub fn snapshot(&self) -> PerformanceMetrics {
         let total_calls = self.total_calls.load(Ordering::Relaxed);
         let error_count = self.error_count.load(Ordering::Relaxed);
+        let timeout_count = self.timeout_count.load(Ordering::Relaxed);
         let avg_ns = self.avg_execution_time_ns.load(Ordering::Relaxed);
         let last_ms = self.last_executed_ms.load(Ordering::Relaxed);
         let peak = self.peak_memory_usage.load(Ordering::Relaxed);
@@ -122,6 +130,7 @@ impl FunctionMetrics {
             peak_memory_usage: peak,
             last_executed,
             error_count,
+            timeout_count,
             success_rate,
         }
     }
@@ -164,6 +173,10 @@ impl PerformanceMetrics {
             "error_count".to_string(),
             Value::Number(self.error_count.into()),
         );
+        stats.insert(
+            "timeout_count".to_string(),
+            Value::Number(self.timeout_count.into()),
+        );
         stats.insert(
             "success_rate".to_string(),
             serde_json::Number::from_f64(self.success_rate)
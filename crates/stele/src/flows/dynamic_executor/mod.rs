@@ -18,10 +18,11 @@ pub mod hot_reload;
 pub mod import_export;
 pub mod metrics;
 pub mod strategy;
+pub use crate::codegen::wat_validate::{validate_wat, WatError};
 pub use assembly::*;
 pub use dependency::DependencyManager;
 pub use executor::{DynamicExecutor, DynamicSource};
-pub use function::DynamicFunction;
+pub use function::{DynamicExecError, DynamicFunction};
 pub use hot_reload::HotReloadManager;
 pub use import_export::ImportExportManager;
 pub use metrics::PerformanceMetrics;
@@ -12,9 +12,11 @@
 
 
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
 use std::cell::UnsafeCell;
 use std::collections::HashMap;
+use std::ops::ControlFlow;
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
@@ -781,6 +783,298 @@ fn execute_sparse(plan: &StrategyPlan, eval: Arc<dyn EvalFn>) -> Result<Strategy
 }
 
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StrategyCheckpoint {
+    pub range_start: u64,
+    pub range_end: u64,
+    pub completed: Vec<(u64, u64)>,
+    pub processed: u64,
+    pub best_n: u64,
+    pub best_score: u32,
+    pub top: Option<Vec<TopEntry>>,
+    pub pareto: Option<Vec<ParetoEntry>>,
+}
+
+impl StrategyCheckpoint {
+    pub fn new(plan: &StrategyPlan) -> Self {
+        Self {
+            range_start: plan.range_start,
+            range_end: plan.range_end,
+            completed: Vec::new(),
+            processed: 0,
+            best_n: 1,
+            best_score: 1,
+            top: plan.top_k.map(|_| Vec::new()),
+            pareto: None,
+        }
+    }
+
+    pub fn remaining(&self, plan: &StrategyPlan) -> Vec<(u64, u64)> {
+        let mut out = Vec::new();
+        let mut cursor = first_candidate(plan);
+        let end = plan.range_end;
+        for &(start, stop) in &self.completed {
+            if cursor > end {
+                return out;
+            }
+            if stop < cursor {
+                continue;
+            }
+            if start > end {
+                break;
+            }
+            if start > cursor {
+                out.push((cursor, start - 1));
+            }
+            match stop.checked_add(1) {
+                Some(next) => cursor = cursor.max(next),
+                None => return out,
+            }
+        }
+        if cursor <= end {
+            out.push((cursor, end));
+        }
+        out
+    }
+
+    pub fn is_complete(&self, plan: &StrategyPlan) -> bool {
+        self.remaining(plan).is_empty()
+    }
+
+    fn absorb(&mut self, plan: &StrategyPlan, range: (u64, u64), outcome: SegmentOutcome) {
+        if outcome.best_score > self.best_score
+            || (outcome.best_score == self.best_score && outcome.best_n < self.best_n)
+        {
+            self.best_score = outcome.best_score;
+            self.best_n = outcome.best_n;
+        }
+        if let (Some(k), Some(tv)) = (plan.top_k, self.top.as_mut()) {
+            tv.extend(outcome.top);
+            sort_top(tv);
+            tv.truncate(k);
+        }
+        if let Some(front) = outcome.pareto {
+            let pf = self.pareto.get_or_insert_with(Vec::new);
+            for (n, score, aux) in front {
+                update_pareto(pf, n, score, aux);
+            }
+            pf.sort_by_key(|entry| entry.0);
+        }
+        self.processed += outcome.processed;
+        self.completed.push(range);
+        self.completed.sort_unstable();
+        let mut merged: Vec<(u64, u64)> = Vec::with_capacity(self.completed.len());
+        for &(start, stop) in &self.completed {
+            match merged.last_mut() {
+                Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(stop),
+                _ => merged.push((start, stop)),
+            }
+        }
+        self.completed = merged;
+    }
+
+    fn into_result(self) -> StrategyResult {
+        StrategyResult {
+            best_n: self.best_n,
+            best_score: self.best_score,
+            top: self.top,
+            pareto: self.pareto,
+        }
+    }
+}
+
+struct SegmentOutcome {
+    best_n: u64,
+    best_score: u32,
+    top: Vec<TopEntry>,
+    pareto: Option<Vec<ParetoEntry>>,
+    processed: u64,
+}
+
+fn first_candidate(plan: &StrategyPlan) -> u64 {
+    if plan.odd_only {
+        let s = plan.range_start.max(3);
+        if s % 2 == 0 {
+            s + 1
+        } else {
+            s
+        }
+    } else {
+        plan.range_start.max(2)
+    }
+}
+
+fn sort_top(entries: &mut [TopEntry]) {
+    entries.sort_by(|a, b| {
+        b.3.partial_cmp(&a.3)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| b.1.cmp(&a.1))
+            .then_with(|| b.0.cmp(&a.0))
+    });
+}
+
+fn split_segments(ranges: &[(u64, u64)], chunk: u64) -> Vec<(u64, u64)> {
+    let mut segments = Vec::new();
+    for &(start, stop) in ranges {
+        let mut lo = start;
+        loop {
+            let hi = lo.saturating_add(chunk - 1).min(stop);
+            segments.push((lo, hi));
+            if hi >= stop {
+                break;
+            }
+            lo = hi + 1;
+        }
+    }
+    segments
+}
+
+fn scan_segment(
+    plan: &StrategyPlan,
+    eval: &dyn EvalFn,
+    memo: &dyn MemoBackend,
+    compiled_expr: Option<&[ExprToken]>,
+    (start, stop): (u64, u64),
+) -> SegmentOutcome {
+    let step = if plan.odd_only { 2 } else { 1 };
+    let first = if plan.odd_only && start % 2 == 0 {
+        start + 1
+    } else {
+        start
+    };
+    let mut out = SegmentOutcome {
+        best_n: 1,
+        best_score: 1,
+        top: Vec::new(),
+        pareto: None,
+        processed: 0,
+    };
+    if first > stop {
+        return out;
+    }
+    for n in (first..=stop).step_by(step) {
+        let EvalOutcome { score, path, aux } = eval.eval(n, memo);
+        if plan.min_score.is_some_and(|ms| score < ms) {
+            continue;
+        }
+        if plan.min_aux.is_some_and(|ma| aux.unwrap_or(0) < ma) {
+            continue;
+        }
+        if score > out.best_score {
+            out.best_score = score;
+            out.best_n = n;
+        }
+        if aux.is_some() {
+            update_pareto(out.pareto.get_or_insert_with(Vec::new), n, score, aux);
+        }
+        if let Some(k) = plan.top_k {
+            let order_score = compiled_expr
+                .and_then(|rpn| eval_expr(rpn, score, aux))
+                .unwrap_or(score as f64);
+            out.top.push((n, score, aux, order_score));
+            if out.top.len() > k * 6 {
+                sort_top(&mut out.top);
+                out.top.truncate(k);
+            }
+        }
+        memo.insert_path(&path);
+        out.processed += 1;
+    }
+    if let Some(k) = plan.top_k {
+        sort_top(&mut out.top);
+        out.top.truncate(k);
+    }
+    out
+}
+
+
+pub fn execute_with_checkpoints(
+    plan: &StrategyPlan,
+    eval: &Arc<dyn EvalFn>,
+    on_checkpoint: &mut dyn FnMut(&StrategyCheckpoint) -> ControlFlow<()>,
+) -> Result<StrategyResult> {
+    execute_resume_with_checkpoints(plan, eval, StrategyCheckpoint::new(plan), on_checkpoint)
+}
+
+
+pub fn execute_resume(
+    plan: &StrategyPlan,
+    eval: &Arc<dyn EvalFn>,
+    checkpoint: StrategyCheckpoint,
+) -> Result<StrategyResult> {
+    execute_resume_with_checkpoints(plan, eval, checkpoint, &mut |_| ControlFlow::Continue(()))
+}
+
+
+pub fn execute_resume_with_checkpoints(
+    plan: &StrategyPlan,
+    eval: &Arc<dyn EvalFn>,
+    checkpoint: StrategyCheckpoint,
+    on_checkpoint: &mut dyn FnMut(&StrategyCheckpoint) -> ControlFlow<()>,
+) -> Result<StrategyResult> {
+    if plan.early_stop_no_improve.is_some() || plan.upper_bound.is_some() {
+        bail!("resumable scans do not support early stopping or upper-bound pruning");
+    }
+    if checkpoint.range_start != plan.range_start || checkpoint.range_end != plan.range_end {
+        bail!(
+            "checkpoint covers {}..={} but plan covers {}..={}",
+            checkpoint.range_start,
+            checkpoint.range_end,
+            plan.range_start,
+            plan.range_end
+        );
+    }
+    let t_start = Instant::now();
+    let segments = split_segments(&checkpoint.remaining(plan), plan.chunk.max(1));
+    let resumed_from = checkpoint.processed;
+    let mut state = checkpoint;
+    let memo = ShardedHashMemo::new(plan.shards.max(1).next_power_of_two());
+    let compiled_expr = plan.custom_score_expr.as_deref().and_then(compile_expr);
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(8)
+        .min(64);
+    for batch in segments.chunks(threads) {
+        let outcomes = std::thread::scope(|scope| {
+            let handles: Vec<_> = batch
+                .iter()
+                .map(|&range| {
+                    let memo = &memo;
+                    let compiled = compiled_expr.as_deref();
+                    scope.spawn(move || scan_segment(plan, eval.as_ref(), memo, compiled, range))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join())
+                .collect::<std::thread::Result<Vec<_>>>()
+        })
+        .map_err(|_| anyhow!("strategy segment worker panicked"))?;
+        for (&range, outcome) in batch.iter().zip(outcomes) {
+            state.absorb(plan, range, outcome);
+        }
+        if on_checkpoint(&state).is_break() {
+            bail!(
+                "strategy scan interrupted after {} candidates; resume from the last checkpoint",
+                state.processed
+            );
+        }
+    }
+    let elapsed = t_start.elapsed().as_secs_f64();
+    if elapsed > 0.0 {
+        println!(
+            "EXECUTION_STATS mode=RESUMABLE processed={} resumed_from={} elapsed_sec={:.3} best_n={} best_score={}",
+            state.processed,
+            resumed_from,
+            elapsed,
+            state.best_n,
+            state.best_score
+        );
+    }
+    Ok(state.into_result())
+}
+
+
 pub struct PlaceholderEval;
 impl EvalFn for PlaceholderEval {
     fn eval(&self, _n: u64, _memo: &dyn MemoBackend) -> EvalOutcome {
@@ -821,4 +1115,44 @@ mod tests {
         let r = execute(&plan, &eval).unwrap();
         assert_eq!(r.best_n, 1_000_000);
     }
+    struct WaveEval;
+    impl EvalFn for WaveEval {
+        fn eval(&self, n: u64, _: &dyn MemoBackend) -> EvalOutcome {
+            EvalOutcome::new((n % 97) as u32 + 1, Vec::new(), Some(n % 13))
+        }
+    }
+    #[test]
+    fn resumed_scan_matches_uninterrupted() {
+        let plan = StrategyPlan {
+            range_end: 20_000,
+            prefer_dense_cutoff: 50_000,
+            chunk: 100,
+            top_k: Some(6),
+            ..Default::default()
+        };
+        let eval: Arc<dyn EvalFn> = Arc::new(WaveEval);
+        let fresh = execute(&plan, &eval).unwrap();
+
+        let mut saved = None;
+        let mut seen = 0;
+        let interrupted = execute_with_checkpoints(&plan, &eval, &mut |ck| {
+            saved = Some(ck.clone());
+            seen += 1;
+            if seen == 2 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert!(interrupted.is_err());
+        let checkpoint = saved.unwrap();
+        assert!(!checkpoint.completed.is_empty());
+        assert!(!checkpoint.is_complete(&plan));
+
+        let resumed = execute_resume(&plan, &eval, checkpoint).unwrap();
+        assert_eq!(resumed.best_n, fresh.best_n);
+        assert_eq!(resumed.best_score, fresh.best_score);
+        assert_eq!(resumed.top, fresh.top);
+        assert_eq!(resumed.pareto, fresh.pareto);
+    }
 }
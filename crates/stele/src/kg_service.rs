@@ -11,7 +11,10 @@
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
 
+use crate::database::types::DatabaseError;
+use crate::provenance::ProvenanceTag;
 use crate::scribes::specialists::knowledge_scribe::KnowledgeScribe;
+use chrono::{DateTime, Utc};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -60,15 +63,50 @@ fn skip_zero(v: &usize) -> bool {
 #[derive(Debug, Default, Serialize)]
 pub struct KgIngestSummary {
     pub accepted: usize,
-    pub persisted: usize,
+    pub inserted: usize,
+    pub updated: usize,
+    pub deduped: usize,
+    #[serde(skip_serializing_if = "skip_zero")]
+    pub superseded: usize,
     pub skipped_invalid: usize,
-    pub skipped_duplicate: usize,
     #[serde(skip_serializing_if = "skip_zero")]
     pub provenance_links: usize,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub errors: Vec<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub accepted_facts: Vec<KgFact>, 
+    pub accepted_facts: Vec<KgFact>,
+}
+
+impl KgFact {
+    pub fn normalised_key(&self) -> String {
+        let object = match &self.object {
+            serde_json::Value::String(s) => s
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_lowercase(),
+            other => other.to_string(),
+        };
+        serde_json::json!([
+            self.subject.trim().to_lowercase(),
+            self.predicate.trim().to_lowercase(),
+            object,
+        ])
+        .to_string()
+    }
+    pub fn slot_key(&self) -> String {
+        serde_json::json!([
+            self.subject.trim().to_lowercase(),
+            self.predicate.trim().to_lowercase(),
+        ])
+        .to_string()
+    }
+}
+
+enum FactWrite {
+    Inserted(String),
+    Updated(String),
+    Unchanged,
 }
 
 impl KgService {
@@ -87,12 +125,31 @@ impl KgService {
     pub async fn ingest_facts(
         &self,
         facts: Vec<KgFact>,
-        provenance_sources: Option<&[String]>,
-    ) -> KgIngestSummary {
+        source: ProvenanceTag,
+    ) -> Result<KgIngestSummary, DatabaseError> {
+        self.ingest(facts, source, false).await
+    }
+    pub async fn update_facts(
+        &self,
+        facts: Vec<KgFact>,
+        source: ProvenanceTag,
+    ) -> Result<KgIngestSummary, DatabaseError> {
+        self.ingest(facts, source, true).await
+    }
+    async fn ingest(
+        &self,
+        facts: Vec<KgFact>,
+        source: ProvenanceTag,
+        supersede: bool,
+    ) -> Result<KgIngestSummary, DatabaseError> {
+        if source.source.trim().is_empty() {
+            return Err(DatabaseError::ValidationError(
+                "provenance source must not be empty".into(),
+            ));
+        }
         let mut summary = KgIngestSummary::default();
-        let provenance_vec: Option<Vec<String>> = provenance_sources.map(|s| s.to_vec());
-        let mut unique: HashSet<(String, String, String)> = HashSet::new();
-        let mut staged: Vec<KgFact> = Vec::new();
+        let mut unique: HashSet<String> = HashSet::new();
+        let mut staged: Vec<(String, KgFact)> = Vec::new();
         for f in facts.into_iter() {
             if let Err(e) = self.validate(&f) {
                 summary.skipped_invalid += 1;
@@ -101,13 +158,9 @@ impl KgService {
                     .push(format!("{}:{} -> {}", f.subject, f.predicate, e));
                 continue;
             }
-            let obj_key = if f.object.is_string() {
-                f.object.as_str().unwrap().to_string()
-            } else {
-                f.object.to_string()
-            };
-            if !unique.insert((f.subject.clone(), f.predicate.clone(), obj_key)) {
-                summary.skipped_duplicate += 1;
+            let key = f.normalised_key();
+            if !unique.insert(key.clone()) {
+                summary.deduped += 1;
                 continue;
             }
             if let Some(ks) = &self.knowledge {
@@ -124,29 +177,45 @@ impl KgService {
                 }
             }
             summary.accepted_facts.push(f.clone());
-            staged.push(f);
+            staged.push((key, f));
         }
         summary.accepted = staged.len();
         if let Some(db) = &self.db {
-            for f in staged.into_iter() {
-                if let Ok(mut res) = db.clone().query("CREATE edge SET subject=$s, predicate=$p, object=$o, created_at=time::now();").bind(("s", f.subject)).bind(("p", f.predicate)).bind(("o", f.object)).await {
-                    let created: Vec<serde_json::Value> = res.take(0).unwrap_or_default();
-                    summary.persisted += 1;
-                    if let Some(first) = created.first() {
-                        if let Some(edge_id_str) = first.get("id").and_then(|v| v.as_str()) {
-                            if let Some(srcs) = provenance_vec.as_ref() {
-                                let edge_id = edge_id_str.to_string();
-                                for u in srcs.iter() {
-                                    let utter = u.clone();
-                                    if db.clone().query("CREATE kg_fact_provenance SET edge=$e, utterance=$u, created_at=time::now();").bind(("e", edge_id.clone())).bind(("u", utter)).await.is_ok() { summary.provenance_links += 1; }
-                                }
-                            }
-                        }
+            let provenance = source.to_spec().to_value();
+            let now = Utc::now().to_rfc3339();
+            for (key, f) in staged.into_iter() {
+                if supersede {
+                    summary.superseded += close_slot(db, &f, &key, &now).await?;
+                }
+                let edge_id = match write_fact(db, key, f, &source, &provenance, &now).await? {
+                    FactWrite::Inserted(id) => {
+                        summary.inserted += 1;
+                        id
+                    }
+                    FactWrite::Updated(id) => {
+                        summary.updated += 1;
+                        id
+                    }
+                    FactWrite::Unchanged => {
+                        summary.deduped += 1;
+                        continue;
+                    }
+                };
+                for u in source.utterance_ids.iter() {
+                    if db
+                        .query("CREATE kg_fact_provenance SET edge=$e, utterance=$u, source=$src, created_at=time::now();")
+                        .bind(("e", edge_id.clone()))
+                        .bind(("u", u.clone()))
+                        .bind(("src", source.source.clone()))
+                        .await
+                        .is_ok()
+                    {
+                        summary.provenance_links += 1;
                     }
                 }
             }
         }
-        summary
+        Ok(summary)
     }
 
     pub async fn query(&self, filter: KgQueryFilter) -> KgQueryResult {
@@ -162,7 +231,19 @@ impl KgService {
             if filter.object.is_some() {
                 conditions.push("string(object) = $object");
             }
-            let mut sql = String::from("SELECT subject, predicate, object, created_at FROM edge");
+            if filter.source.is_some() {
+                conditions.push("$source INSIDE sources");
+            }
+            if filter.as_of.is_some() {
+                conditions.push("(valid_from IS NONE OR valid_from <= <datetime>$as_of)");
+                conditions.push("(valid_to IS NONE OR valid_to > <datetime>$as_of)");
+            } else {
+                conditions.push("valid_to IS NONE");
+            }
+            let mut sql = String::from(
+                "SELECT subject, predicate, object, sources, valid_from, valid_to, created_at \
+                 FROM edge",
+            );
             if !conditions.is_empty() {
                 sql.push_str(" WHERE ");
                 sql.push_str(&conditions.join(" AND "));
@@ -176,6 +257,8 @@ impl KgService {
                 .bind(("subject", filter.subject.clone().unwrap_or_default()))
                 .bind(("predicate", filter.predicate.clone().unwrap_or_default()))
                 .bind(("object", filter.object.clone().unwrap_or_default()))
+                .bind(("source", filter.source.clone().unwrap_or_default()))
+                .bind(("as_of", filter.as_of.map(|t| t.to_rfc3339()).unwrap_or_default()))
                 .await
             {
                 rows = q.take(0).unwrap_or_default();
@@ -207,7 +290,7 @@ impl KgService {
     }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default)]
 pub struct KgQueryFilter {
     #[serde(default)]
     pub subject: Option<String>,
@@ -216,6 +299,10 @@ pub struct KgQueryFilter {
     #[serde(default)]
     pub object: Option<String>,
     #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub as_of: Option<DateTime<Utc>>,
+    #[serde(default)]
     pub limit: Option<u32>,
 }
 
@@ -225,3 +312,76 @@ pub struct KgQueryResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub memory: Option<serde_json::Value>,
 }
+
+async fn write_fact(
+    db: &Db,
+    key: String,
+    fact: KgFact,
+    tag: &ProvenanceTag,
+    provenance: &serde_json::Value,
+    now: &str,
+) -> Result<FactWrite, DatabaseError> {
+    let query_err = |e: surrealdb::Error| DatabaseError::QueryFailed(format!("kg ingest: {e}"));
+    let mut existing = db
+        .query("SELECT VALUE sources FROM edge WHERE fact_key = $k AND valid_to IS NONE LIMIT 1;")
+        .bind(("k", key.clone()))
+        .await
+        .map_err(query_err)?;
+    let sources: Vec<Option<Vec<String>>> = existing.take(0).map_err(query_err)?;
+    let (sql, inserted) = match sources.first() {
+        None => (
+            "CREATE edge SET subject=$s, predicate=$p, object=$o, fact_key=$k, slot_key=$slot, \
+             sources=[$src], provenance=[<object>$prov], valid_from=<datetime>$now, \
+             created_at=time::now() RETURN VALUE <string>id;",
+            true,
+        ),
+        Some(known) if known.iter().flatten().any(|s| s == &tag.source) => {
+            return Ok(FactWrite::Unchanged)
+        }
+        Some(_) => (
+            "UPDATE edge SET sources=array::union(sources ?? [], [$src]), \
+             provenance=array::append(provenance ?? [], <object>$prov), updated_at=time::now() \
+             WHERE fact_key = $k AND valid_to IS NONE RETURN VALUE <string>id;",
+            false,
+        ),
+    };
+    let slot = fact.slot_key();
+    let mut res = db
+        .query(sql)
+        .bind(("slot", slot))
+        .bind(("now", now.to_string()))
+        .bind(("s", fact.subject))
+        .bind(("p", fact.predicate))
+        .bind(("o", fact.object))
+        .bind(("k", key))
+        .bind(("src", tag.source.clone()))
+        .bind(("prov", provenance.clone()))
+        .await
+        .map_err(query_err)?;
+    let ids: Vec<String> = res.take(0).map_err(query_err)?;
+    let id = ids
+        .into_iter()
+        .next()
+        .ok_or_else(|| DatabaseError::QueryFailed("kg ingest: write returned no edge id".into()))?;
+    Ok(if inserted {
+        FactWrite::Inserted(id)
+    } else {
+        FactWrite::Updated(id)
+    })
+}
+
+async fn close_slot(db: &Db, fact: &KgFact, key: &str, now: &str) -> Result<usize, DatabaseError> {
+    let query_err = |e: surrealdb::Error| DatabaseError::QueryFailed(format!("kg update: {e}"));
+    let mut res = db
+        .query(
+            "UPDATE edge SET valid_to=<datetime>$now WHERE slot_key = $slot AND fact_key != $k \
+             AND valid_to IS NONE RETURN VALUE <string>id;",
+        )
+        .bind(("now", now.to_string()))
+        .bind(("slot", fact.slot_key()))
+        .bind(("k", key.to_string()))
+        .await
+        .map_err(query_err)?;
+    let closed: Vec<String> = res.take(0).map_err(query_err)?;
+    Ok(closed.len())
+}
@@ -238,6 +238,15 @@ impl UnifiedLLMAdapter {
 
         selection_request = selection_request.with_available_providers(available_providers);
 
+        if let Some(model) = request
+            .context
+            .as_ref()
+            .and_then(|context| context.metadata.get("model"))
+            .and_then(Value::as_str)
+        {
+            selection_request = selection_request.bypass_model(model);
+        }
+
         let selected_model = self
             .model_selector
             .select_model(&selection_request)
@@ -619,3 +628,72 @@ impl crate::nlu::llm_processor::LLMAdapter for UnifiedLLMAdapter {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_contracts::{ModelRequirements, RequestContext};
+
+    fn adapter() -> UnifiedLLMAdapter {
+        let selector = DynamicModelSelector::from_config_path("src/nlu/config/llm_models.yml")
+            .expect("bundled model configuration loads");
+        UnifiedLLMAdapter {
+            model_selector: Arc::new(selector),
+            client_pool: Arc::new(RwLock::new(ClientPool {
+                anthropic_clients: Vec::new(),
+                openai_clients: Vec::new(),
+                ollama_clients: Vec::new(),
+                anthropic_index: 0,
+                openai_index: 0,
+                ollama_index: 0,
+            })),
+            preferred_provider: Some("ollama".to_string()),
+            preferred_model: Some("llama3.2:3b".to_string()),
+        }
+    }
+
+    fn request(model: Option<&str>) -> LLMRequest {
+        let metadata = model
+            .map(|model| HashMap::from([("model".to_string(), Value::from(model))]))
+            .unwrap_or_default();
+        LLMRequest {
+            id: Uuid::new_v4(),
+            prompt: "hello".to_string(),
+            system_prompt: None,
+            model_requirements: ModelRequirements {
+                capabilities: vec!["reasoning".to_string()],
+                preferred_speed_tier: None,
+                max_cost_tier: None,
+                min_max_tokens: None,
+            },
+            generation_config: GenerationConfig::default(),
+            context: Some(RequestContext {
+                conversation_id: None,
+                user_id: None,
+                session_id: None,
+                metadata,
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn model_override_in_request_metadata_selects_that_model() {
+        let selection = adapter()
+            .select_model_for_request(&request(Some("claude-3-5-haiku-latest")))
+            .await
+            .unwrap();
+
+        assert_eq!(selection.model.name, "claude-3-5-haiku-latest");
+        assert_eq!(selection.model.provider, "anthropic");
+    }
+
+    #[tokio::test]
+    async fn unknown_model_override_is_rejected() {
+        let error = adapter()
+            .select_model_for_request(&request(Some("no-such-model")))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, LLMError::ModelNotFound(_)));
+    }
+}
@@ -42,6 +42,10 @@ pub struct LLMProcessor {
 }
 static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| Client::builder().build().expect("HTTP client"));
 
+pub(crate) fn warm_http_client() {
+    Lazy::force(&HTTP_CLIENT);
+}
+
 impl LLMProcessor {
     pub fn new(adapter: Box<dyn LLMAdapter>, config: ConversationConfig) -> Self {
         Self {
@@ -11,8 +11,8 @@
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
 use serde::Deserialize;
-use std::collections::HashMap;
-#[derive(Debug, Clone, Deserialize)]
+use std::collections::{BTreeMap, HashMap};
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct ModelConfig {
     pub name: String,
     pub provider: String,
@@ -24,19 +24,19 @@ pub struct ModelConfig {
     #[serde(default = "default_temperature")]
     pub temperature: f32,
 }
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct PromptTemplate {
     pub system_message: String,
     pub user_template: String,
 }
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct ProcessingPolicy {
     pub name: String,
     pub priority: u8,
     pub conditions: HashMap<String, serde_yaml::Value>,
     pub strategy: ProcessingStrategy,
 }
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct ProcessingStrategy {
     #[serde(rename = "type")]
     pub strategy_type: String,
@@ -45,14 +45,14 @@ pub struct ProcessingStrategy {
     #[serde(default)]
     pub stages: Vec<ProcessingStage>,
 }
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct ProcessingStage {
     pub name: String,
     #[serde(default)]
     pub depends_on: Vec<String>,
     pub actions: Vec<TaskAction>,
 }
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct TaskAction {
     #[serde(default)]
     pub task: String,
@@ -69,7 +69,7 @@ pub struct TaskAction {
 fn default_timeout() -> u64 {
     30
 }
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct SqlValidationConfig {
     pub enabled: bool,
     pub use_sql_parser: bool,
@@ -111,7 +111,7 @@ impl Default for SqlValidationConfig {
         }
     }
 }
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct InputSanitisationConfig {
     pub enabled: bool,
     pub max_input_length: usize,
@@ -140,7 +140,7 @@ impl Default for InputSanitisationConfig {
         }
     }
 }
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct PathSecurityConfig {
     pub canonicalization_first: bool,
     pub base_directory_validation: bool,
@@ -179,7 +179,7 @@ impl Default for PathSecurityConfig {
         }
     }
 }
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct StructuralValidationConfig {
     pub use_ast_validation: bool,
     pub max_expression_depth: usize,
@@ -194,7 +194,7 @@ impl Default for StructuralValidationConfig {
         }
     }
 }
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct AuditLoggingConfig {
     pub security_events_enabled: bool,
     pub log_failed_validations: bool,
@@ -217,7 +217,7 @@ impl Default for AuditLoggingConfig {
         }
     }
 }
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Default)]
 pub struct SecurityConfig {
     #[serde(default)]
     pub sql_validation: SqlValidationConfig,
@@ -236,7 +236,7 @@ pub struct SecurityConfig {
     #[serde(default)]
     pub injection_patterns: HashMap<String, Vec<String>>,
 }
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct NLUConfig {
     pub models: Vec<ModelConfig>,
     pub selection_strategy: HashMap<String, serde_yaml::Value>,
@@ -247,6 +247,85 @@ pub struct NLUConfig {
     #[serde(default)]
     pub security: SecurityConfig,
 }
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigDiff {
+    pub models_added: Vec<String>,
+    pub models_removed: Vec<String>,
+    pub models_changed: Vec<String>,
+    pub prompts_added: Vec<String>,
+    pub prompts_removed: Vec<String>,
+    pub prompts_changed: Vec<String>,
+    pub policies_added: Vec<String>,
+    pub policies_removed: Vec<String>,
+    pub policies_changed: Vec<String>,
+    pub tasks_added: Vec<String>,
+    pub tasks_removed: Vec<String>,
+    pub tasks_changed: Vec<String>,
+    pub selection_strategy_changed: bool,
+    pub global_settings_changed: bool,
+    pub security_changed: bool,
+}
+impl ConfigDiff {
+    pub fn between(old: &NLUConfig, new: &NLUConfig) -> Self {
+        let (models_added, models_removed, models_changed) = diff_named(
+            old.models.iter().map(|m| (m.name.as_str(), m)).collect(),
+            new.models.iter().map(|m| (m.name.as_str(), m)).collect(),
+        );
+        let (prompts_added, prompts_removed, prompts_changed) = diff_named(
+            old.prompts.iter().map(|(k, v)| (k.as_str(), v)).collect(),
+            new.prompts.iter().map(|(k, v)| (k.as_str(), v)).collect(),
+        );
+        let (policies_added, policies_removed, policies_changed) = diff_named(
+            old.policies.iter().map(|p| (p.name.as_str(), p)).collect(),
+            new.policies.iter().map(|p| (p.name.as_str(), p)).collect(),
+        );
+        let (tasks_added, tasks_removed, tasks_changed) = diff_named(
+            old.tasks.iter().map(|(k, v)| (k.as_str(), v)).collect(),
+            new.tasks.iter().map(|(k, v)| (k.as_str(), v)).collect(),
+        );
+        Self {
+            models_added,
+            models_removed,
+            models_changed,
+            prompts_added,
+            prompts_removed,
+            prompts_changed,
+            policies_added,
+            policies_removed,
+            policies_changed,
+            tasks_added,
+            tasks_removed,
+            tasks_changed,
+            selection_strategy_changed: old.selection_strategy != new.selection_strategy,
+            global_settings_changed: old.global_settings != new.global_settings,
+            security_changed: old.security != new.security,
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+fn diff_named<T: PartialEq>(
+    old: BTreeMap<&str, &T>,
+    new: BTreeMap<&str, &T>,
+) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let added = new
+        .keys()
+        .filter(|name| !old.contains_key(*name))
+        .map(|name| name.to_string())
+        .collect();
+    let removed = old
+        .keys()
+        .filter(|name| !new.contains_key(*name))
+        .map(|name| name.to_string())
+        .collect();
+    let changed = new
+        .iter()
+        .filter(|(name, value)| old.get(*name).is_some_and(|prev| prev != *value))
+        .map(|(name, _)| name.to_string())
+        .collect();
+    (added, removed, changed)
+}
 fn default_true() -> bool {
     true
 }
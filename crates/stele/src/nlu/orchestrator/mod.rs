@@ -14,7 +14,7 @@ use crate::llm::unified_adapter::UnifiedLLMAdapter;
 use crate::nlu::llm_processor::{CustomLLMAdapter, LLMAdapter};
 use chrono::{Datelike, Duration, Timelike, Utc};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use steel::messaging::insight::ner_analysis::{NerAnalyser, NerAnalysisResult};
 use tracing::{debug, info, instrument, warn};
 pub mod adapter;
@@ -31,7 +31,8 @@ pub use data_models::*;
 pub use error::OrchestratorError;
 pub use planner::ProcessingPlan;
 pub struct NLUOrchestrator {
-    config: NLUConfig,
+    config_path: String,
+    config: RwLock<Arc<NLUConfig>>,
     llm_adapters: HashMap<String, Arc<dyn LLMAdapter + Send + Sync>>,
     prompt_cache: HashMap<String, String>,
 }
@@ -41,7 +42,8 @@ impl NLUOrchestrator {
         let config = Self::load_config(config_path).await?;
         let llm_adapters = Self::initialise_adapters(&config).await?;
         Ok(Self {
-            config,
+            config_path: config_path.to_string(),
+            config: RwLock::new(Arc::new(config)),
             llm_adapters,
             prompt_cache: HashMap::new(),
         })
@@ -70,11 +72,57 @@ impl NLUOrchestrator {
             llm_adapters.len()
         );
         Ok(Self {
-            config,
+            config_path: config_path.to_string(),
+            config: RwLock::new(Arc::new(config)),
             llm_adapters,
             prompt_cache: HashMap::new(),
         })
     }
+    pub fn config(&self) -> Arc<NLUConfig> {
+        self.config.read().unwrap().clone()
+    }
+    #[instrument(skip(self), name = "nlu_orchestrator_reload")]
+    pub async fn reload_config(&self) -> Result<ConfigDiff, OrchestratorError> {
+        let fresh = Self::load_config(&self.config_path).await?;
+        let diff = ConfigDiff::between(&self.config(), &fresh);
+        if let Some(model) = diff
+            .models_added
+            .iter()
+            .find(|m| !self.llm_adapters.contains_key(*m))
+        {
+            return Err(OrchestratorError::new(format!(
+                "Model '{model}' has no adapter; adding models requires a restart"
+            )));
+        }
+        if let Some(model) = diff.models_changed.first() {
+            return Err(OrchestratorError::new(format!(
+                "Model '{model}' changed; its adapter is rebuilt only on restart"
+            )));
+        }
+        if diff.is_empty() {
+            debug!("NLU config at {} unchanged on reload", self.config_path);
+            return Ok(diff);
+        }
+        *self.config.write().unwrap() = Arc::new(fresh);
+        info!("Reloaded NLU config from {}: {:?}", self.config_path, diff);
+        Ok(diff)
+    }
+    #[instrument(skip(self), name = "nlu_orchestrator_warm_up")]
+    pub async fn warm_up(&self) {
+        let start = std::time::Instant::now();
+        crate::nlu::llm_processor::warm_http_client();
+        if let Err(e) = Self::run_native_ner("Meet Alice in Paris next Monday at 9am.") {
+            warn!("NER warm-up failed: {}", e);
+        }
+        let _ = Self::resolve_temporal_text("next monday at 9am");
+        if let Err(e) = self.plan_input("warm up") {
+            debug!("No policy matched the warm-up input: {}", e);
+        }
+        info!(
+            "NLU orchestrator warmed up in {}ms",
+            start.elapsed().as_millis()
+        );
+    }
     async fn load_config(config_path: &str) -> Result<NLUConfig, OrchestratorError> {
         let models_content =
             tokio::fs::read_to_string(&format!("{config_path}/llm_models.yml")).await?;
@@ -158,9 +206,10 @@ impl NLUOrchestrator {
         let start_time = std::time::Instant::now();
         let analysis = analyser::analyse(input);
         debug!("Input analysis: {:?}", analysis);
-        let policy = self.select_policy(&analysis)?;
+        let config = self.config();
+        let policy = self.select_policy(&config, &analysis)?;
         info!("Selected policy: {}", policy.name);
-        let plan = planner::create_plan(policy, &self.config, input)?;
+        let plan = planner::create_plan(policy, &config, input)?;
         debug!("Created plan with {} tasks", plan.tasks.len());
         let task_results = executor::execute(&plan, &self.llm_adapters, input).await?;
         let unified_data =
@@ -171,12 +220,18 @@ impl NLUOrchestrator {
         );
         Ok(unified_data)
     }
-    fn select_policy(
+    pub fn plan_input(&self, input: &str) -> Result<ProcessingPlan, OrchestratorError> {
+        let analysis = analyser::analyse(input);
+        let config = self.config();
+        let policy = self.select_policy(&config, &analysis)?;
+        planner::create_plan(policy, &config, input)
+    }
+    fn select_policy<'a>(
         &self,
+        config: &'a NLUConfig,
         analysis: &InputAnalysis,
-    ) -> Result<&ProcessingPolicy, OrchestratorError> {
-        let mut matching_policies: Vec<&ProcessingPolicy> = self
-            .config
+    ) -> Result<&'a ProcessingPolicy, OrchestratorError> {
+        let mut matching_policies: Vec<&ProcessingPolicy> = config
             .policies
             .iter()
             .filter(|p| self.policy_matches(p, analysis))
@@ -564,7 +619,7 @@ impl NLUOrchestrator {
         base_cost * execution_factor.max(0.1)
     }
     fn get_prompts_by_category(&self, _category: &str) -> HashMap<String, String> {
-        self.config
+        self.config()
             .prompts
             .iter()
             .map(|(name, template)| (name.clone(), template.system_message.clone()))
@@ -589,30 +644,24 @@ impl NLUOrchestrator {
         &self,
         task_name: &str,
         required_capabilities: &[&str],
-    ) -> Option<&ModelConfig> {
-        if let Some(task_config) = self.config.tasks.get(task_name) {
+    ) -> Option<ModelConfig> {
+        let config = self.config();
+        if let Some(task_config) = config.tasks.get(task_name) {
             if let Some(preferred_model) =
                 task_config.get("preferred_model").and_then(|v| v.as_str())
             {
-                if let Some(model) = self
-                    .config
-                    .models
-                    .iter()
-                    .find(|m| m.name == preferred_model)
-                {
-                    return Some(model);
+                if let Some(model) = config.models.iter().find(|m| m.name == preferred_model) {
+                    return Some(model.clone());
                 }
             }
         }
-        let selection_method = self
-            .config
+        let selection_method = config
             .selection_strategy
             .get("method")
             .and_then(|v| v.as_str())
             .unwrap_or("capability_based");
-        match selection_method {
-            "capability_based" => self
-                .config
+        let selected = match selection_method {
+            "capability_based" => config
                 .models
                 .iter()
                 .filter(|model| {
@@ -626,25 +675,20 @@ impl NLUOrchestrator {
                     "high" => 3,
                     _ => 2,
                 }),
-            "cost_optimised" => {
-                self.config
-                    .models
-                    .iter()
-                    .min_by_key(|model| match model.cost_tier.as_str() {
-                        "low" => 1,
-                        "medium" => 2,
-                        "high" => 3,
-                        _ => 2,
-                    })
-            }
-            "performance_optimised" => self
-                .config
+            "cost_optimised" => config
                 .models
                 .iter()
-                .max_by_key(|model| model.max_tokens),
-            "round_robin" => self.config.models.first(),
-            _ => self.config.models.first(),
-        }
+                .min_by_key(|model| match model.cost_tier.as_str() {
+                    "low" => 1,
+                    "medium" => 2,
+                    "high" => 3,
+                    _ => 2,
+                }),
+            "performance_optimised" => config.models.iter().max_by_key(|model| model.max_tokens),
+            "round_robin" => config.models.first(),
+            _ => config.models.first(),
+        };
+        selected.cloned()
     }
     pub fn cache_prompt(&mut self, key: String, prompt: String) {
         self.prompt_cache.insert(key, prompt);
@@ -656,20 +700,21 @@ impl NLUOrchestrator {
         self.prompt_cache.clear();
     }
     fn validate_input_security(&self, input: &str) -> Result<(), OrchestratorError> {
-        if input.len() > self.config.security.input_sanitisation.max_input_length {
+        let config = self.config();
+        if input.len() > config.security.input_sanitisation.max_input_length {
             return Err(OrchestratorError::new(format!(
                 "Input exceeds maximum length of {} characters",
-                self.config.security.input_sanitisation.max_input_length
+                config.security.input_sanitisation.max_input_length
             )));
         }
-        for pattern in &self.config.security.blocked_operations {
+        for pattern in &config.security.blocked_operations {
             if input.to_lowercase().contains(&pattern.to_lowercase()) {
                 return Err(OrchestratorError::new(format!(
                     "Input contains blocked pattern: {pattern}"
                 )));
             }
         }
-        if self.config.security.input_sanitisation.enabled {
+        if config.security.input_sanitisation.enabled {
             let suspicious_indicators = [
                 "system:",
                 "prompt:",
@@ -690,19 +735,19 @@ impl NLUOrchestrator {
         Ok(())
     }
     fn check_rate_limit(&self, _user_id: &str) -> Result<(), OrchestratorError> {
-        if self.config.security.audit_logging.security_events_enabled {
+        if self.config().security.audit_logging.security_events_enabled {
             debug!("Security features are enabled but rate limiting not yet implemented");
         }
         Ok(())
     }
-    pub fn get_available_models(&self) -> &[ModelConfig] {
-        &self.config.models
+    pub fn get_available_models(&self) -> Vec<ModelConfig> {
+        self.config().models.clone()
     }
-    pub fn get_model(&self, name: &str) -> Option<&ModelConfig> {
-        self.config.models.iter().find(|m| m.name == name)
+    pub fn get_model(&self, name: &str) -> Option<ModelConfig> {
+        self.config().models.iter().find(|m| m.name == name).cloned()
     }
-    pub fn get_policies(&self) -> &[ProcessingPolicy] {
-        &self.config.policies
+    pub fn get_policies(&self) -> Vec<ProcessingPolicy> {
+        self.config().policies.clone()
     }
     pub async fn health_check(&self) -> Result<HashMap<String, bool>, OrchestratorError> {
         let mut health_status = HashMap::new();
@@ -721,11 +766,9 @@ impl NLUOrchestrator {
                 }
             }
         }
-        health_status.insert("config_valid".to_string(), !self.config.models.is_empty());
-        health_status.insert(
-            "prompts_loaded".to_string(),
-            !self.config.prompts.is_empty(),
-        );
+        let config = self.config();
+        health_status.insert("config_valid".to_string(), !config.models.is_empty());
+        health_status.insert("prompts_loaded".to_string(), !config.prompts.is_empty());
         Ok(health_status)
     }
     pub fn get_default_adapter(&self) -> Option<Arc<dyn LLMAdapter + Send + Sync>> {
@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use crate::nlu::orchestrator::{Entity, Relationship, SegmentType, UnifiedNLUData};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+const MAX_CANDIDATES: usize = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryCandidate {
+    pub intent: String,
+    pub confidence: f64,
+    pub entities: Vec<Entity>,
+    pub relationships: Vec<Relationship>,
+}
+
+#[derive(Clone)]
+struct Partial<'a> {
+    intent: String,
+    confidence: f64,
+    chosen: Vec<&'a Entity>,
+}
+
+pub fn rank_interpretations(data: &UnifiedNLUData) -> Vec<QueryCandidate> {
+    let mut beam: Vec<Partial> = intent_distribution(data)
+        .into_iter()
+        .map(|(intent, confidence)| Partial {
+            intent,
+            confidence,
+            chosen: Vec::new(),
+        })
+        .collect();
+    let groups = entity_groups(data);
+    for options in groups.values() {
+        let total: f64 = options.iter().map(|e| e.confidence.max(0.0) as f64).sum();
+        let mut next = Vec::with_capacity(beam.len() * options.len());
+        for partial in &beam {
+            for entity in options {
+                let share = if total > 0.0 {
+                    entity.confidence.max(0.0) as f64 / total
+                } else {
+                    1.0 / options.len() as f64
+                };
+                let mut extended = partial.clone();
+                extended.confidence *= share;
+                extended.chosen.push(entity);
+                next.push(extended);
+            }
+        }
+        sort_desc(&mut next);
+        next.truncate(MAX_CANDIDATES);
+        beam = next;
+    }
+    sort_desc(&mut beam);
+    beam.truncate(MAX_CANDIDATES);
+
+    let all_ids: HashSet<&str> = groups
+        .values()
+        .flatten()
+        .map(|e| e.temp_id.as_str())
+        .collect();
+    beam.into_iter()
+        .map(|partial| {
+            let chosen: HashSet<&str> =
+                partial.chosen.iter().map(|&e| e.temp_id.as_str()).collect();
+            let keeps = |id: &str| !all_ids.contains(id) || chosen.contains(id);
+            QueryCandidate {
+                intent: partial.intent,
+                confidence: partial.confidence,
+                entities: partial.chosen.into_iter().cloned().collect(),
+                relationships: data
+                    .extracted_data
+                    .relationships
+                    .iter()
+                    .filter(|r| keeps(&r.source) && keeps(&r.target))
+                    .cloned()
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+pub fn needs_disambiguation(candidates: &[QueryCandidate], margin: f64) -> bool {
+    match candidates {
+        [first, second, ..] => first.confidence - second.confidence <= margin,
+        _ => false,
+    }
+}
+
+fn intent_distribution(data: &UnifiedNLUData) -> Vec<(String, f64)> {
+    let mut weights: BTreeMap<String, f64> = BTreeMap::new();
+    for segment in &data.segments {
+        let label = match &segment.segment_type {
+            SegmentType::Statement { intent } => intent.clone(),
+            SegmentType::Question { expected_answer_type } => {
+                format!("question:{expected_answer_type}")
+            }
+            SegmentType::Command { operation } => format!("command:{operation}"),
+            SegmentType::Relationship { .. } => "relationship".to_string(),
+        };
+        let weight = segment
+            .metadata
+            .get("confidence")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(1.0)
+            .max(0.0);
+        *weights.entry(label).or_default() += weight;
+    }
+    if weights.is_empty() {
+        return vec![("unknown".to_string(), 1.0)];
+    }
+    let total: f64 = weights.values().sum();
+    let count = weights.len() as f64;
+    weights
+        .into_iter()
+        .map(|(k, w)| (k, if total > 0.0 { w / total } else { 1.0 / count }))
+        .collect()
+}
+
+fn entity_groups(data: &UnifiedNLUData) -> BTreeMap<String, Vec<&Entity>> {
+    let mut groups: BTreeMap<String, Vec<&Entity>> = BTreeMap::new();
+    for entity in data.extracted_data.entities() {
+        let options = groups.entry(entity.name.trim().to_lowercase()).or_default();
+        let entity_type = entity.entity_type.to_lowercase();
+        match options
+            .iter_mut()
+            .find(|e| e.entity_type.to_lowercase() == entity_type)
+        {
+            Some(existing) if existing.confidence < entity.confidence => *existing = entity,
+            Some(_) => {}
+            None => options.push(entity),
+        }
+    }
+    groups
+}
+
+fn sort_desc(partials: &mut [Partial]) {
+    partials.sort_by(|a, b| {
+        b.confidence
+            .total_cmp(&a.confidence)
+            .then_with(|| a.intent.cmp(&b.intent))
+    });
+}
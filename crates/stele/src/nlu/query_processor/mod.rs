@@ -10,6 +10,10 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
+pub mod candidates;
+
+pub use candidates::{needs_disambiguation, rank_interpretations, QueryCandidate};
+
 use crate::database::dynamic_storage::DynamicStorage;
 use crate::nlu::orchestrator::{NLUOrchestrator, OrchestratorError, UnifiedNLUData};
 use serde_json::Value;
@@ -27,6 +31,8 @@ pub enum QueryProcessorError {
     Serialization(#[from] serde_json::Error),
     #[error("Configuration error: {0}")]
     Config(String),
+    #[error("No interpretation produced for input")]
+    NoInterpretation,
 }
 pub type Result<T> = std::result::Result<T, QueryProcessorError>;
 #[derive(Clone)]
@@ -83,6 +89,28 @@ impl QueryProcessor {
     }
 
     
+    #[instrument(skip(self, input), fields(input_length = input.len()))]
+    pub async fn process(&self, input: &str) -> Result<Vec<QueryCandidate>> {
+        let unified_nlu_data = {
+            let orchestrator = self.orchestrator.read().await;
+            orchestrator.process_input(input).await?
+        };
+        let candidates = rank_interpretations(&unified_nlu_data);
+        info!(
+            candidates = candidates.len(),
+            top_confidence = ?candidates.first().map(|c| c.confidence),
+            "Ranked query interpretations"
+        );
+        Ok(candidates)
+    }
+    pub async fn process_best(&self, input: &str) -> Result<QueryCandidate> {
+        self.process(input)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(QueryProcessorError::NoInterpretation)
+    }
+
     #[instrument(skip(self, unified_nlu_data), fields(user_id = %user_id, channel = %channel))]
     pub async fn store_nlu_data(
         &self,
@@ -52,6 +52,33 @@ impl ProvenanceSpec {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ProvenanceTag {
+    pub source: String,
+    #[serde(default)]
+    pub utterance_ids: Vec<String>,
+}
+
+impl ProvenanceTag {
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            utterance_ids: Vec::new(),
+        }
+    }
+    pub fn with_utterances(mut self, ids: impl IntoIterator<Item = String>) -> Self {
+        self.utterance_ids.extend(ids);
+        self
+    }
+    pub fn to_spec(&self) -> ProvenanceSpec {
+        ProvenanceSpec {
+            source: self.source.clone(),
+            utterance_ids: self.utterance_ids.clone(),
+            ..ProvenanceSpec::default()
+        }
+    }
+}
+
 pub struct ProvenanceWriter<'a> {
     pub store: &'a crate::database::structured_store::StructuredStore,
 }
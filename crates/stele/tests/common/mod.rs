@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use std::sync::Arc;
+use stele::KgService;
+use surrealdb::engine::remote::ws::Ws;
+use surrealdb::opt::auth::Root;
+use surrealdb::Surreal;
+
+pub async fn kg_service(namespace: &str, database: &str, reset: &str) -> KgService {
+    let url = std::env::var("SURREALDB_URL").unwrap_or_else(|_| "ws://127.0.0.1:8000".into());
+    let endpoint = url.strip_prefix("ws://").unwrap_or(&url).to_string();
+    let user = std::env::var("SURREALDB_USER").unwrap_or_else(|_| "root".into());
+    let pass = std::env::var("SURREALDB_PASS").unwrap_or_else(|_| "root".into());
+    let client = Surreal::new::<Ws>(&endpoint).await.expect("connect");
+    client
+        .signin(Root {
+            username: &user,
+            password: &pass,
+        })
+        .await
+        .expect("auth");
+    client
+        .use_ns(namespace)
+        .use_db(database)
+        .await
+        .expect("ns/db");
+    client.query(reset).await.expect("reset");
+    KgService::new(Some(Arc::new(client)), None)
+}
@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use std::sync::Arc;
+use std::time::Duration;
+use stele::database::{ConnectionPool, DatabaseError, PoolConfig, PooledConnection};
+use surrealdb::engine::remote::ws::Client;
+use surrealdb::Surreal;
+use tokio::sync::mpsc;
+
+fn pool(size: usize, acquire_timeout: Duration) -> ConnectionPool {
+    let connections = (0..size)
+        .map(|_| PooledConnection {
+            client: Arc::new(Surreal::<Client>::init()),
+            commands: mpsc::channel(1).0,
+        })
+        .collect();
+    ConnectionPool::from_connections(connections, acquire_timeout)
+}
+
+#[tokio::test]
+async fn acquiring_beyond_pool_size_queues_instead_of_failing() {
+    let pool = Arc::new(pool(2, Duration::from_secs(5)));
+    let tasks: Vec<_> = (0..8)
+        .map(|_| {
+            let pool = pool.clone();
+            tokio::spawn(async move {
+                let guard = pool.acquire().await?;
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                drop(guard);
+                Ok::<_, DatabaseError>(())
+            })
+        })
+        .collect();
+    for task in tasks {
+        assert!(task.await.unwrap().is_ok());
+    }
+    assert_eq!(pool.available(), pool.size());
+}
+
+#[tokio::test]
+async fn dropped_guard_returns_its_connection() {
+    let pool = pool(1, Duration::from_millis(50));
+    let guard = pool.acquire().await.unwrap();
+    let client = guard.client.clone();
+    assert_eq!(pool.available(), 0);
+    assert!(matches!(pool.acquire().await, Err(DatabaseError::Timeout)));
+
+    drop(guard);
+    assert_eq!(pool.available(), 1);
+    let again = pool.acquire().await.unwrap();
+    assert!(Arc::ptr_eq(&client, &again.client));
+}
+
+#[tokio::test]
+async fn zero_sized_pool_is_rejected() {
+    let config = PoolConfig {
+        size: 0,
+        ..Default::default()
+    };
+    assert!(matches!(
+        ConnectionPool::connect(config).await,
+        Err(DatabaseError::ValidationError(_))
+    ));
+}
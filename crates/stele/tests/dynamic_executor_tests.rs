@@ -11,7 +11,7 @@
 // along with this program. If not, see https://www.gnu.org/licenses/.
 
 use serde_json::json;
-use stele::flows::dynamic_executor::executor::DynamicExecutor;
+use stele::flows::dynamic_executor::executor::{DynamicExecutor, DynamicSource};
 
 fn setup_executor() -> DynamicExecutor {
     DynamicExecutor::new().expect("Failed to create executor")
@@ -195,3 +195,55 @@ fn test_import_export_function() {
         .unwrap();
     assert_eq!(result, json!(101.0));
 }
+
+fn add_one_source(name: &str) -> DynamicSource<'_> {
+    DynamicSource::Wat {
+        name,
+        export: "execute",
+        wat: ADD_ONE_WAT,
+    }
+}
+
+#[test]
+fn test_repeated_source_registration_is_served_from_cache() {
+    let executor = setup_executor();
+
+    let first = executor
+        .register_dynamic_source(add_one_source("add_one"))
+        .unwrap();
+    assert_eq!(executor.cache_hits(), 0);
+
+    let second = executor
+        .register_dynamic_source(add_one_source("add_one"))
+        .unwrap();
+    assert_eq!(executor.cache_hits(), 1);
+    assert_eq!(executor.cached_function_count(), 1);
+    assert_eq!(second.version, first.version);
+    assert_eq!(second.source_code, first.source_code);
+    assert_eq!(second.execute(&[json!(41.0)]).unwrap(), json!(42.0));
+    assert_eq!(
+        first.execute(&[json!(41.0)]).unwrap(),
+        second.execute(&[json!(41.0)]).unwrap()
+    );
+}
+
+#[test]
+fn test_cache_key_covers_name_and_clear_cache_resets() {
+    let executor = setup_executor();
+
+    executor
+        .register_dynamic_source(add_one_source("add_one"))
+        .unwrap();
+    executor
+        .register_dynamic_source(add_one_source("increment"))
+        .unwrap();
+    assert_eq!(executor.cache_hits(), 0);
+    assert_eq!(executor.cached_function_count(), 2);
+
+    executor.clear_cache();
+    assert_eq!(executor.cached_function_count(), 0);
+    executor
+        .register_dynamic_source(add_one_source("add_one"))
+        .unwrap();
+    assert_eq!(executor.cache_hits(), 0);
+}
@@ -0,0 +1,93 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use stele::blocks::rules::BlockError;
+use stele::flows::dynamic_executor::executor::{DynamicExecutor, DynamicSource};
+use stele::flows::dynamic_executor::{DynamicExecError, DynamicFunction};
+
+const LIMIT: Duration = Duration::from_millis(100);
+const SLACK: Duration = Duration::from_secs(2);
+
+const INFINITE_LOOP_WAT: &str = r#"(module
+  (func $spin (export "execute") (param f64) (result f64)
+    (loop $forever
+      (br $forever))
+    (local.get 0)))
+"#;
+
+fn assert_timed_out(result: Result<Value, BlockError>, started: Instant) {
+    assert!(started.elapsed() < LIMIT + SLACK, "execute did not return in time");
+    match result {
+        Err(BlockError::DynamicExec(DynamicExecError::Timeout { limit })) => {
+            assert_eq!(limit, LIMIT)
+        }
+        other => panic!("expected a timeout, got {other:?}"),
+    }
+}
+
+#[test]
+fn infinite_wat_loop_times_out() {
+    let executor = DynamicExecutor::new().unwrap();
+    executor.set_execution_timeout(Some(LIMIT));
+    executor.set_wasm_fuel(Some(u64::MAX));
+    let function = executor
+        .register_dynamic_source(DynamicSource::Wat {
+            name: "spin",
+            export: "execute",
+            wat: INFINITE_LOOP_WAT,
+        })
+        .unwrap();
+    assert_eq!(function.execution_timeout, Some(LIMIT));
+    assert!(function.interruptible);
+
+    let started = Instant::now();
+    assert_timed_out(function.execute(&[json!(1.0)]), started);
+    assert_eq!(function.get_timeout_count(), 1);
+    assert_eq!(function.get_error_count(), 1);
+}
+
+#[test]
+fn runaway_native_closure_times_out() {
+    let mut function = DynamicFunction::new(
+        Arc::new(|_: &[Value]| -> Result<Value, BlockError> {
+            loop {
+                thread::sleep(Duration::from_millis(10));
+            }
+        }),
+        "1.0".to_string(),
+        "loop {}".to_string(),
+    );
+    function.set_execution_timeout(Some(LIMIT));
+
+    let started = Instant::now();
+    assert_timed_out(function.execute(&[]), started);
+    let stats = function.performance_metrics.to_stats_map();
+    assert_eq!(stats["timeout_count"], json!(1));
+}
+
+#[test]
+fn functions_within_the_bound_are_unaffected() {
+    let mut function = DynamicFunction::new(
+        Arc::new(|args: &[Value]| -> Result<Value, BlockError> {
+            Ok(json!(args[0].as_f64().unwrap_or(0.0) * 2.0))
+        }),
+        "1.0".to_string(),
+        "double".to_string(),
+    );
+    function.set_execution_timeout(Some(LIMIT));
+    assert_eq!(function.execute(&[json!(21.0)]).unwrap(), json!(42.0));
+    assert_eq!(function.get_timeout_count(), 0);
+}
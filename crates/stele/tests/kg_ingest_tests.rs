@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use serde_json::json;
+use stele::database::types::DatabaseError;
+use stele::provenance::ProvenanceTag;
+use stele::{KgFact, KgQueryFilter, KgService};
+
+fn fact(subject: &str, predicate: &str, object: serde_json::Value) -> KgFact {
+    KgFact {
+        subject: subject.into(),
+        predicate: predicate.into(),
+        object,
+    }
+}
+
+fn filter(source: Option<&str>) -> KgQueryFilter {
+    KgQueryFilter {
+        source: source.map(str::to_string),
+        ..KgQueryFilter::default()
+    }
+}
+
+#[test]
+fn normalised_key_ignores_case_and_whitespace() {
+    let a = fact("Alice", "Knows", json!("Bob  Smith"));
+    let b = fact("alice", "knows", json!(" bob smith "));
+    let c = fact("alice", "knows", json!("carol"));
+    assert_eq!(a.normalised_key(), b.normalised_key());
+    assert_ne!(a.normalised_key(), c.normalised_key());
+    assert_ne!(
+        fact("a", "b", json!("1")).normalised_key(),
+        fact("a", "b", json!(1)).normalised_key()
+    );
+}
+
+#[tokio::test]
+async fn batch_duplicates_are_counted_without_a_database() {
+    let service = KgService::new(None, None);
+    let summary = service
+        .ingest_facts(
+            vec![
+                fact("alice", "knows", json!("bob")),
+                fact("Alice", "KNOWS", json!("Bob")),
+                fact("alice", "likes", json!("tea")),
+                fact("bad subject", "knows", json!("bob")),
+            ],
+            ProvenanceTag::new("import-a"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(summary.accepted, 2);
+    assert_eq!(summary.deduped, 1);
+    assert_eq!(summary.skipped_invalid, 1);
+    assert_eq!(summary.inserted, 0);
+    assert_eq!(summary.accepted_facts.len(), 2);
+}
+
+#[tokio::test]
+async fn empty_source_is_rejected() {
+    let service = KgService::new(None, None);
+    let err = service
+        .ingest_facts(vec![fact("a", "b", json!("c"))], ProvenanceTag::new("  "))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DatabaseError::ValidationError(_)));
+}
+
+#[tokio::test]
+#[ignore]
+async fn overlapping_batches_dedupe_and_filter_by_source() {
+    let service = common::kg_service(
+        "kg_ingest_ns",
+        "kg_ingest_db",
+        "DELETE edge; DELETE kg_fact_provenance;",
+    )
+    .await;
+    let first = service
+        .ingest_facts(
+            vec![
+                fact("alice", "knows", json!("bob")),
+                fact("alice", "likes", json!("tea")),
+            ],
+            ProvenanceTag::new("import-a").with_utterances(vec!["utt:1".to_string()]),
+        )
+        .await
+        .unwrap();
+    assert_eq!((first.inserted, first.updated, first.deduped), (2, 0, 0));
+    assert_eq!(first.provenance_links, 2);
+
+    let second = service
+        .ingest_facts(
+            vec![
+                fact("Alice", "knows", json!("Bob")),
+                fact("alice", "likes", json!("Tea")),
+                fact("bob", "knows", json!("carol")),
+            ],
+            ProvenanceTag::new("import-b"),
+        )
+        .await
+        .unwrap();
+    assert_eq!((second.inserted, second.updated, second.deduped), (1, 2, 0));
+
+    let replay = service
+        .ingest_facts(
+            vec![fact("bob", "knows", json!("carol"))],
+            ProvenanceTag::new("import-b"),
+        )
+        .await
+        .unwrap();
+    assert_eq!((replay.inserted, replay.updated, replay.deduped), (0, 0, 1));
+
+    assert_eq!(service.query(filter(None)).await.rows.len(), 3);
+    assert_eq!(service.query(filter(Some("import-a"))).await.rows.len(), 2);
+    assert_eq!(service.query(filter(Some("import-b"))).await.rows.len(), 3);
+    assert!(service.query(filter(Some("import-c"))).await.rows.is_empty());
+}
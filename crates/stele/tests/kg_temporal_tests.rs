@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+mod common;
+
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use std::time::Duration;
+use stele::provenance::ProvenanceTag;
+use stele::{KgFact, KgQueryFilter, KgService};
+
+fn lives_in(city: &str) -> KgFact {
+    KgFact {
+        subject: "alice".into(),
+        predicate: "lives_in".into(),
+        object: json!(city),
+    }
+}
+
+fn alice_as_of(as_of: Option<DateTime<Utc>>) -> KgQueryFilter {
+    KgQueryFilter {
+        subject: Some("alice".into()),
+        predicate: Some("lives_in".into()),
+        as_of,
+        ..KgQueryFilter::default()
+    }
+}
+
+async fn objects(service: &KgService, filter: KgQueryFilter) -> Vec<serde_json::Value> {
+    service
+        .query(filter)
+        .await
+        .rows
+        .into_iter()
+        .map(|row| row["object"].clone())
+        .collect()
+}
+
+#[test]
+fn filter_deserialises_as_of() {
+    let raw = json!({"subject": "alice", "as_of": "2024-05-01T12:00:00Z"});
+    let filter: KgQueryFilter = serde_json::from_value(raw).unwrap();
+    let expected: DateTime<Utc> = "2024-05-01T12:00:00Z".parse().unwrap();
+    assert_eq!(filter.as_of, Some(expected));
+    let latest: KgQueryFilter = serde_json::from_value(json!({"subject": "alice"})).unwrap();
+    assert!(latest.as_of.is_none());
+}
+
+#[tokio::test]
+async fn update_without_a_database_stages_facts() {
+    let service = KgService::new(None, None);
+    let summary = service
+        .update_facts(vec![lives_in("paris")], ProvenanceTag::new("census"))
+        .await
+        .unwrap();
+    assert_eq!(summary.accepted, 1);
+    assert_eq!(summary.superseded, 0);
+}
+
+#[tokio::test]
+#[ignore]
+async fn as_of_returns_the_version_valid_at_that_instant() {
+    let service = common::kg_service("kg_temporal_ns", "kg_temporal_db", "DELETE edge;").await;
+
+    let before = Utc::now();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let first = service
+        .ingest_facts(vec![lives_in("paris")], ProvenanceTag::new("census"))
+        .await
+        .unwrap();
+    assert_eq!(first.inserted, 1);
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let between = Utc::now();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let second = service
+        .update_facts(vec![lives_in("london")], ProvenanceTag::new("census"))
+        .await
+        .unwrap();
+    assert_eq!((second.inserted, second.superseded), (1, 1));
+
+    assert_eq!(objects(&service, alice_as_of(None)).await, vec![json!("london")]);
+    assert_eq!(objects(&service, alice_as_of(Some(between))).await, vec![json!("paris")]);
+    assert_eq!(objects(&service, alice_as_of(Some(Utc::now()))).await, vec![json!("london")]);
+    assert!(objects(&service, alice_as_of(Some(before))).await.is_empty());
+}
+
+#[tokio::test]
+#[ignore]
+async fn ingest_keeps_every_value_current_until_an_update_supersedes_them() {
+    let service =
+        common::kg_service("kg_temporal_ns", "kg_temporal_multi_db", "DELETE edge;").await;
+
+    let ingested = service
+        .ingest_facts(
+            vec![lives_in("paris"), lives_in("london")],
+            ProvenanceTag::new("census"),
+        )
+        .await
+        .unwrap();
+    assert_eq!((ingested.inserted, ingested.superseded), (2, 0));
+    let mut latest = objects(&service, alice_as_of(None)).await;
+    latest.sort_by_key(|v| v.to_string());
+    assert_eq!(latest, vec![json!("london"), json!("paris")]);
+
+    let updated = service
+        .update_facts(vec![lives_in("london")], ProvenanceTag::new("census"))
+        .await
+        .unwrap();
+    assert_eq!((updated.deduped, updated.superseded), (1, 1));
+    assert_eq!(
+        objects(&service, alice_as_of(None)).await,
+        vec![json!("london")]
+    );
+}
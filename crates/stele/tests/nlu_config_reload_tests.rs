@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use std::path::Path;
+use stele::nlu::orchestrator::NLUOrchestrator;
+
+const MODELS: &str = r#"
+models:
+  - name: local-test
+    provider: ollama
+    capabilities: [extraction]
+    speed_tier: fast
+    cost_tier: free
+    max_tokens: 1024
+    parallel_limit: 1
+selection_strategy:
+  method: capability_based
+"#;
+
+const PROMPTS: &str = r#"
+extract:
+  system_message: "Extract facts from {input}"
+  user_template: "{input}"
+classify:
+  system_message: "Classify {input}"
+  user_template: "{input}"
+"#;
+
+fn rules(extract_priority: u8, classify_priority: u8) -> String {
+    format!(
+        r#"
+policies:
+  - name: extract_first
+    priority: {extract_priority}
+    conditions: {{}}
+    strategy:
+      type: parallel
+      actions:
+        - task: extract
+          model_capability: extraction
+  - name: classify_first
+    priority: {classify_priority}
+    conditions: {{}}
+    strategy:
+      type: parallel
+      actions:
+        - task: classify
+          model_capability: extraction
+global_settings:
+  max_parallel_tasks: 1
+tasks: {{}}
+"#
+    )
+}
+
+fn write_config(dir: &Path, extract_priority: u8, classify_priority: u8) {
+    std::fs::write(dir.join("llm_models.yml"), MODELS).unwrap();
+    std::fs::write(dir.join("prompts.yml"), PROMPTS).unwrap();
+    std::fs::write(dir.join("rules.yml"), rules(extract_priority, classify_priority)).unwrap();
+}
+
+#[tokio::test]
+async fn reload_applies_new_policy_priorities() {
+    let dir = tempfile::tempdir().unwrap();
+    write_config(dir.path(), 90, 10);
+    let orchestrator = NLUOrchestrator::new(dir.path().to_str().unwrap())
+        .await
+        .unwrap();
+    orchestrator.warm_up().await;
+
+    let before = orchestrator.plan_input("what did alice buy?").unwrap();
+    assert_eq!(before.strategy_name, "extract_first");
+    let snapshot = orchestrator.config();
+
+    write_config(dir.path(), 10, 90);
+    let diff = orchestrator.reload_config().await.unwrap();
+    assert_eq!(diff.policies_changed, vec!["classify_first", "extract_first"]);
+    assert!(diff.policies_added.is_empty() && diff.models_changed.is_empty());
+    assert!(!diff.security_changed);
+
+    let after = orchestrator.plan_input("what did alice buy?").unwrap();
+    assert_eq!(after.strategy_name, "classify_first");
+    assert_eq!(snapshot.policies[0].priority, 90);
+}
+
+#[tokio::test]
+async fn reload_without_changes_reports_an_empty_diff() {
+    let dir = tempfile::tempdir().unwrap();
+    write_config(dir.path(), 90, 10);
+    let orchestrator = NLUOrchestrator::new(dir.path().to_str().unwrap())
+        .await
+        .unwrap();
+    assert!(orchestrator.reload_config().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn reload_rejects_models_without_adapters() {
+    let dir = tempfile::tempdir().unwrap();
+    write_config(dir.path(), 90, 10);
+    let orchestrator = NLUOrchestrator::new(dir.path().to_str().unwrap())
+        .await
+        .unwrap();
+    let extra_model = MODELS.replace(
+        "selection_strategy:",
+        "  - name: late-arrival\n    provider: ollama\n    capabilities: [extraction]\n    \
+         speed_tier: fast\n    cost_tier: free\n    max_tokens: 1024\n    parallel_limit: 1\n\
+         selection_strategy:",
+    );
+    std::fs::write(dir.path().join("llm_models.yml"), extra_model).unwrap();
+    assert!(orchestrator.reload_config().await.is_err());
+    assert_eq!(orchestrator.get_available_models().len(), 1);
+}
+
+#[tokio::test]
+async fn reload_rejects_changed_models() {
+    let dir = tempfile::tempdir().unwrap();
+    write_config(dir.path(), 90, 10);
+    let orchestrator = NLUOrchestrator::new(dir.path().to_str().unwrap())
+        .await
+        .unwrap();
+    let switched_provider = MODELS.replace("provider: ollama", "provider: anthropic");
+    std::fs::write(dir.path().join("llm_models.yml"), switched_provider).unwrap();
+    assert!(orchestrator.reload_config().await.is_err());
+    assert_eq!(orchestrator.config().models[0].provider, "ollama");
+}
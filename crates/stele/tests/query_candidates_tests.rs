@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use std::collections::HashMap;
+use stele::nlu::orchestrator::{
+    Entity, ExtractedData, InputSegment, KnowledgeNode, ProcessingMetadata, Relationship,
+    SegmentType, UnifiedNLUData,
+};
+use stele::nlu::query_processor::{needs_disambiguation, rank_interpretations};
+
+fn entity(id: &str, name: &str, entity_type: &str, confidence: f32) -> KnowledgeNode {
+    KnowledgeNode::Entity(Entity {
+        temp_id: id.into(),
+        name: name.into(),
+        entity_type: entity_type.into(),
+        confidence,
+        metadata: None,
+    })
+}
+
+fn relationship(source: &str, target: &str, relation_type: &str) -> Relationship {
+    Relationship {
+        source: source.into(),
+        target: target.into(),
+        relation_type: relation_type.into(),
+        confidence: 0.9,
+        metadata: None,
+    }
+}
+
+fn nlu(segments: Vec<InputSegment>, nodes: Vec<KnowledgeNode>) -> UnifiedNLUData {
+    UnifiedNLUData {
+        segments,
+        extracted_data: ExtractedData {
+            nodes,
+            relationships: vec![
+                relationship("user", "jaguar_car", "wants_to_buy"),
+                relationship("user", "jaguar_cat", "wants_to_see"),
+            ],
+        },
+        processing_metadata: ProcessingMetadata::default(),
+    }
+}
+
+fn statement(text: &str, intent: &str, confidence: f64) -> InputSegment {
+    let mut segment = InputSegment::new(
+        text.into(),
+        SegmentType::Statement {
+            intent: intent.into(),
+        },
+    );
+    segment.metadata = HashMap::from([("confidence".to_string(), confidence.into())]);
+    segment
+}
+
+#[test]
+fn ambiguous_entity_yields_ranked_alternatives() {
+    let data = nlu(
+        vec![statement("How fast is a jaguar?", "lookup_fact", 1.0)],
+        vec![
+            entity("jaguar_car", "Jaguar", "vehicle", 0.55),
+            entity("jaguar_cat", "jaguar", "animal", 0.45),
+        ],
+    );
+    let candidates = rank_interpretations(&data);
+    assert!(candidates.len() >= 2);
+    assert!(candidates
+        .windows(2)
+        .all(|pair| pair[0].confidence >= pair[1].confidence));
+
+    let best = &candidates[0];
+    assert_eq!(best.entities[0].entity_type, "vehicle");
+    assert!((best.confidence - 0.55).abs() < 1e-6);
+    assert_eq!(best.relationships.len(), 1);
+    assert_eq!(best.relationships[0].relation_type, "wants_to_buy");
+    assert_eq!(candidates[1].entities[0].entity_type, "animal");
+    assert!(needs_disambiguation(&candidates, 0.15));
+    assert!(!needs_disambiguation(&candidates, 0.05));
+}
+
+#[test]
+fn competing_intents_multiply_with_entity_choices() {
+    let data = nlu(
+        vec![
+            statement("Book the jaguar", "reserve", 0.7),
+            statement("Book the jaguar", "read_about", 0.3),
+        ],
+        vec![
+            entity("jaguar_car", "Jaguar", "vehicle", 0.8),
+            entity("jaguar_cat", "Jaguar", "animal", 0.2),
+        ],
+    );
+    let candidates = rank_interpretations(&data);
+    assert_eq!(candidates.len(), 4);
+    assert_eq!(candidates[0].intent, "reserve");
+    assert!((candidates[0].confidence - 0.56).abs() < 1e-6);
+    let total: f64 = candidates.iter().map(|c| c.confidence).sum();
+    assert!((total - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn unambiguous_input_has_a_single_candidate() {
+    let data = nlu(
+        vec![statement("Who is Alice?", "lookup_person", 1.0)],
+        vec![entity("alice", "Alice", "person", 0.9)],
+    );
+    let candidates = rank_interpretations(&data);
+    assert_eq!(candidates.len(), 1);
+    assert!((candidates[0].confidence - 1.0).abs() < 1e-6);
+    assert!(!needs_disambiguation(&candidates, 0.5));
+}
@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use serde_json::{json, Value};
+use std::sync::Arc;
+use stele::database::query_builder::{Operator, OrderDirection};
+use stele::database::DatabaseError;
+use stele::StructuredStore;
+use surrealdb::engine::remote::ws::Client;
+use surrealdb::Surreal;
+
+fn store() -> StructuredStore {
+    StructuredStore::new(Arc::new(Surreal::<Client>::init()))
+}
+
+#[test]
+fn filters_order_and_limit_compile_to_bound_parameters() {
+    let compiled = store()
+        .select("canonical_entity")
+        .filter("entity_type", Operator::Equals, "person")
+        .filter("confidence", Operator::GreaterThanEquals, 0.8)
+        .order_by("name", OrderDirection::Asc)
+        .order_by("created_at", OrderDirection::Desc)
+        .limit(10)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        compiled.text,
+        "SELECT * FROM canonical_entity WHERE entity_type = $p0 AND confidence >= $p1 \
+         ORDER BY name ASC, created_at DESC LIMIT $limit"
+    );
+    assert_eq!(compiled.params.len(), 3);
+    assert_eq!(compiled.params["p0"], json!("person"));
+    assert_eq!(compiled.params["p1"], json!(0.8));
+    assert_eq!(compiled.params["limit"], json!(10));
+}
+
+#[test]
+fn contains_filter_on_nested_field() {
+    let compiled = store()
+        .select("canonical_task")
+        .filter("metadata.tags", Operator::Contains, "urgent")
+        .filter("status", Operator::NotEquals, Value::Null)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        compiled.text,
+        "SELECT * FROM canonical_task WHERE metadata.tags CONTAINS $p0 AND status != $p1"
+    );
+    assert_eq!(compiled.params["p0"], json!("urgent"));
+    assert_eq!(compiled.params["p1"], Value::Null);
+}
+
+#[test]
+fn string_values_with_semicolons_stay_parameterised() {
+    let hostile = "x'; DELETE canonical_entity; --";
+    let compiled = store()
+        .select("canonical_entity; DELETE canonical_entity")
+        .filter("name; DROP", Operator::Equals, hostile)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        compiled.text,
+        "SELECT * FROM canonical_entityDELETEcanonical_entity WHERE nameDROP = $p0"
+    );
+    assert!(!compiled.text.contains(';'));
+    assert_eq!(compiled.params["p0"], json!(hostile));
+}
+
+#[test]
+fn non_comparison_operators_are_rejected() {
+    let result = store()
+        .select("canonical_entity")
+        .filter("score", Operator::Add, 1)
+        .build();
+    assert!(matches!(result, Err(DatabaseError::ValidationError(_))));
+}
@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024 Jonathan Lee
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3
+// as published by the Free Software Foundation.
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Affero General Public License for more details.
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see https://www.gnu.org/licenses/.
+
+use stele::blocks::rules::BlockError;
+use stele::flows::dynamic_executor::executor::{DynamicExecutor, DynamicSource};
+use stele::flows::dynamic_executor::validate_wat;
+
+const MISSING_ELSE_WAT: &str = r#"(module
+  (func $sign (export "execute") (param f64) (result f64)
+    (if (result f64)
+      (f64.gt (local.get 0) (f64.const 0))
+      (then (f64.const 1))
+      (f64.const -1))))
+"#;
+
+const UNDECLARED_LOCAL_WAT: &str = r#"(module
+  (func $add (export "execute") (param $x f64) (result f64)
+    (f64.add
+      (local.get $x)
+      (local.get $total))))
+"#;
+
+const TYPE_MISMATCH_WAT: &str = r#"(module
+  (func $one (export "one") (result f64)
+    (f64.const 1))
+  (func $bad (export "execute") (param f64) (result f64)
+    (i32.const 0)))
+"#;
+
+#[test]
+fn if_without_else_clause_points_at_the_stray_branch() {
+    let errors = validate_wat(MISSING_ELSE_WAT).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].line, 6);
+    assert!(errors[0].column > 0);
+    assert!(!errors[0].message.is_empty());
+}
+
+#[test]
+fn undeclared_local_points_at_its_use() {
+    let errors = validate_wat(UNDECLARED_LOCAL_WAT).unwrap_err();
+    assert_eq!(errors[0].line, 5);
+    assert!(errors[0].message.contains("$total") || errors[0].message.contains("local"));
+    assert_eq!(errors[0].token.as_deref(), Some("$total"));
+}
+
+#[test]
+fn validation_failures_point_at_the_enclosing_function() {
+    let errors = validate_wat(TYPE_MISMATCH_WAT).unwrap_err();
+    assert_eq!(errors[0].line, 4);
+    assert_eq!(errors[0].column, 3);
+    assert_eq!(errors[0].token.as_deref(), Some("(func"));
+}
+
+#[test]
+fn register_dynamic_source_returns_structured_wat_errors() {
+    let executor = DynamicExecutor::new().unwrap();
+    let result = executor.register_dynamic_source(DynamicSource::Wat {
+        name: "add",
+        export: "execute",
+        wat: UNDECLARED_LOCAL_WAT,
+    });
+    match result {
+        Err(BlockError::InvalidWat(errors)) => assert_eq!(errors[0].line, 5),
+        Err(other) => panic!("expected InvalidWat, got {other}"),
+        Ok(_) => panic!("expected InvalidWat, got a compiled function"),
+    }
+}
+
+#[test]
+fn well_formed_wat_validates() {
+    let wat = r#"(module
+  (func $double (export "execute") (param f64) (result f64)
+    (f64.mul (local.get 0) (f64.const 2))))
+"#;
+    assert_eq!(validate_wat(wat), Ok(()));
+}